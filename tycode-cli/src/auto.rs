@@ -1,17 +1,16 @@
 use anyhow::Result;
 use std::path::PathBuf;
 use terminal_size::{terminal_size, Width};
-use tycode_core::{
-    chat::actor::ChatActorBuilder,
-    formatter::{CompactFormatter, EventFormatter, VerboseFormatter},
-};
+use tycode_core::formatter::{CompactFormatter, EventFormatter, VerboseFormatter};
+use tycode_core::settings::config::AutonomyLevel;
 
 use crate::auto_driver::{drive_auto_conversation, AutoDriverConfig};
+use crate::settings_source::SettingsSource;
 
 pub async fn run_auto(
     task: String,
     workspace_roots: Vec<PathBuf>,
-    profile: Option<String>,
+    settings: &SettingsSource,
     compact: bool,
 ) -> Result<()> {
     let terminal_width = terminal_size()
@@ -27,8 +26,10 @@ pub async fn run_auto(
 
     let initial_agent = "coordinator".to_string();
 
-    let (mut actor, mut event_rx) = ChatActorBuilder::tycode(workspace_roots, None, profile)?
+    let (mut actor, mut event_rx) = settings
+        .actor_builder(workspace_roots)?
         .agent_name(initial_agent.clone())
+        .autonomy_level(AutonomyLevel::Autonomous)
         .build()?;
 
     actor.send_message(task)?;
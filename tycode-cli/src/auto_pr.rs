@@ -3,17 +3,19 @@ use std::path::PathBuf;
 use terminal_size::{terminal_size, Width};
 use tokio::sync::mpsc::UnboundedReceiver;
 use tycode_core::{
-    chat::{actor::ChatActorBuilder, ChatActor, ChatEvent, MessageSender},
+    chat::{ChatActor, ChatEvent, MessageSender},
     formatter::{CompactFormatter, EventFormatter, VerboseFormatter},
+    settings::config::AutonomyLevel,
 };
 
 use crate::auto_driver::{drive_auto_conversation, AutoDriverConfig};
 use crate::github;
+use crate::settings_source::SettingsSource;
 
 pub async fn run_auto_pr(
     issue_number: u32,
     workspace_roots: Vec<PathBuf>,
-    profile: Option<String>,
+    settings: &SettingsSource,
     draft: bool,
     compact: bool,
 ) -> Result<()> {
@@ -39,8 +41,10 @@ pub async fn run_auto_pr(
 
     formatter.print_system(&format!("Created branch: {}", branch_name));
 
-    let (mut actor, mut event_rx) = ChatActorBuilder::tycode(workspace_roots, None, profile)?
+    let (mut actor, mut event_rx) = settings
+        .actor_builder(workspace_roots)?
         .agent_name("auto_pr".to_string())
+        .autonomy_level(AutonomyLevel::Autonomous)
         .build()?;
 
     let initial_message = format!(
@@ -8,6 +8,11 @@ pub enum LocalCommandResult {
     /// A command to exit the app was detected
     Exit,
 
+    /// Start push-to-talk; the next Enter stops listening and sends the
+    /// transcript.
+    #[cfg(feature = "voice")]
+    Talk,
+
     /// The command was not processed locally (and should be sent to the actor).
     Unhandled,
 }
@@ -27,19 +32,38 @@ pub fn handle_local_command(state: &mut State, input: &str) -> LocalCommandResul
                 ),
             }
         }
-        "/verbose" => {
-            state.show_reasoning = !state.show_reasoning;
+        "/verbose" | "/verbose on" | "/verbose off" => {
+            state.verbose = match input.trim() {
+                "/verbose on" => true,
+                "/verbose off" => false,
+                _ => !state.verbose,
+            };
+            LocalCommandResult::Handled {
+                msg: format!(
+                    "Tool detail: {}",
+                    if state.verbose { "full" } else { "summary" }
+                ),
+            }
+        }
+        "/reasoning" | "/reasoning on" | "/reasoning off" => {
+            state.show_reasoning = match input.trim() {
+                "/reasoning on" => true,
+                "/reasoning off" => false,
+                _ => !state.show_reasoning,
+            };
             LocalCommandResult::Handled {
                 msg: format!(
-                    "Verbose mode: {} (showing model reasoning)",
+                    "Model reasoning: {}",
                     if state.show_reasoning {
-                        "enabled"
+                        "shown"
                     } else {
-                        "disabled"
+                        "hidden"
                     }
                 ),
             }
         }
+        #[cfg(feature = "voice")]
+        "/talk" => LocalCommandResult::Talk,
         "/exit" | "/quit" => LocalCommandResult::Exit,
         _ => LocalCommandResult::Unhandled,
     }
@@ -6,16 +6,23 @@ use rustyline::Editor;
 use rustyline_derive::{Completer, Helper, Highlighter, Hinter};
 use std::path::PathBuf;
 use std::thread;
+use std::time::{Duration, Instant};
 use terminal_size::{terminal_size, Width};
 use tokio::sync::mpsc;
-use tycode_core::chat::actor::{ChatActor, ChatActorBuilder};
-use tycode_core::chat::events::{ChatEvent, MessageSender};
+use tycode_core::ai::ImageGenerationStatus;
+use tycode_core::chat::actor::ChatActor;
+use tycode_core::chat::events::{BuildWatchStatus, ChatEvent, MessageSender};
 use tycode_core::formatter::{CompactFormatter, EventFormatter, VerboseFormatter};
 use tycode_core::modules::memory::MemoryConfig;
-use tycode_core::settings::SettingsManager;
+use tycode_core::settings::config::ThinkingIndicatorSettings;
+#[cfg(feature = "voice")]
+use tycode_core::settings::config::VoiceSettings;
+#[cfg(feature = "voice")]
+use tycode_core::voice::input::VoiceInput;
 
 use crate::banner::{print_startup_banner, BannerInfo};
 use crate::commands::{handle_local_command, LocalCommandResult};
+use crate::settings_source::SettingsSource;
 use crate::state::State;
 
 enum ReadlineResponse {
@@ -92,29 +99,62 @@ fn spawn_readline_thread() -> (
     (request_tx, response_rx)
 }
 
+/// How long exit waits for the actor to save the session and close MCP
+/// clients.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Ctrl-C is handled per turn (it cancels the running request), so only
+/// SIGTERM ends the session from outside.
+#[cfg(unix)]
+async fn wait_for_sigterm() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            terminate.recv().await;
+        }
+        Err(_) => std::future::pending().await,
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_sigterm() {
+    std::future::pending().await
+}
+
 pub struct InteractiveApp {
     chat_actor: ChatActor,
     event_rx: mpsc::UnboundedReceiver<ChatEvent>,
     formatter: Box<dyn EventFormatter>,
     state: State,
     is_thinking: bool,
+    /// When the pending request was sent; drives the elapsed time and
+    /// reassurance messages next to the spinner
+    request_started: Option<Instant>,
+    thinking_indicator: ThinkingIndicatorSettings,
+    /// A voice caption is on screen awaiting its final transcript
+    voice_caption_active: bool,
+    #[cfg(feature = "voice")]
+    voice_settings: VoiceSettings,
+    /// Started by the first `/talk`, so the microphone (and the cancel
+    /// keyword listener) stay off until voice input is used
+    #[cfg(feature = "voice")]
+    voice: Option<VoiceInput>,
     readline_tx: mpsc::UnboundedSender<String>,
     readline_rx: mpsc::UnboundedReceiver<ReadlineResponse>,
 }
 
 impl InteractiveApp {
     pub async fn new(
-        workspace_roots: Option<Vec<PathBuf>>,
-        profile: Option<String>,
+        workspace_roots: Vec<PathBuf>,
+        settings_source: SettingsSource,
         compact: bool,
+        quiet: bool,
     ) -> Result<Self> {
-        let workspace_roots = workspace_roots.unwrap_or_else(|| vec![PathBuf::from(".")]);
-
         // Load settings for banner display
-        let root_dir = dirs::home_dir()
-            .expect("Failed to get home directory")
-            .join(".tycode");
-        let settings_manager = SettingsManager::from_settings_dir(root_dir, profile.as_deref())?;
+        let settings_manager = settings_source
+            .settings_manager()?
+            .with_workspace_overrides(&workspace_roots)?;
         let settings = settings_manager.settings();
 
         // Get model from the default agent's config, or fall back to quality tier
@@ -167,11 +207,13 @@ impl InteractiveApp {
             },
         };
         print_startup_banner(&banner_info);
+        let thinking_indicator = settings.thinking_indicator.clone();
+        #[cfg(feature = "voice")]
+        let voice_settings = settings.voice.clone();
 
-        let (chat_actor, event_rx) =
-            ChatActorBuilder::tycode(workspace_roots, None, profile)?.build()?;
+        let (chat_actor, event_rx) = settings_source.actor_builder(workspace_roots)?.build()?;
 
-        let formatter: Box<dyn EventFormatter> = if compact {
+        let mut formatter: Box<dyn EventFormatter> = if compact {
             let terminal_width = terminal_size()
                 .map(|(Width(w), _)| w as usize)
                 .unwrap_or(80);
@@ -180,14 +222,27 @@ impl InteractiveApp {
             Box::new(VerboseFormatter::new())
         };
 
+        let state = State {
+            verbose: !quiet,
+            ..State::default()
+        };
+        formatter.set_tool_detail(state.verbose);
+
         let (readline_tx, readline_rx) = spawn_readline_thread();
 
         Ok(Self {
             chat_actor,
             event_rx,
             formatter,
-            state: State::default(),
+            state,
             is_thinking: false,
+            request_started: None,
+            thinking_indicator,
+            voice_caption_active: false,
+            #[cfg(feature = "voice")]
+            voice_settings,
+            #[cfg(feature = "voice")]
+            voice: None,
             readline_tx,
             readline_rx,
         })
@@ -205,12 +260,34 @@ impl InteractiveApp {
         }
     }
 
-    pub async fn run(&mut self) -> Result<()> {
+    /// Runs the interactive session, sending `initial_prompt` first if given.
+    pub async fn run(&mut self, initial_prompt: Option<String>) -> Result<()> {
+        let result = tokio::select! {
+            result = self.run_loop(initial_prompt) => result,
+            _ = wait_for_sigterm() => Ok(()),
+        };
+
+        // Persist the latest turn even if the loop failed or we were killed
+        if let Err(e) = self.chat_actor.shutdown(SHUTDOWN_TIMEOUT).await {
+            tracing::warn!("{e:?}");
+        }
+        result?;
+
+        println!("\nGoodbye!");
+        Ok(())
+    }
+
+    async fn run_loop(&mut self, initial_prompt: Option<String>) -> Result<()> {
         // We do this handshake at the start of each run to ensure any system
         // messages from the chat actor get printed
         self.chat_actor.get_settings()?;
         self.wait_for_settings().await?;
 
+        if let Some(prompt) = initial_prompt {
+            self.chat_actor.send_message(prompt)?;
+            self.wait_for_response().await?;
+        }
+
         loop {
             let line = match self.readline("\x1b[35m>\x1b[0m ").await? {
                 ReadlineResponse::Line(l) => l,
@@ -226,10 +303,16 @@ impl InteractiveApp {
 
             match handle_local_command(&mut self.state, input) {
                 LocalCommandResult::Handled { msg } => {
+                    self.formatter.set_tool_detail(self.state.verbose);
                     self.formatter.print_system(&msg);
                     continue;
                 }
                 LocalCommandResult::Exit => break,
+                #[cfg(feature = "voice")]
+                LocalCommandResult::Talk => {
+                    self.talk().await?;
+                    continue;
+                }
                 LocalCommandResult::Unhandled => (),
             }
 
@@ -237,11 +320,92 @@ impl InteractiveApp {
             self.wait_for_response().await?
         }
 
-        println!("\nGoodbye!");
         Ok(())
     }
 
+    /// Push-to-talk from the prompt: listens until Enter is pressed, then
+    /// sends the transcript and waits for the response like typed input.
+    #[cfg(feature = "voice")]
+    async fn talk(&mut self) -> Result<()> {
+        let mut voice = match self.voice.take() {
+            Some(voice) => voice,
+            None => match VoiceInput::start(self.chat_actor.clone(), &self.voice_settings).await {
+                Ok(voice) => voice,
+                Err(e) => {
+                    self.formatter
+                        .print_error(&format!("Voice input unavailable: {e:?}"));
+                    return Ok(());
+                }
+            },
+        };
+        let result = self.talk_with(&mut voice).await;
+        self.voice = Some(voice);
+        result
+    }
+
+    #[cfg(feature = "voice")]
+    async fn talk_with(&mut self, voice: &mut VoiceInput) -> Result<()> {
+        if let Err(e) = voice.press().await {
+            self.formatter
+                .print_error(&format!("Failed to start listening: {e:?}"));
+            return Ok(());
+        }
+        self.formatter
+            .print_system("Listening... press Enter to stop");
+        self.wait_for_enter().await?;
+
+        let released = voice.release().await;
+        if !matches!(released, Ok(true)) && self.voice_caption_active {
+            // No final transcript will replace the caption; keep it on its
+            // own line
+            println!();
+            self.voice_caption_active = false;
+        }
+        match released {
+            Ok(true) => self.wait_for_response().await,
+            Ok(false) => {
+                self.formatter.print_system("No speech detected");
+                Ok(())
+            }
+            Err(e) => {
+                self.formatter
+                    .print_error(&format!("Transcription failed: {e:?}"));
+                Ok(())
+            }
+        }
+    }
+
+    /// Waits for the user to press Enter (or Ctrl-C/Ctrl-D), showing live
+    /// captions meanwhile. Whatever was typed is ignored.
+    #[cfg(feature = "voice")]
+    async fn wait_for_enter(&mut self) -> Result<()> {
+        self.readline_tx
+            .send(String::new())
+            .map_err(|e| anyhow::anyhow!("Readline thread died: {e:?}"))?;
+
+        loop {
+            tokio::select! {
+                response = self.readline_rx.recv() => {
+                    return match response {
+                        Some(ReadlineResponse::Error(e)) => {
+                            Err(anyhow::anyhow!("Readline error: {e}"))
+                        }
+                        _ => Ok(()),
+                    };
+                }
+                Some(event) = self.event_rx.recv() => self.format_event(event)?,
+            }
+        }
+    }
+
     async fn wait_for_response(&mut self) -> Result<()> {
+        self.request_started = Some(Instant::now());
+        let result = self.wait_for_response_events().await;
+        self.request_started = None;
+        result
+    }
+
+    async fn wait_for_response_events(&mut self) -> Result<()> {
         use tokio::signal;
         loop {
             tokio::select! {
@@ -263,9 +427,7 @@ impl InteractiveApp {
                     }
                 }
                 _ = tokio::time::sleep(std::time::Duration::from_millis(100)) => {
-                    if self.is_thinking {
-                        self.formatter.print_thinking();
-                    }
+                    self.print_thinking();
                 }
                 _ = signal::ctrl_c() => {
                     self.chat_actor.cancel()?;
@@ -277,6 +439,16 @@ impl InteractiveApp {
         Ok(())
     }
 
+    fn print_thinking(&mut self) {
+        if !self.is_thinking {
+            return;
+        }
+        let elapsed = self.request_started.map(|started| started.elapsed());
+        let message = elapsed.and_then(|elapsed| self.thinking_indicator.message_for(elapsed));
+        let shown_elapsed = elapsed.filter(|_| self.thinking_indicator.show_elapsed);
+        self.formatter.print_thinking(shown_elapsed, message);
+    }
+
     async fn wait_for_settings(&mut self) -> Result<()> {
         loop {
             match self.event_rx.recv().await {
@@ -307,8 +479,7 @@ impl InteractiveApp {
                 MessageSender::Assistant { agent } => {
                     if self.state.show_reasoning {
                         if let Some(ref reasoning) = message.reasoning {
-                            self.formatter
-                                .print_system(&format!("💭 Reasoning: {}", reasoning.text));
+                            self.formatter.print_reasoning(&reasoning.text);
                         }
                     }
 
@@ -341,14 +512,19 @@ impl InteractiveApp {
                 MessageSender::Error => {
                     self.formatter.print_error(&message.content);
                 }
-                MessageSender::User => {}
+                MessageSender::User => {
+                    if self.voice_caption_active {
+                        // Replace the live caption with the final transcript
+                        self.formatter.print_voice_caption(&message.content);
+                        println!();
+                        self.voice_caption_active = false;
+                    }
+                }
             },
             ChatEvent::TypingStatusChanged(typing) => {
                 self.is_thinking = typing;
                 self.formatter.on_typing_status_changed(typing);
-                if typing {
-                    self.formatter.print_thinking();
-                }
+                self.print_thinking();
             }
             ChatEvent::Error(e) => self.formatter.print_error(&e),
             ChatEvent::ToolExecutionCompleted {
@@ -361,10 +537,12 @@ impl InteractiveApp {
                     &tool_name,
                     success,
                     tool_result,
-                    self.state.show_reasoning,
+                    self.state.verbose,
                 );
             }
             ChatEvent::OperationCancelled { .. } => {
+                self.is_thinking = false;
+                self.request_started = None;
                 self.formatter.print_system("Operation Cancelled");
             }
             ChatEvent::Settings(_) => {
@@ -385,8 +563,15 @@ impl InteractiveApp {
                 self.formatter
                     .print_retry_attempt(attempt, max_retries, &error);
             }
-            ChatEvent::TaskUpdate(task_list) => {
-                self.formatter.print_task_update(&task_list);
+            ChatEvent::RetriesExhausted {
+                attempts, error, ..
+            } => {
+                self.formatter.print_error(&format!(
+                    "Gave up after {attempts} retries; the provider kept failing: {error}"
+                ));
+            }
+            ChatEvent::TaskUpdate(update) => {
+                self.formatter.print_task_update(&update.task_list);
             }
             ChatEvent::SessionsList { .. } => {
                 // CLI handles sessions via slash commands, ignore this event
@@ -407,6 +592,66 @@ impl InteractiveApp {
                 // Machine-readable orchestration progress for UI consumers;
                 // the CLI renders the human system messages instead
             }
+            ChatEvent::VoicePartialTranscript { text } => {
+                self.formatter.print_voice_caption(&text);
+                self.voice_caption_active = true;
+            }
+            ChatEvent::ContextWarning {
+                used_tokens,
+                limit_tokens,
+            } => {
+                self.formatter.print_warning(&format!(
+                    "Context is ~{used_tokens} of {limit_tokens} tokens. Consider /compact."
+                ));
+            }
+            ChatEvent::ToolSummary { summary, .. } => {
+                if self.state.verbose {
+                    self.formatter.print_tool_summary(&summary);
+                }
+            }
+            ChatEvent::EditsProposed { edits } => {
+                let mut message = String::from("Proposed edits (not yet written):");
+                for edit in &edits {
+                    message.push_str(&format!("\n  #{} {}", edit.edit_id, edit.file_path));
+                }
+                message.push_str(
+                    "\nReply \"accept <id>\" or \"reject <id>\" for each, or \"accept all\" / \"reject all\".",
+                );
+                self.formatter.print_system(&message);
+            }
+            ChatEvent::ImageGenerationProgress { status, .. } => match status {
+                ImageGenerationStatus::Started => {
+                    self.formatter.print_system("Generating image...")
+                }
+                ImageGenerationStatus::Queued => {
+                    self.formatter.print_system("Image generation queued")
+                }
+                ImageGenerationStatus::Processing => {
+                    self.formatter.print_system("Image generation in progress")
+                }
+                ImageGenerationStatus::Done => self.formatter.print_system("Image generated"),
+                ImageGenerationStatus::Failed { error } => self.formatter.print_error(&error),
+            },
+            ChatEvent::BuildWatch {
+                iteration,
+                max_iterations,
+                errors,
+                status,
+            } => match status {
+                BuildWatchStatus::Retrying => self.formatter.print_system(&format!(
+                    "Build has {errors} error(s), asking the agent to fix them ({iteration}/{max_iterations})"
+                )),
+                BuildWatchStatus::Clean => self.formatter.print_system("Build is clean"),
+                BuildWatchStatus::IterationLimit => self.formatter.print_warning(&format!(
+                    "Build still has {errors} error(s) after {max_iterations} fix attempt(s); stopping build watch"
+                )),
+                BuildWatchStatus::CostLimit => self.formatter.print_warning(&format!(
+                    "Build still has {errors} error(s) but the build watch cost limit was reached"
+                )),
+                BuildWatchStatus::NoProgress => self.formatter.print_warning(&format!(
+                    "Build keeps reporting the same {errors} error(s); stopping build watch"
+                )),
+            },
             ChatEvent::RootAgentChanged { .. } => {
                 // Typed ack for protocol consumers; the CLI's /agent command
                 // already prints its own confirmation message
@@ -441,9 +686,9 @@ impl InteractiveApp {
             ChatEvent::StreamDelta { message_id, text } => {
                 self.formatter.print_stream_delta(&message_id, &text);
             }
-            ChatEvent::StreamReasoningDelta { text, .. } => {
+            ChatEvent::StreamReasoningDelta { message_id, text } => {
                 if self.state.show_reasoning {
-                    self.formatter.print_stream_delta(&String::new(), &text);
+                    self.formatter.print_reasoning_delta(&message_id, &text);
                 }
             }
             ChatEvent::StreamEnd { message } => {
@@ -5,6 +5,8 @@ use tracing::info;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
+use tycode_core::file::workspace::detect_workspace_root;
+use tycode_core::redact::RedactingWriter;
 
 mod auto;
 mod auto_driver;
@@ -13,16 +15,20 @@ mod banner;
 mod commands;
 mod github;
 mod interactive_app;
+mod prompt;
+mod settings_source;
 mod state;
 
 use crate::interactive_app::InteractiveApp;
+use crate::settings_source::SettingsSource;
 
 #[derive(Parser, Debug)]
 #[command(name = "tycode-cli")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
 #[command(about = "TyCode CLI - Native terminal chat interface")]
 struct Args {
-    /// Workspace roots (for multi-root workspaces)
+    /// Workspace roots (for multi-root workspaces). Defaults to the nearest
+    /// enclosing directory containing `.git` or `.tycode`.
     #[arg(long, value_delimiter = ',')]
     workspace_roots: Option<Vec<String>>,
 
@@ -30,6 +36,10 @@ struct Args {
     #[arg(long, value_name = "NAME")]
     profile: Option<String>,
 
+    /// Load settings from this file instead of ~/.tycode
+    #[arg(long, value_name = "FILE", conflicts_with = "profile")]
+    settings_path: Option<PathBuf>,
+
     /// Auto-PR mode: fetch GitHub issue, resolve it, and create a PR
     #[arg(long, value_name = "ISSUE_NUMBER")]
     auto_pr: Option<u32>,
@@ -42,6 +52,11 @@ struct Args {
     #[arg(long)]
     compact: bool,
 
+    /// Show one-line summaries of tool results instead of full diffs and
+    /// command output (toggle at runtime with `/verbose` or `/verbose on|off`)
+    #[arg(long)]
+    quiet: bool,
+
     /// Auto mode: run until task completion then exit
     #[arg(long)]
     auto: bool,
@@ -49,6 +64,17 @@ struct Args {
     /// Task description for auto mode
     #[arg(long)]
     task: Option<String>,
+
+    /// Send this prompt first. With --exit-on-complete the run is
+    /// non-interactive; otherwise the interactive session continues after it.
+    #[arg(long, value_name = "TEXT")]
+    prompt: Option<String>,
+
+    /// With --prompt: exit once the agent finishes. Exits 0 when the task was
+    /// completed, 1 on failure or error, and 2 if the agent stopped without
+    /// completing it.
+    #[arg(long, requires = "prompt")]
+    exit_on_complete: bool,
 }
 
 fn main() -> Result<()> {
@@ -68,9 +94,10 @@ async fn async_main() -> Result<()> {
     let args = Args::parse();
 
     info!(
-        "CLI startup: compact={}, profile={:?}, auto={}, auto_pr={:?}, task={}",
+        "CLI startup: compact={}, profile={:?}, settings_path={:?}, auto={}, auto_pr={:?}, task={}",
         args.compact,
         args.profile,
+        args.settings_path,
         args.auto,
         args.auto_pr,
         args.task.as_deref().unwrap_or("none")
@@ -80,28 +107,40 @@ async fn async_main() -> Result<()> {
         return Err(anyhow::anyhow!("--auto requires --task to be specified"));
     }
 
+    let settings = SettingsSource::new(args.profile, args.settings_path)?;
+
     let workspace_roots = args
         .workspace_roots
         .map(|roots| roots.into_iter().map(canonicalize_workspace_root).collect())
-        .transpose()?;
+        .transpose()?
+        .unwrap_or_else(|| {
+            let cwd = std::env::current_dir().expect("Failed to get current directory");
+            vec![detect_workspace_root(&cwd)]
+        });
 
     if let Some(issue_number) = args.auto_pr {
-        let roots = workspace_roots.unwrap_or_else(|| {
-            vec![std::env::current_dir().expect("Failed to get current directory")]
-        });
-        return auto_pr::run_auto_pr(issue_number, roots, args.profile, args.draft, args.compact)
-            .await;
+        return auto_pr::run_auto_pr(
+            issue_number,
+            workspace_roots,
+            &settings,
+            args.draft,
+            args.compact,
+        )
+        .await;
     }
 
     if args.auto {
-        let roots = workspace_roots.unwrap_or_else(|| {
-            vec![std::env::current_dir().expect("Failed to get current directory")]
-        });
-        return auto::run_auto(args.task.unwrap(), roots, args.profile, args.compact).await;
+        return auto::run_auto(args.task.unwrap(), workspace_roots, &settings, args.compact).await;
+    }
+
+    if args.exit_on_complete {
+        let prompt = args.prompt.expect("clap enforces --prompt");
+        let outcome = prompt::run_prompt(prompt, workspace_roots, &settings, args.compact).await?;
+        std::process::exit(outcome.exit_code());
     }
 
-    let mut app = InteractiveApp::new(workspace_roots, args.profile, args.compact).await?;
-    app.run().await?;
+    let mut app = InteractiveApp::new(workspace_roots, settings, args.compact, args.quiet).await?;
+    app.run(args.prompt).await?;
 
     Ok(())
 }
@@ -127,11 +166,11 @@ fn setup_tracing() -> Result<()> {
         .append(true)
         .open(&log_file)?;
 
-    // Setup tracing subscriber with file output
+    // Setup tracing subscriber with file output, redacting secrets
     tracing_subscriber::registry()
         .with(
             fmt::layer()
-                .with_writer(file)
+                .with_writer(std::sync::Mutex::new(RedactingWriter::new(file)))
                 .with_ansi(false)
                 .with_target(true)
                 .with_thread_ids(true)
@@ -0,0 +1,166 @@
+//! Non-interactive single-prompt mode: `--prompt <TEXT> --exit-on-complete`.
+//!
+//! Sends one prompt to the default agent and prints events until the root
+//! agent calls `complete_task`, an error occurs, or the turn ends. Nobody is
+//! there to answer questions, so every agent runs at the `autonomous` level
+//! (no plan approval or edit confirmation), and an agent that still asks for
+//! user input fails the run instead of waiting.
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::time::Duration;
+use terminal_size::{terminal_size, Width};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tycode_core::{
+    chat::{
+        events::{ToolExecutionResult, ToolRequestType},
+        ChatEvent, MessageSender,
+    },
+    formatter::{CompactFormatter, EventFormatter, VerboseFormatter},
+    settings::config::AutonomyLevel,
+    spawn::complete_task::CompleteTask,
+};
+
+use crate::auto_driver::handle_message_added;
+use crate::settings_source::SettingsSource;
+
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How a single-prompt run ended, mapped to the process exit code.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PromptOutcome {
+    /// The root agent called complete_task with success=true.
+    Completed,
+    /// The agent reported failure, hit an error, or needed user input.
+    Failed(String),
+    /// The turn ended without the root agent calling complete_task.
+    Incomplete,
+}
+
+impl PromptOutcome {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            PromptOutcome::Completed => 0,
+            PromptOutcome::Failed(_) => 1,
+            PromptOutcome::Incomplete => 2,
+        }
+    }
+}
+
+pub async fn run_prompt(
+    prompt: String,
+    workspace_roots: Vec<PathBuf>,
+    settings: &SettingsSource,
+    compact: bool,
+) -> Result<PromptOutcome> {
+    let terminal_width = terminal_size()
+        .map(|(Width(w), _)| w as usize)
+        .unwrap_or(80);
+    let mut formatter: Box<dyn EventFormatter> = if compact {
+        Box::new(CompactFormatter::new(terminal_width))
+    } else {
+        Box::new(VerboseFormatter::new())
+    };
+
+    let (actor, mut event_rx) = settings
+        .actor_builder(workspace_roots)?
+        .autonomy_level(AutonomyLevel::Autonomous)
+        .build()?;
+    actor.send_message(prompt)?;
+
+    let outcome = drive_prompt(&mut event_rx, &mut *formatter).await;
+
+    if let Err(e) = actor.shutdown(SHUTDOWN_TIMEOUT).await {
+        tracing::warn!("{e:?}");
+    }
+
+    match &outcome {
+        PromptOutcome::Completed => formatter.print_system("Task completed"),
+        PromptOutcome::Failed(reason) => formatter.print_error(&format!("Task failed: {reason}")),
+        PromptOutcome::Incomplete => {
+            formatter.print_error("Agent stopped without completing the task")
+        }
+    }
+    Ok(outcome)
+}
+
+async fn drive_prompt(
+    event_rx: &mut UnboundedReceiver<ChatEvent>,
+    formatter: &mut dyn EventFormatter,
+) -> PromptOutcome {
+    let mut root_agent: Option<String> = None;
+    let mut current_agent = String::new();
+    let mut completion_success = true;
+
+    while let Some(event) = event_rx.recv().await {
+        match event {
+            ChatEvent::TypingStatusChanged(false) => return PromptOutcome::Incomplete,
+            ChatEvent::Error(msg) => {
+                formatter.print_error(&msg);
+                return PromptOutcome::Failed(msg);
+            }
+            ChatEvent::MessageAdded(chat_message) => {
+                if let MessageSender::Assistant { agent } = &chat_message.sender {
+                    root_agent.get_or_insert_with(|| agent.clone());
+                    current_agent = agent.clone();
+                }
+                handle_message_added(chat_message, formatter);
+            }
+            ChatEvent::ToolRequest(tool_request) => {
+                formatter.print_tool_request(&tool_request);
+                if tool_request.tool_name == CompleteTask::tool_name().as_str() {
+                    if let ToolRequestType::Other { args } = &tool_request.tool_type {
+                        completion_success = args
+                            .get("success")
+                            .and_then(|s| s.as_bool())
+                            .unwrap_or(true);
+                    }
+                }
+            }
+            ChatEvent::ToolExecutionCompleted {
+                tool_name,
+                success,
+                tool_result,
+                ..
+            } => {
+                let waiting_for_user = matches!(
+                    &tool_result,
+                    ToolExecutionResult::Other { result }
+                        if result["status"] == "waiting_for_user"
+                );
+                formatter.print_tool_result(&tool_name, success, tool_result, false);
+                if waiting_for_user {
+                    return PromptOutcome::Failed(
+                        "the agent asked for user input, which is unavailable in \
+                         non-interactive mode"
+                            .to_string(),
+                    );
+                }
+                if tool_name != CompleteTask::tool_name().as_str() || !success {
+                    continue;
+                }
+                if root_agent.as_deref() != Some(current_agent.as_str()) {
+                    continue;
+                }
+                return if completion_success {
+                    PromptOutcome::Completed
+                } else {
+                    PromptOutcome::Failed("the agent reported the task as failed".to_string())
+                };
+            }
+            ChatEvent::RetryAttempt {
+                attempt,
+                max_retries,
+                error,
+                backoff_ms,
+            } => {
+                formatter.print_system(&format!(
+                    "Retry {attempt}/{max_retries}: {error}, backoff {backoff_ms}ms"
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    PromptOutcome::Failed("event stream ended unexpectedly".to_string())
+}
@@ -0,0 +1,46 @@
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+use tycode_core::{chat::actor::ChatActorBuilder, settings::SettingsManager};
+
+/// Where the CLI loads settings from: a profile under `~/.tycode`, or an
+/// explicit file given with `--settings-path`.
+#[derive(Debug, Clone)]
+pub enum SettingsSource {
+    Profile(Option<String>),
+    Path(PathBuf),
+}
+
+impl SettingsSource {
+    pub fn new(profile: Option<String>, settings_path: Option<PathBuf>) -> Result<Self> {
+        let Some(path) = settings_path else {
+            return Ok(Self::Profile(profile));
+        };
+        if !path.is_file() {
+            bail!("Settings file not found: {}", path.display());
+        }
+        Ok(Self::Path(path))
+    }
+
+    pub fn settings_manager(&self) -> Result<SettingsManager> {
+        match self {
+            Self::Profile(profile) => {
+                let root_dir = dirs::home_dir()
+                    .expect("Failed to get home directory")
+                    .join(".tycode");
+                SettingsManager::from_settings_dir(root_dir, profile.as_deref())
+            }
+            Self::Path(path) => SettingsManager::from_path(path.clone()),
+        }
+    }
+
+    pub fn actor_builder(&self, workspace_roots: Vec<PathBuf>) -> Result<ChatActorBuilder> {
+        match self {
+            Self::Profile(profile) => {
+                ChatActorBuilder::tycode(workspace_roots, None, profile.clone())
+            }
+            Self::Path(path) => {
+                ChatActorBuilder::tycode_with_settings_path(workspace_roots, path.clone())
+            }
+        }
+    }
+}
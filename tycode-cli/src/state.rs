@@ -1,5 +1,10 @@
 #[derive(Default)]
 pub struct State {
+    /// Display the model's reasoning (dimmed). Display only: reasoning is
+    /// kept and sent to the provider either way.
     pub show_reasoning: bool,
+    /// Show tool results in full (diffs, command output, error details)
+    /// rather than as one-line summaries. Toggled by `/verbose`.
+    pub verbose: bool,
     pub show_timing: bool,
 }
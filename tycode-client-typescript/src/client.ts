@@ -129,6 +129,23 @@ class ChatActorClient {
     });
   }
 
+  /**
+   * Accepts or rejects one edit announced by an EditsProposed event.
+   */
+  reviewEdit(editId: number, accept: boolean): Promise<void> {
+    if (!this.subprocess) throw new Error('No subprocess');
+    const msg: ChatActorMessage = { ReviewEdit: { edit_id: editId, accept } };
+    const data = JSON.stringify(msg) + '\n';
+    return new Promise<void>((resolve, reject) => {
+      const written = this.subprocess!.stdin!.write(data);
+      if (written) {
+        resolve();
+      } else {
+        this.subprocess!.stdin!.once('drain', resolve);
+      }
+    });
+  }
+
   changeProvider(provider: string): Promise<void> {
     if (!this.subprocess) throw new Error('No subprocess');
     const msg: ChatActorMessage = { ChangeProvider: provider };
@@ -26,13 +26,38 @@ export type ChatEvent =
         backoff_ms: number;
       };
     }
-  | { kind: 'TaskUpdate'; data: TaskList }
+  | {
+      kind: 'RetriesExhausted';
+      data: {
+        attempts: number;
+        max_retries: number;
+        error: string;
+      };
+    }
+  | { kind: 'TaskUpdate'; data: TaskListUpdate }
+  | { kind: 'EditsProposed'; data: { edits: ProposedEdit[] } }
+  | {
+      kind: 'ImageGenerationProgress';
+      data: { tool_call_id: string; status: ImageGenerationStatus };
+    }
   | { kind: 'SessionsList'; data: { sessions: SessionMetadata[] } }
   | { kind: 'ProfilesList'; data: { profiles: string[] } }
   | { kind: 'ModuleSchemas'; data: { schemas: ModuleSchemaInfo[] } }
   | { kind: 'SettingsSchema'; data: { schema: SettingsSchemaInfo } }
   | { kind: 'Orchestration'; data: OrchestrationEvent }
   | { kind: 'RootAgentChanged'; data: { agent: string } }
+  | { kind: 'VoicePartialTranscript'; data: { text: string } }
+  | { kind: 'ContextWarning'; data: { used_tokens: number; limit_tokens: number } }
+  | { kind: 'ToolSummary'; data: { calls: number; failed: number; summary: string } }
+  | {
+      kind: 'BuildWatch';
+      data: {
+        iteration: number;
+        max_iterations: number;
+        errors: number;
+        status: BuildWatchStatus;
+      };
+    }
   | { kind: 'Error'; data: string }
   | {
       kind: 'StreamStart';
@@ -54,6 +79,13 @@ export interface SettingsSchemaInfo {
 
 export type SettingsGroupKind = 'core' | 'module';
 
+export type BuildWatchStatus =
+  | 'retrying'
+  | 'clean'
+  | 'iteration_limit'
+  | 'cost_limit'
+  | 'no_progress';
+
 export interface SettingsGroupInfo {
   id: string;
   title: string;
@@ -203,6 +235,7 @@ export interface ContextBreakdown {
   reasoning_bytes: number;
   context_injection_bytes: number;
   input_tokens: number;
+  estimated_tokens?: number;
   context_window: number;
 }
 
@@ -315,13 +348,26 @@ export type ToolRequestType =
   | { kind: 'GetTypeDocs'; language: string; workspace_root: string; type_path: string }
   | { kind: 'Other'; args: any };
 
+export type ToolErrorCategory =
+  | 'not_found'
+  | 'permission_denied'
+  | 'invalid_argument'
+  | 'conflict'
+  | 'transient'
+  | 'limit_exceeded';
+
 export type ToolExecutionResult =
   | { kind: 'ModifyFile'; lines_added: number; lines_removed: number }
   | { kind: 'RunCommand'; exit_code: number; stdout: string; stderr: string }
   | { kind: 'ReadFiles'; files: FileInfo[] }
   | { kind: 'SearchTypes'; types: string[] }
   | { kind: 'GetTypeDocs'; documentation: string }
-  | { kind: 'Error'; short_message: string; detailed_message: string }
+  | {
+      kind: 'Error';
+      short_message: string;
+      detailed_message: string;
+      category?: ToolErrorCategory;
+    }
   | { kind: 'Other'; result: any };
 
 export interface ToolRequest {
@@ -343,6 +389,33 @@ export interface TaskList {
   tasks: Task[];
 }
 
+export interface TaskProgress {
+  /** Completed or failed tasks */
+  done: number;
+  failed: number;
+  total: number;
+  /** `done` as a percentage of `total`, rounded down */
+  percent: number;
+}
+
+export interface TaskListUpdate extends TaskList {
+  progress: TaskProgress;
+}
+
+export interface ProposedEdit {
+  edit_id: number;
+  tool_call_id: string;
+  tool_name: string;
+  file_path: string;
+}
+
+export type ImageGenerationStatus =
+  | { state: 'started' }
+  | { state: 'queued' }
+  | { state: 'processing' }
+  | { state: 'done' }
+  | { state: 'failed'; error: string };
+
 export interface SessionMetadata {
   id: string;
   title: string;
@@ -360,6 +433,13 @@ export interface SessionData {
 // Exact port from tycode-core/src/chat/actor.rs
 export type ChatActorMessage =
   | { UserInput: string }
+  /**
+   * A finalized speech transcript. Runs as a slash command when it is just
+   * a command name (or "slash"/"command" followed by a command and its
+   * arguments) and voice.spoken_commands is enabled.
+   */
+  | { VoiceTranscript: string }
+  | { VoicePartialTranscript: string }
   | { UserInputWithImages: { text: string; images: ImageData[] } }
   | { ChangeProvider: string }
   | 'GetSettings'
@@ -378,4 +458,11 @@ export type ChatActorMessage =
   | 'ListSessions'
   | { ResumeSession: { session_id: string } }
   | 'GetModuleSchemas'
-  | 'GetSettingsSchema';
+  | 'GetSettingsSchema'
+  /**
+   * Accepts or rejects one edit from an EditsProposed event. The turn resumes
+   * once every proposed edit has been decided.
+   */
+  | { ReviewEdit: { edit_id: number; accept: boolean } }
+  /** Saves the session and closes MCP clients, then stops the actor. */
+  | 'Shutdown';
@@ -11,7 +11,7 @@ use crate::{
         events::{next_orchestration_id, AgentId, OrchestrationPayload},
         ChildAction, ChildOutcome, CompletionAction, TaskAction, WorkflowState,
     },
-    settings::config::Settings,
+    settings::config::{AutonomyLevel, Settings},
     tools::ToolName,
 };
 
@@ -30,8 +30,9 @@ pub trait Agent: Send + Sync {
     /// prompt which are reused by many agents, for example, how to talk to the
     /// user, how to use your tools. Generally opting in to all prompt
     /// components is a safe default, however some agents may wish to disable
-    /// or control prompt more.
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
+    /// or control prompt more. `autonomy` is the level this agent runs at
+    /// (its `agent_autonomy_levels` entry or the global `autonomy_level`).
+    fn requested_prompt_components(&self, _autonomy: AutonomyLevel) -> PromptComponentSelection {
         PromptComponentSelection::All
     }
 
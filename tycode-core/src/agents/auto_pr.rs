@@ -1,9 +1,13 @@
 use crate::agents::agent::Agent;
+use crate::analyzer::find_references::FindReferencesTool;
+use crate::analyzer::get_definition::GetDefinitionTool;
 use crate::analyzer::get_type_docs::GetTypeDocsTool;
 use crate::analyzer::search_types::SearchTypesTool;
+use crate::file::read_files::ReadFilesTool;
 use crate::module::PromptComponentSelection;
 use crate::modules::execution::BashTool;
-use crate::modules::task_list::ManageTaskListTool;
+use crate::modules::task_list::{ManageTaskListTool, ManageTasksTool};
+use crate::settings::config::AutonomyLevel;
 use crate::spawn::complete_task::CompleteTask;
 use crate::spawn::SpawnAgent;
 use crate::steering::autonomy;
@@ -106,18 +110,22 @@ impl Agent for AutoPrAgent {
         CORE_PROMPT
     }
 
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
-        PromptComponentSelection::Exclude(&[autonomy::ID])
+    fn requested_prompt_components(&self, level: AutonomyLevel) -> PromptComponentSelection {
+        autonomy::bespoke_workflow_selection(level)
     }
 
     fn available_tools(&self) -> Vec<ToolName> {
         vec![
             SpawnAgent::tool_name(),
             ManageTaskListTool::tool_name(),
+            ManageTasksTool::tool_name(),
             BashTool::tool_name(),
+            ReadFilesTool::tool_name(),
             CompleteTask::tool_name(),
             SearchTypesTool::tool_name(),
             GetTypeDocsTool::tool_name(),
+            GetDefinitionTool::tool_name(),
+            FindReferencesTool::tool_name(),
         ]
     }
 }
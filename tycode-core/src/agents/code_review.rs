@@ -1,7 +1,12 @@
 use crate::agents::agent::Agent;
+use crate::file::blame::GitBlameTool;
+use crate::file::diff::DiffFilesTool;
+use crate::file::read_files::ReadFilesTool;
+use crate::file::search::SearchFilesTool;
 use crate::module::PromptComponentSelection;
 use crate::modules::execution::BashTool;
-use crate::modules::memory::tool::AppendMemoryTool;
+use crate::modules::memory::tool::{AppendMemoryTool, RecallMemoryTool};
+use crate::settings::config::AutonomyLevel;
 use crate::spawn::complete_task::CompleteTask;
 use crate::steering::autonomy;
 use crate::tools::ToolName;
@@ -74,15 +79,20 @@ impl Agent for CodeReviewAgent {
         CORE_PROMPT
     }
 
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
-        PromptComponentSelection::Exclude(&[autonomy::ID])
+    fn requested_prompt_components(&self, level: AutonomyLevel) -> PromptComponentSelection {
+        autonomy::bespoke_workflow_selection(level)
     }
 
     fn available_tools(&self) -> Vec<ToolName> {
         vec![
             BashTool::tool_name(),
+            ReadFilesTool::tool_name(),
+            SearchFilesTool::tool_name(),
+            GitBlameTool::tool_name(),
+            DiffFilesTool::tool_name(),
             CompleteTask::tool_name(),
             AppendMemoryTool::tool_name(),
+            RecallMemoryTool::tool_name(),
         ]
     }
 
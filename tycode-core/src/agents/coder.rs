@@ -1,21 +1,31 @@
 use crate::agents::agent::Agent;
 use crate::agents::code_review::CodeReviewAgent;
+use crate::analyzer::find_references::FindReferencesTool;
+use crate::analyzer::get_definition::GetDefinitionTool;
 use crate::analyzer::get_type_docs::GetTypeDocsTool;
 use crate::analyzer::search_types::SearchTypesTool;
+use crate::file::blame::GitBlameTool;
 use crate::file::modify::delete_file::DeleteFileTool;
 use crate::file::modify::replace_in_file::ReplaceInFileTool;
+use crate::file::modify::resolve_conflicts::ResolveConflictsTool;
 use crate::file::modify::write_file::WriteFileTool;
+use crate::file::read_files::ReadFilesTool;
+use crate::file::search::SearchFilesTool;
 use crate::module::PromptComponentSelection;
+use crate::modules::execution::format::FormatFileTool;
 use crate::modules::execution::BashTool;
-use crate::modules::image::{GenerateImageTool, ReadImageTool};
-use crate::modules::memory::tool::AppendMemoryTool;
-use crate::modules::task_list::ManageTaskListTool;
+use crate::modules::git::branch::GitBranchTool;
+use crate::modules::git::commit::GitCommitTool;
+use crate::modules::image::{ConvertImageTool, GenerateImageTool, ReadImageTool};
+use crate::modules::memory::tool::{AppendMemoryTool, RecallMemoryTool};
+use crate::modules::semantic_search::tool::SemanticSearchTool;
+use crate::modules::task_list::{ManageTaskListTool, ManageTasksTool};
 use crate::orchestration::{
     default_child_message,
     events::{OrchestrationPayload, ReviewVerdict},
     ChildAction, ChildOutcome, CompletionAction, ConversationSeed, SpawnSpec, WorkflowState,
 };
-use crate::settings::config::{ReviewLevel, Settings};
+use crate::settings::config::{AutonomyLevel, ReviewLevel, Settings};
 use crate::skills::tool::InvokeSkillTool;
 use crate::spawn::complete_task::CompleteTask;
 use crate::spawn::SpawnAgent;
@@ -70,8 +80,8 @@ impl Agent for CoderAgent {
         CORE_PROMPT
     }
 
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
-        PromptComponentSelection::Exclude(&[autonomy::ID])
+    fn requested_prompt_components(&self, level: AutonomyLevel) -> PromptComponentSelection {
+        autonomy::bespoke_workflow_selection(level)
     }
 
     fn available_tools(&self) -> Vec<ToolName> {
@@ -79,16 +89,29 @@ impl Agent for CoderAgent {
             WriteFileTool::tool_name(),
             ReplaceInFileTool::tool_name(),
             DeleteFileTool::tool_name(),
+            ResolveConflictsTool::tool_name(),
             SpawnAgent::tool_name(),
             BashTool::tool_name(),
+            FormatFileTool::tool_name(),
+            ReadFilesTool::tool_name(),
+            SearchFilesTool::tool_name(),
+            SemanticSearchTool::tool_name(),
+            GitBlameTool::tool_name(),
+            GitBranchTool::tool_name(),
+            GitCommitTool::tool_name(),
             CompleteTask::tool_name(),
             SearchTypesTool::tool_name(),
             GetTypeDocsTool::tool_name(),
+            GetDefinitionTool::tool_name(),
+            FindReferencesTool::tool_name(),
             AppendMemoryTool::tool_name(),
+            RecallMemoryTool::tool_name(),
             InvokeSkillTool::tool_name(),
             GenerateImageTool::tool_name(),
             ReadImageTool::tool_name(),
+            ConvertImageTool::tool_name(),
             ManageTaskListTool::tool_name(),
+            ManageTasksTool::tool_name(),
         ]
     }
 
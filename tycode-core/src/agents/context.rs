@@ -1,9 +1,16 @@
 use crate::agents::agent::Agent;
+use crate::analyzer::find_references::FindReferencesTool;
+use crate::analyzer::get_definition::GetDefinitionTool;
 use crate::analyzer::get_type_docs::GetTypeDocsTool;
 use crate::analyzer::search_types::SearchTypesTool;
+use crate::file::blame::GitBlameTool;
+use crate::file::read_files::ReadFilesTool;
+use crate::file::search::SearchFilesTool;
 use crate::module::PromptComponentSelection;
 use crate::modules::execution::BashTool;
-use crate::modules::memory::tool::AppendMemoryTool;
+use crate::modules::memory::tool::{AppendMemoryTool, RecallMemoryTool};
+use crate::modules::semantic_search::tool::SemanticSearchTool;
+use crate::settings::config::AutonomyLevel;
 use crate::spawn::complete_task::CompleteTask;
 use crate::steering::tools;
 use crate::tools::ToolName;
@@ -68,17 +75,24 @@ impl Agent for ContextAgent {
         CORE_PROMPT
     }
 
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
+    fn requested_prompt_components(&self, _autonomy: AutonomyLevel) -> PromptComponentSelection {
         PromptComponentSelection::Only(&[tools::ID])
     }
 
     fn available_tools(&self) -> Vec<ToolName> {
         vec![
             BashTool::tool_name(),
+            ReadFilesTool::tool_name(),
+            SearchFilesTool::tool_name(),
+            SemanticSearchTool::tool_name(),
+            GitBlameTool::tool_name(),
             SearchTypesTool::tool_name(),
             GetTypeDocsTool::tool_name(),
+            GetDefinitionTool::tool_name(),
+            FindReferencesTool::tool_name(),
             CompleteTask::tool_name(),
             AppendMemoryTool::tool_name(),
+            RecallMemoryTool::tool_name(),
         ]
     }
 
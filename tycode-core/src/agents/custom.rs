@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use serde::{Deserialize, Serialize};
 
 use crate::module::PromptComponentSelection;
+use crate::settings::config::AutonomyLevel;
 use crate::tools::ToolName;
 
 use super::agent::Agent;
@@ -151,7 +152,7 @@ impl Agent for CustomAgent {
     /// Custom agents supply their own complete system prompt via the markdown
     /// body, so we exclude the standard prompt components (style mandates,
     /// communication guidelines, etc.).
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
+    fn requested_prompt_components(&self, _autonomy: AutonomyLevel) -> PromptComponentSelection {
         PromptComponentSelection::None
     }
 
@@ -1,12 +1,18 @@
 use crate::agents::agent::Agent;
+use crate::analyzer::find_references::FindReferencesTool;
+use crate::analyzer::get_definition::GetDefinitionTool;
 use crate::analyzer::get_type_docs::GetTypeDocsTool;
 use crate::analyzer::search_types::SearchTypesTool;
+use crate::file::blame::GitBlameTool;
 use crate::file::modify::delete_file::DeleteFileTool;
 use crate::file::modify::replace_in_file::ReplaceInFileTool;
 use crate::file::modify::write_file::WriteFileTool;
+use crate::file::read_files::ReadFilesTool;
+use crate::file::search::SearchFilesTool;
 use crate::module::PromptComponentSelection;
 use crate::modules::execution::BashTool;
-use crate::modules::memory::tool::AppendMemoryTool;
+use crate::modules::memory::tool::{AppendMemoryTool, RecallMemoryTool};
+use crate::settings::config::AutonomyLevel;
 use crate::skills::tool::InvokeSkillTool;
 use crate::spawn::complete_task::CompleteTask;
 use crate::steering::autonomy;
@@ -75,8 +81,8 @@ impl Agent for DebuggerAgent {
         CORE_PROMPT
     }
 
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
-        PromptComponentSelection::Exclude(&[autonomy::ID])
+    fn requested_prompt_components(&self, level: AutonomyLevel) -> PromptComponentSelection {
+        autonomy::bespoke_workflow_selection(level)
     }
 
     fn available_tools(&self) -> Vec<ToolName> {
@@ -85,10 +91,16 @@ impl Agent for DebuggerAgent {
             ReplaceInFileTool::tool_name(),
             DeleteFileTool::tool_name(),
             BashTool::tool_name(),
+            ReadFilesTool::tool_name(),
+            SearchFilesTool::tool_name(),
+            GitBlameTool::tool_name(),
             CompleteTask::tool_name(),
             SearchTypesTool::tool_name(),
             GetTypeDocsTool::tool_name(),
+            GetDefinitionTool::tool_name(),
+            FindReferencesTool::tool_name(),
             AppendMemoryTool::tool_name(),
+            RecallMemoryTool::tool_name(),
             InvokeSkillTool::tool_name(),
         ]
     }
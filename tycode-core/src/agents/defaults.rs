@@ -1,6 +1,6 @@
 use crate::settings::config::{AutonomyLevel, CommunicationTone};
 
-const AUTONOMY_PLAN_APPROVAL: &str = r#"## Autonomy Level: Plan Approval Required
+const AUTONOMY_BALANCED: &str = r#"## Autonomy Level: Balanced
 Before implementing changes, you must:
 1. Present a plan with concrete steps to the user
 2. Wait for explicit approval before proceeding
@@ -10,14 +10,26 @@ Before implementing changes, you must:
 Remember: The user is here to help you! It is always better to stop and ask the user for help or guidance than to make a mistake or get stuck in a loop.
 Critical: User approval must be obtained before executing any plan."#;
 
-const AUTONOMY_FULLY_AUTONOMOUS: &str = r#"## Autonomy Level: Fully Autonomous
-Use your judgment to make decisions and follow system prompt instructions without consulting the user.
+const AUTONOMY_AUTONOMOUS: &str = r#"## Autonomy Level: Autonomous
+Use your judgment to make decisions and follow system prompt instructions without consulting the user. Proceed with implementation directly; do not stop to present a plan or wait for approval. Only ask the user when you are blocked on information you cannot find yourself.
+Edits are applied without confirmation prompts.
 "#;
 
+const AUTONOMY_CONSERVATIVE: &str = r#"## Autonomy Level: Conservative
+Before implementing changes, you must:
+1. Present a plan with concrete steps to the user
+2. Wait for explicit approval before proceeding
+3. If you need to modify the plan for any reason, consult the user again
+4. Each new request from the user requires a new plan and approval
+
+Every file edit is also shown to the user for confirmation before it is written. Make edits small and focused so each one is easy to review, and do not work around a declined edit; ask the user how to proceed instead.
+Critical: User approval must be obtained before executing any plan."#;
+
 pub fn get_autonomy_instructions(level: AutonomyLevel) -> &'static str {
     match level {
-        AutonomyLevel::PlanApprovalRequired => AUTONOMY_PLAN_APPROVAL,
-        AutonomyLevel::FullyAutonomous => AUTONOMY_FULLY_AUTONOMOUS,
+        AutonomyLevel::Conservative => AUTONOMY_CONSERVATIVE,
+        AutonomyLevel::Balanced => AUTONOMY_BALANCED,
+        AutonomyLevel::Autonomous => AUTONOMY_AUTONOMOUS,
     }
 }
 
@@ -2,8 +2,10 @@ use crate::agents::agent::Agent;
 use crate::file::modify::delete_file::DeleteFileTool;
 use crate::file::modify::replace_in_file::ReplaceInFileTool;
 use crate::file::modify::write_file::WriteFileTool;
+use crate::file::read_files::ReadFilesTool;
 use crate::module::PromptComponentSelection;
 use crate::modules::execution::BashTool;
+use crate::settings::config::AutonomyLevel;
 use crate::spawn::complete_task::CompleteTask;
 use crate::steering::autonomy;
 use crate::tools::ToolName;
@@ -36,8 +38,8 @@ impl Agent for FileImplAgent {
         CORE_PROMPT
     }
 
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
-        PromptComponentSelection::Exclude(&[autonomy::ID])
+    fn requested_prompt_components(&self, level: AutonomyLevel) -> PromptComponentSelection {
+        autonomy::bespoke_workflow_selection(level)
     }
 
     fn available_tools(&self) -> Vec<ToolName> {
@@ -46,6 +48,7 @@ impl Agent for FileImplAgent {
             ReplaceInFileTool::tool_name(),
             DeleteFileTool::tool_name(),
             BashTool::tool_name(),
+            ReadFilesTool::tool_name(),
             CompleteTask::tool_name(),
         ]
     }
@@ -1,6 +1,7 @@
 use crate::agents::agent::Agent;
 use crate::module::PromptComponentSelection;
 use crate::modules::memory::tool::AppendMemoryTool;
+use crate::settings::config::AutonomyLevel;
 use crate::spawn::complete_task::CompleteTask;
 use crate::tools::ToolName;
 
@@ -89,7 +90,7 @@ impl Agent for MemoryManagerAgent {
         CORE_PROMPT
     }
 
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
+    fn requested_prompt_components(&self, _autonomy: AutonomyLevel) -> PromptComponentSelection {
         PromptComponentSelection::None
     }
 
@@ -1,5 +1,6 @@
 use crate::agents::agent::Agent;
 use crate::module::PromptComponentSelection;
+use crate::settings::config::AutonomyLevel;
 use crate::spawn::complete_task::CompleteTask;
 use crate::tools::ToolName;
 
@@ -91,7 +92,7 @@ impl Agent for MemorySummarizerAgent {
         CORE_PROMPT
     }
 
-    fn requested_prompt_components(&self) -> PromptComponentSelection {
+    fn requested_prompt_components(&self, _autonomy: AutonomyLevel) -> PromptComponentSelection {
         PromptComponentSelection::None
     }
 
@@ -1,13 +1,16 @@
 use crate::agents::agent::Agent;
+use crate::analyzer::find_references::FindReferencesTool;
+use crate::analyzer::get_definition::GetDefinitionTool;
 use crate::analyzer::get_type_docs::GetTypeDocsTool;
 use crate::analyzer::search_types::SearchTypesTool;
 use crate::file::modify::delete_file::DeleteFileTool;
 use crate::file::modify::replace_in_file::ReplaceInFileTool;
 use crate::file::modify::write_file::WriteFileTool;
+use crate::file::read_files::ReadFilesTool;
 use crate::modules::execution::BashTool;
-use crate::modules::image::GenerateImageTool;
-use crate::modules::memory::tool::AppendMemoryTool;
-use crate::modules::task_list::ManageTaskListTool;
+use crate::modules::image::{ConvertImageTool, GenerateImageTool};
+use crate::modules::memory::tool::{AppendMemoryTool, RecallMemoryTool};
+use crate::modules::task_list::{ManageTaskListTool, ManageTasksTool};
 use crate::skills::tool::InvokeSkillTool;
 use crate::spawn::complete_task::CompleteTask;
 use crate::tools::ask_user_question::AskUserQuestion;
@@ -70,14 +73,20 @@ impl Agent for OneShotAgent {
             ReplaceInFileTool::tool_name(),
             DeleteFileTool::tool_name(),
             BashTool::tool_name(),
+            ReadFilesTool::tool_name(),
             AskUserQuestion::tool_name(),
             ManageTaskListTool::tool_name(),
+            ManageTasksTool::tool_name(),
             CompleteTask::tool_name(),
             SearchTypesTool::tool_name(),
             GetTypeDocsTool::tool_name(),
+            GetDefinitionTool::tool_name(),
+            FindReferencesTool::tool_name(),
             AppendMemoryTool::tool_name(),
+            RecallMemoryTool::tool_name(),
             InvokeSkillTool::tool_name(),
             GenerateImageTool::tool_name(),
+            ConvertImageTool::tool_name(),
         ]
     }
 
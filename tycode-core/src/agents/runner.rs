@@ -20,7 +20,13 @@ use crate::tools::registry::ToolRegistry;
 
 /// Tools that mutate files; subject to write_allowlist enforcement during
 /// fan-out so a worker cannot edit outside its assignment.
-const WRITE_TOOL_NAMES: &[&str] = &["write_file", "modify_file", "delete_file"];
+const WRITE_TOOL_NAMES: &[&str] = &[
+    "write_file",
+    "modify_file",
+    "delete_file",
+    "resolve_conflicts",
+    "format_file",
+];
 
 /// A sub-agent runner.
 ///
@@ -222,7 +228,9 @@ impl AgentRunner {
                 format!("Task completed (success={}): {}", success, result)
             }
             ToolOutput::ImageResult { content, .. } => content.clone(),
-            ToolOutput::PushAgent { .. } | ToolOutput::PromptUser { .. } => {
+            ToolOutput::PushAgent { .. }
+            | ToolOutput::PromptUser { .. }
+            | ToolOutput::ProposeEdit { .. } => {
                 return Err(anyhow!(
                     "Tool '{}' returned unsupported action for AgentRunner context",
                     name
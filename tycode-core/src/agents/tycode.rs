@@ -1,13 +1,23 @@
 use crate::agents::agent::Agent;
+use crate::analyzer::find_references::FindReferencesTool;
+use crate::analyzer::get_definition::GetDefinitionTool;
 use crate::analyzer::get_type_docs::GetTypeDocsTool;
 use crate::analyzer::search_types::SearchTypesTool;
+use crate::file::blame::GitBlameTool;
 use crate::file::modify::delete_file::DeleteFileTool;
 use crate::file::modify::replace_in_file::ReplaceInFileTool;
+use crate::file::modify::resolve_conflicts::ResolveConflictsTool;
 use crate::file::modify::write_file::WriteFileTool;
+use crate::file::read_files::ReadFilesTool;
+use crate::file::search::SearchFilesTool;
+use crate::modules::execution::format::FormatFileTool;
 use crate::modules::execution::BashTool;
-use crate::modules::image::{GenerateImageTool, ReadImageTool};
-use crate::modules::memory::tool::AppendMemoryTool;
-use crate::modules::task_list::ManageTaskListTool;
+use crate::modules::git::branch::GitBranchTool;
+use crate::modules::git::commit::GitCommitTool;
+use crate::modules::image::{ConvertImageTool, GenerateImageTool, ReadImageTool};
+use crate::modules::memory::tool::{AppendMemoryTool, RecallMemoryTool};
+use crate::modules::semantic_search::tool::SemanticSearchTool;
+use crate::modules::task_list::{ManageTaskListTool, ManageTasksTool};
 use crate::settings::config::OrchestrationMode;
 use crate::skills::tool::InvokeSkillTool;
 use crate::spawn::complete_task::CompleteTask;
@@ -141,17 +151,30 @@ impl Agent for TycodeAgent {
             WriteFileTool::tool_name(),
             ReplaceInFileTool::tool_name(),
             DeleteFileTool::tool_name(),
+            ResolveConflictsTool::tool_name(),
             BashTool::tool_name(),
+            FormatFileTool::tool_name(),
+            ReadFilesTool::tool_name(),
+            SearchFilesTool::tool_name(),
+            SemanticSearchTool::tool_name(),
+            GitBlameTool::tool_name(),
+            GitBranchTool::tool_name(),
+            GitCommitTool::tool_name(),
             AskUserQuestion::tool_name(),
             ManageTaskListTool::tool_name(),
+            ManageTasksTool::tool_name(),
             CompleteTask::tool_name(),
             SearchTypesTool::tool_name(),
             GetTypeDocsTool::tool_name(),
+            GetDefinitionTool::tool_name(),
+            FindReferencesTool::tool_name(),
             AppendMemoryTool::tool_name(),
+            RecallMemoryTool::tool_name(),
             InvokeSkillTool::tool_name(),
             SpawnAgent::tool_name(),
             GenerateImageTool::tool_name(),
             ReadImageTool::tool_name(),
+            ConvertImageTool::tool_name(),
         ]
     }
 
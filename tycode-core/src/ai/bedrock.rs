@@ -8,6 +8,7 @@ use base64::Engine;
 use aws_sdk_bedrockruntime::{
     operation::converse::{builders::ConverseFluentBuilder, ConverseError},
     operation::converse_stream::{builders::ConverseStreamFluentBuilder, ConverseStreamError},
+    operation::invoke_model::InvokeModelError,
     types::ConverseStreamOutput as BedrockStreamEvent,
     types::{
         CachePointBlock, ContentBlock as BedrockContentBlock, ImageBlock, ImageFormat, ImageSource,
@@ -27,6 +28,11 @@ use crate::ai::{
     model::Model,
 };
 
+/// Titan text embeddings. The model embeds one text per request.
+const EMBEDDING_MODEL_ID: &str = "amazon.titan-embed-text-v2:0";
+/// Embedding requests in flight at once.
+const EMBEDDING_CONCURRENCY: usize = 8;
+
 #[derive(Clone)]
 pub struct BedrockProvider {
     client: BedrockClient,
@@ -193,6 +199,43 @@ impl BedrockProvider {
         }
     }
 
+    async fn embed_one(&self, text: &str) -> Result<Vec<f32>, AiError> {
+        let body = serde_json::to_vec(&json!({ "inputText": text }))
+            .map_err(|e| AiError::Terminal(anyhow::anyhow!(e)))?;
+        let response = self
+            .client
+            .invoke_model()
+            .model_id(EMBEDDING_MODEL_ID)
+            .content_type("application/json")
+            .accept("application/json")
+            .body(Blob::new(body))
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::warn!(?e, "Bedrock embedding failed");
+                match e.into_service_error() {
+                    InvokeModelError::ThrottlingException(e) => {
+                        AiError::Retryable(anyhow::anyhow!(e))
+                    }
+                    InvokeModelError::ServiceUnavailableException(e) => {
+                        AiError::Retryable(anyhow::anyhow!(e))
+                    }
+                    InvokeModelError::InternalServerException(e) => {
+                        AiError::Retryable(anyhow::anyhow!(e))
+                    }
+                    InvokeModelError::ModelTimeoutException(e) => {
+                        AiError::Retryable(anyhow::anyhow!(e))
+                    }
+                    e => AiError::Terminal(anyhow::anyhow!("Bedrock embedding failed: {e:?}")),
+                }
+            })?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(response.body().as_ref())
+            .map_err(|e| AiError::Terminal(anyhow::anyhow!("Invalid embedding response: {e}")))?;
+        serde_json::from_value(parsed["embedding"].clone())
+            .map_err(|e| AiError::Terminal(anyhow::anyhow!("Embedding missing from response: {e}")))
+    }
+
     fn default_native_models() -> HashMap<Model, String> {
         HashMap::from([
             (
@@ -526,11 +569,17 @@ impl BedrockProvider {
         }
     }
 
+    /// Adaptive-thinking models take an effort level; an explicit
+    /// `effort` is mapped onto the budget scale they understand.
     fn adaptive_reasoning_effort(model: &ModelSettings) -> Option<&'static str> {
-        match (&model.model, &model.reasoning_budget) {
+        let budget = match model.effort {
+            Some(effort) => effort.to_budget(),
+            None => model.reasoning_budget.clone(),
+        };
+        match (&model.model, &budget) {
             (_, ReasoningBudget::Off) => None,
             (Model::ClaudeFable | Model::ClaudeOpus, ReasoningBudget::Max) => Some("xhigh"),
-            _ => model.reasoning_budget.get_effort_level(),
+            _ => budget.get_effort_level(),
         }
     }
 
@@ -1211,6 +1260,21 @@ impl AiProvider for BedrockProvider {
         }
     }
 
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, AiError> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBEDDING_CONCURRENCY) {
+            let embedded =
+                futures_util::future::try_join_all(batch.iter().map(|text| self.embed_one(text)))
+                    .await?;
+            vectors.extend(embedded);
+        }
+        Ok(vectors)
+    }
+
     fn model_version(&self, model: &Model) -> String {
         self.mantle_models
             .get(model)
@@ -1218,6 +1282,15 @@ impl AiProvider for BedrockProvider {
             .map(|model_id| bedrock_display_version(model_id))
             .unwrap_or_else(|| model.versioned_name().to_string())
     }
+
+    fn temperature_range(&self, model: &Model) -> (f32, f32) {
+        // Mantle serves OpenAI-compatible models, which accept up to 2.0
+        if self.mantle_models.contains_key(model) {
+            (0.0, 2.0)
+        } else {
+            (0.0, 1.0)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1288,6 +1361,7 @@ mod tests {
             temperature: None,
             top_p: None,
             reasoning_budget: ReasoningBudget::Max,
+            effort: None,
         })
         .unwrap();
 
@@ -1301,6 +1375,7 @@ mod tests {
             temperature: None,
             top_p: None,
             reasoning_budget: ReasoningBudget::Max,
+            effort: None,
         })
         .unwrap();
         assert_eq!(output_config["effort"], "xhigh");
@@ -1315,6 +1390,7 @@ mod tests {
                 temperature: None,
                 top_p: None,
                 reasoning_budget: budget,
+                effort: None,
             };
 
             let thinking = BedrockProvider::build_adaptive_thinking(&settings).unwrap();
@@ -1428,6 +1504,7 @@ mod tests {
                         temperature: None,
                         top_p: None,
                         reasoning_budget: ReasoningBudget::Off,
+                        effort: None,
                     },
                     system_prompt: String::new(),
                     stop_sequences: Vec::new(),
@@ -1581,6 +1658,7 @@ mod tests {
                 temperature: None,
                 top_p: None,
                 reasoning_budget: ReasoningBudget::High,
+                effort: None,
             },
             system_prompt: "You are a helpful AI assistant. Use tools when requested.".to_string(),
             stop_sequences: Vec::new(),
@@ -1663,6 +1741,7 @@ mod tests {
                 temperature: None,
                 top_p: None,
                 reasoning_budget: ReasoningBudget::High,
+                effort: None,
             },
             system_prompt: "You are a helpful AI assistant. Use tools when requested.".to_string(),
             stop_sequences: Vec::new(),
@@ -1775,6 +1854,7 @@ mod tests {
                 temperature: None,
                 top_p: None,
                 reasoning_budget: reasoning_budget.clone(),
+                effort: None,
             },
             system_prompt:
                 "You are a helpful AI assistant. Use reasoning when solving logic puzzles."
@@ -1860,6 +1940,7 @@ mod tests {
                 temperature: None,
                 top_p: None,
                 reasoning_budget,
+                effort: None,
             },
             system_prompt:
                 "You are a helpful AI assistant. Use reasoning when solving logic puzzles."
@@ -0,0 +1,350 @@
+//! Response cache: replays the stored response for an exact repeat of an
+//! earlier request instead of sending it to the provider again.
+//!
+//! Requests are keyed by a hash of everything that shapes the response
+//! (provider, model settings, system prompt, messages, tools and stop
+//! sequences). Streamed requests and responses that call tools are passed
+//! through unless the settings opt into caching them. Replayed responses
+//! report no token usage, since nothing was billed for them.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio_stream::{Stream, StreamExt};
+
+use crate::ai::tweaks::ModelTweaks;
+use crate::ai::{error::AiError, model::Model, provider::AiProvider, types::*};
+use crate::settings::config::ResponseCacheSettings;
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// Seconds since the Unix epoch
+    stored_at: u64,
+    response: ConversationResponse,
+}
+
+#[derive(Clone)]
+struct ResponseCache {
+    dir: PathBuf,
+    settings: ResponseCacheSettings,
+}
+
+impl ResponseCache {
+    fn key(provider: &str, request: &ConversationRequest) -> String {
+        let keyed = serde_json::json!({
+            "provider": provider,
+            "model": request.model,
+            "system_prompt": request.system_prompt,
+            "messages": request.messages,
+            "tools": request.tools,
+            "stop_sequences": request.stop_sequences,
+        });
+        format!("{:x}", Sha256::digest(keyed.to_string().as_bytes()))
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    fn get(&self, key: &str) -> Option<ConversationResponse> {
+        let path = self.path(key);
+        let entry: CacheEntry = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+        if now_secs().saturating_sub(entry.stored_at) >= self.settings.ttl_secs {
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+
+        let mut response = entry.response;
+        response.usage = TokenUsage::empty();
+        Some(response)
+    }
+
+    fn put(&self, key: &str, response: &ConversationResponse) {
+        if !self.settings.cache_tool_use && !response.content.tool_uses().is_empty() {
+            return;
+        }
+
+        let entry = CacheEntry {
+            stored_at: now_secs(),
+            response: response.clone(),
+        };
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(self.path(key), serde_json::to_string(&entry)?));
+        if let Err(e) = result {
+            tracing::warn!(?e, "Failed to store cached response");
+            return;
+        }
+        evict_oldest(&self.dir, self.settings.max_entries);
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+/// Removes the least recently stored entries beyond `max_entries`.
+fn evict_oldest(dir: &Path, max_entries: usize) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|e| e == "json"))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .collect();
+    if files.len() <= max_entries {
+        return;
+    }
+
+    files.sort();
+    let excess = files.len() - max_entries;
+    for (_, path) in files.into_iter().take(excess) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+pub struct CachingProvider {
+    inner: Arc<dyn AiProvider>,
+    cache: ResponseCache,
+}
+
+impl CachingProvider {
+    /// Wraps `inner` so responses are cached as files in `dir`.
+    pub fn wrap(
+        inner: Arc<dyn AiProvider>,
+        dir: PathBuf,
+        settings: ResponseCacheSettings,
+    ) -> Arc<dyn AiProvider> {
+        Arc::new(Self {
+            inner,
+            cache: ResponseCache { dir, settings },
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AiProvider for CachingProvider {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn supported_models(&self) -> HashSet<Model> {
+        self.inner.supported_models()
+    }
+
+    async fn converse(
+        &self,
+        request: ConversationRequest,
+    ) -> Result<ConversationResponse, AiError> {
+        let key = ResponseCache::key(self.inner.name(), &request);
+        if let Some(response) = self.cache.get(&key) {
+            return Ok(response);
+        }
+
+        let response = self.inner.converse(request).await?;
+        self.cache.put(&key, &response);
+        Ok(response)
+    }
+
+    fn get_cost(&self, model: &Model) -> Cost {
+        self.inner.get_cost(model)
+    }
+
+    fn model_version(&self, model: &Model) -> String {
+        self.inner.model_version(model)
+    }
+
+    fn context_window(&self, model: &Model) -> u32 {
+        self.inner.context_window(model)
+    }
+
+    fn temperature_range(&self, model: &Model) -> (f32, f32) {
+        self.inner.temperature_range(model)
+    }
+
+    async fn converse_stream(
+        &self,
+        request: ConversationRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamEvent, AiError>> + Send>>, AiError> {
+        if !self.cache.settings.cache_streaming {
+            return self.inner.converse_stream(request).await;
+        }
+
+        let key = ResponseCache::key(self.inner.name(), &request);
+        if let Some(response) = self.cache.get(&key) {
+            return Ok(Box::pin(tokio_stream::once(Ok(
+                StreamEvent::MessageComplete { response },
+            ))));
+        }
+
+        let stream = self.inner.converse_stream(request).await?;
+        let cache = self.cache.clone();
+        Ok(Box::pin(stream.map(move |event| {
+            if let Ok(StreamEvent::MessageComplete { response }) = &event {
+                cache.put(&key, response);
+            }
+            event
+        })))
+    }
+
+    fn supports_image_generation(&self) -> bool {
+        self.inner.supports_image_generation()
+    }
+
+    async fn generate_image(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, AiError> {
+        self.inner.generate_image(request).await
+    }
+
+    async fn generate_image_with_progress(
+        &self,
+        request: ImageGenerationRequest,
+        progress: &(dyn Fn(ImageGenerationStatus) + Send + Sync),
+    ) -> Result<ImageGenerationResponse, AiError> {
+        self.inner
+            .generate_image_with_progress(request, progress)
+            .await
+    }
+
+    fn max_images_per_request(&self) -> u32 {
+        self.inner.max_images_per_request()
+    }
+
+    async fn generate_images(
+        &self,
+        request: ImageGenerationRequest,
+        count: u32,
+        progress: &(dyn Fn(ImageGenerationStatus) + Send + Sync),
+    ) -> Result<Vec<ImageGenerationResponse>, AiError> {
+        self.inner.generate_images(request, count, progress).await
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        self.inner.supports_embeddings()
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, AiError> {
+        self.inner.embed(texts).await
+    }
+
+    fn tweaks(&self) -> ModelTweaks {
+        self.inner.tweaks()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::mock::{MockBehavior, MockProvider};
+
+    fn request(text: &str) -> ConversationRequest {
+        ConversationRequest {
+            messages: vec![Message::user(text.to_string())],
+            model: Model::None.default_settings(),
+            system_prompt: String::new(),
+            stop_sequences: Vec::new(),
+            tools: Vec::new(),
+        }
+    }
+
+    fn settings() -> ResponseCacheSettings {
+        ResponseCacheSettings {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_requests_are_served_from_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let mock = Arc::new(MockProvider::new(MockBehavior::Success));
+        let provider = CachingProvider::wrap(mock.clone(), dir.path().to_path_buf(), settings());
+
+        let first = provider.converse(request("hello")).await.unwrap();
+        let second = provider.converse(request("hello")).await.unwrap();
+        provider.converse(request("goodbye")).await.unwrap();
+
+        assert_eq!(mock.get_call_count(), 2);
+        assert_eq!(first.content.text(), second.content.text());
+        assert_eq!(second.usage.total_tokens, 0);
+    }
+
+    #[tokio::test]
+    async fn expired_entries_and_tool_calls_are_not_replayed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mock = Arc::new(MockProvider::new(MockBehavior::Success));
+        let expired = ResponseCacheSettings {
+            ttl_secs: 0,
+            ..settings()
+        };
+        let provider = CachingProvider::wrap(mock.clone(), dir.path().to_path_buf(), expired);
+        provider.converse(request("hello")).await.unwrap();
+        provider.converse(request("hello")).await.unwrap();
+        assert_eq!(mock.get_call_count(), 2);
+
+        let mock = Arc::new(MockProvider::new(MockBehavior::ToolUse {
+            tool_name: "read_file".to_string(),
+            tool_arguments: "{}".to_string(),
+        }));
+        let provider = CachingProvider::wrap(mock.clone(), dir.path().to_path_buf(), settings());
+        provider.converse(request("read")).await.unwrap();
+        provider.converse(request("read")).await.unwrap();
+        assert_eq!(mock.get_call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn streaming_is_cached_only_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let mock = Arc::new(MockProvider::new(MockBehavior::Success));
+        let provider = CachingProvider::wrap(mock.clone(), dir.path().to_path_buf(), settings());
+        for _ in 0..2 {
+            let stream = provider.converse_stream(request("hello")).await.unwrap();
+            stream.collect::<Vec<_>>().await;
+        }
+        assert_eq!(mock.get_call_count(), 2);
+
+        let streaming = ResponseCacheSettings {
+            cache_streaming: true,
+            ..settings()
+        };
+        let provider = CachingProvider::wrap(mock.clone(), dir.path().to_path_buf(), streaming);
+        for _ in 0..2 {
+            let stream = provider.converse_stream(request("hello")).await.unwrap();
+            stream.collect::<Vec<_>>().await;
+        }
+        assert_eq!(mock.get_call_count(), 3);
+    }
+
+    #[test]
+    fn eviction_keeps_the_newest_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        for (index, age) in [30, 20, 10].into_iter().enumerate() {
+            let path = dir.path().join(format!("{index}.json"));
+            fs::write(&path, "{}").unwrap();
+            let modified = SystemTime::now() - Duration::from_secs(age);
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+
+        evict_oldest(dir.path(), 2);
+
+        assert!(!dir.path().join("0.json").exists());
+        assert!(dir.path().join("1.json").exists());
+        assert!(dir.path().join("2.json").exists());
+    }
+}
@@ -0,0 +1,223 @@
+//! Caps the number of in-flight requests to one provider. Parallel sub-agents
+//! and tool calls share the active provider, and without a cap a burst of
+//! them runs straight into the provider's rate limits.
+//!
+//! Requests over the limit wait for a permit instead of failing. Waiting is
+//! cancel-safe: dropping a queued request (e.g. when the user cancels) gives
+//! up its place immediately without ever taking a permit.
+
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::ai::tweaks::ModelTweaks;
+use crate::ai::{error::AiError, model::Model, provider::AiProvider, types::*};
+
+pub struct ConcurrencyLimitedProvider {
+    inner: Arc<dyn AiProvider>,
+    permits: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimitedProvider {
+    /// Wraps `inner` so at most `max_concurrent` conversations run at once.
+    /// A limit of 0 is treated as 1.
+    pub fn wrap(inner: Arc<dyn AiProvider>, max_concurrent: usize) -> Arc<dyn AiProvider> {
+        Arc::new(Self {
+            inner,
+            permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AiProvider for ConcurrencyLimitedProvider {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn supported_models(&self) -> HashSet<Model> {
+        self.inner.supported_models()
+    }
+
+    async fn converse(
+        &self,
+        request: ConversationRequest,
+    ) -> Result<ConversationResponse, AiError> {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| AiError::Terminal(e.into()))?;
+        self.inner.converse(request).await
+    }
+
+    fn get_cost(&self, model: &Model) -> Cost {
+        self.inner.get_cost(model)
+    }
+
+    fn model_version(&self, model: &Model) -> String {
+        self.inner.model_version(model)
+    }
+
+    fn context_window(&self, model: &Model) -> u32 {
+        self.inner.context_window(model)
+    }
+
+    fn temperature_range(&self, model: &Model) -> (f32, f32) {
+        self.inner.temperature_range(model)
+    }
+
+    async fn converse_stream(
+        &self,
+        request: ConversationRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamEvent, AiError>> + Send>>, AiError> {
+        let permit = self
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| AiError::Terminal(e.into()))?;
+        let stream = self.inner.converse_stream(request).await?;
+        // The response is still being generated while the stream is read, so
+        // the permit is held until the stream is dropped
+        Ok(Box::pin(stream.map(move |event| {
+            let _ = &permit;
+            event
+        })))
+    }
+
+    fn supports_image_generation(&self) -> bool {
+        self.inner.supports_image_generation()
+    }
+
+    async fn generate_image(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, AiError> {
+        self.inner.generate_image(request).await
+    }
+
+    async fn generate_image_with_progress(
+        &self,
+        request: ImageGenerationRequest,
+        progress: &(dyn Fn(ImageGenerationStatus) + Send + Sync),
+    ) -> Result<ImageGenerationResponse, AiError> {
+        self.inner
+            .generate_image_with_progress(request, progress)
+            .await
+    }
+
+    fn max_images_per_request(&self) -> u32 {
+        self.inner.max_images_per_request()
+    }
+
+    async fn generate_images(
+        &self,
+        request: ImageGenerationRequest,
+        count: u32,
+        progress: &(dyn Fn(ImageGenerationStatus) + Send + Sync),
+    ) -> Result<Vec<ImageGenerationResponse>, AiError> {
+        self.inner.generate_images(request, count, progress).await
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        self.inner.supports_embeddings()
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, AiError> {
+        self.inner.embed(texts).await
+    }
+
+    fn tweaks(&self) -> ModelTweaks {
+        self.inner.tweaks()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::mock::{MockBehavior, MockProvider};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Counts overlapping `converse` calls, each of which takes a while.
+    struct SlowProvider {
+        active: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl AiProvider for SlowProvider {
+        fn name(&self) -> &'static str {
+            "slow"
+        }
+
+        fn supported_models(&self) -> HashSet<Model> {
+            HashSet::from([Model::None])
+        }
+
+        async fn converse(
+            &self,
+            _request: ConversationRequest,
+        ) -> Result<ConversationResponse, AiError> {
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(active, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            Ok(ConversationResponse {
+                content: Content::text_only("done".to_string()),
+                usage: TokenUsage::new(1, 1),
+                stop_reason: StopReason::EndTurn,
+            })
+        }
+
+        fn get_cost(&self, _model: &Model) -> Cost {
+            Cost::new(0.0, 0.0, 0.0, 0.0)
+        }
+    }
+
+    fn request() -> ConversationRequest {
+        ConversationRequest {
+            messages: vec![Message::user("hi".to_string())],
+            model: Model::None.default_settings(),
+            system_prompt: String::new(),
+            stop_sequences: Vec::new(),
+            tools: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_over_the_limit_queue() {
+        let slow = Arc::new(SlowProvider {
+            active: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        });
+        let provider = ConcurrencyLimitedProvider::wrap(slow.clone(), 2);
+
+        let calls = (0..5).map(|_| provider.converse(request()));
+        let results = futures_util::future::join_all(calls).await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(slow.peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn open_stream_holds_its_permit_and_cancelled_waiters_leave_the_queue() {
+        let provider =
+            ConcurrencyLimitedProvider::wrap(Arc::new(MockProvider::new(MockBehavior::Success)), 1);
+
+        let stream = provider.converse_stream(request()).await.unwrap();
+        let queued =
+            tokio::time::timeout(Duration::from_millis(50), provider.converse(request())).await;
+        assert!(queued.is_err(), "second request should wait for the stream");
+
+        drop(stream);
+        let next = tokio::time::timeout(Duration::from_millis(50), provider.converse(request()))
+            .await
+            .expect("permit should be free once the stream is dropped");
+        assert!(next.is_ok());
+    }
+}
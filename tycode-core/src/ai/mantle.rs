@@ -341,16 +341,14 @@ fn build_request_body(
         body["tool_choice"] = json!("auto");
     }
 
-    // Max maps to "high": Grok 4.3 tops out at high, and unlike OpenRouter
+    // XHigh maps to "high": Grok 4.3 tops out at high, and unlike OpenRouter
     // there is no normalization layer to downgrade an unsupported "xhigh".
-    let effort = match request.model.reasoning_budget {
-        ReasoningBudget::Off => None,
-        ReasoningBudget::Low => Some("low"),
-        ReasoningBudget::Medium => Some("medium"),
-        ReasoningBudget::High | ReasoningBudget::Max => Some("high"),
-    };
+    let effort = request.model.effective_effort().map(|effort| match effort {
+        ReasoningEffort::XHigh => ReasoningEffort::High,
+        effort => effort,
+    });
     if let Some(effort) = effort {
-        body["reasoning"] = json!({ "effort": effort });
+        body["reasoning"] = json!({ "effort": effort.as_str() });
     }
 
     // GPT reasoning models reject sampling parameters; Grok accepts them.
@@ -701,6 +699,7 @@ mod tests {
                 temperature: Some(1.0),
                 top_p: None,
                 reasoning_budget: ReasoningBudget::High,
+                effort: None,
             },
             system_prompt: "You are a test agent.".to_string(),
             stop_sequences: vec![],
@@ -824,6 +823,18 @@ mod tests {
         assert_eq!(input[3]["output"], "ok");
     }
 
+    #[test]
+    fn explicit_reasoning_effort_overrides_budget() {
+        let mut request = request_with(vec![Message::user("hi".to_string())], vec![]);
+        request.model.effort = Some(ReasoningEffort::Low);
+        let body = build_request_body("openai.gpt-5.5", &request, false).unwrap();
+        assert_eq!(body["reasoning"]["effort"], "low");
+
+        request.model.effort = Some(ReasoningEffort::XHigh);
+        let body = build_request_body("openai.gpt-5.5", &request, false).unwrap();
+        assert_eq!(body["reasoning"]["effort"], "high");
+    }
+
     #[test]
     fn request_body_includes_sampling_for_xai_and_flattened_tools() {
         let messages = vec![Message::user(Content::text_only("hi".to_string()))];
@@ -55,6 +55,8 @@ fn validate_tool_use_results(messages: &[Message]) -> Result<(), AiError> {
     Ok(())
 }
 
+const MOCK_EMBEDDING_DIMS: usize = 64;
+
 /// Mock behavior for the mock provider
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -68,6 +70,8 @@ pub enum MockBehavior {
     AlwaysRetryableError,
     /// Always return a non-retryable error
     AlwaysNonRetryableError,
+    /// Always return a transient error
+    AlwaysTransientError,
     /// Return a tool use response
     ToolUse {
         tool_name: String,
@@ -120,6 +124,7 @@ pub struct MockProvider {
     call_count: Arc<Mutex<usize>>,
     captured_requests: Arc<Mutex<Vec<ConversationRequest>>>,
     image_gen_enabled: Arc<Mutex<bool>>,
+    embeddings_enabled: Arc<Mutex<bool>>,
 }
 
 impl MockProvider {
@@ -129,6 +134,7 @@ impl MockProvider {
             call_count: Arc::new(Mutex::new(0)),
             captured_requests: Arc::new(Mutex::new(Vec::new())),
             image_gen_enabled: Arc::new(Mutex::new(false)),
+            embeddings_enabled: Arc::new(Mutex::new(false)),
         }
     }
 
@@ -146,6 +152,10 @@ impl MockProvider {
         *self.image_gen_enabled.lock().unwrap() = enabled;
     }
 
+    pub fn set_embeddings_enabled(&self, enabled: bool) {
+        *self.embeddings_enabled.lock().unwrap() = enabled;
+    }
+
     pub fn set_behavior(&self, behavior: MockBehavior) {
         *self.behavior.lock().unwrap() = behavior;
     }
@@ -234,6 +244,9 @@ impl AiProvider for MockProvider {
             MockBehavior::AlwaysNonRetryableError => Err(AiError::Terminal(anyhow::anyhow!(
                 "Mock non-retryable error"
             ))),
+            MockBehavior::AlwaysTransientError => Err(AiError::Transient(anyhow::anyhow!(
+                "Mock transient error (always fails)"
+            ))),
             MockBehavior::ToolUse {
                 tool_name,
                 tool_arguments,
@@ -485,6 +498,50 @@ impl AiProvider for MockProvider {
             media_type: "image/png".to_string(),
         })
     }
+
+    /// Reports the queued/processing states a slow provider would.
+    async fn generate_image_with_progress(
+        &self,
+        request: ImageGenerationRequest,
+        progress: &(dyn Fn(ImageGenerationStatus) + Send + Sync),
+    ) -> Result<ImageGenerationResponse, AiError> {
+        if self.supports_image_generation() {
+            progress(ImageGenerationStatus::Queued);
+            progress(ImageGenerationStatus::Processing);
+        }
+        self.generate_image(request).await
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        *self.embeddings_enabled.lock().unwrap()
+    }
+
+    /// Bag-of-words vectors: each lowercase word is hashed into one of
+    /// `MOCK_EMBEDDING_DIMS` buckets, so texts sharing words score as similar.
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, AiError> {
+        if !self.supports_embeddings() {
+            return Err(AiError::Terminal(anyhow::anyhow!(
+                "Embeddings are not supported by this provider"
+            )));
+        }
+
+        Ok(texts
+            .iter()
+            .map(|text| {
+                let mut vector = vec![0.0; MOCK_EMBEDDING_DIMS];
+                for word in text
+                    .split(|c: char| !c.is_alphanumeric())
+                    .filter(|word| !word.is_empty())
+                {
+                    let bucket = word.to_lowercase().bytes().fold(0usize, |hash, b| {
+                        hash.wrapping_mul(31).wrapping_add(b as usize)
+                    });
+                    vector[bucket % MOCK_EMBEDDING_DIMS] += 1.0;
+                }
+                vector
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
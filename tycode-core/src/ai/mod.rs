@@ -1,4 +1,6 @@
 pub mod bedrock;
+pub mod cache;
+pub mod concurrency;
 pub mod error;
 pub mod json;
 pub mod mantle;
@@ -378,6 +378,7 @@ impl Model {
             temperature: Some(1.0),
             top_p: None,
             reasoning_budget: ReasoningBudget::High,
+            effort: None,
         }
     }
 
@@ -12,6 +12,11 @@ use tracing::{debug, info};
 use crate::ai::model::Model;
 use crate::ai::{error::AiError, provider::AiProvider, types::*};
 
+/// OpenAI embeddings served through OpenRouter's OpenAI-compatible API.
+const EMBEDDING_MODEL: &str = "openai/text-embedding-3-small";
+/// Inputs per embeddings request; OpenAI rejects more than 2048.
+const EMBEDDING_BATCH_SIZE: usize = 256;
+
 #[derive(Clone)]
 pub struct OpenRouterProvider {
     client: Client,
@@ -436,6 +441,61 @@ impl AiProvider for OpenRouterProvider {
         })
     }
 
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, AiError> {
+        let url = format!("{}/embeddings", self.base_url);
+        let mut vectors = Vec::with_capacity(texts.len());
+
+        for batch in texts.chunks(EMBEDDING_BATCH_SIZE) {
+            let response = self
+                .client
+                .post(&url)
+                .bearer_auth(&self.api_key)
+                .json(&serde_json::json!({ "model": EMBEDDING_MODEL, "input": batch }))
+                .send()
+                .await
+                .map_err(|e| {
+                    AiError::Transient(anyhow::anyhow!("Embeddings request failed: {e:?}"))
+                })?;
+
+            let status = response.status();
+            let response_text = response.text().await.map_err(|e| {
+                AiError::Transient(anyhow::anyhow!("Failed to read embeddings response: {e:?}"))
+            })?;
+            if status.as_u16() == 429 || status.is_server_error() {
+                return Err(AiError::Retryable(anyhow::anyhow!(
+                    "Embeddings failed with status {status}: {response_text}"
+                )));
+            }
+            if !status.is_success() {
+                return Err(AiError::Terminal(anyhow::anyhow!(
+                    "Embeddings failed with status {status}: {response_text}"
+                )));
+            }
+
+            let mut parsed: EmbeddingsResponse =
+                serde_json::from_str(&response_text).map_err(|e| {
+                    AiError::Terminal(anyhow::anyhow!(
+                        "Failed to parse embeddings response: {e:?}"
+                    ))
+                })?;
+            if parsed.data.len() != batch.len() {
+                return Err(AiError::Terminal(anyhow::anyhow!(
+                    "Expected {} embeddings, got {}",
+                    batch.len(),
+                    parsed.data.len()
+                )));
+            }
+            parsed.data.sort_by_key(|item| item.index);
+            vectors.extend(parsed.data.into_iter().map(|item| item.embedding));
+        }
+
+        Ok(vectors)
+    }
+
     async fn converse(
         &self,
         request: ConversationRequest,
@@ -474,18 +534,12 @@ impl AiProvider for OpenRouterProvider {
             } else {
                 None
             },
-            reasoning: match request.model.reasoning_budget {
-                ReasoningBudget::Off => None,
-                _ => Some(ReasoningConfig {
-                    effort: Some(match request.model.reasoning_budget {
-                        ReasoningBudget::Low => ReasoningEffort::Low,
-                        ReasoningBudget::Medium => ReasoningEffort::Medium,
-                        ReasoningBudget::High => ReasoningEffort::High,
-                        ReasoningBudget::Max => ReasoningEffort::XHigh,
-                        ReasoningBudget::Off => unreachable!(),
-                    }),
+            reasoning: request
+                .model
+                .effective_effort()
+                .map(|effort| ReasoningConfig {
+                    effort: Some(effort),
                 }),
-            },
             usage: Some(UsageConfig { include: true }),
         };
 
@@ -634,18 +688,12 @@ impl AiProvider for OpenRouterProvider {
             } else {
                 None
             },
-            reasoning: match request.model.reasoning_budget {
-                ReasoningBudget::Off => None,
-                _ => Some(ReasoningConfig {
-                    effort: Some(match request.model.reasoning_budget {
-                        ReasoningBudget::Low => ReasoningEffort::Low,
-                        ReasoningBudget::Medium => ReasoningEffort::Medium,
-                        ReasoningBudget::High => ReasoningEffort::High,
-                        ReasoningBudget::Max => ReasoningEffort::XHigh,
-                        ReasoningBudget::Off => unreachable!(),
-                    }),
+            reasoning: request
+                .model
+                .effective_effort()
+                .map(|effort| ReasoningConfig {
+                    effort: Some(effort),
                 }),
-            },
             usage: Some(UsageConfig { include: true }),
         };
 
@@ -718,6 +766,10 @@ impl AiProvider for OpenRouterProvider {
             .map(|resolved| resolved.context_window)
             .unwrap_or_else(|| model.context_window())
     }
+
+    fn temperature_range(&self, _model: &Model) -> (f32, f32) {
+        (0.0, 2.0)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -840,20 +892,22 @@ struct ReasoningConfig {
     pub effort: Option<ReasoningEffort>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-enum ReasoningEffort {
-    Low,
-    Medium,
-    High,
-    XHigh,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 struct UsageConfig {
     pub include: bool,
 }
 
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingItem {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct OpenRouterResponse {
     pub id: String,
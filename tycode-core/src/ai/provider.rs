@@ -29,6 +29,11 @@ pub trait AiProvider: Send + Sync {
         model.context_window()
     }
 
+    /// Inclusive range of temperatures the provider accepts for the model.
+    fn temperature_range(&self, _model: &Model) -> (f32, f32) {
+        (0.0, 1.0)
+    }
+
     async fn converse_stream(
         &self,
         request: ConversationRequest,
@@ -52,6 +57,53 @@ pub trait AiProvider: Send + Sync {
         )))
     }
 
+    /// `generate_image`, reporting status changes to `progress` along the
+    /// way. Providers that cannot report progress keep this default.
+    async fn generate_image_with_progress(
+        &self,
+        request: ImageGenerationRequest,
+        _progress: &(dyn Fn(ImageGenerationStatus) + Send + Sync),
+    ) -> Result<ImageGenerationResponse, AiError> {
+        self.generate_image(request).await
+    }
+
+    /// Most variants one `generate_images` call may ask for.
+    fn max_images_per_request(&self) -> u32 {
+        1
+    }
+
+    /// Generates `count` variants of one prompt. Callers keep `count` within
+    /// `max_images_per_request`; providers that cannot batch keep this
+    /// default, which makes one request per image.
+    async fn generate_images(
+        &self,
+        request: ImageGenerationRequest,
+        count: u32,
+        progress: &(dyn Fn(ImageGenerationStatus) + Send + Sync),
+    ) -> Result<Vec<ImageGenerationResponse>, AiError> {
+        let mut images = Vec::new();
+        for _ in 0..count {
+            images.push(
+                self.generate_image_with_progress(request.clone(), progress)
+                    .await?,
+            );
+        }
+        Ok(images)
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        false
+    }
+
+    /// Embeds each of `texts`, returning one vector per input in order.
+    /// Implementations split the input into requests that fit the
+    /// provider's limits, so callers may pass any number of texts.
+    async fn embed(&self, _texts: Vec<String>) -> Result<Vec<Vec<f32>>, AiError> {
+        Err(AiError::Terminal(anyhow::anyhow!(
+            "Embeddings are not supported by this provider"
+        )))
+    }
+
     fn tweaks(&self) -> ModelTweaks {
         ModelTweaks::default()
     }
@@ -13,6 +13,7 @@ pub async fn test_hello_world_model<P: AiProvider>(provider: &P, model: Model) -
             temperature: Some(0.7),
             top_p: None,
             reasoning_budget: ReasoningBudget::Off,
+            effort: None,
         },
         system_prompt: "You are a helpful AI assistant.".to_string(),
         stop_sequences: Vec::new(),
@@ -116,6 +117,7 @@ pub async fn test_reasoning_conversation<P: AiProvider>(provider: P) -> Result<(
                 temperature: Some(1.0),
                 top_p: None,
                 reasoning_budget: ReasoningBudget::High,
+                effort: None,
             },
             system_prompt:
                 "You are a helpful AI assistant. Think step by step and provide clear explanations."
@@ -202,6 +204,7 @@ pub async fn test_reasoning_conversation<P: AiProvider>(provider: P) -> Result<(
                 temperature: Some(1.0),
                 top_p: None,
                 reasoning_budget: ReasoningBudget::High,
+                effort: None,
             },
             system_prompt:
                 "You are a helpful AI assistant. Think step by step and provide clear explanations."
@@ -323,6 +326,7 @@ pub async fn test_tool_usage<P: AiProvider>(provider: P) -> Result<()> {
                 temperature: Some(0.1),
                 top_p: None,
                 reasoning_budget: ReasoningBudget::Off,
+                effort: None,
             },
             system_prompt: "You are a helpful AI assistant. When asked to perform calculations, use the calculator tool provided.".to_string(),
             stop_sequences: Vec::new(),
@@ -450,6 +454,7 @@ pub async fn test_reasoning_with_tools<P: AiProvider>(provider: P) -> Result<()>
                 temperature: Some(1.0),
                 top_p: None,
                 reasoning_budget: ReasoningBudget::Low,
+                effort: None,
             },
             system_prompt: "You are a helpful AI assistant. Think step by step when solving problems and use the calculator tool when you need to perform arithmetic calculations.".to_string(),
             stop_sequences: Vec::new(),
@@ -614,6 +619,7 @@ pub async fn test_multiple_tool_calls<P: AiProvider>(provider: P) -> Result<()>
                 temperature: Some(0.1),
                 top_p: None,
                 reasoning_budget: ReasoningBudget::Off,
+                effort: None,
             },
             system_prompt: "You are a helpful AI assistant. When asked to perform calculations, use the calculator tool provided.".to_string(),
             stop_sequences: Vec::new(),
@@ -703,6 +709,7 @@ pub async fn test_multiple_tool_calls<P: AiProvider>(provider: P) -> Result<()>
                 temperature: Some(0.1),
                 top_p: None,
                 reasoning_budget: ReasoningBudget::Off,
+                effort: None,
             },
             system_prompt: "You are a helpful AI assistant. When asked to perform calculations, use the calculator tool provided.".to_string(),
             stop_sequences: Vec::new(),
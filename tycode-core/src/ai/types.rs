@@ -44,6 +44,17 @@ impl ReasoningBudget {
         }
     }
 
+    /// The effort level this budget maps to when no explicit effort is set.
+    pub fn to_effort(&self) -> Option<ReasoningEffort> {
+        match self {
+            ReasoningBudget::Off => None,
+            ReasoningBudget::Low => Some(ReasoningEffort::Low),
+            ReasoningBudget::Medium => Some(ReasoningEffort::Medium),
+            ReasoningBudget::High => Some(ReasoningEffort::High),
+            ReasoningBudget::Max => Some(ReasoningEffort::XHigh),
+        }
+    }
+
     pub fn from_u32(value: u32) -> Self {
         if value == 0 {
             ReasoningBudget::Off
@@ -59,6 +70,69 @@ impl ReasoningBudget {
     }
 }
 
+/// OpenAI-style reasoning effort, for providers that take an effort level
+/// instead of a token budget.
+///
+/// When `ModelSettings::effort` is unset, the effort is derived
+/// from the reasoning budget: Off sends no effort, Low/Medium/High map to the
+/// same effort, and Max maps to XHigh. Each provider then applies it as
+/// follows:
+/// - OpenRouter: sent as `reasoning.effort` as-is.
+/// - Bedrock Mantle (GPT, Grok): sent as `reasoning.effort`, with XHigh
+///   capped to high.
+/// - Bedrock Claude with adaptive thinking: Minimal is sent as low and XHigh
+///   as the model's top effort. Claude models with a token budget ignore it
+///   and use the reasoning budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Minimal,
+    Low,
+    Medium,
+    High,
+    XHigh,
+}
+
+impl ReasoningEffort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasoningEffort::Minimal => "minimal",
+            ReasoningEffort::Low => "low",
+            ReasoningEffort::Medium => "medium",
+            ReasoningEffort::High => "high",
+            ReasoningEffort::XHigh => "xhigh",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "minimal" => Some(ReasoningEffort::Minimal),
+            "low" => Some(ReasoningEffort::Low),
+            "medium" => Some(ReasoningEffort::Medium),
+            "high" => Some(ReasoningEffort::High),
+            "xhigh" => Some(ReasoningEffort::XHigh),
+            _ => None,
+        }
+    }
+
+    /// The reasoning budget closest to this effort, for providers that only
+    /// understand budgets.
+    pub fn to_budget(self) -> ReasoningBudget {
+        match self {
+            ReasoningEffort::Minimal | ReasoningEffort::Low => ReasoningBudget::Low,
+            ReasoningEffort::Medium => ReasoningBudget::Medium,
+            ReasoningEffort::High => ReasoningBudget::High,
+            ReasoningEffort::XHigh => ReasoningBudget::Max,
+        }
+    }
+}
+
+impl std::fmt::Display for ReasoningEffort {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl std::fmt::Display for ReasoningBudget {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -78,6 +152,19 @@ pub struct ModelSettings {
     pub temperature: Option<f32>,
     pub top_p: Option<f32>,
     pub reasoning_budget: ReasoningBudget,
+    /// Explicit effort level for providers that take one, set with
+    /// `/model reasoning_effort=...`; overrides the effort derived from
+    /// `reasoning_budget`. See `ReasoningEffort`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffort>,
+}
+
+impl ModelSettings {
+    /// The effort to send to effort-based providers: the explicit
+    /// `effort`, or else the one derived from the budget.
+    pub fn effective_effort(&self) -> Option<ReasoningEffort> {
+        self.effort.or_else(|| self.reasoning_budget.to_effort())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -297,10 +384,14 @@ pub struct ModelConfig {
 /// Breakdown of context usage by category.
 /// Byte sizes are measured before sending; actual input_tokens come from the API response.
 /// Per-category token estimates are derived by applying byte proportions to actual input_tokens.
+/// `estimated_tokens` is the pre-send estimate for the whole request, available before the
+/// provider reports usage.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextBreakdown {
     pub context_window: u32,
     pub input_tokens: u32,
+    #[serde(default)]
+    pub estimated_tokens: u32,
     pub system_prompt_bytes: usize,
     pub tool_io_bytes: usize,
     pub conversation_history_bytes: usize,
@@ -316,7 +407,7 @@ impl ContextBreakdown {
         (self.input_tokens as f64 / self.context_window as f64) * 100.0
     }
 
-    fn total_bytes(&self) -> usize {
+    pub fn total_bytes(&self) -> usize {
         self.system_prompt_bytes
             + self.tool_io_bytes
             + self.conversation_history_bytes
@@ -353,7 +444,7 @@ impl ContextBreakdown {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationResponse {
     pub content: Content,
     pub usage: TokenUsage,
@@ -379,7 +470,7 @@ pub struct TokenUsage {
     pub reasoning_tokens: Option<u32>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum StopReason {
     EndTurn,
     MaxTokens,
@@ -440,6 +531,19 @@ pub struct ImageGenerationResponse {
     pub media_type: String,
 }
 
+/// Where an image generation request is. Providers that report progress
+/// send `Queued` and `Processing`; `Started`, `Done` and `Failed` are sent
+/// by the tool for every provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ImageGenerationStatus {
+    Started,
+    Queued,
+    Processing,
+    Done,
+    Failed { error: String },
+}
+
 #[derive(Debug)]
 pub enum StreamEvent {
     TextDelta { text: String },
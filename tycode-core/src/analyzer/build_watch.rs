@@ -0,0 +1,255 @@
+//! Build watch: after a turn that edited files, re-run the build and feed any
+//! remaining errors back to the agent as its next message, so it keeps fixing
+//! until the build is clean instead of stopping at the first attempt.
+//!
+//! The loop is bounded by an iteration cap, an optional cost ceiling for the
+//! turn, and a repeat limit that stops when the same errors keep coming back.
+
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::analyzer::rust_analyzer::RustAnalyzer;
+use crate::analyzer::TypeAnalyzer;
+use crate::chat::events::BuildWatchStatus;
+
+/// Errors included in a feedback message; the rest are summarized as a count.
+const MAX_FEEDBACK_ERRORS: usize = 20;
+
+fn default_max_iterations() -> u32 {
+    5
+}
+
+fn default_max_repeats() -> u32 {
+    3
+}
+
+/// Build watch settings.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(title = "Build Watch")]
+pub struct BuildWatchConfig {
+    /// Re-run the build after the agent edits files and feed errors back
+    /// until it is clean.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Maximum number of times errors are fed back in a single turn.
+    #[serde(default = "default_max_iterations")]
+    #[schemars(default = "default_max_iterations")]
+    pub max_iterations: u32,
+
+    /// Stop feeding errors back once the turn has cost this much (USD).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost: Option<f64>,
+
+    /// Stop when the build reports the same errors this many times in a row.
+    #[serde(default = "default_max_repeats")]
+    #[schemars(default = "default_max_repeats")]
+    pub max_repeats: u32,
+}
+
+impl BuildWatchConfig {
+    pub const NAMESPACE: &'static str = "build_watch";
+}
+
+impl Default for BuildWatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_iterations: default_max_iterations(),
+            max_cost: None,
+            max_repeats: default_max_repeats(),
+        }
+    }
+}
+
+/// Per-turn state of the watch loop.
+#[derive(Debug)]
+pub struct BuildWatch {
+    start_cost: f64,
+    edits_checked: usize,
+    iteration: u32,
+    last_errors: Vec<String>,
+    repeats: u32,
+}
+
+impl BuildWatch {
+    pub fn new(start_cost: f64) -> Self {
+        Self {
+            start_cost,
+            edits_checked: 0,
+            iteration: 0,
+            last_errors: Vec::new(),
+            repeats: 0,
+        }
+    }
+
+    /// Whether files were edited since the last check, given the turn's
+    /// running count of successful edits.
+    pub fn has_new_edits(&mut self, edits: usize) -> bool {
+        let new_edits = edits > self.edits_checked;
+        self.edits_checked = edits;
+        new_edits
+    }
+
+    /// Feedback rounds sent so far this turn.
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    /// Records a build's errors and decides whether to feed them back.
+    /// `Retrying` counts as a new iteration.
+    pub fn check(
+        &mut self,
+        config: &BuildWatchConfig,
+        errors: &[String],
+        session_cost: f64,
+    ) -> BuildWatchStatus {
+        if errors.is_empty() {
+            return BuildWatchStatus::Clean;
+        }
+
+        if errors == self.last_errors.as_slice() {
+            self.repeats += 1;
+        } else {
+            self.last_errors = errors.to_vec();
+            self.repeats = 1;
+        }
+
+        if self.repeats >= config.max_repeats {
+            return BuildWatchStatus::NoProgress;
+        }
+        if self.iteration >= config.max_iterations {
+            return BuildWatchStatus::IterationLimit;
+        }
+        if config
+            .max_cost
+            .is_some_and(|max_cost| session_cost - self.start_cost >= max_cost)
+        {
+            return BuildWatchStatus::CostLimit;
+        }
+
+        self.iteration += 1;
+        BuildWatchStatus::Retrying
+    }
+}
+
+/// Builds every workspace root that has a `Cargo.toml` and returns the
+/// errors. Roots whose build cannot run are skipped.
+pub async fn build_errors(workspace_roots: &[PathBuf]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for root in workspace_roots {
+        if !root.join("Cargo.toml").is_file() {
+            continue;
+        }
+        match RustAnalyzer::new(root.clone()).get_build_status().await {
+            Ok(status) => errors.extend(status.errors),
+            Err(e) => tracing::warn!(?e, root = %root.display(), "Build watch failed to build"),
+        }
+    }
+    errors
+}
+
+/// The message sent to the agent for a `Retrying` check.
+pub fn feedback_message(errors: &[String], iteration: u32, max_iterations: u32) -> String {
+    let mut message = format!(
+        "The build still has {} error(s) after your changes (build watch {iteration}/{max_iterations}). Fix them before finishing:\n",
+        errors.len()
+    );
+    for error in errors.iter().take(MAX_FEEDBACK_ERRORS) {
+        message.push_str(&format!("  - {error}\n"));
+    }
+    if errors.len() > MAX_FEEDBACK_ERRORS {
+        message.push_str(&format!(
+            "  ... and {} more\n",
+            errors.len() - MAX_FEEDBACK_ERRORS
+        ));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(messages: &[&str]) -> Vec<String> {
+        messages.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn clean_build_stops_the_loop() {
+        let mut watch = BuildWatch::new(0.0);
+        let config = BuildWatchConfig::default();
+        assert_eq!(
+            watch.check(&config, &errors(&["a"]), 0.0),
+            BuildWatchStatus::Retrying
+        );
+        assert_eq!(watch.check(&config, &[], 0.0), BuildWatchStatus::Clean);
+        assert_eq!(watch.iteration(), 1);
+    }
+
+    #[test]
+    fn stops_at_iteration_limit() {
+        let mut watch = BuildWatch::new(0.0);
+        let config = BuildWatchConfig {
+            max_iterations: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            watch.check(&config, &errors(&["a"]), 0.0),
+            BuildWatchStatus::Retrying
+        );
+        assert_eq!(
+            watch.check(&config, &errors(&["b"]), 0.0),
+            BuildWatchStatus::Retrying
+        );
+        assert_eq!(
+            watch.check(&config, &errors(&["c"]), 0.0),
+            BuildWatchStatus::IterationLimit
+        );
+    }
+
+    #[test]
+    fn stops_when_errors_repeat() {
+        let mut watch = BuildWatch::new(0.0);
+        let config = BuildWatchConfig {
+            max_repeats: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            watch.check(&config, &errors(&["a"]), 0.0),
+            BuildWatchStatus::Retrying
+        );
+        assert_eq!(
+            watch.check(&config, &errors(&["a"]), 0.0),
+            BuildWatchStatus::NoProgress
+        );
+    }
+
+    #[test]
+    fn stops_at_cost_ceiling_for_the_turn() {
+        let mut watch = BuildWatch::new(1.0);
+        let config = BuildWatchConfig {
+            max_cost: Some(0.5),
+            ..Default::default()
+        };
+        assert_eq!(
+            watch.check(&config, &errors(&["a"]), 1.2),
+            BuildWatchStatus::Retrying
+        );
+        assert_eq!(
+            watch.check(&config, &errors(&["b"]), 1.5),
+            BuildWatchStatus::CostLimit
+        );
+    }
+
+    #[test]
+    fn only_new_edits_trigger_a_check() {
+        let mut watch = BuildWatch::new(0.0);
+        assert!(!watch.has_new_edits(0));
+        assert!(watch.has_new_edits(2));
+        assert!(!watch.has_new_edits(2));
+        assert!(watch.has_new_edits(3));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::analyzer::rust_analyzer::RustAnalyzer;
+use crate::analyzer::{SupportedLanguage, TypeAnalyzer};
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::workspace::WorkspacePaths;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+use crate::tools::ToolName;
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 200;
+
+pub struct FindReferencesTool {
+    workspace_paths: WorkspacePaths,
+}
+
+impl FindReferencesTool {
+    pub fn new(workspace_paths: WorkspacePaths) -> Self {
+        Self { workspace_paths }
+    }
+
+    pub fn tool_name() -> ToolName {
+        ToolName::new("find_references")
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for FindReferencesTool {
+    fn name(&self) -> String {
+        "find_references".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Find every use of a symbol in the workspace's source (call sites, type uses, imports, macro arguments), skipping comments and strings. Returns file, line and the line's text for each reference, paginated with offset/limit. Use this before renaming or changing a signature instead of grepping. Matching is by name, so same-named items on other types are included.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "language": {
+                    "type": "string",
+                    "description": "The programming language to analyze",
+                    "enum": SupportedLanguage::all()
+                },
+                "workspace_root": {
+                    "type": "string",
+                    "description": "Absolute workspace root to search in"
+                },
+                "symbol": {
+                    "type": "string",
+                    "description": "Name to find, e.g. \"parse_config\" or \"Config::load\" (matched by its last segment)"
+                },
+                "offset": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Number of references to skip, for paging through large results"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT,
+                    "description": format!("References to return (default {DEFAULT_LIMIT})")
+                },
+            },
+            "required": ["language", "workspace_root", "symbol"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Read
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let Some(language_str) = request.arguments["language"].as_str() else {
+            bail!("Missing required argument \"language\"");
+        };
+        let Some(language) = SupportedLanguage::from_str(language_str) else {
+            bail!(
+                "Unsupported language \"{}\". Supported: {:?}",
+                language_str,
+                SupportedLanguage::all()
+            );
+        };
+        let Some(workspace_root_str) = request.arguments["workspace_root"].as_str() else {
+            bail!("Missing required argument \"workspace_root\"");
+        };
+        let Some(symbol) = request.arguments["symbol"].as_str() else {
+            bail!("Missing required argument \"symbol\"");
+        };
+        let offset = request.arguments["offset"].as_u64().unwrap_or(0) as usize;
+        let limit = request.arguments["limit"]
+            .as_u64()
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+
+        let workspace_root = self.workspace_paths.resolve_root(workspace_root_str)?;
+
+        match language {
+            SupportedLanguage::Rust => Ok(Box::new(FindReferencesHandle {
+                workspace_root,
+                symbol: symbol.to_string(),
+                offset,
+                limit,
+                arguments: request.arguments.clone(),
+                tool_use_id: request.tool_use_id.clone(),
+            })),
+        }
+    }
+}
+
+struct FindReferencesHandle {
+    workspace_root: PathBuf,
+    symbol: String,
+    offset: usize,
+    limit: usize,
+    arguments: Value,
+    tool_use_id: String,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for FindReferencesHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "find_references".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: self.arguments.clone(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let mut analyzer = RustAnalyzer::new(self.workspace_root.clone());
+
+        let locations = match analyzer.find_references(&self.symbol).await {
+            Ok(locations) => locations,
+            Err(e) => {
+                return ToolOutput::Result {
+                    content: format!("Failed to find references: {e:?}"),
+                    is_error: true,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::Error {
+                        short_message: "Find references failed".to_string(),
+                        detailed_message: format!("Failed to find references: {e:?}"),
+                        category: None,
+                    },
+                }
+            }
+        };
+
+        let total = locations.len();
+        let mut sources: HashMap<&PathBuf, String> = HashMap::new();
+        let page: Vec<Value> = locations
+            .iter()
+            .skip(self.offset)
+            .take(self.limit)
+            .map(|location| {
+                let source = sources
+                    .entry(&location.path)
+                    .or_insert_with(|| std::fs::read_to_string(&location.path).unwrap_or_default());
+                let text = source
+                    .lines()
+                    .nth(location.line - 1)
+                    .map(|line| line.trim().to_string())
+                    .unwrap_or_default();
+                json!({
+                    "file_path": location.path.display().to_string(),
+                    "line": location.line,
+                    "column": location.column,
+                    "text": text,
+                })
+            })
+            .collect();
+        let next_offset = (self.offset + page.len() < total).then(|| self.offset + page.len());
+
+        let content = json!({
+            "symbol": self.symbol,
+            "total": total,
+            "offset": self.offset,
+            "references": page,
+            "next_offset": next_offset,
+        });
+        ToolOutput::Result {
+            content: content.to_string(),
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Other { result: content },
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::analyzer::rust_analyzer::RustAnalyzer;
+use crate::analyzer::{SupportedLanguage, TypeAnalyzer};
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::workspace::WorkspacePaths;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+use crate::tools::ToolName;
+use anyhow::{anyhow, bail, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+pub struct GetDefinitionTool {
+    workspace_paths: WorkspacePaths,
+}
+
+impl GetDefinitionTool {
+    pub fn new(workspace_paths: WorkspacePaths) -> Self {
+        Self { workspace_paths }
+    }
+
+    pub fn tool_name() -> ToolName {
+        ToolName::new("get_definition")
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for GetDefinitionTool {
+    fn name(&self) -> String {
+        "get_definition".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Get the exact source of one definition (function, struct, enum, trait, method, ...) in a file, with its doc comments, file path and line range. Use this to see a signature or implementation without reading the whole file; use get_type_docs for an outline of a type and its impls.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "language": {
+                    "type": "string",
+                    "description": "The programming language to analyze",
+                    "enum": SupportedLanguage::all()
+                },
+                "file_path": {
+                    "type": "string",
+                    "description": "Absolute path of the file containing the definition"
+                },
+                "symbol": {
+                    "type": "string",
+                    "description": "Name of the definition: \"name\", \"module::name\" for inline modules, or \"Type::method\" for methods and associated items"
+                },
+            },
+            "required": ["language", "file_path", "symbol"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Read
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let Some(language_str) = request.arguments["language"].as_str() else {
+            bail!("Missing required argument \"language\"");
+        };
+        let Some(language) = SupportedLanguage::from_str(language_str) else {
+            bail!(
+                "Unsupported language \"{}\". Supported: {:?}",
+                language_str,
+                SupportedLanguage::all()
+            );
+        };
+        let Some(file_path) = request.arguments["file_path"].as_str() else {
+            bail!("Missing required argument \"file_path\"");
+        };
+        let Some(symbol) = request.arguments["symbol"].as_str() else {
+            bail!("Missing required argument \"symbol\"");
+        };
+
+        let file = self.workspace_paths.resolve(file_path)?;
+        if !file.is_file() {
+            bail!("File not found: {file_path}");
+        }
+        let workspace_root = self
+            .workspace_paths
+            .roots()
+            .into_iter()
+            .find(|root| file.starts_with(root))
+            .ok_or_else(|| anyhow!("{file_path} is not inside a workspace root"))?;
+
+        match language {
+            SupportedLanguage::Rust => Ok(Box::new(GetDefinitionHandle {
+                workspace_root,
+                file,
+                symbol: symbol.to_string(),
+                arguments: request.arguments.clone(),
+                tool_use_id: request.tool_use_id.clone(),
+            })),
+        }
+    }
+}
+
+struct GetDefinitionHandle {
+    workspace_root: PathBuf,
+    file: PathBuf,
+    symbol: String,
+    arguments: Value,
+    tool_use_id: String,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for GetDefinitionHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "get_definition".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: self.arguments.clone(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let mut analyzer = RustAnalyzer::new(self.workspace_root.clone());
+
+        match analyzer.get_definition(&self.file, &self.symbol).await {
+            Ok(definition) => {
+                let content = json!({
+                    "symbol": self.symbol,
+                    "file_path": definition.path.display().to_string(),
+                    "start_line": definition.start_line,
+                    "end_line": definition.end_line,
+                    "source": definition.source,
+                });
+                ToolOutput::Result {
+                    content: content.to_string(),
+                    is_error: false,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::Other { result: content },
+                }
+            }
+            Err(e) => ToolOutput::Result {
+                content: format!("Failed to get definition: {e:?}"),
+                is_error: true,
+                continuation: ContinuationPreference::Continue,
+                ui_result: ToolExecutionResult::Error {
+                    short_message: "Get definition failed".to_string(),
+                    detailed_message: format!("Failed to get definition: {e:?}"),
+                    category: None,
+                },
+            },
+        }
+    }
+}
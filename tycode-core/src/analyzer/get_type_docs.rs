@@ -57,7 +57,7 @@ impl ToolExecutor for GetTypeDocsTool {
     }
 
     fn category(&self) -> ToolCategory {
-        ToolCategory::Execution
+        ToolCategory::Read
     }
 
     async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
@@ -143,6 +143,7 @@ impl ToolCallHandle for GetTypeDocsHandle {
                 ui_result: ToolExecutionResult::Error {
                     short_message: "Get docs failed".to_string(),
                     detailed_message: format!("Failed to get type docs: {e:?}"),
+                    category: None,
                 },
             },
         }
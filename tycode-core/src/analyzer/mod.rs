@@ -1,12 +1,19 @@
+pub mod build_watch;
+pub mod find_references;
+pub mod get_definition;
 pub mod get_type_docs;
 pub mod rust_analyzer;
 pub mod search_types;
 
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+use serde::Serialize;
 use tokio::sync::Mutex;
 
 use crate::file::workspace::WorkspacePaths;
@@ -15,19 +22,100 @@ use crate::module::PromptComponent;
 use crate::module::{Module, SessionStateComponent};
 use crate::tools::r#trait::SharedTool;
 
+use build_watch::BuildWatchConfig;
+use find_references::FindReferencesTool;
+use get_definition::GetDefinitionTool;
 use get_type_docs::GetTypeDocsTool;
 use search_types::SearchTypesTool;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One compiler error or warning. `file` is absolute, and `line`/`column`
+/// are 1-based; all three are unset for messages without a source location
+/// (e.g. "aborting due to previous error").
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub file: Option<PathBuf>,
+    pub line: u32,
+    pub column: u32,
+    pub message: String,
+    pub severity: Severity,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(
+                f,
+                "{}:{}:{}: {}",
+                file.display(),
+                self.line,
+                self.column,
+                self.message
+            ),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// Result of a build check. `errors` and `warnings` are the display form of
+/// `diagnostics`, split by severity.
 #[derive(Debug, Clone)]
 pub struct BuildStatus {
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl BuildStatus {
+    pub fn from_diagnostics(diagnostics: Vec<Diagnostic>) -> Self {
+        let render = |severity: Severity| {
+            diagnostics
+                .iter()
+                .filter(|d| d.severity == severity)
+                .map(ToString::to_string)
+                .collect()
+        };
+        Self {
+            errors: render(Severity::Error),
+            warnings: render(Severity::Warning),
+            diagnostics,
+        }
+    }
+}
+
+/// Source of a single definition. Lines are 1-based and inclusive, and
+/// cover the item's doc comments and attributes.
+#[derive(Debug, Clone)]
+pub struct Definition {
+    pub path: PathBuf,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub source: String,
+}
+
+/// A position in a source file. Line and column are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
 }
 
 #[async_trait]
 pub trait TypeAnalyzer: Send {
     async fn search_types_by_name(&mut self, type_name: &str) -> Result<Vec<String>>;
     async fn get_type_docs(&mut self, type_path: &str) -> Result<String>;
+    /// Finds the definition of `symbol` (`name`, `module::name` or
+    /// `Type::member`) in `file`.
+    async fn get_definition(&mut self, file: &Path, symbol: &str) -> Result<Definition>;
+    /// Every use of `symbol` in the workspace, sorted by file and position.
+    async fn find_references(&mut self, symbol: &str) -> Result<Vec<Location>>;
     async fn get_build_status(&mut self) -> Result<BuildStatus>;
 }
 
@@ -53,6 +141,16 @@ impl SharedTypeAnalyzer {
         analyzer.get_type_docs(type_path).await
     }
 
+    pub async fn get_definition(&self, file: &Path, symbol: &str) -> anyhow::Result<Definition> {
+        let mut analyzer = self.inner.lock().await;
+        analyzer.get_definition(file, symbol).await
+    }
+
+    pub async fn find_references(&self, symbol: &str) -> anyhow::Result<Vec<Location>> {
+        let mut analyzer = self.inner.lock().await;
+        analyzer.find_references(symbol).await
+    }
+
     pub async fn get_build_status(&self) -> anyhow::Result<BuildStatus> {
         let mut analyzer = self.inner.lock().await;
         analyzer.get_build_status().await
@@ -103,10 +201,20 @@ impl Module for AnalyzerModule {
         vec![
             Arc::new(SearchTypesTool::new(self.workspace_paths.clone())),
             Arc::new(GetTypeDocsTool::new(self.workspace_paths.clone())),
+            Arc::new(GetDefinitionTool::new(self.workspace_paths.clone())),
+            Arc::new(FindReferencesTool::new(self.workspace_paths.clone())),
         ]
     }
 
     fn session_state(&self) -> Option<Arc<dyn SessionStateComponent>> {
         None
     }
+
+    fn settings_namespace(&self) -> Option<&'static str> {
+        Some(BuildWatchConfig::NAMESPACE)
+    }
+
+    fn settings_json_schema(&self) -> Option<RootSchema> {
+        Some(schema_for!(BuildWatchConfig))
+    }
 }
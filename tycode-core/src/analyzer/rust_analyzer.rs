@@ -1,15 +1,19 @@
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
 use cargo_metadata::MetadataCommand;
+use ignore::WalkBuilder;
+use proc_macro2::{TokenStream, TokenTree};
 use serde::Deserialize;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use syn::{Item, ItemImpl, ItemTrait};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{ImplItem, Item, ItemImpl, ItemTrait, TraitItem};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 
-use super::{BuildStatus, TypeAnalyzer};
+use super::{BuildStatus, Definition, Diagnostic, Location, Severity, TypeAnalyzer};
 
 fn get_host_platform() -> Result<String> {
     let output = std::process::Command::new("rustc")
@@ -125,6 +129,49 @@ impl TypeAnalyzer for RustAnalyzer {
         Ok(code_outline)
     }
 
+    async fn get_definition(&mut self, file: &Path, symbol: &str) -> Result<Definition> {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("failed to read {}", file.display()))?;
+        let parsed = syn::parse_file(&content).context("failed to parse source file")?;
+
+        let path: Vec<&str> = symbol.split("::").map(str::trim).collect();
+        let Some((start_line, end_line)) = find_definition_lines(&parsed.items, &path) else {
+            bail!("definition of '{}' not found in {}", symbol, file.display());
+        };
+        if start_line == 0 {
+            bail!("source locations are unavailable for {}", file.display());
+        }
+
+        let source = content
+            .lines()
+            .skip(start_line - 1)
+            .take(end_line + 1 - start_line)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Ok(Definition {
+            path: file.to_path_buf(),
+            start_line,
+            end_line,
+            source,
+        })
+    }
+
+    async fn find_references(&mut self, symbol: &str) -> Result<Vec<Location>> {
+        // Matching is by name: the last path segment, so `Type::method` finds
+        // every `method`, including same-named items on other types
+        let name = symbol.rsplit("::").next().unwrap_or(symbol).trim();
+        let name = name.strip_prefix("r#").unwrap_or(name).to_string();
+        if name.is_empty() {
+            bail!("empty symbol");
+        }
+
+        let workspace_root = self.workspace_root.clone();
+        tokio::task::spawn_blocking(move || find_references_in_workspace(&workspace_root, &name))
+            .await
+            .context("reference search panicked")
+    }
+
     async fn get_build_status(&mut self) -> Result<BuildStatus> {
         let mut child = Command::new("cargo")
             .args(["check", "--tests", "--message-format=json"])
@@ -138,30 +185,21 @@ impl TypeAnalyzer for RustAnalyzer {
         let reader = BufReader::new(stdout);
         let mut lines = reader.lines();
 
-        let mut errors = Vec::new();
-        let mut warnings = Vec::new();
+        let mut diagnostics = Vec::new();
 
         while let Some(line) = lines.next_line().await? {
-            let Ok(message) = serde_json::from_str::<CargoMessage>(&line) else {
-                continue;
-            };
-
-            let Some(compiler_message) = message.message else {
-                continue;
-            };
-
-            let formatted = format_compiler_message(&compiler_message);
-
-            match compiler_message.level.as_str() {
-                "error" => errors.push(formatted),
-                "warning" => warnings.push(formatted),
-                _ => {}
+            if let Some(diagnostic) = parse_cargo_message(&line, &self.workspace_root) {
+                // Targets sharing a source file (lib and tests) report the
+                // same diagnostic once each
+                if !diagnostics.contains(&diagnostic) {
+                    diagnostics.push(diagnostic);
+                }
             }
         }
 
         child.wait().await?;
 
-        Ok(BuildStatus { errors, warnings })
+        Ok(BuildStatus::from_diagnostics(diagnostics))
     }
 }
 
@@ -182,18 +220,223 @@ struct CompilerSpan {
     file_name: String,
     line_start: u32,
     column_start: u32,
+    #[serde(default)]
+    is_primary: bool,
+}
+
+/// Parses one line of `cargo --message-format=json` output into an error or
+/// warning. Other lines (artifacts, notes, build-script output) yield None.
+fn parse_cargo_message(line: &str, workspace_root: &Path) -> Option<Diagnostic> {
+    let message = serde_json::from_str::<CargoMessage>(line).ok()?.message?;
+    let severity = match message.level.as_str() {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => return None,
+    };
+
+    let span = message
+        .spans
+        .iter()
+        .find(|span| span.is_primary)
+        .or(message.spans.first());
+    Some(Diagnostic {
+        file: span.map(|span| workspace_root.join(&span.file_name)),
+        line: span.map_or(0, |span| span.line_start),
+        column: span.map_or(0, |span| span.column_start),
+        message: message.message,
+        severity,
+    })
+}
+
+/// Line range of the item named by `path`: `name` anywhere in the file,
+/// `module::name` through inline modules, or `Type::member` for impl and
+/// trait members. A bare name that is not an item falls back to members.
+fn find_definition_lines(items: &[Item], path: &[&str]) -> Option<(usize, usize)> {
+    match path {
+        [] => None,
+        [name] => find_item_lines(items, name).or_else(|| find_member_lines(items, None, name)),
+        [parent, rest @ ..] => {
+            let in_module = items.iter().find_map(|item| match item {
+                Item::Mod(m) if m.ident == parent => {
+                    let (_, nested) = m.content.as_ref()?;
+                    find_definition_lines(nested, rest)
+                }
+                _ => None,
+            });
+            match (in_module, rest) {
+                (Some(lines), _) => Some(lines),
+                (None, [member]) => find_member_lines(items, Some(*parent), member),
+                (None, _) => None,
+            }
+        }
+    }
+}
+
+fn find_item_lines(items: &[Item], name: &str) -> Option<(usize, usize)> {
+    for item in items {
+        let ident = match item {
+            Item::Struct(s) => Some(&s.ident),
+            Item::Enum(e) => Some(&e.ident),
+            Item::Trait(t) => Some(&t.ident),
+            Item::Type(t) => Some(&t.ident),
+            Item::Union(u) => Some(&u.ident),
+            Item::Fn(f) => Some(&f.sig.ident),
+            Item::Const(c) => Some(&c.ident),
+            Item::Static(s) => Some(&s.ident),
+            Item::Mod(m) => Some(&m.ident),
+            Item::Macro(m) => m.ident.as_ref(),
+            _ => None,
+        };
+        if ident.is_some_and(|ident| ident == name) {
+            return Some(span_lines(item));
+        }
+        if let Item::Mod(m) = item {
+            if let Some(lines) = m
+                .content
+                .as_ref()
+                .and_then(|(_, nested)| find_item_lines(nested, name))
+            {
+                return Some(lines);
+            }
+        }
+    }
+    None
+}
+
+/// Finds `member` in impl blocks for `owner` and in the trait named
+/// `owner`, or in any impl or trait when `owner` is None.
+fn find_member_lines(items: &[Item], owner: Option<&str>, member: &str) -> Option<(usize, usize)> {
+    for item in items {
+        match item {
+            Item::Impl(impl_item) if owner.is_none_or(|o| impl_matches_type(impl_item, o)) => {
+                let found = impl_item.items.iter().find(|i| {
+                    let ident = match i {
+                        ImplItem::Fn(f) => &f.sig.ident,
+                        ImplItem::Const(c) => &c.ident,
+                        ImplItem::Type(t) => &t.ident,
+                        _ => return false,
+                    };
+                    ident == member
+                });
+                if let Some(found) = found {
+                    return Some(span_lines(found));
+                }
+            }
+            Item::Trait(t) if owner.is_none_or(|o| t.ident == o) => {
+                let found = t.items.iter().find(|i| {
+                    let ident = match i {
+                        TraitItem::Fn(f) => &f.sig.ident,
+                        TraitItem::Const(c) => &c.ident,
+                        TraitItem::Type(t) => &t.ident,
+                        _ => return false,
+                    };
+                    ident == member
+                });
+                if let Some(found) = found {
+                    return Some(span_lines(found));
+                }
+            }
+            Item::Mod(m) => {
+                if let Some(lines) = m
+                    .content
+                    .as_ref()
+                    .and_then(|(_, nested)| find_member_lines(nested, owner, member))
+                {
+                    return Some(lines);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses every Rust source file under `workspace_root`, skipping ignored
+/// files and `target` directories, and collects identifiers named `name`.
+/// Comments and string literals never match; macro arguments do.
+fn find_references_in_workspace(workspace_root: &Path, name: &str) -> Vec<Location> {
+    let walker = WalkBuilder::new(workspace_root)
+        .filter_entry(|entry| entry.file_name() != "target")
+        .build();
+
+    let mut references = Vec::new();
+    for entry in walker.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|e| e != "rs") || !path.is_file() {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        if content.len() > 500_000 {
+            continue;
+        }
+        let Ok(file) = syn::parse_file(&content) else {
+            continue;
+        };
+
+        let mut collector = IdentCollector {
+            name,
+            found: Vec::new(),
+        };
+        collector.visit_file(&file);
+        references.extend(collector.found.into_iter().map(|(line, column)| Location {
+            path: path.to_path_buf(),
+            line,
+            column,
+        }));
+    }
+
+    references.sort();
+    references.dedup();
+    references
+}
+
+struct IdentCollector<'a> {
+    name: &'a str,
+    found: Vec<(usize, usize)>,
+}
+
+impl IdentCollector<'_> {
+    fn check(&mut self, ident: &proc_macro2::Ident) {
+        let text = ident.to_string();
+        if text.strip_prefix("r#").unwrap_or(&text) == self.name {
+            let start = ident.span().start();
+            self.found.push((start.line, start.column + 1));
+        }
+    }
+
+    /// Macro input is an opaque token stream to syn, so walk it directly.
+    fn visit_tokens(&mut self, tokens: &TokenStream) {
+        for token in tokens.clone() {
+            match token {
+                TokenTree::Ident(ident) => self.check(&ident),
+                TokenTree::Group(group) => self.visit_tokens(&group.stream()),
+                _ => {}
+            }
+        }
+    }
 }
 
-fn format_compiler_message(msg: &CompilerMessage) -> String {
-    if msg.spans.is_empty() {
-        return msg.message.clone();
+impl<'ast> Visit<'ast> for IdentCollector<'_> {
+    fn visit_ident(&mut self, ident: &'ast proc_macro2::Ident) {
+        self.check(ident);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        syn::visit::visit_macro(self, mac);
+        self.visit_tokens(&mac.tokens);
+    }
+
+    fn visit_meta_list(&mut self, list: &'ast syn::MetaList) {
+        syn::visit::visit_meta_list(self, list);
+        self.visit_tokens(&list.tokens);
     }
+}
 
-    let span = &msg.spans[0];
-    format!(
-        "{}:{}:{}: {}",
-        span.file_name, span.line_start, span.column_start, msg.message
-    )
+fn span_lines(node: &impl Spanned) -> (usize, usize) {
+    let span = node.span();
+    (span.start().line, span.end().line)
 }
 
 fn search_crate_for_type(
@@ -880,3 +1123,94 @@ fn strip_trait_bodies(trait_item: &ItemTrait) -> ItemTrait {
 
     trait_clone
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"use std::fmt;
+
+/// A point.
+#[derive(Debug)]
+pub struct Point {
+    x: i32,
+}
+
+impl Point {
+    pub fn new(x: i32) -> Self {
+        Self { x }
+    }
+}
+
+mod shapes {
+    pub fn area() -> u32 {
+        0
+    }
+}
+"#;
+
+    fn lines(symbol: &str) -> Option<(usize, usize)> {
+        let file = syn::parse_file(SOURCE).unwrap();
+        let path: Vec<&str> = symbol.split("::").collect();
+        find_definition_lines(&file.items, &path)
+    }
+
+    #[test]
+    fn finds_items_with_their_docs_and_attributes() {
+        assert_eq!(lines("Point"), Some((3, 7)));
+        assert_eq!(lines("shapes::area"), Some((16, 18)));
+        assert_eq!(lines("area"), Some((16, 18)));
+    }
+
+    #[test]
+    fn parses_cargo_diagnostics_at_primary_span() {
+        let root = Path::new("/work");
+        let warning = r#"{"reason":"compiler-message","message":{"message":"unused variable: `x`","level":"warning","spans":[{"file_name":"src/other.rs","line_start":1,"column_start":1,"is_primary":false},{"file_name":"src/lib.rs","line_start":3,"column_start":9,"is_primary":true}]}}"#;
+        let diagnostic = parse_cargo_message(warning, root).unwrap();
+        assert_eq!(diagnostic.file, Some(PathBuf::from("/work/src/lib.rs")));
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 9));
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(
+            diagnostic.to_string(),
+            "/work/src/lib.rs:3:9: unused variable: `x`"
+        );
+
+        let summary = r#"{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","level":"error","spans":[]}}"#;
+        let diagnostic = parse_cargo_message(summary, root).unwrap();
+        assert_eq!(diagnostic.file, None);
+        assert_eq!(diagnostic.to_string(), "aborting due to 1 previous error");
+
+        let note = r#"{"reason":"compiler-message","message":{"message":"see here","level":"note","spans":[]}}"#;
+        assert!(parse_cargo_message(note, root).is_none());
+        assert!(
+            parse_cargo_message(r#"{"reason":"build-finished","success":true}"#, root).is_none()
+        );
+
+        let status = BuildStatus::from_diagnostics(vec![
+            parse_cargo_message(warning, root).unwrap(),
+            parse_cargo_message(summary, root).unwrap(),
+        ]);
+        assert_eq!(status.errors, vec!["aborting due to 1 previous error"]);
+        assert_eq!(status.warnings.len(), 1);
+    }
+
+    #[test]
+    fn collects_identifiers_including_macro_arguments() {
+        let source = "// Point in a comment\nfn main() {\n    let p = Point::new(1);\n    println!(\"{:?} Point\", p.x);\n    assert!(matches!(p, Point { .. }));\n}\n";
+        let file = syn::parse_file(source).unwrap();
+        let mut collector = IdentCollector {
+            name: "Point",
+            found: Vec::new(),
+        };
+        collector.visit_file(&file);
+        assert_eq!(collector.found, vec![(3, 13), (5, 25)]);
+    }
+
+    #[test]
+    fn finds_members_by_owner_or_bare_name() {
+        assert_eq!(lines("Point::new"), Some((10, 12)));
+        assert_eq!(lines("new"), Some((10, 12)));
+        assert_eq!(lines("Point::missing"), None);
+        assert_eq!(lines("Missing"), None);
+    }
+}
@@ -57,7 +57,7 @@ impl ToolExecutor for SearchTypesTool {
     }
 
     fn category(&self) -> ToolCategory {
-        ToolCategory::Execution
+        ToolCategory::Read
     }
 
     async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
@@ -142,6 +142,7 @@ impl ToolCallHandle for SearchTypesHandle {
                 ui_result: ToolExecutionResult::Error {
                     short_message: "Search failed".to_string(),
                     detailed_message: format!("Failed to search types: {e:?}"),
+                    category: None,
                 },
             },
         }
@@ -0,0 +1,242 @@
+//! Structured audit log of tool invocations.
+//!
+//! Enabled through the `audit` module config:
+//!
+//! ```toml
+//! [modules.audit]
+//! enabled = true
+//! ```
+//!
+//! Every executed tool call appends one JSON line to
+//! `~/.tycode/audit/<session>.jsonl`. Unlike the trace log, the record schema
+//! is stable so the files can be consumed by other tooling. String arguments
+//! are passed through the `redaction` patterns before they are written.
+//!
+//! Calls held for the user's approval (confirmed or proposed edits, gated git
+//! changes) are recorded as `pending` when the tool returns, and again as
+//! `approved` or `declined` once the user answers.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::chat::events::ToolRequest as ToolRequestEvent;
+use crate::redact::Redactor;
+use crate::tools::r#trait::{ToolCallHandle, ToolOutput};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Audit {
+    /// Record every tool invocation to `~/.tycode/audit/<session>.jsonl`.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+impl Audit {
+    pub const NAMESPACE: &str = "audit";
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    pub agent: String,
+    pub tool_name: String,
+    pub tool_call_id: String,
+    pub arguments: Value,
+    pub outcome: AuditOutcome,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Error,
+    /// Held for the user's approval; a later record gives the resolution.
+    Pending,
+    /// The user approved a held call and it ran. Failures while running it
+    /// are recorded as `Error`.
+    Approved,
+    /// A held call was declined, timed out or discarded without running.
+    Declined,
+}
+
+#[derive(Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+    redactor: Arc<Redactor>,
+}
+
+impl AuditLog {
+    pub fn new(dir: &Path, session_id: &str, redactor: Arc<Redactor>) -> Self {
+        Self {
+            path: dir.join(format!("{session_id}.jsonl")),
+            redactor,
+        }
+    }
+
+    /// The audit context for one tool call.
+    pub fn call(
+        &self,
+        agent: &str,
+        tool_name: &str,
+        tool_call_id: &str,
+        arguments: &Value,
+    ) -> AuditCall {
+        AuditCall {
+            log: self.clone(),
+            agent: agent.to_string(),
+            tool_name: tool_name.to_string(),
+            tool_call_id: tool_call_id.to_string(),
+            arguments: arguments.clone(),
+            held: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Append a record for a tool call. Failures are logged rather than
+    /// returned so auditing never fails the tool call itself.
+    pub fn record(
+        &self,
+        agent: &str,
+        tool_name: &str,
+        tool_call_id: &str,
+        arguments: &Value,
+        outcome: AuditOutcome,
+        duration: Duration,
+    ) {
+        let record = AuditRecord {
+            timestamp: Utc::now(),
+            agent: agent.to_string(),
+            tool_name: tool_name.to_string(),
+            tool_call_id: tool_call_id.to_string(),
+            arguments: self.redact(arguments),
+            outcome,
+            duration_ms: duration.as_millis() as u64,
+        };
+        if let Err(e) = self.append(&record) {
+            tracing::warn!(?e, path = %self.path.display(), "Failed to write audit record");
+        }
+    }
+
+    fn append(&self, record: &AuditRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create audit directory: {}", parent.display())
+            })?;
+        }
+        let mut line = serde_json::to_string(record).context("Failed to serialize record")?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open audit log: {}", self.path.display()))?;
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("Failed to write audit log: {}", self.path.display()))
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        match value {
+            Value::String(text) => Value::String(self.redactor.redact(text).into_owned()),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.redact(v)).collect()),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, v)| (key.clone(), self.redact(v)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// One tool call's audit context, handed to its handle so a call that is held
+/// for approval can record how it was resolved.
+#[derive(Clone)]
+pub struct AuditCall {
+    log: AuditLog,
+    agent: String,
+    tool_name: String,
+    tool_call_id: String,
+    arguments: Value,
+    held: Arc<AtomicBool>,
+}
+
+impl AuditCall {
+    pub fn record(&self, outcome: AuditOutcome, duration: Duration) {
+        self.log.record(
+            &self.agent,
+            &self.tool_name,
+            &self.tool_call_id,
+            &self.arguments,
+            outcome,
+            duration,
+        );
+    }
+
+    /// Whether the call was held for approval rather than run.
+    pub fn is_held(&self) -> bool {
+        self.held.load(Ordering::Relaxed)
+    }
+
+    /// Wraps a handle that is being held for approval. Running the wrapper
+    /// records the call as approved; dropping it unrun records it as
+    /// declined.
+    pub fn hold(self, handle: Box<dyn ToolCallHandle>) -> Box<dyn ToolCallHandle> {
+        self.held.store(true, Ordering::Relaxed);
+        Box::new(HeldHandle {
+            handle: Some(handle),
+            call: self,
+        })
+    }
+}
+
+struct HeldHandle {
+    /// Taken when the call runs, so `Drop` can tell it was never approved.
+    handle: Option<Box<dyn ToolCallHandle>>,
+    call: AuditCall,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for HeldHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        self.handle.as_ref().expect("held handle").tool_request()
+    }
+
+    fn edited_path(&self) -> Option<PathBuf> {
+        self.handle.as_ref().expect("held handle").edited_path()
+    }
+
+    async fn execute(mut self: Box<Self>) -> ToolOutput {
+        let handle = self.handle.take().expect("held handle");
+        let started = Instant::now();
+        let output = handle.execute().await;
+        let outcome = match output {
+            ToolOutput::Result { is_error: true, .. } => AuditOutcome::Error,
+            _ => AuditOutcome::Approved,
+        };
+        self.call.record(outcome, started.elapsed());
+        output
+    }
+}
+
+impl Drop for HeldHandle {
+    fn drop(&mut self) {
+        if self.handle.is_some() {
+            self.call.record(AuditOutcome::Declined, Duration::ZERO);
+        }
+    }
+}
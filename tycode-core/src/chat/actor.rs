@@ -1,3 +1,5 @@
+use crate::ai::cache::CachingProvider;
+use crate::ai::concurrency::ConcurrencyLimitedProvider;
 use crate::ai::ContextBreakdown;
 use crate::modules::context_management::ContextManagementModule;
 use crate::{
@@ -26,6 +28,7 @@ use crate::{
     analyzer::AnalyzerModule,
     chat::{
         ai,
+        edit_review::{parse_review_reply, EditReview, EditTarget},
         events::{
             ChatEvent, ChatMessage, EventSender, ModuleSchemaInfo, SettingsGroupInfo,
             SettingsGroupKind, SettingsSchemaInfo,
@@ -34,11 +37,13 @@ use crate::{
         tools,
     },
     file::access::FileAccessManager,
+    file::config::{EditDecision, File},
     file::{modify::FileModifyModule, read_only::ReadOnlyFileModule},
     mcp::McpModule,
     module::{ContextBuilder, Module, PromptBuilder, PromptComponent},
     modules::{
         execution::ExecutionModule,
+        git::GitModule,
         image::{ImageModule, SharedProvider},
         memory::{
             background::{safe_conversation_slice, spawn_memory_manager},
@@ -46,12 +51,16 @@ use crate::{
             MemoryConfig, MemoryModule,
         },
         review::ReviewModule,
+        semantic_search::SemanticSearchModule,
         task_list::TaskListModule,
     },
-    settings::{config::McpServerConfig, ProviderConfig, Settings, SettingsManager},
+    settings::{
+        config::{AutonomyLevel, McpServerConfig},
+        ProviderConfig, Settings, SettingsManager,
+    },
     skills::SkillsModule,
     spawn::AgentStack,
-    steering::{SteeringDocuments, SteeringModule},
+    steering::{self, SteeringDocuments, SteeringModule},
 };
 
 use anyhow::{bail, Result};
@@ -64,8 +73,8 @@ use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::mpsc;
-use tracing::{error, info};
+use tokio::sync::{mpsc, watch};
+use tracing::{error, info, warn};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TimingState {
@@ -185,6 +194,8 @@ impl ChatActorBuilder {
         profile: Option<String>,
         settings_manager: SettingsManager,
     ) -> Result<Self> {
+        let settings_manager = settings_manager.with_workspace_overrides(&workspace_roots)?;
+
         // Generate tool_calls_dir for persisting truncated tool output.
         // Stored under root_dir (typically ~/.tycode) so it's accessible
         // even when no workspaces are open.
@@ -193,6 +204,9 @@ impl ChatActorBuilder {
         std::fs::create_dir_all(&tool_calls_dir)?;
 
         let settings = settings_manager.settings();
+        for name in steering::unknown_builtins(&settings.disabled_steering_builtins) {
+            tracing::warn!("Unknown steering builtin in disabled_steering_builtins: {name}");
+        }
 
         let steering = Arc::new(SteeringDocuments::new(
             workspace_roots.clone(),
@@ -201,7 +215,7 @@ impl ChatActorBuilder {
         ));
 
         let memory_path = root_dir.join("memory").join("memories_log.json");
-        let memory_log = Arc::new(MemoryLog::new(memory_path));
+        let memory_log = Arc::new(MemoryLog::new(memory_path).with_workspaces(&workspace_roots));
 
         // Create EventSender upfront so components can use it
         let (event_sender, event_rx) = EventSender::new();
@@ -210,6 +224,7 @@ impl ChatActorBuilder {
         let read_only_file_module = Arc::new(ReadOnlyFileModule::new(
             workspace_roots.clone(),
             settings_manager.clone(),
+            tool_calls_dir.clone(),
         )?);
         let task_list_module = Arc::new(TaskListModule::new(event_sender.clone()));
         let memory_module = MemoryModule::new(memory_log.clone(), settings_manager.clone());
@@ -244,9 +259,17 @@ impl ChatActorBuilder {
         builder.with_module(task_list_module);
         builder.with_module(Arc::new(memory_module));
 
+        let file_modify_module = Arc::new(FileModifyModule::new(
+            builder.workspace_roots.clone(),
+            settings_manager.clone(),
+            builder.event_sender.clone(),
+        )?);
+        let edit_wrapper = file_modify_module.edit_wrapper();
         let execution_module = Arc::new(ExecutionModule::new(
             builder.workspace_roots.clone(),
             settings_manager.clone(),
+            builder.tool_calls_dir.clone(),
+            edit_wrapper.clone(),
         )?);
         builder.with_module(execution_module);
 
@@ -267,10 +290,22 @@ impl ChatActorBuilder {
         builder.with_module(Arc::new(ReviewModule));
 
         // File modification module (write, delete, modify tools)
-        let roots = builder.workspace_roots.clone();
-        let file_modify_module = Arc::new(FileModifyModule::new(roots, settings_manager.clone())?);
         builder.with_module(file_modify_module);
 
+        let git_module = Arc::new(GitModule::new(
+            builder.workspace_roots.clone(),
+            settings_manager.clone(),
+            builder.shared_provider.clone(),
+        )?);
+        builder.with_module(git_module);
+
+        let semantic_search_module = Arc::new(SemanticSearchModule::new(
+            builder.workspace_roots.clone(),
+            settings_manager.clone(),
+            builder.shared_provider.clone(),
+        )?);
+        builder.with_module(semantic_search_module);
+
         // LSP/analyzer module
         let workspace_roots_for_analyzer = builder.workspace_roots.clone();
         builder.with_module(Arc::new(
@@ -278,14 +313,19 @@ impl ChatActorBuilder {
                 .expect("Failed to create AnalyzerModule"),
         ));
 
-        let steering_module = Arc::new(SteeringModule::new(steering, settings_manager.clone()));
+        let steering_module = Arc::new(SteeringModule::new(steering));
         builder.with_module(steering_module);
 
-        let file_access = Arc::new(FileAccessManager::new(builder.workspace_roots.clone())?);
+        let file_access = Arc::new(
+            FileAccessManager::new(builder.workspace_roots.clone())?
+                .with_settings(settings_manager.clone()),
+        );
         let image_module = Arc::new(ImageModule::new(
             shared_provider,
             file_access,
             Arc::new(settings_manager.clone()),
+            builder.event_sender.clone(),
+            edit_wrapper,
         ));
         builder.with_module(image_module);
 
@@ -294,7 +334,7 @@ impl ChatActorBuilder {
 
     pub fn new(workspace_roots: Vec<PathBuf>, root_dir: PathBuf) -> Self {
         let memory_path = root_dir.join("memory").join("memories_log.json");
-        let memory_log = Arc::new(MemoryLog::new(memory_path));
+        let memory_log = Arc::new(MemoryLog::new(memory_path).with_workspaces(&workspace_roots));
         let (event_sender, event_rx) = EventSender::new();
 
         let session_id = ActorState::generate_session_id();
@@ -390,9 +430,22 @@ impl ChatActorBuilder {
         self
     }
 
+    /// Runs every agent at `level`, replacing the configured autonomy levels
+    /// in memory only. For runs with nobody to answer approval prompts.
+    pub fn autonomy_level(self, level: AutonomyLevel) -> Self {
+        if let Some(settings) = &self.settings_manager {
+            settings.update_setting(|settings| {
+                settings.autonomy_level = level;
+                settings.agent_autonomy_levels.clear();
+            });
+        }
+        self
+    }
+
     pub fn build(self) -> Result<(ChatActor, mpsc::UnboundedReceiver<ChatEvent>)> {
         let (tx, rx) = mpsc::unbounded_channel();
         let (cancel_tx, cancel_rx) = mpsc::unbounded_channel();
+        let (stopped_tx, stopped) = watch::channel(false);
 
         let workspace_roots = self.workspace_roots;
         let root_dir = self.root_dir;
@@ -436,9 +489,17 @@ impl ChatActorBuilder {
             .await;
 
             run_actor(actor_state, rx, cancel_rx).await;
+            let _ = stopped_tx.send(true);
         });
 
-        Ok((ChatActor { tx, cancel_tx }, event_rx))
+        Ok((
+            ChatActor {
+                tx,
+                cancel_tx,
+                stopped,
+            },
+            event_rx,
+        ))
     }
 }
 
@@ -453,6 +514,15 @@ pub enum ChatActorMessage {
     /// A user input to the conversation with the current AI agent
     UserInput(String),
 
+    /// A finalized speech transcript. Routed to a slash command when it is a
+    /// spoken command (see `match_spoken_command`) and
+    /// `voice.spoken_commands` is enabled; otherwise handled like `UserInput`.
+    VoiceTranscript(String),
+
+    /// An interim speech transcript, echoed back as
+    /// `ChatEvent::VoicePartialTranscript` for live captions.
+    VoicePartialTranscript(String),
+
     /// A user input with attached images
     UserInputWithImages {
         text: String,
@@ -499,6 +569,16 @@ pub enum ChatActorMessage {
 
     /// Requests current settings plus grouped JSON schemas for generic settings UIs
     GetSettingsSchema,
+
+    /// Accepts or rejects one edit announced by `ChatEvent::EditsProposed`.
+    /// Accepted edits are written immediately; once every proposed edit is
+    /// decided, the outcomes are sent to the agent and the turn resumes.
+    ReviewEdit { edit_id: u64, accept: bool },
+
+    /// Saves the session and closes MCP clients, then stops the actor. Later
+    /// messages are ignored. The actor also shuts down when its input channel
+    /// closes.
+    Shutdown,
 }
 
 /// The `ChatActor` implements the core (or backend) of tycode.
@@ -516,17 +596,47 @@ pub enum ChatActorMessage {
 /// channel, however that is encapsulated by the ChatActor). Events from the
 /// actor are received through a `mpsc::UnboundedReceiver<ChatEvent>` which is
 /// returned when the actor is launched.
+#[derive(Clone)]
 pub struct ChatActor {
     pub tx: mpsc::UnboundedSender<ChatActorMessage>,
     pub cancel_tx: mpsc::UnboundedSender<()>,
+    stopped: watch::Receiver<bool>,
 }
 
 impl ChatActor {
+    /// Cancels any running turn, asks the actor to persist its state and
+    /// waits up to `timeout` for it to stop. Safe to call more than once.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<()> {
+        // Either send fails only if the actor already stopped
+        let _ = self.cancel_tx.send(());
+        let _ = self.tx.send(ChatActorMessage::Shutdown);
+
+        let mut stopped = self.stopped.clone();
+        let result = tokio::time::timeout(timeout, stopped.wait_for(|stopped| *stopped)).await;
+        match result {
+            // An error means the actor task is gone, which is also stopped
+            Ok(_) => Ok(()),
+            Err(_) => bail!("Timed out after {timeout:?} waiting for the actor to shut down"),
+        }
+    }
+
     pub fn send_message(&self, message: String) -> Result<()> {
         self.tx.send(ChatActorMessage::UserInput(message))?;
         Ok(())
     }
 
+    pub fn send_voice_transcript(&self, transcript: String) -> Result<()> {
+        self.tx
+            .send(ChatActorMessage::VoiceTranscript(transcript))?;
+        Ok(())
+    }
+
+    pub fn send_voice_partial(&self, text: String) -> Result<()> {
+        self.tx
+            .send(ChatActorMessage::VoicePartialTranscript(text))?;
+        Ok(())
+    }
+
     pub fn send_message_with_images(&self, message: String, images: Vec<ImageData>) -> Result<()> {
         self.tx.send(ChatActorMessage::UserInputWithImages {
             text: message,
@@ -570,6 +680,12 @@ impl ChatActor {
         self.tx.send(ChatActorMessage::GetSettingsSchema)?;
         Ok(())
     }
+
+    pub fn review_edit(&self, edit_id: u64, accept: bool) -> Result<()> {
+        self.tx
+            .send(ChatActorMessage::ReviewEdit { edit_id, accept })?;
+        Ok(())
+    }
 }
 
 pub struct ActorState {
@@ -584,9 +700,13 @@ pub struct ActorState {
     pub session_token_usage: TokenUsage,
     pub session_cost: f64,
     pub pending_context_breakdown: Option<ContextBreakdown>,
+    /// Set once a `ContextWarning` has been sent; cleared when the estimate
+    /// falls back under the threshold, e.g. after compaction.
+    pub context_warning_sent: bool,
     pub profile_name: Option<String>,
     pub session_id: Option<String>,
     pub sessions_dir: PathBuf,
+    pub audit_dir: PathBuf,
     pub ephemeral: bool,
     pub timing_stats: TimingStats,
     pub memory_log: Arc<MemoryLog>,
@@ -595,8 +715,15 @@ pub struct ActorState {
     pub prompt_builder: PromptBuilder,
     pub context_builder: ContextBuilder,
     pub modules: Vec<Arc<dyn Module>>,
+    /// Edits proposed for piecewise review, awaiting the user's decisions.
+    pub edit_review: EditReview,
+    pub shut_down: bool,
 }
 
+/// Upper bound on closing MCP clients during shutdown, so a hung server
+/// cannot block exit.
+const MCP_CLOSE_TIMEOUT: Duration = Duration::from_secs(3);
+
 impl ActorState {
     fn generate_session_id() -> String {
         let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
@@ -620,6 +747,7 @@ impl ActorState {
                 });
 
         session.messages = messages;
+        session.edit_review = self.edit_review.save();
 
         for module in &self.modules {
             if let Some(session_state) = module.session_state() {
@@ -700,6 +828,7 @@ impl ActorState {
         });
         let profile_name = profile;
         let sessions_dir = root_dir.join("sessions");
+        let audit_dir = root_dir.join("audit");
 
         let mut settings_snapshot = settings.settings();
 
@@ -712,6 +841,10 @@ impl ActorState {
             settings_snapshot.mcp_servers.extend(extra_mcp_servers);
         }
 
+        for warning in settings.load_warnings() {
+            event_sender.add_message(ChatMessage::warning(format!("Settings: {warning}")));
+        }
+
         if settings_snapshot.active_provider().is_none() {
             event_sender.add_message(ChatMessage::error(
                 "No AI provider is configured. Configure one in settings or with the command /provider add ..."
@@ -819,9 +952,11 @@ impl ActorState {
             session_token_usage: TokenUsage::empty(),
             session_cost: 0.0,
             pending_context_breakdown: None,
+            context_warning_sent: false,
             profile_name,
             session_id: None,
             sessions_dir,
+            audit_dir,
             ephemeral,
             timing_stats: TimingStats::new(),
             memory_log,
@@ -830,6 +965,28 @@ impl ActorState {
             prompt_builder,
             context_builder,
             modules,
+            edit_review: EditReview::default(),
+            shut_down: false,
+        }
+    }
+
+    /// Persists the session and closes MCP clients. Idempotent. The memory
+    /// log needs no flush since every append is written through to disk.
+    pub async fn shutdown(&mut self) {
+        if self.shut_down {
+            return;
+        }
+        self.shut_down = true;
+        info!("ChatActor shutting down");
+
+        if let Err(e) = self.save_session() {
+            warn!(?e, "Failed to save session during shutdown");
+        }
+        if tokio::time::timeout(MCP_CLOSE_TIMEOUT, self.mcp_manager.shutdown())
+            .await
+            .is_err()
+        {
+            warn!("Timed out closing MCP clients during shutdown");
         }
     }
 
@@ -957,6 +1114,11 @@ async fn run_actor(
         });
         state.event_sender.set_typing(false);
         state.transition_timing_state(TimingState::WaitingForHuman);
+
+        if state.shut_down {
+            info!("ChatActor stopped");
+            return;
+        }
     }
 }
 
@@ -966,21 +1128,29 @@ const GENERAL_SETTINGS_FIELDS: &[&str] = &[
     "review_level",
     "communication_tone",
     "autonomy_level",
+    "security",
     "reasoning_effort",
     "disable_streaming",
 ];
 
 const PROVIDER_SETTINGS_FIELDS: &[&str] = &["active_provider", "providers"];
-const MCP_SETTINGS_FIELDS: &[&str] = &["mcp_servers"];
-const AGENT_MODEL_SETTINGS_FIELDS: &[&str] = &["agent_models"];
+const MCP_SETTINGS_FIELDS: &[&str] = &["mcp_servers", "max_mcp_tools", "mcp_server_priority"];
+const AGENT_MODEL_SETTINGS_FIELDS: &[&str] = &[
+    "agent_models",
+    "agent_autonomy_levels",
+    "agent_temperature_schedules",
+];
 const ADVANCED_SETTINGS_FIELDS: &[&str] = &[
     "max_review_rounds",
     "fanout_concurrency",
+    "tool_concurrency",
+    "max_tool_calls_per_turn",
     "orchestration_mode",
     "orchestration_progress_messages",
     "swarm_models",
     "spawn_context_mode",
     "disable_custom_steering",
+    "disabled_steering_builtins",
     "voice",
     "skills",
 ];
@@ -1066,7 +1236,7 @@ fn settings_schema_groups(modules: &[Arc<dyn Module>]) -> Result<Vec<SettingsGro
             &root_schema,
             "agents",
             "Agent Models",
-            "Per-agent model overrides.",
+            "Per-agent model, autonomy and temperature overrides.",
             &[],
             &string_fields(AGENT_MODEL_SETTINGS_FIELDS),
         )?,
@@ -1210,8 +1380,13 @@ async fn process_message(
     state: &mut ActorState,
 ) -> Result<()> {
     let Some(message) = rx.recv().await else {
-        bail!("request queue dropped")
+        // Every ChatActor handle is gone; nothing can reach us again
+        state.shutdown().await;
+        return Ok(());
     };
+    if state.shut_down {
+        return Ok(());
+    }
 
     state.transition_timing_state(TimingState::Idle);
 
@@ -1225,6 +1400,20 @@ async fn process_message(
         ChatActorMessage::UserInput(input) => {
             handle_user_input(state, input, vec![], &mut protocol).await
         }
+        ChatActorMessage::VoiceTranscript(transcript) => {
+            handle_voice_transcript(state, transcript, &mut protocol).await
+        }
+        ChatActorMessage::Shutdown => {
+            state.shutdown().await;
+            Ok(())
+        }
+        ChatActorMessage::VoicePartialTranscript(text) => {
+            // Bypasses event history so captions are never persisted
+            state
+                .event_sender
+                .send_replay(ChatEvent::VoicePartialTranscript { text });
+            Ok(())
+        }
         ChatActorMessage::UserInputWithImages { text, images } => {
             handle_user_input(state, text, images, &mut protocol).await
         }
@@ -1303,12 +1492,38 @@ async fn process_message(
                 .send_replay(ChatEvent::SettingsSchema { schema });
             Ok(())
         }
+        ChatActorMessage::ReviewEdit { edit_id, accept } => {
+            handle_review_edit(state, EditTarget::One(edit_id), accept, &mut protocol).await
+        }
     };
 
     protocol.finish();
     result
 }
 
+async fn handle_voice_transcript(
+    state: &mut ActorState,
+    transcript: String,
+    protocol: &mut TurnProtocol,
+) -> Result<()> {
+    let input = if state.settings.settings().voice.spoken_commands {
+        let commands = crate::chat::commands::get_available_commands(&state.modules);
+        match crate::chat::commands::match_spoken_command(&transcript, &commands) {
+            Some(command) => {
+                state.event_sender.send_message(ChatMessage::system(format!(
+                    "Interpreted \"{}\" as {command}",
+                    transcript.trim()
+                )));
+                command
+            }
+            None => transcript,
+        }
+    } else {
+        transcript
+    };
+    handle_user_input(state, input, vec![], protocol).await
+}
+
 async fn handle_user_input(
     state: &mut ActorState,
     input: String,
@@ -1338,7 +1553,34 @@ async fn handle_user_input(
             .send_message(ChatMessage::user_with_images(input.clone(), images.clone()));
     }
 
-    if let Some(command) = input.strip_prefix('/') {
+    if state.edit_review.has_pending() {
+        if let Some((target, accept)) = parse_review_reply(&input) {
+            return handle_review_edit(state, target, accept, protocol).await;
+        }
+    }
+
+    if crate::mcp::command::is_call_command(&input) {
+        for message in crate::mcp::command::handle_call_command(state, &input).await {
+            state.event_sender.send_message(message);
+        }
+        return Ok(());
+    }
+
+    // An expanded prompt is server-provided text for the model; one that
+    // happens to start with `/` must not run as a command
+    let (input, expanded_prompt) = if crate::mcp::command::is_prompt_command(&input) {
+        match crate::mcp::command::expand_prompt_command(state, &input).await {
+            Ok(prompt) => (prompt, true),
+            Err(e) => {
+                state.event_sender.send_message(ChatMessage::error(e));
+                return Ok(());
+            }
+        }
+    } else {
+        (input, false)
+    };
+
+    if let Some(command) = input.strip_prefix('/').filter(|_| !expanded_prompt) {
         if crate::chat::commands::is_known_command(command, &state.modules) {
             let messages = crate::chat::commands::process_command(state, command).await;
 
@@ -1349,6 +1591,24 @@ async fn handle_user_input(
         }
     }
 
+    let mut input = input;
+    for module in state.modules.clone() {
+        if let Some(replacement) = module.on_user_input(&input).await {
+            input = replacement;
+        }
+    }
+
+    // A reply that is not a review decision settles the remaining proposals
+    // the same way an unanswered confirmation is settled
+    if state.edit_review.has_pending() {
+        let config: File = state.settings.get_module_config(File::NAMESPACE);
+        let accept = config.unanswered_edit_decision == EditDecision::Approve;
+        let _ = state.edit_review.resolve(EditTarget::All, accept).await;
+    }
+    if let Some(outcomes) = state.edit_review.take_outcomes() {
+        input = format!("{outcomes}\n\n{input}");
+    }
+
     let content = if images.is_empty() {
         Content::text_only(input.clone())
     } else {
@@ -1414,6 +1674,46 @@ async fn handle_user_input(
     Ok(())
 }
 
+/// Applies a review decision to proposed edits. The turn resumes once the
+/// last pending edit is decided.
+async fn handle_review_edit(
+    state: &mut ActorState,
+    target: EditTarget,
+    accept: bool,
+    protocol: &mut TurnProtocol,
+) -> Result<()> {
+    let outcomes = match state.edit_review.resolve(target, accept).await {
+        Ok(outcomes) => outcomes,
+        Err(e) => {
+            state.event_sender.send_message(ChatMessage::error(e));
+            return Ok(());
+        }
+    };
+    for outcome in outcomes {
+        state
+            .event_sender
+            .send_message(ChatMessage::system(outcome));
+    }
+
+    let Some(summary) = state.edit_review.take_outcomes() else {
+        return Ok(());
+    };
+    tools::current_agent_mut(state, |a| {
+        a.conversation.push(Message {
+            role: MessageRole::User,
+            content: Content::text_only(summary),
+        })
+    });
+    ai::send_ai_request(state, protocol).await?;
+
+    if !state.ephemeral {
+        if let Err(e) = state.save_session() {
+            tracing::warn!("Failed to auto-save session: {}", e);
+        }
+    }
+    Ok(())
+}
+
 async fn handle_provider_change(state: &mut ActorState, provider_name: String) -> Result<()> {
     info!("Changing provider to: {}", provider_name);
     let new_provider = create_provider(&state.settings, &provider_name).await?;
@@ -1437,11 +1737,12 @@ pub async fn create_provider(
         bail!("No active provider configured in settings")
     };
 
-    match provider_config {
+    let provider: Arc<dyn AiProvider> = match provider_config {
         ProviderConfig::Bedrock {
             profile,
             region,
             mantle_region,
+            ..
         } => {
             // An unset OR empty mantle_region falls back to the native region;
             // an empty string would otherwise produce a broken bedrock-mantle
@@ -1450,15 +1751,29 @@ pub async fn create_provider(
                 .as_deref()
                 .filter(|region| !region.is_empty())
                 .unwrap_or(region);
-            create_bedrock_provider(profile, region, mantle_region).await
+            create_bedrock_provider(profile, region, mantle_region).await?
         }
-        ProviderConfig::OpenRouter { api_key } => {
+        ProviderConfig::OpenRouter { api_key, .. } => {
             use crate::ai::openrouter::OpenRouterProvider;
-            Ok(Arc::new(OpenRouterProvider::new(api_key.clone()).await?))
+            Arc::new(OpenRouterProvider::new(api_key.clone()).await?)
         }
-        ProviderConfig::Mock { behavior } => Ok(Arc::new(MockProvider::new(behavior.clone()))),
+        ProviderConfig::Mock { behavior } => Arc::new(MockProvider::new(behavior.clone())),
         ProviderConfig::Unknown => bail!("Cannot create provider from unknown provider type"),
+    };
+
+    let mut provider = match provider_config.max_concurrent_requests() {
+        Some(limit) => ConcurrencyLimitedProvider::wrap(provider, limit),
+        None => provider,
+    };
+    // Outermost, so cache hits never wait for a concurrency permit
+    if config.response_cache.enabled {
+        provider = CachingProvider::wrap(
+            provider,
+            settings.settings_dir().join("cache").join("responses"),
+            config.response_cache.clone(),
+        );
     }
+    Ok(provider)
 }
 
 async fn create_bedrock_provider(
@@ -1558,8 +1873,14 @@ pub async fn resume_session(state: &mut ActorState, session_id: &str) -> Result<
     }
     state.event_sender.clear_history();
 
+    let (messages, fixes) =
+        crate::persistence::repair::repair_conversation(session_data.messages.clone());
+    for fix in &fixes {
+        warn!(session_id, "Repaired resumed conversation: {fix}");
+    }
+
     tools::current_agent_mut(state, |a| {
-        a.conversation = session_data.messages.clone();
+        a.conversation = messages;
         // The stream resets below (ConversationCleared + replay), which
         // discards the pre-resume root announcement on the consumer side;
         // clear the flag so the next live orchestration re-announces the
@@ -1578,6 +1899,8 @@ pub async fn resume_session(state: &mut ActorState, session_id: &str) -> Result<
         }
     }
 
+    crate::chat::edit_review::restore(state, session_data.edit_review).await;
+
     state.session_id = Some(session_data.id.clone());
     state.event_sender.send_replay(ChatEvent::SessionStarted {
         session_id: session_data.id.clone(),
@@ -1595,3 +1918,44 @@ pub async fn resume_session(state: &mut ActorState, session_id: &str) -> Result<
 
     Ok(())
 }
+
+/// Saves the current session and switches to a brand-new one, keeping
+/// settings, provider, and MCP connections. Returns the new session id, or
+/// None for ephemeral actors. The previous session stays resumable.
+pub fn start_new_session(state: &mut ActorState) -> Option<String> {
+    // Same ordering as resume_session: close the old agent tree and persist
+    // its completions before anything is reset.
+    state.unwind_sub_agents_with_hooks();
+    if let Err(error) = state.save_session() {
+        tracing::warn!(?error, "Failed to save departing session before /new");
+    }
+    state.event_sender.clear_history();
+
+    tools::current_agent_mut(state, |a| {
+        a.conversation.clear();
+        a.announced = false;
+    });
+
+    for module in &state.modules {
+        if let Some(session_state) = module.session_state() {
+            session_state.reset();
+        }
+    }
+
+    state.edit_review = EditReview::default();
+    state.session_token_usage = TokenUsage::empty();
+    state.session_cost = 0.0;
+    state.pending_context_breakdown = None;
+    state.context_warning_sent = false;
+    state.steering.reload();
+
+    state.session_id = (!state.ephemeral).then(ActorState::generate_session_id);
+    if let Some(session_id) = &state.session_id {
+        state.event_sender.send_replay(ChatEvent::SessionStarted {
+            session_id: session_id.clone(),
+        });
+    }
+    state.clear_conversation();
+
+    state.session_id.clone()
+}
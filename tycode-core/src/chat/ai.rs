@@ -1,17 +1,23 @@
 use crate::ai::{
-    error::AiError, provider::AiProvider, Content, ContentBlock, ConversationRequest,
-    ConversationResponse, Message, MessageRole, ModelSettings, StreamEvent, ToolUseData,
+    error::AiError, provider::AiProvider, Content, ContentBlock, ContextBreakdown,
+    ConversationRequest, ConversationResponse, Message, MessageRole, ModelSettings, StreamEvent,
+    ToolUseData,
 };
 use crate::chat::events::{ChatEvent, ChatMessage, ModelInfo};
 use crate::chat::request::{prepare_request, select_model_for_agent};
 use crate::chat::tools::{self, current_agent_mut};
 
 use crate::agents::agent::RequestTelemetry;
+use crate::analyzer::build_watch::{self, BuildWatch, BuildWatchConfig};
+use crate::chat::events::BuildWatchStatus;
+use crate::chat::loop_detection::{self, LoopDetector, RepeatedCall};
 use crate::modules::context_management::{
     self,
     planner::{self, CompactionTrigger},
     ContextManagementConfig,
 };
+use crate::settings::config::LoopAction;
+use crate::spawn::complete_task::CompleteTask;
 use anyhow::{Context, Result};
 use chrono::Utc;
 use std::pin::Pin;
@@ -23,7 +29,134 @@ use tracing::{error, info, warn};
 
 use super::{actor::ActorState, protocol::TurnProtocol};
 
+/// Emits `ContextWarning` when the estimated request size first crosses the
+/// configured fraction of the context window. The warning re-arms once the
+/// estimate drops back below the threshold.
+fn check_context_pressure(state: &mut ActorState, breakdown: &ContextBreakdown) {
+    let config: ContextManagementConfig = state
+        .settings
+        .get_module_config(ContextManagementConfig::NAMESPACE);
+    if config.context_warning_fraction <= 0.0 || breakdown.context_window == 0 {
+        return;
+    }
+
+    let threshold = (breakdown.context_window as f64 * config.context_warning_fraction) as u32;
+    if breakdown.estimated_tokens < threshold {
+        state.context_warning_sent = false;
+        return;
+    }
+    if state.context_warning_sent {
+        return;
+    }
+
+    state.context_warning_sent = true;
+    state.event_sender.send(ChatEvent::ContextWarning {
+        used_tokens: breakdown.estimated_tokens,
+        limit_tokens: breakdown.context_window,
+    });
+}
+
+/// The agent's scheduled temperature for its next turn, clamped to what the
+/// provider accepts for the model. `None` leaves the model's own temperature.
+fn scheduled_temperature(
+    state: &ActorState,
+    agent_name: &str,
+    conversation: &[Message],
+    provider: &dyn AiProvider,
+    model_settings: &ModelSettings,
+) -> Option<f32> {
+    let schedule = *state
+        .settings
+        .settings()
+        .temperature_schedule_for(agent_name)?;
+    let turn = conversation
+        .iter()
+        .filter(|message| message.role == MessageRole::Assistant)
+        .count();
+    let (min, max) = provider.temperature_range(&model_settings.model);
+    Some(schedule.temperature_at(turn).clamp(min, max))
+}
+
+/// Re-runs the build after a turn that edited files and, while errors remain
+/// and the watch limits allow, feeds them back as the next user message.
+/// Runs when the agent would end the turn: on a final text reply, or on
+/// `complete_task` for agents that must finish through a tool (e.g. coder).
+/// Returns true when the conversation should continue.
+async fn watch_build(
+    state: &mut ActorState,
+    protocol: &TurnProtocol,
+    watch: &mut BuildWatch,
+) -> bool {
+    let config: BuildWatchConfig = state
+        .settings
+        .get_module_config(BuildWatchConfig::NAMESPACE);
+    if !config.enabled || !watch.has_new_edits(protocol.files_modified()) {
+        return false;
+    }
+
+    let errors = build_watch::build_errors(&state.workspace_roots).await;
+    let status = watch.check(&config, &errors, state.session_cost);
+    state.event_sender.send(ChatEvent::BuildWatch {
+        iteration: watch.iteration(),
+        max_iterations: config.max_iterations,
+        errors: errors.len() as u32,
+        status,
+    });
+    if status != BuildWatchStatus::Retrying {
+        return false;
+    }
+
+    let feedback = build_watch::feedback_message(&errors, watch.iteration(), config.max_iterations);
+    current_agent_mut(state, |a| match a.conversation.last_mut() {
+        // After complete_task the tool results are the last message
+        Some(last) if last.role == MessageRole::User => {
+            let mut blocks = std::mem::replace(&mut last.content, Content::empty()).into_blocks();
+            blocks.push(ContentBlock::Text(feedback));
+            last.content = Content::new(blocks);
+        }
+        _ => a.conversation.push(Message {
+            role: MessageRole::User,
+            content: Content::text_only(feedback),
+        }),
+    });
+    true
+}
+
+/// Intervenes after the agent repeated a tool call: either appends a nudge
+/// to the tool results it is about to read, or pauses for the user.
+/// Returns true when the conversation should continue.
+fn intervene_on_loop(state: &mut ActorState, repeated: &RepeatedCall) -> bool {
+    let action = state.settings.settings().loop_detection.action;
+    warn!(?repeated, ?action, "Agent is repeating a tool call");
+
+    match action {
+        LoopAction::Nudge => {
+            let nudge = loop_detection::nudge_message(repeated);
+            current_agent_mut(state, |a| {
+                if let Some(last) = a.conversation.last_mut() {
+                    if last.role == MessageRole::User {
+                        let mut blocks =
+                            std::mem::replace(&mut last.content, Content::empty()).into_blocks();
+                        blocks.push(ContentBlock::Text(nudge));
+                        last.content = Content::new(blocks);
+                    }
+                }
+            });
+            true
+        }
+        LoopAction::Pause => {
+            state.event_sender.send_message(ChatMessage::warning(format!(
+                "Paused: the agent called {} with the same arguments {} times without making progress. Reply to redirect it or ask it to continue.",
+                repeated.tool_name, repeated.count
+            )));
+            false
+        }
+    }
+}
+
 pub async fn send_ai_request(state: &mut ActorState, protocol: &mut TurnProtocol) -> Result<()> {
+    let mut build_watch = BuildWatch::new(state.session_cost);
+    let mut loop_detector = LoopDetector::default();
     loop {
         // Best-effort: a planner failure should never block the request.
         if let Err(error) = run_compaction_planner(state).await {
@@ -39,7 +172,7 @@ pub async fn send_ai_request(state: &mut ActorState, protocol: &mut TurnProtocol
         });
 
         let provider = state.provider.read().unwrap().clone();
-        let (request, model_settings, context_breakdown, _tools) = prepare_request(
+        let (mut request, mut model_settings, context_breakdown, _tools) = prepare_request(
             agent.as_ref(),
             &conversation,
             provider.as_ref(),
@@ -53,6 +186,18 @@ pub async fn send_ai_request(state: &mut ActorState, protocol: &mut TurnProtocol
         )
         .await?;
 
+        if let Some(temperature) = scheduled_temperature(
+            state,
+            agent.name(),
+            &conversation,
+            provider.as_ref(),
+            &model_settings,
+        ) {
+            request.model.temperature = Some(temperature);
+            model_settings.temperature = Some(temperature);
+        }
+
+        check_context_pressure(state, &context_breakdown);
         state.pending_context_breakdown = Some(context_breakdown);
 
         state.transition_timing_state(crate::chat::actor::TimingState::ProcessingAI);
@@ -74,6 +219,9 @@ pub async fn send_ai_request(state: &mut ActorState, protocol: &mut TurnProtocol
         if tool_calls.is_empty() {
             let is_sub_agent = state.spawn_module.stack_depth() > 1;
             if !is_sub_agent && !tools::current_agent(state, |a| a.agent.requires_tool_use()) {
+                if watch_build(state, protocol, &mut build_watch).await {
+                    continue;
+                }
                 break;
             }
             tools::current_agent_mut(state, |a| {
@@ -85,13 +233,29 @@ pub async fn send_ai_request(state: &mut ActorState, protocol: &mut TurnProtocol
             continue;
         }
 
+        let repeated = loop_detector.record(
+            &state.settings.settings().loop_detection,
+            protocol.files_modified(),
+            &tool_calls,
+        );
+        let completes_task = tool_calls
+            .iter()
+            .any(|call| call.name == CompleteTask::tool_name().as_str());
+
         match tools::execute_tool_calls(state, tool_calls, protocol).await {
             Ok(tool_results) => {
                 if tool_results.continue_conversation {
+                    if let Some(repeated) = repeated {
+                        if !intervene_on_loop(state, &repeated) {
+                            break;
+                        }
+                    }
                     continue;
-                } else {
-                    break;
                 }
+                if completes_task && watch_build(state, protocol, &mut build_watch).await {
+                    continue;
+                }
+                break;
             }
             Err(e) => {
                 state.event_sender.send(ChatEvent::RetryAttempt {
@@ -334,21 +498,20 @@ async fn send_request_streaming_with_retry(
     const MAX_BACKOFF_MS: u64 = 1000;
     const BACKOFF_MULTIPLIER: f64 = 2.0;
 
-    let mut attempt = 0;
+    // Each kind of error has its own budget, so e.g. earlier throttling does
+    // not use up the retries allowed for transient failures
+    let mut retryable_attempts = 0;
+    let mut transient_attempts = 0;
 
     loop {
         let provider = state.provider.read().unwrap().clone();
         let result = try_send_request_stream(&provider, &request).await;
 
-        let max_retries = match &result {
-            Err(AiError::Transient(_)) => MAX_TRANSIENT_RETRIES,
-            _ => MAX_RETRIES,
-        };
-
         match result {
             Ok(stream) => {
-                if attempt > 0 {
-                    info!("Streaming request succeeded after {} retries", attempt);
+                let retries = retryable_attempts + transient_attempts;
+                if retries > 0 {
+                    info!("Streaming request succeeded after {} retries", retries);
                 }
                 return Ok(stream);
             }
@@ -396,9 +559,20 @@ async fn send_request_streaming_with_retry(
                 continue;
             }
             Err(error) => {
-                if !should_retry(&error, attempt, max_retries) {
+                let (attempt, max_retries) = match &error {
+                    AiError::Transient(_) => (&mut transient_attempts, MAX_TRANSIENT_RETRIES),
+                    _ => (&mut retryable_attempts, MAX_RETRIES),
+                };
+                if !should_retry(&error, *attempt, max_retries) {
+                    if is_retryable(&error) {
+                        state.event_sender.send(ChatEvent::RetriesExhausted {
+                            attempts: *attempt,
+                            max_retries,
+                            error: error.to_string(),
+                        });
+                    }
                     warn!(
-                        attempt,
+                        attempt = *attempt,
                         max_retries,
                         "Streaming request failed after {} retries: {}",
                         attempt,
@@ -408,24 +582,24 @@ async fn send_request_streaming_with_retry(
                 }
 
                 let backoff_ms = calculate_backoff(
-                    attempt,
+                    *attempt,
                     INITIAL_BACKOFF_MS,
                     MAX_BACKOFF_MS,
                     BACKOFF_MULTIPLIER,
                 );
 
-                emit_retry_event(state, attempt + 1, max_retries, &error, backoff_ms);
+                emit_retry_event(state, *attempt + 1, max_retries, &error, backoff_ms);
 
                 warn!(
-                    attempt = attempt + 1,
-                    max_retries = MAX_RETRIES,
+                    attempt = *attempt + 1,
+                    max_retries,
                     backoff_ms,
                     error = %error,
                     "Streaming request failed, retrying after backoff"
                 );
 
                 sleep(Duration::from_millis(backoff_ms)).await;
-                attempt += 1;
+                *attempt += 1;
             }
         }
     }
@@ -448,9 +622,12 @@ fn emit_retry_event(
     state.event_sender.send(retry_event);
 }
 
+fn is_retryable(error: &AiError) -> bool {
+    matches!(error, AiError::Retryable(_)) || matches!(error, AiError::Transient(_))
+}
+
 fn should_retry(error: &AiError, attempt: u32, max_retries: u32) -> bool {
-    (matches!(error, AiError::Retryable(_)) || matches!(error, AiError::Transient(_)))
-        && attempt < max_retries
+    is_retryable(error) && attempt < max_retries
 }
 
 fn calculate_backoff(attempt: u32, initial_ms: u64, max_ms: u64, multiplier: f64) -> u64 {
@@ -1,8 +1,9 @@
 use crate::ai::model::{Model, ModelCost};
 use crate::ai::{
-    Content, Message, MessageRole, ModelSettings, ReasoningBudget, TokenUsage, ToolUseData,
+    Content, Message, MessageRole, ModelSettings, ReasoningBudget, ReasoningEffort, TokenUsage,
+    ToolUseData,
 };
-use crate::chat::actor::{create_provider, resume_session, TimingStat};
+use crate::chat::actor::{create_provider, resume_session, start_new_session, TimingStat};
 use crate::chat::request::select_model_for_agent;
 use crate::chat::tools::{current_agent, current_agent_mut};
 use crate::chat::{
@@ -13,13 +14,16 @@ use crate::chat::{
     },
 };
 
+use crate::mcp::command::{PROMPT_COMMAND, PROMPT_USAGE};
 use crate::module::{ContextComponentSelection, Module, SlashCommand};
+use crate::modules::context_management::planner::BYTES_PER_TOKEN;
 use crate::settings::config::{ProviderConfig, ReviewLevel};
 use chrono::Utc;
 use dirs;
 use serde_json::json;
 use std::fs;
 use std::iter::Peekable;
+use std::path::Path;
 use std::str::Chars;
 use std::sync::Arc;
 use toml;
@@ -39,7 +43,7 @@ fn handle_escape_sequence(chars: &mut Peekable<Chars>, current: &mut String, c:
     current.push(c);
 }
 
-fn parse_command_with_quotes(input: &str) -> Vec<String> {
+pub(crate) fn parse_command_with_quotes(input: &str) -> Vec<String> {
     let mut parts = Vec::new();
     let mut current = String::new();
     let mut in_quotes = false;
@@ -104,7 +108,10 @@ pub async fn process_command(state: &mut ActorState, command: &str) -> Vec<ChatM
 
     match command_name {
         "clear" => handle_clear_command(state).await,
+        "new" => handle_new_command(state),
         "context" => handle_context_command(state).await,
+        "reload" => handle_reload_command(state),
+        "export" => handle_export_command(state, &parts_refs).await,
         "model" => handle_model_command(state, &parts_refs).await,
         "settings" => handle_settings_command(state, &parts_refs).await,
 
@@ -119,6 +126,8 @@ pub async fn process_command(state: &mut ActorState, command: &str) -> Vec<ChatM
         "profile" => handle_profile_command(state, &parts_refs).await,
         "sessions" => handle_sessions_command(state, &parts_refs).await,
         "debug_ui" => handle_debug_ui_command(state).await,
+        #[cfg(feature = "voice")]
+        "voice" => handle_voice_command(state, &parts_refs).await,
         _ => vec![create_message(
             format!("Unknown command: /{}", command_name),
             MessageSender::Error,
@@ -149,23 +158,48 @@ pub fn is_known_command(input: &str, modules: &[Arc<dyn Module>]) -> bool {
 
 /// Get core commands (not from modules)
 fn get_core_commands() -> Vec<CommandInfo> {
-    vec![
+    #[allow(unused_mut)]
+    let mut commands = vec![
         CommandInfo {
             name: "clear".to_string(),
             description: r"Clear the conversation history".to_string(),
             usage: "/clear".to_string(),
             hidden: false,
         },
+        CommandInfo {
+            name: "new".to_string(),
+            description: r"Save this session and start a fresh one".to_string(),
+            usage: "/new".to_string(),
+            hidden: false,
+        },
         CommandInfo {
             name: "context".to_string(),
             description: r"Show what files would be included in the AI context".to_string(),
             usage: "/context".to_string(),
             hidden: false,
         },
+        CommandInfo {
+            name: "reload".to_string(),
+            description: r"Re-read steering documents and skills from disk".to_string(),
+            usage: "/reload".to_string(),
+            hidden: false,
+        },
+        CommandInfo {
+            name: PROMPT_COMMAND.to_string(),
+            description: r"Send a prompt provided by an MCP server".to_string(),
+            usage: PROMPT_USAGE.to_string(),
+            hidden: false,
+        },
+        CommandInfo {
+            name: "export".to_string(),
+            description: r"Write the full assembled AI context to a file".to_string(),
+            usage: "/export context <path> [--force]".to_string(),
+            hidden: false,
+        },
         CommandInfo {
             name: r"model".to_string(),
             description: r"Set the AI model for all agents".to_string(),
-            usage: r"/model <name> [temperature=0.7] [max_tokens=4096] [top_p=1.0] [reasoning_budget=...]".to_string(),
+            usage: r"/model <name> [temperature=0.7] [max_tokens=4096] [top_p=1.0] [reasoning_budget=...] [reasoning_effort=...]".to_string(),
             hidden: false,
         },
         CommandInfo {
@@ -177,7 +211,7 @@ fn get_core_commands() -> Vec<CommandInfo> {
         CommandInfo {
             name: "settings".to_string(),
             description: "Display current settings and configuration".to_string(),
-            usage: "/settings or /settings save".to_string(),
+            usage: "/settings [save|validate]".to_string(),
             hidden: false,
         },
 
@@ -208,7 +242,7 @@ fn get_core_commands() -> Vec<CommandInfo> {
         CommandInfo {
             name: "agentmodel".to_string(),
             description: "Set the AI model for a specific agent with tunings".to_string(),
-            usage: "/agentmodel <agent_name> <model_name> [temperature=0.7] [max_tokens=4096] [top_p=1.0] [reasoning_budget=...]".to_string(),
+            usage: "/agentmodel <agent_name> <model_name> [temperature=0.7] [max_tokens=4096] [top_p=1.0] [reasoning_budget=...] [reasoning_effort=...]".to_string(),
             hidden: false,
         },
         CommandInfo {
@@ -248,7 +282,57 @@ fn get_core_commands() -> Vec<CommandInfo> {
             usage: "/debug_ui".to_string(),
             hidden: true,
         },
-    ]
+    ];
+
+    #[cfg(feature = "voice")]
+    commands.push(CommandInfo {
+        name: "voice".to_string(),
+        description: "Voice input settings".to_string(),
+        usage: "/voice devices".to_string(),
+        hidden: false,
+    });
+
+    commands
+}
+
+/// Maps a spoken transcript to a slash command. Without a trigger word the
+/// transcript must be nothing but a visible command's name, e.g. "Clear." or
+/// "review level"; arguments need the trigger, e.g. "slash sessions list".
+/// Multi-word names are spoken with spaces for `_`. Returns None for
+/// anything else, so ordinary speech that happens to start with a command
+/// name goes to the model.
+pub fn match_spoken_command(transcript: &str, commands: &[CommandInfo]) -> Option<String> {
+    let words: Vec<String> = transcript
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric() && c != '_')
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+    let (triggered, words) = match words.first().map(String::as_str) {
+        Some("slash" | "command") => (true, &words[1..]),
+        _ => (false, &words[..]),
+    };
+
+    let is_command = |name: &str| commands.iter().any(|cmd| !cmd.hidden && cmd.name == name);
+    let (name, rest) = match words {
+        [first, second, rest @ ..] if is_command(&format!("{first}_{second}")) => {
+            (format!("{first}_{second}"), rest)
+        }
+        [first, rest @ ..] if is_command(first) => (first.clone(), rest),
+        _ => return None,
+    };
+    if !triggered && !rest.is_empty() {
+        return None;
+    }
+
+    let mut command = format!("/{name}");
+    for arg in rest {
+        command.push(' ');
+        command.push_str(arg);
+    }
+    Some(command)
 }
 
 /// Get all available commands with their descriptions
@@ -285,6 +369,7 @@ async fn handle_clear_command(state: &mut ActorState) -> Vec<ChatMessage> {
     state.event_sender.clear_history();
 
     state.clear_conversation();
+    state.steering.reload();
     current_agent_mut(state, |a| {
         a.conversation.clear();
         a.announced = false;
@@ -295,21 +380,124 @@ async fn handle_clear_command(state: &mut ActorState) -> Vec<ChatMessage> {
     )]
 }
 
+fn handle_reload_command(state: &ActorState) -> Vec<ChatMessage> {
+    state.steering.reload();
+    let (custom, external) = state.steering.document_counts();
+    let mut lines = vec![format!(
+        "Steering: {custom} custom and {external} external document(s) loaded"
+    )];
+    lines.extend(state.modules.iter().filter_map(|module| module.reload()));
+
+    vec![create_message(
+        format!("Reloaded.\n{}", lines.join("\n")),
+        MessageSender::System,
+    )]
+}
+
+fn handle_new_command(state: &mut ActorState) -> Vec<ChatMessage> {
+    let message = match start_new_session(state) {
+        Some(session_id) => format!(
+            "Started new session '{session_id}'. The previous session can be resumed with /sessions."
+        ),
+        None => "Started new session.".to_string(),
+    };
+    vec![create_message(message, MessageSender::System)]
+}
+
 async fn handle_context_command(state: &ActorState) -> Vec<ChatMessage> {
     let context_content = state
         .context_builder
-        .build(&ContextComponentSelection::All, &state.modules)
+        .build(
+            &state.settings.settings(),
+            &ContextComponentSelection::All,
+            &state.modules,
+        )
         .await;
 
     let message = if context_content.is_empty() {
         "=== Current Context ===\n\nNo context components configured.".to_string()
     } else {
-        format!("=== Current Context ===\n{}", context_content)
+        format!(
+            "=== Current Context ===\n{}\n\nEstimated size: ~{} tokens ({} bytes)",
+            context_content,
+            context_content.len() / BYTES_PER_TOKEN,
+            context_content.len()
+        )
     };
 
     vec![create_message(message, MessageSender::System)]
 }
 
+/// Writes the full context, as the model would see it, to a file. Unlike
+/// `/context` nothing is truncated by the terminal. Relative paths are
+/// resolved against the first workspace root. An existing file is only
+/// replaced with `--force`.
+async fn handle_export_command(state: &ActorState, parts: &[&str]) -> Vec<ChatMessage> {
+    let force = parts.contains(&"--force");
+    let args: Vec<&str> = parts
+        .iter()
+        .copied()
+        .filter(|part| *part != "--force")
+        .collect();
+    let (Some(&"context"), Some(path), None) = (args.get(1), args.get(2), args.get(3)) else {
+        return vec![create_message(
+            "Usage: /export context <path> [--force]".to_string(),
+            MessageSender::Error,
+        )];
+    };
+
+    let path = Path::new(path);
+    let path = match state.workspace_roots.first() {
+        Some(root) if path.is_relative() => root.join(path),
+        _ => path.to_path_buf(),
+    };
+    if !force && path.exists() {
+        return vec![create_message(
+            format!(
+                "{} already exists; use /export context <path> --force to overwrite it",
+                path.display()
+            ),
+            MessageSender::Error,
+        )];
+    }
+
+    let context_content = state
+        .context_builder
+        .build(
+            &state.settings.settings(),
+            &ContextComponentSelection::All,
+            &state.modules,
+        )
+        .await;
+    let contents = format!(
+        "=== Current Context ===\nEstimated size: ~{} tokens ({} bytes)\n\n{}",
+        context_content.len() / BYTES_PER_TOKEN,
+        context_content.len(),
+        context_content
+    );
+
+    let result = match path.parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+    .and_then(|_| fs::write(&path, contents));
+    match result {
+        Ok(()) => vec![create_message(
+            format!(
+                "Exported context (~{} tokens, {} bytes) to {}",
+                context_content.len() / BYTES_PER_TOKEN,
+                context_content.len(),
+                path.display()
+            ),
+            MessageSender::System,
+        )],
+        Err(e) => vec![create_message(
+            format!("Failed to export context to {}: {e}", path.display()),
+            MessageSender::Error,
+        )],
+    }
+}
+
 async fn handle_settings_command(state: &ActorState, parts: &[&str]) -> Vec<ChatMessage> {
     let settings = state.settings.settings();
 
@@ -338,6 +526,21 @@ async fn handle_settings_command(state: &ActorState, parts: &[&str]) -> Vec<Chat
                 MessageSender::Error,
             )],
         }
+    } else if parts.len() > 1 && parts[1] == "validate" {
+        let provider = state.provider.read().unwrap().clone();
+        let agents = state.agent_catalog.get_agent_names();
+        let issues = crate::settings::validate::validate(&settings, provider.as_ref(), &agents);
+        if issues.is_empty() {
+            return vec![create_message(
+                "Settings are valid.".to_string(),
+                MessageSender::System,
+            )];
+        }
+        let mut message = format!("Found {} settings issue(s):\n", issues.len());
+        for issue in issues {
+            message.push_str(&format!("  - {issue}\n"));
+        }
+        vec![create_message(message, MessageSender::Warning)]
     } else {
         vec![create_message(
             format!("Unknown arguments: {parts:?}"),
@@ -489,13 +692,13 @@ async fn handle_models_command(state: &ActorState) -> Vec<ChatMessage> {
 async fn handle_model_command(state: &mut ActorState, parts: &[&str]) -> Vec<ChatMessage> {
     if parts.len() < 2 {
         return vec![create_message(
-            "Usage: /model <name> [key=value...]\nValid keys: temperature, max_tokens, top_p, reasoning_budget\nUse /models to list available models.".to_string(),
+            "Usage: /model <name> [key=value...]\nValid keys: temperature, max_tokens, top_p, reasoning_budget, reasoning_effort\nUse /models to list available models.".to_string(),
             MessageSender::System,
         )];
     }
 
     let model_name = parts[1];
-    let model = match Model::from_name(model_name) {
+    let model = match state.settings.settings().resolve_model(model_name) {
         Some(m) => m,
         None => {
             return vec![create_message(
@@ -530,6 +733,9 @@ async fn handle_model_command(state: &mut ActorState, parts: &[&str]) -> Vec<Cha
         overrides.push(format!("top_p={}", settings.top_p.unwrap()));
     }
     overrides.push(format!("reasoning_budget={}", settings.reasoning_budget));
+    if let Some(effort) = settings.effort {
+        overrides.push(format!("reasoning_effort={effort}"));
+    }
 
     let overrides_str = if overrides.is_empty() {
         "".to_string()
@@ -549,7 +755,7 @@ async fn handle_model_command(state: &mut ActorState, parts: &[&str]) -> Vec<Cha
 
 async fn handle_agentmodel_command(state: &mut ActorState, parts: &[&str]) -> Vec<ChatMessage> {
     if parts.len() < 3 {
-        return vec![create_message(format!("Usage: /agentmodel <agent_name> <model_name> [temperature=0.7] [max_tokens=4096] [top_p=1.0] [reasoning_budget=...]\nValid agents: {}", state.agent_catalog.get_agent_names().join(", ")), MessageSender::System)];
+        return vec![create_message(format!("Usage: /agentmodel <agent_name> <model_name> [temperature=0.7] [max_tokens=4096] [top_p=1.0] [reasoning_budget=...] [reasoning_effort=...]\nValid agents: {}", state.agent_catalog.get_agent_names().join(", ")), MessageSender::System)];
     }
     let agent_name = parts[1];
     if !state
@@ -567,7 +773,7 @@ async fn handle_agentmodel_command(state: &mut ActorState, parts: &[&str]) -> Ve
         )];
     }
     let model_name = parts[2];
-    let model = match Model::from_name(model_name) {
+    let model = match state.settings.settings().resolve_model(model_name) {
         Some(m) => m,
         None => {
             return vec![create_message(
@@ -595,6 +801,9 @@ async fn handle_agentmodel_command(state: &mut ActorState, parts: &[&str]) -> Ve
         overrides.push(format!("top_p={v}"));
     }
     overrides.push(format!("reasoning_budget={}", settings.reasoning_budget));
+    if let Some(effort) = settings.effort {
+        overrides.push(format!("reasoning_effort={effort}"));
+    }
 
     let overrides_str = if overrides.is_empty() {
         "".to_string()
@@ -645,7 +854,13 @@ fn parse_model_settings_overrides(
                 };
                 settings.reasoning_budget = reasoning_budget;
             }
-            _ => return Err(format!("Unknown parameter: {key}. Valid parameters: temperature, max_tokens, top_p, reasoning_budget")),
+            "reasoning_effort" => {
+                let effort = ReasoningEffort::from_name(value_str).ok_or_else(|| {
+                    format!("Unsupported reasoning effort: {value_str}. Must be one of minimal, low, medium, high or xhigh")
+                })?;
+                settings.effort = Some(effort);
+            }
+            _ => return Err(format!("Unknown parameter: {key}. Valid parameters: temperature, max_tokens, top_p, reasoning_budget, reasoning_effort")),
         }
     }
     Ok(settings)
@@ -859,6 +1074,7 @@ async fn handle_provider_add_command(state: &mut ActorState, parts: &[&str]) ->
                     profile,
                     region,
                     mantle_region: None,
+                    max_concurrent_requests: None,
                 },
                 "bedrock",
             )
@@ -872,7 +1088,13 @@ async fn handle_provider_add_command(state: &mut ActorState, parts: &[&str]) ->
                 )];
             }
 
-            (ProviderConfig::OpenRouter { api_key }, "openrouter")
+            (
+                ProviderConfig::OpenRouter {
+                    api_key,
+                    max_concurrent_requests: None,
+                },
+                "openrouter",
+            )
         }
         other => {
             return vec![create_message(
@@ -932,6 +1154,50 @@ async fn handle_provider_add_command(state: &mut ActorState, parts: &[&str]) ->
     messages
 }
 
+#[cfg(feature = "voice")]
+async fn handle_voice_command(state: &ActorState, parts: &[&str]) -> Vec<ChatMessage> {
+    match parts.get(1).copied() {
+        Some("devices") => {}
+        _ => {
+            return vec![create_message(
+                "Usage: /voice devices".to_string(),
+                MessageSender::Error,
+            )]
+        }
+    }
+
+    let devices = match crate::voice::audio::capture::list_input_devices() {
+        Ok(devices) => devices,
+        Err(e) => {
+            return vec![create_message(
+                format!("Failed to list input devices: {e:?}"),
+                MessageSender::Error,
+            )]
+        }
+    };
+
+    let configured = state.settings.settings().voice.input_device;
+    let mut message = String::from("Input devices:\n");
+    if devices.is_empty() {
+        message.push_str("  (none found)\n");
+    }
+    for device in &devices {
+        let marker = if configured.as_deref() == Some(device.as_str()) {
+            " (selected)"
+        } else {
+            ""
+        };
+        message.push_str(&format!("  {device}{marker}\n"));
+    }
+    if let Some(name) = configured.filter(|name| !devices.contains(name)) {
+        message.push_str(&format!(
+            "\nConfigured input_device \"{name}\" was not found; the default device will be used."
+        ));
+    }
+
+    vec![create_message(message, MessageSender::System)]
+}
+
 pub async fn handle_debug_ui_command(state: &mut ActorState) -> Vec<ChatMessage> {
     state
         .event_sender
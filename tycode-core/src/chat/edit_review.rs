@@ -0,0 +1,263 @@
+//! Piecewise review of proposed edits.
+//!
+//! With `propose_edits` enabled, edit tools are validated but not applied;
+//! each edit is parked here and announced with `ChatEvent::EditsProposed`.
+//! The user then accepts or rejects the edits one at a time (a
+//! `ChatActorMessage::ReviewEdit` or a reply such as `accept 2`). Accepted
+//! edits are applied through the tool's normal write path. Once nothing is
+//! left pending, the outcomes are sent to the agent and the turn resumes.
+//!
+//! The review is saved with the session. Handles cannot be saved, so each
+//! pending edit keeps its tool arguments and is validated again through its
+//! tool when the session is resumed; `/new` discards the review.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::chat::actor::ActorState;
+use crate::chat::events::ProposedEdit;
+use crate::file::modify::confirm::proposes_edits;
+use crate::tools::r#trait::{ToolCallHandle, ToolOutput, ToolRequest};
+use crate::tools::registry::ToolRegistry;
+
+struct PendingEdit {
+    edit: ProposedEdit,
+    arguments: Value,
+    handle: Box<dyn ToolCallHandle>,
+}
+
+/// The review as stored with the session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedReview {
+    #[serde(default)]
+    pub pending: Vec<SavedEdit>,
+    /// Outcomes not yet sent to the agent.
+    #[serde(default)]
+    pub outcomes: Vec<String>,
+}
+
+impl SavedReview {
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty() && self.outcomes.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedEdit {
+    #[serde(flatten)]
+    pub edit: ProposedEdit,
+    pub arguments: Value,
+}
+
+/// Which pending edits a review decision applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditTarget {
+    One(u64),
+    All,
+}
+
+#[derive(Default)]
+pub struct EditReview {
+    next_id: u64,
+    pending: Vec<PendingEdit>,
+    outcomes: Vec<String>,
+}
+
+impl EditReview {
+    /// Parks a validated edit and returns its description for the UI.
+    pub fn propose(
+        &mut self,
+        handle: Box<dyn ToolCallHandle>,
+        tool_call_id: String,
+        tool_name: String,
+        arguments: Value,
+        file_path: String,
+    ) -> ProposedEdit {
+        self.next_id += 1;
+        let edit = ProposedEdit {
+            edit_id: self.next_id,
+            tool_call_id,
+            tool_name,
+            file_path,
+        };
+        self.pending.push(PendingEdit {
+            edit: edit.clone(),
+            arguments,
+            handle,
+        });
+        edit
+    }
+
+    pub fn save(&self) -> SavedReview {
+        SavedReview {
+            pending: self
+                .pending
+                .iter()
+                .map(|pending| SavedEdit {
+                    edit: pending.edit.clone(),
+                    arguments: pending.arguments.clone(),
+                })
+                .collect(),
+            outcomes: self.outcomes.clone(),
+        }
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Accepts or rejects the targeted edits. Returns one outcome line per
+    /// resolved edit, or an error for an unknown edit id.
+    pub async fn resolve(
+        &mut self,
+        target: EditTarget,
+        accept: bool,
+    ) -> Result<Vec<String>, String> {
+        let resolved: Vec<PendingEdit> = match target {
+            EditTarget::All => std::mem::take(&mut self.pending),
+            EditTarget::One(edit_id) => {
+                let Some(index) = self.pending.iter().position(|p| p.edit.edit_id == edit_id)
+                else {
+                    return Err(format!("No pending edit with id {edit_id}"));
+                };
+                vec![self.pending.remove(index)]
+            }
+        };
+
+        let mut outcomes = Vec::new();
+        for PendingEdit { edit, handle, .. } in resolved {
+            let outcome = if !accept {
+                format!(
+                    "The user rejected edit {} to {}; the file was not changed.",
+                    edit.edit_id, edit.file_path
+                )
+            } else {
+                match handle.execute().await {
+                    ToolOutput::Result {
+                        content, is_error, ..
+                    } if is_error => format!(
+                        "The user accepted edit {} to {}, but applying it failed: {content}",
+                        edit.edit_id, edit.file_path
+                    ),
+                    _ => format!(
+                        "The user accepted edit {} to {}; it has been applied.",
+                        edit.edit_id, edit.file_path
+                    ),
+                }
+            };
+            outcomes.push(outcome);
+        }
+        self.outcomes.extend(outcomes.iter().cloned());
+        Ok(outcomes)
+    }
+
+    /// The outcomes of every edit resolved since the last call, once no edit
+    /// is left pending.
+    pub fn take_outcomes(&mut self) -> Option<String> {
+        if self.has_pending() || self.outcomes.is_empty() {
+            return None;
+        }
+        Some(std::mem::take(&mut self.outcomes).join("\n"))
+    }
+}
+
+/// Replaces the review with one saved with a resumed session. An edit that
+/// can no longer be proposed (its file changed, or the agent no longer has
+/// edits proposed) is dropped and the agent is told with the next outcomes.
+pub async fn restore(state: &mut ActorState, saved: SavedReview) {
+    let mut review = EditReview {
+        outcomes: saved.outcomes,
+        ..EditReview::default()
+    };
+    if saved.pending.is_empty() {
+        state.edit_review = review;
+        return;
+    }
+
+    let agent = state.spawn_module.current_agent_name().unwrap_or_default();
+    let settings = state.settings.settings();
+    let registry = ToolRegistry::new(
+        crate::spawn::build_tools(
+            &state.modules,
+            state.spawn_module.catalog().clone(),
+            &agent,
+            settings.orchestration_mode,
+            settings.complete_task_guard,
+            settings.security.mode,
+        )
+        .await,
+    );
+
+    for SavedEdit { edit, arguments } in saved.pending {
+        review.next_id = review.next_id.max(edit.edit_id);
+        let handle = match registry.get_tool_executor_by_name(&edit.tool_name) {
+            Some(_) if !proposes_edits(&state.settings, &agent) => {
+                Err("edits are no longer proposed for review".to_string())
+            }
+            Some(tool) => {
+                let request = ToolRequest::new(arguments.clone(), edit.tool_call_id.clone())
+                    .with_agent(agent.clone());
+                match tool.process(&request).await {
+                    Ok(handle) => match handle.execute().await {
+                        ToolOutput::ProposeEdit { handle, .. } => Ok(handle),
+                        _ => Err("the edit could not be proposed again".to_string()),
+                    },
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+            None => Err(format!("{} is no longer available", edit.tool_name)),
+        };
+        match handle {
+            Ok(handle) => review.pending.push(PendingEdit {
+                edit,
+                arguments,
+                handle,
+            }),
+            Err(reason) => review.outcomes.push(format!(
+                "Edit {} to {} was dropped when the session was resumed ({reason}); the file was not changed.",
+                edit.edit_id, edit.file_path
+            )),
+        }
+    }
+    state.edit_review = review;
+}
+
+/// Parses a typed review reply: `accept 2`, `reject 3`, `accept all` or
+/// `reject all` (`yes`/`no` work as aliases).
+pub fn parse_review_reply(reply: &str) -> Option<(EditTarget, bool)> {
+    let mut words = reply.split_whitespace();
+    let accept = match words.next()?.to_lowercase().as_str() {
+        "accept" | "yes" | "y" => true,
+        "reject" | "no" | "n" => false,
+        _ => return None,
+    };
+    let target = match words.next()? {
+        "all" => EditTarget::All,
+        id => EditTarget::One(id.trim_start_matches('#').parse().ok()?),
+    };
+    if words.next().is_some() {
+        return None;
+    }
+    Some((target, accept))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_review_replies() {
+        assert_eq!(
+            parse_review_reply("accept 2"),
+            Some((EditTarget::One(2), true))
+        );
+        assert_eq!(
+            parse_review_reply("Reject #3"),
+            Some((EditTarget::One(3), false))
+        );
+        assert_eq!(parse_review_reply("yes all"), Some((EditTarget::All, true)));
+        assert_eq!(parse_review_reply("accept"), None);
+        assert_eq!(parse_review_reply("accept the first one"), None);
+        assert_eq!(parse_review_reply("please fix the bug"), None);
+    }
+}
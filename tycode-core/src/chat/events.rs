@@ -1,7 +1,8 @@
 use crate::ai::{
-    model::Model, ContextBreakdown, ImageData, ReasoningData, TokenUsage, ToolUseData,
+    model::Model, ContextBreakdown, ImageData, ImageGenerationStatus, ReasoningData, TokenUsage,
+    ToolUseData,
 };
-use crate::modules::task_list::TaskList;
+use crate::modules::task_list::TaskListUpdate;
 use crate::orchestration::events::OrchestrationEvent;
 use crate::persistence::session::SessionMetadata;
 use chrono::Utc;
@@ -62,7 +63,15 @@ pub enum ChatEvent {
         error: String,
         backoff_ms: u64,
     },
-    TaskUpdate(TaskList),
+    /// A retryable provider error outlasted every retry and the request
+    /// failed. Sent in addition to the error message so UIs can tell a flaky
+    /// provider apart from a request that failed outright on the first try.
+    RetriesExhausted {
+        attempts: u32,
+        max_retries: u32,
+        error: String,
+    },
+    TaskUpdate(TaskListUpdate),
     SessionsList {
         sessions: Vec<SessionMetadata>,
     },
@@ -94,6 +103,49 @@ pub enum ChatEvent {
     RootAgentChanged {
         agent: String,
     },
+    /// Interim speech transcript for live captions; each one replaces the
+    /// previous. Never persisted or sent to the model — the final transcript
+    /// arrives as a normal user message.
+    VoicePartialTranscript {
+        text: String,
+    },
+    /// The estimated request size crossed the configured fraction of the
+    /// model's context window. Sent once per crossing rather than every
+    /// turn; UIs may suggest `/compact`.
+    ContextWarning {
+        used_tokens: u32,
+        limit_tokens: u32,
+    },
+    /// Recap of the tools used during the turn that just ended, e.g.
+    /// "read 3 files, modified main.rs (+5/-1), cargo build: ok". Sent only
+    /// for turns that called tools.
+    ToolSummary {
+        calls: u32,
+        failed: u32,
+        summary: String,
+    },
+    /// Build watch re-ran the build after the agent's edits. `Retrying`
+    /// means the errors were fed back for another attempt; every other
+    /// status ends the loop.
+    BuildWatch {
+        iteration: u32,
+        max_iterations: u32,
+        errors: u32,
+        status: BuildWatchStatus,
+    },
+    /// Edits held for piecewise review (`propose_edits`). Each edit's diff
+    /// was already sent as the `ToolRequest` with the same `tool_call_id`.
+    /// Nothing is written until the user accepts it with
+    /// `ChatActorMessage::ReviewEdit`.
+    EditsProposed {
+        edits: Vec<ProposedEdit>,
+    },
+    /// Progress of the `generate_image` call with the same `tool_call_id`,
+    /// sent while the provider is still working on it.
+    ImageGenerationProgress {
+        tool_call_id: String,
+        status: ImageGenerationStatus,
+    },
     Error(String),
 }
 
@@ -109,6 +161,24 @@ impl ChatEvent {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildWatchStatus {
+    Retrying,
+    Clean,
+    IterationLimit,
+    CostLimit,
+    NoProgress,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposedEdit {
+    pub edit_id: u64,
+    pub tool_call_id: String,
+    pub tool_name: String,
+    pub file_path: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleSchemaInfo {
     pub namespace: String,
@@ -353,12 +423,116 @@ pub enum ToolExecutionResult {
     Error {
         short_message: String,
         detailed_message: String,
+        /// Set by tools that know why they failed; otherwise filled in by
+        /// dispatch from the error message.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        category: Option<ToolErrorCategory>,
     },
     Other {
         result: serde_json::Value,
     },
 }
 
+/// Broad reason a tool call failed, surfaced to the model alongside the error
+/// text so it can decide whether to retry, fix its arguments, or move on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolErrorCategory {
+    NotFound,
+    PermissionDenied,
+    InvalidArgument,
+    Conflict,
+    Transient,
+    /// Not run because the turn exceeded a limit on tool calls. Kept apart
+    /// from `Transient` so the model defers the call instead of retrying it
+    /// straight away.
+    LimitExceeded,
+}
+
+impl ToolErrorCategory {
+    /// Best-effort mapping of an existing error message to a category.
+    /// Returns None when nothing in the message is recognizable.
+    pub fn classify(message: &str) -> Option<Self> {
+        let message = message.to_lowercase();
+        let matches = |needles: &[&str]| needles.iter().any(|n| message.contains(n));
+
+        if matches(&[
+            "timed out",
+            "timeout",
+            "temporarily",
+            "connection reset",
+            "connection refused",
+            "rate limit",
+            "try again",
+        ]) {
+            Some(Self::Transient)
+        } else if matches(&[
+            "permission denied",
+            "not allowed",
+            "access denied",
+            "outside the workspace",
+            "outside workspace",
+            "not available for current agent",
+            "forbidden",
+        ]) {
+            Some(Self::PermissionDenied)
+        } else if matches(&[
+            "already exists",
+            "conflict",
+            "modified since",
+            "match not found",
+            "appears more than once",
+        ]) {
+            // Checked before NotFound: a search block that no longer matches
+            // means the file changed underneath the model.
+            Some(Self::Conflict)
+        } else if matches(&[
+            "not found",
+            "no such file",
+            "does not exist",
+            "unknown agent",
+            "unknown tool",
+        ]) {
+            Some(Self::NotFound)
+        } else if matches(&[
+            "invalid",
+            "missing",
+            "required",
+            "expected",
+            "must be",
+            "unknown field",
+            "failed to parse",
+        ]) {
+            Some(Self::InvalidArgument)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::PermissionDenied => "permission_denied",
+            Self::InvalidArgument => "invalid_argument",
+            Self::Conflict => "conflict",
+            Self::Transient => "transient",
+            Self::LimitExceeded => "limit_exceeded",
+        }
+    }
+
+    /// One-line hint appended to the model-facing error.
+    pub fn guidance(self) -> &'static str {
+        match self {
+            Self::NotFound => "Check the name or path before retrying.",
+            Self::PermissionDenied => "Retrying will not help; take a different approach.",
+            Self::InvalidArgument => "Fix the arguments and call the tool again.",
+            Self::Conflict => "Re-read the current state, then retry against it.",
+            Self::Transient => "This may succeed if retried unchanged.",
+            Self::LimitExceeded => "Issue this call again in a later turn.",
+        }
+    }
+}
+
 /// A small wrapper over the `event_tx` for convienance.
 #[derive(Clone)]
 pub struct EventSender {
@@ -0,0 +1,196 @@
+//! No-progress loop detection: spots an agent repeating the same tool call
+//! (same tool, same arguments) within a turn, e.g. re-reading one file or
+//! re-running a failing command, so the turn can nudge it or pause.
+//!
+//! Only the last `window` calls are remembered; a call counts as a loop once
+//! it appears `threshold` times among them. The window starts over whenever
+//! files were edited since the previous batch, so an edit/re-run cycle (e.g.
+//! fixing code and running `cargo test` again) is progress, not a loop.
+
+use std::collections::VecDeque;
+
+use serde_json::Value;
+
+use crate::ai::ToolUseData;
+use crate::settings::config::LoopDetectionSettings;
+
+/// A tool call the agent has repeated past the threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepeatedCall {
+    pub tool_name: String,
+    pub count: usize,
+}
+
+/// Per-turn history of recent tool-call signatures.
+#[derive(Debug, Default)]
+pub struct LoopDetector {
+    recent: VecDeque<String>,
+    edits_seen: usize,
+}
+
+impl LoopDetector {
+    /// Records a batch of tool calls and returns the most repeated one if it
+    /// reached the threshold. `files_modified` is the turn's running count of
+    /// successful file edits.
+    pub fn record(
+        &mut self,
+        settings: &LoopDetectionSettings,
+        files_modified: usize,
+        tool_calls: &[ToolUseData],
+    ) -> Option<RepeatedCall> {
+        if files_modified > self.edits_seen {
+            self.edits_seen = files_modified;
+            self.recent.clear();
+        }
+        if !settings.enabled || settings.threshold == 0 {
+            return None;
+        }
+
+        let mut repeated: Option<RepeatedCall> = None;
+        for call in tool_calls {
+            let signature = signature(call);
+            self.recent.push_back(signature.clone());
+            while self.recent.len() > settings.window.max(1) {
+                self.recent.pop_front();
+            }
+
+            let count = self.recent.iter().filter(|s| **s == signature).count();
+            if count >= settings.threshold && count > repeated.as_ref().map_or(0, |r| r.count) {
+                repeated = Some(RepeatedCall {
+                    tool_name: call.name.clone(),
+                    count,
+                });
+            }
+        }
+        repeated
+    }
+}
+
+/// Tool name plus arguments, with object keys sorted so the same arguments
+/// in a different order still match.
+fn signature(call: &ToolUseData) -> String {
+    format!("{}:{}", call.name, canonical(&call.arguments))
+}
+
+fn canonical(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = entries
+                .into_iter()
+                .map(|(key, value)| format!("{}:{}", Value::from(key.as_str()), canonical(value)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// The note added to the agent's tool results when it is looping.
+pub fn nudge_message(repeated: &RepeatedCall) -> String {
+    format!(
+        "You have called {} with the same arguments {} times this turn without making progress. Stop repeating it and try a different approach.",
+        repeated.tool_name, repeated.count
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(name: &str, arguments: serde_json::Value) -> ToolUseData {
+        ToolUseData {
+            id: "id".to_string(),
+            name: name.to_string(),
+            arguments,
+        }
+    }
+
+    #[test]
+    fn identical_calls_are_detected_at_the_threshold() {
+        let settings = LoopDetectionSettings::default();
+        let mut detector = LoopDetector::default();
+        let read = call("read_file", json!({"path": "a.rs", "limit": 10}));
+
+        assert_eq!(
+            detector.record(&settings, 0, std::slice::from_ref(&read)),
+            None
+        );
+        assert_eq!(
+            detector.record(&settings, 0, &[call("read_file", json!({"path": "b.rs"}))]),
+            None
+        );
+        assert_eq!(detector.record(&settings, 0, &[read]), None);
+        assert_eq!(
+            detector.record(
+                &settings,
+                0,
+                &[call("read_file", json!({"limit": 10, "path": "a.rs"}))]
+            ),
+            Some(RepeatedCall {
+                tool_name: "read_file".to_string(),
+                count: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn repeats_outside_the_window_are_forgotten() {
+        let settings = LoopDetectionSettings {
+            window: 3,
+            threshold: 2,
+            ..Default::default()
+        };
+        let mut detector = LoopDetector::default();
+        let run = call("run_build_test", json!({"command": "cargo test"}));
+
+        detector.record(&settings, 0, std::slice::from_ref(&run));
+        detector.record(
+            &settings,
+            0,
+            &[
+                call("read_file", json!({"path": "a.rs"})),
+                call("read_file", json!({"path": "b.rs"})),
+                call("read_file", json!({"path": "c.rs"})),
+            ],
+        );
+        assert_eq!(detector.record(&settings, 0, &[run]), None);
+    }
+
+    #[test]
+    fn edits_start_the_window_over() {
+        let settings = LoopDetectionSettings {
+            threshold: 2,
+            ..Default::default()
+        };
+        let mut detector = LoopDetector::default();
+        let test = call("bash", json!({"command": "cargo test"}));
+        let edit = call("modify_file", json!({"file_path": "a.rs"}));
+
+        detector.record(&settings, 0, std::slice::from_ref(&test));
+        detector.record(&settings, 0, std::slice::from_ref(&edit));
+        assert_eq!(
+            detector.record(&settings, 1, std::slice::from_ref(&test)),
+            None
+        );
+        detector.record(&settings, 1, &[edit]);
+        assert_eq!(
+            detector.record(&settings, 2, std::slice::from_ref(&test)),
+            None
+        );
+
+        assert_eq!(
+            detector.record(&settings, 2, &[test]),
+            Some(RepeatedCall {
+                tool_name: "bash".to_string(),
+                count: 2,
+            })
+        );
+    }
+}
@@ -1,9 +1,12 @@
 pub mod actor;
 pub mod ai;
 pub mod commands;
+pub mod edit_review;
 pub mod events;
+pub mod loop_detection;
 pub mod protocol;
 pub mod request;
+pub mod tool_summary;
 pub mod tools;
 
 pub use actor::{ChatActor, ChatActorBuilder, ChatActorMessage};
@@ -2,7 +2,10 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::ai::{Content, ContentBlock, Message, MessageRole, ToolResultData, ToolUseData};
-use crate::chat::events::{ChatEvent, ChatMessage, EventSender, ToolExecutionResult, ToolRequest};
+use crate::chat::events::{
+    ChatEvent, ChatMessage, EventSender, ToolExecutionResult, ToolRequest, ToolRequestType,
+};
+use crate::chat::tool_summary::ToolUsageSummary;
 use crate::spawn::AgentStack;
 
 pub struct TurnProtocol {
@@ -14,6 +17,9 @@ pub struct TurnProtocol {
     expected_tool_results: HashMap<String, String>,
     completed_tool_results: HashSet<String>,
     staged_tool_results: Vec<ContentBlock>,
+    request_types: HashMap<String, ToolRequestType>,
+    tool_usage: ToolUsageSummary,
+    files_modified: usize,
     finished: bool,
 }
 
@@ -28,12 +34,28 @@ impl TurnProtocol {
             expected_tool_results: HashMap::new(),
             completed_tool_results: HashSet::new(),
             staged_tool_results: Vec::new(),
+            request_types: HashMap::new(),
+            tool_usage: ToolUsageSummary::default(),
+            files_modified: 0,
             finished: false,
         }
     }
 
+    /// Ends the turn, sending the recap of the tools it used (if any).
     pub fn finish(mut self) {
         self.finished = true;
+        if !self.tool_usage.is_empty() {
+            self.event_sender.send(ChatEvent::ToolSummary {
+                calls: self.tool_usage.calls(),
+                failed: self.tool_usage.failed(),
+                summary: self.tool_usage.render(),
+            });
+        }
+    }
+
+    /// Successful file edits so far this turn.
+    pub fn files_modified(&self) -> usize {
+        self.files_modified
     }
 
     pub fn send(&self, event: ChatEvent) {
@@ -79,6 +101,8 @@ impl TurnProtocol {
     pub fn tool_request(&mut self, request: ToolRequest) {
         self.emitted_tool_requests
             .insert(request.tool_call_id.clone(), request.tool_name.clone());
+        self.request_types
+            .insert(request.tool_call_id.clone(), request.tool_type.clone());
         self.event_sender.send(ChatEvent::ToolRequest(request));
     }
 
@@ -92,6 +116,20 @@ impl TurnProtocol {
     ) {
         self.completed_tool_requests
             .insert(tool_call_id.to_string());
+        if success
+            && matches!(
+                self.request_types.get(tool_call_id),
+                Some(ToolRequestType::ModifyFile { .. })
+            )
+        {
+            self.files_modified += 1;
+        }
+        self.tool_usage.record(
+            tool_name,
+            self.request_types.get(tool_call_id),
+            &tool_result,
+            success,
+        );
         self.event_sender.send(ChatEvent::ToolExecutionCompleted {
             tool_call_id: tool_call_id.to_string(),
             tool_name: tool_name.to_string(),
@@ -161,6 +199,7 @@ impl TurnProtocol {
                 tool_result: ToolExecutionResult::Error {
                     short_message: "Cancelled".to_string(),
                     detailed_message: cancellation_message.clone(),
+                    category: None,
                 },
                 success: false,
                 error: Some("Cancelled by user".to_string()),
@@ -3,12 +3,14 @@ use crate::agents::catalog::AgentCatalog;
 use crate::ai::error::AiError;
 use crate::ai::model::{Model, ModelCost};
 use crate::ai::provider::AiProvider;
-use crate::ai::types::ContextBreakdown;
+use crate::ai::types::{ContextBreakdown, ToolResultData};
 use crate::ai::{Content, ContentBlock, ConversationRequest, Message, MessageRole, ModelSettings};
 use crate::module::ContextBuilder;
 use crate::module::Module;
 use crate::module::PromptBuilder;
+use crate::modules::context_management::planner::BYTES_PER_TOKEN;
 use crate::modules::memory::MemoryConfig;
+use crate::redact::{self, Redaction, Redactor};
 use crate::settings::config::Settings;
 use crate::settings::SettingsManager;
 use crate::spawn::build_tools;
@@ -93,6 +95,8 @@ pub async fn prepare_request(
         catalog.clone(),
         agent_name,
         settings.orchestration_mode,
+        settings.complete_task_guard,
+        settings.security.mode,
     )
     .await;
 
@@ -111,8 +115,13 @@ pub async fn prepare_request(
         ));
     }
 
-    let prompt_selection = agent.requested_prompt_components();
-    let filtered_content = prompt_builder.build(&settings, &prompt_selection, modules);
+    // The agent picks its components for its own autonomy level, and the
+    // components see that level in place of the global one.
+    let autonomy = settings.autonomy_level_for(agent_name);
+    let mut prompt_settings = settings.clone();
+    prompt_settings.autonomy_level = autonomy;
+    let prompt_selection = agent.requested_prompt_components(autonomy);
+    let filtered_content = prompt_builder.build(&prompt_settings, &prompt_selection, modules);
     let system_prompt = format!("{}{}", base_prompt, filtered_content);
 
     let model_settings = match model_override {
@@ -126,8 +135,15 @@ pub async fn prepare_request(
     let available_tools = tool_registry.get_tool_definitions(&allowed_tool_names);
 
     let context_selection = agent.requested_context_components();
-    let context_content = context_builder.build(&context_selection, modules).await;
-    let mut conversation = conversation.to_vec();
+    let redactor = redact::for_config(&settings_manager.get_module_config(Redaction::NAMESPACE));
+    let context_content = redactor
+        .redact(
+            &context_builder
+                .build(&settings, &context_selection, modules)
+                .await,
+        )
+        .into_owned();
+    let mut conversation = redact_conversation(conversation, &redactor);
     if conversation.is_empty() {
         bail!("No messages to send to AI. Conversation is empty!")
     }
@@ -181,15 +197,19 @@ pub async fn prepare_request(
         .len();
     let system_prompt_bytes = system_prompt.len() + tool_definitions_bytes;
 
-    let context_breakdown = ContextBreakdown {
+    let mut context_breakdown = ContextBreakdown {
         context_window: provider.context_window(&model_settings.model),
         input_tokens: 0,
+        estimated_tokens: 0,
         system_prompt_bytes,
         tool_io_bytes,
         conversation_history_bytes,
         reasoning_bytes,
         context_injection_bytes,
     };
+    context_breakdown.estimated_tokens = (context_breakdown.total_bytes() / BYTES_PER_TOKEN)
+        .try_into()
+        .unwrap_or(u32::MAX);
 
     let request = ConversationRequest {
         messages: conversation,
@@ -203,3 +223,32 @@ pub async fn prepare_request(
 
     Ok((request, model_settings, context_breakdown, tools))
 }
+
+/// Copies the conversation with secrets redacted from text and tool output,
+/// which is where file contents and command output end up.
+fn redact_conversation(conversation: &[Message], redactor: &Redactor) -> Vec<Message> {
+    conversation
+        .iter()
+        .map(|message| {
+            let blocks = message
+                .content
+                .blocks()
+                .iter()
+                .map(|block| match block {
+                    ContentBlock::Text(text) => {
+                        ContentBlock::Text(redactor.redact(text).into_owned())
+                    }
+                    ContentBlock::ToolResult(result) => ContentBlock::ToolResult(ToolResultData {
+                        content: redactor.redact(&result.content).into_owned(),
+                        ..result.clone()
+                    }),
+                    other => other.clone(),
+                })
+                .collect();
+            Message {
+                role: message.role.clone(),
+                content: Content::new(blocks),
+            }
+        })
+        .collect()
+}
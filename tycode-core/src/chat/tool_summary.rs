@@ -0,0 +1,178 @@
+//! One-line recap of the tools used during a turn, e.g.
+//! `read 3 files, modified main.rs (+5/-1), cargo build: ok`.
+//!
+//! Built by `TurnProtocol` from the turn's tool completions and sent as
+//! `ChatEvent::ToolSummary` when the turn ends.
+
+use std::path::Path;
+
+use crate::chat::events::{ToolExecutionResult, ToolRequestType};
+
+/// Commands longer than this are shortened in the summary.
+const MAX_COMMAND_CHARS: usize = 40;
+
+#[derive(Debug, Default)]
+pub struct ToolUsageSummary {
+    calls: u32,
+    failed: u32,
+    files_read: usize,
+    modified: Vec<(String, u32, u32)>,
+    commands: Vec<String>,
+    other: Vec<(String, u32)>,
+    failures: Vec<String>,
+}
+
+impl ToolUsageSummary {
+    pub fn record(
+        &mut self,
+        tool_name: &str,
+        request: Option<&ToolRequestType>,
+        result: &ToolExecutionResult,
+        success: bool,
+    ) {
+        self.calls += 1;
+        if !success {
+            self.failed += 1;
+        }
+
+        match (result, request) {
+            (ToolExecutionResult::ReadFiles { files }, _) => self.files_read += files.len(),
+            (
+                ToolExecutionResult::ModifyFile {
+                    lines_added,
+                    lines_removed,
+                },
+                Some(ToolRequestType::ModifyFile { file_path, .. }),
+            ) => {
+                let name = Path::new(file_path)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| file_path.clone());
+                match self.modified.iter_mut().find(|(file, ..)| *file == name) {
+                    Some((_, added, removed)) => {
+                        *added += lines_added;
+                        *removed += lines_removed;
+                    }
+                    None => self.modified.push((name, *lines_added, *lines_removed)),
+                }
+            }
+            (
+                ToolExecutionResult::RunCommand { exit_code, .. },
+                Some(ToolRequestType::RunCommand { command, .. }),
+            ) => {
+                let outcome = if *exit_code == 0 {
+                    "ok".to_string()
+                } else {
+                    format!("failed (exit {exit_code})")
+                };
+                self.commands
+                    .push(format!("{}: {outcome}", shorten(command.trim())));
+            }
+            _ if !success => self.failures.push(format!("{tool_name} failed")),
+            _ => match self.other.iter_mut().find(|(name, _)| name == tool_name) {
+                Some((_, count)) => *count += 1,
+                None => self.other.push((tool_name.to_string(), 1)),
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls == 0
+    }
+
+    pub fn calls(&self) -> u32 {
+        self.calls
+    }
+
+    pub fn failed(&self) -> u32 {
+        self.failed
+    }
+
+    pub fn render(&self) -> String {
+        let mut parts = Vec::new();
+        match self.files_read {
+            0 => {}
+            1 => parts.push("read 1 file".to_string()),
+            n => parts.push(format!("read {n} files")),
+        }
+        for (file, added, removed) in &self.modified {
+            parts.push(format!("modified {file} (+{added}/-{removed})"));
+        }
+        parts.extend(self.commands.iter().cloned());
+        for (name, count) in &self.other {
+            match count {
+                1 => parts.push(name.clone()),
+                n => parts.push(format!("{name} x{n}")),
+            }
+        }
+        parts.extend(self.failures.iter().cloned());
+        parts.join(", ")
+    }
+}
+
+fn shorten(command: &str) -> String {
+    if command.chars().count() <= MAX_COMMAND_CHARS {
+        return command.to_string();
+    }
+    let short: String = command.chars().take(MAX_COMMAND_CHARS).collect();
+    format!("{short}...")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::events::FileInfo;
+
+    #[test]
+    fn renders_reads_edits_and_commands() {
+        let mut summary = ToolUsageSummary::default();
+        let files = |n| ToolExecutionResult::ReadFiles {
+            files: (0..n)
+                .map(|i| FileInfo {
+                    path: format!("/ws/{i}.rs"),
+                    bytes: 1,
+                })
+                .collect(),
+        };
+        summary.record("read_files", None, &files(2), true);
+        summary.record("read_files", None, &files(1), true);
+
+        let edit = ToolRequestType::ModifyFile {
+            file_path: "/ws/src/main.rs".to_string(),
+            before: String::new(),
+            after: String::new(),
+        };
+        for (added, removed) in [(3, 1), (2, 0)] {
+            let result = ToolExecutionResult::ModifyFile {
+                lines_added: added,
+                lines_removed: removed,
+            };
+            summary.record("modify_file", Some(&edit), &result, true);
+        }
+
+        let build = ToolRequestType::RunCommand {
+            command: "cargo build".to_string(),
+            working_directory: "/ws".to_string(),
+        };
+        let result = ToolExecutionResult::RunCommand {
+            exit_code: 0,
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        summary.record("bash", Some(&build), &result, true);
+
+        let error = ToolExecutionResult::Error {
+            short_message: "nope".to_string(),
+            detailed_message: "nope".to_string(),
+            category: None,
+        };
+        summary.record("git_commit", None, &error, false);
+
+        assert_eq!(summary.calls(), 6);
+        assert_eq!(summary.failed(), 1);
+        assert_eq!(
+            summary.render(),
+            "read 3 files, modified main.rs (+5/-1), cargo build: ok, git_commit failed"
+        );
+    }
+}
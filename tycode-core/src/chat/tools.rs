@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use base64::engine::general_purpose;
@@ -15,10 +16,14 @@ use crate::agents::runner::AgentRunner;
 use crate::ai::model::Model;
 use crate::ai::types::ImageData;
 use crate::ai::{Content, ContentBlock, Message, MessageRole, ToolResultData, ToolUseData};
+use crate::audit::{Audit, AuditLog, AuditOutcome};
 use crate::chat::actor::ActorState;
-use crate::chat::events::{ChatEvent, ChatMessage, ToolExecutionResult, ToolRequest};
+use crate::chat::events::{
+    ChatEvent, ChatMessage, ToolErrorCategory, ToolExecutionResult, ToolRequest,
+};
 use crate::chat::protocol::TurnProtocol;
 use crate::chat::request::pinned_model_settings;
+use crate::file::resolver;
 use crate::modules::execution::config::ExecutionConfig;
 use crate::modules::execution::{compact_output, truncate_and_persist};
 use crate::orchestration::events::{
@@ -29,8 +34,9 @@ use crate::orchestration::{
     ChildAction, ChildOutcome, CompletionAction, ConversationSeed, FanOutSpec, SpawnSpec,
     TaskAction, WorkerResult, WorkerSpec, FANOUT_AGENT,
 };
+use crate::redact::{self, Redaction};
 use crate::settings::config::SpawnContextMode;
-use futures_util::stream::{FuturesUnordered, StreamExt};
+use futures_util::stream::{self, FuturesUnordered, StreamExt};
 
 use crate::tools::r#trait::{ContinuationPreference, ToolCallHandle, ToolOutput};
 use crate::tools::registry::ToolRegistry;
@@ -182,7 +188,7 @@ fn send_tool_completion(
 
 pub async fn execute_tool_calls(
     state: &mut ActorState,
-    tool_calls: Vec<ToolUseData>,
+    mut tool_calls: Vec<ToolUseData>,
     protocol: &mut TurnProtocol,
 ) -> Result<ToolResults> {
     state.transition_timing_state(crate::chat::actor::TimingState::ExecutingTools);
@@ -201,11 +207,14 @@ pub async fn execute_tool_calls(
     let allowed_tool_names: Vec<ToolName> = current_agent(state, |a| a.agent.available_tools());
 
     let current_agent_name = state.spawn_module.current_agent_name().unwrap_or_default();
+    let settings = state.settings.settings();
     let all_tools = crate::spawn::build_tools(
         &state.modules,
         state.spawn_module.catalog().clone(),
         &current_agent_name,
-        state.settings.settings().orchestration_mode,
+        settings.orchestration_mode,
+        settings.complete_task_guard,
+        settings.security.mode,
     )
     .await;
 
@@ -216,11 +225,25 @@ pub async fn execute_tool_calls(
     // Initialize preferences vector early to track all error and success preferences
     let mut preferences = vec![];
 
-    let mut validated: Vec<(ToolUseData, Box<dyn ToolCallHandle>)> = vec![];
+    // Calls past the per-turn cap are answered with errors rather than
+    // dropped, so every tool use still has a paired result.
+    let max_tool_calls = settings.max_tool_calls_per_turn.max(1);
+    let total_tool_calls = tool_calls.len();
+    let over_limit = if total_tool_calls > max_tool_calls {
+        warn!(
+            total_tool_calls,
+            max_tool_calls, "Tool call limit exceeded, rejecting the excess calls"
+        );
+        tool_calls.split_off(max_tool_calls)
+    } else {
+        Vec::new()
+    };
+
+    let mut validated: Vec<ValidatedCall> = vec![];
     let mut invalid_tool_results = vec![];
     for tool_use in tool_calls {
         match tool_registry
-            .process_tools(&tool_use, &allowed_tool_names)
+            .process_tools(&tool_use, &allowed_tool_names, &current_agent_name)
             .await
         {
             Ok(handle) => validated.push((tool_use, handle)),
@@ -230,178 +253,293 @@ pub async fn execute_tool_calls(
                     error = %error,
                     "Tool call validation failed, will return error response"
                 );
-                let error_result = handle_tool_error(state, protocol, &tool_use, error);
+                let error_result = handle_tool_error(state, protocol, &tool_use, error, None);
                 invalid_tool_results.push(error_result.content_block);
                 preferences.push(error_result.continuation_preference);
             }
         }
     }
+    for tool_use in over_limit {
+        let error = format!(
+            "Not executed: this turn requested {total_tool_calls} tool calls but at most \
+             {max_tool_calls} run per turn. Review the results of the calls that ran, then \
+             issue the remaining calls incrementally in later turns."
+        );
+        let error_result = handle_tool_error(
+            state,
+            protocol,
+            &tool_use,
+            error,
+            Some(ToolErrorCategory::LimitExceeded),
+        );
+        invalid_tool_results.push(error_result.content_block);
+        preferences.push(error_result.continuation_preference);
+    }
 
+    let audit_log = audit_log(state);
+    let tool_concurrency = settings.tool_concurrency.max(1);
     let mut results = Vec::new();
     let mut deferred_actions = Vec::new();
-    for (raw, handle) in validated {
-        let request = handle.tool_request();
-        let tool_call_id = request.tool_call_id.clone();
-        let tool_name = request.tool_name.clone();
-        protocol.tool_request(request);
-
-        let output = handle.execute().await;
-
-        match output {
-            ToolOutput::Result {
-                content,
-                is_error,
-                continuation,
-                ui_result,
-            } => {
-                let content =
-                    truncate_tool_result(content, &raw.id, max_output_bytes, &tool_calls_dir).await;
-
-                let result = ToolResultData {
-                    tool_use_id: raw.id.clone(),
-                    content,
-                    is_error,
-                };
-
-                send_tool_completion(
-                    protocol,
-                    &tool_call_id,
+    let mut proposed_edits = Vec::new();
+    for batch in concurrent_batches(validated, &tool_registry, tool_concurrency) {
+        let mut calls = Vec::with_capacity(batch.len());
+        let mut handles = Vec::with_capacity(batch.len());
+        for (raw, mut handle) in batch {
+            let request = handle.tool_request();
+            let tool_call_id = request.tool_call_id.clone();
+            let tool_name = request.tool_name.clone();
+            protocol.tool_request(request);
+            let audit = audit_log.as_ref().map(|log| {
+                log.call(
+                    &current_agent_name,
                     &tool_name,
-                    ui_result,
-                    !is_error,
-                    None,
-                );
+                    &tool_call_id,
+                    &raw.arguments,
+                )
+            });
+            if let Some(audit) = &audit {
+                handle.attach_audit(audit.clone());
+            }
+            calls.push((raw, tool_call_id, tool_name, audit));
+            handles.push(handle);
+        }
+
+        // `buffered` yields outputs in call order, so results land in the
+        // conversation in the order the model issued them.
+        let outputs: Vec<(ToolOutput, Duration)> = stream::iter(handles)
+            .map(|handle| async move {
+                let started = Instant::now();
+                let output = handle.execute().await;
+                (output, started.elapsed())
+            })
+            .buffered(tool_concurrency)
+            .collect()
+            .await;
 
-                let result_block = ContentBlock::ToolResult(result);
-                protocol.stage_tool_result(result_block.clone());
-                results.push(result_block);
-                preferences.push(continuation);
+        for ((raw, tool_call_id, tool_name, audit), (output, duration)) in
+            calls.into_iter().zip(outputs)
+        {
+            if let Some(audit) = &audit {
+                let outcome = match output {
+                    ToolOutput::Result { is_error: true, .. } => AuditOutcome::Error,
+                    _ if audit.is_held() => AuditOutcome::Pending,
+                    _ => AuditOutcome::Success,
+                };
+                audit.record(outcome, duration);
             }
-            ToolOutput::ImageResult {
-                content,
-                images,
-                continuation,
-                ui_result,
-            } => {
-                let content =
-                    truncate_tool_result(content, &raw.id, max_output_bytes, &tool_calls_dir).await;
-
-                let result = ToolResultData {
-                    tool_use_id: raw.id.clone(),
+
+            match output {
+                ToolOutput::Result {
                     content,
-                    is_error: false,
-                };
+                    is_error,
+                    continuation,
+                    ui_result,
+                } => {
+                    let content =
+                        truncate_tool_result(content, &raw.id, max_output_bytes, &tool_calls_dir)
+                            .await;
+                    let (content, ui_result) = if is_error {
+                        categorize_error(content, ui_result)
+                    } else {
+                        (content, ui_result)
+                    };
+
+                    let result = ToolResultData {
+                        tool_use_id: raw.id.clone(),
+                        content,
+                        is_error,
+                    };
+
+                    send_tool_completion(
+                        protocol,
+                        &tool_call_id,
+                        &tool_name,
+                        ui_result,
+                        !is_error,
+                        None,
+                    );
 
-                send_tool_completion(protocol, &tool_call_id, &tool_name, ui_result, true, None);
+                    let result_block = ContentBlock::ToolResult(result);
+                    protocol.stage_tool_result(result_block.clone());
+                    results.push(result_block);
+                    preferences.push(continuation);
+                }
+                ToolOutput::ImageResult {
+                    content,
+                    images,
+                    continuation,
+                    ui_result,
+                } => {
+                    let content =
+                        truncate_tool_result(content, &raw.id, max_output_bytes, &tool_calls_dir)
+                            .await;
+
+                    let result = ToolResultData {
+                        tool_use_id: raw.id.clone(),
+                        content,
+                        is_error: false,
+                    };
+
+                    send_tool_completion(
+                        protocol,
+                        &tool_call_id,
+                        &tool_name,
+                        ui_result,
+                        true,
+                        None,
+                    );
 
-                let result_block = ContentBlock::ToolResult(result);
-                protocol.stage_tool_result(result_block.clone());
-                results.push(result_block);
-                for (image_data, media_type) in images {
-                    results.push(ContentBlock::Image(ImageData {
-                        media_type,
-                        data: general_purpose::STANDARD.encode(&image_data),
-                    }));
+                    let result_block = ContentBlock::ToolResult(result);
+                    protocol.stage_tool_result(result_block.clone());
+                    results.push(result_block);
+                    for (image_data, media_type) in images {
+                        results.push(ContentBlock::Image(ImageData {
+                            media_type,
+                            data: general_purpose::STANDARD.encode(&image_data),
+                        }));
+                    }
+                    preferences.push(continuation);
                 }
-                preferences.push(continuation);
-            }
-            ToolOutput::PushAgent {
-                agent,
-                task,
-                spawn_params,
-            } => {
-                let agent_type = agent.name().to_string();
-                let acknowledgment = ContentBlock::ToolResult(ToolResultData {
-                    tool_use_id: raw.id.clone(),
-                    content: json!({
-                        "status": "spawned",
-                        "agent_type": agent_type,
-                        "task": task
-                    })
-                    .to_string(),
-                    is_error: false,
-                });
-                protocol.stage_tool_result(acknowledgment.clone());
-                results.push(acknowledgment);
-                deferred_actions.push(DeferredAction::PushAgent {
+                ToolOutput::PushAgent {
                     agent,
                     task,
-                    agent_type,
                     spawn_params,
-                    tool_call_id,
-                    tool_name,
-                });
-                preferences.push(ContinuationPreference::Continue);
-            }
-            ToolOutput::PopAgent { success, result } => {
-                let is_root = state.spawn_module.stack_depth() <= 1;
-                let preference = if is_root {
-                    ContinuationPreference::Stop
-                } else {
-                    ContinuationPreference::Continue
-                };
-
-                let acknowledgment = ContentBlock::ToolResult(ToolResultData {
-                    tool_use_id: raw.id.clone(),
-                    content: json!({
-                        "status": "completing",
-                        "success": success,
-                        "result": result
-                    })
-                    .to_string(),
-                    is_error: false,
-                });
-                protocol.stage_tool_result(acknowledgment.clone());
-                results.push(acknowledgment);
-                deferred_actions.push(DeferredAction::PopAgent {
-                    success,
-                    result,
-                    tool_call_id,
-                    tool_name,
-                });
-                preferences.push(preference);
-            }
-            ToolOutput::PromptUser { question } => {
-                let result = ToolResultData {
-                    tool_use_id: raw.id.clone(),
-                    content: json!({}).to_string(),
-                    is_error: false,
-                };
+                } => {
+                    let agent_type = agent.name().to_string();
+                    let acknowledgment = ContentBlock::ToolResult(ToolResultData {
+                        tool_use_id: raw.id.clone(),
+                        content: json!({
+                            "status": "spawned",
+                            "agent_type": agent_type,
+                            "task": task
+                        })
+                        .to_string(),
+                        is_error: false,
+                    });
+                    protocol.stage_tool_result(acknowledgment.clone());
+                    results.push(acknowledgment);
+                    deferred_actions.push(DeferredAction::PushAgent {
+                        agent,
+                        task,
+                        agent_type,
+                        spawn_params,
+                        tool_call_id,
+                        tool_name,
+                    });
+                    preferences.push(ContinuationPreference::Continue);
+                }
+                ToolOutput::PopAgent { success, result } => {
+                    let is_root = state.spawn_module.stack_depth() <= 1;
+                    let preference = if is_root {
+                        ContinuationPreference::Stop
+                    } else {
+                        ContinuationPreference::Continue
+                    };
+
+                    let acknowledgment = ContentBlock::ToolResult(ToolResultData {
+                        tool_use_id: raw.id.clone(),
+                        content: json!({
+                            "status": "completing",
+                            "success": success,
+                            "result": result
+                        })
+                        .to_string(),
+                        is_error: false,
+                    });
+                    protocol.stage_tool_result(acknowledgment.clone());
+                    results.push(acknowledgment);
+                    deferred_actions.push(DeferredAction::PopAgent {
+                        success,
+                        result,
+                        tool_call_id,
+                        tool_name,
+                    });
+                    preferences.push(preference);
+                }
+                ToolOutput::PromptUser { question } => {
+                    let result = ToolResultData {
+                        tool_use_id: raw.id.clone(),
+                        content: json!({}).to_string(),
+                        is_error: false,
+                    };
+
+                    let agent_name = current_agent(state, |a| a.agent.name().to_string());
+                    state.event_sender.send_message(ChatMessage::assistant(
+                        agent_name,
+                        question,
+                        vec![],
+                        crate::chat::events::ModelInfo::new(Model::None),
+                        crate::ai::types::TokenUsage::empty(),
+                        None,
+                        None,
+                    ));
+
+                    send_tool_completion(
+                        protocol,
+                        &tool_call_id,
+                        &tool_name,
+                        ToolExecutionResult::Other {
+                            result: json!({ "status": "waiting_for_user" }),
+                        },
+                        true,
+                        None,
+                    );
 
-                let agent_name = current_agent(state, |a| a.agent.name().to_string());
-                state.event_sender.send_message(ChatMessage::assistant(
-                    agent_name,
-                    question,
-                    vec![],
-                    crate::chat::events::ModelInfo::new(Model::None),
-                    crate::ai::types::TokenUsage::empty(),
-                    None,
-                    None,
-                ));
-
-                send_tool_completion(
-                    protocol,
-                    &tool_call_id,
-                    &tool_name,
-                    ToolExecutionResult::Other {
-                        result: json!({ "status": "waiting_for_user" }),
-                    },
-                    true,
-                    None,
-                );
+                    let result_block = ContentBlock::ToolResult(result);
+                    protocol.stage_tool_result(result_block.clone());
+                    results.push(result_block);
+                    preferences.push(ContinuationPreference::Stop);
+                }
+                ToolOutput::ProposeEdit { handle, file_path } => {
+                    let edit = state.edit_review.propose(
+                        handle,
+                        tool_call_id.clone(),
+                        tool_name.clone(),
+                        raw.arguments.clone(),
+                        file_path,
+                    );
+                    send_tool_completion(
+                        protocol,
+                        &tool_call_id,
+                        &tool_name,
+                        ToolExecutionResult::Other {
+                            result: json!({ "status": "proposed", "edit_id": edit.edit_id }),
+                        },
+                        true,
+                        None,
+                    );
 
-                let result_block = ContentBlock::ToolResult(result);
-                protocol.stage_tool_result(result_block.clone());
-                results.push(result_block);
-                preferences.push(ContinuationPreference::Stop);
+                    let result_block = ContentBlock::ToolResult(ToolResultData {
+                        tool_use_id: raw.id.clone(),
+                        content: json!({
+                            "status": "proposed",
+                            "edit_id": edit.edit_id,
+                            "message": "The edit has not been written yet. The user will accept or reject it and you will be told the outcome."
+                        })
+                        .to_string(),
+                        is_error: false,
+                    });
+                    protocol.stage_tool_result(result_block.clone());
+                    results.push(result_block);
+                    proposed_edits.push(edit);
+                    preferences.push(ContinuationPreference::AwaitEditReview);
+                }
             }
         }
     }
 
+    if !proposed_edits.is_empty() {
+        state.event_sender.send(ChatEvent::EditsProposed {
+            edits: proposed_edits,
+        });
+    }
+
     // Implement truth table for continuation preferences:
-    // - Any Stop → stop conversation
+    // - Any Stop or AwaitEditReview → stop conversation
     // - Otherwise, any Continue → continue conversation
-    let mut continue_conversation = if preferences.contains(&ContinuationPreference::Stop) {
+    let mut continue_conversation = if preferences.contains(&ContinuationPreference::Stop)
+        || preferences.contains(&ContinuationPreference::AwaitEditReview)
+    {
         false
     } else {
         preferences.contains(&ContinuationPreference::Continue)
@@ -444,7 +582,7 @@ async fn truncate_tool_result(
         return content;
     }
 
-    let display_path = tool_calls_dir.join(tool_call_id).display().to_string();
+    let display_path = resolver::display_path(&tool_calls_dir.join(tool_call_id));
 
     match truncate_and_persist(
         &content,
@@ -475,17 +613,97 @@ fn create_short_message(detailed: &str) -> String {
     }
 }
 
+/// The audit log for this session, when auditing is enabled.
+fn audit_log(state: &ActorState) -> Option<AuditLog> {
+    let config: Audit = state.settings.get_module_config(Audit::NAMESPACE);
+    if !config.enabled {
+        return None;
+    }
+    let session_id = state.session_id.as_deref().unwrap_or("ephemeral");
+    let redactor = redact::for_config(&state.settings.get_module_config(Redaction::NAMESPACE));
+    Some(AuditLog::new(&state.audit_dir, session_id, redactor))
+}
+
+type ValidatedCall = (ToolUseData, Box<dyn ToolCallHandle>);
+
+/// Split validated calls into batches executed one after another. Consecutive
+/// parallel-safe calls share a batch and run concurrently; every other call
+/// runs alone, so mutating tools stay ordered relative to all other calls.
+fn concurrent_batches(
+    validated: Vec<ValidatedCall>,
+    registry: &ToolRegistry,
+    concurrency: usize,
+) -> Vec<Vec<ValidatedCall>> {
+    let mut batches: Vec<Vec<ValidatedCall>> = Vec::new();
+    let mut open_batch = false;
+    for (raw, handle) in validated {
+        let parallel_safe = concurrency > 1
+            && registry
+                .get_tool_category_by_name(&raw.name)
+                .is_some_and(|category| category.parallel_safe());
+        match batches.last_mut() {
+            Some(batch) if open_batch && parallel_safe => batch.push((raw, handle)),
+            _ => batches.push(vec![(raw, handle)]),
+        }
+        open_batch = parallel_safe;
+    }
+    batches
+}
+
+/// Attach an error category to a failed tool result: the one the tool set on
+/// its `ToolExecutionResult::Error`, or else one classified from the error
+/// message. Other failed results (e.g. a command exiting non-zero) carry
+/// program output rather than an error message and are left uncategorized.
+/// The model sees the category and a one-line hint appended to the content.
+fn categorize_error(
+    content: String,
+    mut ui_result: ToolExecutionResult,
+) -> (String, ToolExecutionResult) {
+    let category = match &mut ui_result {
+        ToolExecutionResult::Error {
+            detailed_message,
+            category,
+            ..
+        } => {
+            if category.is_none() {
+                *category = ToolErrorCategory::classify(detailed_message)
+                    .or_else(|| ToolErrorCategory::classify(&content));
+            }
+            *category
+        }
+        _ => None,
+    };
+    match category {
+        Some(category) => (with_error_category(content, category), ui_result),
+        None => (content, ui_result),
+    }
+}
+
+fn with_error_category(content: String, category: ToolErrorCategory) -> String {
+    format!(
+        "{content}\n\n[error category: {}] {}",
+        category.as_str(),
+        category.guidance()
+    )
+}
+
 fn handle_tool_error(
     _state: &mut ActorState,
     protocol: &mut TurnProtocol,
     tool_use: &ToolUseData,
     error: String,
+    category: Option<ToolErrorCategory>,
 ) -> ToolCallResult {
     let short_message = create_short_message(&error);
+    // Calls rejected before execution are malformed unless the message says
+    // otherwise (unknown or disallowed tool).
+    let category = category
+        .or_else(|| ToolErrorCategory::classify(&error))
+        .unwrap_or(ToolErrorCategory::InvalidArgument);
 
     let result = ToolResultData {
         tool_use_id: tool_use.id.clone(),
-        content: error.clone(),
+        content: with_error_category(error.clone(), category),
         is_error: true,
     };
 
@@ -503,6 +721,7 @@ fn handle_tool_error(
         ToolExecutionResult::Error {
             short_message,
             detailed_message: error.clone(),
+            category: Some(category),
         },
         false,
         Some(error),
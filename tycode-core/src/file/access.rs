@@ -1,13 +1,24 @@
+use crate::file::cache::{self, content_hash, Decoding, FileCache};
+use crate::file::config::File;
+use crate::file::encoding::TextEncoding;
+use crate::file::line_endings::{self, LineEnding};
 use crate::file::workspace::WorkspacePaths;
+use crate::settings::SettingsManager;
 use anyhow::{Context, Result};
 use ignore::WalkBuilder;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tokio::fs;
 
 #[derive(Clone)]
 pub struct FileAccessManager {
     pub roots: Vec<PathBuf>,
     workspace_paths: WorkspacePaths,
+    /// Process-wide, so every manager sees the same cached reads.
+    cache: Arc<Mutex<FileCache>>,
+    /// Source of `latin1_fallback`; without settings the fallback is off.
+    settings: Option<SettingsManager>,
 }
 
 impl FileAccessManager {
@@ -18,9 +29,40 @@ impl FileAccessManager {
         Ok(Self {
             roots,
             workspace_paths,
+            cache: cache::shared(),
+            settings: None,
         })
     }
 
+    /// Reads the file settings (currently `latin1_fallback`) from `settings`
+    /// on each read and write.
+    pub fn with_settings(mut self, settings: SettingsManager) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    fn decoding(&self) -> Decoding {
+        let latin1_fallback = self.settings.as_ref().is_some_and(|settings| {
+            settings
+                .get_module_config::<File>(File::NAMESPACE)
+                .latin1_fallback
+        });
+        Decoding { latin1_fallback }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<(String, TextEncoding)> {
+        TextEncoding::decode(bytes, self.decoding().latin1_fallback)
+    }
+
+    /// See `WorkspacePaths::display`.
+    pub fn display_path(&self, path: &Path) -> String {
+        self.workspace_paths.display(path)
+    }
+
+    /// Reads a file as text with line endings normalized to `\n`. Non-UTF-8
+    /// text is decoded (see encoding.rs) or, without `latin1_fallback`,
+    /// rejected; binary files are rejected. Repeated
+    /// reads of unchanged content skip decoding via the cache.
     pub async fn read_file(&self, file_path: &str) -> Result<String> {
         let path = self.resolve(file_path)?;
 
@@ -32,9 +74,24 @@ impl FileAccessManager {
             anyhow::bail!("Path is not a file: {}", file_path);
         }
 
-        fs::read_to_string(&path)
+        let bytes = fs::read(&path)
             .await
-            .with_context(|| format!("Failed to read file: {file_path}"))
+            .with_context(|| format!("Failed to read file: {file_path}"))?;
+        let hash = content_hash(&bytes);
+        let decoding = self.decoding();
+        if let Some(content) = self.cache.lock().unwrap().get(&path, &hash, decoding) {
+            return Ok(content);
+        }
+
+        let (content, _) = TextEncoding::decode(&bytes, decoding.latin1_fallback)
+            .with_context(|| format!("Failed to read file: {file_path}"))?;
+        let content = line_endings::normalize(&content).into_owned();
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(path, hash, decoding, content.clone());
+        Ok(content)
     }
 
     pub async fn read_bytes(&self, file_path: &str) -> Result<Vec<u8>> {
@@ -53,22 +110,37 @@ impl FileAccessManager {
             .with_context(|| format!("Failed to read file: {file_path}"))
     }
 
+    /// Writes a text file. When overwriting an existing text file its
+    /// encoding, BOM and line ending style are preserved; new files are UTF-8.
     pub async fn write_file(&self, file_path: &str, content: &str) -> Result<()> {
         let path = self.resolve(file_path)?;
 
+        let existing = match fs::read(&path).await {
+            Ok(bytes) => self.decode(&bytes).ok(),
+            Err(_) => None,
+        };
+        let data = match existing {
+            Some((existing, encoding)) => encoding
+                .encode(&LineEnding::detect(&existing).apply(content))
+                .with_context(|| format!("Failed to encode file: {file_path}"))?,
+            None => content.as_bytes().to_vec(),
+        };
+
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .await
                 .with_context(|| format!("Failed to create parent directories for: {file_path}"))?;
         }
 
-        fs::write(&path, content)
+        self.cache.lock().unwrap().invalidate(&path);
+        fs::write(&path, data)
             .await
             .with_context(|| format!("Failed to write file: {file_path}"))
     }
 
     pub async fn write_bytes(&self, file_path: &str, data: &[u8]) -> Result<()> {
         let path = self.resolve(file_path)?;
+        self.cache.lock().unwrap().invalidate(&path);
 
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
@@ -83,6 +155,7 @@ impl FileAccessManager {
 
     pub async fn delete_file(&self, file_path: &str) -> Result<()> {
         let path = self.resolve(file_path)?;
+        self.cache.lock().unwrap().invalidate(&path);
 
         let metadata = fs::metadata(&path)
             .await
@@ -154,25 +227,58 @@ impl FileAccessManager {
         max_bytes: Option<usize>,
     ) -> Result<Vec<PathBuf>> {
         let real_root = self.resolve_root(workspace_root)?;
+        let files = self.walk(&real_root, None)?;
 
+        if let Some(limit) = max_bytes {
+            Ok(Self::truncate_by_bytes(files, limit))
+        } else {
+            Ok(files)
+        }
+    }
+
+    /// The files `list_all_files_recursive` would return for
+    /// `workspace_root` that are among or below `paths`, found by walking
+    /// only the directories leading to them so ignore rules apply exactly as
+    /// in a full listing.
+    pub async fn filter_listed(
+        &self,
+        workspace_root: &str,
+        paths: &[PathBuf],
+    ) -> Result<Vec<PathBuf>> {
+        let real_root = self.resolve_root(workspace_root)?;
+        let wanted: HashSet<PathBuf> = paths.iter().cloned().collect();
+        self.walk(&real_root, Some(Arc::new(wanted)))
+    }
+
+    /// Files under `real_root`, respecting .gitignore and skipping nested
+    /// repositories. With `only`, just the directories leading to or below
+    /// those paths are descended into.
+    fn walk(&self, real_root: &Path, only: Option<Arc<HashSet<PathBuf>>>) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
-        let root_for_filter = real_root.clone();
+        let root_for_filter = real_root.to_path_buf();
         let root_is_git_repo = real_root.join(".git").exists();
 
-        for result in WalkBuilder::new(&real_root)
+        for result in WalkBuilder::new(real_root)
             .hidden(false)
             .filter_entry(move |entry| {
                 if entry.file_name().to_string_lossy() == ".git" {
                     return false;
                 }
 
-                if root_is_git_repo && entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+                if root_is_git_repo && is_dir {
                     let is_root = entry.path() == root_for_filter;
                     if !is_root && entry.path().join(".git").exists() {
                         return false;
                     }
                 }
-                true
+
+                match &only {
+                    Some(only) => only.iter().any(|path| {
+                        entry.path().starts_with(path) || (is_dir && path.starts_with(entry.path()))
+                    }),
+                    None => true,
+                }
             })
             .build()
         {
@@ -190,12 +296,7 @@ impl FileAccessManager {
 
             files.push(resolved);
         }
-
-        if let Some(limit) = max_bytes {
-            Ok(Self::truncate_by_bytes(files, limit))
-        } else {
-            Ok(files)
-        }
+        Ok(files)
     }
 
     fn truncate_by_bytes(files: Vec<PathBuf>, max_bytes: usize) -> Vec<PathBuf> {
@@ -293,6 +394,79 @@ mod tests {
         assert_eq!(std_fs::read_to_string(path).unwrap(), "content");
     }
 
+    #[tokio::test]
+    async fn test_write_file_preserves_encoding_and_bom() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        std_fs::create_dir(&workspace).unwrap();
+        let settings = SettingsManager::from_path(temp.path().join("settings.toml")).unwrap();
+        let manager = FileAccessManager::new(vec![workspace.clone()])
+            .unwrap()
+            .with_settings(settings.clone());
+
+        let path = workspace.join("latin1.txt");
+        std_fs::write(&path, b"caf\xE9\r\n").unwrap();
+        let err = manager.read_file(&path_str(&path)).await.unwrap_err();
+        assert!(format!("{err:#}").contains("latin1_fallback"), "{err:#}");
+
+        let mut config: File = settings.get_module_config(File::NAMESPACE);
+        config.latin1_fallback = true;
+        settings.set_module_config(File::NAMESPACE, config);
+        let content = manager.read_file(&path_str(&path)).await.unwrap();
+        assert_eq!(content, "café\n");
+        manager
+            .write_file(&path_str(&path), "café au lait\n")
+            .await
+            .unwrap();
+        assert_eq!(std_fs::read(&path).unwrap(), b"caf\xE9 au lait\r\n");
+
+        let path = workspace.join("bom.txt");
+        std_fs::write(&path, b"\xEF\xBB\xBFold").unwrap();
+        manager.write_file(&path_str(&path), "new").await.unwrap();
+        assert_eq!(std_fs::read(&path).unwrap(), b"\xEF\xBB\xBFnew");
+    }
+
+    #[tokio::test]
+    async fn test_read_file_cache_sees_changes_that_keep_size_and_mtime() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        std_fs::create_dir(&workspace).unwrap();
+        let manager = FileAccessManager::new(vec![workspace.clone()]).unwrap();
+        let other = FileAccessManager::new(vec![workspace.clone()]).unwrap();
+
+        let file = workspace.join("cached.txt");
+        let path = path_str(&file);
+        std_fs::write(&file, "first").unwrap();
+        let modified = std_fs::metadata(&file).unwrap().modified().unwrap();
+        assert_eq!(manager.read_file(&path).await.unwrap(), "first");
+        assert_eq!(other.read_file(&path).await.unwrap(), "first");
+
+        std_fs::write(&file, "fresh").unwrap();
+        std_fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_eq!(manager.read_file(&path).await.unwrap(), "fresh");
+
+        other.write_file(&path, "second!").await.unwrap();
+        assert_eq!(manager.read_file(&path).await.unwrap(), "second!");
+    }
+
+    #[tokio::test]
+    async fn test_read_file_rejects_binary() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        std_fs::create_dir(&workspace).unwrap();
+        let manager = FileAccessManager::new(vec![workspace.clone()]).unwrap();
+
+        let path = workspace.join("image.bin");
+        std_fs::write(&path, [0x89, b'P', b'N', b'G', 0, 0, 0, 0]).unwrap();
+        let err = manager.read_file(&path_str(&path)).await.unwrap_err();
+        assert!(format!("{err:#}").contains("binary file"), "{err:#}");
+    }
+
     #[tokio::test]
     async fn test_delete_file_success() {
         let temp = tempdir().unwrap();
@@ -0,0 +1,298 @@
+//! `git_blame` tool: shows who last changed each region of a file.
+//!
+//! Output is grouped into runs of consecutive lines from the same commit and
+//! capped at `MAX_BLAME_RUNS` so a large or heavily churned file cannot flood
+//! the context. Disabled by default (see `File::git_blame`) since running
+//! blame on big files is slow.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use tokio::process::Command;
+
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::access::FileAccessManager;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+use crate::tools::ToolName;
+
+const MAX_BLAME_RUNS: usize = 100;
+
+/// A run of consecutive lines last changed by the same commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameRun {
+    pub start_line: u32,
+    pub end_line: u32,
+    pub commit: String,
+    pub author: String,
+    pub date: String,
+    pub summary: String,
+}
+
+impl BlameRun {
+    fn render(&self) -> String {
+        let lines = if self.start_line == self.end_line {
+            format!("line {}", self.start_line)
+        } else {
+            format!("lines {}-{}", self.start_line, self.end_line)
+        };
+        let short: String = self.commit.chars().take(8).collect();
+        format!(
+            "{lines}: {short} {} {} {}",
+            self.author, self.date, self.summary
+        )
+    }
+}
+
+/// Parses `git blame --line-porcelain` output into runs.
+pub fn parse_line_porcelain(output: &str) -> Vec<BlameRun> {
+    let mut runs: Vec<BlameRun> = Vec::new();
+    let mut current: Option<(String, u32)> = None;
+    let mut author = String::new();
+    let mut date = String::new();
+    let mut summary = String::new();
+
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("author ") {
+            author = rest.to_string();
+        } else if let Some(rest) = line.strip_prefix("author-time ") {
+            date = rest
+                .parse::<i64>()
+                .ok()
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                .map(|time| time.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+        } else if let Some(rest) = line.strip_prefix("summary ") {
+            summary = rest.to_string();
+        } else if line.starts_with('\t') {
+            // The content line closes the entry for one source line
+            let Some((commit, line_no)) = current.take() else {
+                continue;
+            };
+            match runs.last_mut() {
+                Some(run) if run.commit == commit && run.end_line + 1 == line_no => {
+                    run.end_line = line_no;
+                }
+                _ => runs.push(BlameRun {
+                    start_line: line_no,
+                    end_line: line_no,
+                    commit,
+                    author: author.clone(),
+                    date: date.clone(),
+                    summary: summary.clone(),
+                }),
+            }
+        } else {
+            let mut parts = line.split(' ');
+            let (Some(sha), Some(_orig), Some(final_line)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            if sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit()) {
+                if let Ok(line_no) = final_line.parse() {
+                    current = Some((sha.to_string(), line_no));
+                }
+            }
+        }
+    }
+
+    runs
+}
+
+pub struct GitBlameTool {
+    file_manager: FileAccessManager,
+}
+
+impl GitBlameTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("git_blame")
+    }
+
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> Result<Self> {
+        Ok(Self {
+            file_manager: FileAccessManager::new(workspace_roots)?.with_settings(settings),
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for GitBlameTool {
+    fn name(&self) -> String {
+        "git_blame".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Show the last commit (author, date, summary) that touched each region of a file. Use before reworking code to see whether it was changed recently and why.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "file_path": {
+                    "type": "string",
+                    "description": "Absolute path inside a workspace root to the file to annotate"
+                },
+                "start_line": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Optional first line to annotate"
+                },
+                "end_line": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Optional last line to annotate"
+                }
+            },
+            "required": ["file_path"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Read
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let Some(file_path) = request.arguments["file_path"].as_str() else {
+            bail!("Missing required argument \"file_path\"");
+        };
+        let path = self.file_manager.resolve(file_path)?;
+        if !path.is_file() {
+            bail!("Not a file: {file_path}");
+        }
+
+        let start_line = request.arguments["start_line"].as_u64();
+        let end_line = request.arguments["end_line"].as_u64();
+        let range = match (start_line, end_line) {
+            (Some(start), Some(end)) if start > end => {
+                bail!("start_line must not be after end_line")
+            }
+            (Some(start), Some(end)) => Some(format!("{start},{end}")),
+            (Some(start), None) => Some(format!("{start},")),
+            (None, Some(end)) => Some(format!("1,{end}")),
+            (None, None) => None,
+        };
+
+        Ok(Box::new(GitBlameHandle {
+            path,
+            range,
+            arguments: request.arguments.clone(),
+            tool_use_id: request.tool_use_id.clone(),
+        }))
+    }
+}
+
+struct GitBlameHandle {
+    path: PathBuf,
+    range: Option<String>,
+    arguments: Value,
+    tool_use_id: String,
+}
+
+async fn run_blame(path: &Path, range: Option<&str>) -> Result<String> {
+    let Some(dir) = path.parent() else {
+        bail!("File has no parent directory");
+    };
+    let mut command = Command::new("git");
+    command.arg("blame").arg("--line-porcelain");
+    if let Some(range) = range {
+        command.arg("-L").arg(range);
+    }
+    let output = command
+        .arg("--")
+        .arg(path)
+        .current_dir(dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git blame failed: {}", stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for GitBlameHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "git_blame".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: self.arguments.clone(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let output = match run_blame(&self.path, self.range.as_deref()).await {
+            Ok(output) => output,
+            Err(e) => {
+                return ToolOutput::Result {
+                    content: format!("{e:#}"),
+                    is_error: true,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::Error {
+                        short_message: "git blame failed".to_string(),
+                        detailed_message: format!("{e:#}"),
+                        category: None,
+                    },
+                }
+            }
+        };
+
+        let runs = parse_line_porcelain(&output);
+        let mut lines: Vec<String> = runs
+            .iter()
+            .take(MAX_BLAME_RUNS)
+            .map(BlameRun::render)
+            .collect();
+        if runs.len() > MAX_BLAME_RUNS {
+            lines.push(format!(
+                "... {} more regions omitted; narrow start_line/end_line to see them",
+                runs.len() - MAX_BLAME_RUNS
+            ));
+        }
+        let content = lines.join("\n");
+
+        ToolOutput::Result {
+            content: content.clone(),
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Other {
+                result: json!({ "blame": content }),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_consecutive_lines_by_commit() {
+        let a = "a".repeat(40);
+        let b = "b".repeat(40);
+        let output = format!(
+            "{a} 1 1 2\nauthor Alice\nauthor-time 1700000000\nsummary Add parser\n\tfn parse() {{\n\
+             {a} 2 2\nauthor Alice\nauthor-time 1700000000\nsummary Add parser\n\t}}\n\
+             {b} 3 3 1\nauthor Bob\nauthor-time 1710000000\nsummary Fix bug\n\tparse();\n"
+        );
+
+        let runs = parse_line_porcelain(&output);
+        assert_eq!(runs.len(), 2);
+        assert_eq!((runs[0].start_line, runs[0].end_line), (1, 2));
+        assert_eq!(runs[0].author, "Alice");
+        assert_eq!(runs[0].date, "2023-11-14");
+        assert_eq!(runs[1].render(), "line 3: bbbbbbbb Bob 2024-03-09 Fix bug");
+    }
+}
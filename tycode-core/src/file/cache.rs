@@ -0,0 +1,179 @@
+//! In-memory cache of decoded file contents, shared by every
+//! `FileAccessManager` in the process.
+//!
+//! Entries are keyed by path and by the decoding settings they were read
+//! with, and validated against a hash of the file's bytes on every lookup, so
+//! edits made outside the manager (the user's editor, a bash command) are
+//! picked up even when they keep the file's size and mtime. A hit saves
+//! decoding and line ending normalization, not the read itself. Writes
+//! through a manager invalidate the entry directly. Total cached bytes are
+//! bounded; the oldest entries are evicted first.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use sha2::{Digest, Sha256};
+
+/// Upper bound on cached content across all files.
+pub const MAX_CACHED_BYTES: usize = 16 * 1024 * 1024;
+
+/// A file's size and modification time, for callers (the search indexes)
+/// that re-check files without reading them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub modified: SystemTime,
+    pub len: u64,
+}
+
+impl FileStamp {
+    pub fn of(metadata: &std::fs::Metadata) -> Option<Self> {
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Settings that change how bytes decode to text. An entry only serves reads
+/// made with the same settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decoding {
+    pub latin1_fallback: bool,
+}
+
+pub type ContentHash = [u8; 32];
+
+pub fn content_hash(bytes: &[u8]) -> ContentHash {
+    Sha256::digest(bytes).into()
+}
+
+/// The cache shared by every `FileAccessManager`.
+pub fn shared() -> Arc<Mutex<FileCache>> {
+    static SHARED: OnceLock<Arc<Mutex<FileCache>>> = OnceLock::new();
+    SHARED
+        .get_or_init(|| Arc::new(Mutex::new(FileCache::new(MAX_CACHED_BYTES))))
+        .clone()
+}
+
+struct CachedFile {
+    hash: ContentHash,
+    decoding: Decoding,
+    content: String,
+}
+
+pub struct FileCache {
+    entries: HashMap<PathBuf, CachedFile>,
+    /// Insertion order for eviction; may hold paths already removed.
+    order: VecDeque<PathBuf>,
+    max_bytes: usize,
+    bytes: usize,
+}
+
+impl FileCache {
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            max_bytes,
+            bytes: 0,
+        }
+    }
+
+    /// Returns the cached content if it was decoded from the same bytes with
+    /// the same settings.
+    pub fn get(&self, path: &Path, hash: &ContentHash, decoding: Decoding) -> Option<String> {
+        self.entries
+            .get(path)
+            .filter(|entry| entry.hash == *hash && entry.decoding == decoding)
+            .map(|entry| entry.content.clone())
+    }
+
+    pub fn insert(
+        &mut self,
+        path: PathBuf,
+        hash: ContentHash,
+        decoding: Decoding,
+        content: String,
+    ) {
+        self.invalidate(&path);
+        if content.len() > self.max_bytes {
+            return;
+        }
+
+        self.bytes += content.len();
+        self.entries.insert(
+            path.clone(),
+            CachedFile {
+                hash,
+                decoding,
+                content,
+            },
+        );
+        self.order.push_back(path);
+
+        while self.bytes > self.max_bytes {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.invalidate(&oldest);
+        }
+    }
+
+    pub fn invalidate(&mut self, path: &Path) {
+        if let Some(entry) = self.entries.remove(path) {
+            self.bytes -= entry.content.len();
+            self.order.retain(|p| p != path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UTF8: Decoding = Decoding {
+        latin1_fallback: false,
+    };
+    const LATIN1: Decoding = Decoding {
+        latin1_fallback: true,
+    };
+
+    #[test]
+    fn changed_bytes_miss() {
+        let mut cache = FileCache::new(1024);
+        let path = PathBuf::from("/a.txt");
+        cache.insert(path.clone(), content_hash(b"abc"), UTF8, "abc".to_string());
+
+        assert_eq!(
+            cache.get(&path, &content_hash(b"abc"), UTF8).as_deref(),
+            Some("abc")
+        );
+        assert_eq!(cache.get(&path, &content_hash(b"abd"), UTF8), None);
+    }
+
+    #[test]
+    fn other_decoding_settings_miss() {
+        let mut cache = FileCache::new(1024);
+        let path = PathBuf::from("/a.txt");
+        let hash = content_hash(b"caf\xE9");
+        cache.insert(path.clone(), hash, LATIN1, "café".to_string());
+
+        assert_eq!(cache.get(&path, &hash, UTF8), None);
+        assert!(cache.get(&path, &hash, LATIN1).is_some());
+    }
+
+    #[test]
+    fn evicts_oldest_beyond_byte_bound() {
+        let mut cache = FileCache::new(10);
+        let hash = content_hash(b"");
+        cache.insert(PathBuf::from("/a"), hash, UTF8, "aaaaaa".to_string());
+        cache.insert(PathBuf::from("/b"), hash, UTF8, "bbbbbb".to_string());
+
+        assert_eq!(cache.get(Path::new("/a"), &hash, UTF8), None);
+        assert!(cache.get(Path::new("/b"), &hash, UTF8).is_some());
+        assert_eq!(cache.bytes, 6);
+        assert_eq!(cache.entries.len(), 1);
+    }
+}
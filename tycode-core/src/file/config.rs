@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::settings::config::FileModificationApi;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -10,6 +12,84 @@ fn default_auto_context_bytes() -> usize {
     80_000
 }
 
+fn default_read_files_max_files() -> usize {
+    20
+}
+
+fn default_read_files_max_bytes() -> usize {
+    100_000
+}
+
+fn default_max_read_file_bytes() -> usize {
+    50_000
+}
+
+fn default_tool_examples() -> bool {
+    true
+}
+
+fn default_modify_context_lines() -> usize {
+    3
+}
+
+fn default_search_index_max_bytes() -> u64 {
+    256 * 1024 * 1024
+}
+
+fn default_max_lint_messages() -> usize {
+    30
+}
+
+/// How modify_file handles a call where only some search/replace blocks match.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplaceMode {
+    /// Reject the whole edit if any block fails to match.
+    #[default]
+    Atomic,
+    /// Apply the blocks that match and report per-block status for the rest.
+    BestEffort,
+}
+
+/// How a pending edit confirmation is resolved when the reply is not a clear
+/// yes/no (e.g. unattended runs that answer with a canned message).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EditDecision {
+    #[default]
+    Decline,
+    Approve,
+}
+
+/// When a linter runs on edited files.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LintTrigger {
+    /// Right after each edit. Suits fast per-file linters such as eslint.
+    #[default]
+    Edit,
+    /// Once before the next model request, covering every file edited since
+    /// the last run. Suits slow project-wide linters such as cargo clippy.
+    NextRequest,
+}
+
+/// A linter run on files after write_file/modify_file change them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct LinterConfig {
+    /// File extensions, without the dot, that trigger this linter.
+    pub extensions: Vec<String>,
+
+    /// Command run from the file's workspace root. `{file}` is replaced with
+    /// the file's quoted absolute path; without it the command is run once
+    /// and its findings are matched to the edited files. Findings are read
+    /// from `path:line[:col]: message` lines, e.g.
+    /// `cargo clippy --message-format short` or `eslint --format unix {file}`.
+    pub command: String,
+
+    #[serde(default)]
+    pub trigger: LintTrigger,
+}
+
 /// Settings for tools that interact with the file system.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct File {
@@ -24,10 +104,119 @@ pub struct File {
     /// needs to be configured.
     #[serde(default = "default_auto_context_bytes")]
     pub auto_context_bytes: usize,
+
+    /// Per-agent overrides of `auto_context_bytes`, keyed by agent name (e.g. a
+    /// larger listing for a context-gathering agent, a smaller one for a quick
+    /// editor). Larger values risk pushing requests past the model's context
+    /// limit.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub agent_auto_context_bytes: HashMap<String, usize>,
+
+    /// Maximum number of files a single read_files call returns.
+    #[serde(default = "default_read_files_max_files")]
+    pub read_files_max_files: usize,
+
+    /// Maximum total bytes a single read_files call returns.
+    #[serde(default = "default_read_files_max_bytes")]
+    pub read_files_max_bytes: usize,
+
+    /// Maximum bytes returned for any one file, independent of the total
+    /// `read_files_max_bytes`. Larger files are truncated with a note giving
+    /// their full size.
+    #[serde(default = "default_max_read_file_bytes")]
+    pub max_read_file_bytes: usize,
+
+    /// Read text that is not valid UTF-8 as Latin-1 instead of failing, and
+    /// write it back as Latin-1 after edits. Off by default: any byte
+    /// sequence decodes as Latin-1, so a file in another legacy encoding
+    /// would be shown garbled rather than rejected.
+    #[serde(default)]
+    pub latin1_fallback: bool,
+
+    /// Keep a persistent trigram index under `.tycode/index` so search_files
+    /// only scans files that can match. Worthwhile on large repositories.
+    #[serde(default)]
+    pub search_index: bool,
+
+    /// Workspaces with more indexable content than this are searched by
+    /// walking the tree instead.
+    #[serde(default = "default_search_index_max_bytes")]
+    pub search_index_max_bytes: u64,
+
+    /// Offer the git_blame tool. Off by default since blame is slow on large
+    /// files and its output adds to context.
+    #[serde(default)]
+    pub git_blame: bool,
+
+    /// Offer the resolve_conflicts tool, which keeps one side (or both) of
+    /// each git merge conflict in a file.
+    #[serde(default)]
+    pub resolve_conflicts: bool,
+
+    /// Whether find/replace edits are all-or-nothing or apply the matching
+    /// blocks and report the rest.
+    #[serde(default)]
+    pub replace_mode: ReplaceMode,
+
+    /// Unchanged lines shown around each change in a successful modify_file
+    /// result, so the edit can be checked without re-reading the file.
+    #[serde(default = "default_modify_context_lines")]
+    pub modify_context_lines: usize,
+
+    /// Show a worked example of the selected modify_file tool in the system
+    /// prompt. Turn off to save tokens once a model uses the tool reliably.
+    #[serde(default = "default_tool_examples")]
+    pub tool_examples: bool,
+
+    /// Show the diff and ask for a y/n confirmation before write_file and
+    /// modify_file changes are written to disk. Applies at the balanced
+    /// autonomy level; conservative agents always confirm and autonomous
+    /// ones never do.
+    #[serde(default)]
+    pub confirm_edits: bool,
+
+    /// Decision applied when a confirmation is answered with something other
+    /// than yes/no.
+    #[serde(default)]
+    pub unanswered_edit_decision: EditDecision,
+
+    /// Seconds after which an unanswered confirmation is discarded, without
+    /// waiting for the next message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm_edits_timeout_seconds: Option<u64>,
+
+    /// Hold write_file and modify_file changes as proposals the user accepts
+    /// or rejects one by one before any is written. Takes precedence over
+    /// `confirm_edits`. Ignored for agents at the autonomous level.
+    #[serde(default)]
+    pub propose_edits: bool,
+
+    /// Linters run on edited files. Their findings stay in the model's context
+    /// until a later run on the same file comes back clean.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub linters: Vec<LinterConfig>,
+
+    /// Maximum lint findings shown in context.
+    #[serde(default = "default_max_lint_messages")]
+    pub max_lint_messages: usize,
 }
 
 impl File {
     pub const NAMESPACE: &str = "file";
+
+    /// The file listing budget for `agent`, falling back to the global
+    /// `auto_context_bytes`. A zero override would hide the listing entirely,
+    /// so it is ignored.
+    pub fn auto_context_bytes_for(&self, agent: &str) -> usize {
+        match self.agent_auto_context_bytes.get(agent) {
+            Some(0) => {
+                tracing::warn!(agent, "Ignoring auto_context_bytes override of 0");
+                self.auto_context_bytes
+            }
+            Some(&bytes) => bytes,
+            None => self.auto_context_bytes,
+        }
+    }
 }
 
 impl Default for File {
@@ -35,6 +224,24 @@ impl Default for File {
         Self {
             file_modification_api: FileModificationApi::Default,
             auto_context_bytes: default_auto_context_bytes(),
+            agent_auto_context_bytes: HashMap::new(),
+            read_files_max_files: default_read_files_max_files(),
+            read_files_max_bytes: default_read_files_max_bytes(),
+            max_read_file_bytes: default_max_read_file_bytes(),
+            latin1_fallback: false,
+            search_index: false,
+            search_index_max_bytes: default_search_index_max_bytes(),
+            git_blame: false,
+            resolve_conflicts: false,
+            replace_mode: ReplaceMode::default(),
+            modify_context_lines: default_modify_context_lines(),
+            tool_examples: default_tool_examples(),
+            confirm_edits: false,
+            unanswered_edit_decision: EditDecision::default(),
+            confirm_edits_timeout_seconds: None,
+            propose_edits: false,
+            linters: Vec::new(),
+            max_lint_messages: default_max_lint_messages(),
         }
     }
 }
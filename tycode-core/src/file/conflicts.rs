@@ -0,0 +1,286 @@
+//! Detection and resolution of git merge-conflict markers.
+//!
+//! A conflict is a `<<<<<<<` line, the "ours" lines, an optional diff3
+//! `|||||||` base section, a `=======` line, the "theirs" lines and a closing
+//! `>>>>>>>` line. Markers may be indented, since pasted or re-indented
+//! conflicts are common in files agents are asked to clean up. Unterminated
+//! regions are not treated as conflicts.
+
+use serde::Serialize;
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// One conflicted region. Line numbers are 1-based and inclusive of the
+/// marker lines.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Conflict {
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Text after `<<<<<<<`, usually `HEAD`.
+    pub ours_label: String,
+    /// Text after `>>>>>>>`, usually the merged branch or commit.
+    pub theirs_label: String,
+    pub ours: Vec<String>,
+    /// Common ancestor lines from a diff3-style conflict.
+    pub base: Option<Vec<String>>,
+    pub theirs: Vec<String>,
+}
+
+/// Which side of each conflict to keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Ours,
+    Theirs,
+    /// Ours followed by theirs.
+    Both,
+}
+
+impl Resolution {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "ours" => Some(Self::Ours),
+            "theirs" => Some(Self::Theirs),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the label after `marker` if `line` is that marker line.
+fn marker_label<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line.trim_start().strip_prefix(marker)?;
+    if rest.is_empty() {
+        return Some("");
+    }
+    // Longer runs (e.g. `========`) are content such as underlines, not markers
+    rest.strip_prefix(' ').map(str::trim_end)
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    marker_label(line, marker).is_some()
+}
+
+/// Finds every complete conflict in `content`, in file order.
+pub fn find_conflicts(content: &str) -> Vec<Conflict> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut conflicts = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some(ours_label) = marker_label(lines[i], OURS_MARKER) else {
+            i += 1;
+            continue;
+        };
+        match parse_conflict(&lines, i, ours_label) {
+            Some(conflict) => {
+                i = conflict.end_line;
+                conflicts.push(conflict);
+            }
+            None => i += 1,
+        }
+    }
+
+    conflicts
+}
+
+/// Parses the conflict whose `<<<<<<<` line is at index `start`.
+fn parse_conflict(lines: &[&str], start: usize, ours_label: &str) -> Option<Conflict> {
+    let mut ours = Vec::new();
+    let mut base: Option<Vec<String>> = None;
+    let mut i = start + 1;
+
+    loop {
+        let line = *lines.get(i)?;
+        if is_marker(line, SEPARATOR_MARKER) {
+            break;
+        }
+        if is_marker(line, OURS_MARKER) || is_marker(line, THEIRS_MARKER) {
+            return None;
+        }
+        if is_marker(line, BASE_MARKER) {
+            base = Some(Vec::new());
+        } else if let Some(base) = &mut base {
+            base.push(line.to_string());
+        } else {
+            ours.push(line.to_string());
+        }
+        i += 1;
+    }
+
+    let mut theirs = Vec::new();
+    i += 1;
+    loop {
+        let line = *lines.get(i)?;
+        if let Some(theirs_label) = marker_label(line, THEIRS_MARKER) {
+            return Some(Conflict {
+                start_line: start + 1,
+                end_line: i + 1,
+                ours_label: ours_label.to_string(),
+                theirs_label: theirs_label.to_string(),
+                ours,
+                base,
+                theirs,
+            });
+        }
+        if is_marker(line, OURS_MARKER)
+            || is_marker(line, SEPARATOR_MARKER)
+            || is_marker(line, BASE_MARKER)
+        {
+            return None;
+        }
+        theirs.push(line.to_string());
+        i += 1;
+    }
+}
+
+/// Replaces every conflict in `content` with the chosen side, keeping the
+/// surrounding text and trailing newline untouched.
+pub fn resolve_conflicts(content: &str, resolution: Resolution) -> String {
+    resolve_selected(content, resolution, |_| true)
+}
+
+/// Like [`resolve_conflicts`] but only for the conflict at `index`
+/// (0-based, in file order); the others are left in place.
+pub fn resolve_conflict_at(content: &str, index: usize, resolution: Resolution) -> String {
+    resolve_selected(content, resolution, |i| i == index)
+}
+
+fn resolve_selected(
+    content: &str,
+    resolution: Resolution,
+    selected: impl Fn(usize) -> bool,
+) -> String {
+    let conflicts = find_conflicts(content);
+    if conflicts.is_empty() {
+        return content.to_string();
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut output: Vec<&str> = Vec::with_capacity(lines.len());
+    let mut next_line = 0;
+
+    for (index, conflict) in conflicts.iter().enumerate() {
+        if !selected(index) {
+            continue;
+        }
+        output.extend(&lines[next_line..conflict.start_line - 1]);
+        let (ours, theirs) = (conflict.ours.iter(), conflict.theirs.iter());
+        match resolution {
+            Resolution::Ours => output.extend(ours.map(String::as_str)),
+            Resolution::Theirs => output.extend(theirs.map(String::as_str)),
+            Resolution::Both => output.extend(ours.chain(theirs).map(String::as_str)),
+        }
+        next_line = conflict.end_line;
+    }
+    output.extend(&lines[next_line..]);
+
+    let mut resolved = output.join("\n");
+    if content.ends_with('\n') && !output.is_empty() {
+        resolved.push('\n');
+    }
+    resolved
+}
+
+/// Warning for edits that would leave conflict markers in a file, or None
+/// when the content is clean.
+pub fn conflict_marker_warning(content: &str) -> Option<String> {
+    let conflicts = find_conflicts(content);
+    if conflicts.is_empty() {
+        return None;
+    }
+    let ranges: Vec<String> = conflicts
+        .iter()
+        .map(|c| format!("{}-{}", c.start_line, c.end_line))
+        .collect();
+    Some(format!(
+        "The file still contains {} unresolved merge conflict(s) at lines {}. Remove the <<<<<<< / ======= / >>>>>>> markers, or use resolve_conflicts if it is available.",
+        conflicts.len(),
+        ranges.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The conflict used by the modify_file merge-conflict fixture.
+    const FIXTURE: &str = r#"fn sum_numbers(numbers: Vec<i32>) -> i32 {
+    let mut total = 0;
+    for num in numbers {
+        total += num;
+        <<<<<<< HEAD
+        // Old debug output
+        println!("Adding {} to total", num);
+        =======
+        // New debug output with emoji! 🎯
+        println!("🔢 Adding {} to total 📊", num);
+        >>>>>>> branch-feature-emoji-logs
+    }
+    return total;
+}"#;
+
+    #[test]
+    fn finds_indented_fixture_conflict() {
+        let conflicts = find_conflicts(FIXTURE);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!((conflict.start_line, conflict.end_line), (5, 11));
+        assert_eq!(conflict.ours_label, "HEAD");
+        assert_eq!(conflict.theirs_label, "branch-feature-emoji-logs");
+        assert_eq!(conflict.ours.len(), 2);
+        assert_eq!(
+            conflict.theirs[1].trim(),
+            r#"println!("🔢 Adding {} to total 📊", num);"#
+        );
+        assert_eq!(conflict.base, None);
+    }
+
+    #[test]
+    fn resolves_fixture_to_either_side() {
+        let theirs = resolve_conflicts(FIXTURE, Resolution::Theirs);
+        assert!(find_conflicts(&theirs).is_empty());
+        assert!(theirs.contains("🔢 Adding"));
+        assert!(!theirs.contains("Old debug output"));
+        assert!(theirs.ends_with("    return total;\n}"));
+
+        let ours = resolve_conflicts(FIXTURE, Resolution::Ours);
+        assert!(ours.contains("Old debug output"));
+        assert!(!ours.contains("🔢"));
+
+        let both = resolve_conflicts(FIXTURE, Resolution::Both);
+        let old = both.find("Old debug output").unwrap();
+        let new = both.find("New debug output").unwrap();
+        assert!(old < new, "ours should come first: {both}");
+    }
+
+    #[test]
+    fn handles_diff3_base_and_multiple_conflicts() {
+        let content = "a\n<<<<<<< HEAD\nours\n||||||| base\nbase\n=======\ntheirs\n>>>>>>> feature\nb\n<<<<<<< HEAD\nx\n=======\ny\n>>>>>>> feature\nc\n";
+        let conflicts = find_conflicts(content);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].base, Some(vec!["base".to_string()]));
+        assert_eq!(conflicts[0].ours, vec!["ours".to_string()]);
+
+        assert_eq!(
+            resolve_conflicts(content, Resolution::Theirs),
+            "a\ntheirs\nb\ny\nc\n"
+        );
+        assert_eq!(
+            resolve_conflict_at(content, 1, Resolution::Ours),
+            "a\n<<<<<<< HEAD\nours\n||||||| base\nbase\n=======\ntheirs\n>>>>>>> feature\nb\nx\nc\n"
+        );
+    }
+
+    #[test]
+    fn ignores_incomplete_and_lookalike_markers() {
+        assert!(find_conflicts("<<<<<<< HEAD\nours\n=======\nno end\n").is_empty());
+        assert!(find_conflicts("Title\n=======\n\n<<<<<<<< not a marker\n").is_empty());
+        assert_eq!(conflict_marker_warning("clean\n"), None);
+
+        let warning = conflict_marker_warning(FIXTURE).unwrap();
+        assert!(warning.contains("1 unresolved merge conflict(s) at lines 5-11"));
+    }
+}
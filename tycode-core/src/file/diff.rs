@@ -0,0 +1,233 @@
+//! `diff_files` tool: unified diff between two workspace files.
+//!
+//! Useful for comparing a generated file against a reference, or two
+//! versions of the same file, without shelling out to `diff`. The diff text
+//! is capped at `MAX_DIFF_BYTES`; the added/removed counts always cover the
+//! whole diff.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use similar::{ChangeTag, TextDiff};
+
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::access::FileAccessManager;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+use crate::tools::ToolName;
+
+const MAX_DIFF_BYTES: usize = 50_000;
+const DEFAULT_CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FileDiff {
+    pub diff: String,
+    pub added: usize,
+    pub removed: usize,
+    pub truncated: bool,
+}
+
+/// Unified diff of `old` against `new`, labelled with the two paths.
+pub fn diff_text(
+    old: &str,
+    new: &str,
+    old_label: &str,
+    new_label: &str,
+    context_lines: usize,
+    max_bytes: usize,
+) -> FileDiff {
+    let text_diff = TextDiff::from_lines(old, new);
+    let (mut added, mut removed) = (0, 0);
+    for change in text_diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => added += 1,
+            ChangeTag::Delete => removed += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+
+    let full = text_diff
+        .unified_diff()
+        .context_radius(context_lines)
+        .header(old_label, new_label)
+        .to_string();
+    let truncated = full.len() > max_bytes;
+    let diff = if truncated {
+        let mut end = max_bytes;
+        while !full.is_char_boundary(end) {
+            end -= 1;
+        }
+        let end = full[..end].rfind('\n').map_or(end, |i| i + 1);
+        full[..end].to_string()
+    } else {
+        full
+    };
+
+    FileDiff {
+        diff,
+        added,
+        removed,
+        truncated,
+    }
+}
+
+pub struct DiffFilesTool {
+    file_manager: FileAccessManager,
+}
+
+impl DiffFilesTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("diff_files")
+    }
+
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> Result<Self> {
+        Ok(Self {
+            file_manager: FileAccessManager::new(workspace_roots)?.with_settings(settings),
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for DiffFilesTool {
+    fn name(&self) -> String {
+        "diff_files".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Compare two files and return a unified diff plus the number of lines added and removed. Use this to check a generated file against a reference or to compare two versions of a file. Large diffs are truncated.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "old_path": {
+                    "type": "string",
+                    "description": "Absolute path inside a workspace root to the original file"
+                },
+                "new_path": {
+                    "type": "string",
+                    "description": "Absolute path inside a workspace root to the file to compare against it"
+                },
+                "context_lines": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": format!("Unchanged lines shown around each change (default {DEFAULT_CONTEXT_LINES})")
+                }
+            },
+            "required": ["old_path", "new_path"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Read
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let Some(old_path) = request.arguments["old_path"].as_str() else {
+            bail!("Missing required argument \"old_path\"");
+        };
+        let Some(new_path) = request.arguments["new_path"].as_str() else {
+            bail!("Missing required argument \"new_path\"");
+        };
+        let context_lines = request.arguments["context_lines"]
+            .as_u64()
+            .map_or(DEFAULT_CONTEXT_LINES, |n| n as usize);
+
+        let old = self.file_manager.read_file(old_path).await?;
+        let new = self.file_manager.read_file(new_path).await?;
+
+        Ok(Box::new(DiffFilesHandle {
+            result: diff_text(
+                &old,
+                &new,
+                old_path,
+                new_path,
+                context_lines,
+                MAX_DIFF_BYTES,
+            ),
+            arguments: request.arguments.clone(),
+            tool_use_id: request.tool_use_id.clone(),
+        }))
+    }
+}
+
+struct DiffFilesHandle {
+    result: FileDiff,
+    arguments: Value,
+    tool_use_id: String,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for DiffFilesHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "diff_files".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: self.arguments.clone(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let FileDiff {
+            diff,
+            added,
+            removed,
+            truncated,
+        } = self.result;
+
+        let mut content = if added == 0 && removed == 0 {
+            "Files are identical".to_string()
+        } else {
+            format!("{added} line(s) added, {removed} line(s) removed\n\n{diff}")
+        };
+        if truncated {
+            content.push_str(&format!(
+                "\n... diff truncated at {MAX_DIFF_BYTES} bytes; reduce context_lines or compare smaller files to see the rest"
+            ));
+        }
+
+        ToolOutput::Result {
+            content,
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Other {
+                result: json!({
+                    "added": added,
+                    "removed": removed,
+                    "truncated": truncated,
+                    "diff": diff,
+                }),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_changes_and_labels_the_diff() {
+        let result = diff_text("a\nb\nc\n", "a\nB\nc\nd\n", "old.txt", "new.txt", 3, 10_000);
+        assert_eq!((result.added, result.removed), (2, 1));
+        assert!(!result.truncated);
+        assert!(result.diff.starts_with("--- old.txt\n+++ new.txt\n"));
+        assert!(result.diff.contains("-b\n+B\n"));
+    }
+
+    #[test]
+    fn large_diff_is_truncated_on_a_line_boundary() {
+        let new: String = (0..100).map(|i| format!("line {i}\n")).collect();
+        let result = diff_text("", &new, "old", "new", 3, 200);
+        assert!(result.truncated);
+        assert!(result.diff.len() <= 200);
+        assert!(result.diff.ends_with('\n'));
+        assert_eq!(result.added, 100);
+    }
+}
@@ -0,0 +1,145 @@
+//! Text encoding detection so edits round-trip a file's original encoding.
+//!
+//! Files are decoded to a `String` for the model and re-encoded with the
+//! original encoding (and BOM, if any) on write. Only encodings that can be
+//! identified reliably are supported: UTF-8 with or without a BOM, UTF-16
+//! with a BOM, and Latin-1 as an opt-in fallback (`latin1_fallback`) for
+//! non-UTF-8 text. Content with NUL bytes and no UTF-16 BOM is treated as
+//! binary.
+
+use anyhow::{bail, Result};
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// How many leading bytes are inspected for NULs when sniffing for binary
+/// content.
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl TextEncoding {
+    /// Decodes `bytes`, returning the text and the detected encoding. Text
+    /// that is not valid UTF-8 is an error unless `latin1_fallback` is set.
+    pub fn decode(bytes: &[u8], latin1_fallback: bool) -> Result<(String, Self)> {
+        if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+            return Ok((String::from_utf8(rest.to_vec())?, TextEncoding::Utf8Bom));
+        }
+        if let Some(rest) = bytes.strip_prefix(UTF16_LE_BOM) {
+            return Ok((
+                decode_utf16(rest, u16::from_le_bytes)?,
+                TextEncoding::Utf16Le,
+            ));
+        }
+        if let Some(rest) = bytes.strip_prefix(UTF16_BE_BOM) {
+            return Ok((
+                decode_utf16(rest, u16::from_be_bytes)?,
+                TextEncoding::Utf16Be,
+            ));
+        }
+
+        if is_binary(bytes) {
+            bail!("binary file; only text files can be read or edited");
+        }
+
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Ok((text.to_string(), TextEncoding::Utf8)),
+            // Every byte is a valid Latin-1 code point
+            Err(_) if latin1_fallback => Ok((
+                bytes.iter().map(|&b| b as char).collect(),
+                TextEncoding::Latin1,
+            )),
+            Err(e) => bail!("not valid UTF-8 ({e}); enable latin1_fallback to read it as Latin-1"),
+        }
+    }
+
+    /// Encodes `text` in this encoding, including the BOM if it had one.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        Ok(match self {
+            TextEncoding::Utf8 => text.as_bytes().to_vec(),
+            TextEncoding::Utf8Bom => [UTF8_BOM, text.as_bytes()].concat(),
+            TextEncoding::Utf16Le => {
+                let mut bytes = UTF16_LE_BOM.to_vec();
+                bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+                bytes
+            }
+            TextEncoding::Utf16Be => {
+                let mut bytes = UTF16_BE_BOM.to_vec();
+                bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+                bytes
+            }
+            TextEncoding::Latin1 => text
+                .chars()
+                .map(|c| {
+                    u8::try_from(u32::from(c)).map_err(|_| {
+                        anyhow::anyhow!(
+                            "'{c}' cannot be represented in the file's Latin-1 encoding"
+                        )
+                    })
+                })
+                .collect::<Result<Vec<u8>>>()?,
+        })
+    }
+}
+
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_SNIFF_BYTES).any(|&b| b == 0)
+}
+
+fn decode_utf16(bytes: &[u8], to_u16: fn([u8; 2]) -> u16) -> Result<String> {
+    if !bytes.len().is_multiple_of(2) {
+        bail!("truncated UTF-16 content");
+    }
+    let units = bytes.chunks_exact(2).map(|pair| to_u16([pair[0], pair[1]]));
+    Ok(char::decode_utf16(units).collect::<Result<String, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_bom_and_latin1() {
+        for bytes in [
+            b"\xEF\xBB\xBFhello".to_vec(),
+            b"caf\xE9".to_vec(),
+            b"\xFF\xFEh\0i\0".to_vec(),
+            b"plain".to_vec(),
+        ] {
+            let (text, encoding) = TextEncoding::decode(&bytes, true).unwrap();
+            assert_eq!(encoding.encode(&text).unwrap(), bytes, "{encoding:?}");
+        }
+
+        let (text, encoding) = TextEncoding::decode(b"caf\xE9", true).unwrap();
+        assert_eq!(text, "café");
+        assert_eq!(encoding, TextEncoding::Latin1);
+    }
+
+    #[test]
+    fn rejects_binary_content() {
+        let err = TextEncoding::decode(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR", true).unwrap_err();
+        assert!(err.to_string().contains("binary file"), "{err}");
+    }
+
+    #[test]
+    fn latin1_fallback_is_opt_in() {
+        let err = TextEncoding::decode(b"caf\xE9", false).unwrap_err();
+        assert!(err.to_string().contains("latin1_fallback"), "{err}");
+
+        let (_, encoding) = TextEncoding::decode(b"\xEF\xBB\xBFhello", false).unwrap();
+        assert_eq!(encoding, TextEncoding::Utf8Bom);
+    }
+
+    #[test]
+    fn latin1_rejects_unrepresentable_characters() {
+        assert!(TextEncoding::Latin1.encode("snowman ☃").is_err());
+    }
+}
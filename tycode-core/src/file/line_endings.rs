@@ -0,0 +1,69 @@
+//! Line ending detection so edits round-trip a file's existing endings.
+//!
+//! Content is handed to the model with `\n` endings; on write the file's
+//! dominant ending is restored. Files mixing CRLF and LF are written as LF.
+
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    /// Detects the line ending used by `content`. Files without newlines and
+    /// files mixing both styles are treated as LF.
+    pub fn detect(content: &str) -> Self {
+        let crlf = content.matches("\r\n").count();
+        let lf = content.matches('\n').count() - crlf;
+
+        match (crlf, lf) {
+            (0, _) => LineEnding::Lf,
+            (_, 0) => LineEnding::Crlf,
+            _ => {
+                tracing::warn!(crlf, lf, "File has mixed line endings; writing LF");
+                LineEnding::Lf
+            }
+        }
+    }
+
+    /// Rewrites all line endings in `content` to this style.
+    pub fn apply<'a>(&self, content: &'a str) -> Cow<'a, str> {
+        let normalized = normalize(content);
+        match self {
+            LineEnding::Lf => normalized,
+            LineEnding::Crlf => Cow::Owned(normalized.replace('\n', "\r\n")),
+        }
+    }
+}
+
+/// Converts CRLF line endings to LF.
+pub fn normalize(content: &str) -> Cow<'_, str> {
+    if content.contains("\r\n") {
+        Cow::Owned(content.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_dominant_ending() {
+        assert_eq!(LineEnding::detect("a\r\nb\r\n"), LineEnding::Crlf);
+        assert_eq!(LineEnding::detect("a\nb\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("no newline"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("a\r\nb\n"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn apply_round_trips_crlf() {
+        let original = "a\r\nb\r\n";
+        let edited = normalize(original).replace('b', "c");
+        assert_eq!(LineEnding::Crlf.apply(&edited), "a\r\nc\r\n");
+        assert_eq!(LineEnding::Lf.apply(original), "a\nb\n");
+    }
+}
@@ -140,7 +140,7 @@ impl FileModificationManager {
 }
 
 /// Counts the number of lines added when comparing original to new content
-fn count_lines_added(original: &str, new: &str) -> u32 {
+pub(crate) fn count_lines_added(original: &str, new: &str) -> u32 {
     let original_lines: std::collections::HashSet<&str> = original.lines().collect();
     let new_lines: std::collections::HashSet<&str> = new.lines().collect();
 
@@ -149,7 +149,7 @@ fn count_lines_added(original: &str, new: &str) -> u32 {
 }
 
 /// Counts the number of lines removed when comparing original to new content
-fn count_lines_removed(original: &str, new: &str) -> u32 {
+pub(crate) fn count_lines_removed(original: &str, new: &str) -> u32 {
     let original_lines: std::collections::HashSet<&str> = original.lines().collect();
     let new_lines: std::collections::HashSet<&str> = new.lines().collect();
 
@@ -9,6 +9,9 @@
 //! - Offers core APIs: read_file, write_file, delete_file, list_directory
 //! - All I/O goes through this layer; nothing uses std::fs directly
 //! - File discovery uses ignore::WalkBuilder to traverse directories while respecting ignore patterns and size limits
+//! - Text reads normalize CRLF to LF and writes restore the file's existing line endings (line_endings.rs)
+//! - Decoded text is cached process-wide by path, content hash and decoding settings; writes invalidate the entry (cache.rs)
+//! - Text reads decode BOM-marked (and, with `latin1_fallback`, Latin-1) files and writes re-encode them; binary files are rejected (encoding.rs)
 //!
 //! ### manager.rs
 //! Ties everything together and offers high-level APIs:
@@ -16,14 +19,27 @@
 //!
 //! ## Multiple workspaces
 //! Tycode supports multiple workspace roots (typically multiple git root
-//! projects open in the same VS Code window). File tools show and accept real
-//! absolute paths, and access.rs enforces that file operations stay inside one
-//! of the configured roots.
+//! projects open in the same VS Code window). File tools accept absolute paths
+//! and show them through resolver.rs, which writes anything under the home
+//! directory relative to `~` (and expands such paths back); access.rs enforces
+//! that file operations stay inside one of the configured roots.
 
 pub mod access;
+pub mod blame;
+pub mod cache;
 pub mod config;
+pub mod conflicts;
+pub mod diff;
+pub mod encoding;
 pub mod find;
+pub mod line_endings;
 pub mod manager;
 pub mod modify;
+pub mod pinned;
+pub mod read_files;
 pub mod read_only;
+pub mod resolver;
+pub mod search;
+pub mod search_index;
+pub mod skeleton;
 pub mod workspace;
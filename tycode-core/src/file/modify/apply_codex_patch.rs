@@ -1,14 +1,22 @@
 use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
 use crate::file::access::FileAccessManager;
+use crate::file::conflicts::conflict_marker_warning;
 use crate::file::find::find_closest_match;
 use crate::file::manager::FileModificationManager;
+use crate::settings::SettingsManager;
 use crate::tools::r#trait::{
-    ContinuationPreference, FileModification, FileOperation, ToolCallHandle, ToolCategory,
-    ToolExecutor, ToolOutput, ToolRequest,
+    ContinuationPreference, FileModification, FileOperation, ToolCallHandle, ToolCapability,
+    ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
 };
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use serde_json::{json, Value};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+const USAGE_EXAMPLE: &str = r#"To fix the body of `add` and update a caller, give one hunk per change separated by @@. Context lines start with a space and must match the file exactly:
+{
+  "file_path": "/workspace/src/math.rs",
+  "hunks": " fn add(a: i32, b: i32) -> i32 {\n-    a - b\n+    a + b\n }\n@@\n fn main() {\n-    println!(\"{}\", add(1, 2, 3));\n+    println!(\"{}\", add(1, 2));\n }"
+}"#;
 
 /// Tool for applying codex-style patches without line numbers
 #[derive(Clone)]
@@ -49,8 +57,8 @@ impl CodexHunk {
 }
 
 impl ApplyCodexPatchTool {
-    pub fn new(workspace_roots: Vec<PathBuf>) -> anyhow::Result<Self> {
-        let file_manager = FileAccessManager::new(workspace_roots)?;
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> anyhow::Result<Self> {
+        let file_manager = FileAccessManager::new(workspace_roots)?.with_settings(settings);
         Ok(Self { file_manager })
     }
 
@@ -368,6 +376,96 @@ impl ApplyCodexPatchTool {
 
         Ok((file_lines.join("\n"), None))
     }
+
+    /// Reads hunks from a patch file inside a workspace root, for patches
+    /// the agent already produced on disk (e.g. with `git diff > fix.patch`).
+    /// A patch whose headers name a different file than `target` is rejected.
+    async fn read_patch_file(&self, patch_file: &str, target: &Path) -> Result<String> {
+        let resolved = self.file_manager.resolve(patch_file)?;
+        let contents = self
+            .file_manager
+            .read_file(&resolved.to_string_lossy())
+            .await
+            .with_context(|| format!("Failed to read patch file {patch_file}"))?;
+        strip_diff_headers(&contents, target)
+    }
+}
+
+/// Prefixes of the per-file header lines `diff`/`git diff` emit before the
+/// first `@@` range line.
+const DIFF_HEADER_PREFIXES: &[&str] = &[
+    "diff ",
+    "index ",
+    "--- ",
+    "+++ ",
+    "new file mode",
+    "deleted file mode",
+    "old mode",
+    "new mode",
+    "similarity index",
+    "rename from",
+    "rename to",
+];
+
+/// Drops unified diff file headers and "\ No newline at end of file"
+/// markers so a saved `diff`/`git diff` parses like inline hunks; the `@@`
+/// range lines already separate hunks. Files without such headers are bare
+/// codex hunks and are returned unchanged. A diff touching more than one
+/// file, or one whose `---`/`+++` headers name a file other than `target`,
+/// is rejected since the tool edits one file.
+fn strip_diff_headers(patch: &str, target: &Path) -> Result<String> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let is_header = |line: &str| DIFF_HEADER_PREFIXES.iter().any(|p| line.starts_with(p));
+
+    let Some(first_hunk) = lines.iter().position(|line| line.starts_with("@@")) else {
+        return Ok(patch.to_string());
+    };
+    let preamble = &lines[..first_hunk];
+    if preamble.is_empty()
+        || !preamble
+            .iter()
+            .all(|line| line.trim().is_empty() || is_header(line))
+    {
+        return Ok(patch.to_string());
+    }
+
+    // The new side names the file unless it is a deletion
+    let header_name = |prefix: &str| {
+        preamble
+            .iter()
+            .find_map(|line| line.strip_prefix(prefix))
+            // `diff -u` appends a tab and the modification time
+            .map(|name| name.split('\t').next().unwrap_or(name).trim())
+            .filter(|name| *name != "/dev/null")
+    };
+    let named = header_name("+++ ")
+        .map(|name| (name, "b/"))
+        .or_else(|| header_name("--- ").map(|name| (name, "a/")));
+    if let Some((name, git_prefix)) = named {
+        let names_target = |name: &str| target.ends_with(name.trim_start_matches("./"));
+        if !names_target(name) && !name.strip_prefix(git_prefix).is_some_and(names_target) {
+            bail!(
+                "Patch file is for {name}, not {}; pass the file the patch was made for",
+                target.display()
+            );
+        }
+    }
+
+    let mut kept = Vec::new();
+    for (idx, line) in lines.iter().enumerate().skip(first_hunk) {
+        let starts_file = line.starts_with("diff --git ")
+            || (line.starts_with("--- ")
+                && lines
+                    .get(idx + 1)
+                    .is_some_and(|next| next.starts_with("+++ ")));
+        if starts_file {
+            bail!("Patch file contains changes to more than one file; split it per file");
+        }
+        if !line.starts_with("\\ ") {
+            kept.push(*line);
+        }
+    }
+    Ok(kept.join("\n"))
 }
 
 struct ApplyCodexPatchHandle {
@@ -396,21 +494,27 @@ impl ToolCallHandle for ApplyCodexPatchHandle {
 
     async fn execute(self: Box<Self>) -> ToolOutput {
         let manager = FileModificationManager::new(self.file_manager.clone());
+        let warning = self.modification.warning.clone();
         match manager.apply_modification(self.modification).await {
-            Ok(stats) => ToolOutput::Result {
-                content: json!({
+            Ok(stats) => {
+                let mut content = json!({
                     "success": true,
                     "lines_added": stats.lines_added,
                     "lines_removed": stats.lines_removed
-                })
-                .to_string(),
-                is_error: false,
-                continuation: ContinuationPreference::Continue,
-                ui_result: ToolExecutionResult::ModifyFile {
-                    lines_added: stats.lines_added,
-                    lines_removed: stats.lines_removed,
-                },
-            },
+                });
+                if let Some(warning) = warning {
+                    content["warning"] = json!(warning);
+                }
+                ToolOutput::Result {
+                    content: content.to_string(),
+                    is_error: false,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::ModifyFile {
+                        lines_added: stats.lines_added,
+                        lines_removed: stats.lines_removed,
+                    },
+                }
+            }
             Err(e) => ToolOutput::Result {
                 content: format!("Failed to apply codex patch: {e:?}"),
                 is_error: true,
@@ -418,6 +522,7 @@ impl ToolCallHandle for ApplyCodexPatchHandle {
                 ui_result: ToolExecutionResult::Error {
                     short_message: "Codex patch failed".to_string(),
                     detailed_message: format!("{e:?}"),
+                    category: None,
                 },
             },
         }
@@ -431,7 +536,7 @@ impl ToolExecutor for ApplyCodexPatchTool {
     }
 
     fn description(&self) -> String {
-        "Modify a file by applying multiple hunks in a single call (no line numbers required). Each hunk independently specifies a location and changes to apply.".to_string()
+        "Modify a file by applying multiple hunks in a single call (no line numbers required). Each hunk independently specifies a location and changes to apply. Hunks can be given inline or read from a patch file in the workspace.".to_string()
     }
 
     fn input_schema(&self) -> Value {
@@ -471,9 +576,13 @@ Example - multiple changes in one call:
  line 12
 
 Use enough context lines to uniquely identify each location."#
+                },
+                "patch_file": {
+                    "type": "string",
+                    "description": "Instead of hunks: path inside a workspace root to a patch file already on disk (bare hunks, or a unified diff for this one file such as `git diff` output). Avoids repeating a large patch inline."
                 }
             },
-            "required": ["file_path", "hunks"]
+            "required": ["file_path"]
         })
     }
 
@@ -481,6 +590,14 @@ Use enough context lines to uniquely identify each location."#
         ToolCategory::Execution
     }
 
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write]
+    }
+
+    fn usage_example(&self) -> Option<String> {
+        Some(USAGE_EXAMPLE.to_string())
+    }
+
     async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
         let file_path = request
             .arguments
@@ -488,23 +605,30 @@ Use enough context lines to uniquely identify each location."#
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing required parameter: file_path"))?;
 
-        let hunks_string = request
-            .arguments
-            .get("hunks")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| {
-                anyhow::anyhow!("Missing required parameter: hunks (must be a string)")
-            })?;
+        let hunks = request.arguments.get("hunks").and_then(|v| v.as_str());
+        let patch_file = request.arguments.get("patch_file").and_then(|v| v.as_str());
+        let resolved_path = self.file_manager.resolve(file_path)?;
+        let hunks_string = match (hunks, patch_file) {
+            (Some(_), Some(_)) => bail!("Provide either hunks or patch_file, not both"),
+            (Some(hunks), None) => hunks.to_string(),
+            (None, Some(patch_file)) => self.read_patch_file(patch_file, &resolved_path).await?,
+            (None, None) => {
+                bail!("Missing required parameter: hunks (must be a string) or patch_file")
+            }
+        };
 
         if hunks_string.trim().is_empty() {
             bail!("hunks string must not be empty");
         }
 
-        let hunk_strings = self.split_hunks_on_markers(&[hunks_string.to_string()]);
-        let resolved_path = self.file_manager.resolve(file_path)?;
+        let hunk_strings = self.split_hunks_on_markers(&[hunks_string]);
         let resolved_path_str = resolved_path.to_string_lossy().to_string();
         let original_content: String = self.file_manager.read_file(&resolved_path_str).await?;
-        let (patched_content, warning) = self.apply_hunks(&original_content, &hunk_strings)?;
+        let (patched_content, hunk_warning) = self.apply_hunks(&original_content, &hunk_strings)?;
+        let warning = match (hunk_warning, conflict_marker_warning(&patched_content)) {
+            (Some(hunks), Some(conflicts)) => Some(format!("{hunks}{conflicts}")),
+            (hunks, conflicts) => hunks.or(conflicts),
+        };
 
         let modification = FileModification {
             path: resolved_path,
@@ -534,12 +658,16 @@ mod tests {
         path.to_string_lossy().to_string()
     }
 
+    fn test_settings(temp_dir: &TempDir) -> SettingsManager {
+        SettingsManager::from_path(temp_dir.path().join("settings.toml")).unwrap()
+    }
+
     #[tokio::test]
     async fn test_apply_codex_patch_simple() {
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "line 1\nline 2\nline 3\nline 4\nline 5";
@@ -593,7 +721,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "line 1\nline 2\nline 3\nline 4\nline 5";
@@ -632,7 +760,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "line 1\nline 2\n line 3\nline 4";
@@ -671,7 +799,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "line 1\nline 2\nline 3";
@@ -709,7 +837,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "line 1\nline 2";
@@ -743,7 +871,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "line 1\nline 2\nline 3\nline 4\nline 5\nline 6\nline 7";
@@ -788,7 +916,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "some context\nsome line to remove\nsome other context\nanother to remove\nfinal context";
@@ -832,7 +960,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "line 1\nline 2\nline 3";
@@ -873,7 +1001,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "line 1\nline 2\nline 3\nline 4\nline 5";
@@ -916,7 +1044,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = r#"fn sum_numbers(numbers: Vec<i32>) -> i32 {
@@ -988,7 +1116,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "    line with 4 spaces\n        line with 8 spaces\n    back to 4";
@@ -1027,7 +1155,7 @@ line 4"#;
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().join("test");
         fs::create_dir(&root).unwrap();
-        let tool = ApplyCodexPatchTool::new(vec![root.clone()]).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
 
         let file_manager = FileAccessManager::new(vec![root.clone()]).unwrap();
         let original_content = "line 1\n        line 2 with 8 spaces\nline 3";
@@ -1071,7 +1199,8 @@ line 4"#;
 
     #[test]
     fn test_lines_match_tolerant_asymmetry() {
-        let tool = ApplyCodexPatchTool::new(vec![]).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![], test_settings(&temp_dir)).unwrap();
 
         assert!(tool.lines_match_tolerant("line content", "line content"));
 
@@ -1083,4 +1212,122 @@ line 4"#;
             "Bug: lines_match_tolerant is asymmetric. It tolerates file having extra space but not expected having extra space."
         );
     }
+
+    #[tokio::test]
+    async fn test_apply_codex_patch_from_git_diff_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("test");
+        fs::create_dir(&root).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
+
+        let file_path = root.join("test.txt");
+        fs::write(&file_path, "line 1\nline 2\nline 3\nline 4\nline 5").unwrap();
+        let patch_path = root.join("fix.patch");
+        fs::write(
+            &patch_path,
+            r#"diff --git a/test.txt b/test.txt
+index 1111111..2222222 100644
+--- a/test.txt
++++ b/test.txt
+@@ -1,3 +1,3 @@
+ line 1
+-line 2
++line 2 modified
+ line 3
+@@ -4,2 +4,2 @@
+ line 4
+-line 5
++line 5 modified
+\ No newline at end of file
+"#,
+        )
+        .unwrap();
+
+        let request = ToolRequest::new(
+            json!({
+                "file_path": path_str(&file_path),
+                "patch_file": path_str(&patch_path)
+            }),
+            "test_id".to_string(),
+        );
+        let handle = tool.process(&request).await.unwrap();
+
+        if let ToolRequestType::ModifyFile { after, .. } = handle.tool_request().tool_type {
+            assert_eq!(
+                after,
+                "line 1\nline 2 modified\nline 3\nline 4\nline 5 modified"
+            );
+        } else {
+            panic!("Expected ModifyFile request type");
+        }
+    }
+
+    #[test]
+    fn test_strip_diff_headers_matches_target_file() {
+        let target = Path::new("/work/src/lib.rs");
+        for header in [
+            "--- a/src/lib.rs\n+++ b/src/lib.rs",
+            "--- src/lib.rs.orig\t2024-01-01 00:00:00\n+++ src/lib.rs\t2024-01-02 00:00:00",
+            "--- ./lib.rs\n+++ ./lib.rs",
+            "--- /dev/null\n+++ /work/src/lib.rs",
+        ] {
+            let patch = format!("{header}\n@@ -1 +1 @@\n-a\n+b\n");
+            assert_eq!(
+                strip_diff_headers(&patch, target).unwrap(),
+                "@@ -1 +1 @@\n-a\n+b",
+                "{header}"
+            );
+        }
+
+        let err = strip_diff_headers(
+            "--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1 +1 @@\n-a\n+b\n",
+            target,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not /work/src/lib.rs"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_apply_codex_patch_file_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("test");
+        fs::create_dir(&root).unwrap();
+        let tool = ApplyCodexPatchTool::new(vec![root.clone()], test_settings(&temp_dir)).unwrap();
+
+        let file_path = root.join("test.txt");
+        fs::write(&file_path, "line 1\nline 2").unwrap();
+        let multi_file = root.join("multi.patch");
+        fs::write(
+            &multi_file,
+            "--- a/test.txt\n+++ b/test.txt\n@@ -1 +1 @@\n-line 1\n+one\n--- a/other.txt\n+++ b/other.txt\n@@ -1 +1 @@\n-x\n+y\n",
+        )
+        .unwrap();
+        let wrong_file = root.join("wrong.patch");
+        fs::write(
+            &wrong_file,
+            "--- a/other.txt\n+++ b/other.txt\n@@ -1 +1 @@\n-line 1\n+one\n",
+        )
+        .unwrap();
+        let outside = temp_dir.path().join("outside.patch");
+        fs::write(&outside, " line 1\n-line 2\n+two\n").unwrap();
+
+        for (patch_file, expected) in [
+            (path_str(&multi_file), "more than one file"),
+            (path_str(&wrong_file), "Patch file is for b/other.txt"),
+            (path_str(&outside), "outside configured workspace roots"),
+        ] {
+            let request = ToolRequest::new(
+                json!({
+                    "file_path": path_str(&file_path),
+                    "patch_file": patch_file
+                }),
+                "test_id".to_string(),
+            );
+            let err = match tool.process(&request).await {
+                Ok(_) => panic!("patch file {patch_file} should be rejected"),
+                Err(e) => format!("{e:#}"),
+            };
+            assert!(err.contains(expected), "unexpected error: {err}");
+        }
+    }
 }
@@ -0,0 +1,277 @@
+//! Optional y/n review gate for file edits.
+//!
+//! When the calling agent's autonomy level asks for it (always at
+//! `conservative`, when `confirm_edits` is set at `balanced`, never at
+//! `autonomous`), write_file/modify_file calls are validated as usual (so the
+//! diff is rendered from the tool request) but not applied. The tool instead
+//! prompts the user through the ask-user path and parks the validated handle.
+//! The user's next message resolves it: `y`/`yes` applies the edit, `n`/`no`
+//! discards it, and anything else falls back to `unanswered_edit_decision`.
+//! With `confirm_edits_timeout_seconds` set, a timer discards the edit once
+//! the timeout passes and tells the user straight away; the agent hears about
+//! it with the next message.
+//!
+//! With `propose_edits` the handle is instead handed to the actor as a
+//! proposed edit, which the user accepts or rejects individually (see
+//! `chat::edit_review`). Autonomous agents skip this too.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::audit::AuditCall;
+use crate::chat::events::{ChatMessage, EventSender, ToolRequest as ToolRequestEvent};
+use crate::file::config::{EditDecision, File};
+use crate::settings::config::AutonomyLevel;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    SharedTool, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+
+struct PendingEdit {
+    id: u64,
+    handle: Box<dyn ToolCallHandle>,
+    description: String,
+}
+
+/// Holds the edits awaiting the user's confirmation. Edits requested in the
+/// same turn are approved or declined together.
+pub struct EditConfirmation {
+    settings: SettingsManager,
+    event_sender: EventSender,
+    pending: Mutex<Vec<PendingEdit>>,
+    /// Descriptions of edits discarded by the timeout, reported to the agent
+    /// with the next reply.
+    expired: Mutex<Vec<String>>,
+    next_id: AtomicU64,
+}
+
+impl EditConfirmation {
+    pub fn new(settings: SettingsManager, event_sender: EventSender) -> Self {
+        Self {
+            settings,
+            event_sender,
+            pending: Mutex::new(Vec::new()),
+            expired: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Parks `handle` until the user answers, starting the timeout timer if
+    /// one is configured.
+    fn park(self: &Arc<Self>, handle: Box<dyn ToolCallHandle>, description: String) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.pending.lock().unwrap().push(PendingEdit {
+            id,
+            handle,
+            description,
+        });
+
+        let timeout = self
+            .settings
+            .get_module_config::<File>(File::NAMESPACE)
+            .confirm_edits_timeout_seconds;
+        if let Some(secs) = timeout {
+            let confirmation = self.clone();
+            tokio::task::spawn_local(async move {
+                tokio::time::sleep(Duration::from_secs(secs)).await;
+                confirmation.expire(id);
+            });
+        }
+    }
+
+    /// Discards the edit `id` if it is still waiting for an answer.
+    fn expire(&self, id: u64) {
+        let edit = {
+            let mut pending = self.pending.lock().unwrap();
+            let Some(index) = pending.iter().position(|edit| edit.id == id) else {
+                return;
+            };
+            pending.remove(index)
+        };
+        self.event_sender.send_message(ChatMessage::warning(format!(
+            "The edit to {} was not confirmed in time and was discarded.",
+            edit.description
+        )));
+        self.expired.lock().unwrap().push(edit.description);
+    }
+
+    /// Edits are confirmed as the calling agent's autonomy level dictates,
+    /// given `confirm_edits`.
+    fn enabled(&self, agent: &str) -> bool {
+        let configured = self
+            .settings
+            .get_module_config::<File>(File::NAMESPACE)
+            .confirm_edits;
+        self.settings
+            .settings()
+            .autonomy_level_for(agent)
+            .confirms_writes(configured)
+    }
+
+    /// Resolves pending edits with the user's reply. Returns the message to
+    /// send to the agent in place of the raw reply, or None when nothing was
+    /// pending.
+    pub async fn resolve(&self, reply: &str) -> Option<String> {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        let expired = std::mem::take(&mut *self.expired.lock().unwrap());
+        if pending.is_empty() && expired.is_empty() {
+            return None;
+        }
+        let config: File = self.settings.get_module_config(File::NAMESPACE);
+
+        let answer = reply.trim().to_lowercase();
+        let (decision, passthrough) = match answer.as_str() {
+            "y" | "yes" => (EditDecision::Approve, None),
+            "n" | "no" => (EditDecision::Decline, None),
+            _ => (config.unanswered_edit_decision, Some(reply)),
+        };
+
+        let mut outcomes: Vec<String> = expired
+            .into_iter()
+            .map(|description| {
+                format!("The edit to {description} was not confirmed in time and was discarded.")
+            })
+            .collect();
+        for edit in pending {
+            let outcome = match decision {
+                EditDecision::Approve => match edit.handle.execute().await {
+                    ToolOutput::Result {
+                        content, is_error, ..
+                    } if is_error => format!(
+                        "The user approved the edit to {}, but applying it failed: {content}",
+                        edit.description
+                    ),
+                    _ => format!(
+                        "The user approved the edit to {}; it has been applied.",
+                        edit.description
+                    ),
+                },
+                EditDecision::Decline => format!(
+                    "The user declined the edit to {}; the file was not changed.",
+                    edit.description
+                ),
+            };
+            outcomes.push(outcome);
+        }
+
+        let outcome = outcomes.join("\n");
+        Some(match passthrough {
+            Some(reply) => format!("{outcome}\n\n{reply}"),
+            None => outcome,
+        })
+    }
+}
+
+/// Edits are handed to the actor for piecewise review when `propose_edits` is
+/// set, unless the calling agent is autonomous.
+pub fn proposes_edits(settings: &SettingsManager, agent: &str) -> bool {
+    settings
+        .get_module_config::<File>(File::NAMESPACE)
+        .propose_edits
+        && settings.settings().autonomy_level_for(agent) != AutonomyLevel::Autonomous
+}
+
+/// Wraps an edit tool so its calls are parked for confirmation when enabled.
+pub struct ConfirmingEditTool {
+    inner: SharedTool,
+    confirmation: Arc<EditConfirmation>,
+}
+
+impl ConfirmingEditTool {
+    pub fn wrap(inner: SharedTool, confirmation: Arc<EditConfirmation>) -> SharedTool {
+        Arc::new(Self {
+            inner,
+            confirmation,
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for ConfirmingEditTool {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn input_schema(&self) -> Value {
+        self.inner.input_schema()
+    }
+
+    fn category(&self) -> ToolCategory {
+        self.inner.category()
+    }
+
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        self.inner.required_capabilities()
+    }
+
+    fn usage_example(&self) -> Option<String> {
+        self.inner.usage_example()
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let handle = self.inner.process(request).await?;
+        let propose = proposes_edits(&self.confirmation.settings, &request.agent);
+        if !propose && !self.confirmation.enabled(&request.agent) {
+            return Ok(handle);
+        }
+        Ok(Box::new(ConfirmingHandle {
+            handle,
+            confirmation: self.confirmation.clone(),
+            propose,
+            audit: None,
+        }))
+    }
+}
+
+struct ConfirmingHandle {
+    handle: Box<dyn ToolCallHandle>,
+    confirmation: Arc<EditConfirmation>,
+    propose: bool,
+    audit: Option<AuditCall>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for ConfirmingHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        self.handle.tool_request()
+    }
+
+    fn edited_path(&self) -> Option<PathBuf> {
+        self.handle.edited_path()
+    }
+
+    fn attach_audit(&mut self, call: AuditCall) {
+        self.audit = Some(call);
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let description = match self.handle.edited_path() {
+            Some(path) => path.display().to_string(),
+            None => self.handle.tool_request().tool_name,
+        };
+        let handle = match self.audit {
+            Some(audit) => audit.hold(self.handle),
+            None => self.handle,
+        };
+        if self.propose {
+            return ToolOutput::ProposeEdit {
+                handle,
+                file_path: description,
+            };
+        }
+
+        let question = format!("Apply the changes above to {description}? (y/n)");
+        self.confirmation.park(handle, description);
+
+        ToolOutput::PromptUser { question }
+    }
+}
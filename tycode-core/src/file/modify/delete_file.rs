@@ -1,9 +1,10 @@
 use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
 use crate::file::access::FileAccessManager;
 use crate::file::manager::FileModificationManager;
+use crate::settings::SettingsManager;
 use crate::tools::r#trait::{
-    ContinuationPreference, FileModification, FileOperation, ToolCallHandle, ToolCategory,
-    ToolExecutor, ToolOutput, ToolRequest,
+    ContinuationPreference, FileModification, FileOperation, ToolCallHandle, ToolCapability,
+    ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
 };
 use crate::tools::ToolName;
 use anyhow::Result;
@@ -20,8 +21,8 @@ impl DeleteFileTool {
         ToolName::new("delete_file")
     }
 
-    pub fn new(workspace_roots: Vec<PathBuf>) -> anyhow::Result<Self> {
-        let file_manager = FileAccessManager::new(workspace_roots)?;
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> anyhow::Result<Self> {
+        let file_manager = FileAccessManager::new(workspace_roots)?.with_settings(settings);
         Ok(Self { file_manager })
     }
 }
@@ -82,6 +83,7 @@ impl ToolCallHandle for DeleteFileHandle {
                 ui_result: ToolExecutionResult::Error {
                     short_message: "Delete failed".to_string(),
                     detailed_message: format!("{e:?}"),
+                    category: None,
                 },
             },
         }
@@ -115,6 +117,10 @@ impl ToolExecutor for DeleteFileTool {
         ToolCategory::Execution
     }
 
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write]
+    }
+
     async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
         let file_path = request
             .arguments
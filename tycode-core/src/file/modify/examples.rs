@@ -0,0 +1,49 @@
+//! Prompt component showing a worked example of the selected modify_file
+//! tool. Models that misuse the patch or search/replace formats do much
+//! better with one correct call in front of them. Toggled with
+//! `File::tool_examples` to save tokens once a model is reliable.
+
+use crate::file::config::File;
+use crate::module::{PromptComponent, PromptComponentId};
+use crate::settings::config::{FileModificationApi, Settings};
+use crate::tools::r#trait::SharedTool;
+
+pub const ID: PromptComponentId = PromptComponentId("tool_examples");
+
+pub struct ToolExamplesComponent {
+    apply_codex_patch: SharedTool,
+    replace_in_file: SharedTool,
+}
+
+impl ToolExamplesComponent {
+    pub fn new(apply_codex_patch: SharedTool, replace_in_file: SharedTool) -> Self {
+        Self {
+            apply_codex_patch,
+            replace_in_file,
+        }
+    }
+}
+
+impl PromptComponent for ToolExamplesComponent {
+    fn id(&self) -> PromptComponentId {
+        ID
+    }
+
+    fn build_prompt_section(&self, settings: &Settings) -> Option<String> {
+        let config: File = settings.get_module_config(File::NAMESPACE);
+        if !config.tool_examples {
+            return None;
+        }
+        let tool = match config.file_modification_api {
+            FileModificationApi::Patch => &self.apply_codex_patch,
+            FileModificationApi::Default | FileModificationApi::FindReplace => {
+                &self.replace_in_file
+            }
+        };
+        let example = tool.usage_example()?;
+        Some(format!(
+            "## Tool Examples\n\n### {}\n{example}",
+            tool.name()
+        ))
+    }
+}
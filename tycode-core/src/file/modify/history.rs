@@ -0,0 +1,274 @@
+//! Per-session undo/redo of applied file edits.
+//!
+//! Every successful write_file/modify_file/delete_file call records the file's
+//! content before and after the edit. `/undo` restores the most recent
+//! edit's prior content and `/redo` re-applies it. A file that has changed on
+//! disk since the recorded edit is left untouched rather than clobbered.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::chat::actor::ActorState;
+use crate::chat::events::{ChatMessage, ToolRequest as ToolRequestEvent};
+use crate::file::access::FileAccessManager;
+use crate::file::resolver;
+use crate::module::SlashCommand;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    SharedTool, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+
+/// Older edits are dropped beyond this depth.
+const MAX_EDIT_HISTORY: usize = 50;
+
+#[derive(Debug, Clone)]
+struct AppliedEdit {
+    path: PathBuf,
+    /// None when the file did not exist (i.e. the edit created it). Raw
+    /// bytes so the file's encoding is restored exactly.
+    before: Option<Vec<u8>>,
+    /// None when the edit deleted the file.
+    after: Option<Vec<u8>>,
+}
+
+pub struct EditHistory {
+    file_access: FileAccessManager,
+    undo: Mutex<VecDeque<AppliedEdit>>,
+    redo: Mutex<Vec<AppliedEdit>>,
+}
+
+impl EditHistory {
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> Result<Self> {
+        Ok(Self {
+            file_access: FileAccessManager::new(workspace_roots)?.with_settings(settings),
+            undo: Mutex::new(VecDeque::new()),
+            redo: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn record(&self, edit: AppliedEdit) {
+        let mut undo = self.undo.lock().unwrap();
+        undo.push_back(edit);
+        if undo.len() > MAX_EDIT_HISTORY {
+            undo.pop_front();
+        }
+        self.redo.lock().unwrap().clear();
+    }
+
+    /// Reverts the most recent edit and returns a description of it.
+    pub async fn undo(&self) -> Result<String> {
+        let Some(edit) = self.undo.lock().unwrap().pop_back() else {
+            bail!("Nothing to undo");
+        };
+
+        if let Err(e) = self.restore(&edit, &edit.after, &edit.before).await {
+            self.undo.lock().unwrap().push_back(edit);
+            return Err(e);
+        }
+
+        let description = describe(&edit, &edit.before);
+        self.redo.lock().unwrap().push(edit);
+        Ok(format!("Reverted {description}"))
+    }
+
+    /// Re-applies the most recently undone edit.
+    pub async fn redo(&self) -> Result<String> {
+        let Some(edit) = self.redo.lock().unwrap().pop() else {
+            bail!("Nothing to redo");
+        };
+
+        if let Err(e) = self.restore(&edit, &edit.before, &edit.after).await {
+            self.redo.lock().unwrap().push(edit);
+            return Err(e);
+        }
+
+        let description = describe(&edit, &edit.after);
+        self.undo.lock().unwrap().push_back(edit);
+        Ok(format!("Re-applied {description}"))
+    }
+
+    /// Moves the file from `expected` to `target`, refusing if the file on
+    /// disk no longer matches `expected`.
+    async fn restore(
+        &self,
+        edit: &AppliedEdit,
+        expected: &Option<Vec<u8>>,
+        target: &Option<Vec<u8>>,
+    ) -> Result<()> {
+        let path = edit.path.to_string_lossy();
+        let current = read_existing(&edit.path).await;
+        if &current != expected {
+            bail!(
+                "{} has changed since the edit was recorded; leaving it untouched",
+                edit.path.display()
+            );
+        }
+
+        match target {
+            Some(content) => self.file_access.write_bytes(&path, content).await,
+            None => self.file_access.delete_file(&path).await,
+        }
+    }
+}
+
+fn describe(edit: &AppliedEdit, restored: &Option<Vec<u8>>) -> String {
+    let state = match restored {
+        Some(content) => format!("{} lines", String::from_utf8_lossy(content).lines().count()),
+        None => "deleted".to_string(),
+    };
+    format!(
+        "edit to {} (now {state})",
+        resolver::display_path(&edit.path)
+    )
+}
+
+async fn read_existing(path: &Path) -> Option<Vec<u8>> {
+    tokio::fs::read(path).await.ok()
+}
+
+/// Wraps an edit tool so successful calls are recorded in the edit history.
+pub struct RecordingEditTool {
+    inner: SharedTool,
+    history: Arc<EditHistory>,
+}
+
+impl RecordingEditTool {
+    pub fn wrap(inner: SharedTool, history: Arc<EditHistory>) -> SharedTool {
+        Arc::new(Self { inner, history })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for RecordingEditTool {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn input_schema(&self) -> Value {
+        self.inner.input_schema()
+    }
+
+    fn category(&self) -> ToolCategory {
+        self.inner.category()
+    }
+
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        self.inner.required_capabilities()
+    }
+
+    fn usage_example(&self) -> Option<String> {
+        self.inner.usage_example()
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let handle = self.inner.process(request).await?;
+        Ok(Box::new(RecordingHandle {
+            handle,
+            history: self.history.clone(),
+        }))
+    }
+}
+
+struct RecordingHandle {
+    handle: Box<dyn ToolCallHandle>,
+    history: Arc<EditHistory>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for RecordingHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        self.handle.tool_request()
+    }
+
+    fn edited_path(&self) -> Option<PathBuf> {
+        self.handle.edited_path()
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let Some(path) = self.handle.edited_path() else {
+            return self.handle.execute().await;
+        };
+
+        let before = read_existing(&path).await;
+        let output = self.handle.execute().await;
+
+        let succeeded = matches!(
+            output,
+            ToolOutput::Result {
+                is_error: false,
+                ..
+            }
+        );
+        if succeeded {
+            let after = read_existing(&path).await;
+            if before != after {
+                self.history.record(AppliedEdit {
+                    path,
+                    before,
+                    after,
+                });
+            }
+        }
+        output
+    }
+}
+
+pub struct UndoSlashCommand {
+    pub(crate) history: Arc<EditHistory>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl SlashCommand for UndoSlashCommand {
+    fn name(&self) -> &'static str {
+        "undo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Revert the most recent file edit"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/undo"
+    }
+
+    async fn execute(&self, _state: &mut ActorState, _args: &[&str]) -> Vec<ChatMessage> {
+        match self.history.undo().await {
+            Ok(msg) => vec![ChatMessage::system(msg)],
+            Err(e) => vec![ChatMessage::error(format!("Undo failed: {e}"))],
+        }
+    }
+}
+
+pub struct RedoSlashCommand {
+    pub(crate) history: Arc<EditHistory>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl SlashCommand for RedoSlashCommand {
+    fn name(&self) -> &'static str {
+        "redo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Re-apply the most recently undone file edit"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/redo"
+    }
+
+    async fn execute(&self, _state: &mut ActorState, _args: &[&str]) -> Vec<ChatMessage> {
+        match self.history.redo().await {
+            Ok(msg) => vec![ChatMessage::system(msg)],
+            Err(e) => vec![ChatMessage::error(format!("Redo failed: {e}"))],
+        }
+    }
+}
@@ -0,0 +1,379 @@
+//! Lint-on-save: runs the configured linters on files after an edit and keeps
+//! their findings in the model's context until a later run on the same file
+//! comes back clean, so the consequences of an edit are visible right away
+//! instead of after the next explicit build.
+//!
+//! Linters are matched to files by extension. `LintTrigger::Edit` linters run
+//! as part of the edit; `LintTrigger::NextRequest` linters run once when the
+//! next context is built, covering every file edited in between.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use regex::Regex;
+use serde_json::Value;
+
+use crate::chat::events::ToolRequest as ToolRequestEvent;
+use crate::file::access::FileAccessManager;
+use crate::file::config::{File, LintTrigger, LinterConfig};
+use crate::module::{ContextComponent, ContextComponentId};
+use crate::modules::execution::config::ExecutionConfig;
+use crate::modules::execution::run_cmd;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    SharedTool, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+
+pub const LINT_ID: ContextComponentId = ContextComponentId("lint");
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LintMessage {
+    pub line: u32,
+    pub message: String,
+}
+
+pub struct LintOnSave {
+    access: FileAccessManager,
+    settings: SettingsManager,
+    /// Findings by file from the latest run that covered it.
+    findings: Mutex<BTreeMap<PathBuf, Vec<LintMessage>>>,
+    /// Files edited since their `NextRequest` linters last ran.
+    pending: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl LintOnSave {
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> Result<Self> {
+        Ok(Self {
+            access: FileAccessManager::new(workspace_roots)?.with_settings(settings.clone()),
+            settings,
+            findings: Mutex::new(BTreeMap::new()),
+            pending: Mutex::new(BTreeSet::new()),
+        })
+    }
+
+    /// Lints `path` after an edit, or queues it for the next request.
+    pub async fn on_edit(&self, path: &Path) {
+        let config: File = self.settings.get_module_config(File::NAMESPACE);
+        if config.linters.is_empty() {
+            return;
+        }
+        if !path.is_file() {
+            // Deleted files have nothing left to fix
+            self.findings.lock().unwrap().remove(path);
+            self.pending.lock().unwrap().remove(path);
+            return;
+        }
+
+        for linter in linters_for(&config.linters, path) {
+            match linter.trigger {
+                LintTrigger::Edit => self.run(linter, &[path.to_path_buf()]).await,
+                LintTrigger::NextRequest => {
+                    self.pending.lock().unwrap().insert(path.to_path_buf());
+                }
+            }
+        }
+    }
+
+    /// Runs the `NextRequest` linters over every file queued since the last
+    /// request.
+    async fn run_pending(&self) {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        if pending.is_empty() {
+            return;
+        }
+        let config: File = self.settings.get_module_config(File::NAMESPACE);
+        for linter in &config.linters {
+            if linter.trigger != LintTrigger::NextRequest {
+                continue;
+            }
+            let files: Vec<PathBuf> = pending
+                .iter()
+                .filter(|path| path.is_file() && handles(linter, path))
+                .cloned()
+                .collect();
+            if !files.is_empty() {
+                self.run(linter, &files).await;
+            }
+        }
+    }
+
+    /// Runs `linter` over `files` and replaces their findings with the result.
+    async fn run(&self, linter: &LinterConfig, files: &[PathBuf]) {
+        let execution: ExecutionConfig = self.settings.get_module_config("execution");
+        let per_file = linter.command.contains("{file}");
+
+        let mut by_root: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+        for file in files {
+            let Some(root) = self.access.roots.iter().find(|root| file.starts_with(root)) else {
+                continue;
+            };
+            by_root.entry(root.clone()).or_default().push(file.clone());
+        }
+
+        for (root, files) in by_root {
+            let runs: Vec<(String, Vec<PathBuf>)> = if per_file {
+                files
+                    .iter()
+                    .map(|file| {
+                        let quoted = shell_words::quote(&file.to_string_lossy()).to_string();
+                        (
+                            linter.command.replace("{file}", &quoted),
+                            vec![file.clone()],
+                        )
+                    })
+                    .collect()
+            } else {
+                vec![(linter.command.clone(), files)]
+            };
+
+            for (command, covered) in runs {
+                // Linters exit non-zero when they report findings, so the
+                // output is parsed regardless of the exit code
+                let result = match run_cmd(
+                    root.clone(),
+                    command.clone(),
+                    Duration::from_secs(execution.effective_default_timeout()),
+                    execution.execution_mode.clone(),
+                    execution.shell,
+                    execution.output_encoding(),
+                )
+                .await
+                {
+                    Ok(result) => result,
+                    Err(e) => {
+                        tracing::warn!(?e, %command, "Lint command failed to run");
+                        continue;
+                    }
+                };
+
+                let output = format!("{}\n{}", result.out, result.err);
+                let mut parsed = parse_lint_output(&output, &root);
+                let mut findings = self.findings.lock().unwrap();
+                for file in covered {
+                    match parsed.remove(&file) {
+                        Some(messages) => findings.insert(file, messages),
+                        None => findings.remove(&file),
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ContextComponent for LintOnSave {
+    fn id(&self) -> ContextComponentId {
+        LINT_ID
+    }
+
+    async fn build_context_section(&self) -> Option<String> {
+        self.run_pending().await;
+
+        let max_messages = self
+            .settings
+            .get_module_config::<File>(File::NAMESPACE)
+            .max_lint_messages;
+        let findings = self.findings.lock().unwrap();
+        let total: usize = findings.values().map(Vec::len).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut output = "Lint findings in recently edited files (cleared when the linter next passes on the file):\n".to_string();
+        for (path, message) in findings
+            .iter()
+            .flat_map(|(path, messages)| messages.iter().map(move |m| (path, m)))
+            .take(max_messages)
+        {
+            output.push_str(&format!(
+                "  - {}:{}: {}\n",
+                path.display(),
+                message.line,
+                message.message
+            ));
+        }
+        if total > max_messages {
+            output.push_str(&format!("  ... and {} more\n", total - max_messages));
+        }
+        Some(output)
+    }
+}
+
+fn handles(linter: &LinterConfig, path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|extension| {
+            linter
+                .extensions
+                .iter()
+                .any(|e| e.eq_ignore_ascii_case(extension))
+        })
+}
+
+fn linters_for<'a>(
+    linters: &'a [LinterConfig],
+    path: &'a Path,
+) -> impl Iterator<Item = &'a LinterConfig> {
+    linters.iter().filter(move |linter| handles(linter, path))
+}
+
+/// Collects `path:line[:col]: message` lines by file, resolving relative
+/// paths against `root`. Each file's findings are sorted and deduplicated.
+fn parse_lint_output(output: &str, root: &Path) -> BTreeMap<PathBuf, Vec<LintMessage>> {
+    let pattern = Regex::new(r"^\s*([^\s:][^:]*):(\d+)(?::\d+)?:\s*(\S.*)$").unwrap();
+    let mut findings: BTreeMap<PathBuf, Vec<LintMessage>> = BTreeMap::new();
+
+    for line in output.lines() {
+        let Some(captures) = pattern.captures(line) else {
+            continue;
+        };
+        let Ok(line_no) = captures[2].parse() else {
+            continue;
+        };
+        let file = Path::new(captures[1].trim());
+        let file = if file.is_absolute() {
+            file.to_path_buf()
+        } else {
+            root.join(file)
+        };
+        findings.entry(file).or_default().push(LintMessage {
+            line: line_no,
+            message: captures[3].trim().to_string(),
+        });
+    }
+
+    for messages in findings.values_mut() {
+        messages.sort();
+        messages.dedup();
+    }
+    findings
+}
+
+/// Wraps an edit tool so each successful edit is linted.
+pub struct LintingEditTool {
+    inner: SharedTool,
+    lint: Arc<LintOnSave>,
+}
+
+impl LintingEditTool {
+    pub fn wrap(inner: SharedTool, lint: Arc<LintOnSave>) -> SharedTool {
+        Arc::new(Self { inner, lint })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for LintingEditTool {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn input_schema(&self) -> Value {
+        self.inner.input_schema()
+    }
+
+    fn category(&self) -> ToolCategory {
+        self.inner.category()
+    }
+
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        self.inner.required_capabilities()
+    }
+
+    fn usage_example(&self) -> Option<String> {
+        self.inner.usage_example()
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let handle = self.inner.process(request).await?;
+        Ok(Box::new(LintingHandle {
+            handle,
+            lint: self.lint.clone(),
+        }))
+    }
+}
+
+struct LintingHandle {
+    handle: Box<dyn ToolCallHandle>,
+    lint: Arc<LintOnSave>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for LintingHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        self.handle.tool_request()
+    }
+
+    fn edited_path(&self) -> Option<PathBuf> {
+        self.handle.edited_path()
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let path = self.handle.edited_path();
+        let output = self.handle.execute().await;
+
+        let succeeded = matches!(
+            output,
+            ToolOutput::Result {
+                is_error: false,
+                ..
+            }
+        );
+        if let (true, Some(path)) = (succeeded, path) {
+            self.lint.on_edit(&path).await;
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_dedupes_findings_by_file() {
+        let root = Path::new("/work");
+        let output = "\
+    Checking demo v0.1.0
+src/lib.rs:3:9: warning: unused variable: `x`
+src/lib.rs:3:9: warning: unused variable: `x`
+src/lib.rs:1:1: warning: missing docs
+/work/web/app.js:7:3: 'y' is assigned a value but never used. [Error/no-unused-vars]
+warning: `demo` (lib) generated 2 warnings
+";
+        let findings = parse_lint_output(output, root);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(
+            findings[Path::new("/work/src/lib.rs")],
+            vec![
+                LintMessage {
+                    line: 1,
+                    message: "warning: missing docs".to_string(),
+                },
+                LintMessage {
+                    line: 3,
+                    message: "warning: unused variable: `x`".to_string(),
+                },
+            ]
+        );
+        assert_eq!(findings[Path::new("/work/web/app.js")][0].line, 7);
+    }
+
+    #[test]
+    fn linters_match_by_extension() {
+        let linter = LinterConfig {
+            extensions: vec!["ts".to_string(), "tsx".to_string()],
+            command: "eslint --format unix {file}".to_string(),
+            trigger: LintTrigger::Edit,
+        };
+        assert!(handles(&linter, Path::new("/w/app.TSX")));
+        assert!(!handles(&linter, Path::new("/w/app.rs")));
+        assert!(!handles(&linter, Path::new("/w/Makefile")));
+    }
+}
@@ -5,8 +5,14 @@
 
 pub mod apply_codex_patch;
 pub mod command;
+pub mod confirm;
 pub mod delete_file;
+pub mod examples;
+pub mod history;
+pub mod lint;
 pub mod replace_in_file;
+pub mod replay;
+pub mod resolve_conflicts;
 pub mod write_file;
 
 use std::path::PathBuf;
@@ -14,6 +20,7 @@ use std::sync::Arc;
 
 use anyhow::Result;
 
+use crate::chat::events::EventSender;
 use crate::file::config::File;
 use crate::module::ContextComponent;
 use crate::module::Module;
@@ -24,10 +31,16 @@ use crate::settings::SettingsManager;
 use crate::tools::r#trait::SharedTool;
 
 use command::FileApiSlashCommand;
+use confirm::{ConfirmingEditTool, EditConfirmation};
+use examples::ToolExamplesComponent;
+use history::{EditHistory, RecordingEditTool, RedoSlashCommand, UndoSlashCommand};
+use lint::{LintOnSave, LintingEditTool};
+use replay::ReplaySlashCommand;
 
 use apply_codex_patch::ApplyCodexPatchTool;
 use delete_file::DeleteFileTool;
 use replace_in_file::ReplaceInFileTool;
+use resolve_conflicts::ResolveConflictsTool;
 use write_file::WriteFileTool;
 
 /// Module providing file modification capabilities.
@@ -36,56 +49,146 @@ use write_file::WriteFileTool;
 /// - WriteFileTool: Create or overwrite files
 /// - DeleteFileTool: Delete files or empty directories
 /// - modify_file tool: Selected based on FileModificationApi setting (late bound)
+/// - ResolveConflictsTool: Keep one side of merge conflicts (opt-in)
+///
+/// Edits are recorded for `/undo`/`/redo` and, when `confirm_edits` or
+/// `propose_edits` is set, held for the user's approval before being
+/// written. Configured linters run on edited files and their findings are
+/// kept in context until fixed.
 pub struct FileModifyModule {
     write_file: Arc<WriteFileTool>,
     delete_file: Arc<DeleteFileTool>,
     apply_codex_patch: Arc<ApplyCodexPatchTool>,
     replace_in_file: Arc<ReplaceInFileTool>,
+    resolve_conflicts: Arc<ResolveConflictsTool>,
+    confirmation: Arc<EditConfirmation>,
+    history: Arc<EditHistory>,
+    lint: Arc<LintOnSave>,
     settings: SettingsManager,
 }
 
+/// Applies the module's edit handling (confirmation, `/undo` history, lint on
+/// save) to file-editing tools from other modules, such as `format_file`.
+#[derive(Clone)]
+pub struct EditWrapper {
+    confirmation: Arc<EditConfirmation>,
+    history: Arc<EditHistory>,
+    lint: Arc<LintOnSave>,
+}
+
+impl EditWrapper {
+    pub fn wrap(&self, tool: SharedTool) -> SharedTool {
+        ConfirmingEditTool::wrap(
+            RecordingEditTool::wrap(
+                LintingEditTool::wrap(tool, self.lint.clone()),
+                self.history.clone(),
+            ),
+            self.confirmation.clone(),
+        )
+    }
+}
+
 impl FileModifyModule {
-    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> Result<Self> {
+    pub fn new(
+        workspace_roots: Vec<PathBuf>,
+        settings: SettingsManager,
+        event_sender: EventSender,
+    ) -> Result<Self> {
         Ok(Self {
-            write_file: Arc::new(WriteFileTool::new(workspace_roots.clone())?),
-            delete_file: Arc::new(DeleteFileTool::new(workspace_roots.clone())?),
-            apply_codex_patch: Arc::new(ApplyCodexPatchTool::new(workspace_roots.clone())?),
-            replace_in_file: Arc::new(ReplaceInFileTool::new(workspace_roots)?),
+            write_file: Arc::new(WriteFileTool::new(
+                workspace_roots.clone(),
+                settings.clone(),
+            )?),
+            delete_file: Arc::new(DeleteFileTool::new(
+                workspace_roots.clone(),
+                settings.clone(),
+            )?),
+            apply_codex_patch: Arc::new(ApplyCodexPatchTool::new(
+                workspace_roots.clone(),
+                settings.clone(),
+            )?),
+            replace_in_file: Arc::new(ReplaceInFileTool::new(
+                workspace_roots.clone(),
+                settings.clone(),
+            )?),
+            resolve_conflicts: Arc::new(ResolveConflictsTool::new(
+                workspace_roots.clone(),
+                settings.clone(),
+            )?),
+            confirmation: Arc::new(EditConfirmation::new(settings.clone(), event_sender)),
+            history: Arc::new(EditHistory::new(workspace_roots.clone(), settings.clone())?),
+            lint: Arc::new(LintOnSave::new(workspace_roots, settings.clone())?),
             settings,
         })
     }
+
+    pub fn edit_wrapper(&self) -> EditWrapper {
+        EditWrapper {
+            confirmation: self.confirmation.clone(),
+            history: self.history.clone(),
+            lint: self.lint.clone(),
+        }
+    }
 }
 
 #[async_trait::async_trait(?Send)]
 impl Module for FileModifyModule {
     fn prompt_components(&self) -> Vec<Arc<dyn PromptComponent>> {
-        vec![]
+        vec![Arc::new(ToolExamplesComponent::new(
+            self.apply_codex_patch.clone(),
+            self.replace_in_file.clone(),
+        ))]
     }
 
     fn context_components(&self) -> Vec<Arc<dyn ContextComponent>> {
-        vec![]
+        vec![self.lint.clone() as Arc<dyn ContextComponent>]
     }
 
     fn slash_commands(&self) -> Vec<Arc<dyn SlashCommand>> {
-        vec![Arc::new(FileApiSlashCommand)]
+        vec![
+            Arc::new(FileApiSlashCommand),
+            Arc::new(UndoSlashCommand {
+                history: self.history.clone(),
+            }),
+            Arc::new(RedoSlashCommand {
+                history: self.history.clone(),
+            }),
+            Arc::new(ReplaySlashCommand {
+                edit_wrapper: self.edit_wrapper(),
+            }),
+        ]
     }
 
     async fn tools(&self) -> Vec<SharedTool> {
-        let modify_file: SharedTool = match self
-            .settings
-            .get_module_config::<File>(File::NAMESPACE)
-            .file_modification_api
-        {
+        let config = self.settings.get_module_config::<File>(File::NAMESPACE);
+        let modify_file: SharedTool = match config.file_modification_api {
             FileModificationApi::Patch => self.apply_codex_patch.clone(),
             FileModificationApi::Default | FileModificationApi::FindReplace => {
                 self.replace_in_file.clone()
             }
         };
 
-        vec![
-            self.write_file.clone(),
-            self.delete_file.clone(),
-            modify_file,
-        ]
+        let record = |tool: SharedTool| {
+            RecordingEditTool::wrap(
+                LintingEditTool::wrap(tool, self.lint.clone()),
+                self.history.clone(),
+            )
+        };
+        let mut tools = vec![
+            ConfirmingEditTool::wrap(record(self.write_file.clone()), self.confirmation.clone()),
+            record(self.delete_file.clone()),
+            ConfirmingEditTool::wrap(record(modify_file), self.confirmation.clone()),
+        ];
+        if config.resolve_conflicts {
+            tools.push(ConfirmingEditTool::wrap(
+                record(self.resolve_conflicts.clone()),
+                self.confirmation.clone(),
+            ));
+        }
+        tools
+    }
+
+    async fn on_user_input(&self, input: &str) -> Option<String> {
+        self.confirmation.resolve(input).await
     }
 }
@@ -1,27 +1,46 @@
 use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
 use crate::file::access::FileAccessManager;
+use crate::file::config::{File, ReplaceMode};
+use crate::file::conflicts::conflict_marker_warning;
 use crate::file::find::{self, find_closest_match};
 use crate::file::manager::FileModificationManager;
+use crate::settings::SettingsManager;
 use crate::tools::r#trait::{
-    ContinuationPreference, FileModification, FileOperation, ToolCallHandle, ToolCategory,
-    ToolExecutor, ToolOutput, ToolRequest,
+    ContinuationPreference, FileModification, FileOperation, ToolCallHandle, ToolCapability,
+    ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
 };
 use crate::tools::ToolName;
 use anyhow::{bail, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use similar::TextDiff;
 use std::path::PathBuf;
 
+const USAGE_EXAMPLE: &str = r#"To rename `total` to `sum` in one function and fix its return type, copy each search block exactly from the file, with enough surrounding lines to match only once:
+{
+  "file_path": "/workspace/src/math.rs",
+  "diff": [
+    {
+      "search": "fn add(a: i32, b: i32) -> i64 {\n    let total = a + b;\n    total\n}",
+      "replace": "fn add(a: i32, b: i32) -> i32 {\n    let sum = a + b;\n    sum\n}"
+    }
+  ]
+}"#;
+
 /// Tool for replacing sections of content in files
 #[derive(Debug, Clone, Deserialize)]
 pub struct SearchReplaceBlock {
     pub search: String,
     pub replace: String,
+    /// Replace every occurrence instead of requiring a unique match.
+    #[serde(default)]
+    pub replace_all: bool,
 }
 
 #[derive(Clone)]
 pub struct ReplaceInFileTool {
     file_manager: FileAccessManager,
+    settings: SettingsManager,
 }
 
 impl ReplaceInFileTool {
@@ -29,12 +48,16 @@ impl ReplaceInFileTool {
         ToolName::new("modify_file")
     }
 
-    pub fn new(workspace_roots: Vec<PathBuf>) -> anyhow::Result<Self> {
-        let file_manager = FileAccessManager::new(workspace_roots)?;
-        Ok(Self { file_manager })
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> anyhow::Result<Self> {
+        let file_manager = FileAccessManager::new(workspace_roots)?.with_settings(settings.clone());
+        Ok(Self {
+            file_manager,
+            settings,
+        })
     }
 
-    /// Apply replacements to content
+    /// Apply replacements to content, failing on the first block that does
+    /// not match exactly once.
     fn apply_replacements(
         &self,
         content: &str,
@@ -43,38 +66,98 @@ impl ReplaceInFileTool {
         let mut result = content.to_string();
 
         for block in replacements {
-            let search = match search(result.clone(), block.search.clone()) {
-                MatchResult::Multiple { matches, .. } => {
-                    bail!(
-                        "The following search pattern appears more than once in the file (found {} times). Use unique context to match exactly one occurrence.\n\nSearch pattern:\n{}\n\nTip: Include more surrounding context to make this search pattern unique.",
-                        matches,
-                        block.search
-                    );
-                }
-                MatchResult::Guess { closest, .. } => {
-                    let message = match closest {
-                        Some(closest) => closest.get_correction_feedback().unwrap_or_else(|| "Found a perfect line-level match, but the exact string search failed. This may be due to whitespace or formatting differences. Reread the file to see the actual content.".to_string()),
-                        None => "Reread the file with bash and retry with an exact search block.".to_string(),
-                    };
-                    bail!("Exact match not found. {message}");
-                }
-                MatchResult::Exact(search) => search,
-            };
+            match apply_block(&result, &block) {
+                Ok(updated) => result = updated,
+                Err(status) => bail!("{}", status.failure_message(&block)),
+            }
+        }
 
-            // Check if search and replace are identical
-            if search == block.replace {
-                bail!(
-                    "Search and replace contents are identical for the following pattern. No changes would be made. Please provide different replacement content.\n\nSearch/Replace pattern:\n{}",
-                    block.replace
-                );
+        Ok(result)
+    }
+
+    /// Apply every block that matches and report the status of each one.
+    /// Blocks are applied in order, so later blocks see earlier replacements.
+    fn apply_replacements_best_effort(
+        &self,
+        content: &str,
+        replacements: &[SearchReplaceBlock],
+    ) -> (String, Vec<BlockStatus>) {
+        let mut result = content.to_string();
+        let mut statuses = Vec::with_capacity(replacements.len());
+
+        for block in replacements {
+            match apply_block(&result, block) {
+                Ok(updated) => {
+                    result = updated;
+                    statuses.push(BlockStatus::Applied);
+                }
+                Err(status) => statuses.push(status),
             }
+        }
+
+        (result, statuses)
+    }
+}
 
-            // Replace the single occurrence as specified
-            result = result.replacen(&search, &block.replace, 1);
+/// Outcome of a single search/replace block.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BlockStatus {
+    Applied,
+    NotFound { feedback: String },
+    Ambiguous { matches: usize },
+    Unchanged,
+    EmptySearch,
+}
+
+impl BlockStatus {
+    fn failure_message(&self, block: &SearchReplaceBlock) -> String {
+        match self {
+            BlockStatus::Applied => "Applied".to_string(),
+            BlockStatus::NotFound { feedback } => format!("Exact match not found. {feedback}"),
+            BlockStatus::Ambiguous { matches } => format!(
+                "The following search pattern appears more than once in the file (found {} times). Use unique context to match exactly one occurrence.\n\nSearch pattern:\n{}\n\nTip: Include more surrounding context to make this search pattern unique, or set replace_all to true if every occurrence should be replaced.",
+                matches,
+                block.search
+            ),
+            BlockStatus::Unchanged => format!(
+                "Search and replace contents are identical for the following pattern. No changes would be made. Please provide different replacement content.\n\nSearch/Replace pattern:\n{}",
+                block.replace
+            ),
+            BlockStatus::EmptySearch => "The search pattern is empty, so it would match everywhere. Include the existing lines to replace; to write a whole file, use write_file.".to_string(),
         }
+    }
+}
 
-        Ok(result)
+/// Applies one block to `content`, or returns why it could not be applied.
+fn apply_block(content: &str, block: &SearchReplaceBlock) -> Result<String, BlockStatus> {
+    if block.search.is_empty() {
+        return Err(BlockStatus::EmptySearch);
+    }
+
+    let search = match search(content.to_string(), block.search.clone()) {
+        MatchResult::Multiple { requested, .. } if block.replace_all => requested,
+        MatchResult::Multiple { matches, .. } => return Err(BlockStatus::Ambiguous { matches }),
+        MatchResult::Guess { closest, .. } => {
+            let feedback = match closest {
+                Some(closest) => closest.get_correction_feedback().unwrap_or_else(|| "Found a perfect line-level match, but the exact string search failed. This may be due to whitespace or formatting differences. Reread the file to see the actual content.".to_string()),
+                None => "Reread the file with bash and retry with an exact search block.".to_string(),
+            };
+            return Err(BlockStatus::NotFound { feedback });
+        }
+        MatchResult::Exact(search) => search,
+    };
+
+    if search == block.replace {
+        return Err(BlockStatus::Unchanged);
+    }
+
+    if block.replace_all {
+        return Ok(content.replace(&search, &block.replace));
     }
+
+    // Replace the single occurrence as specified
+    Ok(content.replacen(&search, &block.replace, 1))
 }
 
 #[allow(dead_code)]
@@ -118,6 +201,18 @@ struct ReplaceInFileHandle {
     modification: FileModification,
     tool_use_id: String,
     file_manager: FileAccessManager,
+    /// Per-block statuses; only populated in best-effort mode.
+    block_statuses: Vec<BlockStatus>,
+    context_lines: usize,
+}
+
+/// Diff hunks for the applied change, with `context_lines` unchanged lines
+/// around each one.
+fn change_hunks(before: &str, after: &str, context_lines: usize) -> String {
+    TextDiff::from_lines(before, after)
+        .unified_diff()
+        .context_radius(context_lines)
+        .to_string()
 }
 
 #[async_trait::async_trait(?Send)]
@@ -140,21 +235,39 @@ impl ToolCallHandle for ReplaceInFileHandle {
 
     async fn execute(self: Box<Self>) -> ToolOutput {
         let manager = FileModificationManager::new(self.file_manager.clone());
+        let warning = self.modification.warning.clone();
+        let changes = change_hunks(
+            self.modification
+                .original_content
+                .as_deref()
+                .unwrap_or_default(),
+            self.modification.new_content.as_deref().unwrap_or_default(),
+            self.context_lines,
+        );
         match manager.apply_modification(self.modification).await {
-            Ok(stats) => ToolOutput::Result {
-                content: json!({
+            Ok(stats) => {
+                let mut content = json!({
                     "success": true,
                     "lines_added": stats.lines_added,
-                    "lines_removed": stats.lines_removed
-                })
-                .to_string(),
-                is_error: false,
-                continuation: ContinuationPreference::Continue,
-                ui_result: ToolExecutionResult::ModifyFile {
-                    lines_added: stats.lines_added,
-                    lines_removed: stats.lines_removed,
-                },
-            },
+                    "lines_removed": stats.lines_removed,
+                    "changes": changes
+                });
+                if !self.block_statuses.is_empty() {
+                    content["blocks"] = json!(self.block_statuses);
+                }
+                if let Some(warning) = warning {
+                    content["warning"] = json!(warning);
+                }
+                ToolOutput::Result {
+                    content: content.to_string(),
+                    is_error: false,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::ModifyFile {
+                        lines_added: stats.lines_added,
+                        lines_removed: stats.lines_removed,
+                    },
+                }
+            }
             Err(e) => ToolOutput::Result {
                 content: format!("Failed to apply modification: {e:?}"),
                 is_error: true,
@@ -162,6 +275,7 @@ impl ToolCallHandle for ReplaceInFileHandle {
                 ui_result: ToolExecutionResult::Error {
                     short_message: "Modification failed".to_string(),
                     detailed_message: format!("{e:?}"),
+                    category: None,
                 },
             },
         }
@@ -194,11 +308,15 @@ impl ToolExecutor for ReplaceInFileTool {
                         "properties": {
                             "search": {
                                 "type": "string",
-                                "description": "Exact content to find. The search block must exactly match exactly one string in the source file — do not use it to match multiple instances (e.g., you cannot replace all 'banana' with 'carrot' if there are multiple 'banana'). Include sufficient unique surrounding context to ensure unambiguous, exact matching."
+                                "description": "Exact content to find. Unless replace_all is set, the search block must exactly match exactly one string in the source file. Include sufficient unique surrounding context to ensure unambiguous, exact matching."
                             },
                             "replace": {
                                 "type": "string",
                                 "description": "New content to replace with"
+                            },
+                            "replace_all": {
+                                "type": "boolean",
+                                "description": "Replace every occurrence of the search content (e.g. renaming all 'banana' to 'carrot'). Defaults to false, in which case a search matching more than once is rejected."
                             }
                         },
                         "required": ["search", "replace"],
@@ -214,6 +332,14 @@ impl ToolExecutor for ReplaceInFileTool {
         ToolCategory::Execution
     }
 
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write]
+    }
+
+    fn usage_example(&self) -> Option<String> {
+        Some(USAGE_EXAMPLE.to_string())
+    }
+
     async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
         let file_path = request
             .arguments
@@ -249,20 +375,48 @@ impl ToolExecutor for ReplaceInFileTool {
                     .map_err(|e| anyhow::anyhow!("Invalid diff entry: {e:?}"))
             })
             .collect::<Result<Vec<_>, _>>()?;
-        let new_content = self.apply_replacements(&original_content, replacements)?;
+        let config = self.settings.get_module_config::<File>(File::NAMESPACE);
+        let (new_content, block_statuses) = match config.replace_mode {
+            ReplaceMode::Atomic => (
+                self.apply_replacements(&original_content, replacements)?,
+                Vec::new(),
+            ),
+            ReplaceMode::BestEffort => {
+                let (new_content, statuses) =
+                    self.apply_replacements_best_effort(&original_content, &replacements);
+                if !statuses.contains(&BlockStatus::Applied) {
+                    let failures: Vec<String> = statuses
+                        .iter()
+                        .zip(&replacements)
+                        .enumerate()
+                        .map(|(i, (status, block))| {
+                            format!("Block {}: {}", i + 1, status.failure_message(block))
+                        })
+                        .collect();
+                    bail!(
+                        "None of the search/replace blocks could be applied.\n\n{}",
+                        failures.join("\n\n")
+                    );
+                }
+                (new_content, statuses)
+            }
+        };
 
+        let warning = conflict_marker_warning(&new_content);
         let modification = FileModification {
             path: resolved_path,
             operation: FileOperation::Update,
             original_content: Some(original_content),
             new_content: Some(new_content),
-            warning: None,
+            warning,
         };
 
         Ok(Box::new(ReplaceInFileHandle {
             modification,
             tool_use_id: request.tool_use_id.clone(),
             file_manager: self.file_manager.clone(),
+            block_statuses,
+            context_lines: config.modify_context_lines,
         }))
     }
 }
@@ -270,14 +424,22 @@ impl ToolExecutor for ReplaceInFileTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::tempdir;
+
+    fn tool() -> ReplaceInFileTool {
+        let dir = tempdir().unwrap();
+        let settings = SettingsManager::from_path(dir.path().join("settings.toml")).unwrap();
+        ReplaceInFileTool::new(vec![], settings).unwrap()
+    }
 
     #[test]
     fn test_apply_replacements_fails_on_multiple_occurrences() {
-        let tool = ReplaceInFileTool::new(vec![]).unwrap();
+        let tool = tool();
         let content = "line1\nsearch\nline2\nsearch\nline3";
         let replacements = vec![SearchReplaceBlock {
             search: "search".to_string(),
             replace: "replaced".to_string(),
+            replace_all: false,
         }];
 
         let result = tool.apply_replacements(content, replacements);
@@ -290,11 +452,12 @@ mod tests {
 
     #[test]
     fn test_apply_replacements_succeeds_on_single_occurrence() {
-        let tool = ReplaceInFileTool::new(vec![]).unwrap();
+        let tool = tool();
         let content = "line1\nsearch\nline2";
         let replacements = vec![SearchReplaceBlock {
             search: "search".to_string(),
             replace: "replaced".to_string(),
+            replace_all: false,
         }];
 
         let result = tool.apply_replacements(content, replacements);
@@ -304,11 +467,12 @@ mod tests {
 
     #[test]
     fn test_apply_replacements_fails_on_identical_search_and_replace() {
-        let tool = ReplaceInFileTool::new(vec![]).unwrap();
+        let tool = tool();
         let content = "line1\nsearch\nline2";
         let replacements = vec![SearchReplaceBlock {
             search: "search".to_string(),
             replace: "search".to_string(), // identical to search
+            replace_all: false,
         }];
 
         let result = tool.apply_replacements(content, replacements);
@@ -318,4 +482,96 @@ mod tests {
             .to_string()
             .contains("Search and replace contents are identical"));
     }
+
+    #[test]
+    fn test_apply_replacements_rejects_empty_search() {
+        let tool = tool();
+        let replacements = vec![SearchReplaceBlock {
+            search: String::new(),
+            replace: "inserted".to_string(),
+            replace_all: true,
+        }];
+
+        let err = tool
+            .apply_replacements("line1\nline2", replacements)
+            .unwrap_err();
+        assert!(err.to_string().contains("search pattern is empty"), "{err}");
+    }
+
+    #[test]
+    fn test_best_effort_reports_status_per_block() {
+        let tool = tool();
+        let content = "alpha\nbeta\nbeta\ngamma";
+        let replacements = vec![
+            SearchReplaceBlock {
+                search: "alpha".to_string(),
+                replace: "ALPHA".to_string(),
+                replace_all: false,
+            },
+            SearchReplaceBlock {
+                search: "beta".to_string(),
+                replace: "BETA".to_string(),
+                replace_all: false,
+            },
+            SearchReplaceBlock {
+                search: "delta".to_string(),
+                replace: "DELTA".to_string(),
+                replace_all: false,
+            },
+            SearchReplaceBlock {
+                search: "gamma".to_string(),
+                replace: "GAMMA".to_string(),
+                replace_all: false,
+            },
+        ];
+
+        let (result, statuses) = tool.apply_replacements_best_effort(content, &replacements);
+        assert_eq!(result, "ALPHA\nbeta\nbeta\nGAMMA");
+        assert_eq!(statuses[0], BlockStatus::Applied);
+        assert_eq!(statuses[1], BlockStatus::Ambiguous { matches: 2 });
+        assert!(matches!(statuses[2], BlockStatus::NotFound { .. }));
+        assert_eq!(statuses[3], BlockStatus::Applied);
+    }
+
+    #[test]
+    fn test_replace_all_replaces_every_occurrence() {
+        let tool = tool();
+        let content = "line1\nsearch\nline2\nsearch\nline3";
+        let replacements = vec![SearchReplaceBlock {
+            search: "search".to_string(),
+            replace: "replaced".to_string(),
+            replace_all: true,
+        }];
+
+        let result = tool.apply_replacements(content, replacements).unwrap();
+        assert_eq!(result, "line1\nreplaced\nline2\nreplaced\nline3");
+    }
+
+    #[test]
+    fn test_change_hunks_include_surrounding_context() {
+        let before = "a\nb\nc\nd\ne\nf\ng\n";
+        let after = "a\nb\nc\nD\ne\nf\ng\n";
+        let hunks = change_hunks(before, after, 1);
+        assert!(hunks.starts_with("@@ -3,3 +3,3 @@"), "Got: {hunks}");
+        assert!(hunks.contains(" c\n-d\n+D\n e\n"), "Got: {hunks}");
+        assert!(!hunks.contains(" b\n"), "Got: {hunks}");
+    }
+
+    #[test]
+    fn test_multiple_occurrences_error_reports_match_count() {
+        let tool = tool();
+        let content = "a\nx\nb\nx\nc\nx";
+        let replacements = vec![SearchReplaceBlock {
+            search: "x".to_string(),
+            replace: "y".to_string(),
+            replace_all: false,
+        }];
+
+        let err = tool
+            .apply_replacements(content, replacements)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("found 3 times"), "{err}");
+        assert!(err.contains("replace_all"), "{err}");
+    }
 }
@@ -0,0 +1,266 @@
+//! `/replay`: re-apply a saved session's file edits without rerunning the model.
+//!
+//! The successful write_file/modify_file/delete_file calls in the session's
+//! stored conversation are applied in order through the same tool
+//! implementations, so a modify_file whose search text no longer matches
+//! fails exactly as it would for the model. Replay stops at the first edit
+//! that does not apply cleanly and reports how far it got.
+//!
+//! The tools go through the module's `EditWrapper`, so replayed edits are
+//! confirmed or proposed for review like any other and can be undone with
+//! `/undo`. Replay also stops at an edit held for the user, since later edits
+//! may depend on it. `--from <n>` resumes at the nth edit once the held edit
+//! is answered or the conflict is fixed; the report gives the command.
+//!
+//! Edits recorded with absolute paths under a different checkout can be
+//! rebased onto the current workspace by passing the original root. Edits
+//! made inside spawned sub-agents are not part of the stored conversation and
+//! are not replayed.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::ai::types::{Message, ToolUseData};
+use crate::chat::actor::ActorState;
+use crate::chat::events::{ChatEvent, ChatMessage};
+use crate::module::SlashCommand;
+use crate::persistence::storage;
+use crate::tools::r#trait::{SharedTool, ToolOutput, ToolRequest};
+
+use super::apply_codex_patch::ApplyCodexPatchTool;
+use super::delete_file::DeleteFileTool;
+use super::replace_in_file::ReplaceInFileTool;
+use super::write_file::WriteFileTool;
+use super::EditWrapper;
+
+const EDIT_TOOLS: &[&str] = &["write_file", "modify_file", "delete_file"];
+
+pub struct ReplaySlashCommand {
+    pub edit_wrapper: EditWrapper,
+}
+
+#[async_trait::async_trait(?Send)]
+impl SlashCommand for ReplaySlashCommand {
+    fn name(&self) -> &'static str {
+        "replay"
+    }
+
+    fn description(&self) -> &'static str {
+        "Re-apply a saved session's file edits to the current workspace"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/replay <session_id> [--from <n>] [original_workspace_root]"
+    }
+
+    async fn execute(&self, state: &mut ActorState, args: &[&str]) -> Vec<ChatMessage> {
+        let Some(ReplayArgs {
+            session_id,
+            from,
+            rebase_arg,
+        }) = ReplayArgs::parse(args)
+        else {
+            return vec![ChatMessage::error(format!("Usage: {}", self.usage()))];
+        };
+        let rebase = rebase_arg.map(PathBuf::from);
+        if rebase.is_some() && state.workspace_roots.len() != 1 {
+            return vec![ChatMessage::error(
+                "Rebasing paths requires exactly one workspace root".to_string(),
+            )];
+        }
+
+        let session = match storage::load_session(session_id, Some(&state.sessions_dir)) {
+            Ok(session) => session,
+            Err(e) => {
+                return vec![ChatMessage::error(format!(
+                    "Failed to load session '{session_id}': {e:?}"
+                ))]
+            }
+        };
+
+        let edits = recorded_edits(&session.messages);
+        if edits.is_empty() {
+            return vec![ChatMessage::system(format!(
+                "Session '{session_id}' has no file edits to replay."
+            ))];
+        }
+
+        let total = edits.len();
+        if from > total {
+            return vec![ChatMessage::error(format!(
+                "Session '{session_id}' has only {total} edits to replay."
+            ))];
+        }
+        let resume = |next: usize| match rebase_arg {
+            Some(root) => format!("/replay {session_id} --from {next} {root}"),
+            None => format!("/replay {session_id} --from {next}"),
+        };
+
+        let target_root = state.workspace_roots.first().cloned();
+        for (index, mut edit) in edits.into_iter().enumerate().skip(from - 1) {
+            if let (Some(from), Some(to)) = (&rebase, &target_root) {
+                rebase_file_path(&mut edit.arguments, from, to);
+            }
+            let description = describe(&edit);
+            match self.apply(state, &edit).await {
+                Ok(Applied::Written) => {}
+                Ok(Applied::Held(prompt)) if index + 1 < total => {
+                    return vec![ChatMessage::system(format!(
+                        "Replayed {index} of {total} edits. Edit {} ({description}) is \
+                         waiting for you: {prompt}\n\nLater edits were not applied; once \
+                         you have answered, run `{}` to continue.",
+                        index + 1,
+                        resume(index + 2)
+                    ))];
+                }
+                Ok(Applied::Held(prompt)) => {
+                    return vec![ChatMessage::system(format!(
+                        "Replayed {index} of {total} edits. The last edit ({description}) \
+                         is waiting for you: {prompt}"
+                    ))];
+                }
+                Err(e) => {
+                    return vec![ChatMessage::error(format!(
+                        "Replayed {index} of {total} edits. Edit {} ({description}) no longer \
+                         applies:\n{e}\n\nLater edits were not applied. Fix the conflict and \
+                         run `{}` to continue.",
+                        index + 1,
+                        resume(index + 1)
+                    ))];
+                }
+            }
+        }
+
+        let message = if from == 1 {
+            format!("Replayed all {total} edits from session '{session_id}'.")
+        } else {
+            format!("Replayed edits {from} to {total} from session '{session_id}'.")
+        };
+        vec![ChatMessage::system(message)]
+    }
+}
+
+struct ReplayArgs<'a> {
+    session_id: &'a str,
+    /// 1-based index of the first edit to apply.
+    from: usize,
+    rebase_arg: Option<&'a str>,
+}
+
+impl<'a> ReplayArgs<'a> {
+    fn parse(args: &[&'a str]) -> Option<Self> {
+        let (session_id, rest) = args.split_first()?;
+        let mut from = 1;
+        let mut rebase_arg = None;
+        let mut rest = rest.iter();
+        while let Some(arg) = rest.next() {
+            match *arg {
+                "--from" => from = rest.next()?.parse().ok().filter(|n| *n >= 1)?,
+                root if rebase_arg.is_none() => rebase_arg = Some(root),
+                _ => return None,
+            }
+        }
+        Some(Self {
+            session_id,
+            from,
+            rebase_arg,
+        })
+    }
+}
+
+/// Edit tool calls from the conversation, in order, that succeeded when the
+/// session originally ran.
+fn recorded_edits(messages: &[Message]) -> Vec<ToolUseData> {
+    let succeeded: HashSet<&str> = messages
+        .iter()
+        .flat_map(|m| m.content.tool_results())
+        .filter(|result| !result.is_error)
+        .map(|result| result.tool_use_id.as_str())
+        .collect();
+
+    messages
+        .iter()
+        .flat_map(|m| m.content.tool_uses())
+        .filter(|tool_use| {
+            EDIT_TOOLS.contains(&tool_use.name.as_str()) && succeeded.contains(tool_use.id.as_str())
+        })
+        .cloned()
+        .collect()
+}
+
+fn rebase_file_path(arguments: &mut Value, from: &Path, to: &Path) {
+    let Some(file_path) = arguments.get("file_path").and_then(Value::as_str) else {
+        return;
+    };
+    if let Ok(relative) = Path::new(file_path).strip_prefix(from) {
+        arguments["file_path"] = Value::String(to.join(relative).to_string_lossy().into_owned());
+    }
+}
+
+fn describe(edit: &ToolUseData) -> String {
+    match edit.arguments.get("file_path").and_then(Value::as_str) {
+        Some(path) => format!("{} {path}", edit.name),
+        None => edit.name.clone(),
+    }
+}
+
+/// The tool that originally handled `edit`. modify_file is served by either
+/// file modification API; the recorded arguments tell them apart.
+fn tool_for(state: &ActorState, edit: &ToolUseData) -> Result<SharedTool> {
+    let roots = state.workspace_roots.clone();
+    let settings = state.settings.clone();
+    let tool: SharedTool = match edit.name.as_str() {
+        "write_file" => Arc::new(WriteFileTool::new(roots, settings)?),
+        "delete_file" => Arc::new(DeleteFileTool::new(roots, settings)?),
+        _ if edit.arguments.get("hunks").is_some() => {
+            Arc::new(ApplyCodexPatchTool::new(roots, settings)?)
+        }
+        _ => Arc::new(ReplaceInFileTool::new(roots, settings)?),
+    };
+    Ok(tool)
+}
+
+enum Applied {
+    Written,
+    /// Parked for confirmation or proposed for review; carries what the user
+    /// is asked.
+    Held(String),
+}
+
+impl ReplaySlashCommand {
+    async fn apply(&self, state: &mut ActorState, edit: &ToolUseData) -> Result<Applied> {
+        let tool = self.edit_wrapper.wrap(tool_for(state, edit)?);
+        let request = ToolRequest::new(edit.arguments.clone(), edit.id.clone());
+        let handle = tool.process(&request).await?;
+        match handle.execute().await {
+            ToolOutput::Result {
+                content,
+                is_error: true,
+                ..
+            } => anyhow::bail!(content),
+            ToolOutput::PromptUser { question } => Ok(Applied::Held(question)),
+            ToolOutput::ProposeEdit { handle, file_path } => {
+                let proposed = state.edit_review.propose(
+                    handle,
+                    edit.id.clone(),
+                    edit.name.clone(),
+                    edit.arguments.clone(),
+                    file_path,
+                );
+                let prompt = format!(
+                    "accept or reject the proposed edit to {}.",
+                    proposed.file_path
+                );
+                state.event_sender.send(ChatEvent::EditsProposed {
+                    edits: vec![proposed],
+                });
+                Ok(Applied::Held(prompt))
+            }
+            _ => Ok(Applied::Written),
+        }
+    }
+}
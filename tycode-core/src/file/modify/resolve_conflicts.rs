@@ -0,0 +1,188 @@
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::access::FileAccessManager;
+use crate::file::conflicts::{find_conflicts, resolve_conflict_at, resolve_conflicts, Resolution};
+use crate::file::manager::FileModificationManager;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    ContinuationPreference, FileModification, FileOperation, ToolCallHandle, ToolCapability,
+    ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+use crate::tools::ToolName;
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+/// Resolves git merge conflicts by keeping one side (or both) of each
+/// conflicted region, without the model having to reproduce the text.
+#[derive(Clone)]
+pub struct ResolveConflictsTool {
+    file_manager: FileAccessManager,
+}
+
+impl ResolveConflictsTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("resolve_conflicts")
+    }
+
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> anyhow::Result<Self> {
+        let file_manager = FileAccessManager::new(workspace_roots)?.with_settings(settings);
+        Ok(Self { file_manager })
+    }
+}
+
+struct ResolveConflictsHandle {
+    modification: FileModification,
+    resolved: usize,
+    remaining: usize,
+    tool_use_id: String,
+    file_manager: FileAccessManager,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for ResolveConflictsHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "resolve_conflicts".to_string(),
+            tool_type: ToolRequestType::ModifyFile {
+                file_path: self.modification.path.to_string_lossy().to_string(),
+                before: self
+                    .modification
+                    .original_content
+                    .clone()
+                    .unwrap_or_default(),
+                after: self.modification.new_content.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let manager = FileModificationManager::new(self.file_manager.clone());
+        match manager.apply_modification(self.modification).await {
+            Ok(stats) => ToolOutput::Result {
+                content: json!({
+                    "success": true,
+                    "conflicts_resolved": self.resolved,
+                    "conflicts_remaining": self.remaining,
+                    "lines_added": stats.lines_added,
+                    "lines_removed": stats.lines_removed
+                })
+                .to_string(),
+                is_error: false,
+                continuation: ContinuationPreference::Continue,
+                ui_result: ToolExecutionResult::ModifyFile {
+                    lines_added: stats.lines_added,
+                    lines_removed: stats.lines_removed,
+                },
+            },
+            Err(e) => ToolOutput::Result {
+                content: format!("Failed to resolve conflicts: {e:?}"),
+                is_error: true,
+                continuation: ContinuationPreference::Continue,
+                ui_result: ToolExecutionResult::Error {
+                    short_message: "Conflict resolution failed".to_string(),
+                    detailed_message: format!("{e:?}"),
+                    category: None,
+                },
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for ResolveConflictsTool {
+    fn name(&self) -> String {
+        "resolve_conflicts".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Resolve git merge conflicts (<<<<<<< / ======= / >>>>>>> regions) in a file by keeping \"ours\" (the side after <<<<<<<), \"theirs\" (the side before >>>>>>>) or \"both\" (ours then theirs). Resolves every conflict unless `conflict` picks one. Use modify_file afterwards if the result needs hand-merging.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "file_path": {
+                    "type": "string",
+                    "description": "Absolute path inside a workspace root to the conflicted file"
+                },
+                "resolution": {
+                    "type": "string",
+                    "enum": ["ours", "theirs", "both"],
+                    "description": "Which side of each conflict to keep"
+                },
+                "conflict": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Optional 1-based index of a single conflict to resolve, in file order. Defaults to all conflicts"
+                }
+            },
+            "required": ["file_path", "resolution"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Execution
+    }
+
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write]
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let file_path = request
+            .arguments
+            .get("file_path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: file_path"))?;
+        let resolution = request
+            .arguments
+            .get("resolution")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required parameter: resolution"))?;
+        let Some(resolution) = Resolution::parse(resolution) else {
+            bail!("resolution must be one of \"ours\", \"theirs\" or \"both\"");
+        };
+
+        let resolved_path = self.file_manager.resolve(file_path)?;
+        let resolved_path_str = resolved_path.to_string_lossy().to_string();
+        let original_content = self.file_manager.read_file(&resolved_path_str).await?;
+
+        let conflicts = find_conflicts(&original_content).len();
+        if conflicts == 0 {
+            bail!("No merge conflict markers found in {file_path}");
+        }
+        let (new_content, resolved) = match request.arguments.get("conflict") {
+            None | Some(Value::Null) => {
+                (resolve_conflicts(&original_content, resolution), conflicts)
+            }
+            Some(value) => {
+                let index = value
+                    .as_u64()
+                    .filter(|&index| index >= 1 && index as usize <= conflicts)
+                    .ok_or_else(|| anyhow::anyhow!("conflict must be between 1 and {conflicts}"))?;
+                (
+                    resolve_conflict_at(&original_content, index as usize - 1, resolution),
+                    1,
+                )
+            }
+        };
+
+        let modification = FileModification {
+            path: resolved_path,
+            operation: FileOperation::Update,
+            original_content: Some(original_content),
+            new_content: Some(new_content),
+            warning: None,
+        };
+
+        Ok(Box::new(ResolveConflictsHandle {
+            modification,
+            resolved,
+            remaining: conflicts - resolved,
+            tool_use_id: request.tool_use_id.clone(),
+            file_manager: self.file_manager.clone(),
+        }))
+    }
+}
@@ -1,9 +1,11 @@
 use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
 use crate::file::access::FileAccessManager;
+use crate::file::conflicts::conflict_marker_warning;
 use crate::file::manager::FileModificationManager;
+use crate::settings::SettingsManager;
 use crate::tools::r#trait::{
-    ContinuationPreference, FileModification, FileOperation, ToolCallHandle, ToolCategory,
-    ToolExecutor, ToolOutput, ToolRequest,
+    ContinuationPreference, FileModification, FileOperation, ToolCallHandle, ToolCapability,
+    ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
 };
 use crate::tools::ToolName;
 use anyhow::Result;
@@ -20,8 +22,8 @@ impl WriteFileTool {
         ToolName::new("write_file")
     }
 
-    pub fn new(workspace_roots: Vec<PathBuf>) -> anyhow::Result<Self> {
-        let file_manager = FileAccessManager::new(workspace_roots)?;
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> anyhow::Result<Self> {
+        let file_manager = FileAccessManager::new(workspace_roots)?.with_settings(settings);
         Ok(Self { file_manager })
     }
 }
@@ -52,21 +54,27 @@ impl ToolCallHandle for WriteFileHandle {
 
     async fn execute(self: Box<Self>) -> ToolOutput {
         let manager = FileModificationManager::new(self.file_manager.clone());
+        let warning = self.modification.warning.clone();
         match manager.apply_modification(self.modification).await {
-            Ok(stats) => ToolOutput::Result {
-                content: json!({
+            Ok(stats) => {
+                let mut content = json!({
                     "success": true,
                     "lines_added": stats.lines_added,
                     "lines_removed": stats.lines_removed
-                })
-                .to_string(),
-                is_error: false,
-                continuation: ContinuationPreference::Continue,
-                ui_result: ToolExecutionResult::ModifyFile {
-                    lines_added: stats.lines_added,
-                    lines_removed: stats.lines_removed,
-                },
-            },
+                });
+                if let Some(warning) = warning {
+                    content["warning"] = json!(warning);
+                }
+                ToolOutput::Result {
+                    content: content.to_string(),
+                    is_error: false,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::ModifyFile {
+                        lines_added: stats.lines_added,
+                        lines_removed: stats.lines_removed,
+                    },
+                }
+            }
             Err(e) => {
                 let msg = format!("{e:?}");
                 ToolOutput::Result {
@@ -80,6 +88,7 @@ impl ToolCallHandle for WriteFileHandle {
                             msg.clone()
                         },
                         detailed_message: msg,
+                        category: None,
                     },
                 }
             }
@@ -118,6 +127,10 @@ impl ToolExecutor for WriteFileTool {
         ToolCategory::Execution
     }
 
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write]
+    }
+
     async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
         let file_path = request
             .arguments
@@ -144,7 +157,7 @@ impl ToolExecutor for WriteFileTool {
             operation,
             original_content,
             new_content: Some(content.to_string()),
-            warning: None,
+            warning: conflict_marker_warning(content),
         };
 
         Ok(Box::new(WriteFileHandle {
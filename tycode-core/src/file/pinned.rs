@@ -0,0 +1,366 @@
+//! Pinned files: reference material (architecture docs, coding standards, ...)
+//! that stays in context for the whole session.
+//!
+//! When the pinned set exceeds `auto_context_bytes`, files larger than their
+//! share of the budget are condensed (see skeleton.rs) and a warning is
+//! surfaced suggesting files to unpin.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::chat::actor::ActorState;
+use crate::chat::events::ChatMessage;
+use crate::module::{ContextComponent, ContextComponentId, SessionStateComponent, SlashCommand};
+use crate::settings::SettingsManager;
+
+use super::access::FileAccessManager;
+use super::config::File;
+use super::resolver;
+use super::skeleton::{self, Condensed};
+use super::workspace::WorkspacePaths;
+
+pub const PINNED_FILES_ID: ContextComponentId = ContextComponentId("pinned_files");
+
+/// Session-scoped set of files that are always included in context.
+pub struct PinnedFiles {
+    workspace_paths: WorkspacePaths,
+    file_access: FileAccessManager,
+    settings: SettingsManager,
+    pinned: RwLock<BTreeSet<PathBuf>>,
+}
+
+impl PinnedFiles {
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> Result<Self> {
+        Ok(Self {
+            workspace_paths: WorkspacePaths::new(workspace_roots.clone())?,
+            file_access: FileAccessManager::new(workspace_roots)?.with_settings(settings.clone()),
+            settings,
+            pinned: RwLock::new(BTreeSet::new()),
+        })
+    }
+
+    /// Pins a file. Relative paths are resolved against the workspace roots.
+    pub fn pin(&self, path: &str) -> Result<PathBuf> {
+        let resolved = self.resolve_existing(path)?;
+        if !resolved.is_file() {
+            bail!("Not a file: {path}");
+        }
+        self.pinned.write().unwrap().insert(resolved.clone());
+        Ok(resolved)
+    }
+
+    /// Unpins a file. Falls back to suffix matching so files that were
+    /// deleted after being pinned can still be removed.
+    pub fn unpin(&self, path: &str) -> Result<PathBuf> {
+        let mut pinned = self.pinned.write().unwrap();
+
+        if let Ok(resolved) = self.resolve_existing(path) {
+            if pinned.remove(&resolved) {
+                return Ok(resolved);
+            }
+        }
+
+        let suffix = Path::new(path);
+        let matches: Vec<PathBuf> = pinned
+            .iter()
+            .filter(|p| p.ends_with(suffix))
+            .cloned()
+            .collect();
+        match matches.as_slice() {
+            [single] => {
+                pinned.remove(single);
+                Ok(single.clone())
+            }
+            [] => bail!("File is not pinned: {path}"),
+            _ => bail!("Ambiguous path {path}; matches {matches:?}"),
+        }
+    }
+
+    pub fn list(&self) -> Vec<PathBuf> {
+        self.pinned.read().unwrap().iter().cloned().collect()
+    }
+
+    fn resolve_existing(&self, path: &str) -> Result<PathBuf> {
+        let candidate = resolver::expand_home(path);
+        if candidate.is_absolute() {
+            return self.workspace_paths.contains_existing_path(&candidate);
+        }
+
+        for root in self.workspace_paths.roots() {
+            let joined = root.join(&candidate);
+            if joined.exists() {
+                return self.workspace_paths.contains_existing_path(&joined);
+            }
+        }
+
+        bail!("File not found in any workspace root: {path}")
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ContextComponent for PinnedFiles {
+    fn id(&self) -> ContextComponentId {
+        PINNED_FILES_ID
+    }
+
+    async fn build_context_section(&self) -> Option<String> {
+        let pinned = self.list();
+        if pinned.is_empty() {
+            return None;
+        }
+
+        let mut contents = Vec::with_capacity(pinned.len());
+        for path in &pinned {
+            let content = self.file_access.read_file(&path.to_string_lossy()).await;
+            if let Err(e) = &content {
+                warn!(?e, "Failed to read pinned file {:?}", path);
+            }
+            contents.push(content);
+        }
+
+        let total_bytes: usize = contents.iter().flatten().map(String::len).sum();
+        let file_config: File = self.settings.get_module_config(File::NAMESPACE);
+        // Over budget, each file gets an equal share; smaller files are kept whole
+        let per_file_budget = (total_bytes > file_config.auto_context_bytes)
+            .then(|| file_config.auto_context_bytes / pinned.len());
+
+        let mut output = String::from("Pinned Files (always in context):\n");
+        for (path, content) in pinned.iter().zip(contents) {
+            let content = match content {
+                Ok(content) => content,
+                Err(e) => {
+                    output.push_str(&format!("\n=== {} ===\n", resolver::display_path(path)));
+                    output.push_str(&format!("(unable to read pinned file: {e})\n"));
+                    continue;
+                }
+            };
+
+            let condensed =
+                per_file_budget.and_then(|budget| skeleton::condense(path, &content, budget));
+            let (content, note) = match condensed {
+                Some((condensed, Condensed::Skeleton)) => (
+                    condensed,
+                    format!(
+                        " (skeletonized to imports and signatures; full file is {} bytes)",
+                        content.len()
+                    ),
+                ),
+                Some((condensed, Condensed::HeadTail)) => (
+                    condensed,
+                    format!(
+                        " (truncated to head and tail; full file is {} bytes)",
+                        content.len()
+                    ),
+                ),
+                None => (content, String::new()),
+            };
+
+            output.push_str(&format!(
+                "\n=== {}{note} ===\n",
+                resolver::display_path(path)
+            ));
+            output.push_str(&content);
+            if !content.ends_with('\n') {
+                output.push('\n');
+            }
+        }
+
+        if total_bytes > file_config.auto_context_bytes {
+            warn!(
+                total_bytes,
+                budget = file_config.auto_context_bytes,
+                "Pinned files exceed the auto context budget"
+            );
+            output.push_str(&format!(
+                "\nWarning: pinned files total {total_bytes} bytes, exceeding the \
+                 auto_context_bytes budget of {}, so larger files were condensed. Read them \
+                 in full when needed, or unpin some files with /unpin.\n",
+                file_config.auto_context_bytes
+            ));
+        }
+
+        Some(output)
+    }
+}
+
+pub(crate) struct PinnedFilesSessionState {
+    pub(crate) pinned: Arc<PinnedFiles>,
+}
+
+impl SessionStateComponent for PinnedFilesSessionState {
+    fn key(&self) -> &str {
+        "pinned_files"
+    }
+
+    fn save(&self) -> Value {
+        serde_json::to_value(self.pinned.list()).expect("PathBuf list serialization cannot fail")
+    }
+
+    fn load(&self, state: Value) -> Result<()> {
+        let paths: Vec<PathBuf> = serde_json::from_value(state)?;
+        let mut pinned = self.pinned.pinned.write().unwrap();
+        pinned.clear();
+        for path in paths {
+            match self.pinned.workspace_paths.contains_existing_path(&path) {
+                Ok(resolved) => {
+                    pinned.insert(resolved);
+                }
+                Err(e) => warn!(?e, "Dropping pinned file from restored session"),
+            }
+        }
+        Ok(())
+    }
+
+    fn reset(&self) {
+        self.pinned.pinned.write().unwrap().clear();
+    }
+}
+
+pub struct PinSlashCommand {
+    pub(crate) pinned: Arc<PinnedFiles>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl SlashCommand for PinSlashCommand {
+    fn name(&self) -> &'static str {
+        "pin"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pin a file so it is always included in context"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/pin [path]"
+    }
+
+    async fn execute(&self, _state: &mut ActorState, args: &[&str]) -> Vec<ChatMessage> {
+        let Some(path) = args.first() else {
+            return vec![list_pinned(&self.pinned)];
+        };
+
+        match self.pinned.pin(path) {
+            Ok(resolved) => vec![ChatMessage::system(format!(
+                "Pinned {}",
+                resolver::display_path(&resolved)
+            ))],
+            Err(e) => vec![ChatMessage::error(format!("Failed to pin {path}: {e}"))],
+        }
+    }
+}
+
+pub struct UnpinSlashCommand {
+    pub(crate) pinned: Arc<PinnedFiles>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl SlashCommand for UnpinSlashCommand {
+    fn name(&self) -> &'static str {
+        "unpin"
+    }
+
+    fn description(&self) -> &'static str {
+        "Remove a file from the pinned context"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/unpin <path>"
+    }
+
+    async fn execute(&self, _state: &mut ActorState, args: &[&str]) -> Vec<ChatMessage> {
+        let Some(path) = args.first() else {
+            return vec![ChatMessage::error(format!("Usage: {}", self.usage()))];
+        };
+
+        match self.pinned.unpin(path) {
+            Ok(resolved) => vec![ChatMessage::system(format!(
+                "Unpinned {}",
+                resolver::display_path(&resolved)
+            ))],
+            Err(e) => vec![ChatMessage::error(format!("Failed to unpin {path}: {e}"))],
+        }
+    }
+}
+
+fn list_pinned(pinned: &PinnedFiles) -> ChatMessage {
+    let files = pinned.list();
+    if files.is_empty() {
+        return ChatMessage::system("No pinned files. Usage: /pin <path>".to_string());
+    }
+
+    let mut message = String::from("Pinned files:\n");
+    for file in files {
+        message.push_str(&format!("  - {}\n", resolver::display_path(&file)));
+    }
+    ChatMessage::system(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs as std_fs;
+    use tempfile::tempdir;
+
+    fn pinned_in(dir: &Path, workspace: &Path) -> PinnedFiles {
+        let settings = SettingsManager::from_path(dir.join("settings.toml")).unwrap();
+        PinnedFiles::new(vec![workspace.to_path_buf()], settings).unwrap()
+    }
+
+    #[tokio::test]
+    async fn pinned_file_contents_are_rendered() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        std_fs::create_dir(&workspace).unwrap();
+        std_fs::write(workspace.join("ARCHITECTURE.md"), "# Layers\n").unwrap();
+
+        let pinned = pinned_in(temp.path(), &workspace);
+        pinned.pin("ARCHITECTURE.md").unwrap();
+
+        let section = pinned.build_context_section().await.expect("has pins");
+        assert!(section.contains("ARCHITECTURE.md"), "{section}");
+        assert!(section.contains("# Layers"), "{section}");
+        assert!(!section.contains("Warning"), "{section}");
+    }
+
+    #[tokio::test]
+    async fn warns_when_pins_exceed_budget_and_unpin_clears() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        std_fs::create_dir(&workspace).unwrap();
+        std_fs::write(workspace.join("big.txt"), "x".repeat(100)).unwrap();
+
+        let pinned = pinned_in(temp.path(), &workspace);
+        let mut config: File = pinned.settings.get_module_config(File::NAMESPACE);
+        config.auto_context_bytes = 10;
+        pinned.settings.set_module_config(File::NAMESPACE, config);
+
+        pinned.pin("big.txt").unwrap();
+        let section = pinned.build_context_section().await.unwrap();
+        assert!(
+            section.contains("exceeding the auto_context_bytes"),
+            "{section}"
+        );
+
+        assert!(section.contains("truncated to head and tail"), "{section}");
+
+        pinned.unpin("big.txt").unwrap();
+        assert!(pinned.build_context_section().await.is_none());
+    }
+
+    #[test]
+    fn pin_rejects_paths_outside_workspace() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        std_fs::create_dir(&workspace).unwrap();
+        std_fs::write(temp.path().join("outside.txt"), "nope").unwrap();
+
+        let pinned = pinned_in(temp.path(), &workspace);
+        let outside = temp.path().join("outside.txt");
+        assert!(pinned.pin(&outside.to_string_lossy()).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+//! `read_files` tool: reads several files, or every file matching a glob, in
+//! one call.
+//!
+//! Globs use gitignore syntax and are matched against files inside the
+//! workspace roots, so ignored files never match. Relative globs are tried
+//! against every root; absolute globs only against the root containing them.
+//! The number of files, bytes per file and total bytes returned are capped by
+//! the `file` settings, and anything left out is listed so the model can
+//! follow up. A file cut off by the per-file cap is saved in full to the
+//! session's tool-calls directory, like long command output, so the rest can
+//! be read in ranges.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use ignore::overrides::OverrideBuilder;
+use serde_json::{json, Value};
+
+use crate::chat::events::{
+    FileInfo, ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType,
+};
+use crate::file::access::FileAccessManager;
+use crate::file::config::File;
+use crate::file::resolver;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+use crate::tools::ToolName;
+
+pub struct ReadFilesTool {
+    file_manager: FileAccessManager,
+    settings: SettingsManager,
+    tool_calls_dir: PathBuf,
+}
+
+impl ReadFilesTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("read_files")
+    }
+
+    pub fn new(
+        workspace_roots: Vec<PathBuf>,
+        settings: SettingsManager,
+        tool_calls_dir: PathBuf,
+    ) -> Result<Self> {
+        Ok(Self {
+            file_manager: FileAccessManager::new(workspace_roots)?.with_settings(settings.clone()),
+            settings,
+            tool_calls_dir,
+        })
+    }
+
+    /// Expands `pattern` into matching files. Plain paths resolve to
+    /// themselves; globs are matched against each applicable workspace root.
+    async fn expand(&self, pattern: &str) -> Result<Vec<PathBuf>> {
+        if !is_glob(pattern) {
+            return Ok(vec![self.file_manager.resolve(pattern)?]);
+        }
+
+        let expanded = resolver::expand_home(pattern);
+        let mut matches = Vec::new();
+        for root in &self.file_manager.roots {
+            let relative = match expanded.strip_prefix(root) {
+                Ok(relative) => relative.to_string_lossy().to_string(),
+                Err(_) if expanded.is_absolute() => continue,
+                Err(_) => pattern.to_string(),
+            };
+
+            let mut builder = OverrideBuilder::new(root);
+            builder.add(&relative)?;
+            let matcher = builder.build()?;
+
+            let files = self
+                .file_manager
+                .list_all_files_recursive(&root.to_string_lossy(), None)
+                .await?;
+            matches.extend(
+                files
+                    .into_iter()
+                    .filter(|file| matcher.matched(file, false).is_whitelist()),
+            );
+        }
+
+        if matches.is_empty() {
+            bail!("No files in the workspace match {pattern}");
+        }
+        Ok(matches)
+    }
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for ReadFilesTool {
+    fn name(&self) -> String {
+        "read_files".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Read the contents of one or more files. Accepts absolute paths and globs (e.g. `src/auth/*.rs` or `/project/src/**/*.ts`) so related files can be read in a single call.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        let config: File = self.settings.get_module_config(File::NAMESPACE);
+        json!({
+            "type": "object",
+            "properties": {
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": format!(
+                        "Absolute file paths or gitignore-style globs. Relative globs are matched in every workspace root. At most {} files and {} bytes are returned, and at most {} bytes of any one file; anything beyond that is listed as omitted or truncated.",
+                        config.read_files_max_files, config.read_files_max_bytes, config.max_read_file_bytes
+                    )
+                }
+            },
+            "required": ["paths"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Read
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let Some(patterns) = request.arguments["paths"].as_array() else {
+            bail!("Missing required argument \"paths\"");
+        };
+        let patterns: Vec<String> = patterns
+            .iter()
+            .map(|p| {
+                p.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| anyhow::anyhow!("paths must be an array of strings"))
+            })
+            .collect::<Result<_>>()?;
+        if patterns.is_empty() {
+            bail!("paths must not be empty");
+        }
+
+        let mut files = Vec::new();
+        for pattern in &patterns {
+            for file in self.expand(pattern).await? {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+
+        let config: File = self.settings.get_module_config(File::NAMESPACE);
+        Ok(Box::new(ReadFilesHandle {
+            patterns,
+            files,
+            max_files: config.read_files_max_files,
+            max_bytes: config.read_files_max_bytes,
+            max_file_bytes: config.max_read_file_bytes,
+            file_manager: self.file_manager.clone(),
+            tool_calls_dir: self.tool_calls_dir.clone(),
+            tool_use_id: request.tool_use_id.clone(),
+        }))
+    }
+}
+
+struct ReadFilesHandle {
+    patterns: Vec<String>,
+    files: Vec<PathBuf>,
+    max_files: usize,
+    max_bytes: usize,
+    max_file_bytes: usize,
+    file_manager: FileAccessManager,
+    tool_calls_dir: PathBuf,
+    tool_use_id: String,
+}
+
+impl ReadFilesHandle {
+    /// Saves the full content of the `index`th file so a truncated read can
+    /// be continued, and returns how to get at it.
+    async fn persist_full(&self, index: usize, content: &str, path: &str) -> String {
+        let persist_path = self
+            .tool_calls_dir
+            .join(format!("{}-{index}", self.tool_use_id));
+        let write = async {
+            tokio::fs::create_dir_all(&self.tool_calls_dir).await?;
+            tokio::fs::write(&persist_path, content).await
+        };
+        match write.await {
+            Ok(()) => format!(
+                "full content saved to: {}. Use grep or read it in ranges (e.g. with sed -n) to see the rest",
+                self.file_manager.display_path(&persist_path)
+            ),
+            Err(e) => {
+                tracing::warn!(?e, "Failed to persist full file content");
+                format!("search {path} or read it in ranges (e.g. with sed -n) to see the rest")
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for ReadFilesHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "read_files".to_string(),
+            tool_type: ToolRequestType::ReadFiles {
+                file_paths: self.patterns.clone(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let mut results = Vec::new();
+        let mut read = Vec::new();
+        let mut omitted = Vec::new();
+        let mut remaining = self.max_bytes;
+
+        for (i, file) in self.files.iter().enumerate() {
+            let path = self.file_manager.display_path(file);
+            if i >= self.max_files || remaining == 0 {
+                omitted.push(path);
+                continue;
+            }
+
+            match self.file_manager.read_file(&file.to_string_lossy()).await {
+                Ok(mut content) => {
+                    let bytes = content.len();
+                    let file_cap = self.max_file_bytes <= remaining;
+                    let limit = remaining.min(self.max_file_bytes);
+                    let truncated = bytes > limit;
+                    let reason = match (truncated, file_cap) {
+                        (false, _) => None,
+                        (true, true) => {
+                            let full = self.persist_full(i, &content, &path).await;
+                            Some(format!(
+                                "the file is larger than the {limit} byte per-file cap; {full}"
+                            ))
+                        }
+                        (true, false) => Some("the total byte cap was reached".to_string()),
+                    };
+                    if truncated {
+                        let mut end = limit;
+                        while !content.is_char_boundary(end) {
+                            end -= 1;
+                        }
+                        content.truncate(end);
+                    }
+                    remaining -= content.len();
+
+                    let mut entry = json!({ "path": &path, "content": &content });
+                    if let Some(reason) = reason {
+                        entry["truncated"] = json!(format!(
+                            "showing {} of {bytes} bytes; {reason}",
+                            content.len()
+                        ));
+                    }
+                    results.push(entry);
+                    read.push(FileInfo { path, bytes });
+                }
+                Err(e) => results.push(json!({ "path": path, "error": format!("{e:#}") })),
+            }
+        }
+
+        let mut content = json!({ "files": results });
+        if !omitted.is_empty() {
+            content["omitted"] = json!({
+                "reason": format!(
+                    "read_files returns at most {} files and {} bytes per call",
+                    self.max_files, self.max_bytes
+                ),
+                "paths": omitted,
+            });
+        }
+
+        ToolOutput::Result {
+            content: content.to_string(),
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::ReadFiles { files: read },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs as std_fs;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn glob_reads_matching_files_and_reports_omitted() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        std_fs::create_dir_all(workspace.join("src/auth")).unwrap();
+        std_fs::write(workspace.join("src/auth/a.rs"), "fn a() {}").unwrap();
+        std_fs::write(workspace.join("src/auth/b.rs"), "fn b() {}").unwrap();
+        std_fs::write(workspace.join("src/auth/notes.md"), "notes").unwrap();
+        std_fs::write(workspace.join("src/main.rs"), "fn main() {}").unwrap();
+
+        let settings = SettingsManager::from_path(temp.path().join("settings.toml")).unwrap();
+        let mut config: File = settings.get_module_config(File::NAMESPACE);
+        config.read_files_max_files = 1;
+        settings.set_module_config(File::NAMESPACE, config);
+
+        let tool =
+            ReadFilesTool::new(vec![workspace], settings, temp.path().join("tool-calls")).unwrap();
+        let request = ToolRequest::new(json!({ "paths": ["src/auth/*.rs"] }), "id".to_string());
+        let ToolOutput::Result { content, .. } =
+            tool.process(&request).await.unwrap().execute().await
+        else {
+            panic!("expected a result");
+        };
+
+        let content: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(content["files"].as_array().unwrap().len(), 1);
+        let omitted = content["omitted"]["paths"].as_array().unwrap();
+        assert_eq!(omitted.len(), 1);
+        let listed = format!("{content}");
+        assert!(!listed.contains("notes.md"), "{listed}");
+        assert!(!listed.contains("main.rs"), "{listed}");
+    }
+
+    #[tokio::test]
+    async fn large_file_is_truncated_at_per_file_cap() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        std_fs::create_dir_all(&workspace).unwrap();
+        std_fs::write(workspace.join("big.txt"), "x".repeat(100)).unwrap();
+        std_fs::write(workspace.join("small.txt"), "small").unwrap();
+
+        let settings = SettingsManager::from_path(temp.path().join("settings.toml")).unwrap();
+        let mut config: File = settings.get_module_config(File::NAMESPACE);
+        config.max_read_file_bytes = 40;
+        settings.set_module_config(File::NAMESPACE, config);
+
+        let tool_calls_dir = temp.path().join("tool-calls");
+        let tool =
+            ReadFilesTool::new(vec![workspace.clone()], settings, tool_calls_dir.clone()).unwrap();
+        let paths = [workspace.join("big.txt"), workspace.join("small.txt")]
+            .map(|path| path.display().to_string());
+        let request = ToolRequest::new(json!({ "paths": paths }), "id".to_string());
+        let ToolOutput::Result { content, .. } =
+            tool.process(&request).await.unwrap().execute().await
+        else {
+            panic!("expected a result");
+        };
+
+        let content: Value = serde_json::from_str(&content).unwrap();
+        let files = content["files"].as_array().unwrap();
+        assert_eq!(files[0]["content"].as_str().unwrap().len(), 40);
+        let note = files[0]["truncated"].as_str().unwrap();
+        assert!(note.starts_with("showing 40 of 100 bytes"), "{note}");
+        assert!(note.contains("per-file cap"), "{note}");
+        let saved = tool_calls_dir.join("id-0");
+        assert!(note.contains(&saved.display().to_string()), "{note}");
+        assert_eq!(std_fs::read_to_string(saved).unwrap(), "x".repeat(100));
+        assert_eq!(files[1]["content"], "small");
+        assert!(files[1].get("truncated").is_none());
+    }
+}
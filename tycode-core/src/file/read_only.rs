@@ -1,22 +1,32 @@
 //! Read-only file access module.
 //!
-//! Provides a context component for file tree display.
+//! Provides context components for file tree display and pinned files, and
+//! the read_files, search_files, diff_files and (opt-in) git_blame tools.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Component, Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use anyhow::Result;
 use ignore::WalkBuilder;
+use serde_json::Value;
 use tracing::warn;
 
+use crate::agents::agent::ActiveAgent;
+
 use crate::module::Module;
 use crate::module::PromptComponent;
-use crate::module::{ContextComponent, ContextComponentId};
+use crate::module::{ContextComponent, ContextComponentId, SessionStateComponent, SlashCommand};
 use crate::settings::SettingsManager;
 use crate::tools::r#trait::SharedTool;
 
+use super::blame::GitBlameTool;
 use super::config::File;
+use super::diff::DiffFilesTool;
+use super::pinned::{PinSlashCommand, PinnedFiles, PinnedFilesSessionState, UnpinSlashCommand};
+use super::read_files::ReadFilesTool;
+use super::resolver;
+use super::search::SearchFilesTool;
 use super::workspace::WorkspacePaths;
 
 pub const FILE_TREE_ID: ContextComponentId = ContextComponentId("file_tree");
@@ -24,12 +34,48 @@ pub const FILE_TREE_ID: ContextComponentId = ContextComponentId("file_tree");
 /// Module providing read-only file access capabilities.
 pub struct ReadOnlyFileModule {
     file_tree: Arc<FileTreeManager>,
+    pinned: Arc<PinnedFiles>,
+    read_files: Arc<ReadFilesTool>,
+    search_files: Arc<SearchFilesTool>,
+    diff_files: Arc<DiffFilesTool>,
+    git_blame: Arc<GitBlameTool>,
+    settings: SettingsManager,
 }
 
 impl ReadOnlyFileModule {
-    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> Result<Self> {
-        let file_tree = Arc::new(FileTreeManager::new(workspace_roots, settings)?);
-        Ok(Self { file_tree })
+    pub fn new(
+        workspace_roots: Vec<PathBuf>,
+        settings: SettingsManager,
+        tool_calls_dir: PathBuf,
+    ) -> Result<Self> {
+        let pinned = Arc::new(PinnedFiles::new(workspace_roots.clone(), settings.clone())?);
+        let read_files = Arc::new(ReadFilesTool::new(
+            workspace_roots.clone(),
+            settings.clone(),
+            tool_calls_dir,
+        )?);
+        let search_files = Arc::new(SearchFilesTool::new(
+            workspace_roots.clone(),
+            settings.clone(),
+        )?);
+        let diff_files = Arc::new(DiffFilesTool::new(
+            workspace_roots.clone(),
+            settings.clone(),
+        )?);
+        let git_blame = Arc::new(GitBlameTool::new(
+            workspace_roots.clone(),
+            settings.clone(),
+        )?);
+        let file_tree = Arc::new(FileTreeManager::new(workspace_roots, settings.clone())?);
+        Ok(Self {
+            file_tree,
+            pinned,
+            read_files,
+            search_files,
+            diff_files,
+            git_blame,
+            settings,
+        })
     }
 }
 
@@ -40,11 +86,48 @@ impl Module for ReadOnlyFileModule {
     }
 
     fn context_components(&self) -> Vec<Arc<dyn ContextComponent>> {
-        vec![self.file_tree.clone() as Arc<dyn ContextComponent>]
+        // Pinned files come first so they take priority over the (truncatable) file tree.
+        vec![
+            self.pinned.clone() as Arc<dyn ContextComponent>,
+            self.file_tree.clone() as Arc<dyn ContextComponent>,
+        ]
     }
 
     async fn tools(&self) -> Vec<SharedTool> {
-        vec![]
+        let mut tools: Vec<SharedTool> = vec![
+            self.read_files.clone(),
+            self.search_files.clone(),
+            self.diff_files.clone(),
+        ];
+        if self
+            .settings
+            .get_module_config::<File>(File::NAMESPACE)
+            .git_blame
+        {
+            tools.push(self.git_blame.clone());
+        }
+        tools
+    }
+
+    /// Only the pinned files are persisted. The file tree's `agent_stack` is
+    /// not: resuming a session unwinds every sub-agent first (emptying the
+    /// stack through `on_agent_popped`) and lands on the root agent, so a
+    /// resumed session always lists files with the root agent's budget.
+    fn session_state(&self) -> Option<Arc<dyn SessionStateComponent>> {
+        Some(Arc::new(PinnedFilesSessionState {
+            pinned: self.pinned.clone(),
+        }))
+    }
+
+    fn slash_commands(&self) -> Vec<Arc<dyn SlashCommand>> {
+        vec![
+            Arc::new(PinSlashCommand {
+                pinned: self.pinned.clone(),
+            }),
+            Arc::new(UnpinSlashCommand {
+                pinned: self.pinned.clone(),
+            }),
+        ]
     }
 
     fn settings_namespace(&self) -> Option<&'static str> {
@@ -54,12 +137,28 @@ impl Module for ReadOnlyFileModule {
     fn settings_json_schema(&self) -> Option<schemars::schema::RootSchema> {
         Some(schemars::schema_for!(File))
     }
+
+    fn on_agent_pushed(&self, agent: &ActiveAgent, _params: HashMap<String, Value>) {
+        self.file_tree
+            .agent_stack
+            .write()
+            .unwrap()
+            .push(agent.agent.name().to_string());
+    }
+
+    fn on_agent_popped(&self, _agent: &ActiveAgent) {
+        self.file_tree.agent_stack.write().unwrap().pop();
+    }
 }
 
 /// Manages file tree state and renders project structure to context.
 pub struct FileTreeManager {
     workspace_paths: WorkspacePaths,
     settings: SettingsManager,
+    /// Names of the spawned sub-agents, innermost last. Empty while the root
+    /// agent is active. Used to pick the per-agent listing budget. Not saved
+    /// with the session; see `ReadOnlyFileModule::session_state`.
+    agent_stack: RwLock<Vec<String>>,
 }
 
 impl FileTreeManager {
@@ -68,9 +167,19 @@ impl FileTreeManager {
         Ok(Self {
             workspace_paths,
             settings,
+            agent_stack: RwLock::new(Vec::new()),
         })
     }
 
+    fn current_agent(&self) -> String {
+        self.agent_stack
+            .read()
+            .unwrap()
+            .last()
+            .cloned()
+            .unwrap_or_else(|| self.settings.settings().default_agent)
+    }
+
     pub(crate) fn list_files(&self) -> Vec<PathBuf> {
         let mut all_files = Vec::new();
 
@@ -123,7 +232,7 @@ impl FileTreeManager {
         }
 
         let file_config: File = self.settings.get_module_config(File::NAMESPACE);
-        let max_bytes = file_config.auto_context_bytes;
+        let max_bytes = file_config.auto_context_bytes_for(&self.current_agent());
         Self::truncate_by_bytes(all_files, max_bytes)
     }
 
@@ -162,7 +271,7 @@ impl ContextComponent for FileTreeManager {
             output.push_str("Working directories (project roots):\n");
             for root in &roots {
                 output.push_str("- ");
-                output.push_str(&root.to_string_lossy());
+                output.push_str(&resolver::display_path(root));
                 output.push('\n');
             }
             output.push('\n');
@@ -229,7 +338,7 @@ fn build_file_tree(files: &[PathBuf]) -> String {
     let mut root = TrieNode::default();
 
     for file_path in files {
-        let components = display_components(file_path);
+        let components = display_components(Path::new(&resolver::display_path(file_path)));
         root.insert_path(&components);
     }
 
@@ -324,6 +433,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn per_agent_budget_overrides_global() {
+        let temp = tempdir().unwrap();
+        let workspace = temp.path().join("workspace");
+        std_fs::create_dir(&workspace).unwrap();
+        for name in ["a.rs", "b.rs", "c.rs"] {
+            std_fs::write(workspace.join(name), "").unwrap();
+        }
+
+        let settings = settings_in(temp.path());
+        let mut config: File = settings.get_module_config(File::NAMESPACE);
+        config
+            .agent_auto_context_bytes
+            .insert("context".to_string(), 1);
+        config
+            .agent_auto_context_bytes
+            .insert("zero".to_string(), 0);
+        settings.set_module_config(File::NAMESPACE, config);
+
+        let manager = FileTreeManager::new(vec![workspace], settings).unwrap();
+        assert_eq!(manager.list_files().len(), 3);
+
+        manager
+            .agent_stack
+            .write()
+            .unwrap()
+            .push("context".to_string());
+        assert!(manager.list_files().is_empty());
+
+        manager
+            .agent_stack
+            .write()
+            .unwrap()
+            .push("zero".to_string());
+        assert_eq!(manager.list_files().len(), 3);
+    }
+
     /// The conversational roots must include the file tree; sub-agents keep the
     /// lean default that excludes it. This is the regression guard for the file
     /// listing that a "simplify defaults" refactor silently dropped.
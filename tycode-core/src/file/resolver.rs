@@ -0,0 +1,91 @@
+//! How paths are shown in tool results, events and context, and read back
+//! from tool arguments.
+//!
+//! Anything under the home directory, workspace roots included, is shown
+//! relative to `~` so the real home directory (and username) never reaches
+//! the provider or UI. Tools accept those `~` paths back and expand them.
+
+use std::path::{Path, PathBuf};
+
+/// `path` as it should be presented to the model and UI.
+pub fn display_path(path: &Path) -> String {
+    display_with_home(path, dirs::home_dir().as_deref())
+}
+
+/// Turns a `~` path from a tool argument back into a real one; any other
+/// path is returned unchanged.
+pub fn expand_home(path: &str) -> PathBuf {
+    expand_with_home(path, dirs::home_dir().as_deref())
+}
+
+fn display_with_home(path: &Path, home: Option<&Path>) -> String {
+    let Some(home) = home else {
+        return path.display().to_string();
+    };
+    // Workspace roots are canonical, so also match the canonical home in case
+    // $HOME goes through a symlink
+    let canonical = home.canonicalize().ok();
+    let relative = path
+        .strip_prefix(home)
+        .ok()
+        .or_else(|| path.strip_prefix(canonical.as_deref()?).ok());
+    match relative {
+        Some(relative) if relative.as_os_str().is_empty() => "~".to_string(),
+        Some(relative) => Path::new("~").join(relative).display().to_string(),
+        None => path.display().to_string(),
+    }
+}
+
+fn expand_with_home(path: &str, home: Option<&Path>) -> PathBuf {
+    let relative = match path {
+        "~" => Some(""),
+        _ => path.strip_prefix("~/"),
+    };
+    match (relative, home) {
+        (Some(relative), Some(home)) => home.join(relative),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_under_home_are_shown_relative_to_it() {
+        let home = Path::new("/home/alice");
+
+        let workspace_file = home.join("project").join("src").join("lib.rs");
+        assert_eq!(
+            display_with_home(&workspace_file, Some(home)),
+            "~/project/src/lib.rs"
+        );
+
+        let saved = home.join(".tycode").join("tool-calls").join("call-1");
+        let shown = display_with_home(&saved, Some(home));
+        assert_eq!(shown, "~/.tycode/tool-calls/call-1");
+        assert!(!shown.contains("alice"));
+        assert_eq!(display_with_home(home, Some(home)), "~");
+
+        let elsewhere = Path::new("/srv/project/main.rs");
+        assert_eq!(
+            display_with_home(elsewhere, Some(home)),
+            "/srv/project/main.rs"
+        );
+    }
+
+    #[test]
+    fn shown_paths_expand_back() {
+        let home = Path::new("/home/alice");
+        let file = home.join("project").join("lib.rs");
+
+        let shown = display_with_home(&file, Some(home));
+        assert_eq!(expand_with_home(&shown, Some(home)), file);
+        assert_eq!(expand_with_home("~", Some(home)), home);
+        assert_eq!(
+            expand_with_home("/srv/main.rs", Some(home)),
+            Path::new("/srv/main.rs")
+        );
+        assert_eq!(expand_with_home("~bob/x", Some(home)), Path::new("~bob/x"));
+    }
+}
@@ -0,0 +1,317 @@
+//! `search_files` tool: regex search over the text files in the workspace.
+//!
+//! Lines are matched one at a time, so patterns cannot span lines. Files are
+//! found by walking the workspace (respecting .gitignore) unless
+//! `File::search_index` is enabled, in which case the trigram index in
+//! search_index.rs narrows the walk to files that can contain a match. Roots
+//! whose index exceeds the size budget fall back to walking.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use regex::{Regex, RegexBuilder};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::access::FileAccessManager;
+use crate::file::config::File;
+use crate::file::search_index::{is_tycode_path, required_literals, SearchIndex};
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
+use crate::tools::ToolName;
+
+const DEFAULT_MAX_RESULTS: usize = 100;
+const MAX_RESULTS_LIMIT: usize = 500;
+/// Matching lines longer than this are shortened in the output.
+const MAX_LINE_CHARS: usize = 200;
+
+/// Loaded indexes by workspace root. None marks a root that exceeded the
+/// index budget and is walked for the rest of the session.
+type Indexes = Arc<Mutex<HashMap<PathBuf, Option<SearchIndex>>>>;
+
+pub struct SearchFilesTool {
+    file_manager: FileAccessManager,
+    settings: SettingsManager,
+    indexes: Indexes,
+}
+
+impl SearchFilesTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("search_files")
+    }
+
+    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> Result<Self> {
+        Ok(Self {
+            file_manager: FileAccessManager::new(workspace_roots)?.with_settings(settings.clone()),
+            settings,
+            indexes: Arc::default(),
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for SearchFilesTool {
+    fn name(&self) -> String {
+        "search_files".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Search the text files in the workspace for lines matching a regular expression (Rust regex syntax). Returns `path:line: text` for each match. Respects .gitignore. Prefer this over grep through bash.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "Regular expression matched against each line"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Optional absolute file or directory to search within. Defaults to all workspace roots"
+                },
+                "case_insensitive": {
+                    "type": "boolean",
+                    "description": "Ignore case when matching (default false)"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_RESULTS_LIMIT,
+                    "description": "Maximum matching lines to return (default 100)"
+                }
+            },
+            "required": ["pattern"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Read
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let Some(pattern) = request.arguments["pattern"].as_str() else {
+            bail!("Missing required argument \"pattern\"");
+        };
+        if pattern.is_empty() {
+            bail!("pattern must not be empty");
+        }
+        let case_insensitive = request.arguments["case_insensitive"]
+            .as_bool()
+            .unwrap_or(false);
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid pattern: {e}"))?;
+
+        let scope = match request.arguments["path"].as_str() {
+            Some(path) => {
+                let resolved = self.file_manager.resolve(path)?;
+                if !resolved.exists() {
+                    bail!("Path not found: {path}");
+                }
+                Some(resolved)
+            }
+            None => None,
+        };
+
+        let max_results = request.arguments["max_results"]
+            .as_u64()
+            .map(|n| (n as usize).clamp(1, MAX_RESULTS_LIMIT))
+            .unwrap_or(DEFAULT_MAX_RESULTS);
+
+        let config: File = self.settings.get_module_config(File::NAMESPACE);
+        Ok(Box::new(SearchFilesHandle {
+            file_manager: self.file_manager.clone(),
+            indexes: config.search_index.then(|| self.indexes.clone()),
+            index_max_bytes: config.search_index_max_bytes,
+            regex,
+            literals: required_literals(pattern),
+            scope,
+            max_results,
+            arguments: request.arguments.clone(),
+            tool_use_id: request.tool_use_id.clone(),
+        }))
+    }
+}
+
+struct SearchFilesHandle {
+    file_manager: FileAccessManager,
+    /// Set when indexing is enabled.
+    indexes: Option<Indexes>,
+    index_max_bytes: u64,
+    regex: Regex,
+    literals: Vec<String>,
+    scope: Option<PathBuf>,
+    max_results: usize,
+    arguments: Value,
+    tool_use_id: String,
+}
+
+struct SearchOutcome {
+    lines: Vec<String>,
+    files: usize,
+    truncated: bool,
+    indexed: bool,
+}
+
+impl SearchFilesHandle {
+    /// Files under `root` to scan: the index's candidates when available,
+    /// otherwise every file. The flag reports whether the index was used.
+    async fn files_to_scan(&self, root: &Path) -> Result<(Vec<PathBuf>, bool)> {
+        if let Some(indexes) = &self.indexes {
+            let mut indexes = indexes.lock().await;
+            let slot = indexes
+                .entry(root.to_path_buf())
+                .or_insert_with(|| Some(SearchIndex::load(root)));
+            if let Some(index) = slot {
+                match index
+                    .refresh(&self.file_manager, self.index_max_bytes)
+                    .await
+                {
+                    Ok(()) => return Ok((index.candidates(&self.literals), true)),
+                    Err(e) => {
+                        warn!(?e, "Search index unavailable, walking {}", root.display());
+                        *slot = None;
+                    }
+                }
+            }
+        }
+
+        let mut files = self
+            .file_manager
+            .list_all_files_recursive(&root.to_string_lossy(), None)
+            .await?;
+        files.retain(|file| !is_tycode_path(root, file));
+        files.sort();
+        Ok((files, false))
+    }
+
+    async fn search(&self) -> Result<SearchOutcome> {
+        let mut outcome = SearchOutcome {
+            lines: Vec::new(),
+            files: 0,
+            truncated: false,
+            indexed: false,
+        };
+
+        for root in &self.file_manager.roots {
+            if let Some(scope) = &self.scope {
+                if !scope.starts_with(root) {
+                    continue;
+                }
+            }
+
+            let (files, indexed) = self.files_to_scan(root).await?;
+            outcome.indexed |= indexed;
+
+            for file in files {
+                if let Some(scope) = &self.scope {
+                    if !file.starts_with(scope) {
+                        continue;
+                    }
+                }
+                // Binary and unreadable files are skipped
+                let Ok(content) = self.file_manager.read_file(&file.to_string_lossy()).await else {
+                    continue;
+                };
+
+                let mut matched = false;
+                for (number, line) in content.lines().enumerate() {
+                    if !self.regex.is_match(line) {
+                        continue;
+                    }
+                    if outcome.lines.len() == self.max_results {
+                        outcome.truncated = true;
+                        return Ok(outcome);
+                    }
+                    matched = true;
+                    outcome.lines.push(format!(
+                        "{}:{}: {}",
+                        self.file_manager.display_path(&file),
+                        number + 1,
+                        shorten(line)
+                    ));
+                }
+                if matched {
+                    outcome.files += 1;
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+}
+
+fn shorten(line: &str) -> String {
+    let line = line.trim_end();
+    if line.chars().count() <= MAX_LINE_CHARS {
+        return line.to_string();
+    }
+    let short: String = line.chars().take(MAX_LINE_CHARS).collect();
+    format!("{short}...")
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for SearchFilesHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "search_files".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: self.arguments.clone(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let outcome = match self.search().await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                return ToolOutput::Result {
+                    content: format!("{e:#}"),
+                    is_error: true,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::Error {
+                        short_message: "search_files failed".to_string(),
+                        detailed_message: format!("{e:#}"),
+                        category: None,
+                    },
+                }
+            }
+        };
+
+        let mut content = if outcome.lines.is_empty() {
+            format!("No matches for {}", self.regex.as_str())
+        } else {
+            outcome.lines.join("\n")
+        };
+        if outcome.truncated {
+            content.push_str(&format!(
+                "\n... stopped after {} matches; narrow the pattern or path to see the rest",
+                self.max_results
+            ));
+        }
+
+        ToolOutput::Result {
+            content,
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Other {
+                result: json!({
+                    "matches": outcome.lines.len(),
+                    "files": outcome.files,
+                    "truncated": outcome.truncated,
+                    "indexed": outcome.indexed,
+                }),
+            },
+        }
+    }
+}
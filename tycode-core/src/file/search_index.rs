@@ -0,0 +1,624 @@
+//! Persistent trigram index that narrows `search_files` to the files that can
+//! contain a match.
+//!
+//! Every indexed file records its size, mtime and the sorted set of byte
+//! trigrams in its (ASCII-lowercased) content. A search extracts the literal
+//! runs every match must contain (`required_literals`) and only scans files
+//! holding all of their trigrams.
+//!
+//! The first search after loading compares sizes and mtimes of every file
+//! with the workspace and re-reads the ones that changed. It also starts a
+//! filesystem watcher, so later searches only re-check the paths it reported,
+//! whether they were changed by the edit tools, a shell command or the user.
+//! If the watcher cannot be started or loses events, searches fall back to
+//! the full comparison.
+//!
+//! One index per workspace root is stored at `.tycode/index/search.idx`. A
+//! missing or unreadable index is rebuilt; a workspace larger than the
+//! configured byte budget is not indexed at all.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, warn};
+
+use crate::file::access::FileAccessManager;
+use crate::file::cache::FileStamp;
+
+const MAGIC: &[u8] = b"tycode-search-index 1\n";
+
+/// Files larger than this are not indexed and are always scanned.
+pub const MAX_INDEXED_FILE_BYTES: u64 = 1024 * 1024;
+
+/// More changed paths than this between two searches are handled by a full
+/// comparison instead of one by one.
+const MAX_PENDING_CHANGES: usize = 1000;
+
+pub fn index_dir(root: &Path) -> PathBuf {
+    root.join(".tycode").join("index")
+}
+
+/// Whether `path` is inside the workspace's `.tycode` directory (the index
+/// itself, audit logs, saved tool calls), which is never searched.
+pub fn is_tycode_path(root: &Path, path: &Path) -> bool {
+    path.starts_with(root.join(".tycode"))
+}
+
+struct IndexedFile {
+    stamp: FileStamp,
+    /// Sorted, deduplicated trigrams, or None for files too large to index.
+    trigrams: Option<Vec<u32>>,
+}
+
+/// Paths reported by the watcher since the last search.
+#[derive(Default)]
+struct PendingChanges {
+    paths: HashSet<PathBuf>,
+    /// Set when events were lost or too many arrived to track one by one.
+    rescan: bool,
+}
+
+impl PendingChanges {
+    fn record(&mut self, event: notify::Result<notify::Event>) {
+        match event {
+            Ok(event) if !event.need_rescan() => {
+                self.paths.extend(event.paths);
+                if self.paths.len() > MAX_PENDING_CHANGES {
+                    self.paths.clear();
+                    self.rescan = true;
+                }
+            }
+            Ok(_) => self.rescan = true,
+            Err(e) => {
+                debug!(?e, "Search index watcher error");
+                self.rescan = true;
+            }
+        }
+    }
+}
+
+struct Watch {
+    // Events stop when the watcher is dropped
+    _watcher: RecommendedWatcher,
+    pending: Arc<Mutex<PendingChanges>>,
+}
+
+pub struct SearchIndex {
+    root: PathBuf,
+    /// Keyed by path relative to `root`.
+    files: HashMap<PathBuf, IndexedFile>,
+    /// Started by the first refresh; None until then or if watching failed.
+    watch: Option<Watch>,
+}
+
+impl SearchIndex {
+    /// Loads the index stored under `root`, starting empty if there is none
+    /// or it cannot be read.
+    pub fn load(root: &Path) -> Self {
+        let path = index_dir(root).join("search.idx");
+        let files = match std::fs::read(&path) {
+            Ok(bytes) => decode(&bytes).unwrap_or_else(|e| {
+                warn!(?e, "Discarding unreadable search index {}", path.display());
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        Self {
+            root: root.to_path_buf(),
+            files,
+            watch: None,
+        }
+    }
+
+    /// Brings the index up to date and saves it: only the paths the watcher
+    /// reported are re-checked, or every file when there is no watcher yet
+    /// or it lost track. Fails if the indexed content exceeds `max_bytes`.
+    pub async fn refresh(
+        &mut self,
+        file_manager: &FileAccessManager,
+        max_bytes: u64,
+    ) -> Result<()> {
+        let changed = match self.take_pending() {
+            Some(paths) => {
+                let changed = self.update_paths(file_manager, paths).await?;
+                let indexed_bytes: u64 = self
+                    .files
+                    .values()
+                    .filter(|file| file.trigrams.is_some())
+                    .map(|file| file.stamp.len)
+                    .sum();
+                if indexed_bytes > max_bytes {
+                    bail!(
+                        "Workspace {} has more than {max_bytes} bytes to index",
+                        self.root.display()
+                    );
+                }
+                changed
+            }
+            None => {
+                // Watch first so changes made during the comparison are not lost
+                self.start_watching();
+                self.full_refresh(file_manager, max_bytes).await?
+            }
+        };
+
+        if changed > 0 {
+            debug!(root = %self.root.display(), changed, "Updated search index");
+            self.save().await?;
+        }
+        Ok(())
+    }
+
+    /// Paths reported since the last call, or None if every file has to be
+    /// compared instead.
+    fn take_pending(&mut self) -> Option<HashSet<PathBuf>> {
+        let watch = self.watch.as_ref()?;
+        let mut pending = watch.pending.lock().unwrap();
+        if pending.rescan {
+            *pending = PendingChanges::default();
+            return None;
+        }
+        Some(std::mem::take(&mut pending.paths))
+    }
+
+    fn start_watching(&mut self) {
+        if self.watch.is_some() {
+            return;
+        }
+        let pending = Arc::new(Mutex::new(PendingChanges::default()));
+        let sink = pending.clone();
+        let watcher = notify::recommended_watcher(move |event| sink.lock().unwrap().record(event))
+            .and_then(|mut watcher| {
+                watcher.watch(&self.root, RecursiveMode::Recursive)?;
+                Ok(watcher)
+            });
+        match watcher {
+            Ok(watcher) => {
+                self.watch = Some(Watch {
+                    _watcher: watcher,
+                    pending,
+                })
+            }
+            Err(e) => warn!(
+                ?e,
+                "Cannot watch {}, comparing every file before each search",
+                self.root.display()
+            ),
+        }
+    }
+
+    /// Re-checks just `paths` and anything below them: new and modified
+    /// files are (re-)indexed, deleted or now-ignored ones dropped. Returns
+    /// the number of entries that changed.
+    async fn update_paths(
+        &mut self,
+        file_manager: &FileAccessManager,
+        paths: HashSet<PathBuf>,
+    ) -> Result<usize> {
+        let paths: Vec<PathBuf> = paths
+            .into_iter()
+            .filter(|path| path.starts_with(&self.root) && !is_tycode_path(&self.root, path))
+            .collect();
+        if paths.is_empty() {
+            return Ok(0);
+        }
+
+        let listed: HashSet<PathBuf> = file_manager
+            .filter_listed(&self.root.to_string_lossy(), &paths)
+            .await?
+            .into_iter()
+            .filter(|path| !is_tycode_path(&self.root, path))
+            .collect();
+
+        let before = self.files.len();
+        let root = &self.root;
+        self.files.retain(|relative, _| {
+            let path = root.join(relative);
+            listed.contains(&path) || !paths.iter().any(|changed| path.starts_with(changed))
+        });
+        let mut changed = before - self.files.len();
+
+        for path in listed {
+            let Ok(relative) = path.strip_prefix(&self.root).map(Path::to_path_buf) else {
+                continue;
+            };
+            let Some(stamp) = tokio::fs::metadata(&path)
+                .await
+                .ok()
+                .and_then(|metadata| FileStamp::of(&metadata))
+            else {
+                self.files.remove(&relative);
+                continue;
+            };
+            if self
+                .files
+                .get(&relative)
+                .is_some_and(|entry| entry.stamp == stamp)
+            {
+                continue;
+            }
+            let entry = index_file(file_manager, &path, stamp).await;
+            self.files.insert(relative, entry);
+            changed += 1;
+        }
+        Ok(changed)
+    }
+
+    /// Compares every file in the workspace with the index, re-indexing the
+    /// ones whose size or mtime changed and dropping deleted ones. Returns
+    /// the number of entries that changed. Stops early once the indexed
+    /// content exceeds `max_bytes`.
+    async fn full_refresh(
+        &mut self,
+        file_manager: &FileAccessManager,
+        max_bytes: u64,
+    ) -> Result<usize> {
+        let listed = file_manager
+            .list_all_files_recursive(&self.root.to_string_lossy(), None)
+            .await?;
+
+        let mut seen = HashMap::with_capacity(listed.len());
+        let mut changed = 0usize;
+        let mut indexed_bytes = 0u64;
+        for path in listed {
+            if is_tycode_path(&self.root, &path) {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&self.root).map(Path::to_path_buf) else {
+                continue;
+            };
+            let Some(stamp) = tokio::fs::metadata(&path)
+                .await
+                .ok()
+                .and_then(|metadata| FileStamp::of(&metadata))
+            else {
+                continue;
+            };
+
+            let entry = match self.files.remove(&relative) {
+                Some(entry) if entry.stamp == stamp => entry,
+                _ => {
+                    changed += 1;
+                    index_file(file_manager, &path, stamp).await
+                }
+            };
+
+            if entry.trigrams.is_some() {
+                indexed_bytes += stamp.len;
+                if indexed_bytes > max_bytes {
+                    bail!(
+                        "Workspace {} has more than {max_bytes} bytes to index",
+                        self.root.display()
+                    );
+                }
+            }
+            seen.insert(relative, entry);
+        }
+
+        let removed = self.files.len();
+        self.files = seen;
+        Ok(changed + removed)
+    }
+
+    /// Absolute paths of the files that may contain all of `literals`,
+    /// sorted. With no usable literals every file is a candidate.
+    pub fn candidates(&self, literals: &[String]) -> Vec<PathBuf> {
+        let mut wanted: Vec<u32> = literals.iter().flat_map(|l| trigrams(l)).collect();
+        wanted.sort_unstable();
+        wanted.dedup();
+
+        let mut paths: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(_, file)| match &file.trigrams {
+                Some(have) => wanted.iter().all(|t| have.binary_search(t).is_ok()),
+                None => true,
+            })
+            .map(|(relative, _)| self.root.join(relative))
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    async fn save(&self) -> Result<()> {
+        let dir = index_dir(&self.root);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join("search.idx");
+        tokio::fs::write(&path, encode(&self.files))
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+async fn index_file(
+    file_manager: &FileAccessManager,
+    path: &Path,
+    stamp: FileStamp,
+) -> IndexedFile {
+    let trigrams = if stamp.len > MAX_INDEXED_FILE_BYTES {
+        None
+    } else {
+        // Unreadable (e.g. binary) files can never match
+        let content = file_manager
+            .read_file(&path.to_string_lossy())
+            .await
+            .unwrap_or_default();
+        Some(trigrams(&content))
+    };
+    IndexedFile { stamp, trigrams }
+}
+
+/// Trigrams of the ASCII-lowercased bytes of `text`. Trigrams containing
+/// non-ASCII bytes are skipped since case-insensitive matching can change
+/// their encoding.
+fn trigrams(text: &str) -> Vec<u32> {
+    let mut trigrams: Vec<u32> = text
+        .as_bytes()
+        .windows(3)
+        .filter(|w| w.is_ascii())
+        .map(|w| {
+            let [a, b, c] = [w[0], w[1], w[2]].map(|b| b.to_ascii_lowercase() as u32);
+            (a << 16) | (b << 8) | c
+        })
+        .collect();
+    trigrams.sort_unstable();
+    trigrams.dedup();
+    trigrams
+}
+
+/// Literal strings that every match of `pattern` must contain, used to pick
+/// candidate files. Conservative: anything inside groups, classes,
+/// alternations or optional repetitions is skipped, and patterns with inline
+/// flags yield nothing (so every file is scanned).
+pub fn required_literals(pattern: &str) -> Vec<String> {
+    if pattern.contains("(?") || has_unescaped(pattern, '|') {
+        return Vec::new();
+    }
+
+    let mut literals = Vec::new();
+    let mut run = String::new();
+    let mut depth = 0usize;
+    let mut chars = pattern.chars().peekable();
+
+    fn flush(run: &mut String, literals: &mut Vec<String>) {
+        if run.len() >= 3 {
+            literals.push(std::mem::take(run));
+        }
+        run.clear();
+    }
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped.is_ascii_punctuation() && depth == 0 => run.push(escaped),
+                Some(escaped) if escaped.is_ascii_punctuation() => {}
+                Some(_) => {
+                    // \d, \x41, \p{L}, ...: skip the escape's arguments too
+                    flush(&mut run, &mut literals);
+                    while chars.peek().is_some_and(char::is_ascii_alphanumeric) {
+                        chars.next();
+                    }
+                    if chars.peek() == Some(&'{') {
+                        for c in chars.by_ref() {
+                            if c == '}' {
+                                break;
+                            }
+                        }
+                    }
+                }
+                None => {}
+            },
+            '[' => {
+                flush(&mut run, &mut literals);
+                skip_class(&mut chars);
+            }
+            '(' => {
+                flush(&mut run, &mut literals);
+                depth += 1;
+            }
+            ')' => {
+                flush(&mut run, &mut literals);
+                depth = depth.saturating_sub(1);
+            }
+            '?' | '*' | '{' => {
+                // The preceding character may be absent
+                run.pop();
+                flush(&mut run, &mut literals);
+                if c == '{' {
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            break;
+                        }
+                    }
+                }
+            }
+            '+' | '.' | '^' | '$' => flush(&mut run, &mut literals),
+            _ if depth == 0 => run.push(c),
+            _ => {}
+        }
+    }
+    flush(&mut run, &mut literals);
+    literals
+}
+
+fn has_unescaped(pattern: &str, target: char) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if c == target {
+            return true;
+        }
+    }
+    false
+}
+
+/// Skips past the `]` closing a character class, including nested classes
+/// like `[[:alpha:]]`.
+fn skip_class(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    let mut depth = 1;
+    // A `]` (after an optional `^`) right after `[` is a literal
+    if chars.peek() == Some(&'^') {
+        chars.next();
+    }
+    if chars.peek() == Some(&']') {
+        chars.next();
+    }
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn encode(files: &HashMap<PathBuf, IndexedFile>) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    let entries: Vec<_> = files
+        .iter()
+        .filter_map(|(path, file)| Some((path.to_str()?, file)))
+        .collect();
+    out.extend((entries.len() as u32).to_le_bytes());
+    for (path, file) in entries {
+        let modified = file
+            .stamp
+            .modified
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        out.extend((path.len() as u32).to_le_bytes());
+        out.extend(path.as_bytes());
+        out.extend(modified.as_secs().to_le_bytes());
+        out.extend(modified.subsec_nanos().to_le_bytes());
+        out.extend(file.stamp.len.to_le_bytes());
+        match &file.trigrams {
+            Some(trigrams) => {
+                out.push(1);
+                out.extend((trigrams.len() as u32).to_le_bytes());
+                for trigram in trigrams {
+                    out.extend(trigram.to_le_bytes());
+                }
+            }
+            None => out.push(0),
+        }
+    }
+    out
+}
+
+fn decode(bytes: &[u8]) -> Result<HashMap<PathBuf, IndexedFile>> {
+    let Some(mut rest) = bytes.strip_prefix(MAGIC) else {
+        bail!("Unknown search index format");
+    };
+
+    fn take<'a>(rest: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+        if rest.len() < n {
+            bail!("Truncated search index");
+        }
+        let (head, tail) = rest.split_at(n);
+        *rest = tail;
+        Ok(head)
+    }
+    fn u32_at(rest: &mut &[u8]) -> Result<u32> {
+        Ok(u32::from_le_bytes(take(rest, 4)?.try_into()?))
+    }
+    fn u64_at(rest: &mut &[u8]) -> Result<u64> {
+        Ok(u64::from_le_bytes(take(rest, 8)?.try_into()?))
+    }
+
+    let count = u32_at(&mut rest)?;
+    let mut files = HashMap::with_capacity(count as usize);
+    for _ in 0..count {
+        let path_len = u32_at(&mut rest)? as usize;
+        let path = PathBuf::from(std::str::from_utf8(take(&mut rest, path_len)?)?);
+        let secs = u64_at(&mut rest)?;
+        let nanos = u32_at(&mut rest)?;
+        let len = u64_at(&mut rest)?;
+        let trigrams = match take(&mut rest, 1)?[0] {
+            0 => None,
+            _ => {
+                let n = u32_at(&mut rest)? as usize;
+                let mut trigrams = Vec::with_capacity(n);
+                for _ in 0..n {
+                    trigrams.push(u32_at(&mut rest)?);
+                }
+                Some(trigrams)
+            }
+        };
+        let stamp = FileStamp {
+            modified: SystemTime::UNIX_EPOCH + Duration::new(secs, nanos),
+            len,
+        };
+        files.insert(path, IndexedFile { stamp, trigrams });
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_literals_skip_optional_parts() {
+        assert_eq!(required_literals("fn parse_args"), vec!["fn parse_args"]);
+        assert_eq!(
+            required_literals(r"impl\s+Display"),
+            vec!["impl", "Display"]
+        );
+        assert_eq!(required_literals(r"foo\.bar"), vec!["foo.bar"]);
+        assert_eq!(required_literals("colou?r_name"), vec!["colo", "r_name"]);
+        assert_eq!(required_literals("abcd{0,2}xyz"), vec!["abc", "xyz"]);
+        assert_eq!(required_literals("xab+cde"), vec!["xab", "cde"]);
+        assert_eq!(required_literals("(optional)?tail"), vec!["tail"]);
+        assert_eq!(required_literals("[[:alpha:]]word"), vec!["word"]);
+        assert_eq!(required_literals(r"\x41BC"), Vec::<String>::new());
+        assert!(required_literals("foo|bar").is_empty());
+        assert!(required_literals("(?i)needle").is_empty());
+    }
+
+    #[test]
+    fn encode_round_trips() {
+        let mut files = HashMap::new();
+        files.insert(
+            PathBuf::from("src/main.rs"),
+            IndexedFile {
+                stamp: FileStamp {
+                    modified: UNIX_EPOCH + Duration::new(1_700_000_000, 42),
+                    len: 12,
+                },
+                trigrams: Some(trigrams("fn main() {}")),
+            },
+        );
+        files.insert(
+            PathBuf::from("big.log"),
+            IndexedFile {
+                stamp: FileStamp {
+                    modified: UNIX_EPOCH,
+                    len: MAX_INDEXED_FILE_BYTES + 1,
+                },
+                trigrams: None,
+            },
+        );
+
+        let decoded = decode(&encode(&files)).unwrap();
+        assert_eq!(decoded.len(), 2);
+        let main = &decoded[Path::new("src/main.rs")];
+        assert_eq!(main.stamp, files[Path::new("src/main.rs")].stamp);
+        assert_eq!(main.trigrams, files[Path::new("src/main.rs")].trigrams);
+        assert!(decoded[Path::new("big.log")].trigrams.is_none());
+        assert!(decode(b"garbage").is_err());
+    }
+}
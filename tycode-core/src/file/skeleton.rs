@@ -0,0 +1,235 @@
+//! Condenses oversized files so they fit a byte budget while keeping their
+//! structure visible.
+//!
+//! Source files in languages we recognise are reduced to a "skeleton": the
+//! leading header comments, imports, and declaration lines (function, type and
+//! impl signatures), with bodies dropped. This uses simple per-language line
+//! prefixes rather than a real parser, so multi-line signatures keep only their
+//! first line. Anything else falls back to keeping the head and tail.
+
+use std::path::Path;
+
+/// How many leading comment lines (license headers, module docs) are kept.
+const MAX_HEADER_LINES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condensed {
+    /// Imports and declaration lines only.
+    Skeleton,
+    /// The start and end of the file with the middle omitted.
+    HeadTail,
+}
+
+struct LanguageSpec {
+    /// Prefixes stripped before matching, e.g. visibility and `async`.
+    modifiers: &'static [&'static str],
+    /// Line prefixes (after modifiers) that are kept.
+    keep: &'static [&'static str],
+    comments: &'static [&'static str],
+}
+
+const RUST: LanguageSpec = LanguageSpec {
+    modifiers: &[
+        "pub ",
+        "pub(crate) ",
+        "pub(super) ",
+        "async ",
+        "unsafe ",
+        "extern \"C\" ",
+        "default ",
+    ],
+    keep: &[
+        "use ",
+        "mod ",
+        "extern crate ",
+        "fn ",
+        "struct ",
+        "enum ",
+        "trait ",
+        "impl ",
+        "impl<",
+        "type ",
+        "const ",
+        "static ",
+        "macro_rules! ",
+    ],
+    comments: &["//", "/*", "*", "#!["],
+};
+
+const PYTHON: LanguageSpec = LanguageSpec {
+    modifiers: &["async "],
+    keep: &["import ", "from ", "def ", "class ", "@"],
+    comments: &["#", "\"\"\"", "'''"],
+};
+
+const TYPESCRIPT: LanguageSpec = LanguageSpec {
+    modifiers: &["export ", "default ", "async ", "declare ", "abstract "],
+    keep: &[
+        "import ",
+        "function ",
+        "function* ",
+        "class ",
+        "interface ",
+        "type ",
+        "enum ",
+        "namespace ",
+    ],
+    comments: &["//", "/*", "*"],
+};
+
+const GO: LanguageSpec = LanguageSpec {
+    modifiers: &[],
+    keep: &["package ", "import ", "func ", "type "],
+    comments: &["//", "/*", "*"],
+};
+
+fn language_for(path: &Path) -> Option<&'static LanguageSpec> {
+    let extension = path.extension()?.to_str()?;
+    match extension {
+        "rs" => Some(&RUST),
+        "py" | "pyi" => Some(&PYTHON),
+        "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" => Some(&TYPESCRIPT),
+        "go" => Some(&GO),
+        _ => None,
+    }
+}
+
+/// Shrinks `content` to roughly `max_bytes`. Returns None when the content
+/// already fits.
+pub fn condense(path: &Path, content: &str, max_bytes: usize) -> Option<(String, Condensed)> {
+    if content.len() <= max_bytes {
+        return None;
+    }
+
+    match language_for(path) {
+        Some(spec) => {
+            let skeleton = skeletonize(spec, content);
+            Some((head_tail(&skeleton, max_bytes), Condensed::Skeleton))
+        }
+        None => Some((head_tail(content, max_bytes), Condensed::HeadTail)),
+    }
+}
+
+fn skeletonize(spec: &LanguageSpec, content: &str) -> String {
+    let mut output = String::new();
+    let mut lines = content.lines().peekable();
+
+    let mut header_lines = 0;
+    while let Some(line) = lines.peek() {
+        let trimmed = line.trim_start();
+        let is_header = trimmed.is_empty() || spec.comments.iter().any(|c| trimmed.starts_with(c));
+        if !is_header || header_lines == MAX_HEADER_LINES {
+            break;
+        }
+        output.push_str(line);
+        output.push('\n');
+        header_lines += 1;
+        lines.next();
+    }
+
+    for line in lines {
+        if is_structural(spec, line.trim_start()) {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn is_structural(spec: &LanguageSpec, mut line: &str) -> bool {
+    loop {
+        if spec.keep.iter().any(|k| line.starts_with(k)) {
+            return true;
+        }
+        match spec.modifiers.iter().find_map(|m| line.strip_prefix(m)) {
+            Some(rest) => line = rest,
+            None => return false,
+        }
+    }
+}
+
+/// Keeps about two thirds of the budget from the start and one third from the
+/// end, cut at line boundaries where possible.
+pub fn head_tail(content: &str, max_bytes: usize) -> String {
+    if content.len() <= max_bytes {
+        return content.to_string();
+    }
+
+    let mut head_end = floor_char_boundary(content, max_bytes * 2 / 3);
+    if let Some(newline) = content[..head_end].rfind('\n') {
+        head_end = newline + 1;
+    }
+
+    let mut tail_start = ceil_char_boundary(content, content.len() - max_bytes / 3);
+    if let Some(newline) = content[tail_start..].find('\n') {
+        tail_start += newline + 1;
+    }
+    let tail_start = tail_start.max(head_end);
+
+    format!(
+        "{}\n... [{} bytes omitted] ...\n{}",
+        &content[..head_end],
+        tail_start - head_end,
+        &content[tail_start..]
+    )
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_skeleton_keeps_imports_and_signatures() {
+        let source = "\
+//! Module docs
+use std::fmt;
+
+pub struct Widget {
+    size: usize,
+}
+
+impl Widget {
+    pub async fn grow(&mut self) {
+        self.size += 1;
+        let filler = \"filler filler filler filler filler filler\";
+    }
+}
+";
+        let (skeleton, kind) = condense(Path::new("widget.rs"), source, 120).unwrap();
+        assert_eq!(kind, Condensed::Skeleton);
+        assert!(skeleton.contains("//! Module docs"), "{skeleton}");
+        assert!(skeleton.contains("use std::fmt;"), "{skeleton}");
+        assert!(skeleton.contains("pub struct Widget {"), "{skeleton}");
+        assert!(skeleton.contains("    pub async fn grow"), "{skeleton}");
+        assert!(!skeleton.contains("filler"), "{skeleton}");
+    }
+
+    #[test]
+    fn unknown_types_keep_head_and_tail() {
+        let content: String = (0..100).map(|i| format!("line {i}\n")).collect();
+        let (condensed, kind) = condense(Path::new("notes.txt"), &content, 100).unwrap();
+        assert_eq!(kind, Condensed::HeadTail);
+        assert!(condensed.starts_with("line 0\n"), "{condensed}");
+        assert!(condensed.ends_with("line 99\n"), "{condensed}");
+        assert!(condensed.contains("bytes omitted"), "{condensed}");
+    }
+
+    #[test]
+    fn small_files_are_untouched() {
+        assert!(condense(Path::new("a.rs"), "fn main() {}", 100).is_none());
+    }
+}
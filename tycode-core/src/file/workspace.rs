@@ -2,6 +2,8 @@ use std::path::{Component, Path, PathBuf};
 
 use anyhow::{bail, Context};
 
+use super::resolver;
+
 /// Configured workspace roots with real-path containment checks.
 #[derive(Debug, Clone)]
 pub struct WorkspacePaths {
@@ -35,8 +37,9 @@ impl WorkspacePaths {
         self.roots.clone()
     }
 
+    /// Resolves an absolute (or `~`) path inside one of the roots.
     pub fn resolve(&self, path_str: &str) -> anyhow::Result<PathBuf> {
-        let path = PathBuf::from(path_str);
+        let path = resolver::expand_home(path_str);
         if !path.is_absolute() {
             bail!("Path must be absolute and inside a workspace root: {path_str}");
         }
@@ -99,6 +102,36 @@ impl WorkspacePaths {
     fn containing_root(&self, path: &Path) -> Option<&PathBuf> {
         self.roots.iter().find(|root| path.starts_with(root))
     }
+
+    /// See `resolver::display_path`.
+    pub fn display(&self, path: &Path) -> String {
+        resolver::display_path(path)
+    }
+}
+
+/// Workspace root to use when none was configured: the nearest ancestor of
+/// `start` (inclusive) containing a `.git` or `.tycode` directory, falling
+/// back to `start` itself. The `.tycode` directory in the home directory
+/// holds global settings and does not mark a workspace.
+pub fn detect_workspace_root(start: &Path) -> PathBuf {
+    let home = dirs::home_dir();
+    let detected = start.ancestors().find(|dir| {
+        dir.join(".git").exists() || (Some(*dir) != home.as_deref() && dir.join(".tycode").is_dir())
+    });
+
+    match detected {
+        Some(root) => {
+            tracing::info!(root = %root.display(), "Detected workspace root");
+            root.to_path_buf()
+        }
+        None => {
+            tracing::info!(
+                root = %start.display(),
+                "No .git or .tycode directory found, using current directory as workspace root"
+            );
+            start.to_path_buf()
+        }
+    }
 }
 
 fn normalize_absolute(path: &Path) -> anyhow::Result<PathBuf> {
@@ -215,6 +248,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn detects_nearest_git_ancestor() -> anyhow::Result<()> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path().join("repo");
+        let nested = repo.join("crates").join("core");
+        fs::create_dir_all(&nested)?;
+        fs::create_dir(repo.join(".git"))?;
+
+        assert_eq!(repo, detect_workspace_root(&nested));
+        assert_eq!(repo, detect_workspace_root(&repo));
+        Ok(())
+    }
+
+    #[test]
+    fn detects_tycode_marker_and_falls_back_to_start() -> anyhow::Result<()> {
+        let temp = tempfile::tempdir()?;
+        let project = temp.path().join("project");
+        let nested = project.join("src");
+        fs::create_dir_all(&nested)?;
+        fs::create_dir(project.join(".tycode"))?;
+
+        assert_eq!(project, detect_workspace_root(&nested));
+
+        let bare = temp.path().join("bare");
+        fs::create_dir(&bare)?;
+        assert_eq!(bare, detect_workspace_root(&bare));
+        Ok(())
+    }
+
     #[cfg(unix)]
     #[test]
     fn rejects_symlink_parent_escape_for_new_file() -> anyhow::Result<()> {
@@ -1,10 +1,11 @@
-use super::EventFormatter;
+use super::{retry_budget_warning, thinking_suffix, EventFormatter};
 use crate::ai::model::Model;
 use crate::ai::TokenUsage;
 use crate::chat::events::{ChatMessage, ToolExecutionResult, ToolRequest, ToolRequestType};
 use crate::chat::ModelInfo;
 use crate::modules::task_list::{TaskList, TaskStatus};
 use std::io::Write;
+use std::time::Duration;
 
 #[derive(Clone, Debug)]
 pub enum MessageType {
@@ -306,9 +307,12 @@ impl EventFormatter for CompactFormatter {
         } else {
             error.to_string()
         };
+        let budget = retry_budget_warning(attempt, max_retries)
+            .map(|warning| format!(" ({warning})"))
+            .unwrap_or_default();
         self.print_compact_bullet(&format!(
-            "⟳ Retry {}/{}: {}",
-            attempt, max_retries, error_preview
+            "⟳ Retry {}/{}{}: {}",
+            attempt, max_retries, budget, error_preview
         ));
     }
 
@@ -453,7 +457,7 @@ impl EventFormatter for CompactFormatter {
         self.last_tool_request = None;
     }
 
-    fn print_thinking(&mut self) {
+    fn print_thinking(&mut self, elapsed: Option<Duration>, message: Option<&str>) {
         if self.typing_state {
             let spinner = self.get_spinner_char();
             let text = if let Some(ref tool_request) = self.last_tool_request {
@@ -461,7 +465,8 @@ impl EventFormatter for CompactFormatter {
             } else {
                 format!("{} Thinking...", spinner)
             };
-            self.print_compact_bullet(&text);
+            let suffix = thinking_suffix(elapsed, message);
+            self.print_compact_bullet(&format!("{text}{suffix}"));
             self.thinking_shown = true;
         }
     }
@@ -473,19 +478,18 @@ impl EventFormatter for CompactFormatter {
             .iter()
             .find(|t| matches!(t.status, TaskStatus::InProgress))
         {
-            let completed = task_list
-                .tasks
-                .iter()
-                .filter(|t| matches!(t.status, TaskStatus::Completed))
-                .count();
-            let total = task_list.tasks.len();
+            let progress = task_list.progress();
             self.finish_compact_bullet(&format!(
-                "Task {}/{}: {}",
-                completed, total, current_task.description
+                "Task {}/{} ({}%): {}",
+                progress.done, progress.total, progress.percent, current_task.description
             ));
         }
     }
 
+    fn print_tool_summary(&mut self, summary: &str) {
+        self.finish_compact_bullet(&format!("Tools used: {summary}"));
+    }
+
     fn print_stream_start(
         &mut self,
         _message_id: &str,
@@ -503,6 +507,11 @@ impl EventFormatter for CompactFormatter {
         let _ = std::io::stdout().flush();
     }
 
+    fn print_reasoning(&mut self, text: &str) {
+        print!("\r\x1b[2K");
+        self.print_line(&format!("\x1b[90m💭 {text}\x1b[0m"));
+    }
+
     fn print_stream_end(&mut self, message: &ChatMessage) {
         println!();
         if let Some(ref usage) = message.token_usage {
@@ -512,6 +521,11 @@ impl EventFormatter for CompactFormatter {
         }
     }
 
+    fn print_voice_caption(&mut self, text: &str) {
+        print!("\r\x1b[2K\x1b[90m🎤 {text}\x1b[0m");
+        let _ = std::io::stdout().flush();
+    }
+
     fn on_typing_status_changed(&mut self, typing: bool) {
         self.typing_state = typing;
 
@@ -6,6 +6,8 @@ use crate::ai::TokenUsage;
 use crate::chat::events::{ChatMessage, ToolExecutionResult, ToolRequest};
 use crate::chat::ModelInfo;
 use crate::modules::task_list::TaskList;
+use std::io::Write;
+use std::time::Duration;
 
 pub use compact::CompactFormatter;
 pub use verbose::VerboseFormatter;
@@ -38,12 +40,27 @@ pub trait EventFormatter: Send + Sync {
         verbose: bool,
     );
 
-    fn print_thinking(&mut self);
+    /// Redraws the spinner line. `elapsed` is the time spent waiting so far,
+    /// when it should be shown, and `message` an optional reassurance for
+    /// long waits.
+    fn print_thinking(&mut self, elapsed: Option<Duration>, message: Option<&str>);
 
     fn print_task_update(&mut self, task_list: &TaskList);
 
+    /// A completed message's reasoning, shown dimmed before its text.
+    fn print_reasoning(&mut self, text: &str);
+
+    /// End-of-turn recap of the tools used, e.g. `read 3 files, cargo build: ok`.
+    fn print_tool_summary(&mut self, summary: &str) {
+        self.print_system(&format!("Tools used: {summary}"));
+    }
+
     fn on_typing_status_changed(&mut self, _typing: bool) {}
 
+    /// Controls whether tool output is rendered in full (diffs, command
+    /// output) or reduced to one-line summaries.
+    fn set_tool_detail(&mut self, _detailed: bool) {}
+
     fn print_stream_start(
         &mut self,
         _message_id: &str,
@@ -55,13 +72,75 @@ pub trait EventFormatter: Send + Sync {
 
     fn print_stream_delta(&mut self, _message_id: &str, _text: &str) {}
 
+    /// Streamed reasoning text, dimmed so it reads apart from the answer.
+    fn print_reasoning_delta(&mut self, _message_id: &str, text: &str) {
+        print!("\x1b[90m{text}\x1b[0m");
+        let _ = std::io::stdout().flush();
+    }
+
     fn print_stream_end(&mut self, _message: &ChatMessage) {}
 
+    /// Live caption for an in-progress voice transcript; each call replaces
+    /// the previous caption on the same line.
+    fn print_voice_caption(&mut self, _text: &str) {}
+
     fn clone_box(&self) -> Box<dyn EventFormatter>;
 }
 
+/// Warning shown with a retry once most of the retry budget is used, e.g.
+/// "only 2 retries left".
+fn retry_budget_warning(attempt: u32, max_retries: u32) -> Option<String> {
+    let remaining = max_retries.saturating_sub(attempt);
+    if attempt * 5 < max_retries * 4 {
+        return None;
+    }
+    Some(match remaining {
+        0 => "last retry".to_string(),
+        1 => "only 1 retry left".to_string(),
+        n => format!("only {n} retries left"),
+    })
+}
+
+/// Text appended to the spinner line, e.g. ` 12s` or ` (still working... 45s)`.
+fn thinking_suffix(elapsed: Option<Duration>, message: Option<&str>) -> String {
+    match (message, elapsed) {
+        (Some(message), Some(elapsed)) => format!(" ({message} {}s)", elapsed.as_secs()),
+        (Some(message), None) => format!(" ({message})"),
+        (None, Some(elapsed)) => format!(" {}s", elapsed.as_secs()),
+        (None, None) => String::new(),
+    }
+}
+
 impl Clone for Box<dyn EventFormatter> {
     fn clone(&self) -> Self {
         self.clone_box()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::retry_budget_warning;
+
+    #[test]
+    fn retry_budget_warning_starts_at_four_fifths() {
+        assert_eq!(retry_budget_warning(7, 10), None);
+        assert_eq!(
+            retry_budget_warning(8, 10).as_deref(),
+            Some("only 2 retries left")
+        );
+        assert_eq!(
+            retry_budget_warning(9, 10).as_deref(),
+            Some("only 1 retry left")
+        );
+        assert_eq!(retry_budget_warning(10, 10).as_deref(), Some("last retry"));
+    }
+
+    #[test]
+    fn retry_budget_warning_scales_with_the_budget() {
+        assert_eq!(retry_budget_warning(799, 1000), None);
+        assert_eq!(
+            retry_budget_warning(800, 1000).as_deref(),
+            Some("only 200 retries left")
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::EventFormatter;
+use super::{retry_budget_warning, thinking_suffix, EventFormatter};
 use crate::ai::model::Model;
 use crate::ai::TokenUsage;
 use crate::chat::events::{ChatMessage, ToolExecutionResult, ToolRequest, ToolRequestType};
@@ -7,6 +7,11 @@ use crate::modules::task_list::{TaskList, TaskStatus};
 use serde_json::Value;
 use similar::{ChangeTag, TextDiff};
 use std::io::Write;
+use std::time::Duration;
+
+/// Diffs longer than this are cut off with a note; huge rewrites are better
+/// reviewed in an editor than scrolled through in the terminal.
+const MAX_DIFF_LINES: usize = 200;
 
 #[derive(Clone)]
 pub struct VerboseFormatter {
@@ -14,6 +19,7 @@ pub struct VerboseFormatter {
     spinner_state: usize,
     thinking_shown: bool,
     last_tool_request: Option<ToolRequest>,
+    tool_detail: bool,
 }
 
 impl Default for VerboseFormatter {
@@ -29,6 +35,7 @@ impl VerboseFormatter {
             spinner_state: 0,
             thinking_shown: false,
             last_tool_request: None,
+            tool_detail: true,
         }
     }
 
@@ -89,9 +96,27 @@ impl VerboseFormatter {
         let mut diff = diff.unified_diff();
         let unified = diff.context_radius(7);
 
+        let mut printed = 0;
+        let mut omitted = 0;
         for hunk in unified.iter_hunks() {
-            self.print_line(&hunk.header().to_string());
+            if printed >= MAX_DIFF_LINES {
+                omitted += hunk.iter_changes().count();
+                continue;
+            }
+
+            let header = hunk.header().to_string();
+            if use_colors {
+                self.print_line(&format!("\x1b[36m{header}\x1b[0m"));
+            } else {
+                self.print_line(&header);
+            }
             for change in hunk.iter_changes() {
+                if printed >= MAX_DIFF_LINES {
+                    omitted += 1;
+                    continue;
+                }
+                printed += 1;
+
                 let line = change.value().trim_end_matches('\n');
                 match change.tag() {
                     ChangeTag::Equal => self.print_line(&format!(" {line}")),
@@ -112,6 +137,12 @@ impl VerboseFormatter {
                 }
             }
         }
+
+        if omitted > 0 {
+            self.print_system(&format!(
+                "... diff truncated: {omitted} more lines not shown"
+            ));
+        }
     }
 
     fn format_bytes(&self, bytes: usize) -> String {
@@ -226,6 +257,11 @@ impl EventFormatter for VerboseFormatter {
                 attempt, max_retries, error
             ));
         }
+        if let Some(warning) = retry_budget_warning(attempt, max_retries) {
+            self.print_warning(&format!(
+                "Retry budget nearly used ({warning}); the provider may be unavailable"
+            ));
+        }
     }
 
     fn print_tool_request(&mut self, tool_request: &ToolRequest) {
@@ -237,7 +273,9 @@ impl EventFormatter for VerboseFormatter {
                 after,
             } => {
                 self.print_system(&format!("📝 Modifying file {file_path}"));
-                self.print_file_diff(before, after, self.use_colors);
+                if self.tool_detail {
+                    self.print_file_diff(before, after, self.use_colors);
+                }
             }
             ToolRequestType::RunCommand {
                 command,
@@ -316,6 +354,11 @@ impl EventFormatter for VerboseFormatter {
                     self.print_line(&format!("  Exit Code: {exit_code}"));
                 }
 
+                if !self.tool_detail {
+                    self.last_tool_request = None;
+                    return;
+                }
+
                 if !stdout.is_empty() {
                     if self.use_colors {
                         self.print_line("  \x1b[32mStdout:\x1b[0m");
@@ -356,6 +399,7 @@ impl EventFormatter for VerboseFormatter {
             ToolExecutionResult::Error {
                 short_message,
                 detailed_message,
+                ..
             } => {
                 let message = if verbose {
                     detailed_message
@@ -388,7 +432,7 @@ impl EventFormatter for VerboseFormatter {
         self.last_tool_request = None;
     }
 
-    fn print_thinking(&mut self) {
+    fn print_thinking(&mut self, elapsed: Option<Duration>, message: Option<&str>) {
         let spinner = self.get_spinner_char();
         let text = if let Some(ref tool_request) = self.last_tool_request {
             match &tool_request.tool_type {
@@ -420,6 +464,7 @@ impl EventFormatter for VerboseFormatter {
         } else {
             "Thinking...".to_string()
         };
+        let text = format!("{text}{}", thinking_suffix(elapsed, message));
 
         if self.use_colors {
             print!("\r\x1b[2K\x1b[36m{} {}\x1b[0m", spinner, text);
@@ -456,6 +501,28 @@ impl EventFormatter for VerboseFormatter {
         let _ = std::io::stdout().flush();
     }
 
+    fn print_reasoning_delta(&mut self, _message_id: &str, text: &str) {
+        if self.use_colors {
+            print!("\x1b[90m{text}\x1b[0m");
+        } else {
+            print!("{text}");
+        }
+        let _ = std::io::stdout().flush();
+    }
+
+    fn print_reasoning(&mut self, text: &str) {
+        if self.use_colors {
+            self.print_line(&format!("\x1b[90m💭 {text}\x1b[0m"));
+        } else {
+            self.print_line(&format!("💭 {text}"));
+        }
+    }
+
+    fn print_voice_caption(&mut self, text: &str) {
+        print!("\r\x1b[2K\x1b[90m🎤 {text}\x1b[0m");
+        let _ = std::io::stdout().flush();
+    }
+
     fn print_stream_end(&mut self, message: &ChatMessage) {
         println!();
         if let Some(ref usage) = message.token_usage {
@@ -491,7 +558,16 @@ impl EventFormatter for VerboseFormatter {
     }
 
     fn print_task_update(&mut self, task_list: &TaskList) {
-        self.print_system("Task List:");
+        let progress = task_list.progress();
+        let failed = if progress.failed > 0 {
+            format!(", {} failed", progress.failed)
+        } else {
+            String::new()
+        };
+        self.print_system(&format!(
+            "Task List: {}/{} done ({}%){failed}",
+            progress.done, progress.total, progress.percent
+        ));
         for task in &task_list.tasks {
             let (status_text, color_code) = match task.status {
                 TaskStatus::Pending => ("Pending", "\x1b[37m"),
@@ -507,6 +583,10 @@ impl EventFormatter for VerboseFormatter {
         }
     }
 
+    fn set_tool_detail(&mut self, detailed: bool) {
+        self.tool_detail = detailed;
+    }
+
     fn clone_box(&self) -> Box<dyn EventFormatter> {
         Box::new(self.clone())
     }
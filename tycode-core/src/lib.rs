@@ -1,6 +1,7 @@
 pub mod agents;
 pub mod ai;
 pub mod analyzer;
+pub mod audit;
 pub mod chat;
 pub mod file;
 pub mod formatter;
@@ -9,6 +10,7 @@ pub mod module;
 pub mod modules;
 pub mod orchestration;
 pub mod persistence;
+pub mod redact;
 pub mod settings;
 pub mod skills;
 pub mod spawn;
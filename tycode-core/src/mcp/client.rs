@@ -2,7 +2,9 @@ use std::process::Stdio;
 
 use crate::settings::config::McpServerConfig;
 use rmcp::{
-    model::{CallToolRequestParam, CallToolResult, Tool},
+    model::{
+        CallToolRequestParam, CallToolResult, GetPromptRequestParam, GetPromptResult, Prompt, Tool,
+    },
     service::{RunningService, ServiceExt},
     transport::{
         streamable_http_client::{
@@ -32,7 +34,9 @@ impl McpClient {
         info!(client_name = %name, endpoint = %config.display_label(), "Initializing MCP client");
 
         let client_handle = match &config {
-            McpServerConfig::Stdio { command, args, env } => {
+            McpServerConfig::Stdio {
+                command, args, env, ..
+            } => {
                 let cmd = Command::new(command).configure(|c| {
                     c.args(args);
                     c.envs(env.iter());
@@ -54,7 +58,7 @@ impl McpClient {
                     .await
                     .map_err(|e| anyhow::anyhow!("Failed to serve stdio MCP client: {e:?}"))?
             }
-            McpServerConfig::Http { url, headers } => {
+            McpServerConfig::Http { url, headers, .. } => {
                 let http_config = StreamableHttpClientTransportConfig::with_uri(url.as_str());
 
                 let transport = if headers.is_empty() {
@@ -121,6 +125,34 @@ impl McpClient {
             .map_err(|e| anyhow::anyhow!("Failed to call MCP tool '{name}': {e:?}"))
     }
 
+    /// Prompts the server offers. Servers without the prompts capability
+    /// answer with an error, which is treated as offering none.
+    pub async fn list_prompts(&mut self) -> Vec<Prompt> {
+        match self.client_handle.list_prompts(Default::default()).await {
+            Ok(response) => response.prompts,
+            Err(e) => {
+                debug!(client_name = %self.name, error = ?e, "MCP server did not list prompts");
+                Vec::new()
+            }
+        }
+    }
+
+    pub async fn get_prompt(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Map<String, serde_json::Value>,
+    ) -> anyhow::Result<GetPromptResult> {
+        let request = GetPromptRequestParam {
+            name: name.to_string(),
+            arguments: (!arguments.is_empty()).then_some(arguments),
+        };
+
+        self.client_handle
+            .get_prompt(request)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get MCP prompt '{name}': {e:?}"))
+    }
+
     pub async fn close(self) -> anyhow::Result<()> {
         info!(client_name = %self.name, "Closing MCP client");
 
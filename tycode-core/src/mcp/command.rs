@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use chrono::Utc;
 
 use crate::chat::actor::ActorState;
+use crate::chat::commands::parse_command_with_quotes;
 use crate::chat::events::{ChatMessage, MessageSender};
 use crate::module::SlashCommand;
 use crate::settings::config::McpServerConfig;
@@ -20,7 +21,7 @@ impl SlashCommand for McpSlashCommand {
     }
 
     fn usage(&self) -> &'static str {
-        "/mcp [add|remove] [args...]"
+        "/mcp [add|remove|prompts|call] [args...]"
     }
 
     async fn execute(&self, state: &mut ActorState, args: &[&str]) -> Vec<ChatMessage> {
@@ -59,13 +60,24 @@ async fn handle_mcp_command(state: &mut ActorState, parts: &[String]) -> Vec<Cha
             )];
         }
 
+        let tool_counts = state.mcp_manager.tool_counts().await;
         let mut message = String::from("Configured MCP servers:\n\n");
         for (name, config) in &settings.mcp_servers {
+            message.push_str(&format!("  {}:\n", name));
+            let (registered, offered) = tool_counts.get(name).copied().unwrap_or_default();
+            if offered < registered {
+                message.push_str(&format!(
+                    "    Tools: {offered} offered of {registered} (max_mcp_tools)\n"
+                ));
+            } else {
+                message.push_str(&format!("    Tools: {registered}\n"));
+            }
             match config {
-                McpServerConfig::Stdio { command, args, env } => {
+                McpServerConfig::Stdio {
+                    command, args, env, ..
+                } => {
                     message.push_str(&format!(
-                        "  {}:\n    Type: stdio\n    Command: {}\n    Args: {}\n    Env: {}\n\n",
-                        name,
+                        "    Type: stdio\n    Command: {}\n    Args: {}\n    Env: {}\n\n",
                         command,
                         if args.is_empty() {
                             "<none>".to_string()
@@ -82,10 +94,9 @@ async fn handle_mcp_command(state: &mut ActorState, parts: &[String]) -> Vec<Cha
                         }
                     ));
                 }
-                McpServerConfig::Http { url, headers } => {
+                McpServerConfig::Http { url, headers, .. } => {
                     message.push_str(&format!(
-                        "  {}:\n    Type: http\n    URL: {}\n    Headers: {}\n\n",
-                        name,
+                        "    Type: http\n    URL: {}\n    Headers: {}\n\n",
                         url,
                         if headers.is_empty() {
                             "<none>".to_string()
@@ -102,13 +113,169 @@ async fn handle_mcp_command(state: &mut ActorState, parts: &[String]) -> Vec<Cha
     match parts[1].as_str() {
         "add" => handle_mcp_add_command(state, parts).await,
         "remove" => handle_mcp_remove_command(state, parts).await,
+        "prompts" => handle_mcp_prompts_command(state).await,
         _ => vec![create_message(
-            "Usage: /mcp [add|remove] [args...]. Use `/mcp` to list all servers.".to_string(),
+            "Usage: /mcp [add|remove|prompts|call] [args...]. Use `/mcp` to list all servers."
+                .to_string(),
             MessageSender::Error,
         )],
     }
 }
 
+/// Expands into a server-provided prompt, sent as the user's message.
+/// Handled by the actor before slash commands, since it starts a turn.
+pub const PROMPT_COMMAND: &str = "mcp-prompt";
+pub const PROMPT_USAGE: &str = "/mcp-prompt <server> <prompt> [name=value ...]";
+
+pub fn is_prompt_command(input: &str) -> bool {
+    input
+        .strip_prefix('/')
+        .and_then(|command| command.split_whitespace().next())
+        == Some(PROMPT_COMMAND)
+}
+
+/// Splits a `/mcp-prompt` input into the server, the prompt and its
+/// `name=value` arguments.
+fn parse_prompt_command(input: &str) -> Result<(String, String, HashMap<String, String>), String> {
+    let parts = parse_command_with_quotes(input.trim_start_matches('/'));
+    let (Some(server), Some(prompt)) = (parts.get(1), parts.get(2)) else {
+        return Err(format!("Usage: {PROMPT_USAGE}"));
+    };
+
+    let mut arguments = HashMap::new();
+    for argument in &parts[3..] {
+        let Some((name, value)) = argument.split_once('=') else {
+            return Err(format!(
+                "Prompt arguments must be name=value, got '{argument}'. Usage: {PROMPT_USAGE}"
+            ));
+        };
+        arguments.insert(name.to_string(), value.to_string());
+    }
+
+    Ok((server.clone(), prompt.clone(), arguments))
+}
+
+/// Fetches the prompt named by a `/mcp-prompt` input and returns its text.
+/// The text is the user's message as-is; the caller must not dispatch it as a
+/// slash command even if it starts with `/`.
+pub async fn expand_prompt_command(state: &ActorState, input: &str) -> Result<String, String> {
+    let (server, prompt, arguments) = parse_prompt_command(input)?;
+    state
+        .mcp_manager
+        .get_prompt(&server, &prompt, arguments)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Calls an MCP tool directly for debugging. The arguments are raw JSON,
+/// which the quote-aware command parser would mangle, so the actor hands the
+/// whole input here before slash commands are parsed.
+pub const CALL_USAGE: &str = "/mcp call <server> <tool> [json-args]";
+
+pub fn is_call_command(input: &str) -> bool {
+    let mut words = input.split_whitespace();
+    words.next() == Some("/mcp") && words.next() == Some("call")
+}
+
+/// Splits the first whitespace-separated word off `input`.
+fn next_word(input: &str) -> Option<(&str, &str)> {
+    let input = input.trim_start();
+    if input.is_empty() {
+        return None;
+    }
+    Some(input.split_once(char::is_whitespace).unwrap_or((input, "")))
+}
+
+pub async fn handle_call_command(state: &ActorState, input: &str) -> Vec<ChatMessage> {
+    let Some((server, tool, raw_args)) = next_word(input)
+        .and_then(|(_, rest)| next_word(rest))
+        .and_then(|(_, rest)| next_word(rest))
+        .and_then(|(server, rest)| {
+            let (tool, raw_args) = next_word(rest)?;
+            Some((server, tool, raw_args.trim()))
+        })
+    else {
+        return vec![create_message(
+            format!("Usage: {CALL_USAGE}"),
+            MessageSender::Error,
+        )];
+    };
+
+    let arguments = if raw_args.is_empty() {
+        serde_json::Value::Object(Default::default())
+    } else {
+        match serde_json::from_str::<serde_json::Value>(raw_args) {
+            Ok(value) if value.is_object() => value,
+            Ok(_) => {
+                return vec![create_message(
+                    "Tool arguments must be a JSON object".to_string(),
+                    MessageSender::Error,
+                )]
+            }
+            Err(e) => {
+                return vec![create_message(
+                    format!("Invalid JSON arguments: {e}"),
+                    MessageSender::Error,
+                )]
+            }
+        }
+    };
+
+    let result = match state.mcp_manager.call_tool(server, tool, arguments).await {
+        Ok(result) => result,
+        Err(e) => return vec![create_message(e.to_string(), MessageSender::Error)],
+    };
+
+    let raw = serde_json::to_string_pretty(&result)
+        .unwrap_or_else(|e| format!("Failed to serialize the response: {e}"));
+    let (heading, sender) = if result.is_error == Some(true) {
+        ("reported an error", MessageSender::Error)
+    } else {
+        ("returned", MessageSender::System)
+    };
+    vec![create_message(
+        format!("MCP tool '{tool}' on '{server}' {heading}:\n\n```json\n{raw}\n```"),
+        sender,
+    )]
+}
+
+async fn handle_mcp_prompts_command(state: &ActorState) -> Vec<ChatMessage> {
+    let servers = state.mcp_manager.list_prompts().await;
+    if servers.iter().all(|(_, prompts)| prompts.is_empty()) {
+        return vec![create_message(
+            "No connected MCP server offers prompts.".to_string(),
+            MessageSender::System,
+        )];
+    }
+
+    let mut message = String::from("MCP prompts:\n\n");
+    for (server, prompts) in servers {
+        for prompt in prompts {
+            let arguments: Vec<String> = prompt
+                .arguments
+                .unwrap_or_default()
+                .into_iter()
+                .map(|arg| {
+                    if arg.required == Some(true) {
+                        format!("{}=<value>", arg.name)
+                    } else {
+                        format!("[{}=<value>]", arg.name)
+                    }
+                })
+                .collect();
+            message.push_str(&format!(
+                "  /{PROMPT_COMMAND} {server} {} {}\n",
+                prompt.name,
+                arguments.join(" ")
+            ));
+            if let Some(description) = prompt.description.filter(|d| !d.is_empty()) {
+                message.push_str(&format!("    {description}\n"));
+            }
+        }
+    }
+    vec![create_message(message, MessageSender::System)]
+}
+
 const ADD_USAGE: &str = "Usage:\n  \
     /mcp add <name> <command> [--args \"args...\"] [--env \"KEY=VALUE\"]\n  \
     /mcp add <name> --url <url> [--header \"Name: Value\"]";
@@ -173,7 +340,12 @@ fn parse_stdio_config(parts: &[String]) -> Result<McpServerConfig, String> {
         }
     }
 
-    Ok(McpServerConfig::Stdio { command, args, env })
+    Ok(McpServerConfig::Stdio {
+        command,
+        args,
+        env,
+        startup_timeout_secs: None,
+    })
 }
 
 fn parse_http_config(parts: &[String]) -> Result<McpServerConfig, String> {
@@ -199,7 +371,11 @@ fn parse_http_config(parts: &[String]) -> Result<McpServerConfig, String> {
         }
     }
 
-    Ok(McpServerConfig::Http { url, headers })
+    Ok(McpServerConfig::Http {
+        url,
+        headers,
+        startup_timeout_secs: None,
+    })
 }
 
 async fn handle_mcp_add_command(state: &mut ActorState, parts: &[String]) -> Vec<ChatMessage> {
@@ -243,7 +419,18 @@ async fn handle_mcp_add_command(state: &mut ActorState, parts: &[String]) -> Vec
     }
 
     let connection_status = match state.mcp_manager.add_server(name.clone(), config).await {
-        Ok(()) => "\nServer connected successfully.".to_string(),
+        Ok(()) => {
+            let dropped = state.mcp_manager.dropped_tools().await;
+            let mut status = "\nServer connected successfully.".to_string();
+            if !dropped.is_empty() {
+                status.push_str(&format!(
+                    "\nWarning: max_mcp_tools leaves out {} tool(s): {}",
+                    dropped.len(),
+                    dropped.join(", ")
+                ));
+            }
+            status
+        }
         Err(e) => format!(
             "\nWarning: Failed to connect to server: {e:?}. Server will be retried on next session."
         ),
@@ -307,3 +494,44 @@ async fn handle_mcp_remove_command(state: &mut ActorState, parts: &[String]) ->
         MessageSender::System,
     )]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_only_the_prompt_command() {
+        assert!(is_prompt_command("/mcp-prompt docs summarize"));
+        assert!(is_prompt_command("/mcp-prompt"));
+        assert!(!is_prompt_command("/mcp-prompts docs summarize"));
+        assert!(!is_prompt_command("/mcp prompts"));
+        assert!(!is_prompt_command("mcp-prompt docs summarize"));
+    }
+
+    #[test]
+    fn parses_server_prompt_and_arguments() {
+        let (server, prompt, arguments) =
+            parse_prompt_command(r#"/mcp-prompt docs summarize topic="error handling" depth=2"#)
+                .unwrap();
+        assert_eq!(server, "docs");
+        assert_eq!(prompt, "summarize");
+        assert_eq!(arguments.len(), 2);
+        assert_eq!(arguments["topic"], "error handling");
+        assert_eq!(arguments["depth"], "2");
+    }
+
+    #[test]
+    fn argument_values_may_contain_equals() {
+        let (_, _, arguments) = parse_prompt_command("/mcp-prompt docs run query=a=b").unwrap();
+        assert_eq!(arguments["query"], "a=b");
+    }
+
+    #[test]
+    fn rejects_missing_prompt_and_bare_arguments() {
+        let err = parse_prompt_command("/mcp-prompt docs").unwrap_err();
+        assert!(err.contains(PROMPT_USAGE), "{err}");
+
+        let err = parse_prompt_command("/mcp-prompt docs summarize topic").unwrap_err();
+        assert!(err.contains("must be name=value, got 'topic'"), "{err}");
+    }
+}
@@ -8,7 +8,7 @@ use crate::module::PromptComponent;
 use crate::module::SlashCommand;
 use crate::settings::config::{McpServerConfig, Settings};
 use crate::tools::r#trait::SharedTool;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 pub mod client;
 pub mod command;
@@ -28,9 +28,62 @@ pub struct McpToolDef {
     pub server_name: String,
 }
 
+/// `max_mcp_tools` and `mcp_server_priority` from the settings.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ToolLimit {
+    max_tools: Option<usize>,
+    priority: Vec<String>,
+}
+
+impl ToolLimit {
+    fn from_settings(settings: &Settings) -> Self {
+        Self {
+            max_tools: settings.max_mcp_tools,
+            priority: settings.mcp_server_priority.clone(),
+        }
+    }
+
+    /// Indices of the tools to offer, given each tool's server. Tools from
+    /// higher-priority servers are taken first; a server's own tools keep
+    /// their order.
+    fn offered(&self, servers: &[&str]) -> Vec<usize> {
+        let rank = |server: &str| {
+            let position = self.priority.iter().position(|name| name == server);
+            (position.unwrap_or(self.priority.len()), server.to_string())
+        };
+        let mut indices: Vec<usize> = (0..servers.len()).collect();
+        indices.sort_by_cached_key(|&index| rank(servers[index]));
+        indices.truncate(self.max_tools.unwrap_or(usize::MAX));
+        indices.sort_unstable();
+        indices
+    }
+}
+
 pub(crate) struct McpModuleInner {
     pub(crate) clients: HashMap<String, Arc<tokio::sync::Mutex<McpClient>>>,
     pub(crate) tool_defs: Vec<McpToolDef>,
+    limit: ToolLimit,
+}
+
+impl McpModuleInner {
+    /// The tools offered to the model and the tools dropped by the cap.
+    fn split_offered(&self) -> (Vec<&McpToolDef>, Vec<&McpToolDef>) {
+        let servers: Vec<&str> = self
+            .tool_defs
+            .iter()
+            .map(|def| def.server_name.as_str())
+            .collect();
+        let offered = self.limit.offered(&servers);
+        let (mut kept, mut dropped) = (Vec::new(), Vec::new());
+        for (index, def) in self.tool_defs.iter().enumerate() {
+            if offered.binary_search(&index).is_ok() {
+                kept.push(def);
+            } else {
+                dropped.push(def);
+            }
+        }
+        (kept, dropped)
+    }
 }
 
 pub struct McpModule {
@@ -43,12 +96,14 @@ impl McpModule {
             inner: Arc::new(RwLock::new(McpModuleInner {
                 clients: HashMap::new(),
                 tool_defs: Vec::new(),
+                limit: ToolLimit::default(),
             })),
         })
     }
 
     pub async fn from_settings(settings: &Settings) -> anyhow::Result<Arc<Self>> {
         let module = Self::empty();
+        module.inner.write().await.limit = ToolLimit::from_settings(settings);
 
         for (name, config) in &settings.mcp_servers {
             info!(server_name = %name, endpoint = %config.display_label(), "Initializing MCP server");
@@ -66,21 +121,45 @@ impl McpModule {
             );
         }
 
+        let dropped = module.dropped_tools().await;
+        if !dropped.is_empty() {
+            warn!(
+                max_mcp_tools = ?settings.max_mcp_tools,
+                dropped = ?dropped,
+                "MCP servers offer more tools than max_mcp_tools; dropping the lowest-priority ones"
+            );
+        }
+
         Ok(module)
     }
 
     pub async fn add_server(&self, name: String, config: McpServerConfig) -> anyhow::Result<()> {
         debug!(server_name = %name, "Adding MCP server");
 
-        let mut client = McpClient::new(name.clone(), config).await.map_err(|e| {
-            error!(error = ?e, server_name = %name, "Failed to initialize MCP client");
-            anyhow::anyhow!("Failed to initialize MCP server '{name}': {e:?}")
-        })?;
+        // A server that never answers `initialize` or `list_tools` would
+        // otherwise hold up every server after it
+        let timeout = config.startup_timeout();
+        let startup = async {
+            let mut client = McpClient::new(name.clone(), config).await.map_err(|e| {
+                error!(error = ?e, server_name = %name, "Failed to initialize MCP client");
+                anyhow::anyhow!("Failed to initialize MCP server '{name}': {e:?}")
+            })?;
 
-        let mcp_tools = client.list_tools().await.map_err(|e| {
-            error!(error = ?e, server_name = %name, "Failed to list MCP tools");
-            anyhow::anyhow!("Failed to list tools from MCP server '{name}': {e:?}")
-        })?;
+            let mcp_tools = client.list_tools().await.map_err(|e| {
+                error!(error = ?e, server_name = %name, "Failed to list MCP tools");
+                anyhow::anyhow!("Failed to list tools from MCP server '{name}': {e:?}")
+            })?;
+            anyhow::Ok((client, mcp_tools))
+        };
+        let (client, mcp_tools) = tokio::time::timeout(timeout, startup)
+            .await
+            .map_err(|_| {
+                warn!(server_name = %name, timeout_secs = timeout.as_secs(), "MCP server did not start in time; skipping it");
+                anyhow::anyhow!(
+                    "MCP server '{name}' did not start within {}s; raise startup_timeout_secs if it needs longer",
+                    timeout.as_secs()
+                )
+            })??;
 
         debug!(server_name = %name, tool_count = mcp_tools.len(), "Found MCP tools");
 
@@ -108,10 +187,151 @@ impl McpModule {
         Ok(())
     }
 
+    /// Closes every client. Clients still borrowed by an in-flight tool call
+    /// are dropped instead, which stops their server process.
+    pub async fn shutdown(&self) {
+        let clients: Vec<_> = self.inner.write().await.clients.drain().collect();
+        for (name, client) in clients {
+            let Ok(client) = Arc::try_unwrap(client) else {
+                continue;
+            };
+            if let Err(e) = client.into_inner().close().await {
+                warn!(server_name = %name, "Failed to close MCP client: {e:?}");
+            }
+        }
+    }
+
     pub async fn get_tool_definitions(&self) -> Vec<McpToolDef> {
         let inner = self.inner.read().await;
         inner.tool_defs.clone()
     }
+
+    /// Calls `tool` on `server` directly, bypassing the model. Tools left
+    /// out by `max_mcp_tools` can still be called.
+    pub async fn call_tool(
+        &self,
+        server: &str,
+        tool: &str,
+        arguments: serde_json::Value,
+    ) -> anyhow::Result<rmcp::model::CallToolResult> {
+        let client = {
+            let inner = self.inner.read().await;
+            let client = inner
+                .clients
+                .get(server)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("No connected MCP server named '{server}'"))?;
+            if !inner
+                .tool_defs
+                .iter()
+                .any(|def| def.server_name == server && def.tool.name == tool)
+            {
+                anyhow::bail!("MCP server '{server}' has no tool named '{tool}'");
+            }
+            client
+        };
+
+        let mut client = client.lock().await;
+        client.call_tool(tool, Some(arguments)).await
+    }
+
+    /// Prompts offered by each connected server, sorted by server name.
+    pub async fn list_prompts(&self) -> Vec<(String, Vec<rmcp::model::Prompt>)> {
+        let clients: Vec<_> = {
+            let inner = self.inner.read().await;
+            inner
+                .clients
+                .iter()
+                .map(|(name, client)| (name.clone(), client.clone()))
+                .collect()
+        };
+
+        let mut prompts = Vec::new();
+        for (name, client) in clients {
+            prompts.push((name, client.lock().await.list_prompts().await));
+        }
+        prompts.sort_by(|a, b| a.0.cmp(&b.0));
+        prompts
+    }
+
+    /// Fetches `prompt` from `server` with `arguments` substituted and
+    /// returns its messages as text. Required arguments that are missing are
+    /// reported before the server is asked.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        prompt: &str,
+        arguments: HashMap<String, String>,
+    ) -> anyhow::Result<String> {
+        let client = self
+            .inner
+            .read()
+            .await
+            .clients
+            .get(server)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No connected MCP server named '{server}'"))?;
+        let mut client = client.lock().await;
+
+        let declared = client
+            .list_prompts()
+            .await
+            .into_iter()
+            .find(|p| p.name == prompt)
+            .ok_or_else(|| {
+                anyhow::anyhow!("MCP server '{server}' has no prompt named '{prompt}'")
+            })?;
+        let missing: Vec<String> = declared
+            .arguments
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|arg| arg.required == Some(true) && !arguments.contains_key(&arg.name))
+            .map(|arg| arg.name)
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "Prompt '{prompt}' requires argument(s): {}",
+                missing.join(", ")
+            );
+        }
+
+        let arguments = arguments
+            .into_iter()
+            .map(|(name, value)| (name, serde_json::Value::String(value)))
+            .collect();
+        let result = client.get_prompt(prompt, arguments).await?;
+        let text: Vec<String> = result
+            .messages
+            .iter()
+            .map(|message| match &message.content {
+                rmcp::model::PromptMessageContent::Text { text } => text.clone(),
+                _ => "[non-text prompt content omitted]".to_string(),
+            })
+            .collect();
+        Ok(text.join("\n\n"))
+    }
+
+    /// Names of the tools left out by `max_mcp_tools`.
+    pub async fn dropped_tools(&self) -> Vec<String> {
+        let inner = self.inner.read().await;
+        let (_, dropped) = inner.split_offered();
+        dropped.into_iter().map(|def| def.name.clone()).collect()
+    }
+
+    /// Per server: the number of tools it registers and how many of them are
+    /// offered to the model.
+    pub async fn tool_counts(&self) -> HashMap<String, (usize, usize)> {
+        let inner = self.inner.read().await;
+        let (kept, _) = inner.split_offered();
+        let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+        for def in &inner.tool_defs {
+            counts.entry(def.server_name.clone()).or_default().0 += 1;
+        }
+        for def in kept {
+            counts.entry(def.server_name.clone()).or_default().1 += 1;
+        }
+        counts
+    }
 }
 
 #[async_trait::async_trait(?Send)]
@@ -126,9 +346,9 @@ impl Module for McpModule {
 
     async fn tools(&self) -> Vec<SharedTool> {
         let inner = self.inner.read().await;
-        inner
-            .tool_defs
-            .iter()
+        let (offered, _) = inner.split_offered();
+        offered
+            .into_iter()
             .filter_map(|def| McpTool::new(def, self.inner.clone()).ok())
             .map(|tool| Arc::new(tool) as SharedTool)
             .collect()
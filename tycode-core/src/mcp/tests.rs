@@ -6,7 +6,7 @@ use tempfile::TempDir;
 use tokio::process::Child;
 use tokio::time::sleep;
 
-use super::McpModule;
+use super::{McpModule, ToolLimit};
 use crate::module::Module;
 use crate::settings::config::{McpServerConfig, Settings};
 use crate::tools::r#trait::{SharedTool, ToolOutput, ToolRequest};
@@ -51,6 +51,7 @@ impl McpTestHarness {
                 command: "uvx".to_string(),
                 args: vec!["mcp-server-fetch".to_string()],
                 env: HashMap::new(),
+                startup_timeout_secs: None,
             },
         );
 
@@ -104,13 +105,13 @@ mod tests {
             .ok_or(anyhow::anyhow!("mcp_fetch tool not found"))?;
 
         // Create a request to fetch example.com
-        let request = ToolRequest {
-            arguments: serde_json::json!({
+        let request = ToolRequest::new(
+            serde_json::json!({
                 "url": "https://example.com",
                 "max_length": 1000
             }),
-            tool_use_id: "test-example-fetch".to_string(),
-        };
+            "test-example-fetch".to_string(),
+        );
 
         // Process the request using handle API
         let handle = fetch_tool.process(&request).await?;
@@ -137,4 +138,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn unresponsive_server_is_skipped_after_its_startup_timeout() {
+        // `sleep` starts fine but never answers `initialize`
+        let config = McpServerConfig::Stdio {
+            command: "sleep".to_string(),
+            args: vec!["30".to_string()],
+            env: HashMap::new(),
+            startup_timeout_secs: Some(1),
+        };
+        let module = McpModule::empty();
+
+        let started = std::time::Instant::now();
+        let err = module
+            .add_server("stuck".to_string(), config)
+            .await
+            .expect_err("server should time out");
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(err.to_string().contains("did not start within 1s"));
+        assert!(module.tools().await.is_empty());
+    }
+
+    #[test]
+    fn tool_limit_prefers_listed_servers() {
+        let limit = ToolLimit {
+            max_tools: Some(3),
+            priority: vec!["docs".to_string()],
+        };
+        let servers = ["fetch", "docs", "fetch", "github", "docs"];
+
+        // Both docs tools, then the next server by name (fetch)
+        assert_eq!(limit.offered(&servers), vec![0, 1, 4]);
+
+        let unlimited = ToolLimit::default();
+        assert_eq!(unlimited.offered(&servers), vec![0, 1, 2, 3, 4]);
+    }
 }
@@ -5,9 +5,12 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use super::{McpModuleInner, McpToolDef};
-use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::chat::events::{
+    ToolErrorCategory, ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType,
+};
 use crate::tools::r#trait::{
-    ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+    ContinuationPreference, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput,
+    ToolRequest,
 };
 
 fn format_mcp_content(content: &rmcp::model::Content) -> String {
@@ -100,6 +103,7 @@ impl ToolCallHandle for McpToolHandle {
                                 "MCP server '{}' not found",
                                 self.server_name
                             ),
+                            category: Some(ToolErrorCategory::NotFound),
                         },
                     };
                 }
@@ -153,6 +157,7 @@ impl ToolCallHandle for McpToolHandle {
                 ui_result: ToolExecutionResult::Error {
                     short_message: "MCP call failed".to_string(),
                     detailed_message: format!("MCP tool call failed: {e:?}"),
+                    category: None,
                 },
             },
         }
@@ -177,6 +182,10 @@ impl ToolExecutor for McpTool {
         ToolCategory::Execution
     }
 
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Network]
+    }
+
     async fn process(
         &self,
         request: &ToolRequest,
@@ -68,6 +68,9 @@ pub trait SessionStateComponent: Send + Sync {
 
     /// Restore state from persisted session data.
     fn load(&self, state: Value) -> Result<()>;
+
+    /// Return to the state of a brand-new session.
+    fn reset(&self);
 }
 
 /// A slash command that can be provided by a module.
@@ -146,6 +149,27 @@ pub trait Module: Send + Sync {
     fn on_agent_pushed(&self, _agent: &ActiveAgent, _params: HashMap<String, Value>) {}
 
     fn on_agent_popped(&self, _agent: &ActiveAgent) {}
+
+    /// Unfinished work owned by the current agent, one description per
+    /// item. `complete_task` consults this when `complete_task_guard` is on.
+    fn unfinished_work(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Called with each (non-command) user message before it reaches the
+    /// agent. A module with an outstanding question for the user (e.g. an
+    /// edit awaiting confirmation) may consume the reply and return the text
+    /// the agent should see instead.
+    async fn on_user_input(&self, _input: &str) -> Option<String> {
+        None
+    }
+
+    /// Called by `/reload` to re-read on-disk sources that are otherwise
+    /// loaded once at startup. Returns a one-line summary of what is loaded
+    /// now, if the module has anything worth reporting.
+    fn reload(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Encapsulates prompt component management and builds the combined prompt.
@@ -278,8 +302,10 @@ impl ContextBuilder {
     }
 
     /// Builds context sections filtered by the given selection, including components from modules.
+    /// Sections are ordered per `settings.context_order`.
     pub async fn build(
         &self,
+        settings: &Settings,
         selection: &ContextComponentSelection,
         modules: &[Arc<dyn Module>],
     ) -> String {
@@ -298,7 +324,13 @@ impl ContextBuilder {
             return String::new();
         }
 
-        let filtered: Vec<_> = all_components
+        for id in &settings.context_order {
+            if !all_components.iter().any(|c| c.id().0 == id) {
+                tracing::warn!("Skipping unknown context section '{id}' in context_order");
+            }
+        }
+
+        let mut filtered: Vec<_> = all_components
             .iter()
             .filter(|c| match selection {
                 ContextComponentSelection::All => true,
@@ -307,6 +339,9 @@ impl ContextBuilder {
                 ContextComponentSelection::None => false,
             })
             .collect();
+        // Stable sort: unlisted sections (None) keep their default order
+        // ahead of listed ones.
+        filtered.sort_by_key(|c| settings.context_order.iter().position(|id| id == c.id().0));
 
         let mut sections = Vec::new();
         for component in filtered {
@@ -7,6 +7,7 @@ use crate::chat::request::select_model_for_agent;
 use crate::chat::tools;
 use crate::module::SlashCommand;
 
+use super::config::ContextManagementConfig;
 use super::{compact_conversation, count_reasoning_blocks, prune_reasoning_blocks};
 
 pub struct CompactReasoningCommand;
@@ -23,7 +24,8 @@ impl SlashCommand for CompactReasoningCommand {
 
     fn usage(&self) -> &'static str {
         "/compact              - Summarize entire conversation into one message
-/compact reasoning <N> - Keep only N most recent reasoning blocks"
+/compact reasoning <N> - Keep only N most recent reasoning blocks
+/compact reasoning now - Prune reasoning blocks down to the configured retain count"
     }
 
     async fn execute(&self, state: &mut ActorState, args: &[&str]) -> Vec<ChatMessage> {
@@ -33,17 +35,26 @@ impl SlashCommand for CompactReasoningCommand {
 
         if args[0] != "reasoning" {
             return vec![create_system_message(format!(
-                "Unknown compact subcommand: {}. Use: /compact or /compact reasoning <count>",
+                "Unknown compact subcommand: {}. Use: /compact or /compact reasoning <count|now>",
                 args[0]
             ))];
         }
 
+        if args.get(1) == Some(&"now") {
+            // Apply the automatic pruner's retain count without waiting for
+            // its trigger threshold
+            let config: ContextManagementConfig = state
+                .settings
+                .get_module_config(ContextManagementConfig::NAMESPACE);
+            return prune_reasoning_blocks_cmd(state, config.reasoning_prune_retain);
+        }
+
         let count = args.get(1).and_then(|s| s.parse::<usize>().ok());
         let count = match count {
             Some(c) if c > 0 => c,
             _ => {
                 return vec![create_system_message(
-                    "Please specify a positive number of reasoning blocks to retain. Example: /compact reasoning 10".to_string(),
+                    "Please specify a positive number of reasoning blocks to retain, or `now` for the configured retain count. Example: /compact reasoning 10".to_string(),
                 )];
             }
         };
@@ -136,13 +147,16 @@ fn prune_reasoning_blocks_cmd(state: &mut ActorState, count: usize) -> Vec<ChatM
     }
 
     // Prune reasoning blocks using the existing module function
-    let pruned_count = total_blocks - count;
-    tools::current_agent_mut(state, |agent| {
+    let remaining = tools::current_agent_mut(state, |agent| {
         prune_reasoning_blocks(&mut agent.conversation, count);
+        count_reasoning_blocks(&agent.conversation)
     });
 
     vec![create_system_message(format!(
-        "Compacted conversation by pruning {} reasoning block(s). Retained {} most recent reasoning block(s).",
-        pruned_count, count
+        "Compacted conversation by pruning {} reasoning block(s): {} → {}. Retained {} most recent reasoning block(s).",
+        total_blocks - remaining,
+        total_blocks,
+        remaining,
+        count
     ))]
 }
@@ -23,6 +23,10 @@ fn default_window_pressure_fraction() -> f64 {
     0.8
 }
 
+fn default_context_warning_fraction() -> f64 {
+    0.8
+}
+
 fn default_expected_remaining_requests() -> u32 {
     25
 }
@@ -73,6 +77,12 @@ pub struct ContextManagementConfig {
     #[schemars(default = "default_window_pressure_fraction")]
     pub window_pressure_fraction: f64,
 
+    /// Fraction of the model's context window at which a `ContextWarning`
+    /// event is emitted so the UI can suggest `/compact`. Set to 0 to disable.
+    #[serde(default = "default_context_warning_fraction")]
+    #[schemars(default = "default_context_warning_fraction")]
+    pub context_warning_fraction: f64,
+
     /// Estimated number of AI requests remaining in the session, used in the
     /// break-even computation: compaction pays off when this many requests of
     /// cache-read savings exceed the one-time cache rebuild cost.
@@ -116,6 +126,7 @@ impl Default for ContextManagementConfig {
             auto_compact: default_auto_compact(),
             reasoning_prune_retain: default_reasoning_prune_retain(),
             window_pressure_fraction: default_window_pressure_fraction(),
+            context_warning_fraction: default_context_warning_fraction(),
             expected_remaining_requests: default_expected_remaining_requests(),
             cache_ttl_seconds: default_cache_ttl_seconds(),
             tool_result_keep_recent_turns: default_tool_result_keep_recent_turns(),
@@ -134,6 +145,12 @@ impl ContextManagementConfig {
                 self.window_pressure_fraction
             ));
         }
+        if !(0.0..=1.0).contains(&self.context_warning_fraction) {
+            return Err(format!(
+                "context_warning_fraction ({}) must be in [0, 1]",
+                self.context_warning_fraction
+            ));
+        }
         Ok(())
     }
 }
@@ -0,0 +1,300 @@
+//! Tracks errors from the most recent failed build so they stay visible to the
+//! model across turns, even after the raw command output has scrolled out of
+//! the conversation. A subsequent successful build clears the list.
+
+use std::sync::RwLock;
+
+use crate::module::{ContextComponent, ContextComponentId};
+
+pub const BUILD_ERRORS_ID: ContextComponentId = ContextComponentId("build_errors");
+
+/// Upper bound on errors kept; beyond this the model should re-run the build
+/// anyway, and a huge list would crowd out the rest of the context.
+const MAX_BUILD_ERRORS: usize = 50;
+
+/// Command fragments that identify a build/test invocation. Only these update
+/// the tracked errors so unrelated failing commands (grep, ls) do not clear them.
+const BUILD_COMMAND_MARKERS: &[&str] = &[
+    "cargo build",
+    "cargo check",
+    "cargo test",
+    "cargo clippy",
+    "cargo run",
+    "tsc",
+    "npm run build",
+    "npm run test",
+    "npm test",
+    "npm run compile",
+    "yarn build",
+    "make",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError {
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub message: String,
+}
+
+#[derive(Default)]
+struct BuildErrorState {
+    command: String,
+    errors: Vec<BuildError>,
+}
+
+#[derive(Default)]
+pub struct BuildErrorsContextComponent {
+    state: RwLock<BuildErrorState>,
+}
+
+impl BuildErrorsContextComponent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the outcome of a command. Non-build commands are ignored.
+    pub fn record(&self, command: &str, exit_code: i32, stdout: &str, stderr: &str) {
+        if !is_build_command(command) {
+            return;
+        }
+
+        let mut state = self.state.write().unwrap();
+        if exit_code == 0 {
+            *state = BuildErrorState::default();
+            return;
+        }
+
+        let mut errors = parse_build_errors(stderr);
+        errors.extend(parse_build_errors(stdout));
+        errors.dedup();
+        errors.truncate(MAX_BUILD_ERRORS);
+
+        *state = BuildErrorState {
+            command: command.to_string(),
+            errors,
+        };
+    }
+
+    pub fn errors(&self) -> Vec<BuildError> {
+        self.state.read().unwrap().errors.clone()
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ContextComponent for BuildErrorsContextComponent {
+    fn id(&self) -> ContextComponentId {
+        BUILD_ERRORS_ID
+    }
+
+    async fn build_context_section(&self) -> Option<String> {
+        let state = self.state.read().unwrap();
+        if state.errors.is_empty() {
+            return None;
+        }
+
+        let mut output = format!(
+            "Outstanding build errors from `{}` (cleared after the next successful build):\n",
+            state.command
+        );
+        for error in &state.errors {
+            let location = match (&error.file, error.line) {
+                (Some(file), Some(line)) => format!("{file}:{line}: "),
+                (Some(file), None) => format!("{file}: "),
+                _ => String::new(),
+            };
+            output.push_str(&format!("  - {location}{}\n", error.message));
+        }
+        Some(output)
+    }
+}
+
+pub fn is_build_command(command: &str) -> bool {
+    command.split(['&', '|', ';']).map(str::trim).any(|part| {
+        BUILD_COMMAND_MARKERS
+            .iter()
+            .any(|marker| part == *marker || part.starts_with(&format!("{marker} ")))
+            || part.starts_with("npx tsc")
+    })
+}
+
+/// Parses cargo/rustc diagnostics, tsc diagnostics and generic
+/// `file:line:col: error: message` / `error: message` lines.
+pub fn parse_build_errors(output: &str) -> Vec<BuildError> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut errors = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.trim_end();
+
+        if let Some(error) = parse_tsc_line(line) {
+            errors.push(error);
+        } else if line.starts_with("error") {
+            let Some(message) = cargo_error_message(line) else {
+                continue;
+            };
+            // rustc prints the location on a following ` --> file:line:col` line
+            let (file, line_no) = lines[i + 1..]
+                .iter()
+                .take(3)
+                .find_map(|l| l.trim_start().strip_prefix("--> "))
+                .map(split_location)
+                .unwrap_or((None, None));
+            errors.push(BuildError {
+                file,
+                line: line_no,
+                message,
+            });
+        } else if let Some(error) = parse_generic_line(line) {
+            errors.push(error);
+        }
+    }
+
+    errors
+}
+
+fn cargo_error_message(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("error")?;
+    let rest = if rest.starts_with('[') {
+        &rest[rest.find(']')? + 1..]
+    } else {
+        rest
+    };
+    let message = rest.strip_prefix(": ")?.trim();
+    // Summary lines carry no actionable location or detail
+    if message.starts_with("could not compile") || message.starts_with("aborting due to") {
+        return None;
+    }
+    Some(message.to_string())
+}
+
+/// `src/app.ts(10,5): error TS2322: ...` or `src/app.ts:10:5 - error TS2322: ...`
+fn parse_tsc_line(line: &str) -> Option<BuildError> {
+    if let Some((location, message)) = line.split_once("): error ") {
+        let (file, position) = location.rsplit_once('(')?;
+        let line_no = position.split(',').next()?.parse().ok();
+        return Some(BuildError {
+            file: Some(file.trim().to_string()),
+            line: line_no,
+            message: message.trim().to_string(),
+        });
+    }
+
+    let (location, message) = line.split_once(" - error ")?;
+    let (file, line_no) = split_location(location);
+    Some(BuildError {
+        file,
+        line: line_no,
+        message: message.trim().to_string(),
+    })
+}
+
+fn parse_generic_line(line: &str) -> Option<BuildError> {
+    let idx = line.find("error:")?;
+    let message = line[idx + "error:".len()..].trim();
+    if message.is_empty() {
+        return None;
+    }
+
+    let prefix = line[..idx].trim().trim_end_matches(':');
+    let (file, line_no) = if prefix.is_empty() {
+        (None, None)
+    } else {
+        split_location(prefix)
+    };
+    Some(BuildError {
+        file,
+        line: line_no,
+        message: message.to_string(),
+    })
+}
+
+/// Splits `file:line[:col]` into its file and line parts.
+fn split_location(location: &str) -> (Option<String>, Option<u32>) {
+    let location = location.trim();
+    let mut parts = location.rsplitn(3, ':');
+    let last = parts.next();
+    let middle = parts.next();
+    let first = parts.next();
+
+    match (first, middle, last) {
+        (Some(file), Some(line), Some(_col)) if line.parse::<u32>().is_ok() => {
+            (Some(file.to_string()), line.parse().ok())
+        }
+        (None, Some(file), Some(line)) if line.parse::<u32>().is_ok() => {
+            (Some(file.to_string()), line.parse().ok())
+        }
+        _ => (Some(location.to_string()), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cargo_errors_with_locations() {
+        let output = "\
+   Compiling demo v0.1.0
+error[E0308]: mismatched types
+  --> src/main.rs:4:18
+   |
+4  |     let x: u32 = \"a\";
+error: cannot find value `y` in this scope
+ --> src/lib.rs:10:5
+error: could not compile `demo` (bin \"demo\") due to 2 previous errors
+";
+        let errors = parse_build_errors(output);
+        assert_eq!(
+            errors,
+            vec![
+                BuildError {
+                    file: Some("src/main.rs".to_string()),
+                    line: Some(4),
+                    message: "mismatched types".to_string(),
+                },
+                BuildError {
+                    file: Some("src/lib.rs".to_string()),
+                    line: Some(10),
+                    message: "cannot find value `y` in this scope".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_tsc_and_generic_errors() {
+        let output = "\
+src/app.ts(12,3): error TS2322: Type 'string' is not assignable to type 'number'.
+src/util.ts:7:1 - error TS1005: ';' expected.
+main.c:3:5: error: expected ';' before '}' token
+";
+        let errors = parse_build_errors(output);
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].file.as_deref(), Some("src/app.ts"));
+        assert_eq!(errors[0].line, Some(12));
+        assert_eq!(errors[1].file.as_deref(), Some("src/util.ts"));
+        assert_eq!(errors[1].line, Some(7));
+        assert_eq!(errors[2].file.as_deref(), Some("main.c"));
+        assert_eq!(errors[2].message, "expected ';' before '}' token");
+    }
+
+    #[tokio::test]
+    async fn successful_build_clears_errors() {
+        let component = BuildErrorsContextComponent::new();
+        component.record(
+            "cargo build",
+            101,
+            "",
+            "error: boom\n --> src/main.rs:1:1\n",
+        );
+        let section = component.build_context_section().await.expect("errors");
+        assert!(section.contains("src/main.rs:1: boom"), "{section}");
+
+        // Unrelated commands leave the errors in place
+        component.record("ls", 0, "", "");
+        assert_eq!(component.errors().len(), 1);
+
+        component.record("cargo build", 0, "", "");
+        assert!(component.build_context_section().await.is_none());
+    }
+}
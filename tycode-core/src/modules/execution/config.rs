@@ -1,3 +1,4 @@
+use encoding_rs::Encoding;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +9,66 @@ pub enum CommandExecutionMode {
     Direct,
 }
 
+/// Interpreter used by `CommandExecutionMode::Bash`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Shell {
+    #[default]
+    Bash,
+    Zsh,
+    Sh,
+    Pwsh,
+    Cmd,
+}
+
+impl Shell {
+    pub fn program(&self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Sh => "sh",
+            Shell::Pwsh => "pwsh",
+            Shell::Cmd => "cmd",
+        }
+    }
+
+    /// The flag that makes the interpreter run the following argument as a command.
+    pub fn command_flag(&self) -> &'static str {
+        match self {
+            Shell::Bash | Shell::Zsh | Shell::Sh => "-c",
+            Shell::Pwsh => "-Command",
+            Shell::Cmd => "/C",
+        }
+    }
+}
+
+/// A formatter run by the format_file tool.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct FormatterConfig {
+    /// File extensions, without the dot, this formatter handles.
+    pub extensions: Vec<String>,
+
+    /// Command that formats the file in place. `{file}` is replaced with the
+    /// file's quoted absolute path. Runs from the file's workspace root.
+    pub command: String,
+
+    /// Config files (e.g. `.clang-format`) of which one must exist in the
+    /// file's directory or a parent within the workspace. Empty means the
+    /// formatter is always used for its extensions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub config_files: Vec<String>,
+}
+
+impl FormatterConfig {
+    fn new(extensions: &[&str], command: &str, config_files: &[&str]) -> Self {
+        Self {
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            command: command.to_string(),
+            config_files: config_files.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[schemars(title = "Execution")]
 pub struct ExecutionConfig {
@@ -15,22 +76,110 @@ pub struct ExecutionConfig {
     #[serde(default)]
     pub execution_mode: CommandExecutionMode,
 
+    /// Shell used to interpret commands in Bash execution mode.
+    #[serde(default)]
+    pub shell: Shell,
+
     /// Maximum bytes of command output to include. Large outputs are compacted
     /// by keeping the first half and last half with a truncation marker.
     /// Defaults to 200KB.
     #[serde(default = "default_max_output_bytes")]
     pub max_output_bytes: Option<usize>,
+
+    /// Timeout in seconds for commands that do not specify `timeout_seconds`.
+    #[serde(default = "default_timeout_seconds")]
+    pub default_timeout_seconds: u64,
+
+    /// Largest `timeout_seconds` a command may request. Requests above this
+    /// cap are rejected.
+    #[serde(default = "default_max_timeout_seconds")]
+    pub max_timeout_seconds: u64,
+
+    /// Regular expressions matched against each output line; matching lines
+    /// (e.g. `^\s*Compiling `) are dropped from the output shown to the model.
+    /// The unfiltered output is saved to disk.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_filters: Vec<String>,
+
+    /// Formatters available to format_file, tried in order. The first whose
+    /// extensions match and whose config file (if any) is found is used.
+    #[serde(default = "default_formatters")]
+    pub formatters: Vec<FormatterConfig>,
+
+    /// Encoding of command output, as a label such as `windows-1252` or
+    /// `shift_jis`. Unset decodes output as UTF-8, replacing invalid bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_encoding: Option<String>,
 }
 
 fn default_max_output_bytes() -> Option<usize> {
     Some(200_000)
 }
 
+fn default_timeout_seconds() -> u64 {
+    60
+}
+
+fn default_max_timeout_seconds() -> u64 {
+    300
+}
+
+fn default_formatters() -> Vec<FormatterConfig> {
+    vec![
+        FormatterConfig::new(&["rs"], "rustfmt --edition 2021 {file}", &[]),
+        FormatterConfig::new(
+            &[
+                "js", "jsx", "mjs", "cjs", "ts", "tsx", "json", "css", "scss", "html", "md",
+                "yaml", "yml",
+            ],
+            "prettier --write {file}",
+            &[],
+        ),
+        FormatterConfig::new(&["py"], "ruff format {file}", &["ruff.toml", ".ruff.toml"]),
+        FormatterConfig::new(&["py"], "black --quiet {file}", &[]),
+        FormatterConfig::new(&["go"], "gofmt -w {file}", &[]),
+        FormatterConfig::new(
+            &["c", "h", "cc", "cpp", "hpp"],
+            "clang-format -i {file}",
+            &[".clang-format", "_clang-format"],
+        ),
+    ]
+}
+
+impl ExecutionConfig {
+    /// The default timeout, never exceeding the configured cap.
+    pub fn effective_default_timeout(&self) -> u64 {
+        self.default_timeout_seconds
+            .min(self.max_timeout_seconds)
+            .max(1)
+    }
+
+    /// The configured output encoding, or `None` for UTF-8. Unknown labels
+    /// fall back to UTF-8 with a warning.
+    pub fn output_encoding(&self) -> Option<&'static Encoding> {
+        let label = self.output_encoding.as_deref()?;
+        let encoding = Encoding::for_label(label.as_bytes());
+        if encoding.is_none() {
+            tracing::warn!(
+                label,
+                "Unknown output_encoding; decoding command output as UTF-8"
+            );
+        }
+        encoding
+    }
+}
+
 impl Default for ExecutionConfig {
     fn default() -> Self {
         Self {
             execution_mode: CommandExecutionMode::default(),
+            shell: Shell::default(),
             max_output_bytes: default_max_output_bytes(),
+            default_timeout_seconds: default_timeout_seconds(),
+            max_timeout_seconds: default_max_timeout_seconds(),
+            output_filters: Vec::new(),
+            formatters: default_formatters(),
+            output_encoding: None,
         }
     }
 }
@@ -0,0 +1,302 @@
+//! `format_file` tool: runs the configured formatter for a file's language
+//! (rustfmt, prettier, ...) so the agent can clean up its own edits.
+//!
+//! The tool is wrapped like the file edit tools (see
+//! [`crate::file::modify::EditWrapper`]), so formatting waits for
+//! confirmation when edits are confirmed and can be reverted with `/undo`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use encoding_rs::Encoding;
+use serde_json::{json, Value};
+
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::access::FileAccessManager;
+use crate::file::manager::{count_lines_added, count_lines_removed};
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput,
+    ToolRequest,
+};
+use crate::tools::ToolName;
+
+use super::config::{CommandExecutionMode, ExecutionConfig, FormatterConfig, Shell};
+use super::{compact_output, run_cmd, ExecutionModuleInner};
+
+pub struct FormatFileTool {
+    pub(super) inner: Arc<ExecutionModuleInner>,
+}
+
+impl FormatFileTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("format_file")
+    }
+}
+
+/// The first formatter that handles `path`'s extension and whose config file,
+/// if it requires one, exists between the file and `root`.
+fn select_formatter<'a>(
+    formatters: &'a [FormatterConfig],
+    path: &Path,
+    root: &Path,
+) -> Option<&'a FormatterConfig> {
+    let extension = path.extension()?.to_str()?;
+    formatters.iter().find(|formatter| {
+        formatter
+            .extensions
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(extension))
+            && (formatter.config_files.is_empty()
+                || has_config_file(path, root, &formatter.config_files))
+    })
+}
+
+fn has_config_file(path: &Path, root: &Path, config_files: &[String]) -> bool {
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(root))
+        .any(|dir| config_files.iter().any(|name| dir.join(name).is_file()))
+}
+
+struct FormatFileHandle {
+    access: FileAccessManager,
+    path: PathBuf,
+    root: PathBuf,
+    command: String,
+    timeout: Duration,
+    execution_mode: CommandExecutionMode,
+    shell: Shell,
+    output_encoding: Option<&'static Encoding>,
+    max_output_bytes: Option<usize>,
+    tool_use_id: String,
+}
+
+impl FormatFileHandle {
+    fn error(&self, short_message: &str, detail: String) -> ToolOutput {
+        ToolOutput::Result {
+            content: detail.clone(),
+            is_error: true,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Error {
+                short_message: short_message.to_string(),
+                detailed_message: detail,
+                category: None,
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for FormatFileHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "format_file".to_string(),
+            tool_type: ToolRequestType::RunCommand {
+                command: self.command.clone(),
+                working_directory: self.root.to_string_lossy().to_string(),
+            },
+        }
+    }
+
+    fn edited_path(&self) -> Option<PathBuf> {
+        Some(self.path.clone())
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        // Formatters rewrite the file themselves, so compare the text before
+        // and after to report what changed
+        let before = self
+            .access
+            .read_file(&self.path.to_string_lossy())
+            .await
+            .unwrap_or_default();
+
+        let result = match run_cmd(
+            self.root.clone(),
+            self.command.clone(),
+            self.timeout,
+            self.execution_mode.clone(),
+            self.shell,
+            self.output_encoding,
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                return self.error(
+                    "Formatter failed to run",
+                    format!("Failed to run `{}`: {e:?}", self.command),
+                )
+            }
+        };
+
+        if result.code != 0 {
+            let output = format!("{}{}", result.out, result.err);
+            let output = match self.max_output_bytes {
+                Some(max_bytes) => compact_output(&output, max_bytes),
+                None => output,
+            };
+            return self.error(
+                "Formatter reported errors",
+                format!(
+                    "`{}` exited with code {}:\n{}",
+                    self.command, result.code, output
+                ),
+            );
+        }
+
+        let after = self
+            .access
+            .read_file(&self.path.to_string_lossy())
+            .await
+            .unwrap_or_default();
+        let lines_added = count_lines_added(&before, &after);
+        let lines_removed = count_lines_removed(&before, &after);
+
+        ToolOutput::Result {
+            content: json!({
+                "success": true,
+                "formatter": self.command,
+                "changed": before != after,
+                "lines_added": lines_added,
+                "lines_removed": lines_removed
+            })
+            .to_string(),
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::ModifyFile {
+                lines_added,
+                lines_removed,
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for FormatFileTool {
+    fn name(&self) -> String {
+        "format_file".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Format a file in place with the project's formatter for its language (e.g. rustfmt for .rs, prettier for .js/.ts). Run it after editing a file instead of fixing formatting by hand. Returns whether the file changed, or the formatter's errors.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "file_path": {
+                    "type": "string",
+                    "description": "Absolute path inside a workspace root of the file to format"
+                }
+            },
+            "required": ["file_path"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Execution
+    }
+
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write, ToolCapability::Execute]
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let file_path = request
+            .arguments
+            .get("file_path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Missing required parameter: file_path"))?;
+
+        let path = self.inner.access.resolve(file_path)?;
+        if !path.is_file() {
+            bail!("File not found: {file_path}");
+        }
+        let root = self
+            .inner
+            .access
+            .roots
+            .iter()
+            .find(|root| path.starts_with(root))
+            .cloned()
+            .ok_or_else(|| anyhow!("{file_path} is not inside a workspace root"))?;
+
+        let config: ExecutionConfig = self.inner.settings.get_module_config("execution");
+        let Some(formatter) = select_formatter(&config.formatters, &path, &root) else {
+            bail!(
+                "No formatter is configured for {file_path}. Formatters are set in the execution settings (`formatters`)."
+            );
+        };
+        let quoted = shell_words::quote(&path.to_string_lossy()).to_string();
+        let command = formatter.command.replace("{file}", &quoted);
+
+        Ok(Box::new(FormatFileHandle {
+            access: self.inner.access.clone(),
+            path,
+            root,
+            command,
+            timeout: Duration::from_secs(config.effective_default_timeout()),
+            execution_mode: config.execution_mode.clone(),
+            shell: config.shell,
+            output_encoding: config.output_encoding(),
+            max_output_bytes: config.max_output_bytes,
+            tool_use_id: request.tool_use_id.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formatters() -> Vec<FormatterConfig> {
+        ExecutionConfig::default().formatters
+    }
+
+    #[test]
+    fn selects_formatter_by_extension() {
+        let root = tempfile::tempdir().unwrap();
+        let file = root.path().join("src/main.rs");
+        let formatters = formatters();
+
+        let formatter = select_formatter(&formatters, &file, root.path()).unwrap();
+        assert!(formatter.command.starts_with("rustfmt"));
+
+        let formatter =
+            select_formatter(&formatters, &root.path().join("app.TSX"), root.path()).unwrap();
+        assert!(formatter.command.starts_with("prettier"));
+
+        assert!(
+            select_formatter(&formatters, &root.path().join("notes.txt"), root.path()).is_none()
+        );
+        assert!(
+            select_formatter(&formatters, &root.path().join("Makefile"), root.path()).is_none()
+        );
+    }
+
+    #[test]
+    fn config_file_selects_formatter() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("pkg")).unwrap();
+        let file = root.path().join("pkg/app.py");
+        let formatters = formatters();
+
+        let formatter = select_formatter(&formatters, &file, root.path()).unwrap();
+        assert!(formatter.command.starts_with("black"));
+
+        std::fs::write(root.path().join("ruff.toml"), "").unwrap();
+        let formatter = select_formatter(&formatters, &file, root.path()).unwrap();
+        assert!(formatter.command.starts_with("ruff"));
+
+        let c_file = root.path().join("pkg/main.c");
+        assert!(
+            select_formatter(&formatters, &c_file, root.path()).is_none(),
+            "clang-format requires a .clang-format file"
+        );
+    }
+}
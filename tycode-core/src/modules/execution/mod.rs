@@ -1,27 +1,34 @@
+pub mod build_errors;
 pub mod config;
+pub mod format;
 
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use std::{env, process::Stdio};
 
 use anyhow::{anyhow, Result};
+use encoding_rs::Encoding;
+use regex::Regex;
 use serde::Serialize;
 use serde_json::{json, Value};
 use tokio::process::Command;
 
 use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
 use crate::file::access::FileAccessManager;
-use crate::module::{ContextComponent, Module};
+use crate::file::modify::EditWrapper;
 use crate::module::PromptComponent;
+use crate::module::{ContextComponent, Module};
 use crate::settings::SettingsManager;
 use crate::tools::r#trait::{
-    ContinuationPreference, SharedTool, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput,
-    ToolRequest,
+    ContinuationPreference, SharedTool, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor,
+    ToolOutput, ToolRequest,
 };
 use crate::tools::ToolName;
 
-use config::{CommandExecutionMode, ExecutionConfig};
+use build_errors::BuildErrorsContextComponent;
+use config::{CommandExecutionMode, ExecutionConfig, Shell};
+use format::FormatFileTool;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct CommandResult {
@@ -36,9 +43,18 @@ pub async fn run_cmd(
     cmd: String,
     timeout: Duration,
     execution_mode: CommandExecutionMode,
+    shell: Shell,
+    output_encoding: Option<&'static Encoding>,
 ) -> Result<CommandResult> {
     let path = env::var("PATH")?;
-    tracing::info!(?path, ?dir, ?cmd, ?execution_mode, "Attempting to run_cmd");
+    tracing::info!(
+        ?path,
+        ?dir,
+        ?cmd,
+        ?execution_mode,
+        ?shell,
+        "Attempting to run_cmd"
+    );
 
     let child = match execution_mode {
         CommandExecutionMode::Direct => {
@@ -58,8 +74,8 @@ pub async fn run_cmd(
                 .kill_on_drop(true)
                 .spawn()?
         }
-        CommandExecutionMode::Bash => Command::new("bash")
-            .args(["-c", &cmd])
+        CommandExecutionMode::Bash => Command::new(shell.program())
+            .args([shell.command_flag(), &cmd])
             .current_dir(&dir)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -74,8 +90,8 @@ pub async fn run_cmd(
     .await??;
 
     let code = output.status.code().unwrap_or(1);
-    let out = String::from_utf8_lossy(&output.stdout).to_string();
-    let err = String::from_utf8_lossy(&output.stderr).to_string();
+    let out = decode_output(&output.stdout, output_encoding);
+    let err = decode_output(&output.stderr, output_encoding);
 
     Ok(CommandResult {
         command: cmd,
@@ -85,19 +101,76 @@ pub async fn run_cmd(
     })
 }
 
+/// Decodes command output in `encoding`, or as UTF-8 with invalid bytes
+/// replaced when none is configured.
+pub fn decode_output(bytes: &[u8], encoding: Option<&'static Encoding>) -> String {
+    match encoding {
+        Some(encoding) => encoding.decode(bytes).0.into_owned(),
+        None => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Drops lines matching any of `filters`. Returns the remaining output and
+/// the number of dropped lines.
+pub fn filter_output(output: &str, filters: &[Regex]) -> (String, usize) {
+    if filters.is_empty() {
+        return (output.to_string(), 0);
+    }
+
+    let mut kept = String::with_capacity(output.len());
+    let mut dropped = 0;
+    for line in output.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if filters.iter().any(|f| f.is_match(content)) {
+            dropped += 1;
+        } else {
+            kept.push_str(line);
+        }
+    }
+    (kept, dropped)
+}
+
 pub struct ExecutionModule {
     inner: Arc<ExecutionModuleInner>,
+    edits: EditWrapper,
 }
 
 struct ExecutionModuleInner {
     access: FileAccessManager,
     default_working_directory: PathBuf,
     settings: SettingsManager,
+    tool_calls_dir: PathBuf,
+    build_errors: Arc<BuildErrorsContextComponent>,
+    /// Most recent explicitly requested working directory; commands that omit
+    /// `working_directory` run here.
+    last_working_directory: RwLock<Option<PathBuf>>,
+}
+
+impl ExecutionModuleInner {
+    fn working_directory(&self, requested: Option<&str>) -> Result<PathBuf> {
+        if let Some(dir) = requested {
+            let resolved = self.access.resolve(dir)?;
+            *self.last_working_directory.write().unwrap() = Some(resolved.clone());
+            return Ok(resolved);
+        }
+
+        let last = self.last_working_directory.read().unwrap().clone();
+        match last {
+            // Re-validate: the directory may have been removed since it was used
+            Some(dir) => self.access.resolve(&dir.to_string_lossy()),
+            None => Ok(self.default_working_directory.clone()),
+        }
+    }
 }
 
 impl ExecutionModule {
-    pub fn new(workspace_roots: Vec<PathBuf>, settings: SettingsManager) -> Result<Self> {
-        let access = FileAccessManager::new(workspace_roots)?;
+    pub fn new(
+        workspace_roots: Vec<PathBuf>,
+        settings: SettingsManager,
+        tool_calls_dir: PathBuf,
+        edits: EditWrapper,
+    ) -> Result<Self> {
+        let access = FileAccessManager::new(workspace_roots)?.with_settings(settings.clone());
         // No workspace roots is a legitimate state (e.g. the VSCode extension
         // with no folder open, loading settings); commands without an
         // explicit working_directory then run from the home directory, like
@@ -107,12 +180,25 @@ impl ExecutionModule {
             None => dirs::home_dir().unwrap_or_else(std::env::temp_dir),
         };
 
+        let config: ExecutionConfig = settings.get_module_config("execution");
+        if config.execution_mode == CommandExecutionMode::Bash
+            && !is_on_path(config.shell.program())
+        {
+            tracing::warn!(
+                shell = config.shell.program(),
+                "Configured shell was not found on PATH; commands will fail to run"
+            );
+        }
+
         let inner = Arc::new(ExecutionModuleInner {
             access,
             default_working_directory,
             settings,
+            tool_calls_dir,
+            build_errors: Arc::new(BuildErrorsContextComponent::new()),
+            last_working_directory: RwLock::new(None),
         });
-        Ok(Self { inner })
+        Ok(Self { inner, edits })
     }
 }
 
@@ -123,17 +209,26 @@ impl Module for ExecutionModule {
     }
 
     fn context_components(&self) -> Vec<Arc<dyn ContextComponent>> {
-        vec![]
+        vec![self.inner.build_errors.clone() as Arc<dyn ContextComponent>]
     }
 
     async fn tools(&self) -> Vec<SharedTool> {
-        vec![Arc::new(BashTool {
-            inner: self.inner.clone(),
-        })]
+        vec![
+            Arc::new(BashTool {
+                inner: self.inner.clone(),
+            }),
+            // Formatters rewrite files, so they go through the same
+            // confirmation and undo history as the file edit tools
+            self.edits.wrap(Arc::new(FormatFileTool {
+                inner: self.inner.clone(),
+            })),
+        ]
     }
 
     fn session_state(&self) -> Option<Arc<dyn crate::module::SessionStateComponent>> {
-        None
+        Some(Arc::new(ExecutionSessionState {
+            inner: self.inner.clone(),
+        }))
     }
 
     fn settings_namespace(&self) -> Option<&'static str> {
@@ -145,6 +240,45 @@ impl Module for ExecutionModule {
     }
 }
 
+fn is_on_path(program: &str) -> bool {
+    let Some(path) = env::var_os("PATH") else {
+        return false;
+    };
+    env::split_paths(&path)
+        .any(|dir| dir.join(program).is_file() || dir.join(format!("{program}.exe")).is_file())
+}
+
+struct ExecutionSessionState {
+    inner: Arc<ExecutionModuleInner>,
+}
+
+impl crate::module::SessionStateComponent for ExecutionSessionState {
+    fn key(&self) -> &str {
+        "execution"
+    }
+
+    fn save(&self) -> Value {
+        json!({
+            "last_working_directory": *self.inner.last_working_directory.read().unwrap(),
+        })
+    }
+
+    fn load(&self, state: Value) -> Result<()> {
+        let last = state
+            .get("last_working_directory")
+            .cloned()
+            .map(serde_json::from_value::<Option<PathBuf>>)
+            .transpose()?
+            .flatten();
+        *self.inner.last_working_directory.write().unwrap() = last;
+        Ok(())
+    }
+
+    fn reset(&self) {
+        *self.inner.last_working_directory.write().unwrap() = None;
+    }
+}
+
 pub struct BashTool {
     inner: Arc<ExecutionModuleInner>,
 }
@@ -161,6 +295,12 @@ struct BashHandle {
     timeout_seconds: u64,
     tool_use_id: String,
     execution_mode: CommandExecutionMode,
+    shell: Shell,
+    output_encoding: Option<&'static Encoding>,
+    output_filters: Vec<Regex>,
+    tool_calls_dir: PathBuf,
+    build_errors: Arc<BuildErrorsContextComponent>,
+    access: FileAccessManager,
 }
 
 /// Compact output by keeping first half and last half with truncation marker.
@@ -207,6 +347,37 @@ pub async fn truncate_and_persist(
     Ok((truncated, persist_path))
 }
 
+impl BashHandle {
+    /// Saves the full output so filtered lines can still be inspected, and
+    /// returns a note describing what was dropped.
+    async fn persist_unfiltered(&self, result: &CommandResult, filtered_lines: usize) -> String {
+        let path = self
+            .tool_calls_dir
+            .join(format!("{}-unfiltered", self.tool_use_id));
+        let full = json!({
+            "exit_code": result.code,
+            "stdout": result.out,
+            "stderr": result.err,
+        })
+        .to_string();
+
+        let write = async {
+            tokio::fs::create_dir_all(&self.tool_calls_dir).await?;
+            tokio::fs::write(&path, full).await
+        };
+        match write.await {
+            Ok(()) => format!(
+                "{filtered_lines} lines matched output filters and were removed. Full output saved to: {}",
+                self.access.display_path(&path)
+            ),
+            Err(e) => {
+                tracing::warn!(?e, "Failed to persist unfiltered command output");
+                format!("{filtered_lines} lines matched output filters and were removed.")
+            }
+        }
+    }
+}
+
 #[async_trait::async_trait(?Send)]
 impl ToolCallHandle for BashHandle {
     fn tool_request(&self) -> ToolRequestEvent {
@@ -215,7 +386,7 @@ impl ToolCallHandle for BashHandle {
             tool_name: "bash".to_string(),
             tool_type: ToolRequestType::RunCommand {
                 command: self.command.clone(),
-                working_directory: self.working_directory.to_string_lossy().to_string(),
+                working_directory: self.access.display_path(&self.working_directory),
             },
         }
     }
@@ -228,6 +399,8 @@ impl ToolCallHandle for BashHandle {
             self.command.clone(),
             timeout,
             self.execution_mode.clone(),
+            self.shell,
+            self.output_encoding,
         )
         .await
         {
@@ -241,18 +414,29 @@ impl ToolCallHandle for BashHandle {
                     ui_result: ToolExecutionResult::Error {
                         short_message: "Command failed".to_string(),
                         detailed_message: error_msg,
+                        category: None,
                     },
                 };
             }
         };
 
+        self.build_errors
+            .record(&self.command, result.code, &result.out, &result.err);
+
+        let (out, out_dropped) = filter_output(&result.out, &self.output_filters);
+        let (err, err_dropped) = filter_output(&result.err, &self.output_filters);
+        let filtered_lines = out_dropped + err_dropped;
+
         let is_error = result.code != 0;
-        let content = json!({
+        let mut content = json!({
             "exit_code": result.code,
-            "stdout": result.out,
-            "stderr": result.err,
-        })
-        .to_string();
+            "stdout": out,
+            "stderr": err,
+        });
+        if filtered_lines > 0 {
+            content["note"] = json!(self.persist_unfiltered(&result, filtered_lines).await);
+        }
+        let content = content.to_string();
 
         ToolOutput::Result {
             content,
@@ -260,8 +444,8 @@ impl ToolCallHandle for BashHandle {
             continuation: ContinuationPreference::Continue,
             ui_result: ToolExecutionResult::RunCommand {
                 exit_code: result.code,
-                stdout: result.out,
-                stderr: result.err,
+                stdout: out,
+                stderr: err,
             },
         }
     }
@@ -278,6 +462,7 @@ impl ToolExecutor for BashTool {
     }
 
     fn input_schema(&self) -> Value {
+        let config: ExecutionConfig = self.inner.settings.get_module_config("execution");
         json!({
             "type": "object",
             "properties": {
@@ -287,13 +472,20 @@ impl ToolExecutor for BashTool {
                 },
                 "working_directory": {
                     "type": "string",
-                    "description": "Absolute directory to run the command in. Defaults to the first workspace root. Must be inside a configured workspace root."
+                    "description": "Absolute directory to run the command in. Defaults to the working directory of the previous command that specified one, or the first workspace root. Must be inside a configured workspace root."
                 },
                 "timeout_seconds": {
                     "type": "integer",
-                    "description": "Maximum seconds to wait for command completion. Defaults to 60.",
+                    "description": format!(
+                        "Maximum seconds to wait for command completion. Defaults to {}.",
+                        config.effective_default_timeout()
+                    ),
                     "minimum": 1,
-                    "maximum": 300
+                    "maximum": config.max_timeout_seconds
+                },
+                "output_filter": {
+                    "type": "string",
+                    "description": "Optional regex; output lines matching it are dropped (e.g. \"^\\s*Compiling \"). The full output is saved to disk."
                 }
             },
             "required": ["command"]
@@ -304,6 +496,10 @@ impl ToolExecutor for BashTool {
         ToolCategory::Execution
     }
 
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Execute, ToolCapability::Write]
+    }
+
     async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
         let command_str = request
             .arguments
@@ -311,22 +507,54 @@ impl ToolExecutor for BashTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow!("Missing 'command' argument"))?;
 
-        let timeout_seconds = request
+        let config: ExecutionConfig = self.inner.settings.get_module_config("execution");
+        let timeout_seconds = match request
             .arguments
             .get("timeout_seconds")
             .and_then(|v| v.as_u64())
-            .unwrap_or(60);
+        {
+            Some(0) => return Err(anyhow!("timeout_seconds must be at least 1")),
+            Some(t) if t > config.max_timeout_seconds => {
+                return Err(anyhow!(
+                    "timeout_seconds {t} exceeds the configured maximum of {}",
+                    config.max_timeout_seconds
+                ))
+            }
+            Some(t) => t,
+            None => config.effective_default_timeout(),
+        };
 
-        let resolved_working_directory = request
-            .arguments
-            .get("working_directory")
-            .and_then(|v| v.as_str())
-            .map(|dir| self.inner.access.resolve(dir))
-            .transpose()?
-            .unwrap_or_else(|| self.inner.default_working_directory.clone());
+        let resolved_working_directory = self.inner.working_directory(
+            request
+                .arguments
+                .get("working_directory")
+                .and_then(|v| v.as_str()),
+        )?;
 
-        let config: ExecutionConfig = self.inner.settings.get_module_config("execution");
         let execution_mode = config.execution_mode.clone();
+        let shell = config.shell;
+        let output_encoding = config.output_encoding();
+
+        let mut output_filters: Vec<Regex> = config
+            .output_filters
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(re) => Some(re),
+                Err(e) => {
+                    tracing::warn!(?e, pattern, "Ignoring invalid output filter");
+                    None
+                }
+            })
+            .collect();
+        if let Some(pattern) = request
+            .arguments
+            .get("output_filter")
+            .and_then(|v| v.as_str())
+        {
+            let re = Regex::new(pattern)
+                .map_err(|e| anyhow!("Invalid output_filter regex {pattern:?}: {e}"))?;
+            output_filters.push(re);
+        }
 
         Ok(Box::new(BashHandle {
             command: command_str.to_string(),
@@ -334,6 +562,12 @@ impl ToolExecutor for BashTool {
             timeout_seconds,
             tool_use_id: request.tool_use_id.clone(),
             execution_mode,
+            shell,
+            output_encoding,
+            output_filters,
+            tool_calls_dir: self.inner.tool_calls_dir.clone(),
+            build_errors: self.inner.build_errors.clone(),
+            access: self.inner.access.clone(),
         }))
     }
 }
@@ -0,0 +1,116 @@
+//! y/n approval for git tool calls that change the repository.
+//!
+//! Mirrors the `confirm_edits` gate for file edits: the validated handle is
+//! parked, the user is asked through the ask-user path, and their next
+//! message resolves it. `y`/`yes` runs the change; anything else declines it,
+//! and a reply other than `n`/`no` is passed on to the agent.
+
+use std::sync::{Arc, Mutex};
+
+use crate::audit::AuditCall;
+use crate::chat::events::ToolRequest as ToolRequestEvent;
+use crate::tools::r#trait::{ToolCallHandle, ToolOutput};
+
+struct PendingChange {
+    handle: Box<dyn ToolCallHandle>,
+    description: String,
+}
+
+#[derive(Default)]
+pub struct GitApproval {
+    pending: Mutex<Vec<PendingChange>>,
+}
+
+impl GitApproval {
+    /// Wraps `handle` so executing it asks the user to approve `description`
+    /// instead of running it.
+    pub fn gate(
+        self: &Arc<Self>,
+        handle: Box<dyn ToolCallHandle>,
+        description: String,
+    ) -> Box<dyn ToolCallHandle> {
+        Box::new(ApprovalHandle {
+            handle,
+            description,
+            approval: self.clone(),
+            audit: None,
+        })
+    }
+
+    /// Resolves pending changes with the user's reply. Returns the message to
+    /// send to the agent in place of the raw reply, or None when nothing was
+    /// pending.
+    pub async fn resolve(&self, reply: &str) -> Option<String> {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        if pending.is_empty() {
+            return None;
+        }
+
+        let answer = reply.trim().to_lowercase();
+        let approved = matches!(answer.as_str(), "y" | "yes");
+        let passthrough = !approved && !matches!(answer.as_str(), "n" | "no");
+
+        let mut outcomes = Vec::new();
+        for change in pending {
+            let outcome = if approved {
+                match change.handle.execute().await {
+                    ToolOutput::Result {
+                        content, is_error, ..
+                    } if is_error => format!(
+                        "The user approved the request to {}, but it failed: {content}",
+                        change.description
+                    ),
+                    ToolOutput::Result { content, .. } => format!(
+                        "The user approved the request to {}. {content}",
+                        change.description
+                    ),
+                    _ => format!("The user approved the request to {}.", change.description),
+                }
+            } else {
+                format!(
+                    "The user declined the request to {}; nothing was changed.",
+                    change.description
+                )
+            };
+            outcomes.push(outcome);
+        }
+
+        let outcome = outcomes.join("\n");
+        Some(if passthrough {
+            format!("{outcome}\n\n{reply}")
+        } else {
+            outcome
+        })
+    }
+}
+
+struct ApprovalHandle {
+    handle: Box<dyn ToolCallHandle>,
+    description: String,
+    approval: Arc<GitApproval>,
+    audit: Option<AuditCall>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for ApprovalHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        self.handle.tool_request()
+    }
+
+    fn attach_audit(&mut self, call: AuditCall) {
+        self.audit = Some(call);
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let question = format!("Allow the agent to {}? (y/n)", self.description);
+        let handle = match self.audit {
+            Some(audit) => audit.hold(self.handle),
+            None => self.handle,
+        };
+        self.approval.pending.lock().unwrap().push(PendingChange {
+            handle,
+            description: self.description,
+        });
+        ToolOutput::PromptUser { question }
+    }
+}
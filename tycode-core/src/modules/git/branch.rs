@@ -0,0 +1,305 @@
+//! `git_branch` tool: list branches, show the current one, or check one out.
+//!
+//! Checkout refuses to run over uncommitted changes to tracked files unless
+//! `force` is set, and goes through [`GitApproval`] when `confirm_changes`
+//! is enabled.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use git2::build::CheckoutBuilder;
+use git2::{BranchType, Repository, Status, StatusOptions};
+use serde_json::{json, Value};
+
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::workspace::WorkspacePaths;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput,
+    ToolRequest,
+};
+use crate::tools::ToolName;
+
+use super::approval::GitApproval;
+use super::config::Git;
+use super::{target_root, with_repo};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BranchAction {
+    List,
+    Current,
+    Checkout { branch: String, force: bool },
+}
+
+pub struct GitBranchTool {
+    workspace: WorkspacePaths,
+    settings: SettingsManager,
+    approval: Arc<GitApproval>,
+}
+
+impl GitBranchTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("git_branch")
+    }
+
+    pub fn new(
+        workspace_roots: Vec<PathBuf>,
+        settings: SettingsManager,
+        approval: Arc<GitApproval>,
+    ) -> Result<Self> {
+        Ok(Self {
+            workspace: WorkspacePaths::new(workspace_roots)?,
+            settings,
+            approval,
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for GitBranchTool {
+    fn name(&self) -> String {
+        "git_branch".to_string()
+    }
+
+    fn description(&self) -> String {
+        "List local git branches, show the current branch, or check out a branch. Checkout is refused while tracked files have uncommitted changes unless force is set; force discards those changes.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "current", "checkout"],
+                    "description": "list: all local branches; current: the checked out branch; checkout: switch to `branch`"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Branch to check out (required for checkout)"
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Check out even if tracked files have uncommitted changes, discarding them"
+                },
+                "workspace_root": {
+                    "type": "string",
+                    "description": "Workspace root of the repository. Required when there are multiple workspace roots"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Execution
+    }
+
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write, ToolCapability::Execute]
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let action = match request.arguments["action"].as_str() {
+            Some("list") => BranchAction::List,
+            Some("current") => BranchAction::Current,
+            Some("checkout") => {
+                let Some(branch) = request.arguments["branch"].as_str() else {
+                    bail!("Missing required argument \"branch\" for checkout");
+                };
+                if branch.is_empty() || branch.starts_with('-') {
+                    bail!("Invalid branch name: {branch:?}");
+                }
+                BranchAction::Checkout {
+                    branch: branch.to_string(),
+                    force: request.arguments["force"].as_bool().unwrap_or(false),
+                }
+            }
+            Some(other) => bail!("Invalid action {other:?}: expected list, current or checkout"),
+            None => bail!("Missing required argument \"action\""),
+        };
+        let root = target_root(&self.workspace, &request.arguments)?;
+
+        let description = match &action {
+            BranchAction::Checkout { branch, force } => Some(format!(
+                "check out branch '{branch}' in {}{}",
+                root.display(),
+                if *force {
+                    ", discarding uncommitted changes"
+                } else {
+                    ""
+                }
+            )),
+            _ => None,
+        };
+
+        let handle: Box<dyn ToolCallHandle> = Box::new(GitBranchHandle {
+            root,
+            action,
+            arguments: request.arguments.clone(),
+            tool_use_id: request.tool_use_id.clone(),
+        });
+
+        let configured = self
+            .settings
+            .get_module_config::<Git>(Git::NAMESPACE)
+            .confirm_changes;
+        let confirm = self
+            .settings
+            .settings()
+            .autonomy_level_for(&request.agent)
+            .confirms_writes(configured);
+        Ok(match description {
+            Some(description) if confirm => self.approval.gate(handle, description),
+            _ => handle,
+        })
+    }
+}
+
+struct GitBranchHandle {
+    root: PathBuf,
+    action: BranchAction,
+    arguments: Value,
+    tool_use_id: String,
+}
+
+impl GitBranchHandle {
+    async fn run(&self) -> Result<(String, Value)> {
+        let action = self.action.clone();
+        with_repo(&self.root, move |repo| match action {
+            BranchAction::List => list(repo),
+            BranchAction::Current => current(repo),
+            BranchAction::Checkout { branch, force } => checkout(repo, &branch, force),
+        })
+        .await
+    }
+}
+
+/// The checked out branch, or None when HEAD is detached.
+fn current_branch(repo: &Repository) -> Result<Option<String>> {
+    let head = repo.find_reference("HEAD")?;
+    Ok(head
+        .symbolic_target()
+        .and_then(|target| target.strip_prefix("refs/heads/"))
+        .map(str::to_string))
+}
+
+fn list(repo: &Repository) -> Result<(String, Value)> {
+    let current = current_branch(repo)?;
+    let mut branches = Vec::new();
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        if let Some(name) = branch.name()? {
+            branches.push(name.to_string());
+        }
+    }
+    branches.sort();
+
+    let content = branches
+        .iter()
+        .map(|branch| {
+            let marker = if Some(branch) == current.as_ref() {
+                "* "
+            } else {
+                "  "
+            };
+            format!("{marker}{branch}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok((content, json!({ "current": current, "branches": branches })))
+}
+
+fn current(repo: &Repository) -> Result<(String, Value)> {
+    if let Some(current) = current_branch(repo)? {
+        return Ok((current.clone(), json!({ "current": current })));
+    }
+    let head = repo.head()?.peel_to_commit()?.as_object().short_id()?;
+    let head = head.as_str().unwrap_or_default();
+    Ok((
+        format!("HEAD is detached at {head}"),
+        json!({ "current": null, "head": head }),
+    ))
+}
+
+fn checkout(repo: &Repository, branch: &str, force: bool) -> Result<(String, Value)> {
+    let target = repo
+        .find_branch(branch, BranchType::Local)
+        .with_context(|| format!("Branch '{branch}' not found"))?;
+    let Some(refname) = target.get().name() else {
+        bail!("Branch name is not valid UTF-8: {branch:?}");
+    };
+    let commit = target.get().peel_to_commit()?;
+
+    if !force {
+        let changed = changed_tracked_files(repo)?;
+        if !changed.is_empty() {
+            bail!(
+                "Refusing to check out '{branch}': the working tree has uncommitted \
+                 changes to tracked files. Commit or stash them, or pass force=true \
+                 to discard them.\n{}",
+                changed.join("\n")
+            );
+        }
+    }
+
+    let mut options = CheckoutBuilder::new();
+    if force {
+        options.force();
+    } else {
+        options.safe();
+    }
+    repo.checkout_tree(commit.as_object(), Some(&mut options))?;
+    repo.set_head(refname)?;
+    Ok((
+        format!("Checked out branch '{branch}'"),
+        json!({ "current": branch }),
+    ))
+}
+
+/// Tracked files with staged or unstaged changes.
+fn changed_tracked_files(repo: &Repository) -> Result<Vec<String>> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(false).include_ignored(false);
+    let statuses = repo.statuses(Some(&mut options))?;
+    Ok(statuses
+        .iter()
+        .filter(|entry| entry.status() != Status::CURRENT)
+        .map(|entry| String::from_utf8_lossy(entry.path_bytes()).to_string())
+        .collect())
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for GitBranchHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "git_branch".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: self.arguments.clone(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        match self.run().await {
+            Ok((content, result)) => ToolOutput::Result {
+                content,
+                is_error: false,
+                continuation: ContinuationPreference::Continue,
+                ui_result: ToolExecutionResult::Other { result },
+            },
+            Err(e) => ToolOutput::Result {
+                content: format!("{e:#}"),
+                is_error: true,
+                continuation: ContinuationPreference::Continue,
+                ui_result: ToolExecutionResult::Error {
+                    short_message: "git_branch failed".to_string(),
+                    detailed_message: format!("{e:#}"),
+                    category: None,
+                },
+            },
+        }
+    }
+}
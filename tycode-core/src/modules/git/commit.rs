@@ -0,0 +1,384 @@
+//! `git_commit` tool: stage the given paths and commit them.
+//!
+//! Only the listed paths are committed, even if other changes are already
+//! staged. The tool never amends, rewrites or pushes history. When no message
+//! is given, a Conventional Commits style message is generated from the
+//! staged diff with a model at `commit_message_cost`. The message is generated
+//! before the approval prompt, so the user approves the exact message.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use git2::{
+    Commit, DiffFormat, ErrorCode, Index, IndexAddOption, Pathspec, PathspecFlags, Repository, Tree,
+};
+use serde_json::{json, Value};
+
+use crate::ai::model::{Model, ModelCost};
+use crate::ai::types::{ConversationRequest, Message};
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::workspace::WorkspacePaths;
+use crate::modules::image::SharedProvider;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput,
+    ToolRequest,
+};
+use crate::tools::ToolName;
+
+use super::approval::GitApproval;
+use super::config::Git;
+use super::{repo_relative, target_root, with_repo};
+
+/// Diff bytes sent to the model when generating a message.
+const MAX_DIFF_BYTES: usize = 20_000;
+
+const MESSAGE_PROMPT: &str = "Write a git commit message for the staged diff below in the Conventional Commits format: a `type(scope): summary` subject line of at most 72 characters, optionally followed by a blank line and a short body explaining why. Reply with the commit message only.";
+
+pub struct GitCommitTool {
+    workspace: WorkspacePaths,
+    settings: SettingsManager,
+    provider: SharedProvider,
+    approval: Arc<GitApproval>,
+}
+
+impl GitCommitTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("git_commit")
+    }
+
+    pub fn new(
+        workspace_roots: Vec<PathBuf>,
+        settings: SettingsManager,
+        provider: SharedProvider,
+        approval: Arc<GitApproval>,
+    ) -> Result<Self> {
+        Ok(Self {
+            workspace: WorkspacePaths::new(workspace_roots)?,
+            settings,
+            provider,
+            approval,
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for GitCommitTool {
+    fn name(&self) -> String {
+        "git_commit".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Stage the given files and commit them. Only the listed paths are committed. If message is omitted, a Conventional Commits message is generated from the diff. Returns the new commit hash.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "minItems": 1,
+                    "description": "Absolute paths of the files to commit, inside the repository's workspace root"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Commit message. Omit to generate one from the diff"
+                },
+                "workspace_root": {
+                    "type": "string",
+                    "description": "Workspace root of the repository. Required when there are multiple workspace roots"
+                }
+            },
+            "required": ["paths"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Execution
+    }
+
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write, ToolCapability::Execute]
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let Some(raw_paths) = request.arguments["paths"].as_array() else {
+            bail!("Missing required argument \"paths\"");
+        };
+        if raw_paths.is_empty() {
+            bail!("paths must list at least one file");
+        }
+        let root = target_root(&self.workspace, &request.arguments)?;
+
+        let mut paths = Vec::with_capacity(raw_paths.len());
+        for raw in raw_paths {
+            let Some(raw) = raw.as_str() else {
+                bail!("paths must be strings");
+            };
+            let path = self.workspace.resolve(raw)?;
+            if !path.starts_with(&root) {
+                bail!(
+                    "Path is outside the repository root {}: {raw}",
+                    root.display()
+                );
+            }
+            paths.push(path);
+        }
+
+        let message = match request.arguments["message"].as_str().map(str::trim) {
+            Some("") => bail!("message must not be empty; omit it to generate one"),
+            message => message.map(str::to_string),
+        };
+
+        // Generated before asking for approval, so the user sees the message
+        // that will be committed
+        let config: Git = self.settings.get_module_config(Git::NAMESPACE);
+        let message = match message {
+            Some(message) => message,
+            None => {
+                let staged = paths.clone();
+                let diff = with_repo(&root, move |repo| prepare(repo, &staged)?.diff(repo)).await?;
+                generate_message(&self.provider, config.commit_message_cost, diff).await?
+            }
+        };
+
+        let description = format!(
+            "commit {} in {} with message \"{message}\"",
+            paths
+                .iter()
+                .map(|p| p.strip_prefix(&root).unwrap_or(p).display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            root.display(),
+        );
+
+        let handle: Box<dyn ToolCallHandle> = Box::new(GitCommitHandle {
+            root,
+            paths,
+            message,
+            arguments: request.arguments.clone(),
+            tool_use_id: request.tool_use_id.clone(),
+        });
+
+        let confirm = self
+            .settings
+            .settings()
+            .autonomy_level_for(&request.agent)
+            .confirms_writes(config.confirm_changes);
+        Ok(if confirm {
+            self.approval.gate(handle, description)
+        } else {
+            handle
+        })
+    }
+}
+
+struct GitCommitHandle {
+    root: PathBuf,
+    paths: Vec<PathBuf>,
+    message: String,
+    arguments: Value,
+    tool_use_id: String,
+}
+
+impl GitCommitHandle {
+    async fn commit(&self) -> Result<String> {
+        let paths = self.paths.clone();
+        let message = self.message.clone();
+        with_repo(&self.root, move |repo| {
+            prepare(repo, &paths)?.commit(repo, &message)
+        })
+        .await
+    }
+}
+
+/// Asks a model at `cost` for a Conventional Commits message describing
+/// `diff`.
+async fn generate_message(
+    provider: &SharedProvider,
+    cost: ModelCost,
+    mut diff: String,
+) -> Result<String> {
+    if diff.len() > MAX_DIFF_BYTES {
+        let mut end = MAX_DIFF_BYTES;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        diff.truncate(end);
+        diff.push_str("\n[diff truncated]");
+    }
+
+    let provider = provider.read().unwrap().clone();
+    let model = Model::select_for_cost(provider.as_ref(), cost).with_context(|| {
+        format!(
+            "No model available for {cost:?} to generate a commit message; pass message instead"
+        )
+    })?;
+    let response = provider
+        .converse(ConversationRequest {
+            messages: vec![Message::user(format!("{MESSAGE_PROMPT}\n\n{diff}"))],
+            model,
+            system_prompt: "You write concise, accurate git commit messages.".to_string(),
+            stop_sequences: vec![],
+            tools: vec![],
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to generate a commit message: {e:?}"))?;
+
+    let message = response
+        .content
+        .text()
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("```"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    if message.is_empty() {
+        bail!("The model returned an empty commit message; pass message instead");
+    }
+    Ok(message)
+}
+
+/// The paths staged in a copy of the index, and the tree they produce: HEAD
+/// with only those paths updated, so that other staged changes stay out of
+/// the commit (like `git commit --only`).
+struct Prepared<'r> {
+    index: Index,
+    head: Option<Commit<'r>>,
+    head_tree: Option<Tree<'r>>,
+    tree: Tree<'r>,
+}
+
+/// Stages `paths` (absolute) like `git add --all -- <paths>` without writing
+/// the index yet.
+fn prepare<'r>(repo: &'r Repository, paths: &[PathBuf]) -> Result<Prepared<'r>> {
+    // An empty pathspec would stage every change in the repository
+    if paths.is_empty() {
+        bail!("No paths to commit");
+    }
+    let paths = paths
+        .iter()
+        .map(|path| repo_relative(repo, path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut index = repo.index()?;
+    index.add_all(&paths, IndexAddOption::DEFAULT, None)?;
+    index.update_all(&paths, None)?;
+
+    let head = match repo.head() {
+        Ok(head) => Some(head.peel_to_commit()?),
+        Err(e) if e.code() == ErrorCode::UnbornBranch || e.code() == ErrorCode::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let head_tree = head.as_ref().map(Commit::tree).transpose()?;
+
+    let pathspec = Pathspec::new(&paths)?;
+    let staged: HashSet<Vec<u8>> = pathspec
+        .match_index(&index, PathspecFlags::DEFAULT)?
+        .entries()
+        .map(<[u8]>::to_vec)
+        .collect();
+    let mut tree_index = Index::new()?;
+    if let Some(head_tree) = &head_tree {
+        tree_index.read_tree(head_tree)?;
+    }
+    tree_index.remove_all(&paths, None)?;
+    for entry in index.iter().filter(|entry| staged.contains(&entry.path)) {
+        tree_index.add(&entry)?;
+    }
+    let tree = repo.find_tree(tree_index.write_tree_to(repo)?)?;
+
+    let unchanged = match &head_tree {
+        Some(head_tree) => head_tree.id() == tree.id(),
+        None => tree.is_empty(),
+    };
+    if unchanged {
+        bail!("Nothing to commit: the given paths have no changes");
+    }
+    Ok(Prepared {
+        index,
+        head,
+        head_tree,
+        tree,
+    })
+}
+
+impl Prepared<'_> {
+    /// The patch the commit would introduce.
+    fn diff(&self, repo: &Repository) -> Result<String> {
+        let diff = repo.diff_tree_to_tree(self.head_tree.as_ref(), Some(&self.tree), None)?;
+        let mut patch = String::new();
+        diff.print(DiffFormat::Patch, |_, _, line| {
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                patch.push(line.origin());
+            }
+            patch.push_str(&String::from_utf8_lossy(line.content()));
+            true
+        })?;
+        Ok(patch)
+    }
+
+    /// Writes the staged paths to the index and commits the tree on HEAD,
+    /// returning the new commit hash.
+    fn commit(&mut self, repo: &Repository, message: &str) -> Result<String> {
+        let signature = repo
+            .signature()
+            .context("git user.name and user.email must be configured to commit")?;
+        self.index.write()?;
+        let parents: Vec<&Commit> = self.head.iter().collect();
+        let oid = repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &self.tree,
+            &parents,
+        )?;
+        Ok(oid.to_string())
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for GitCommitHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "git_commit".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: self.arguments.clone(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        match self.commit().await {
+            Ok(hash) => {
+                let message = &self.message;
+                let subject = message.lines().next().unwrap_or_default();
+                ToolOutput::Result {
+                    content: format!("Committed {hash}: {subject}"),
+                    is_error: false,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::Other {
+                        result: json!({ "commit": hash, "message": message }),
+                    },
+                }
+            }
+            Err(e) => ToolOutput::Result {
+                content: format!("{e:#}"),
+                is_error: true,
+                continuation: ContinuationPreference::Continue,
+                ui_result: ToolExecutionResult::Error {
+                    short_message: "git_commit failed".to_string(),
+                    detailed_message: format!("{e:#}"),
+                    category: None,
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Git module configuration.
+
+use crate::ai::model::ModelCost;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+fn default_confirm_changes() -> bool {
+    true
+}
+
+fn default_commit_message_cost() -> ModelCost {
+    ModelCost::Low
+}
+
+/// Structured git tools for inspecting and changing the repository state.
+/// Off by default; agents can always fall back to running git through bash.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[schemars(title = "Git")]
+pub struct Git {
+    /// Offer the git tools to agents.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Ask for a y/n confirmation before a git tool changes the repository
+    /// (checking out a branch or committing). Applies at the balanced
+    /// autonomy level; conservative agents always confirm and autonomous
+    /// ones never do.
+    #[serde(default = "default_confirm_changes")]
+    pub confirm_changes: bool,
+
+    /// Cost tier of the model that writes commit messages when git_commit is
+    /// called without one.
+    #[serde(default = "default_commit_message_cost")]
+    pub commit_message_cost: ModelCost,
+}
+
+impl Git {
+    pub const NAMESPACE: &str = "git";
+}
+
+impl Default for Git {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            confirm_changes: default_confirm_changes(),
+            commit_message_cost: default_commit_message_cost(),
+        }
+    }
+}
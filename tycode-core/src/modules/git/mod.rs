@@ -0,0 +1,167 @@
+//! Git module - structured git tools as an alternative to free-form git
+//! commands through bash.
+//!
+//! Disabled by default; enable with:
+//!
+//! ```toml
+//! [modules.git]
+//! enabled = true
+//! ```
+//!
+//! The tools are only offered when a workspace root is inside a git
+//! repository. They use libgit2 rather than the git CLI, so git does not need
+//! to be installed.
+//!
+//! Tools that change the repository ask the user for a y/n confirmation
+//! first unless `confirm_changes` is turned off.
+
+pub mod approval;
+pub mod branch;
+pub mod commit;
+pub mod config;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use git2::Repository;
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+use serde_json::Value;
+
+use crate::file::workspace::WorkspacePaths;
+use crate::module::{ContextComponent, Module, PromptComponent};
+use crate::modules::image::SharedProvider;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::SharedTool;
+
+use approval::GitApproval;
+use branch::GitBranchTool;
+use commit::GitCommitTool;
+pub use config::Git;
+
+/// Opens the repository containing `root`.
+pub(crate) fn open_repo(root: &Path) -> Result<Repository> {
+    Repository::discover(root)
+        .with_context(|| format!("{} is not inside a git repository", root.display()))
+}
+
+/// Runs `f` on the repository containing `root`. git2 calls block, so they
+/// run on the blocking thread pool.
+pub(crate) async fn with_repo<T, F>(root: &Path, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&Repository) -> Result<T> + Send + 'static,
+{
+    let root = root.to_path_buf();
+    tokio::task::spawn_blocking(move || f(&open_repo(&root)?)).await?
+}
+
+/// `path` (canonical, as resolved by [`WorkspacePaths`]) relative to the
+/// working directory of `repo`, which is how git2 addresses files.
+pub(crate) fn repo_relative(repo: &Repository, path: &Path) -> Result<PathBuf> {
+    let Some(workdir) = repo.workdir() else {
+        bail!("Bare repositories are not supported");
+    };
+    let workdir = workdir.canonicalize()?;
+    match path.strip_prefix(&workdir) {
+        Ok(relative) => Ok(relative.to_path_buf()),
+        Err(_) => bail!(
+            "Path is outside the repository at {}: {}",
+            workdir.display(),
+            path.display()
+        ),
+    }
+}
+
+/// The repository a git tool call targets: the given `workspace_root`, or
+/// the only configured root when it is omitted.
+pub(crate) fn target_root(workspace: &WorkspacePaths, arguments: &Value) -> Result<PathBuf> {
+    if let Some(root) = arguments["workspace_root"].as_str() {
+        return workspace.resolve_root(root);
+    }
+    match workspace.roots().as_slice() {
+        [root] => Ok(root.clone()),
+        [] => bail!("No workspace roots configured"),
+        roots => bail!("workspace_root is required with multiple workspace roots: {roots:?}"),
+    }
+}
+
+/// Bundles:
+/// - Tool: GitBranchTool (list, current, checkout)
+/// - Tool: GitCommitTool (stage and commit paths)
+pub struct GitModule {
+    branch: Arc<GitBranchTool>,
+    commit: Arc<GitCommitTool>,
+    approval: Arc<GitApproval>,
+    settings: SettingsManager,
+    workspace_roots: Vec<PathBuf>,
+}
+
+impl GitModule {
+    pub fn new(
+        workspace_roots: Vec<PathBuf>,
+        settings: SettingsManager,
+        provider: SharedProvider,
+    ) -> Result<Self> {
+        let approval = Arc::new(GitApproval::default());
+        Ok(Self {
+            branch: Arc::new(GitBranchTool::new(
+                workspace_roots.clone(),
+                settings.clone(),
+                approval.clone(),
+            )?),
+            commit: Arc::new(GitCommitTool::new(
+                workspace_roots.clone(),
+                settings.clone(),
+                provider,
+                approval.clone(),
+            )?),
+            approval,
+            settings,
+            workspace_roots,
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Module for GitModule {
+    fn prompt_components(&self) -> Vec<Arc<dyn PromptComponent>> {
+        vec![]
+    }
+
+    fn context_components(&self) -> Vec<Arc<dyn ContextComponent>> {
+        vec![]
+    }
+
+    async fn tools(&self) -> Vec<SharedTool> {
+        if !self
+            .settings
+            .get_module_config::<Git>(Git::NAMESPACE)
+            .enabled
+        {
+            return vec![];
+        }
+        // Without a repository every call would fail
+        if !self
+            .workspace_roots
+            .iter()
+            .any(|root| Repository::discover(root).is_ok())
+        {
+            return vec![];
+        }
+        vec![self.branch.clone(), self.commit.clone()]
+    }
+
+    fn settings_namespace(&self) -> Option<&'static str> {
+        Some(Git::NAMESPACE)
+    }
+
+    fn settings_json_schema(&self) -> Option<RootSchema> {
+        Some(schema_for!(Git))
+    }
+
+    async fn on_user_input(&self, input: &str) -> Option<String> {
+        self.approval.resolve(input).await
+    }
+}
@@ -17,6 +17,10 @@ fn default_image_size() -> String {
     "1K".to_string()
 }
 
+fn default_max_image_count() -> u32 {
+    4
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Image {
     #[serde(default = "default_enabled")]
@@ -30,6 +34,10 @@ pub struct Image {
 
     #[serde(default = "default_image_size")]
     pub default_image_size: String,
+
+    /// Most variants one generate_image call may ask for.
+    #[serde(default = "default_max_image_count")]
+    pub max_image_count: u32,
 }
 
 impl Default for Image {
@@ -39,6 +47,7 @@ impl Default for Image {
             model: default_image_model(),
             default_aspect_ratio: default_aspect_ratio(),
             default_image_size: default_image_size(),
+            max_image_count: default_max_image_count(),
         }
     }
 }
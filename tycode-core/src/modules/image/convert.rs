@@ -0,0 +1,277 @@
+//! `convert_image` tool: resizes or re-encodes a workspace image, e.g. PNG to
+//! JPEG or a screenshot scaled down so it fits `read_image`'s size limit.
+//! Formats come from the file extensions; SVG is not supported since it is
+//! not a raster format.
+//!
+//! An existing output file is only replaced when `overwrite` is set. Writes
+//! go through the file edit wrappers, so they are confirmed when edits are and
+//! can be reverted with `/undo`.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, ImageFormat};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::media_type_from_extension;
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::access::FileAccessManager;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput,
+    ToolRequest,
+};
+use crate::tools::ToolName;
+
+pub struct ConvertImageTool {
+    file_access: Arc<FileAccessManager>,
+}
+
+impl ConvertImageTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("convert_image")
+    }
+
+    pub fn new(file_access: Arc<FileAccessManager>) -> Self {
+        Self { file_access }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ConvertImageInput {
+    input_path: String,
+    output_path: String,
+    max_dimension: Option<u32>,
+    #[serde(default)]
+    overwrite: bool,
+}
+
+/// The raster format for `path`'s extension, if it is one the tool can read
+/// and write.
+fn raster_format(path: &str) -> Result<ImageFormat> {
+    let format = media_type_from_extension(path).and_then(ImageFormat::from_mime_type);
+    match format {
+        Some(
+            format @ (ImageFormat::Png
+            | ImageFormat::Jpeg
+            | ImageFormat::Gif
+            | ImageFormat::WebP
+            | ImageFormat::Bmp),
+        ) => Ok(format),
+        _ => {
+            bail!("Unsupported image format for: {path}. Supported: png, jpg, jpeg, gif, webp, bmp")
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConvertedImage {
+    pub data: Vec<u8>,
+    pub original: (u32, u32),
+    pub dimensions: (u32, u32),
+}
+
+/// Decodes `data`, shrinks it so neither side exceeds `max_dimension`
+/// (keeping the aspect ratio; smaller images are never enlarged) and encodes
+/// it as `output`.
+pub fn convert_image(
+    data: &[u8],
+    input: ImageFormat,
+    output: ImageFormat,
+    max_dimension: Option<u32>,
+) -> Result<ConvertedImage> {
+    let mut image = image::load_from_memory_with_format(data, input)
+        .context("Failed to decode the input image")?;
+    let original = image.dimensions();
+
+    if let Some(max) = max_dimension {
+        if max == 0 {
+            bail!("max_dimension must be at least 1");
+        }
+        if original.0 > max || original.1 > max {
+            image = image.resize(max, max, FilterType::Lanczos3);
+        }
+    }
+
+    // JPEG has no alpha channel
+    if output == ImageFormat::Jpeg {
+        image = DynamicImage::ImageRgb8(image.to_rgb8());
+    }
+
+    let mut encoded = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut encoded), output)
+        .context("Failed to encode the output image")?;
+
+    Ok(ConvertedImage {
+        data: encoded,
+        original,
+        dimensions: image.dimensions(),
+    })
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for ConvertImageTool {
+    fn name(&self) -> String {
+        "convert_image".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Resize or convert an image in the workspace and write the result to a new file. The output format follows the output_path extension (e.g. .png to .jpg). Use max_dimension to shrink large images, e.g. so read_image can load them. Fails if output_path exists unless overwrite is set.".to_string()
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "input_path": {
+                    "type": "string",
+                    "description": "Absolute path inside a workspace root to the image to convert (png, jpg, jpeg, gif, webp or bmp)"
+                },
+                "output_path": {
+                    "type": "string",
+                    "description": "Absolute path inside a workspace root to write the result to; its extension selects the format"
+                },
+                "max_dimension": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Scale the image down, keeping its aspect ratio, so neither side is larger than this many pixels. Smaller images are not enlarged"
+                },
+                "overwrite": {
+                    "type": "boolean",
+                    "description": "Replace output_path if it already exists. Defaults to false"
+                }
+            },
+            "required": ["input_path", "output_path"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Execution
+    }
+
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write]
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let mut input: ConvertImageInput = serde_json::from_value(request.arguments.clone())?;
+        for path in [&mut input.input_path, &mut input.output_path] {
+            *path = self
+                .file_access
+                .resolve(path.as_str())?
+                .to_string_lossy()
+                .to_string();
+        }
+        let input_format = raster_format(&input.input_path)?;
+        let output_format = raster_format(&input.output_path)?;
+        if !input.overwrite && tokio::fs::try_exists(&input.output_path).await? {
+            bail!(
+                "{} already exists; pass overwrite=true to replace it",
+                self.file_access.display_path(Path::new(&input.output_path))
+            );
+        }
+
+        Ok(Box::new(ConvertImageHandle {
+            input,
+            input_format,
+            output_format,
+            tool_use_id: request.tool_use_id.clone(),
+            file_access: self.file_access.clone(),
+        }))
+    }
+}
+
+struct ConvertImageHandle {
+    input: ConvertImageInput,
+    input_format: ImageFormat,
+    output_format: ImageFormat,
+    tool_use_id: String,
+    file_access: Arc<FileAccessManager>,
+}
+
+impl ConvertImageHandle {
+    fn shown(&self, path: &str) -> String {
+        self.file_access.display_path(Path::new(path))
+    }
+
+    async fn convert(&self) -> Result<ConvertedImage> {
+        let data = self.file_access.read_bytes(&self.input.input_path).await?;
+        let (input, output, max_dimension) = (
+            self.input_format,
+            self.output_format,
+            self.input.max_dimension,
+        );
+        // Decoding and resampling large images is CPU-bound
+        let converted =
+            tokio::task::spawn_blocking(move || convert_image(&data, input, output, max_dimension))
+                .await??;
+        self.file_access
+            .write_bytes(&self.input.output_path, &converted.data)
+            .await?;
+        Ok(converted)
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for ConvertImageHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "convert_image".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: json!({
+                    "input_path": self.shown(&self.input.input_path),
+                    "output_path": self.shown(&self.input.output_path),
+                    "max_dimension": self.input.max_dimension,
+                    "overwrite": self.input.overwrite
+                }),
+            },
+        }
+    }
+
+    fn edited_path(&self) -> Option<PathBuf> {
+        Some(PathBuf::from(&self.input.output_path))
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        match self.convert().await {
+            Ok(converted) => {
+                let (width, height) = converted.dimensions;
+                let (original_width, original_height) = converted.original;
+                let output_path = self.shown(&self.input.output_path);
+                ToolOutput::Result {
+                    content: format!(
+                        "Converted {} ({original_width}x{original_height}) to {output_path} ({width}x{height}, {} bytes)",
+                        self.shown(&self.input.input_path),
+                        converted.data.len()
+                    ),
+                    is_error: false,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::Other {
+                        result: json!({
+                            "path": output_path,
+                            "width": width,
+                            "height": height,
+                            "size_bytes": converted.data.len(),
+                        }),
+                    },
+                }
+            }
+            Err(e) => ToolOutput::Result {
+                content: format!(
+                    "Failed to convert {}: {e:?}",
+                    self.shown(&self.input.input_path)
+                ),
+                is_error: true,
+                continuation: ContinuationPreference::Continue,
+                ui_result: ToolExecutionResult::Other {
+                    result: json!({"error": format!("{e:?}")}),
+                },
+            },
+        }
+    }
+}
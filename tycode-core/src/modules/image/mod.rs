@@ -1,24 +1,30 @@
-use std::sync::{Arc, RwLock};
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use serde::Deserialize;
 use serde_json::json;
 
 use crate::ai::provider::AiProvider;
-use crate::ai::types::ImageGenerationRequest;
-use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::ai::types::{ImageGenerationRequest, ImageGenerationStatus};
+use crate::chat::events::{
+    ChatEvent, EventSender, ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType,
+};
 use crate::file::access::FileAccessManager;
+use crate::file::modify::EditWrapper;
 use crate::module::{ContextComponent, Module, PromptComponent};
 use crate::settings::SettingsManager;
 use crate::tools::r#trait::{
-    ContinuationPreference, SharedTool, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput,
-    ToolRequest,
+    ContinuationPreference, SharedTool, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor,
+    ToolOutput, ToolRequest,
 };
 use crate::tools::ToolName;
 
 pub mod config;
+pub mod convert;
 
 use config::Image;
+pub use convert::ConvertImageTool;
 
 const MAX_READ_IMAGE_BYTES: usize = 5 * 1024 * 1024;
 
@@ -28,6 +34,8 @@ pub struct ImageModule {
     provider: SharedProvider,
     file_access: Arc<FileAccessManager>,
     settings: Arc<SettingsManager>,
+    event_sender: EventSender,
+    edits: EditWrapper,
 }
 
 impl ImageModule {
@@ -35,11 +43,15 @@ impl ImageModule {
         provider: SharedProvider,
         file_access: Arc<FileAccessManager>,
         settings: Arc<SettingsManager>,
+        event_sender: EventSender,
+        edits: EditWrapper,
     ) -> Self {
         Self {
             provider,
             file_access,
             settings,
+            event_sender,
+            edits,
         }
     }
 }
@@ -59,19 +71,25 @@ impl Module for ImageModule {
         if !config.enabled {
             return vec![];
         }
+        // Converting is purely local, so it does not depend on the provider
+        let convert = self
+            .edits
+            .wrap(Arc::new(ConvertImageTool::new(self.file_access.clone())));
         let provider = self.provider.read().unwrap();
         if !provider.supports_image_generation() {
-            return vec![];
+            return vec![convert];
         }
         vec![
             Arc::new(GenerateImageTool {
                 provider: self.provider.clone(),
                 file_access: self.file_access.clone(),
+                event_sender: self.event_sender.clone(),
                 config,
             }),
             Arc::new(ReadImageTool {
                 file_access: self.file_access.clone(),
             }),
+            convert,
         ]
     }
 
@@ -99,7 +117,7 @@ struct ReadImageInput {
     file_path: String,
 }
 
-fn media_type_from_extension(path: &str) -> Option<&'static str> {
+pub(crate) fn media_type_from_extension(path: &str) -> Option<&'static str> {
     let ext = path.rsplit('.').next()?.to_ascii_lowercase();
     match ext.as_str() {
         "png" => Some("image/png"),
@@ -169,19 +187,23 @@ impl ToolCallHandle for ReadImageHandle {
             tool_call_id: self.tool_use_id.clone(),
             tool_name: "read_image".to_string(),
             tool_type: ToolRequestType::Other {
-                args: json!({ "file_path": self.input.file_path }),
+                args: json!({
+                    "file_path": self.file_access.display_path(Path::new(&self.input.file_path))
+                }),
             },
         }
     }
 
     async fn execute(self: Box<Self>) -> ToolOutput {
+        let shown = self
+            .file_access
+            .display_path(Path::new(&self.input.file_path));
         let media_type = match media_type_from_extension(&self.input.file_path) {
             Some(mt) => mt,
             None => {
                 return ToolOutput::Result {
                     content: format!(
-                        "Unsupported image format for: {}. Supported: png, jpg, jpeg, gif, webp, svg, bmp",
-                        self.input.file_path
+                        "Unsupported image format for: {shown}. Supported: png, jpg, jpeg, gif, webp, svg, bmp"
                     ),
                     is_error: true,
                     continuation: ContinuationPreference::Continue,
@@ -196,7 +218,7 @@ impl ToolCallHandle for ReadImageHandle {
             Ok(d) => d,
             Err(e) => {
                 return ToolOutput::Result {
-                    content: format!("Failed to read image {}: {e:?}", self.input.file_path),
+                    content: format!("Failed to read image {shown}: {e:?}"),
                     is_error: true,
                     continuation: ContinuationPreference::Continue,
                     ui_result: ToolExecutionResult::Other {
@@ -209,7 +231,7 @@ impl ToolCallHandle for ReadImageHandle {
         if data.len() > MAX_READ_IMAGE_BYTES {
             return ToolOutput::Result {
                 content: format!(
-                    "Image too large: {} bytes (max {} bytes). Resize the image first.",
+                    "Image too large: {} bytes (max {} bytes). Resize it with convert_image first.",
                     data.len(),
                     MAX_READ_IMAGE_BYTES
                 ),
@@ -223,15 +245,12 @@ impl ToolCallHandle for ReadImageHandle {
 
         let size_bytes = data.len();
         ToolOutput::ImageResult {
-            content: format!(
-                "Image loaded: {} ({}, {} bytes)",
-                self.input.file_path, media_type, size_bytes
-            ),
+            content: format!("Image loaded: {shown} ({media_type}, {size_bytes} bytes)"),
             images: vec![(data, media_type.to_string())],
             continuation: ContinuationPreference::Continue,
             ui_result: ToolExecutionResult::Other {
                 result: json!({
-                    "path": self.input.file_path,
+                    "path": shown,
                     "media_type": media_type,
                     "size_bytes": size_bytes,
                 }),
@@ -243,6 +262,7 @@ impl ToolCallHandle for ReadImageHandle {
 pub struct GenerateImageTool {
     provider: SharedProvider,
     file_access: Arc<FileAccessManager>,
+    event_sender: EventSender,
     config: Image,
 }
 
@@ -258,6 +278,31 @@ struct GenerateImageInput {
     output_path: String,
     aspect_ratio: Option<String>,
     image_size: Option<String>,
+    count: Option<u32>,
+}
+
+/// Where each of `count` variants is saved: `output_path` itself for a
+/// single image, otherwise `name-1.png`, `name-2.png` and so on.
+fn variant_paths(output_path: &str, count: u32) -> Vec<String> {
+    if count <= 1 {
+        return vec![output_path.to_string()];
+    }
+    let path = Path::new(output_path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..=count)
+        .map(|i| {
+            path.with_file_name(format!("{stem}-{i}{extension}"))
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect()
 }
 
 #[async_trait::async_trait(?Send)]
@@ -267,7 +312,7 @@ impl ToolExecutor for GenerateImageTool {
     }
 
     fn description(&self) -> String {
-        "Generate an image from a text prompt and save it to a file in the workspace.".to_string()
+        "Generate an image from a text prompt and save it to a file in the workspace. Set count to get several variants of the same prompt, saved with numbered suffixes.".to_string()
     }
 
     fn input_schema(&self) -> serde_json::Value {
@@ -289,6 +334,12 @@ impl ToolExecutor for GenerateImageTool {
                 "image_size": {
                     "type": "string",
                     "description": "Image resolution: 1K, 2K, or 4K"
+                },
+                "count": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": self.config.max_image_count.max(1),
+                    "description": "Number of variants to generate (default 1). With more than one, output_path gets a numbered suffix per image, e.g. logo-1.png, logo-2.png"
                 }
             },
             "required": ["prompt", "output_path"]
@@ -299,6 +350,10 @@ impl ToolExecutor for GenerateImageTool {
         ToolCategory::Execution
     }
 
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write, ToolCapability::Network]
+    }
+
     async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
         let mut input: GenerateImageInput = serde_json::from_value(request.arguments.clone())?;
         input.output_path = self
@@ -307,11 +362,21 @@ impl ToolExecutor for GenerateImageTool {
             .to_string_lossy()
             .to_string();
 
+        let max_count = self.config.max_image_count.max(1);
+        match input.count {
+            Some(0) => bail!("count must be at least 1"),
+            Some(count) if count > max_count => bail!(
+                "count {count} exceeds the configured maximum of {max_count} (image.max_image_count)"
+            ),
+            _ => {}
+        }
+
         Ok(Box::new(GenerateImageHandle {
             input,
             tool_use_id: request.tool_use_id.clone(),
             provider: self.provider.clone(),
             file_access: self.file_access.clone(),
+            event_sender: self.event_sender.clone(),
             config: self.config.clone(),
         }))
     }
@@ -322,9 +387,33 @@ struct GenerateImageHandle {
     tool_use_id: String,
     provider: SharedProvider,
     file_access: Arc<FileAccessManager>,
+    event_sender: EventSender,
     config: Image,
 }
 
+impl GenerateImageHandle {
+    fn report(&self, status: ImageGenerationStatus) {
+        self.event_sender.send(ChatEvent::ImageGenerationProgress {
+            tool_call_id: self.tool_use_id.clone(),
+            status,
+        });
+    }
+
+    fn failed(&self, content: String) -> ToolOutput {
+        self.report(ImageGenerationStatus::Failed {
+            error: content.clone(),
+        });
+        ToolOutput::Result {
+            content: content.clone(),
+            is_error: true,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Other {
+                result: json!({"error": content}),
+            },
+        }
+    }
+}
+
 #[async_trait::async_trait(?Send)]
 impl ToolCallHandle for GenerateImageHandle {
     fn tool_request(&self) -> ToolRequestEvent {
@@ -334,7 +423,8 @@ impl ToolCallHandle for GenerateImageHandle {
             tool_type: ToolRequestType::Other {
                 args: json!({
                     "prompt": self.input.prompt,
-                    "output_path": self.input.output_path
+                    "output_path": self.file_access.display_path(Path::new(&self.input.output_path)),
+                    "count": self.input.count.unwrap_or(1)
                 }),
             },
         }
@@ -349,57 +439,122 @@ impl ToolCallHandle for GenerateImageHandle {
             aspect_ratio: self
                 .input
                 .aspect_ratio
+                .clone()
                 .or(Some(self.config.default_aspect_ratio.clone())),
             image_size: self
                 .input
                 .image_size
+                .clone()
                 .or(Some(self.config.default_image_size.clone())),
         };
 
-        let result = match provider.generate_image(request).await {
-            Ok(response) => response,
-            Err(e) => {
-                return ToolOutput::Result {
-                    content: format!("Image generation failed: {e:?}"),
-                    is_error: true,
-                    continuation: ContinuationPreference::Continue,
-                    ui_result: ToolExecutionResult::Other {
-                        result: json!({"error": format!("{e:?}")}),
-                    },
+        let paths = variant_paths(&self.input.output_path, self.input.count.unwrap_or(1));
+        let batch_size = provider.max_images_per_request().max(1);
+
+        // The last status the provider reported, so a failure can say how
+        // far the request got
+        let stage = Mutex::new(ImageGenerationStatus::Started);
+        self.report(ImageGenerationStatus::Started);
+        let progress = |status: ImageGenerationStatus| {
+            *stage.lock().unwrap() = status.clone();
+            self.report(status);
+        };
+
+        // Images are saved as each batch arrives, so a later failure keeps
+        // the variants that were already generated
+        let mut saved: Vec<(String, usize, String)> = Vec::new();
+        while saved.len() < paths.len() {
+            let remaining = (paths.len() - saved.len()) as u32;
+            let images = if batch_size == 1 || remaining == 1 {
+                provider
+                    .generate_image_with_progress(request.clone(), &progress)
+                    .await
+                    .map(|image| vec![image])
+            } else {
+                provider
+                    .generate_images(request.clone(), batch_size.min(remaining), &progress)
+                    .await
+            };
+            let images = match images {
+                Ok(images) if !images.is_empty() => images,
+                Ok(_) => {
+                    return self.failed(format!(
+                        "Image generation returned no images{}",
+                        saved_note(&saved)
+                    ))
+                }
+                Err(e) => {
+                    let stage = match &*stage.lock().unwrap() {
+                        ImageGenerationStatus::Queued => "while queued",
+                        ImageGenerationStatus::Processing => "while processing",
+                        _ => "before the provider accepted it",
+                    };
+                    return self.failed(format!(
+                        "Image generation failed {stage}: {e:?}{}",
+                        saved_note(&saved)
+                    ));
+                }
+            };
+
+            for image in images {
+                let Some(path) = paths.get(saved.len()) else {
+                    break;
                 };
+                let shown = self.file_access.display_path(Path::new(path));
+                if let Err(e) = self.file_access.write_bytes(path, &image.image_data).await {
+                    return self.failed(format!(
+                        "Image was generated but could not be saved to {shown}: {e:?}{}",
+                        saved_note(&saved)
+                    ));
+                }
+                saved.push((shown, image.image_data.len(), image.media_type));
             }
-        };
+        }
 
-        match self
-            .file_access
-            .write_bytes(&self.input.output_path, &result.image_data)
-            .await
-        {
-            Ok(()) => ToolOutput::Result {
-                content: format!(
-                    "Image generated and saved to {} ({} bytes, {})",
-                    self.input.output_path,
-                    result.image_data.len(),
-                    result.media_type
-                ),
-                is_error: false,
-                continuation: ContinuationPreference::Continue,
-                ui_result: ToolExecutionResult::Other {
-                    result: json!({
-                        "path": self.input.output_path,
-                        "size_bytes": result.image_data.len(),
-                        "media_type": result.media_type
-                    }),
-                },
-            },
-            Err(e) => ToolOutput::Result {
-                content: format!("Failed to save image to {}: {e:?}", self.input.output_path),
-                is_error: true,
-                continuation: ContinuationPreference::Continue,
-                ui_result: ToolExecutionResult::Other {
-                    result: json!({"error": format!("{e:?}")}),
-                },
+        self.report(ImageGenerationStatus::Done);
+        let content = match saved.as_slice() {
+            [(path, size, media_type)] => {
+                format!("Image generated and saved to {path} ({size} bytes, {media_type})")
+            }
+            _ => {
+                let mut content = format!("Generated {} images:", saved.len());
+                for (path, size, media_type) in &saved {
+                    content.push_str(&format!("\n- {path} ({size} bytes, {media_type})"));
+                }
+                content
+            }
+        };
+        let images: Vec<_> = saved
+            .iter()
+            .map(|(path, size, media_type)| {
+                json!({ "path": path, "size_bytes": size, "media_type": media_type })
+            })
+            .collect();
+        ToolOutput::Result {
+            content,
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Other {
+                result: json!({
+                    "path": saved[0].0,
+                    "size_bytes": saved[0].1,
+                    "media_type": saved[0].2,
+                    "images": images
+                }),
             },
         }
     }
 }
+
+/// Lists the variants saved before a failure, if any.
+fn saved_note(saved: &[(String, usize, String)]) -> String {
+    if saved.is_empty() {
+        return String::new();
+    }
+    let paths: Vec<&str> = saved.iter().map(|(path, _, _)| path.as_str()).collect();
+    format!(
+        ". {} image(s) were saved before the failure: {}",
+        saved.len(),
+        paths.join(", ")
+    )
+}
@@ -181,12 +181,9 @@ async fn maybe_auto_compact(
 }
 
 /// Safely slice a conversation to get the last N messages without tearing tool call pairs.
-/// Returns messages starting from a clean boundary (User message without orphaned ToolResults).
+/// Returns messages starting from a clean boundary (User message without orphaned ToolResults),
+/// even when the whole conversation fits.
 pub fn safe_conversation_slice(conversation: &[Message], max_messages: usize) -> Vec<Message> {
-    if conversation.len() <= max_messages {
-        return conversation.to_vec();
-    }
-
     let start_idx = conversation.len().saturating_sub(max_messages);
     let mut slice = &conversation[start_idx..];
 
@@ -12,6 +12,7 @@ use crate::chat::events::{ChatMessage, MessageSender};
 use crate::module::SlashCommand;
 
 use super::compaction::{self, CompactionStore};
+use super::log::Memory;
 
 pub struct MemorySlashCommand;
 
@@ -22,17 +23,17 @@ impl SlashCommand for MemorySlashCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Manage memories (summarize, compact)"
+        "Manage memories (summarize, compact, list, forget)"
     }
 
     fn usage(&self) -> &'static str {
-        "/memory <summarize|compact|show>"
+        "/memory <summarize|compact|show|list [count]|forget <seq> [confirm]>"
     }
 
     async fn execute(&self, state: &mut ActorState, args: &[&str]) -> Vec<ChatMessage> {
         if args.is_empty() {
             return vec![create_message(
-                "Usage: /memory <summarize|compact|show>".to_string(),
+                format!("Usage: {}", self.usage()),
                 MessageSender::System,
             )];
         }
@@ -41,9 +42,11 @@ impl SlashCommand for MemorySlashCommand {
             "summarize" => handle_memory_summarize_command(state).await,
             "compact" => handle_memory_compact_command(state).await,
             "show" => handle_memory_show_command(state),
+            "list" => handle_memory_list_command(state, &args[1..]),
+            "forget" => handle_memory_forget_command(state, &args[1..]),
             _ => vec![create_message(
                 format!(
-                    "Unknown memory subcommand: {}. Use: summarize, compact, show",
+                    "Unknown memory subcommand: {}. Use: summarize, compact, show, list, forget",
                     args[0]
                 ),
                 MessageSender::Error,
@@ -235,3 +238,127 @@ fn handle_memory_show_command(state: &mut ActorState) -> Vec<ChatMessage> {
         MessageSender::System,
     )]
 }
+
+const DEFAULT_LIST_COUNT: usize = 20;
+
+fn format_memory(memory: &Memory) -> String {
+    let scope = if memory.is_global() {
+        String::new()
+    } else {
+        format!(", workspace: {}", memory.workspaces.join(", "))
+    };
+    format!(
+        "#{} ({}, {}{})\n{}",
+        memory.seq,
+        memory.created_at.format("%Y-%m-%d %H:%M UTC"),
+        memory.source.as_deref().unwrap_or("global"),
+        scope,
+        memory.content
+    )
+}
+
+fn handle_memory_list_command(state: &mut ActorState, args: &[&str]) -> Vec<ChatMessage> {
+    let count = match args.first() {
+        None => DEFAULT_LIST_COUNT,
+        Some(arg) => match arg.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                return vec![create_message(
+                    format!("Invalid count: {arg}. Usage: /memory list [count]"),
+                    MessageSender::Error,
+                )];
+            }
+        },
+    };
+
+    let memories = match state.memory_log.read_all() {
+        Ok(m) => m,
+        Err(e) => {
+            return vec![create_message(
+                format!("Failed to read memories: {e:?}"),
+                MessageSender::Error,
+            )];
+        }
+    };
+
+    if memories.is_empty() {
+        return vec![create_message(
+            "No memories stored.".to_string(),
+            MessageSender::System,
+        )];
+    }
+
+    let shown = &memories[memories.len().saturating_sub(count)..];
+    let mut output = format!(
+        "=== Memories ({} of {}) ===\n\n",
+        shown.len(),
+        memories.len()
+    );
+    for memory in shown.iter().rev() {
+        output.push_str(&format_memory(memory));
+        output.push_str("\n\n");
+    }
+    output.push_str("Use /memory forget <seq> to remove a memory.");
+
+    vec![create_message(output, MessageSender::System)]
+}
+
+fn handle_memory_forget_command(state: &mut ActorState, args: &[&str]) -> Vec<ChatMessage> {
+    let seq = match args
+        .first()
+        .map(|arg| arg.trim_start_matches('#').parse::<u64>())
+    {
+        Some(Ok(seq)) => seq,
+        _ => {
+            return vec![create_message(
+                "Usage: /memory forget <seq> [confirm]".to_string(),
+                MessageSender::Error,
+            )];
+        }
+    };
+    let confirmed = args.get(1) == Some(&"confirm");
+
+    if !confirmed {
+        let memories = match state.memory_log.read_all() {
+            Ok(m) => m,
+            Err(e) => {
+                return vec![create_message(
+                    format!("Failed to read memories: {e:?}"),
+                    MessageSender::Error,
+                )];
+            }
+        };
+        return match memories.iter().find(|m| m.seq == seq) {
+            Some(memory) => vec![create_message(
+                format!(
+                    "This will permanently delete memory {}\n\n\
+                    Run /memory forget {seq} confirm to proceed.",
+                    format_memory(memory)
+                ),
+                MessageSender::System,
+            )],
+            None => vec![create_message(
+                format!("No memory with seq #{seq}."),
+                MessageSender::Error,
+            )],
+        };
+    }
+
+    match state.memory_log.forget(seq) {
+        Ok(Some(_)) => vec![create_message(
+            format!(
+                "Forgot memory #{seq}. An existing compaction may still include it \
+                until the next /memory compact."
+            ),
+            MessageSender::System,
+        )],
+        Ok(None) => vec![create_message(
+            format!("No memory with seq #{seq}."),
+            MessageSender::Error,
+        )],
+        Err(e) => vec![create_message(
+            format!("Failed to forget memory: {e:?}"),
+            MessageSender::Error,
+        )],
+    }
+}
@@ -8,6 +8,18 @@ use crate::ai::model::ModelCost;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// How memories are chosen for the agent's context.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MemorySelection {
+    /// The most recent memories.
+    #[default]
+    Recency,
+    /// Memories sharing the most keywords with recent user messages, topped
+    /// up with the most recent ones.
+    Relevance,
+}
+
 fn default_memory_cost() -> ModelCost {
     ModelCost::High
 }
@@ -55,6 +67,10 @@ pub struct MemoryConfig {
     #[serde(default = "default_recent_memories_count")]
     #[schemars(default = "default_recent_memories_count")]
     pub recent_memories_count: usize,
+    /// How memories are chosen for the agent's context. Either way at most
+    /// `recent_memories_count` are included.
+    #[serde(default)]
+    pub selection: MemorySelection,
     /// When set, automatically trigger background compaction after this many
     /// new memories since the last compaction.
     #[serde(
@@ -63,6 +79,12 @@ pub struct MemoryConfig {
     )]
     #[schemars(default = "default_auto_compaction_threshold")]
     pub auto_compaction_threshold: Option<usize>,
+    /// Scope new memories to the current workspace unless the model marks
+    /// them as global, and only show global memories and memories from the
+    /// current workspace in context. Compaction summaries still cover all
+    /// memories.
+    #[serde(default)]
+    pub scope_to_workspace: bool,
 }
 
 impl MemoryConfig {
@@ -77,7 +99,9 @@ impl Default for MemoryConfig {
             recorder_cost: default_memory_cost(),
             context_message_count: default_context_message_count(),
             recent_memories_count: default_recent_memories_count(),
+            selection: MemorySelection::default(),
             auto_compaction_threshold: default_auto_compaction_threshold(),
+            scope_to_workspace: false,
         }
     }
 }
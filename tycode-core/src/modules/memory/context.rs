@@ -5,9 +5,9 @@ use std::sync::Arc;
 use crate::module::{ContextComponent, ContextComponentId};
 use crate::settings::manager::SettingsManager;
 
-use super::config::MemoryConfig;
-
+use super::config::{MemoryConfig, MemorySelection};
 use super::log::MemoryLog;
+use super::rank::{self, RecentInput};
 
 pub const ID: ContextComponentId = ContextComponentId("memories");
 
@@ -15,13 +15,19 @@ pub const ID: ContextComponentId = ContextComponentId("memories");
 pub struct MemoriesManager {
     memory_log: Arc<MemoryLog>,
     settings: SettingsManager,
+    recent_input: RecentInput,
 }
 
 impl MemoriesManager {
-    pub fn new(memory_log: Arc<MemoryLog>, settings: SettingsManager) -> Self {
+    pub fn new(
+        memory_log: Arc<MemoryLog>,
+        settings: SettingsManager,
+        recent_input: RecentInput,
+    ) -> Self {
         Self {
             memory_log,
             settings,
+            recent_input,
         }
     }
 
@@ -37,21 +43,38 @@ impl ContextComponent for MemoriesManager {
     }
 
     async fn build_context_section(&self) -> Option<String> {
-        let memories = self.memory_log.read_all().ok()?;
+        let config: MemoryConfig = self.settings.get_module_config(MemoryConfig::NAMESPACE);
+        let memories = if config.scope_to_workspace {
+            self.memory_log.read_relevant().ok()?
+        } else {
+            self.memory_log.read_all().ok()?
+        };
         if memories.is_empty() {
             return None;
         }
 
-        let config: MemoryConfig = self.settings.get_module_config(MemoryConfig::NAMESPACE);
         let max_recent = config.recent_memories_count;
-        let recent: Vec<_> = memories.iter().rev().take(max_recent).collect();
+        let (heading, selected) = match config.selection {
+            MemorySelection::Recency => {
+                let mut recent: Vec<_> = memories.iter().rev().take(max_recent).collect();
+                recent.reverse();
+                ("Recent Memories:\n", recent)
+            }
+            MemorySelection::Relevance => {
+                let query = self.recent_input.query();
+                (
+                    "Relevant Memories:\n",
+                    rank::select_relevant(&memories, &query, max_recent),
+                )
+            }
+        };
 
-        if recent.is_empty() {
+        if selected.is_empty() {
             return None;
         }
 
-        let mut output = String::from("Recent Memories:\n");
-        for memory in recent.iter().rev() {
+        let mut output = String::from(heading);
+        for memory in selected {
             let source_info = memory
                 .source
                 .as_ref()
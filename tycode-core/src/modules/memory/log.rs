@@ -2,6 +2,7 @@
 //!
 //! Memories are stored as a JSON log at ~/.tycode/memory/memories_log.json.
 //! Each memory has a monotonic sequence number, content, timestamp, and optional source.
+//! Memories tagged with workspace roots are workspace-scoped; untagged memories are global.
 
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -16,6 +17,26 @@ pub struct Memory {
     pub content: String,
     pub created_at: DateTime<Utc>,
     pub source: Option<String>,
+    /// Workspace roots this memory applies to. Empty for global memories.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspaces: Vec<String>,
+}
+
+impl Memory {
+    pub fn is_global(&self) -> bool {
+        self.workspaces.is_empty()
+    }
+
+    /// True for global memories and memories recorded in any of `workspaces`.
+    pub fn applies_to(&self, workspaces: &[String]) -> bool {
+        self.is_global() || self.workspaces.iter().any(|w| workspaces.contains(w))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryScope {
+    Global,
+    Workspace,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,11 +49,33 @@ struct MemoryLogInner {
 #[derive(Debug)]
 pub struct MemoryLog {
     path: PathBuf,
+    workspaces: Vec<String>,
 }
 
 impl MemoryLog {
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            workspaces: Vec::new(),
+        }
+    }
+
+    /// Set the workspace roots used to tag and filter workspace-scoped memories.
+    pub fn with_workspaces(mut self, roots: &[PathBuf]) -> Self {
+        self.workspaces = roots
+            .iter()
+            .map(|root| {
+                root.canonicalize()
+                    .unwrap_or_else(|_| root.clone())
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        self
+    }
+
+    pub fn workspaces(&self) -> &[String] {
+        &self.workspaces
     }
 
     /// Load current state from disk. Returns empty if file doesn't exist.
@@ -66,10 +109,23 @@ impl MemoryLog {
             .with_context(|| format!("Failed to write memory log: {}", self.path.display()))
     }
 
+    /// Append a new global memory.
+    pub fn append(&self, content: String, source: Option<String>) -> Result<u64> {
+        self.append_scoped(content, source, MemoryScope::Global)
+    }
+
     /// Append a new memory. Loads from disk, adds memory, saves back.
     /// Race condition: if two processes append simultaneously, one may lose.
     /// This is acceptable - we lose a few memories, not the entire log.
-    pub fn append(&self, content: String, source: Option<String>) -> Result<u64> {
+    ///
+    /// A workspace-scoped memory is tagged with the current workspace roots;
+    /// with no workspace open it is stored as global.
+    pub fn append_scoped(
+        &self,
+        content: String,
+        source: Option<String>,
+        scope: MemoryScope,
+    ) -> Result<u64> {
         let mut inner = self.load_inner()?;
 
         let seq = inner.next_seq;
@@ -80,6 +136,10 @@ impl MemoryLog {
             content,
             created_at: Utc::now(),
             source,
+            workspaces: match scope {
+                MemoryScope::Global => Vec::new(),
+                MemoryScope::Workspace => self.workspaces.clone(),
+            },
         });
 
         self.save_inner(&inner)?;
@@ -91,6 +151,46 @@ impl MemoryLog {
         self.load_inner().map(|inner| inner.memories)
     }
 
+    /// Read global memories and those scoped to the current workspaces.
+    pub fn read_relevant(&self) -> Result<Vec<Memory>> {
+        let mut memories = self.read_all()?;
+        memories.retain(|m| m.applies_to(&self.workspaces));
+        Ok(memories)
+    }
+
+    /// Memories whose content contains `query` (case-insensitive) and, when
+    /// given, whose source matches `source`. An empty query matches all.
+    pub fn search(&self, query: &str, source: Option<&str>) -> Result<Vec<Memory>> {
+        let query = query.to_lowercase();
+        let mut memories = self.read_all()?;
+        memories.retain(|m| {
+            let source_matches = match source {
+                Some(source) => m
+                    .source
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(source)),
+                None => true,
+            };
+            source_matches && m.content.to_lowercase().contains(&query)
+        });
+        Ok(memories)
+    }
+
+    /// Remove the memory with the given seq by rewriting the log without it.
+    /// `next_seq` is left untouched so sequence numbers are never reused.
+    /// Returns the removed memory, or None if no memory has that seq.
+    pub fn forget(&self, seq: u64) -> Result<Option<Memory>> {
+        let mut inner = self.load_inner()?;
+
+        let Some(index) = inner.memories.iter().position(|m| m.seq == seq) else {
+            return Ok(None);
+        };
+        let removed = inner.memories.remove(index);
+
+        self.save_inner(&inner)?;
+        Ok(Some(removed))
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -1,6 +1,7 @@
 //! Memory module - self-contained memory management functionality.
 //!
-//! Provides persistent memory storage, context rendering, and the append_memory tool.
+//! Provides persistent memory storage, context rendering, and the append_memory
+//! and recall_memory tools.
 
 use std::sync::Arc;
 
@@ -23,22 +24,26 @@ pub mod compaction;
 pub mod context;
 pub mod log;
 pub mod prompt;
+pub mod rank;
 pub mod tool;
 
 use command::MemorySlashCommand;
 use context::MemoriesManager;
 use log::MemoryLog;
 use prompt::CompactionPromptComponent;
-use tool::AppendMemoryTool;
+use rank::RecentInput;
+use tool::{AppendMemoryTool, RecallMemoryTool};
 
 /// Memory module providing persistent memory storage and retrieval.
 ///
 /// Bundles:
 /// - Context: MemoriesManager (renders recent memories)
 /// - Tool: AppendMemoryTool (stores new memories)
+/// - Tool: RecallMemoryTool (searches the full memory log)
 pub struct MemoryModule {
     memory_log: Arc<MemoryLog>,
     settings: SettingsManager,
+    recent_input: RecentInput,
 }
 
 impl MemoryModule {
@@ -46,6 +51,7 @@ impl MemoryModule {
         Self {
             memory_log,
             settings,
+            recent_input: RecentInput::default(),
         }
     }
 
@@ -66,11 +72,21 @@ impl Module for MemoryModule {
         vec![Arc::new(MemoriesManager::new(
             self.memory_log.clone(),
             self.settings.clone(),
+            self.recent_input.clone(),
         ))]
     }
 
     async fn tools(&self) -> Vec<SharedTool> {
-        vec![Arc::new(AppendMemoryTool::new(self.memory_log.clone()))]
+        vec![
+            Arc::new(AppendMemoryTool::new(
+                self.memory_log.clone(),
+                self.settings.clone(),
+            )),
+            Arc::new(RecallMemoryTool::new(
+                self.memory_log.clone(),
+                self.settings.clone(),
+            )),
+        ]
     }
 
     fn slash_commands(&self) -> Vec<Arc<dyn SlashCommand>> {
@@ -84,4 +100,10 @@ impl Module for MemoryModule {
     fn settings_json_schema(&self) -> Option<RootSchema> {
         Some(schema_for!(MemoryConfig))
     }
+
+    /// Records user messages as the query for relevance-ranked memories.
+    async fn on_user_input(&self, input: &str) -> Option<String> {
+        self.recent_input.push(input);
+        None
+    }
 }
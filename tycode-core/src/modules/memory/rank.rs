@@ -0,0 +1,130 @@
+//! Keyword-overlap ranking of memories against recent user input.
+//!
+//! Each memory is scored by the TF-IDF weight of the query terms it shares,
+//! normalized by memory length so long memories don't win by volume alone.
+//! This is deliberately cheap: it runs on every request and needs no model.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use super::log::Memory;
+
+/// Number of recent user messages used as the relevance query.
+const QUERY_MESSAGES: usize = 3;
+
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "are", "but", "not", "you", "all", "any", "can", "had", "has", "have",
+    "her", "his", "how", "its", "our", "out", "was", "were", "who", "why", "with", "this", "that",
+    "from", "they", "them", "then", "than", "what", "when", "will", "would", "should", "could",
+    "into", "about", "there", "their", "these", "those", "which", "while", "also", "just", "like",
+    "use", "using", "please", "make", "some",
+];
+
+/// Recent user messages, shared between the memory module (which records
+/// them) and the context component (which ranks against them).
+#[derive(Debug, Clone, Default)]
+pub struct RecentInput(Arc<Mutex<VecDeque<String>>>);
+
+impl RecentInput {
+    pub fn push(&self, input: &str) {
+        let mut inputs = self.0.lock().unwrap();
+        if inputs.len() == QUERY_MESSAGES {
+            inputs.pop_front();
+        }
+        inputs.push_back(input.to_string());
+    }
+
+    pub fn query(&self) -> String {
+        let inputs = self.0.lock().unwrap();
+        inputs.iter().cloned().collect::<Vec<_>>().join("\n")
+    }
+}
+
+fn terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| word.len() >= 3)
+        .map(|word| word.to_lowercase())
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Select up to `limit` memories, most relevant to `query` first. Slots left
+/// over once no memory matches are filled with the most recent memories.
+/// The result is returned in log (seq) order.
+pub fn select_relevant<'a>(memories: &'a [Memory], query: &str, limit: usize) -> Vec<&'a Memory> {
+    let query_terms: HashSet<String> = terms(query).into_iter().collect();
+    let memory_terms: Vec<Vec<String>> = memories.iter().map(|m| terms(&m.content)).collect();
+
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for memory in &memory_terms {
+        let unique: HashSet<&str> = memory.iter().map(String::as_str).collect();
+        for term in unique {
+            *document_frequency.entry(term).or_default() += 1;
+        }
+    }
+
+    let total = memories.len() as f64;
+    let mut scored: Vec<(usize, f64)> = memory_terms
+        .iter()
+        .enumerate()
+        .map(|(idx, memory)| {
+            let score: f64 = memory
+                .iter()
+                .filter(|term| query_terms.contains(*term))
+                .map(|term| (total / document_frequency[term.as_str()] as f64).ln() + 1.0)
+                .sum();
+            (idx, score / (memory.len().max(1) as f64).sqrt())
+        })
+        .collect();
+
+    // Highest score first; ties and non-matches fall back to recency.
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.0.cmp(&a.0)));
+
+    let mut selected: Vec<usize> = scored.into_iter().take(limit).map(|(idx, _)| idx).collect();
+    selected.sort_unstable();
+    selected.into_iter().map(|idx| &memories[idx]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn memory(seq: u64, content: &str) -> Memory {
+        Memory {
+            seq,
+            content: content.to_string(),
+            created_at: Utc::now(),
+            source: None,
+            workspaces: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn prefers_matching_memories_over_recent_ones() {
+        let memories = vec![
+            memory(
+                1,
+                "Database migrations run through sqlx, never edit schema by hand",
+            ),
+            memory(2, "User prefers tabs"),
+            memory(3, "Release notes go in CHANGELOG.md"),
+            memory(4, "Prefer terse commit messages"),
+        ];
+
+        let selected = select_relevant(&memories, "add a migration for the schema", 2);
+
+        let seqs: Vec<u64> = selected.iter().map(|m| m.seq).collect();
+        assert_eq!(seqs, vec![1, 4]);
+    }
+
+    #[test]
+    fn falls_back_to_recency_without_query() {
+        let memories = vec![memory(1, "one"), memory(2, "two"), memory(3, "three")];
+
+        let selected = select_relevant(&memories, "", 2);
+
+        let seqs: Vec<u64> = selected.iter().map(|m| m.seq).collect();
+        assert_eq!(seqs, vec![2, 3]);
+    }
+}
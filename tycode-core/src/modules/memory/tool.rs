@@ -4,19 +4,27 @@ use std::sync::Arc;
 
 use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
 use crate::tools::r#trait::{
-    ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+    ContinuationPreference, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput,
+    ToolRequest,
 };
 use crate::tools::ToolName;
 
-use super::log::MemoryLog;
+use crate::settings::manager::SettingsManager;
+
+use super::config::MemoryConfig;
+use super::log::{MemoryLog, MemoryScope};
 
 pub struct AppendMemoryTool {
     memory_log: Arc<MemoryLog>,
+    settings: SettingsManager,
 }
 
 impl AppendMemoryTool {
-    pub fn new(memory_log: Arc<MemoryLog>) -> Self {
-        Self { memory_log }
+    pub fn new(memory_log: Arc<MemoryLog>, settings: SettingsManager) -> Self {
+        Self {
+            memory_log,
+            settings,
+        }
     }
 
     pub fn tool_name() -> ToolName {
@@ -45,6 +53,10 @@ impl ToolExecutor for AppendMemoryTool {
                 "source": {
                     "type": "string",
                     "description": "Optional project name this memory applies to. Omit for global memories."
+                },
+                "global": {
+                    "type": "boolean",
+                    "description": "Set true for learnings that apply across all projects, such as general user preferences. Defaults to false, which ties the memory to the current workspace when workspace scoping is enabled."
                 }
             },
             "required": ["content"]
@@ -55,6 +67,11 @@ impl ToolExecutor for AppendMemoryTool {
         ToolCategory::Execution
     }
 
+    // Memories are persisted to disk
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Write]
+    }
+
     async fn process(&self, request: &ToolRequest) -> anyhow::Result<Box<dyn ToolCallHandle>> {
         let content = request.arguments["content"]
             .as_str()
@@ -67,9 +84,22 @@ impl ToolExecutor for AppendMemoryTool {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        let config: MemoryConfig = self.settings.get_module_config(MemoryConfig::NAMESPACE);
+        let global = request
+            .arguments
+            .get("global")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let scope = if config.scope_to_workspace && !global {
+            MemoryScope::Workspace
+        } else {
+            MemoryScope::Global
+        };
+
         Ok(Box::new(AppendMemoryHandle {
             content,
             source,
+            scope,
             tool_use_id: request.tool_use_id.clone(),
             memory_log: self.memory_log.clone(),
         }))
@@ -79,6 +109,7 @@ impl ToolExecutor for AppendMemoryTool {
 struct AppendMemoryHandle {
     content: String,
     source: Option<String>,
+    scope: MemoryScope,
     tool_use_id: String,
     memory_log: Arc<MemoryLog>,
 }
@@ -101,7 +132,7 @@ impl ToolCallHandle for AppendMemoryHandle {
     async fn execute(self: Box<Self>) -> ToolOutput {
         match self
             .memory_log
-            .append(self.content.clone(), self.source.clone())
+            .append_scoped(self.content.clone(), self.source.clone(), self.scope)
         {
             Ok(seq) => ToolOutput::Result {
                 content: serde_json::json!({
@@ -127,7 +158,177 @@ impl ToolCallHandle for AppendMemoryHandle {
                 ui_result: ToolExecutionResult::Error {
                     short_message: "Memory append failed".to_string(),
                     detailed_message: format!("{e:?}"),
+                    category: None,
+                },
+            },
+        }
+    }
+}
+
+const DEFAULT_RECALL_LIMIT: usize = 10;
+const MAX_RECALL_LIMIT: usize = 50;
+
+/// Searches the full memory log, including memories that were not injected
+/// into context.
+pub struct RecallMemoryTool {
+    memory_log: Arc<MemoryLog>,
+    settings: SettingsManager,
+}
+
+impl RecallMemoryTool {
+    pub fn new(memory_log: Arc<MemoryLog>, settings: SettingsManager) -> Self {
+        Self {
+            memory_log,
+            settings,
+        }
+    }
+
+    pub fn tool_name() -> ToolName {
+        ToolName::new("recall_memory")
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for RecallMemoryTool {
+    fn name(&self) -> String {
+        "recall_memory".to_string()
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "Searches the memory log for past learnings. Only a subset of memories is shown in context; use this to look up a preference or decision that may have been recorded earlier. Returns the most recent matches first, at most {MAX_RECALL_LIMIT}."
+        )
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Case-insensitive text to search for in memory content. Omit to list the most recent memories."
+                },
+                "source": {
+                    "type": "string",
+                    "description": "Only return memories recorded for this project name"
                 },
+                "limit": {
+                    "type": "integer",
+                    "description": format!("Maximum number of memories to return (default {DEFAULT_RECALL_LIMIT}, max {MAX_RECALL_LIMIT})")
+                }
+            }
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Read
+    }
+
+    async fn process(&self, request: &ToolRequest) -> anyhow::Result<Box<dyn ToolCallHandle>> {
+        let query = request
+            .arguments
+            .get("query")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let source = request
+            .arguments
+            .get("source")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let limit = request
+            .arguments
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_RECALL_LIMIT, |n| n as usize)
+            .clamp(1, MAX_RECALL_LIMIT);
+        let config: MemoryConfig = self.settings.get_module_config(MemoryConfig::NAMESPACE);
+
+        Ok(Box::new(RecallMemoryHandle {
+            query,
+            source,
+            limit,
+            scope_to_workspace: config.scope_to_workspace,
+            tool_use_id: request.tool_use_id.clone(),
+            memory_log: self.memory_log.clone(),
+        }))
+    }
+}
+
+struct RecallMemoryHandle {
+    query: String,
+    source: Option<String>,
+    limit: usize,
+    scope_to_workspace: bool,
+    tool_use_id: String,
+    memory_log: Arc<MemoryLog>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for RecallMemoryHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "recall_memory".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: serde_json::json!({
+                    "query": self.query,
+                    "source": self.source,
+                    "limit": self.limit
+                }),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let mut matches = match self.memory_log.search(&self.query, self.source.as_deref()) {
+            Ok(matches) => matches,
+            Err(e) => {
+                return ToolOutput::Result {
+                    content: format!("Failed to search memories: {e:?}"),
+                    is_error: true,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::Error {
+                        short_message: "Memory search failed".to_string(),
+                        detailed_message: format!("{e:?}"),
+                        category: None,
+                    },
+                };
+            }
+        };
+        if self.scope_to_workspace {
+            matches.retain(|m| m.applies_to(self.memory_log.workspaces()));
+        }
+
+        let total = matches.len();
+        let memories: Vec<_> = matches
+            .iter()
+            .rev()
+            .take(self.limit)
+            .map(|m| {
+                serde_json::json!({
+                    "seq": m.seq,
+                    "content": m.content,
+                    "source": m.source,
+                    "created_at": m.created_at.to_rfc3339(),
+                })
+            })
+            .collect();
+        let returned = memories.len();
+
+        ToolOutput::Result {
+            content: serde_json::json!({
+                "total_matches": total,
+                "memories": memories,
+            })
+            .to_string(),
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Other {
+                result: serde_json::json!({
+                    "total_matches": total,
+                    "returned": returned
+                }),
             },
         }
     }
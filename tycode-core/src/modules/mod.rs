@@ -3,7 +3,9 @@
 
 pub mod context_management;
 pub mod execution;
+pub mod git;
 pub mod image;
 pub mod memory;
 pub mod review;
+pub mod semantic_search;
 pub mod task_list;
@@ -0,0 +1,63 @@
+//! Semantic search module configuration.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+fn default_chunk_lines() -> usize {
+    40
+}
+
+fn default_max_results() -> usize {
+    8
+}
+
+fn default_max_file_bytes() -> u64 {
+    200_000
+}
+
+fn default_max_chunks() -> usize {
+    20_000
+}
+
+/// Natural-language code search over embeddings of the workspace. Requires
+/// a provider with an embeddings endpoint; the tool is not offered otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[schemars(title = "Semantic Search")]
+pub struct SemanticSearch {
+    /// Offer the semantic_search tool to agents.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Number of lines embedded together as one searchable chunk.
+    #[serde(default = "default_chunk_lines")]
+    pub chunk_lines: usize,
+
+    /// Chunks returned when the model does not ask for a specific number.
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+
+    /// Files larger than this are not embedded.
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+
+    /// Upper bound on embedded chunks per workspace root. Files beyond it are
+    /// left out of the index.
+    #[serde(default = "default_max_chunks")]
+    pub max_chunks: usize,
+}
+
+impl SemanticSearch {
+    pub const NAMESPACE: &str = "semantic_search";
+}
+
+impl Default for SemanticSearch {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            chunk_lines: default_chunk_lines(),
+            max_results: default_max_results(),
+            max_file_bytes: default_max_file_bytes(),
+            max_chunks: default_max_chunks(),
+        }
+    }
+}
@@ -0,0 +1,332 @@
+//! Per-root store of chunk embeddings at `.tycode/embeddings/index.json`.
+//!
+//! Files are split into fixed-size line chunks and each chunk is embedded
+//! once. On refresh only files whose size or mtime changed are re-embedded;
+//! the whole store is rebuilt when the provider or chunk size changes, since
+//! vectors from different settings are not comparable.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::ai::provider::AiProvider;
+use crate::file::access::FileAccessManager;
+use crate::file::cache::FileStamp;
+
+use super::config::SemanticSearch;
+
+/// Chunks sent to the provider per embed call.
+const EMBED_BATCH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Stamp {
+    len: u64,
+    modified_secs: u64,
+    modified_nanos: u32,
+}
+
+impl From<FileStamp> for Stamp {
+    fn from(stamp: FileStamp) -> Self {
+        let modified = stamp
+            .modified
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            len: stamp.len,
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Chunk {
+    start_line: usize,
+    end_line: usize,
+    vector: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    stamp: Stamp,
+    chunks: Vec<Chunk>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Stored {
+    provider: String,
+    chunk_lines: usize,
+    /// Keyed by path relative to the root.
+    files: BTreeMap<String, IndexedFile>,
+}
+
+/// A new or changed file awaiting embeddings.
+struct PendingFile {
+    relative: String,
+    stamp: Stamp,
+    ranges: Vec<(usize, usize)>,
+    texts: Vec<String>,
+}
+
+/// A chunk of a file that scored against a query.
+#[derive(Debug, Clone)]
+pub struct ChunkMatch {
+    pub path: PathBuf,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub score: f32,
+}
+
+pub struct EmbeddingIndex {
+    root: PathBuf,
+    stored: Stored,
+}
+
+fn store_path(root: &Path) -> PathBuf {
+    root.join(".tycode").join("embeddings").join("index.json")
+}
+
+impl EmbeddingIndex {
+    pub fn load(root: &Path) -> Self {
+        let path = store_path(root);
+        let stored = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                warn!(?e, "Discarding unreadable embeddings {}", path.display());
+                Stored::default()
+            }),
+            Err(_) => Stored::default(),
+        };
+        Self {
+            root: root.to_path_buf(),
+            stored,
+        }
+    }
+
+    /// Embeds new and changed files, drops deleted ones and saves the store.
+    pub async fn refresh(
+        &mut self,
+        file_manager: &FileAccessManager,
+        provider: &dyn AiProvider,
+        config: &SemanticSearch,
+    ) -> Result<()> {
+        let chunk_lines = config.chunk_lines.max(1);
+        if self.stored.provider != provider.name() || self.stored.chunk_lines != chunk_lines {
+            self.stored = Stored {
+                provider: provider.name().to_string(),
+                chunk_lines,
+                files: BTreeMap::new(),
+            };
+        }
+
+        let skip = self.root.join(".tycode");
+        let listed = file_manager
+            .list_all_files_recursive(&self.root.to_string_lossy(), None)
+            .await?;
+
+        let mut previous = std::mem::take(&mut self.stored.files);
+        let mut total_chunks = 0usize;
+        let mut pending: Vec<PendingFile> = Vec::new();
+        let mut over_budget = false;
+
+        for path in listed {
+            if path.starts_with(&skip) {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&self.root) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().to_string();
+            let Some(stamp) = tokio::fs::metadata(&path)
+                .await
+                .ok()
+                .and_then(|metadata| FileStamp::of(&metadata))
+            else {
+                continue;
+            };
+            if stamp.len > config.max_file_bytes {
+                continue;
+            }
+            let stamp = Stamp::from(stamp);
+
+            if let Some(file) = previous.remove(&relative) {
+                if file.stamp == stamp {
+                    if total_chunks + file.chunks.len() > config.max_chunks {
+                        over_budget = true;
+                        continue;
+                    }
+                    total_chunks += file.chunks.len();
+                    self.stored.files.insert(relative, file);
+                    continue;
+                }
+            }
+
+            // Binary and unreadable files are not embedded
+            let Ok(content) = file_manager.read_file(&path.to_string_lossy()).await else {
+                continue;
+            };
+            let (ranges, texts) = chunk(&relative, &content, chunk_lines);
+            if total_chunks + texts.len() > config.max_chunks {
+                over_budget = true;
+                continue;
+            }
+            total_chunks += texts.len();
+            pending.push(PendingFile {
+                relative,
+                stamp,
+                ranges,
+                texts,
+            });
+        }
+
+        if over_budget {
+            warn!(
+                root = %self.root.display(),
+                max_chunks = config.max_chunks,
+                "Semantic search index is full; some files are not searchable"
+            );
+        }
+
+        let changed = !pending.is_empty() || !previous.is_empty();
+        let texts: Vec<String> = pending
+            .iter()
+            .flat_map(|file| file.texts.iter().cloned())
+            .collect();
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBED_BATCH) {
+            vectors.extend(
+                provider
+                    .embed(batch.to_vec())
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to embed workspace files: {e:?}"))?,
+            );
+        }
+        if vectors.len() != texts.len() {
+            anyhow::bail!(
+                "Provider returned {} embeddings for {} chunks",
+                vectors.len(),
+                texts.len()
+            );
+        }
+
+        let mut vectors = vectors.into_iter();
+        for file in pending {
+            let chunks = file
+                .ranges
+                .into_iter()
+                .zip(vectors.by_ref())
+                .map(|((start_line, end_line), vector)| Chunk {
+                    start_line,
+                    end_line,
+                    vector,
+                })
+                .collect();
+            self.stored.files.insert(
+                file.relative,
+                IndexedFile {
+                    stamp: file.stamp,
+                    chunks,
+                },
+            );
+        }
+
+        if changed {
+            debug!(
+                root = %self.root.display(),
+                files = self.stored.files.len(),
+                chunks = total_chunks,
+                "Updated semantic search index"
+            );
+            self.save().await?;
+        }
+        Ok(())
+    }
+
+    /// Scores every chunk under `scope` (or the whole root) against `query`.
+    pub fn matches(&self, query: &[f32], scope: Option<&Path>) -> Vec<ChunkMatch> {
+        let mut matches = Vec::new();
+        for (relative, file) in &self.stored.files {
+            let path = self.root.join(relative);
+            if scope.is_some_and(|scope| !path.starts_with(scope)) {
+                continue;
+            }
+            for chunk in &file.chunks {
+                matches.push(ChunkMatch {
+                    path: path.clone(),
+                    start_line: chunk.start_line,
+                    end_line: chunk.end_line,
+                    score: cosine_similarity(query, &chunk.vector),
+                });
+            }
+        }
+        matches
+    }
+
+    async fn save(&self) -> Result<()> {
+        let path = store_path(&self.root);
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        tokio::fs::write(&path, serde_json::to_vec(&self.stored)?)
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Splits `content` into chunks of `chunk_lines` lines, returning each
+/// chunk's 1-based inclusive line range and the text to embed. Blank chunks
+/// are skipped. The path is prepended so file names inform the embedding.
+fn chunk(relative: &str, content: &str, chunk_lines: usize) -> (Vec<(usize, usize)>, Vec<String>) {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut ranges = Vec::new();
+    let mut texts = Vec::new();
+    for (i, window) in lines.chunks(chunk_lines).enumerate() {
+        if window.iter().all(|line| line.trim().is_empty()) {
+            continue;
+        }
+        let start = i * chunk_lines + 1;
+        ranges.push((start, start + window.len() - 1));
+        texts.push(format!("{relative}\n{}", window.join("\n")));
+    }
+    (ranges, texts)
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_cover_file_and_skip_blank_windows() {
+        let content = "a\nb\nc\n\n\n\nd";
+        let (ranges, texts) = chunk("src/x.rs", content, 3);
+        assert_eq!(ranges, vec![(1, 3), (7, 7)]);
+        assert_eq!(texts[0], "src/x.rs\na\nb\nc");
+        assert_eq!(texts[1], "src/x.rs\nd");
+    }
+
+    #[test]
+    fn cosine_similarity_handles_degenerate_vectors() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+}
@@ -0,0 +1,85 @@
+//! Semantic search module - natural-language code search over embeddings of
+//! the workspace.
+//!
+//! Disabled by default, and only offered when the active provider supports
+//! embeddings:
+//!
+//! ```toml
+//! [modules.semantic_search]
+//! enabled = true
+//! ```
+//!
+//! Embeddings are stored per workspace root under `.tycode/embeddings` and
+//! updated incrementally before each search.
+
+pub mod config;
+pub mod index;
+pub mod tool;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+use crate::module::{ContextComponent, Module, PromptComponent};
+use crate::modules::image::SharedProvider;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::SharedTool;
+
+pub use config::SemanticSearch;
+use tool::SemanticSearchTool;
+
+/// Bundles:
+/// - Tool: SemanticSearchTool
+pub struct SemanticSearchModule {
+    tool: Arc<SemanticSearchTool>,
+    provider: SharedProvider,
+    settings: SettingsManager,
+}
+
+impl SemanticSearchModule {
+    pub fn new(
+        workspace_roots: Vec<PathBuf>,
+        settings: SettingsManager,
+        provider: SharedProvider,
+    ) -> Result<Self> {
+        Ok(Self {
+            tool: Arc::new(SemanticSearchTool::new(
+                workspace_roots,
+                settings.clone(),
+                provider.clone(),
+            )?),
+            provider,
+            settings,
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Module for SemanticSearchModule {
+    fn prompt_components(&self) -> Vec<Arc<dyn PromptComponent>> {
+        vec![]
+    }
+
+    fn context_components(&self) -> Vec<Arc<dyn ContextComponent>> {
+        vec![]
+    }
+
+    async fn tools(&self) -> Vec<SharedTool> {
+        let config: SemanticSearch = self.settings.get_module_config(SemanticSearch::NAMESPACE);
+        if !config.enabled || !self.provider.read().unwrap().supports_embeddings() {
+            return vec![];
+        }
+        vec![self.tool.clone()]
+    }
+
+    fn settings_namespace(&self) -> Option<&'static str> {
+        Some(SemanticSearch::NAMESPACE)
+    }
+
+    fn settings_json_schema(&self) -> Option<RootSchema> {
+        Some(schema_for!(SemanticSearch))
+    }
+}
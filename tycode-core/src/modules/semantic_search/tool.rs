@@ -0,0 +1,275 @@
+//! `semantic_search` tool: finds the code chunks most related to a
+//! natural-language query.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::file::access::FileAccessManager;
+use crate::modules::image::SharedProvider;
+use crate::settings::SettingsManager;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCapability, ToolCategory, ToolExecutor, ToolOutput,
+    ToolRequest,
+};
+use crate::tools::ToolName;
+
+use super::config::SemanticSearch;
+use super::index::{ChunkMatch, EmbeddingIndex};
+
+const MAX_RESULTS_LIMIT: usize = 50;
+/// Lines of each matching chunk shown in the result.
+const SNIPPET_LINES: usize = 12;
+
+pub struct SemanticSearchTool {
+    file_manager: FileAccessManager,
+    settings: SettingsManager,
+    provider: SharedProvider,
+    indexes: Arc<Mutex<HashMap<PathBuf, EmbeddingIndex>>>,
+}
+
+impl SemanticSearchTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("semantic_search")
+    }
+
+    pub fn new(
+        workspace_roots: Vec<PathBuf>,
+        settings: SettingsManager,
+        provider: SharedProvider,
+    ) -> Result<Self> {
+        Ok(Self {
+            file_manager: FileAccessManager::new(workspace_roots)?.with_settings(settings.clone()),
+            settings,
+            provider,
+            indexes: Arc::default(),
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for SemanticSearchTool {
+    fn name(&self) -> String {
+        "semantic_search".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Find the code most related to a natural-language description (e.g. \"where are auth tokens validated\") using embeddings of the workspace. Returns file paths, line ranges and snippets. Use search_files instead when you know an exact name or string.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "What the code you are looking for does"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Optional absolute file or directory to search within. Defaults to all workspace roots"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_RESULTS_LIMIT,
+                    "description": "Number of chunks to return"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Read
+    }
+
+    // Queries and workspace chunks are sent to the embeddings provider
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        vec![ToolCapability::Network]
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let Some(query) = request.arguments["query"].as_str() else {
+            bail!("Missing required argument \"query\"");
+        };
+        if query.trim().is_empty() {
+            bail!("query must not be empty");
+        }
+        let scope = match request.arguments["path"].as_str() {
+            Some(path) => Some(self.file_manager.resolve(path)?),
+            None => None,
+        };
+
+        let config: SemanticSearch = self.settings.get_module_config(SemanticSearch::NAMESPACE);
+        let max_results = request.arguments["max_results"]
+            .as_u64()
+            .map(|n| n as usize)
+            .unwrap_or(config.max_results)
+            .clamp(1, MAX_RESULTS_LIMIT);
+
+        Ok(Box::new(SemanticSearchHandle {
+            file_manager: self.file_manager.clone(),
+            provider: self.provider.clone(),
+            indexes: self.indexes.clone(),
+            config,
+            query: query.to_string(),
+            scope,
+            max_results,
+            arguments: request.arguments.clone(),
+            tool_use_id: request.tool_use_id.clone(),
+        }))
+    }
+}
+
+struct SemanticSearchHandle {
+    file_manager: FileAccessManager,
+    provider: SharedProvider,
+    indexes: Arc<Mutex<HashMap<PathBuf, EmbeddingIndex>>>,
+    config: SemanticSearch,
+    query: String,
+    scope: Option<PathBuf>,
+    max_results: usize,
+    arguments: Value,
+    tool_use_id: String,
+}
+
+impl SemanticSearchHandle {
+    async fn search(&self) -> Result<Vec<ChunkMatch>> {
+        let provider = self.provider.read().unwrap().clone();
+        if !provider.supports_embeddings() {
+            bail!(
+                "The {} provider does not support embeddings; semantic search is unavailable",
+                provider.name()
+            );
+        }
+
+        let vectors = provider
+            .embed(vec![self.query.clone()])
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to embed the query: {e:?}"))?;
+        let Some(query) = vectors.into_iter().next() else {
+            bail!("Provider returned no embedding for the query");
+        };
+
+        let mut indexes = self.indexes.lock().await;
+        let mut matches = Vec::new();
+        for root in &self.file_manager.roots {
+            if let Some(scope) = &self.scope {
+                if !scope.starts_with(root) {
+                    continue;
+                }
+            }
+
+            let index = indexes
+                .entry(root.clone())
+                .or_insert_with(|| EmbeddingIndex::load(root));
+            index
+                .refresh(&self.file_manager, provider.as_ref(), &self.config)
+                .await?;
+            matches.extend(index.matches(&query, self.scope.as_deref()));
+        }
+
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(self.max_results);
+        Ok(matches)
+    }
+
+    async fn snippet(&self, chunk: &ChunkMatch) -> String {
+        let Ok(content) = self
+            .file_manager
+            .read_file(&chunk.path.to_string_lossy())
+            .await
+        else {
+            return String::new();
+        };
+        let lines: Vec<&str> = content
+            .lines()
+            .skip(chunk.start_line - 1)
+            .take((chunk.end_line + 1 - chunk.start_line).min(SNIPPET_LINES))
+            .collect();
+        let mut snippet = lines.join("\n");
+        if chunk.end_line + 1 - chunk.start_line > SNIPPET_LINES {
+            snippet.push_str("\n...");
+        }
+        snippet
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for SemanticSearchHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "semantic_search".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: self.arguments.clone(),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        let matches = match self.search().await {
+            Ok(matches) => matches,
+            Err(e) => {
+                return ToolOutput::Result {
+                    content: format!("{e:#}"),
+                    is_error: true,
+                    continuation: ContinuationPreference::Continue,
+                    ui_result: ToolExecutionResult::Error {
+                        short_message: "semantic_search failed".to_string(),
+                        detailed_message: format!("{e:#}"),
+                        category: None,
+                    },
+                }
+            }
+        };
+
+        if matches.is_empty() {
+            return ToolOutput::Result {
+                content: "No indexed code to search".to_string(),
+                is_error: false,
+                continuation: ContinuationPreference::Continue,
+                ui_result: ToolExecutionResult::Other {
+                    result: json!({ "results": [] }),
+                },
+            };
+        }
+
+        let mut sections = Vec::with_capacity(matches.len());
+        for chunk in &matches {
+            sections.push(format!(
+                "{}:{}-{} (score {:.2})\n{}",
+                chunk.path.display(),
+                chunk.start_line,
+                chunk.end_line,
+                chunk.score,
+                self.snippet(chunk).await
+            ));
+        }
+
+        ToolOutput::Result {
+            content: sections.join("\n\n"),
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Other {
+                result: json!({
+                    "results": matches
+                        .iter()
+                        .map(|chunk| json!({
+                            "path": chunk.path,
+                            "start_line": chunk.start_line,
+                            "end_line": chunk.end_line,
+                            "score": chunk.score,
+                        }))
+                        .collect::<Vec<_>>(),
+                }),
+            },
+        }
+    }
+}
@@ -27,7 +27,10 @@ pub struct TaskListModule {
 pub(crate) struct TaskListModuleInner {
     pub(crate) task_list: RwLock<TaskList>,
     pub(crate) event_sender: EventSender,
-    saved_stack: RwLock<Vec<TaskList>>,
+    /// Whether the current agent set the task list (via manage_task_list or
+    /// initial_task_list) rather than inheriting it from its parent.
+    owned: RwLock<bool>,
+    saved_stack: RwLock<Vec<(TaskList, bool)>>,
 }
 
 impl TaskListModule {
@@ -35,6 +38,7 @@ impl TaskListModule {
         let inner = Arc::new(TaskListModuleInner {
             task_list: RwLock::new(TaskList::default()),
             event_sender,
+            owned: RwLock::new(false),
             saved_stack: RwLock::new(Vec::new()),
         });
         inner.emit_update();
@@ -47,6 +51,12 @@ impl TaskListModule {
         })
     }
 
+    pub fn manage_tasks_tool(&self) -> SharedTool {
+        Arc::new(ManageTasksTool {
+            inner: self.inner.clone(),
+        })
+    }
+
     pub fn context_component(&self) -> Arc<dyn ContextComponent + Send + Sync> {
         Arc::new(TaskListContextComponent {
             inner: self.inner.clone(),
@@ -73,7 +83,7 @@ impl Module for TaskListModule {
     }
 
     async fn tools(&self) -> Vec<SharedTool> {
-        vec![self.manage_tool()]
+        vec![self.manage_tool(), self.manage_tasks_tool()]
     }
 
     fn session_state(&self) -> Option<Arc<dyn SessionStateComponent>> {
@@ -99,7 +109,12 @@ impl Module for TaskListModule {
 
     fn on_agent_pushed(&self, _agent: &ActiveAgent, params: HashMap<String, Value>) {
         let current = self.inner.get();
-        self.inner.saved_stack.write().unwrap().push(current);
+        let owned = std::mem::replace(&mut *self.inner.owned.write().unwrap(), false);
+        self.inner
+            .saved_stack
+            .write()
+            .unwrap()
+            .push((current, owned));
 
         if let Some(Value::Array(tasks)) = params.get("initial_task_list") {
             let task_list: Vec<TaskWithStatus> = tasks
@@ -121,17 +136,32 @@ impl Module for TaskListModule {
 
                 let new_list = TaskList::from_tasks_with_status(title, task_list);
                 *self.inner.task_list.write().unwrap() = new_list;
+                *self.inner.owned.write().unwrap() = true;
                 self.inner.emit_update();
             }
         }
     }
 
     fn on_agent_popped(&self, _agent: &ActiveAgent) {
-        if let Some(saved) = self.inner.saved_stack.write().unwrap().pop() {
+        if let Some((saved, owned)) = self.inner.saved_stack.write().unwrap().pop() {
             *self.inner.task_list.write().unwrap() = saved;
+            *self.inner.owned.write().unwrap() = owned;
             self.inner.emit_update();
         }
     }
+
+    fn unfinished_work(&self) -> Vec<String> {
+        if !*self.inner.owned.read().unwrap() {
+            return Vec::new();
+        }
+        self.inner
+            .get()
+            .tasks
+            .iter()
+            .filter(|task| matches!(task.status, TaskStatus::Pending | TaskStatus::InProgress))
+            .map(|task| format!("Task {}: {} ({:?})", task.id, task.description, task.status))
+            .collect()
+    }
 }
 
 struct TaskListSessionState {
@@ -160,12 +190,20 @@ impl SessionStateComponent for TaskListSessionState {
         self.inner.replace(task_list.title, tasks);
         Ok(())
     }
+
+    fn reset(&self) {
+        self.inner.saved_stack.write().unwrap().clear();
+        *self.inner.task_list.write().unwrap() = TaskList::default();
+        *self.inner.owned.write().unwrap() = false;
+        self.inner.emit_update();
+    }
 }
 
 impl TaskListModuleInner {
     pub(crate) fn replace(&self, title: String, tasks: Vec<TaskWithStatus>) {
         let new_list = TaskList::from_tasks_with_status(title, tasks);
         *self.task_list.write().unwrap() = new_list;
+        *self.owned.write().unwrap() = true;
         self.emit_update();
     }
 
@@ -174,7 +212,8 @@ impl TaskListModuleInner {
     }
 
     fn emit_update(&self) {
-        self.event_sender.send(ChatEvent::TaskUpdate(self.get()));
+        self.event_sender
+            .send(ChatEvent::TaskUpdate(self.get().into()));
     }
 }
 
@@ -222,6 +261,7 @@ const TASK_LIST_MANAGEMENT: &str = r#"## Task List Management
 • The 'context' will always include a task list. The task list is designed to help you break down large tasks in to smaller chunks of work and to provide feedback to the user about what you are working on.
 • When possible, design each step so that it can be validated (compile and pass tests). Some tasks may require multiple steps before validation is feasible. 
 • The task list can be updated with a special tool called "manage_task_list". Ensure the task list is always up to date.
+• For small changes to an existing task list (marking a task complete, adding or removing a few tasks) prefer "manage_tasks", which applies a batch of edits by task id instead of rewriting the whole list. The same rules for combining with other tool calls apply to it.
 • The "manage_task_list" is neither an "Execution" nor a "Meta" tool and may be combined with either type of response. "manage_task_list" may never be the only tool request; "manage_task_list" must always be combined with at least 1 other tool call. 
 
 ## When to Update the Task List
@@ -286,6 +326,61 @@ impl TaskList {
 
         Self { title, tasks }
     }
+
+    /// Completed vs total tasks. Failed tasks are finished work too, so they
+    /// count towards `done` and are also reported separately.
+    pub fn progress(&self) -> TaskProgress {
+        let completed = self
+            .tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Completed)
+            .count();
+        let failed = self
+            .tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Failed)
+            .count();
+        let total = self.tasks.len();
+        let done = completed + failed;
+        let percent = (done * 100).checked_div(total).unwrap_or(0) as u8;
+
+        TaskProgress {
+            done,
+            failed,
+            total,
+            percent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskProgress {
+    /// Tasks that are completed or failed
+    pub done: usize,
+    pub failed: usize,
+    pub total: usize,
+    /// `done` as a percentage of `total`, rounded down
+    pub percent: u8,
+}
+
+/// Payload of `ChatEvent::TaskUpdate`: the task list with its progress rollup.
+/// The list's fields are flattened so consumers reading `title`/`tasks` are
+/// unaffected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskListUpdate {
+    #[serde(flatten)]
+    pub task_list: TaskList,
+    pub progress: TaskProgress,
+}
+
+impl From<TaskList> for TaskListUpdate {
+    fn from(task_list: TaskList) -> Self {
+        let progress = task_list.progress();
+        Self {
+            task_list,
+            progress,
+        }
+    }
 }
 
 impl Default for TaskList {
@@ -439,3 +534,271 @@ impl ToolExecutor for ManageTaskListTool {
         }))
     }
 }
+
+// ============================================================================
+// ManageTasksTool - applies a batch of edits to the current task list
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum TaskOperation {
+    Add {
+        description: String,
+        #[serde(default)]
+        status: Option<TaskStatus>,
+        #[serde(default)]
+        position: Option<usize>,
+    },
+    Remove {
+        task_id: usize,
+    },
+    Reorder {
+        task_id: usize,
+        position: usize,
+    },
+    UpdateStatus {
+        task_id: usize,
+        status: TaskStatus,
+    },
+    Retitle {
+        #[serde(default)]
+        task_id: Option<usize>,
+        title: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct ManageTasksInput {
+    operations: Vec<TaskOperation>,
+}
+
+/// Applies `operations` in order to a copy of `list`. Task ids refer to the
+/// list as it was before the batch, so earlier operations don't shift them;
+/// positions are indexes into the list as it stands when the operation runs.
+/// Any invalid operation rejects the whole batch.
+fn apply_task_operations(
+    list: &TaskList,
+    operations: &[TaskOperation],
+) -> Result<(String, Vec<TaskWithStatus>)> {
+    let mut title = list.title.clone();
+    let mut tasks: Vec<(Option<usize>, TaskWithStatus)> = list
+        .tasks
+        .iter()
+        .map(|task| {
+            (
+                Some(task.id),
+                TaskWithStatus {
+                    description: task.description.clone(),
+                    status: task.status,
+                },
+            )
+        })
+        .collect();
+
+    let index_of = |tasks: &[(Option<usize>, TaskWithStatus)], index: usize, task_id: usize| {
+        tasks
+            .iter()
+            .position(|(id, _)| *id == Some(task_id))
+            .ok_or_else(|| {
+                anyhow::anyhow!("Operation {index}: no task with id {task_id} in the task list")
+            })
+    };
+
+    for (index, operation) in operations.iter().enumerate() {
+        let index = index + 1;
+        match operation {
+            TaskOperation::Add {
+                description,
+                status,
+                position,
+            } => {
+                let position = position.unwrap_or(tasks.len());
+                if position > tasks.len() {
+                    anyhow::bail!(
+                        "Operation {index}: position {position} is past the end of the list ({} tasks)",
+                        tasks.len()
+                    );
+                }
+                tasks.insert(
+                    position,
+                    (
+                        None,
+                        TaskWithStatus {
+                            description: description.clone(),
+                            status: status.unwrap_or(TaskStatus::Pending),
+                        },
+                    ),
+                );
+            }
+            TaskOperation::Remove { task_id } => {
+                let at = index_of(&tasks, index, *task_id)?;
+                tasks.remove(at);
+            }
+            TaskOperation::Reorder { task_id, position } => {
+                let at = index_of(&tasks, index, *task_id)?;
+                if *position >= tasks.len() {
+                    anyhow::bail!(
+                        "Operation {index}: position {position} is out of range for {} tasks",
+                        tasks.len()
+                    );
+                }
+                let task = tasks.remove(at);
+                tasks.insert(*position, task);
+            }
+            TaskOperation::UpdateStatus { task_id, status } => {
+                let at = index_of(&tasks, index, *task_id)?;
+                tasks[at].1.status = *status;
+            }
+            TaskOperation::Retitle {
+                task_id: Some(task_id),
+                title: description,
+            } => {
+                let at = index_of(&tasks, index, *task_id)?;
+                tasks[at].1.description = description.clone();
+            }
+            TaskOperation::Retitle {
+                task_id: None,
+                title: new_title,
+            } => {
+                title = new_title.clone();
+            }
+        }
+    }
+
+    if tasks.is_empty() {
+        anyhow::bail!("Task list cannot be empty");
+    }
+    Ok((title, tasks.into_iter().map(|(_, task)| task).collect()))
+}
+
+pub struct ManageTasksTool {
+    pub(crate) inner: Arc<TaskListModuleInner>,
+}
+
+impl ManageTasksTool {
+    pub fn tool_name() -> ToolName {
+        ToolName::new("manage_tasks")
+    }
+}
+
+struct ManageTasksHandle {
+    title: String,
+    tasks: Vec<TaskWithStatus>,
+    operation_count: usize,
+    tool_use_id: String,
+    inner: Arc<TaskListModuleInner>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolCallHandle for ManageTasksHandle {
+    fn tool_request(&self) -> ToolRequestEvent {
+        ToolRequestEvent {
+            tool_call_id: self.tool_use_id.clone(),
+            tool_name: "manage_tasks".to_string(),
+            tool_type: ToolRequestType::Other {
+                args: json!({ "title": self.title, "operations": self.operation_count }),
+            },
+        }
+    }
+
+    async fn execute(self: Box<Self>) -> ToolOutput {
+        self.inner.replace(self.title, self.tasks);
+        let list = self.inner.get();
+
+        let mut content = format!("Task list updated: {}\n", list.title);
+        for task in &list.tasks {
+            content.push_str(&format!(
+                "  - Task {} [{:?}]: {}\n",
+                task.id, task.status, task.description
+            ));
+        }
+        ToolOutput::Result {
+            content,
+            is_error: false,
+            continuation: ContinuationPreference::Continue,
+            ui_result: ToolExecutionResult::Other {
+                result: json!({ "title": list.title, "task_count": list.tasks.len() }),
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ToolExecutor for ManageTasksTool {
+    fn name(&self) -> String {
+        "manage_tasks".to_string()
+    }
+
+    fn description(&self) -> String {
+        "Edit the current task list in place with a batch of operations (add, remove, reorder, update_status, retitle), applied in order and all-or-nothing. Task ids are those shown in the task list before this call. Returns the resulting list. Prefer this over manage_task_list for small changes. This tool must be combined with at least 1 other tool call.".to_string()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "operations": {
+                    "type": "array",
+                    "minItems": 1,
+                    "description": "Edits to apply in order",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "op": {
+                                "type": "string",
+                                "enum": ["add", "remove", "reorder", "update_status", "retitle"],
+                                "description": "add: new task (description, optional status and position). remove: delete task_id. reorder: move task_id to position. update_status: set task_id's status. retitle: rename task_id, or the list itself when task_id is omitted (title)."
+                            },
+                            "task_id": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "description": "Id of an existing task"
+                            },
+                            "description": {
+                                "type": "string",
+                                "description": "Description of the task to add"
+                            },
+                            "status": {
+                                "type": "string",
+                                "enum": ["pending", "in_progress", "completed", "failed"],
+                                "description": "Task status (defaults to pending for add)"
+                            },
+                            "position": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "description": "0-based index in the list at this point in the batch (defaults to the end for add)"
+                            },
+                            "title": {
+                                "type": "string",
+                                "description": "New task description or list title"
+                            }
+                        },
+                        "required": ["op"]
+                    }
+                }
+            },
+            "required": ["operations"]
+        })
+    }
+
+    fn category(&self) -> ToolCategory {
+        ToolCategory::TaskList
+    }
+
+    async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>> {
+        let input: ManageTasksInput = serde_json::from_value(request.arguments.clone())?;
+        if input.operations.is_empty() {
+            return Err(anyhow::anyhow!("At least one operation is required"));
+        }
+
+        let (title, tasks) = apply_task_operations(&self.inner.get(), &input.operations)?;
+
+        Ok(Box::new(ManageTasksHandle {
+            title,
+            tasks,
+            operation_count: input.operations.len(),
+            tool_use_id: request.tool_use_id.clone(),
+            inner: self.inner.clone(),
+        }))
+    }
+}
@@ -1,2 +1,3 @@
+pub mod repair;
 pub mod session;
 pub mod storage;
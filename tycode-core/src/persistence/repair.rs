@@ -0,0 +1,148 @@
+//! Repairs stored conversations whose tool-use/tool-result pairing was left
+//! inconsistent, typically by a crash between recording a tool call and its
+//! result. Providers reject such conversations outright (Bedrock fails
+//! `toolConfig` validation), so a resumed session would be unusable.
+//!
+//! The conversation is first cut to a clean start with
+//! `safe_conversation_slice` (a user message without tool results); after
+//! that, every tool result must answer a tool use from the assistant message
+//! immediately before it. Role alternation is preserved, so emptied messages
+//! are replaced with a note rather than removed.
+
+use std::collections::HashSet;
+
+use crate::ai::types::{Content, ContentBlock, Message, MessageRole, ToolResultData};
+use crate::modules::memory::background::safe_conversation_slice;
+
+const INTERRUPTED_RESULT: &str =
+    "Tool call was interrupted before a result was recorded. Re-run it if still needed.";
+const LOST_RESULTS_NOTE: &str = "[Tool results from this turn were lost when the session ended]";
+const INTERRUPTED_CALLS_NOTE: &str = "[Tool calls from this turn were interrupted]";
+
+/// Returns the repaired conversation and a description of each fix made.
+pub fn repair_conversation(messages: Vec<Message>) -> (Vec<Message>, Vec<String>) {
+    let mut fixes = Vec::new();
+
+    let total = messages.len();
+    let messages = safe_conversation_slice(&messages, total);
+    let start = total - messages.len();
+    if start > 0 {
+        fixes.push(format!(
+            "dropped {start} leading message(s) that continued a missing tool exchange"
+        ));
+    }
+
+    let mut repaired: Vec<Message> = Vec::new();
+    // Tool use ids from the preceding assistant message awaiting results
+    let mut pending: Vec<String> = Vec::new();
+
+    for (offset, message) in messages.into_iter().enumerate() {
+        let idx = start + offset;
+        match message.role {
+            MessageRole::User => {
+                let mut blocks = message.content.into_blocks();
+                let before = blocks.len();
+                blocks.retain(|block| match block {
+                    ContentBlock::ToolResult(result) => pending.contains(&result.tool_use_id),
+                    _ => true,
+                });
+                if blocks.len() < before {
+                    fixes.push(format!(
+                        "messages.{idx}: dropped {} orphaned tool result(s)",
+                        before - blocks.len()
+                    ));
+                }
+
+                let answered: HashSet<String> = blocks
+                    .iter()
+                    .filter_map(|block| match block {
+                        ContentBlock::ToolResult(result) => Some(result.tool_use_id.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                let missing: Vec<String> = pending
+                    .drain(..)
+                    .filter(|id| !answered.contains(id))
+                    .collect();
+                if !missing.is_empty() {
+                    fixes.push(format!(
+                        "messages.{idx}: added results for {} unanswered tool call(s)",
+                        missing.len()
+                    ));
+                    let mut paired = interrupted_results(missing);
+                    paired.append(&mut blocks);
+                    blocks = paired;
+                }
+
+                if blocks.is_empty() {
+                    blocks.push(ContentBlock::Text(LOST_RESULTS_NOTE.to_string()));
+                }
+                repaired.push(Message {
+                    role: MessageRole::User,
+                    content: Content::new(blocks),
+                });
+            }
+            MessageRole::Assistant => {
+                if !pending.is_empty() {
+                    fixes.push(format!(
+                        "messages.{idx}: inserted results for {} unanswered tool call(s)",
+                        pending.len()
+                    ));
+                    repaired.push(Message {
+                        role: MessageRole::User,
+                        content: Content::new(interrupted_results(std::mem::take(&mut pending))),
+                    });
+                }
+                pending = message
+                    .content
+                    .tool_uses()
+                    .iter()
+                    .map(|tool_use| tool_use.id.clone())
+                    .collect();
+                repaired.push(message);
+            }
+        }
+    }
+
+    // A trailing assistant message whose tools never ran: drop the calls so
+    // the next user message follows a plain assistant turn.
+    if !pending.is_empty() {
+        if let Some(last) = repaired.pop() {
+            let mut blocks: Vec<ContentBlock> = last
+                .content
+                .into_blocks()
+                .into_iter()
+                .filter(|block| !matches!(block, ContentBlock::ToolUse(_)))
+                .collect();
+            if !blocks
+                .iter()
+                .any(|block| matches!(block, ContentBlock::Text(_)))
+            {
+                blocks.push(ContentBlock::Text(INTERRUPTED_CALLS_NOTE.to_string()));
+            }
+            fixes.push(format!(
+                "last message: removed {} tool call(s) that never ran",
+                pending.len()
+            ));
+            repaired.push(Message {
+                role: MessageRole::Assistant,
+                content: Content::new(blocks),
+            });
+        }
+    }
+
+    (repaired, fixes)
+}
+
+fn interrupted_results(tool_use_ids: Vec<String>) -> Vec<ContentBlock> {
+    tool_use_ids
+        .into_iter()
+        .map(|tool_use_id| {
+            ContentBlock::ToolResult(ToolResultData {
+                tool_use_id,
+                content: INTERRUPTED_RESULT.to_string(),
+                is_error: true,
+            })
+        })
+        .collect()
+}
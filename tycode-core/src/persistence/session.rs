@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::ai::types::{Message, MessageRole};
+use crate::chat::edit_review::SavedReview;
 use crate::chat::events::ChatEvent;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +15,9 @@ pub struct SessionData {
     pub last_modified: u64,
     pub messages: Vec<Message>,
     pub events: Vec<ChatEvent>,
+    /// Proposed edits still awaiting the user's review.
+    #[serde(default, skip_serializing_if = "SavedReview::is_empty")]
+    pub edit_review: SavedReview,
 
     /// Module state is flattened into the JSON root.
     /// This provides backwards compatibility: old sessions with top-level
@@ -31,6 +35,7 @@ impl SessionData {
             last_modified: now,
             messages,
             events: Vec::new(),
+            edit_review: SavedReview::default(),
             module_state: HashMap::new(),
         }
     }
@@ -2,6 +2,7 @@ use crate::ai::{
     model::{Model, ModelCost},
     types::{ModelSettings, ReasoningBudget},
 };
+use crate::tools::r#trait::ToolCapability;
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -16,6 +17,21 @@ pub enum FileModificationApi {
     FindReplace,
 }
 
+/// What `complete_task` does when the agent's own task list still has
+/// pending or in-progress tasks. Reporting failure is never guarded.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionGuard {
+    /// Complete regardless of the task list.
+    #[default]
+    Off,
+    /// Decline and list the unfinished tasks; the agent may still complete
+    /// by calling again with `allow_incomplete: true`.
+    Warn,
+    /// Decline until every task is marked completed or failed.
+    Block,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
 pub enum ReviewLevel {
     #[default]
@@ -61,11 +77,65 @@ pub enum CommunicationTone {
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AutonomyLevel {
-    /// Agent can proceed with implementation directly without presenting a plan
+    /// Agent presents a plan and waits for approval, and every write (file
+    /// edits, git changes) is held for a y/n confirmation regardless of
+    /// `confirm_edits` or the git module's `confirm_changes`
+    Conservative,
+    /// Agent presents a plan and waits for approval before implementing;
+    /// writes are confirmed only when the settings above ask for it
     #[default]
-    FullyAutonomous,
-    /// Agent must present and get approval before implementing changes
-    PlanApprovalRequired,
+    #[serde(alias = "plan_approval_required")]
+    Balanced,
+    /// Agent proceeds without presenting a plan, and writes are applied
+    /// without pausing for confirmation even when the settings above ask
+    /// for it
+    #[serde(alias = "fully_autonomous")]
+    Autonomous,
+}
+
+impl AutonomyLevel {
+    /// Whether a write should wait for the user's y/n, given whether the
+    /// settings for that kind of write (`confirm_edits`, `confirm_changes`)
+    /// ask for confirmation.
+    pub fn confirms_writes(self, configured: bool) -> bool {
+        match self {
+            Self::Conservative => true,
+            Self::Balanced => configured,
+            Self::Autonomous => false,
+        }
+    }
+}
+
+/// Which kinds of tools agents are offered.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityMode {
+    /// Only tools that read; anything that writes files, runs commands or
+    /// reaches the network is hidden
+    ReadOnly,
+    /// Read, write and execute tools; tools that reach the network (MCP
+    /// servers, image generation, semantic search) are hidden
+    Auto,
+    /// Every tool, unrestricted
+    #[default]
+    All,
+}
+
+impl SecurityMode {
+    /// Whether tools needing `capability` are offered in this mode.
+    pub fn grants(&self, capability: ToolCapability) -> bool {
+        match self {
+            SecurityMode::ReadOnly => false,
+            SecurityMode::Auto => capability != ToolCapability::Network,
+            SecurityMode::All => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SecuritySettings {
+    #[serde(default)]
+    pub mode: SecurityMode,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -123,6 +193,30 @@ pub struct VoiceSettings {
 
     #[serde(default)]
     pub stt_providers: HashMap<String, SttProviderConfig>,
+
+    /// Microphone to capture from, by name as listed by `/voice devices`.
+    /// Falls back to the system default input if unset or not found.
+    #[serde(default)]
+    pub input_device: Option<String>,
+
+    /// Run transcripts that are just a command name (e.g. "clear"), or a
+    /// command with arguments after "slash"/"command", as slash commands
+    /// instead of sending them to the model. Off by default.
+    #[serde(default)]
+    pub spoken_commands: bool,
+
+    /// Phrase that cancels the running agent when spoken, listened for even
+    /// while the model is responding. Disabled when unset.
+    #[serde(default)]
+    pub cancel_keyword: Option<String>,
+
+    /// Minimum time between two cancels triggered by the keyword
+    #[serde(default = "default_cancel_debounce_ms")]
+    pub cancel_debounce_ms: u64,
+}
+
+fn default_cancel_debounce_ms() -> u64 {
+    2000
 }
 
 impl Default for VoiceSettings {
@@ -132,6 +226,10 @@ impl Default for VoiceSettings {
             default_stt: None,
             tts_providers: HashMap::new(),
             stt_providers: HashMap::new(),
+            input_device: None,
+            spoken_commands: false,
+            cancel_keyword: None,
+            cancel_debounce_ms: default_cancel_debounce_ms(),
         }
     }
 }
@@ -187,6 +285,185 @@ impl Default for SkillsConfig {
     }
 }
 
+/// Terminal "thinking" indicator shown while waiting on a response.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ThinkingIndicatorSettings {
+    /// Show seconds elapsed since the request started next to the spinner
+    #[serde(default = "default_show_elapsed")]
+    pub show_elapsed: bool,
+
+    /// Seconds of waiting before each reassurance message is shown. 0 disables
+    /// the messages.
+    #[serde(default = "default_thinking_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Messages shown after each interval, in order; the last one repeats
+    #[serde(default = "default_thinking_messages")]
+    pub messages: Vec<String>,
+}
+
+fn default_show_elapsed() -> bool {
+    true
+}
+
+fn default_thinking_interval_secs() -> u64 {
+    30
+}
+
+fn default_thinking_messages() -> Vec<String> {
+    vec![
+        "still working...".to_string(),
+        "still working, long responses can take a while...".to_string(),
+    ]
+}
+
+impl Default for ThinkingIndicatorSettings {
+    fn default() -> Self {
+        Self {
+            show_elapsed: default_show_elapsed(),
+            interval_secs: default_thinking_interval_secs(),
+            messages: default_thinking_messages(),
+        }
+    }
+}
+
+impl ThinkingIndicatorSettings {
+    /// The reassurance message for a wait of `elapsed`, if one is due.
+    pub fn message_for(&self, elapsed: std::time::Duration) -> Option<&str> {
+        if self.interval_secs == 0 {
+            return None;
+        }
+        let intervals = (elapsed.as_secs() / self.interval_secs) as usize;
+        let index = intervals
+            .checked_sub(1)?
+            .min(self.messages.len().checked_sub(1)?);
+        Some(&self.messages[index])
+    }
+}
+
+/// Opt-in cache of provider responses for repeated identical requests,
+/// mainly useful while iterating on prompts and in tests.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResponseCacheSettings {
+    /// Return stored responses for exact repeats of a request
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Seconds a stored response stays valid
+    #[serde(default = "default_response_cache_ttl_secs")]
+    pub ttl_secs: u64,
+
+    /// Maximum stored responses; the oldest are evicted first
+    #[serde(default = "default_response_cache_max_entries")]
+    pub max_entries: usize,
+
+    /// Also cache streamed requests, which include every chat turn
+    #[serde(default)]
+    pub cache_streaming: bool,
+
+    /// Also cache responses that call tools. Replaying these repeats the
+    /// tool calls, including their side effects.
+    #[serde(default)]
+    pub cache_tool_use: bool,
+}
+
+fn default_response_cache_ttl_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_response_cache_max_entries() -> usize {
+    500
+}
+
+impl Default for ResponseCacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: default_response_cache_ttl_secs(),
+            max_entries: default_response_cache_max_entries(),
+            cache_streaming: false,
+            cache_tool_use: false,
+        }
+    }
+}
+
+/// What to do when the agent keeps repeating the same tool call.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LoopAction {
+    /// Tell the agent it is repeating itself and let it continue
+    #[default]
+    Nudge,
+    /// End the turn and wait for the user
+    Pause,
+}
+
+/// Detection of agents stuck repeating identical tool calls within a turn.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LoopDetectionSettings {
+    /// Watch for repeated identical tool calls
+    #[serde(default = "default_loop_detection_enabled")]
+    pub enabled: bool,
+
+    /// Identical calls (same tool and arguments) that count as a loop
+    #[serde(default = "default_loop_threshold")]
+    pub threshold: usize,
+
+    /// Number of recent tool calls searched for repeats
+    #[serde(default = "default_loop_window")]
+    pub window: usize,
+
+    /// Intervention once a loop is detected
+    #[serde(default)]
+    pub action: LoopAction,
+}
+
+fn default_loop_detection_enabled() -> bool {
+    true
+}
+
+fn default_loop_threshold() -> usize {
+    3
+}
+
+fn default_loop_window() -> usize {
+    10
+}
+
+impl Default for LoopDetectionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_loop_detection_enabled(),
+            threshold: default_loop_threshold(),
+            window: default_loop_window(),
+            action: LoopAction::default(),
+        }
+    }
+}
+
+/// Per-agent temperature that moves linearly from `start` on the agent's
+/// first turn to `end` after `turns` turns, and stays at `end` from then on.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TemperatureSchedule {
+    /// Temperature for the first turn
+    pub start: f32,
+    /// Temperature once the schedule has run its course
+    pub end: f32,
+    /// Turns taken to move from `start` to `end`
+    pub turns: u32,
+}
+
+impl TemperatureSchedule {
+    /// Temperature for the 0-based `turn`.
+    pub fn temperature_at(&self, turn: usize) -> f32 {
+        if self.turns == 0 || turn >= self.turns as usize {
+            return self.end;
+        }
+        let progress = turn as f32 / self.turns as f32;
+        self.start + (self.end - self.start) * progress
+    }
+}
+
 /// Core application settings.
 ///
 /// # Maintainer Note
@@ -209,6 +486,17 @@ pub struct Settings {
     #[serde(default)]
     pub agent_models: HashMap<String, ModelSettings>,
 
+    /// Friendly names for models accepted by `/model` and `/agentmodel`,
+    /// e.g. `fast = "claude-haiku"`. Aliases are checked before the
+    /// built-in model names. Aliases for unknown models are skipped with a
+    /// warning.
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        deserialize_with = "deserialize_model_aliases"
+    )]
+    pub model_aliases: HashMap<String, Model>,
+
     /// Default agent to use for new conversations
     #[serde(default = "default_agent_name")]
     pub default_agent: String,
@@ -230,6 +518,18 @@ pub struct Settings {
     #[serde(default = "default_fanout_concurrency")]
     pub fanout_concurrency: usize,
 
+    /// Maximum read-only tool calls from one assistant turn run concurrently.
+    /// Tools that modify state always run one at a time, in order. Set to 1
+    /// to run every tool call sequentially.
+    #[serde(default = "default_tool_concurrency")]
+    pub tool_concurrency: usize,
+
+    /// Maximum tool calls executed from a single assistant turn. Calls past
+    /// the limit are returned to the model as errors asking it to proceed
+    /// incrementally.
+    #[serde(default = "default_max_tool_calls_per_turn")]
+    pub max_tool_calls_per_turn: usize,
+
     /// How the tycode agent implements code changes: auto (model decides,
     /// swarm unavailable), builder (all changes through the builder
     /// pipeline), or swarm (all changes through the swarm workflow).
@@ -255,22 +555,65 @@ pub struct Settings {
     #[serde(default)]
     pub mcp_servers: HashMap<String, McpServerConfig>,
 
+    /// Most MCP tools offered to the model across all servers. When the
+    /// servers register more, tools from lower-priority servers are dropped.
+    /// Unlimited when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_mcp_tools: Option<usize>,
+
+    /// MCP server names in priority order for `max_mcp_tools`. Servers not
+    /// listed come after the listed ones, in name order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mcp_server_priority: Vec<String>,
+
     /// Controls how sub-agent context is initialized when spawning
     #[serde(default)]
     pub spawn_context_mode: SpawnContextMode,
 
+    /// Guard against `complete_task` while the agent's task list still has
+    /// unfinished tasks.
+    #[serde(default)]
+    pub complete_task_guard: CompletionGuard,
+
+    /// Order of context sections by id (e.g. "memories", "tasks",
+    /// "file_tree", "pinned_files", "build_errors", "lint", "skills"). Unlisted
+    /// sections keep their default order and come first; listed sections
+    /// follow in the given order. Models tend to attend most to the end of
+    /// the context, so list the most important section last.
+    #[serde(default)]
+    pub context_order: Vec<String>,
+
     /// Disable custom steering documents (from .tycode and external agent configs)
     #[serde(default)]
     pub disable_custom_steering: bool,
 
+    /// Built-in steering documents to leave out of the prompt, by name
+    /// (`understanding_tools`, `style_mandates`, `communication_guidelines`)
+    #[serde(default)]
+    pub disabled_steering_builtins: Vec<String>,
+
     /// Communication tone for agent responses
     #[serde(default)]
     pub communication_tone: CommunicationTone,
 
-    /// Controls whether agent must get plan approval before implementing
+    /// How much the agent checks in with the user: conservative, balanced or
+    /// autonomous
     #[serde(default)]
     pub autonomy_level: AutonomyLevel,
 
+    /// Which kinds of tools agents are offered
+    #[serde(default)]
+    pub security: SecuritySettings,
+
+    /// Agent-specific autonomy level overrides
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub agent_autonomy_levels: HashMap<String, AutonomyLevel>,
+
+    /// Agent-specific temperature schedules, replacing the model's static
+    /// temperature for that agent
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub agent_temperature_schedules: HashMap<String, TemperatureSchedule>,
+
     /// Voice/speech-to-text configuration
     #[serde(default)]
     pub voice: VoiceSettings,
@@ -287,6 +630,20 @@ pub struct Settings {
     #[serde(default)]
     pub disable_streaming: bool,
 
+    /// Spinner, elapsed time and reassurance messages shown by the CLI while
+    /// a response is pending
+    #[serde(default)]
+    pub thinking_indicator: ThinkingIndicatorSettings,
+
+    /// Reuse stored responses for identical requests, stored under
+    /// `~/.tycode/cache/responses`
+    #[serde(default)]
+    pub response_cache: ResponseCacheSettings,
+
+    /// Detect repeated identical tool calls within a turn and intervene
+    #[serde(default)]
+    pub loop_detection: LoopDetectionSettings,
+
     /// Enables modules to own their configuration without modifying tycode-core,
     /// supporting external/plugin modules that aren't known at compile time.
     #[serde(default)]
@@ -303,6 +660,11 @@ pub enum McpServerConfig {
         /// Optional HTTP headers (e.g. Authorization)
         #[serde(default)]
         headers: HashMap<String, String>,
+
+        /// Seconds to wait for the server to start and list its tools
+        /// before it is skipped. Defaults to 30.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        startup_timeout_secs: Option<u64>,
     },
     Stdio {
         /// Command to execute for the MCP server
@@ -315,9 +677,16 @@ pub enum McpServerConfig {
         /// Environment variables to set for the server process
         #[serde(default)]
         env: HashMap<String, String>,
+
+        /// Seconds to wait for the server to start and list its tools
+        /// before it is skipped. Defaults to 30.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        startup_timeout_secs: Option<u64>,
     },
 }
 
+const DEFAULT_MCP_STARTUP_TIMEOUT_SECS: u64 = 30;
+
 impl McpServerConfig {
     /// Returns a display label for logging/UI (the command or URL)
     pub fn display_label(&self) -> &str {
@@ -326,6 +695,21 @@ impl McpServerConfig {
             McpServerConfig::Http { url, .. } => url,
         }
     }
+
+    /// How long the server may take to start and list its tools.
+    pub fn startup_timeout(&self) -> std::time::Duration {
+        let secs = match self {
+            McpServerConfig::Stdio {
+                startup_timeout_secs,
+                ..
+            }
+            | McpServerConfig::Http {
+                startup_timeout_secs,
+                ..
+            } => startup_timeout_secs.unwrap_or(DEFAULT_MCP_STARTUP_TIMEOUT_SECS),
+        };
+        std::time::Duration::from_secs(secs)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -342,6 +726,10 @@ pub enum ProviderConfig {
         /// Bedrock models (e.g. Fable in us-west-2, GPT in us-east-2).
         #[serde(default, skip_serializing_if = "Option::is_none")]
         mantle_region: Option<String>,
+        /// Maximum requests in flight to this provider at once; further
+        /// requests queue. Unlimited when unset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_concurrent_requests: Option<usize>,
     },
     #[serde(rename = "mock")]
     Mock {
@@ -349,7 +737,13 @@ pub enum ProviderConfig {
         behavior: crate::ai::mock::MockBehavior,
     },
     #[serde(rename = "openrouter")]
-    OpenRouter { api_key: String },
+    OpenRouter {
+        api_key: String,
+        /// Maximum requests in flight to this provider at once; further
+        /// requests queue. Unlimited when unset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_concurrent_requests: Option<usize>,
+    },
     #[serde(other)]
     Unknown,
 }
@@ -366,10 +760,44 @@ fn default_fanout_concurrency() -> usize {
     4
 }
 
+fn default_tool_concurrency() -> usize {
+    4
+}
+
+fn default_max_tool_calls_per_turn() -> usize {
+    50
+}
+
 fn default_orchestration_progress_messages() -> bool {
     true
 }
 
+/// Keeps the aliases that name a known model, so one bad target does not
+/// fail the whole settings load.
+fn deserialize_model_aliases<'de, D>(deserializer: D) -> Result<HashMap<String, Model>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Target {
+        Known(Model),
+        Invalid(serde_json::Value),
+    }
+
+    let aliases = HashMap::<String, Target>::deserialize(deserializer)?;
+    Ok(aliases
+        .into_iter()
+        .filter_map(|(alias, target)| match target {
+            Target::Known(model) => Some((alias, model)),
+            Target::Invalid(target) => {
+                tracing::warn!("Ignoring model alias `{alias}`: unknown model {target}");
+                None
+            }
+        })
+        .collect())
+}
+
 fn default_agent_name() -> String {
     "tycode".to_string()
 }
@@ -380,21 +808,35 @@ impl Default for Settings {
             active_provider: None,
             providers: HashMap::new(),
             agent_models: HashMap::new(),
+            model_aliases: HashMap::new(),
             default_agent: default_agent_name(),
             model_quality: None,
             review_level: ReviewLevel::None,
             max_review_rounds: default_max_review_rounds(),
             fanout_concurrency: default_fanout_concurrency(),
+            tool_concurrency: default_tool_concurrency(),
+            max_tool_calls_per_turn: default_max_tool_calls_per_turn(),
             orchestration_mode: OrchestrationMode::default(),
             orchestration_progress_messages: default_orchestration_progress_messages(),
             swarm_models: Vec::new(),
             mcp_servers: HashMap::new(),
+            max_mcp_tools: None,
+            mcp_server_priority: Vec::new(),
             spawn_context_mode: SpawnContextMode::default(),
+            complete_task_guard: CompletionGuard::default(),
+            context_order: Vec::new(),
             disable_custom_steering: false,
+            disabled_steering_builtins: Vec::new(),
             communication_tone: CommunicationTone::default(),
             autonomy_level: AutonomyLevel::default(),
+            security: SecuritySettings::default(),
+            agent_autonomy_levels: HashMap::new(),
+            agent_temperature_schedules: HashMap::new(),
             reasoning_effort: None,
             disable_streaming: false,
+            thinking_indicator: ThinkingIndicatorSettings::default(),
+            response_cache: ResponseCacheSettings::default(),
+            loop_detection: LoopDetectionSettings::default(),
             voice: VoiceSettings::default(),
             skills: SkillsConfig::default(),
             modules: HashMap::new(),
@@ -502,6 +944,29 @@ impl Settings {
     pub fn set_agent_model(&mut self, agent_name: String, model: ModelSettings) {
         self.agent_models.insert(agent_name, model);
     }
+
+    /// The autonomy level for a specific agent, falling back to the global
+    /// `autonomy_level`.
+    pub fn autonomy_level_for(&self, agent_name: &str) -> AutonomyLevel {
+        self.agent_autonomy_levels
+            .get(agent_name)
+            .copied()
+            .unwrap_or(self.autonomy_level)
+    }
+
+    /// Resolves a model name typed by the user, checking `model_aliases`
+    /// before the built-in names
+    pub fn resolve_model(&self, name: &str) -> Option<Model> {
+        self.model_aliases
+            .get(name)
+            .copied()
+            .or_else(|| Model::from_name(name))
+    }
+
+    /// The temperature schedule for a specific agent, if one is configured
+    pub fn temperature_schedule_for(&self, agent_name: &str) -> Option<&TemperatureSchedule> {
+        self.agent_temperature_schedules.get(agent_name)
+    }
 }
 
 impl ProviderConfig {
@@ -516,7 +981,22 @@ impl ProviderConfig {
     /// Get the API key for OpenRouter provider
     pub fn openrouter_api_key(&self) -> Option<&str> {
         match self {
-            ProviderConfig::OpenRouter { api_key } => Some(api_key.as_str()),
+            ProviderConfig::OpenRouter { api_key, .. } => Some(api_key.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Cap on concurrent requests to this provider, if one is configured
+    pub fn max_concurrent_requests(&self) -> Option<usize> {
+        match self {
+            ProviderConfig::Bedrock {
+                max_concurrent_requests,
+                ..
+            }
+            | ProviderConfig::OpenRouter {
+                max_concurrent_requests,
+                ..
+            } => *max_concurrent_requests,
             _ => None,
         }
     }
@@ -18,8 +18,59 @@ pub struct SettingsManager {
     // Arc<Mutex<..>> is AI slop friendly - everything wants its own settings
     // and this ensures that everyone has the same instance.
     inner: Arc<Mutex<Settings>>,
+    /// Contents of the workspace `.tycode/settings.toml`, if any. Applied on
+    /// top of every loaded profile and kept out of the global file on save.
+    workspace_overrides: Option<toml::Value>,
+    /// Problems found while loading the settings file that were recovered
+    /// from by using defaults.
+    load_warnings: Vec<String>,
 }
 
+/// Keys that are never replaced by defaults when invalid: silently dropping
+/// them would disconnect the user's provider and clobber it on next save.
+const CRITICAL_KEYS: &[&str] = &["active_provider", "providers"];
+
+/// Workspace-level overrides, relative to a workspace root.
+const WORKSPACE_SETTINGS_PATH: &str = ".tycode/settings.toml";
+
+/// Settings a workspace file may override, as dotted paths; a path covers
+/// everything beneath it. A cloned repository is untrusted, so anything that
+/// reaches providers, MCP servers, security, autonomy, edit confirmation or
+/// runs commands (linters, build watch) can only be set globally.
+const WORKSPACE_OVERRIDABLE_KEYS: &[&str] = &[
+    "default_agent",
+    "agent_models",
+    "model_quality",
+    "review_level",
+    "max_review_rounds",
+    "orchestration_mode",
+    "orchestration_progress_messages",
+    "spawn_context_mode",
+    "complete_task_guard",
+    "context_order",
+    "disable_custom_steering",
+    "disabled_steering_builtins",
+    "communication_tone",
+    "reasoning_effort",
+    "agent_temperature_schedules",
+    "loop_detection",
+    "modules.file.file_modification_api",
+    "modules.file.auto_context_bytes",
+    "modules.file.agent_auto_context_bytes",
+    "modules.file.read_files_max_files",
+    "modules.file.read_files_max_bytes",
+    "modules.file.max_read_file_bytes",
+    "modules.file.latin1_fallback",
+    "modules.file.search_index",
+    "modules.file.search_index_max_bytes",
+    "modules.file.git_blame",
+    "modules.file.replace_mode",
+    "modules.file.modify_context_lines",
+    "modules.file.tool_examples",
+    "modules.semantic_search",
+    "modules.context_management",
+];
+
 impl SettingsManager {
     /// Create a settings manager from a specific settings directory and optional profile
     pub fn from_settings_dir(settings_dir: PathBuf, profile_name: Option<&str>) -> Result<Self> {
@@ -35,13 +86,15 @@ impl SettingsManager {
 
         let current_profile = profile_name.map(|s| s.to_string());
 
-        let loaded = Self::load_from_file(&settings_path)?;
+        let (loaded, load_warnings) = Self::load_from_file(&settings_path)?;
 
         Ok(Self {
             settings_dir,
             settings_path,
             current_profile,
             inner: Arc::new(Mutex::new(loaded)),
+            workspace_overrides: None,
+            load_warnings,
         })
     }
 
@@ -54,13 +107,15 @@ impl SettingsManager {
 
         let current_profile = Self::infer_profile_from_path(&path);
 
-        let loaded = Self::load_from_file(&path)?;
+        let (loaded, load_warnings) = Self::load_from_file(&path)?;
 
         Ok(Self {
             settings_dir,
             settings_path: path,
             current_profile,
             inner: Arc::new(Mutex::new(loaded)),
+            workspace_overrides: None,
+            load_warnings,
         })
     }
 
@@ -94,8 +149,10 @@ impl SettingsManager {
         }
     }
 
-    /// Load settings from a TOML file with backup on parse failure
-    fn load_from_file(path: &Path) -> Result<Settings> {
+    /// Load settings from a TOML file. Invalid values for non-critical keys
+    /// fall back to their defaults and are reported as warnings; TOML syntax
+    /// errors and invalid provider configuration are errors.
+    fn load_from_file(path: &Path) -> Result<(Settings, Vec<String>)> {
         if !path.exists() {
             let default_settings = Settings::default();
             if let Some(parent) = path.parent() {
@@ -106,28 +163,108 @@ impl SettingsManager {
                 .context("Failed to serialize default settings")?;
             fs::write(path, contents)
                 .with_context(|| format!("Failed to write default settings to {path:?}"))?;
-            return Ok(default_settings);
+            return Ok((default_settings, Vec::new()));
         }
 
         let contents = fs::read_to_string(path)
             .with_context(|| format!("Failed to read settings from {path:?}"))?;
 
-        let mut settings: Settings = toml::from_str(&contents)
+        let table: toml::Table = toml::from_str(&contents)
             .with_context(|| format!("Failed to parse settings from {path:?}"))?;
+        let (mut settings, warnings) =
+            deserialize_settings(table).with_context(|| format!("Invalid settings in {path:?}"))?;
+        drop_unknown_providers(&mut settings);
+
+        let warnings = warnings
+            .into_iter()
+            .map(|warning| format!("{}: {warning}", path.display()))
+            .collect();
+        Ok((settings, warnings))
+    }
+
+    /// Applies the workspace `.tycode/settings.toml` found in `workspace_roots`
+    /// on top of the loaded settings. Precedence, lowest to highest: defaults,
+    /// the global file (or active profile), the workspace file. With several
+    /// roots, files must be identical; differing files are an error. Only
+    /// keys in `WORKSPACE_OVERRIDABLE_KEYS` apply; others are ignored with a
+    /// load warning. The overrides stay applied across profile switches and
+    /// are never saved.
+    pub fn with_workspace_overrides(mut self, workspace_roots: &[PathBuf]) -> Result<Self> {
+        let settings_dir = fs::canonicalize(&self.settings_dir).ok();
+        let mut found: Option<(PathBuf, toml::Value)> = None;
+        for root in workspace_roots {
+            let path = root.join(WORKSPACE_SETTINGS_PATH);
+            if !path.is_file() {
+                continue;
+            }
+            // A workspace at the home directory shares the global settings
+            // directory; its settings file is the global one, not an override
+            let dir = path.parent().and_then(|dir| fs::canonicalize(dir).ok());
+            if dir.is_some() && dir == settings_dir {
+                continue;
+            }
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read workspace settings from {path:?}"))?;
+            let table: toml::Table = toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse workspace settings from {path:?}"))?;
+            let value = toml::Value::Table(table);
+            match &found {
+                Some((first, existing)) if *existing != value => bail!(
+                    "Conflicting workspace settings: {} and {} differ; make them identical or remove one",
+                    first.display(),
+                    path.display()
+                ),
+                Some(_) => {}
+                None => found = Some((path, value)),
+            }
+        }
+
+        let Some((path, overrides)) = found else {
+            return Ok(self);
+        };
+        let (overrides, ignored) = retain_overridable(&overrides, "");
+        self.load_warnings.extend(ignored.into_iter().map(|key| {
+            format!(
+                "{}: `{key}` cannot be set by workspace settings and was ignored; set it in your global settings instead",
+                path.display()
+            )
+        }));
+        let merged = Self::apply_overrides(self.settings(), &overrides)?;
+        *self.inner.lock().unwrap() = merged;
+        self.workspace_overrides = Some(overrides);
+        Ok(self)
+    }
+
+    /// Merges `overrides` over `settings`. Invalid override values fall back
+    /// the same way invalid global values do.
+    fn apply_overrides(settings: Settings, overrides: &toml::Value) -> Result<Settings> {
+        let mut value = toml::Value::try_from(&settings).context("Failed to serialize settings")?;
+        merge_toml(&mut value, overrides);
+        let toml::Value::Table(table) = value else {
+            bail!("Settings did not serialize to a table");
+        };
+        let (mut merged, _) = deserialize_settings(table).context("Invalid workspace settings")?;
+        drop_unknown_providers(&mut merged);
+        Ok(merged)
+    }
 
-        settings
-            .providers
-            .retain(|_, v| !matches!(v, crate::settings::config::ProviderConfig::Unknown));
-        settings
-            .voice
-            .tts_providers
-            .retain(|_, v| !matches!(v, crate::settings::config::TtsProviderConfig::Unknown));
-        settings
-            .voice
-            .stt_providers
-            .retain(|_, v| !matches!(v, crate::settings::config::SttProviderConfig::Unknown));
-
-        Ok(settings)
+    /// The form of `settings` to write to disk: values that came from the
+    /// workspace overrides are reset to what `global_path` had (or dropped),
+    /// while values the user changed since loading are kept.
+    fn without_workspace_overrides(
+        &self,
+        settings: &Settings,
+        global_path: &Path,
+    ) -> Result<toml::Value> {
+        let mut value = toml::Value::try_from(settings).context("Failed to serialize settings")?;
+        if let Some(overrides) = &self.workspace_overrides {
+            let global = fs::read_to_string(global_path)
+                .ok()
+                .and_then(|contents| toml::from_str::<toml::Table>(&contents).ok())
+                .map(toml::Value::Table);
+            strip_overrides(&mut value, overrides, global.as_ref());
+        }
+        Ok(value)
     }
 
     /// Get the in-memory settings
@@ -156,7 +293,9 @@ impl SettingsManager {
                 .with_context(|| format!("Failed to create directory: {parent:?}"))?;
         }
 
-        let contents = toml::to_string_pretty(&settings).context("Failed to serialize settings")?;
+        let persisted = self.without_workspace_overrides(&settings, &self.settings_path)?;
+        let contents =
+            toml::to_string_pretty(&persisted).context("Failed to serialize settings")?;
 
         fs::write(&self.settings_path, contents)
             .with_context(|| format!("Failed to write settings to {:?}", self.settings_path))?;
@@ -184,7 +323,10 @@ impl SettingsManager {
         };
         fs::create_dir_all(&self.settings_dir)
             .with_context(|| format!("Failed to create directory: {:?}", self.settings_dir))?;
-        let new_settings = Self::load_from_file(&new_path)?;
+        let (mut new_settings, load_warnings) = Self::load_from_file(&new_path)?;
+        if let Some(overrides) = &self.workspace_overrides {
+            new_settings = Self::apply_overrides(new_settings, overrides)?;
+        }
         self.settings_path = new_path;
         self.current_profile = if name == "default" {
             None
@@ -192,6 +334,7 @@ impl SettingsManager {
             Some(name.to_string())
         };
         *self.inner.lock().unwrap() = new_settings;
+        self.load_warnings = load_warnings;
         Ok(())
     }
 
@@ -205,7 +348,9 @@ impl SettingsManager {
         fs::create_dir_all(&self.settings_dir)
             .with_context(|| format!("Failed to create directory: {:?}", self.settings_dir))?;
         let target_path = self.settings_dir.join(format!("settings_{}.toml", name));
-        let contents = toml::to_string_pretty(&settings).context("Failed to serialize settings")?;
+        let persisted = self.without_workspace_overrides(&settings, &self.settings_path)?;
+        let contents =
+            toml::to_string_pretty(&persisted).context("Failed to serialize settings")?;
         fs::write(&target_path, contents)
             .with_context(|| format!("Failed to write settings to {target_path:?}"))?;
         Ok(())
@@ -241,11 +386,22 @@ impl SettingsManager {
         Ok(profiles)
     }
 
+    /// Invalid settings that were replaced by defaults when the current
+    /// settings file was loaded.
+    pub fn load_warnings(&self) -> &[String] {
+        &self.load_warnings
+    }
+
     /// Get the settings file path
     pub fn path(&self) -> &Path {
         &self.settings_path
     }
 
+    /// Directory holding the settings files, e.g. `~/.tycode`
+    pub fn settings_dir(&self) -> &Path {
+        &self.settings_dir
+    }
+
     /// Get a module's configuration, falling back to default if missing or unparseable.
     pub fn get_module_config<T: Default + DeserializeOwned>(&self, namespace: &str) -> T {
         self.inner
@@ -271,3 +427,155 @@ impl SettingsManager {
         }
     }
 }
+
+/// Deserializes settings, replacing each invalid non-critical value with its
+/// default. Returns a warning for every replaced value naming its key and
+/// the serde error (which states the expected type or variants).
+fn deserialize_settings(mut table: toml::Table) -> Result<(Settings, Vec<String>)> {
+    let mut warnings = Vec::new();
+    let keys: Vec<String> = table.keys().cloned().collect();
+    for key in keys {
+        let Err(error) = check_key(&key, table[&key].clone()) else {
+            continue;
+        };
+        if CRITICAL_KEYS.contains(&key.as_str()) {
+            bail!("Invalid value for `{key}`: {}", error.message());
+        }
+        let dropped = drop_invalid(&mut table, &key);
+        warnings.push(format!(
+            "Invalid value for `{dropped}`, using the default: {}",
+            error.message()
+        ));
+    }
+
+    let settings = toml::Value::Table(table)
+        .try_into()
+        .context("Failed to parse settings")?;
+    for warning in &warnings {
+        tracing::warn!("{warning}");
+    }
+    Ok((settings, warnings))
+}
+
+/// Deserializes `Settings` from just `key`; every other field defaults.
+fn check_key(key: &str, value: toml::Value) -> Result<(), toml::de::Error> {
+    let mut table = toml::Table::new();
+    table.insert(key.to_string(), value);
+    toml::Value::Table(table).try_into::<Settings>().map(|_| ())
+}
+
+/// Removes the invalid part of `table[key]` and returns its dotted path.
+/// When exactly one entry of a nested table is at fault only that entry is
+/// dropped, so one typo does not reset a whole section.
+fn drop_invalid(table: &mut toml::Table, key: &str) -> String {
+    if let Some(toml::Value::Table(inner)) = table.get(key) {
+        let culprit = inner.keys().find(|sub_key| {
+            let mut trimmed = inner.clone();
+            trimmed.remove(*sub_key);
+            check_key(key, toml::Value::Table(trimmed)).is_ok()
+        });
+        if let Some(sub_key) = culprit.cloned() {
+            if let Some(toml::Value::Table(inner)) = table.get_mut(key) {
+                inner.remove(&sub_key);
+            }
+            return format!("{key}.{sub_key}");
+        }
+    }
+    table.remove(key);
+    key.to_string()
+}
+
+fn drop_unknown_providers(settings: &mut Settings) {
+    settings
+        .providers
+        .retain(|_, v| !matches!(v, crate::settings::config::ProviderConfig::Unknown));
+    settings
+        .voice
+        .tts_providers
+        .retain(|_, v| !matches!(v, crate::settings::config::TtsProviderConfig::Unknown));
+    settings
+        .voice
+        .stt_providers
+        .retain(|_, v| !matches!(v, crate::settings::config::SttProviderConfig::Unknown));
+}
+
+/// The part of the workspace `overrides` table (at dotted `prefix`) that
+/// `WORKSPACE_OVERRIDABLE_KEYS` allows, plus the dotted paths of the keys
+/// that were dropped.
+fn retain_overridable(overrides: &toml::Value, prefix: &str) -> (toml::Value, Vec<String>) {
+    let mut kept = toml::Table::new();
+    let mut ignored = Vec::new();
+    if let toml::Value::Table(table) = overrides {
+        for (key, value) in table {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            if WORKSPACE_OVERRIDABLE_KEYS.contains(&path.as_str()) {
+                kept.insert(key.clone(), value.clone());
+                continue;
+            }
+            let nested = format!("{path}.");
+            let has_allowed_children = WORKSPACE_OVERRIDABLE_KEYS
+                .iter()
+                .any(|allowed| allowed.starts_with(&nested));
+            if has_allowed_children && value.is_table() {
+                let (child, child_ignored) = retain_overridable(value, &path);
+                ignored.extend(child_ignored);
+                if child.as_table().is_some_and(|child| !child.is_empty()) {
+                    kept.insert(key.clone(), child);
+                }
+                continue;
+            }
+            ignored.push(path);
+        }
+    }
+    (toml::Value::Table(kept), ignored)
+}
+
+/// Recursively merges `overlay` into `base`. Tables merge key by key; any
+/// other value (including arrays) is replaced wholesale.
+fn merge_toml(base: &mut toml::Value, overlay: &toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+/// Inverse of `merge_toml` for persistence: every key of `overrides` whose
+/// value in `value` is still the override is reset to its `global` value,
+/// or removed if the global file did not set it.
+fn strip_overrides(value: &mut toml::Value, overrides: &toml::Value, global: Option<&toml::Value>) {
+    let (toml::Value::Table(table), toml::Value::Table(overrides)) = (value, overrides) else {
+        return;
+    };
+    for (key, override_value) in overrides {
+        let global_value = global.and_then(|global| global.get(key));
+        let Some(current) = table.get_mut(key) else {
+            continue;
+        };
+        if current.is_table() && override_value.is_table() {
+            strip_overrides(current, override_value, global_value);
+            continue;
+        }
+        if current != override_value {
+            continue;
+        }
+        match global_value {
+            Some(global_value) => *current = global_value.clone(),
+            None => {
+                table.remove(key);
+            }
+        }
+    }
+}
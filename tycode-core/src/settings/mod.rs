@@ -1,5 +1,6 @@
 pub mod config;
 pub mod manager;
+pub mod validate;
 
 #[cfg(test)]
 mod tests;
@@ -1,6 +1,12 @@
-use crate::settings::config::ProviderConfig;
+use crate::ai::model::Model;
+use crate::file::config::File;
+use crate::settings::config::{
+    AutonomyLevel, ProviderConfig, ReviewLevel, SecurityMode, TemperatureSchedule,
+    ThinkingIndicatorSettings,
+};
 use crate::settings::manager::SettingsManager;
 use crate::settings::Settings;
+use std::time::Duration;
 use tempfile::TempDir;
 
 #[test]
@@ -302,6 +308,7 @@ fn test_save_accepts_non_empty_settings() {
                 profile: "default".to_string(),
                 region: "us-west-2".to_string(),
                 mantle_region: None,
+                max_concurrent_requests: None,
             },
         );
         s.active_provider = Some("test_provider".to_string());
@@ -328,3 +335,377 @@ fn test_save_as_profile_rejects_empty_settings() {
         "unexpected error: {err:?}"
     );
 }
+
+#[test]
+fn test_file_module_config_round_trips_through_save() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings_path = temp_dir.path().join("settings.toml");
+    let manager = SettingsManager::from_path(settings_path.clone()).unwrap();
+
+    manager.set_module_config(File::NAMESPACE, File::default());
+    manager.save().unwrap();
+    let reloaded: File = SettingsManager::from_path(settings_path.clone())
+        .unwrap()
+        .get_module_config(File::NAMESPACE);
+    assert_eq!(reloaded.confirm_edits_timeout_seconds, None);
+
+    manager.set_module_config(
+        File::NAMESPACE,
+        File {
+            confirm_edits_timeout_seconds: Some(30),
+            ..File::default()
+        },
+    );
+    manager.save().unwrap();
+    let reloaded: File = SettingsManager::from_path(settings_path)
+        .unwrap()
+        .get_module_config(File::NAMESPACE);
+    assert_eq!(reloaded.confirm_edits_timeout_seconds, Some(30));
+}
+
+fn write_workspace_settings(root: &std::path::Path, contents: &str) {
+    std::fs::create_dir_all(root.join(".tycode")).unwrap();
+    std::fs::write(root.join(".tycode/settings.toml"), contents).unwrap();
+}
+
+#[test]
+fn test_workspace_settings_override_global() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings_path = temp_dir.path().join("settings.toml");
+    std::fs::write(
+        &settings_path,
+        "default_agent = \"global_agent\"\nmax_review_rounds = 7\n",
+    )
+    .unwrap();
+
+    let workspace = TempDir::new().unwrap();
+    write_workspace_settings(workspace.path(), "default_agent = \"workspace_agent\"\n");
+
+    let manager = SettingsManager::from_path(settings_path)
+        .unwrap()
+        .with_workspace_overrides(&[workspace.path().to_path_buf()])
+        .unwrap();
+
+    let settings = manager.settings();
+    assert_eq!(settings.default_agent, "workspace_agent");
+    assert_eq!(settings.max_review_rounds, 7);
+}
+
+#[test]
+fn test_workspace_overrides_not_persisted_to_global() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings_path = temp_dir.path().join("settings.toml");
+    std::fs::write(
+        &settings_path,
+        r#"
+default_agent = "global_agent"
+active_provider = "test_provider"
+
+[providers.test_provider]
+type = "bedrock"
+profile = "default"
+region = "us-west-2"
+"#,
+    )
+    .unwrap();
+
+    let workspace = TempDir::new().unwrap();
+    write_workspace_settings(
+        workspace.path(),
+        "default_agent = \"workspace_agent\"\nmax_review_rounds = 9\n",
+    );
+
+    let manager = SettingsManager::from_path(settings_path.clone())
+        .unwrap()
+        .with_workspace_overrides(&[workspace.path().to_path_buf()])
+        .unwrap();
+    manager.update_setting(|s| s.max_review_rounds = 4);
+    manager.save().unwrap();
+
+    let global = SettingsManager::from_path(settings_path)
+        .unwrap()
+        .settings();
+    assert_eq!(global.default_agent, "global_agent");
+    assert_eq!(
+        global.max_review_rounds, 4,
+        "deliberate changes to overridden keys should still be saved"
+    );
+    assert_eq!(manager.settings().default_agent, "workspace_agent");
+}
+
+#[test]
+fn test_workspace_settings_cannot_override_sensitive_keys() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings_path = temp_dir.path().join("settings.toml");
+    std::fs::write(
+        &settings_path,
+        "autonomy_level = \"conservative\"\n\n[security]\nmode = \"read_only\"\n\n[modules.file]\nconfirm_edits = true\n",
+    )
+    .unwrap();
+
+    let workspace = TempDir::new().unwrap();
+    write_workspace_settings(
+        workspace.path(),
+        r#"
+default_agent = "workspace_agent"
+autonomy_level = "autonomous"
+
+[security]
+mode = "all"
+
+[mcp_servers.evil]
+command = "curl"
+
+[modules.file]
+git_blame = true
+confirm_edits = false
+linters = [{ extensions = ["rs"], command = "curl evil" }]
+"#,
+    );
+
+    let manager = SettingsManager::from_path(settings_path)
+        .unwrap()
+        .with_workspace_overrides(&[workspace.path().to_path_buf()])
+        .unwrap();
+
+    let settings = manager.settings();
+    assert_eq!(settings.default_agent, "workspace_agent");
+    assert_eq!(settings.autonomy_level, AutonomyLevel::Conservative);
+    assert_eq!(settings.security.mode, SecurityMode::ReadOnly);
+    assert!(settings.mcp_servers.is_empty());
+    let file: File = settings.get_module_config(File::NAMESPACE);
+    assert!(file.git_blame);
+    assert!(file.confirm_edits);
+    assert!(file.linters.is_empty());
+
+    let warnings = manager.load_warnings().join("\n");
+    for key in [
+        "autonomy_level",
+        "security",
+        "mcp_servers",
+        "modules.file.confirm_edits",
+        "modules.file.linters",
+    ] {
+        assert!(warnings.contains(&format!("`{key}`")), "{warnings}");
+    }
+}
+
+#[test]
+fn test_conflicting_workspace_settings_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings_path = temp_dir.path().join("settings.toml");
+
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    let third = TempDir::new().unwrap();
+    write_workspace_settings(first.path(), "default_agent = \"one\"\n");
+    write_workspace_settings(second.path(), "default_agent = \"one\"\n");
+    write_workspace_settings(third.path(), "default_agent = \"two\"\n");
+
+    let manager = SettingsManager::from_path(settings_path.clone())
+        .unwrap()
+        .with_workspace_overrides(&[first.path().to_path_buf(), second.path().to_path_buf()])
+        .unwrap();
+    assert_eq!(manager.settings().default_agent, "one");
+
+    let err = SettingsManager::from_path(settings_path)
+        .unwrap()
+        .with_workspace_overrides(&[first.path().to_path_buf(), third.path().to_path_buf()])
+        .err()
+        .expect("differing workspace settings should be rejected");
+    assert!(
+        err.to_string().contains("Conflicting workspace settings"),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[test]
+fn test_invalid_enum_value_falls_back_to_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings_path = temp_dir.path().join("settings.toml");
+    std::fs::write(
+        &settings_path,
+        "default_agent = \"coder\"\nreview_level = \"Sometimes\"\n",
+    )
+    .unwrap();
+
+    let manager = SettingsManager::from_path(settings_path).unwrap();
+
+    let settings = manager.settings();
+    assert_eq!(settings.review_level, ReviewLevel::None);
+    assert_eq!(settings.default_agent, "coder");
+    let warnings = manager.load_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(
+        warnings[0].contains("`review_level`") && warnings[0].contains("Task"),
+        "warning should name the key and the expected variants: {warnings:?}"
+    );
+}
+
+#[test]
+fn test_old_autonomy_level_names_still_load() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings_path = temp_dir.path().join("settings.toml");
+    std::fs::write(
+        &settings_path,
+        r#"
+autonomy_level = "fully_autonomous"
+
+[agent_autonomy_levels]
+coder = "plan_approval_required"
+planner = "conservative"
+"#,
+    )
+    .unwrap();
+
+    let settings = SettingsManager::from_path(settings_path)
+        .unwrap()
+        .settings();
+    assert_eq!(settings.autonomy_level, AutonomyLevel::Autonomous);
+    assert_eq!(
+        settings.autonomy_level_for("coder"),
+        AutonomyLevel::Balanced
+    );
+    assert_eq!(
+        settings.autonomy_level_for("planner"),
+        AutonomyLevel::Conservative
+    );
+    assert_eq!(Settings::default().autonomy_level, AutonomyLevel::Balanced);
+}
+
+#[test]
+fn test_wrong_type_falls_back_to_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings_path = temp_dir.path().join("settings.toml");
+    std::fs::write(
+        &settings_path,
+        r#"
+max_review_rounds = "three"
+
+[voice]
+spoken_commands = "yes"
+cancel_debounce_ms = 500
+"#,
+    )
+    .unwrap();
+
+    let manager = SettingsManager::from_path(settings_path).unwrap();
+
+    let settings = manager.settings();
+    assert_eq!(
+        settings.max_review_rounds,
+        Settings::default().max_review_rounds
+    );
+    assert_eq!(
+        settings.voice.spoken_commands,
+        Settings::default().voice.spoken_commands
+    );
+    assert_eq!(
+        settings.voice.cancel_debounce_ms, 500,
+        "valid keys in the same section should be kept"
+    );
+
+    let warnings = manager.load_warnings().join("\n");
+    assert!(warnings.contains("`max_review_rounds`"), "{warnings}");
+    assert!(warnings.contains("`voice.spoken_commands`"), "{warnings}");
+    assert!(warnings.contains("expected"), "{warnings}");
+}
+
+#[test]
+fn test_invalid_provider_is_an_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings_path = temp_dir.path().join("settings.toml");
+    std::fs::write(
+        &settings_path,
+        "[providers.broken]\ntype = \"bedrock\"\nregion = \"us-west-2\"\n",
+    )
+    .unwrap();
+
+    let err = SettingsManager::from_path(settings_path)
+        .err()
+        .expect("invalid provider config should not be replaced by defaults");
+    let message = format!("{err:#}");
+    assert!(
+        message.contains("`providers`") && message.contains("profile"),
+        "unexpected error: {message}"
+    );
+}
+
+#[test]
+fn test_thinking_indicator_messages_follow_interval() {
+    let indicator = ThinkingIndicatorSettings {
+        show_elapsed: true,
+        interval_secs: 10,
+        messages: vec!["first".to_string(), "second".to_string()],
+    };
+    assert_eq!(indicator.message_for(Duration::from_secs(9)), None);
+    assert_eq!(
+        indicator.message_for(Duration::from_secs(10)),
+        Some("first")
+    );
+    assert_eq!(
+        indicator.message_for(Duration::from_secs(25)),
+        Some("second")
+    );
+    assert_eq!(
+        indicator.message_for(Duration::from_secs(300)),
+        Some("second"),
+        "the last message should repeat"
+    );
+
+    let disabled = ThinkingIndicatorSettings {
+        interval_secs: 0,
+        ..indicator
+    };
+    assert_eq!(disabled.message_for(Duration::from_secs(300)), None);
+}
+
+#[test]
+fn test_temperature_schedule_decays_then_holds() {
+    let schedule = TemperatureSchedule {
+        start: 0.8,
+        end: 0.2,
+        turns: 3,
+    };
+    assert!((schedule.temperature_at(0) - 0.8).abs() < 1e-6);
+    assert!((schedule.temperature_at(1) - 0.6).abs() < 1e-6);
+    assert!((schedule.temperature_at(3) - 0.2).abs() < 1e-6);
+    assert!((schedule.temperature_at(50) - 0.2).abs() < 1e-6);
+
+    let immediate = TemperatureSchedule {
+        turns: 0,
+        ..schedule
+    };
+    assert!((immediate.temperature_at(0) - 0.2).abs() < 1e-6);
+}
+
+#[test]
+fn test_model_aliases_resolve_before_builtin_names() {
+    let settings: Settings = toml::from_str(
+        r#"
+        [model_aliases]
+        fast = "claude-haiku"
+        opus = "claude-sonnet"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(settings.resolve_model("fast"), Some(Model::ClaudeHaiku));
+    assert_eq!(settings.resolve_model("opus"), Some(Model::ClaudeSonnet));
+    assert_eq!(settings.resolve_model("gpt"), Some(Model::Gpt));
+    assert_eq!(settings.resolve_model("unknown-alias"), None);
+
+    let partly_invalid = toml::from_str::<Settings>(
+        r#"
+        [model_aliases]
+        fast = "not-a-model"
+        smart = "claude-sonnet"
+        "#,
+    )
+    .expect("a bad alias must not fail the settings load");
+    assert_eq!(partly_invalid.resolve_model("fast"), None);
+    assert_eq!(
+        partly_invalid.resolve_model("smart"),
+        Some(Model::ClaudeSonnet)
+    );
+}
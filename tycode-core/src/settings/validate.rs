@@ -0,0 +1,179 @@
+//! Read-only consistency checks for `/settings validate`.
+//!
+//! Parsing already rejects unknown model names and malformed values; these
+//! checks catch settings that parse fine but cannot work together, such as an
+//! active provider that was never added.
+
+use std::path::Path;
+
+use crate::ai::model::Model;
+use crate::ai::provider::AiProvider;
+use crate::settings::config::{McpServerConfig, Settings};
+use crate::steering;
+
+/// Returns every problem found, so they can all be reported at once.
+pub fn validate(
+    settings: &Settings,
+    provider: &dyn AiProvider,
+    known_agents: &[String],
+) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    match &settings.active_provider {
+        Some(name) if !settings.providers.contains_key(name) => issues.push(format!(
+            "active_provider '{name}' is not defined in [providers]"
+        )),
+        None if !settings.providers.is_empty() => {
+            issues.push("Providers are configured but active_provider is not set".to_string())
+        }
+        _ => {}
+    }
+
+    if !known_agents.contains(&settings.default_agent) {
+        issues.push(format!(
+            "default_agent '{}' is not a known agent",
+            settings.default_agent
+        ));
+    }
+
+    let supported = provider.supported_models();
+    let mut configured: Vec<(String, Model)> = Vec::new();
+    let mut agents: Vec<_> = settings.agent_models.iter().collect();
+    agents.sort_by(|a, b| a.0.cmp(b.0));
+    for (agent, model_settings) in agents {
+        if !known_agents.contains(agent) {
+            issues.push(format!(
+                "agent_models.{agent}: '{agent}' is not a known agent"
+            ));
+        }
+        configured.push((format!("agent_models.{agent}"), model_settings.model));
+    }
+    for model in &settings.swarm_models {
+        configured.push(("swarm_models".to_string(), *model));
+    }
+
+    for (key, model) in configured {
+        if !supported.contains(&model) {
+            issues.push(format!(
+                "{key}: model '{}' is not supported by the {} provider",
+                model.name(),
+                provider.name()
+            ));
+            continue;
+        }
+        let cost = provider.get_cost(&model);
+        if cost.input_cost_per_million_tokens == 0.0 && cost.output_cost_per_million_tokens == 0.0 {
+            issues.push(format!(
+                "{key}: no pricing for model '{}' on the {} provider; session costs will read as zero",
+                model.name(),
+                provider.name()
+            ));
+        }
+    }
+
+    for name in steering::unknown_builtins(&settings.disabled_steering_builtins) {
+        issues.push(format!(
+            "disabled_steering_builtins: '{name}' is not a built-in steering document"
+        ));
+    }
+
+    let mut servers: Vec<_> = settings.mcp_servers.iter().collect();
+    servers.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, server) in servers {
+        if let McpServerConfig::Stdio { command, .. } = server {
+            if !command_resolves(command) {
+                issues.push(format!(
+                    "mcp_servers.{name}: command '{command}' was not found on PATH"
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+fn command_resolves(command: &str) -> bool {
+    let path = Path::new(command);
+    if path.components().count() > 1 {
+        return path.is_file();
+    }
+    let Some(search_path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&search_path).any(|dir| {
+        dir.join(command).is_file()
+            || dir
+                .join(format!("{command}{}", std::env::consts::EXE_SUFFIX))
+                .is_file()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::mock::{MockBehavior, MockProvider};
+    use crate::settings::config::ProviderConfig;
+    use std::collections::HashMap;
+
+    fn agents() -> Vec<String> {
+        vec!["tycode".to_string(), "coder".to_string()]
+    }
+
+    #[test]
+    fn default_settings_are_valid() {
+        let provider = MockProvider::new(MockBehavior::Success);
+        assert!(validate(&Settings::default(), &provider, &agents()).is_empty());
+    }
+
+    #[test]
+    fn reports_all_issues_at_once() {
+        let provider = MockProvider::new(MockBehavior::Success);
+        let mut settings = Settings {
+            active_provider: Some("missing".to_string()),
+            ..Default::default()
+        };
+        settings.providers.insert(
+            "present".to_string(),
+            ProviderConfig::Mock {
+                behavior: MockBehavior::Success,
+            },
+        );
+        settings
+            .agent_models
+            .insert("ghost".to_string(), Model::ClaudeSonnet.default_settings());
+        settings.mcp_servers.insert(
+            "tools".to_string(),
+            McpServerConfig::Stdio {
+                command: "definitely-not-a-real-command-xyz".to_string(),
+                args: vec![],
+                env: HashMap::new(),
+                startup_timeout_secs: None,
+            },
+        );
+
+        let issues = validate(&settings, &provider, &agents());
+
+        assert_eq!(issues.len(), 4, "{issues:#?}");
+        assert!(issues[0].contains("'missing'"));
+        assert!(issues[1].contains("'ghost' is not a known agent"));
+        assert!(issues[2].contains("not supported by the mock provider"));
+        assert!(issues[3].contains("mcp_servers.tools"));
+    }
+
+    #[test]
+    fn unknown_disabled_steering_builtins_are_reported() {
+        let provider = MockProvider::new(MockBehavior::Success);
+        let settings = Settings {
+            disabled_steering_builtins: vec![
+                "style_mandates".to_string(),
+                "task_lists".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let issues = validate(&settings, &provider, &agents());
+
+        assert_eq!(issues.len(), 1, "{issues:#?}");
+        assert!(issues[0].contains("'task_lists'"));
+    }
+}
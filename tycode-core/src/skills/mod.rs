@@ -21,6 +21,7 @@ pub mod prompt;
 pub mod tool;
 pub mod types;
 
+use std::collections::BTreeSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -133,6 +134,26 @@ impl Module for SkillsModule {
             Arc::new(SkillInvokeCommand::new(self.manager.clone())),
         ]
     }
+
+    fn reload(&self) -> Option<String> {
+        let names = |skills: Vec<SkillMetadata>| -> BTreeSet<String> {
+            skills.into_iter().map(|skill| skill.name).collect()
+        };
+        let before = names(self.get_all_skills());
+        self.manager.reload();
+        let after = names(self.get_all_skills());
+
+        let mut summary = format!("Skills: {} loaded", after.len());
+        let added: Vec<&str> = after.difference(&before).map(String::as_str).collect();
+        let removed: Vec<&str> = before.difference(&after).map(String::as_str).collect();
+        if !added.is_empty() {
+            summary.push_str(&format!(", added {}", added.join(", ")));
+        }
+        if !removed.is_empty() {
+            summary.push_str(&format!(", removed {}", removed.join(", ")));
+        }
+        Some(summary)
+    }
 }
 
 /// Session state component for persisting invoked skills.
@@ -174,6 +195,10 @@ impl SessionStateComponent for SkillsSessionState {
 
         Ok(())
     }
+
+    fn reset(&self) {
+        self.state.clear();
+    }
 }
 
 #[cfg(test)]
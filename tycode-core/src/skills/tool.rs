@@ -3,7 +3,10 @@ use std::sync::Arc;
 use anyhow::{bail, Result};
 use serde_json::{json, Value};
 
-use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::chat::events::{
+    ToolErrorCategory, ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType,
+};
+use crate::file::resolver;
 use crate::tools::r#trait::{
     ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
 };
@@ -68,7 +71,7 @@ impl ToolCallHandle for InvokeSkillHandle {
                         "The following reference files are available. Use the read_file tool to access them:\n",
                     );
                     for file in &skill.reference_files {
-                        response.push_str(&format!("- {}\n", file.display()));
+                        response.push_str(&format!("- {}\n", resolver::display_path(file)));
                     }
                 }
 
@@ -78,7 +81,7 @@ impl ToolCallHandle for InvokeSkillHandle {
                     response
                         .push_str("The following scripts are available for use with this skill:\n");
                     for script in &skill.scripts {
-                        response.push_str(&format!("- {}\n", script.display()));
+                        response.push_str(&format!("- {}\n", resolver::display_path(script)));
                     }
                 }
 
@@ -101,6 +104,7 @@ impl ToolCallHandle for InvokeSkillHandle {
                 ui_result: ToolExecutionResult::Error {
                     short_message: format!("Skill '{}' not found", self.skill_name),
                     detailed_message: e.to_string(),
+                    category: Some(ToolErrorCategory::NotFound),
                 },
             },
         }
@@ -1,7 +1,15 @@
 //! Complete task tool - signals task completion and pops the agent stack.
 
-use crate::chat::events::{ToolRequest as ToolRequestEvent, ToolRequestType};
-use crate::tools::r#trait::{ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest};
+use std::sync::Arc;
+
+use crate::chat::events::{
+    ToolErrorCategory, ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType,
+};
+use crate::module::Module;
+use crate::settings::config::CompletionGuard;
+use crate::tools::r#trait::{
+    ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
+};
 use crate::tools::ToolName;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -11,27 +19,89 @@ use serde_json::{json, Value};
 struct CompleteTaskParams {
     result: String,
     success: bool,
+    #[serde(default)]
+    allow_incomplete: bool,
 }
 
-pub struct CompleteTask;
+pub struct CompleteTask {
+    modules: Vec<Arc<dyn Module>>,
+    guard: CompletionGuard,
+}
 
 impl CompleteTask {
+    pub fn new(modules: Vec<Arc<dyn Module>>, guard: CompletionGuard) -> Self {
+        Self { modules, guard }
+    }
+
     pub fn tool_name() -> ToolName {
         ToolName::new("complete_task")
     }
 
     /// Creates a standalone CompleteTask for use outside SpawnModule context
     pub fn standalone() -> Self {
-        Self
+        Self::new(Vec::new(), CompletionGuard::Off)
     }
 }
 
 struct CompleteTaskHandle {
     success: bool,
     result: String,
+    allow_incomplete: bool,
+    modules: Vec<Arc<dyn Module>>,
+    guard: CompletionGuard,
     tool_use_id: String,
 }
 
+impl CompleteTaskHandle {
+    /// Checked at execution rather than validation so a task list update in
+    /// the same batch is taken into account.
+    fn declined(&self) -> Option<ToolOutput> {
+        let overridden = self.guard == CompletionGuard::Warn && self.allow_incomplete;
+        if !self.success || self.guard == CompletionGuard::Off || overridden {
+            return None;
+        }
+        let unfinished: Vec<String> = self
+            .modules
+            .iter()
+            .flat_map(|m| m.unfinished_work())
+            .collect();
+        if unfinished.is_empty() {
+            return None;
+        }
+
+        let mut message = format!(
+            "Task not completed: {} task(s) in your task list are unfinished:\n- {}\n\n\
+             Finish them, or use manage_task_list to mark them completed or failed.",
+            unfinished.len(),
+            unfinished.join("\n- ")
+        );
+        if self.guard == CompletionGuard::Warn {
+            message.push_str(
+                " If stopping early is intentional, call complete_task again with allow_incomplete: true.",
+            );
+        }
+
+        let is_error = self.guard == CompletionGuard::Block;
+        let ui_result = if is_error {
+            ToolExecutionResult::Error {
+                short_message: "Unfinished tasks remain".to_string(),
+                detailed_message: message.clone(),
+                category: Some(ToolErrorCategory::Conflict),
+            }
+        } else {
+            ToolExecutionResult::Other {
+                result: json!({ "declined": true, "unfinished": unfinished }),
+            }
+        };
+        Some(ToolOutput::Result {
+            content: message,
+            is_error,
+            continuation: ContinuationPreference::Continue,
+            ui_result,
+        })
+    }
+}
+
 #[async_trait::async_trait(?Send)]
 impl ToolCallHandle for CompleteTaskHandle {
     fn tool_request(&self) -> ToolRequestEvent {
@@ -48,6 +118,9 @@ impl ToolCallHandle for CompleteTaskHandle {
     }
 
     async fn execute(self: Box<Self>) -> ToolOutput {
+        if let Some(declined) = self.declined() {
+            return declined;
+        }
         ToolOutput::PopAgent {
             success: self.success,
             result: self.result,
@@ -89,6 +162,10 @@ impl ToolExecutor for CompleteTask {
                 "success": {
                     "type": "boolean",
                     "description": "Whether the task completed successfully"
+                },
+                "allow_incomplete": {
+                    "type": "boolean",
+                    "description": "Complete successfully even though tasks in your task list are unfinished. Only set this when stopping early is intentional."
                 }
             }
         })
@@ -104,6 +181,9 @@ impl ToolExecutor for CompleteTask {
         Ok(Box::new(CompleteTaskHandle {
             success: params.success,
             result: params.result,
+            allow_incomplete: params.allow_incomplete,
+            modules: self.modules.clone(),
+            guard: self.guard,
             tool_use_id: request.tool_use_id.clone(),
         }))
     }
@@ -9,7 +9,7 @@ use std::sync::{Arc, RwLock};
 use crate::agents::agent::ActiveAgent;
 use crate::agents::catalog::AgentCatalog;
 use crate::module::{Module, SpawnParameter};
-use crate::settings::config::OrchestrationMode;
+use crate::settings::config::{CompletionGuard, OrchestrationMode, SecurityMode};
 use crate::tools::ask_user_question::AskUserQuestion;
 use crate::tools::r#trait::SharedTool;
 use crate::Agent;
@@ -167,6 +167,8 @@ pub async fn build_tools_for_stack(
     modules: &[Arc<dyn Module>],
     agent_stack: &AgentStack,
     orchestration_mode: OrchestrationMode,
+    completion_guard: CompletionGuard,
+    security_mode: SecurityMode,
 ) -> Vec<SharedTool> {
     let current_agent_name = agent_stack.current_agent_name().unwrap_or_default();
     build_tools(
@@ -174,6 +176,8 @@ pub async fn build_tools_for_stack(
         agent_stack.catalog().clone(),
         &current_agent_name,
         orchestration_mode,
+        completion_guard,
+        security_mode,
     )
     .await
 }
@@ -183,6 +187,8 @@ pub async fn build_tools(
     catalog: Arc<AgentCatalog>,
     current_agent_name: &str,
     orchestration_mode: OrchestrationMode,
+    completion_guard: CompletionGuard,
+    security_mode: SecurityMode,
 ) -> Vec<SharedTool> {
     let mut tools: Vec<SharedTool> = Vec::new();
     for m in modules {
@@ -193,7 +199,10 @@ pub async fn build_tools(
     let allowed_spawn_agents =
         allowed_agents_for(current_agent_name, &all_names, orchestration_mode);
 
-    tools.push(Arc::new(CompleteTask));
+    tools.push(Arc::new(CompleteTask::new(
+        modules.to_vec(),
+        completion_guard,
+    )));
     tools.push(Arc::new(AskUserQuestion));
 
     if !allowed_spawn_agents.is_empty() {
@@ -208,6 +217,11 @@ pub async fn build_tools(
         )));
     }
 
+    tools.retain(|tool| {
+        tool.required_capabilities()
+            .into_iter()
+            .all(|capability| security_mode.grants(capability))
+    });
     tools
 }
 
@@ -2,7 +2,9 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::agents::catalog::AgentCatalog;
-use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
+use crate::chat::events::{
+    ToolErrorCategory, ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType,
+};
 use crate::module::SpawnParameter;
 use crate::tools::r#trait::{
     ContinuationPreference, ToolCallHandle, ToolCategory, ToolExecutor, ToolOutput, ToolRequest,
@@ -85,6 +87,7 @@ impl ToolCallHandle for SpawnAgentHandle {
                         "Agent '{}' cannot spawn another '{}'. Use complete_task with failure instead.",
                         self.agent_type, self.agent_type
                     ),
+                    category: Some(ToolErrorCategory::InvalidArgument),
                 },
             };
         }
@@ -103,6 +106,7 @@ impl ToolCallHandle for SpawnAgentHandle {
                         "Cannot spawn '{}'. Allowed agent types: {:?}",
                         self.agent_type, self.allowed_agents
                     ),
+                    category: Some(ToolErrorCategory::PermissionDenied),
                 },
             };
         }
@@ -124,6 +128,7 @@ impl ToolCallHandle for SpawnAgentHandle {
                         self.agent_type,
                         self.catalog.get_agent_names()
                     ),
+                    category: Some(ToolErrorCategory::NotFound),
                 },
             },
         }
@@ -1,26 +1,32 @@
 use crate::agents::defaults::get_autonomy_instructions;
-use crate::module::{PromptComponent, PromptComponentId};
+use crate::module::{PromptComponent, PromptComponentId, PromptComponentSelection};
 use crate::settings::config::{AutonomyLevel, Settings};
 
 pub const ID: PromptComponentId = PromptComponentId("autonomy");
 
-/// Provides autonomy-level instructions for the system prompt.
-pub struct AutonomyComponent {
-    level: AutonomyLevel,
-}
-
-impl AutonomyComponent {
-    pub fn new(level: AutonomyLevel) -> Self {
-        Self { level }
+/// Selection for agents whose own prompt defines how they proceed (e.g.
+/// coder, planner). They skip the autonomy instructions unless the agent
+/// runs at the conservative level, which must always reach the model.
+pub fn bespoke_workflow_selection(level: AutonomyLevel) -> PromptComponentSelection {
+    match level {
+        AutonomyLevel::Conservative => PromptComponentSelection::All,
+        AutonomyLevel::Balanced | AutonomyLevel::Autonomous => {
+            PromptComponentSelection::Exclude(&[ID])
+        }
     }
 }
 
+/// Provides autonomy-level instructions for the system prompt. The prompt is
+/// built from settings already scoped to the requesting agent, so
+/// `autonomy_level` reflects any `agent_autonomy_levels` override.
+pub struct AutonomyComponent;
+
 impl PromptComponent for AutonomyComponent {
     fn id(&self) -> PromptComponentId {
         ID
     }
 
-    fn build_prompt_section(&self, _settings: &Settings) -> Option<String> {
-        Some(get_autonomy_instructions(self.level).to_string())
+    fn build_prompt_section(&self, settings: &Settings) -> Option<String> {
+        Some(get_autonomy_instructions(settings.autonomy_level).to_string())
     }
 }
@@ -0,0 +1,108 @@
+//! Optional YAML frontmatter on custom steering documents.
+//!
+//! ```markdown
+//! ---
+//! title: Database conventions
+//! priority: 10
+//! enabled: true
+//! ---
+//! Always use migrations...
+//! ```
+//!
+//! Documents with a higher `priority` are included first (default 0);
+//! `enabled: false` leaves the file on disk but out of the prompt. The
+//! frontmatter itself is never injected. Documents without frontmatter are
+//! included unchanged.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct DocumentMeta {
+    pub title: Option<String>,
+    pub priority: i32,
+    pub enabled: bool,
+}
+
+impl Default for DocumentMeta {
+    fn default() -> Self {
+        Self {
+            title: None,
+            priority: 0,
+            enabled: true,
+        }
+    }
+}
+
+/// Splits `content` into its metadata and the body to inject. Malformed
+/// frontmatter is logged and the whole file is used as the body.
+pub fn parse(path: &Path, content: &str) -> (DocumentMeta, String) {
+    let Some((yaml, body)) = split(content) else {
+        return (DocumentMeta::default(), content.to_string());
+    };
+
+    let meta = match serde_yaml::from_str::<Option<DocumentMeta>>(yaml) {
+        Ok(meta) => meta.unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!(
+                "Ignoring invalid frontmatter in steering document {}: {e}",
+                path.display()
+            );
+            return (DocumentMeta::default(), content.to_string());
+        }
+    };
+
+    let body = match &meta.title {
+        Some(title) => format!("# {title}\n\n{body}"),
+        None => body.to_string(),
+    };
+    (meta, body)
+}
+
+fn split(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let end = rest.find("\n---")?;
+    let yaml = &rest[..end];
+    let after = &rest[end + 4..];
+    // The closing delimiter must be alone on its line
+    let body = match after.find('\n') {
+        Some(newline) if after[..newline].trim().is_empty() => &after[newline + 1..],
+        None if after.trim().is_empty() => "",
+        _ => return None,
+    };
+    Some((yaml, body.trim_start_matches(['\r', '\n'])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_frontmatter_and_reads_fields() {
+        let content = "---\ntitle: Rules\npriority: 5\nenabled: false\n---\nBody text\n";
+        let (meta, body) = parse(Path::new("rules.md"), content);
+        assert_eq!(meta.priority, 5);
+        assert!(!meta.enabled);
+        assert_eq!(body, "# Rules\n\nBody text\n");
+    }
+
+    #[test]
+    fn documents_without_frontmatter_are_unchanged() {
+        let content = "# Heading\n\n---\nnot frontmatter\n";
+        let (meta, body) = parse(Path::new("plain.md"), content);
+        assert_eq!(meta, DocumentMeta::default());
+        assert_eq!(body, content);
+    }
+
+    #[test]
+    fn invalid_frontmatter_keeps_whole_document() {
+        let content = "---\npriority: high\n---\nBody\n";
+        let (meta, body) = parse(Path::new("bad.md"), content);
+        assert_eq!(meta, DocumentMeta::default());
+        assert_eq!(body, content);
+    }
+}
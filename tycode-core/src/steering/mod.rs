@@ -1,5 +1,6 @@
 pub mod autonomy;
 pub mod communication;
+pub mod frontmatter;
 pub mod style;
 pub mod tools;
 
@@ -7,14 +8,14 @@ use std::collections::HashSet;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use crate::agents::defaults;
 use crate::module::ContextComponent;
 use crate::module::Module;
 use crate::module::PromptComponent;
-use crate::settings::config::CommunicationTone;
-use crate::settings::SettingsManager;
+use crate::settings::config::{CommunicationTone, Settings};
 use crate::tools::r#trait::SharedTool;
 
 #[derive(Copy, Clone, Debug)]
@@ -33,23 +34,17 @@ pub enum Builtin {
 /// - Autonomy level (how autonomous the agent should be)
 pub struct SteeringModule {
     documents: Arc<SteeringDocuments>,
-    settings: SettingsManager,
 }
 
 impl SteeringModule {
-    pub fn new(documents: Arc<SteeringDocuments>, settings: SettingsManager) -> Self {
-        Self {
-            documents,
-            settings,
-        }
+    pub fn new(documents: Arc<SteeringDocuments>) -> Self {
+        Self { documents }
     }
 }
 
 #[async_trait::async_trait(?Send)]
 impl Module for SteeringModule {
     fn prompt_components(&self) -> Vec<Arc<dyn PromptComponent>> {
-        let autonomy_level = self.settings.settings().autonomy_level;
-
         vec![
             Arc::new(style::StyleMandatesComponent::new(self.documents.clone())),
             Arc::new(tools::ToolInstructionsComponent::new(
@@ -58,7 +53,7 @@ impl Module for SteeringModule {
             Arc::new(communication::CommunicationComponent::new(
                 self.documents.clone(),
             )),
-            Arc::new(autonomy::AutonomyComponent::new(autonomy_level)),
+            Arc::new(autonomy::AutonomyComponent),
         ]
     }
 
@@ -69,6 +64,11 @@ impl Module for SteeringModule {
     async fn tools(&self) -> Vec<SharedTool> {
         vec![]
     }
+
+    fn reload(&self) -> Option<String> {
+        self.documents.reload();
+        None
+    }
 }
 
 impl Builtin {
@@ -80,13 +80,41 @@ impl Builtin {
         ]
     }
 
-    fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             Builtin::UnderstandingTools => "understanding_tools",
             Builtin::StyleMandates => "style_mandates",
             Builtin::CommunicationGuidelines => "communication_guidelines",
         }
     }
+
+    /// Whether `disabled_steering_builtins` removes this document.
+    pub fn is_disabled(&self, settings: &Settings) -> bool {
+        settings
+            .disabled_steering_builtins
+            .iter()
+            .any(|name| name == self.as_str())
+    }
+}
+
+/// Entries of `names` that are not the name of any builtin document.
+pub fn unknown_builtins(names: &[String]) -> Vec<&str> {
+    names
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !Builtin::all().iter().any(|b| b.as_str() == *name))
+        .collect()
+}
+
+/// Modification time and length of every steering source (directories and
+/// the `.md` files inside them), used to detect when cached documents are
+/// stale without re-reading them.
+type Fingerprint = Vec<(PathBuf, Option<(SystemTime, u64)>)>;
+
+struct CachedDocuments {
+    fingerprint: Fingerprint,
+    custom: Vec<String>,
+    external: Vec<String>,
 }
 
 #[derive(Clone)]
@@ -94,6 +122,7 @@ pub struct SteeringDocuments {
     workspace_roots: Vec<PathBuf>,
     home_dir: PathBuf,
     communication_tone: CommunicationTone,
+    cache: Arc<Mutex<Option<CachedDocuments>>>,
 }
 
 impl SteeringDocuments {
@@ -106,9 +135,16 @@ impl SteeringDocuments {
             workspace_roots,
             home_dir,
             communication_tone,
+            cache: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Drops the cached custom/external documents so the next prompt re-reads
+    /// them from disk.
+    pub fn reload(&self) {
+        *self.cache.lock().unwrap() = None;
+    }
+
     pub fn get_builtin(&self, builtin: Builtin) -> String {
         let name = builtin.as_str();
         if let Some(content) = self.load_from_workspace(name) {
@@ -122,6 +158,17 @@ impl SteeringDocuments {
         self.get_default(name)
     }
 
+    /// The builtin's content, or `None` when the settings disable it.
+    pub fn builtin_section(&self, builtin: Builtin, settings: &Settings) -> Option<String> {
+        if builtin.is_disabled(settings) {
+            return None;
+        }
+        Some(self.get_builtin(builtin))
+    }
+
+    /// Custom `.tycode/*.md` documents from the workspaces and home, ordered by
+    /// frontmatter `priority` (highest first) and with disabled documents
+    /// removed. Ties keep workspace documents ahead of home ones.
     pub fn get_custom_documents(&self) -> Vec<String> {
         let mut documents = Vec::new();
         let mut seen_paths = HashSet::new();
@@ -134,7 +181,12 @@ impl SteeringDocuments {
         let home_tycode = self.home_dir.join(".tycode");
         self.collect_custom_from_dir(&home_tycode, &mut documents, &mut seen_paths);
 
+        documents.sort_by_key(|(meta, _)| std::cmp::Reverse(meta.priority));
         documents
+            .into_iter()
+            .filter(|(meta, _)| meta.enabled)
+            .map(|(_, body)| body)
+            .collect()
     }
 
     pub fn get_external_documents(&self) -> Vec<String> {
@@ -150,20 +202,15 @@ impl SteeringDocuments {
         documents
     }
 
-    pub fn build_steering_content(&self) -> String {
-        let mut sections = Vec::new();
-
-        for builtin in Builtin::all() {
-            sections.push(self.get_builtin(*builtin));
-        }
-
-        for doc in self.get_custom_documents() {
-            sections.push(doc);
-        }
+    pub fn build_steering_content(&self, settings: &Settings) -> String {
+        let mut sections: Vec<String> = Builtin::all()
+            .iter()
+            .filter_map(|builtin| self.builtin_section(*builtin, settings))
+            .collect();
 
-        for doc in self.get_external_documents() {
-            sections.push(doc);
-        }
+        let (custom, external) = self.cached_documents();
+        sections.extend(custom);
+        sections.extend(external);
 
         sections.join("\n\n")
     }
@@ -172,18 +219,83 @@ impl SteeringDocuments {
         let mut prompt = core_prompt.to_string();
 
         if include_custom {
-            for doc in self.get_custom_documents() {
+            let (custom, external) = self.cached_documents();
+            for doc in custom.iter().chain(&external) {
                 prompt.push_str("\n\n");
-                prompt.push_str(&doc);
+                prompt.push_str(doc);
             }
+        }
 
-            for doc in self.get_external_documents() {
-                prompt.push_str("\n\n");
-                prompt.push_str(&doc);
+        prompt
+    }
+
+    /// Number of custom and external documents currently loaded.
+    pub fn document_counts(&self) -> (usize, usize) {
+        let (custom, external) = self.cached_documents();
+        (custom.len(), external.len())
+    }
+
+    /// Custom and external documents, re-read only when a source changed.
+    fn cached_documents(&self) -> (Vec<String>, Vec<String>) {
+        let fingerprint = self.fingerprint();
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(cached) = cache.as_ref() {
+            if cached.fingerprint == fingerprint {
+                return (cached.custom.clone(), cached.external.clone());
             }
         }
 
-        prompt
+        let custom = self.get_custom_documents();
+        let external = self.get_external_documents();
+        *cache = Some(CachedDocuments {
+            fingerprint,
+            custom: custom.clone(),
+            external: external.clone(),
+        });
+        (custom, external)
+    }
+
+    fn fingerprint(&self) -> Fingerprint {
+        let mut sources = Vec::new();
+        for workspace in &self.workspace_roots {
+            sources.extend([
+                workspace.join(".tycode"),
+                workspace.join(".cursor").join("rules"),
+                workspace.join(".cursorrules"),
+                workspace.join(".cline"),
+                workspace.join(".clinerules"),
+                workspace.join(".roo").join("rules"),
+                workspace.join(".roorules"),
+                workspace.join(".kiro").join("steering-docs"),
+            ]);
+        }
+        sources.push(self.home_dir.join(".tycode"));
+
+        let stamp = |path: &Path| {
+            fs::metadata(path)
+                .ok()
+                .and_then(|m| Some((m.modified().ok()?, m.len())))
+        };
+
+        let mut fingerprint = Vec::new();
+        for source in sources {
+            fingerprint.push((source.clone(), stamp(&source)));
+            // Editing a file does not change its directory's mtime
+            let Ok(entries) = fs::read_dir(&source) else {
+                continue;
+            };
+            let mut docs: Vec<PathBuf> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+                .collect();
+            docs.sort();
+            fingerprint.extend(docs.into_iter().map(|doc| {
+                let doc_stamp = stamp(&doc);
+                (doc, doc_stamp)
+            }));
+        }
+        fingerprint
     }
 
     fn load_from_workspace(&self, name: &str) -> Option<String> {
@@ -232,7 +344,7 @@ impl SteeringDocuments {
     fn collect_custom_from_dir(
         &self,
         dir: &Path,
-        documents: &mut Vec<String>,
+        documents: &mut Vec<(frontmatter::DocumentMeta, String)>,
         seen_paths: &mut HashSet<PathBuf>,
     ) {
         let entries = match fs::read_dir(dir) {
@@ -244,6 +356,7 @@ impl SteeringDocuments {
             }
         };
 
+        let mut paths = Vec::new();
         for entry in entries {
             let entry = match entry {
                 Ok(e) => e,
@@ -256,8 +369,12 @@ impl SteeringDocuments {
                     continue;
                 }
             };
-            let path = entry.path();
+            paths.push(entry.path());
+        }
+        // Directory order is unspecified; sort so equal priorities are stable
+        paths.sort();
 
+        for path in paths {
             if !path.extension().map_or(false, |ext| ext == "md") {
                 continue;
             }
@@ -277,8 +394,8 @@ impl SteeringDocuments {
 
             if let Some(content) = self.read_file(&path) {
                 tracing::debug!("Loaded custom steering document: {}", path.display());
+                documents.push(frontmatter::parse(&path, &content));
                 seen_paths.insert(path);
-                documents.push(content);
             }
         }
     }
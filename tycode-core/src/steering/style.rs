@@ -22,7 +22,8 @@ impl PromptComponent for StyleMandatesComponent {
         ID
     }
 
-    fn build_prompt_section(&self, _settings: &Settings) -> Option<String> {
-        Some(self.steering.get_builtin(Builtin::StyleMandates))
+    fn build_prompt_section(&self, settings: &Settings) -> Option<String> {
+        self.steering
+            .builtin_section(Builtin::StyleMandates, settings)
     }
 }
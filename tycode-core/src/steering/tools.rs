@@ -22,7 +22,8 @@ impl PromptComponent for ToolInstructionsComponent {
         ID
     }
 
-    fn build_prompt_section(&self, _settings: &Settings) -> Option<String> {
-        Some(self.steering.get_builtin(Builtin::UnderstandingTools))
+    fn build_prompt_section(&self, settings: &Settings) -> Option<String> {
+        self.steering
+            .builtin_section(Builtin::UnderstandingTools, settings)
     }
 }
@@ -45,6 +45,7 @@ impl ToolRegistry {
         &self,
         tool_use: &ToolUseData,
         allowed_tools: &[ToolName],
+        agent: &str,
     ) -> Result<Box<dyn ToolCallHandle>, String> {
         let mut allowed_names: Vec<&str> = allowed_tools
             .iter()
@@ -93,7 +94,7 @@ impl ToolRegistry {
                 }
             };
 
-        let request = ToolRequest::new(coerced_arguments, tool_use.id.clone());
+        let request = ToolRequest::new(coerced_arguments, tool_use.id.clone()).with_agent(agent);
         tool.process(&request).await.map_err(|e| {
             error!(?e, tool_name = %tool_use.name, "Tool processing failed");
             format!("Error: {e:?}")
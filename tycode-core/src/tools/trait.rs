@@ -6,16 +6,39 @@ use anyhow::Result;
 use serde_json::Value;
 
 use crate::agents::agent::Agent;
-use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent};
+use crate::audit::AuditCall;
+use crate::chat::events::{ToolExecutionResult, ToolRequest as ToolRequestEvent, ToolRequestType};
 
 /// Tool category that determines the type of operation
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ToolCategory {
     TaskList,
     Execution,
+    /// Read-only execution with no side effects
+    Read,
     Meta,
 }
 
+impl ToolCategory {
+    /// Whether calls in this category may run concurrently with each other
+    /// within one assistant turn.
+    pub fn parallel_safe(&self) -> bool {
+        matches!(self, ToolCategory::Read)
+    }
+}
+
+/// Something a tool does beyond reading the workspace. A tool is only offered
+/// when the security mode grants every capability it requires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToolCapability {
+    /// Creates, changes or deletes files
+    Write,
+    /// Runs commands or processes
+    Execute,
+    /// Reaches services outside the workspace
+    Network,
+}
+
 /// Request passed to tool execution
 #[derive(Debug, Clone)]
 pub struct ToolRequest {
@@ -23,6 +46,9 @@ pub struct ToolRequest {
     pub arguments: Value,
     /// The unique ID for this tool use
     pub tool_use_id: String,
+    /// Name of the agent that made the call; empty when called outside an
+    /// agent turn (e.g. `/replay`)
+    pub agent: String,
 }
 
 impl ToolRequest {
@@ -31,8 +57,14 @@ impl ToolRequest {
         Self {
             arguments,
             tool_use_id,
+            agent: String::new(),
         }
     }
+
+    pub fn with_agent(mut self, agent: impl Into<String>) -> Self {
+        self.agent = agent.into();
+        self
+    }
 }
 
 /// Preference for whether the conversation should continue after tool execution
@@ -40,6 +72,8 @@ impl ToolRequest {
 pub enum ContinuationPreference {
     Continue,
     Stop,
+    /// Stop until the user has accepted or rejected every proposed edit
+    AwaitEditReview,
 }
 
 /// Output from tool execution - either a direct result or an action for the orchestrator
@@ -68,12 +102,33 @@ pub enum ToolOutput {
     PopAgent { success: bool, result: String },
     /// Stop and prompt user (ask_user_question)
     PromptUser { question: String },
+    /// Hold a validated edit for the user to accept or reject (propose_edits)
+    ProposeEdit {
+        handle: Box<dyn ToolCallHandle>,
+        file_path: String,
+    },
 }
 
 /// Handle for a validated tool call, encapsulating request generation and execution
 #[async_trait::async_trait(?Send)]
 pub trait ToolCallHandle: Send {
     fn tool_request(&self) -> ToolRequestEvent;
+
+    /// The file this call edits, used by the edit wrappers (confirmation,
+    /// undo history, lint on save). Defaults to the path of a `ModifyFile`
+    /// request; tools that rewrite a file some other way override it.
+    fn edited_path(&self) -> Option<PathBuf> {
+        match self.tool_request().tool_type {
+            ToolRequestType::ModifyFile { file_path, .. } => Some(PathBuf::from(file_path)),
+            _ => None,
+        }
+    }
+
+    /// Gives the call its audit context when auditing is enabled. Handles
+    /// that hold the call for the user's approval keep it to record the
+    /// resolution (see `AuditCall::hold`); others ignore it.
+    fn attach_audit(&mut self, _call: AuditCall) {}
+
     async fn execute(self: Box<Self>) -> ToolOutput;
 }
 
@@ -101,6 +156,18 @@ pub trait ToolExecutor {
     fn description(&self) -> String;
     fn input_schema(&self) -> Value;
     fn category(&self) -> ToolCategory;
+
+    /// Capabilities the security mode must grant for this tool to be offered.
+    fn required_capabilities(&self) -> Vec<ToolCapability> {
+        Vec::new()
+    }
+
+    /// A short worked example of a correct call, shown in the system prompt
+    /// for tools that models tend to misuse.
+    fn usage_example(&self) -> Option<String> {
+        None
+    }
+
     async fn process(&self, request: &ToolRequest) -> Result<Box<dyn ToolCallHandle>>;
 }
 
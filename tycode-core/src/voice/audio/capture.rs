@@ -4,7 +4,7 @@
 use anyhow::{Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{
-    Device, FromSample, Sample, SampleFormat, SizedSample, Stream, StreamConfig,
+    Device, FromSample, Sample, SampleFormat, SampleRate, SizedSample, Stream, StreamConfig,
     SupportedStreamConfig,
 };
 use rubato::{FftFixedIn, Resampler};
@@ -47,18 +47,73 @@ impl Drop for AudioStream {
     }
 }
 
+/// Names of the available input devices
+pub fn list_input_devices() -> Result<Vec<String>> {
+    let host = cpal::default_host();
+    let devices = host
+        .input_devices()
+        .context("failed to enumerate input devices")?;
+    Ok(devices.filter_map(|device| device.name().ok()).collect())
+}
+
+/// Find an input device by name, falling back to the default device with a
+/// warning if it is not connected
+fn select_input_device(device_name: Option<&str>) -> Result<Device> {
+    let host = cpal::default_host();
+
+    if let Some(name) = device_name {
+        let found = host
+            .input_devices()
+            .context("failed to enumerate input devices")?
+            .find(|device| device.name().is_ok_and(|n| n == name));
+        match found {
+            Some(device) => return Ok(device),
+            None => tracing::warn!(
+                device_name = name,
+                "configured input device not found, using default"
+            ),
+        }
+    }
+
+    host.default_input_device()
+        .context("no input device available")
+}
+
+/// Prefer a native format matching the target profile so no resampling is
+/// needed; otherwise use the device default
+fn negotiate_config(device: &Device, profile: AudioProfile) -> Result<SupportedStreamConfig> {
+    let matching = device
+        .supported_input_configs()
+        .ok()
+        .and_then(|mut ranges| {
+            ranges.find(|range| {
+                range.channels() == profile.channels
+                    && matches!(range.sample_format(), SampleFormat::I16 | SampleFormat::F32)
+                    && range.min_sample_rate().0 <= profile.sample_rate
+                    && profile.sample_rate <= range.max_sample_rate().0
+            })
+        });
+    if let Some(range) = matching {
+        return Ok(range.with_sample_rate(SampleRate(profile.sample_rate)));
+    }
+
+    device
+        .default_input_config()
+        .context("failed to get default input config")
+}
+
 impl AudioCapture {
     /// Create a new audio capture using the default input device
     /// Output will be resampled to match the provided AudioProfile
     pub fn new(profile: AudioProfile) -> Result<Self> {
-        let host = cpal::default_host();
-        let device = host
-            .default_input_device()
-            .context("no input device available")?;
-
-        let supported_config = device
-            .default_input_config()
-            .context("failed to get default input config")?;
+        Self::with_device(profile, None)
+    }
+
+    /// Create a new audio capture using the named input device, or the
+    /// default device if `device_name` is None or not connected
+    pub fn with_device(profile: AudioProfile, device_name: Option<&str>) -> Result<Self> {
+        let device = select_input_device(device_name)?;
+        let supported_config = negotiate_config(&device, profile)?;
 
         tracing::debug!(
             device_name = ?device.name(),
@@ -0,0 +1,181 @@
+//! Always-on listening for a spoken cancel keyword (e.g. "stop").
+//!
+//! `CancelListener` keeps its own capture and transcription session running,
+//! independent of push-to-talk, so the keyword is heard while the model is
+//! responding. Detection is deliberately conservative: the keyword must
+//! appear as whole words in a final transcript or in consecutive partial
+//! transcripts, and after firing it is suppressed for a debounce window.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tokio::task::JoinHandle;
+
+use super::audio::capture::AudioCapture;
+use super::push_to_talk::AudioSource;
+use super::stt::provider::SpeechToText;
+use super::stt::types::TranscriptionChunk;
+
+/// Partial transcripts in a row that must contain the keyword before it
+/// fires, since providers often revise early partials.
+const PARTIAL_CONFIRMATIONS: usize = 2;
+
+pub struct KeywordDetector {
+    keyword: Vec<String>,
+    debounce: Duration,
+    partial_hits: usize,
+    last_fired: Option<Instant>,
+}
+
+impl KeywordDetector {
+    pub fn new(keyword: &str, debounce: Duration) -> Self {
+        Self {
+            keyword: words(keyword),
+            debounce,
+            partial_hits: 0,
+            last_fired: None,
+        }
+    }
+
+    /// Returns true if this chunk should trigger a cancel.
+    pub fn observe(&mut self, chunk: &TranscriptionChunk, now: Instant) -> bool {
+        if self.keyword.is_empty() {
+            return false;
+        }
+
+        let heard = words(&chunk.text)
+            .windows(self.keyword.len())
+            .any(|window| window == self.keyword.as_slice());
+        let confirmed = if chunk.is_partial {
+            self.partial_hits = if heard { self.partial_hits + 1 } else { 0 };
+            self.partial_hits >= PARTIAL_CONFIRMATIONS
+        } else {
+            self.partial_hits = 0;
+            heard
+        };
+        if !confirmed {
+            return false;
+        }
+
+        if self
+            .last_fired
+            .is_some_and(|last| now.duration_since(last) < self.debounce)
+        {
+            return false;
+        }
+        self.last_fired = Some(now);
+        self.partial_hits = 0;
+        true
+    }
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Background listener; stops capturing when dropped.
+pub struct CancelListener {
+    task: JoinHandle<()>,
+}
+
+impl Drop for CancelListener {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl CancelListener {
+    /// Starts listening on the given input device. `on_cancel` is called each
+    /// time the keyword is detected, e.g. to call `ChatActor::cancel`. Must be
+    /// called inside a `LocalSet`.
+    pub async fn start(
+        stt: Arc<dyn SpeechToText>,
+        input_device: Option<&str>,
+        detector: KeywordDetector,
+        on_cancel: impl Fn() + 'static,
+    ) -> Result<Self> {
+        let profile = stt.required_audio_profile();
+        let audio = AudioCapture::with_device(profile, input_device)?.start()?;
+        Self::start_with_source(stt, audio, detector, on_cancel).await
+    }
+
+    pub async fn start_with_source(
+        stt: Arc<dyn SpeechToText>,
+        mut audio: impl AudioSource + 'static,
+        mut detector: KeywordDetector,
+        on_cancel: impl Fn() + 'static,
+    ) -> Result<Self> {
+        let (sink, mut transcriptions) = stt.start().await?;
+
+        let task = tokio::task::spawn_local(async move {
+            loop {
+                tokio::select! {
+                    chunk = audio.recv() => {
+                        let Some(chunk) = chunk else { break };
+                        if sink.send(chunk).await.is_err() {
+                            break;
+                        }
+                    }
+                    result = transcriptions.recv() => {
+                        match result {
+                            Some(Ok(chunk)) => {
+                                if detector.observe(&chunk, Instant::now()) {
+                                    tracing::info!(text = %chunk.text, "voice cancel keyword detected");
+                                    on_cancel();
+                                }
+                            }
+                            Some(Err(e)) => {
+                                tracing::warn!(error = %e, "cancel keyword listener stopped");
+                                break;
+                            }
+                            None => break,
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(Self { task })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(text: &str, is_partial: bool) -> TranscriptionChunk {
+        TranscriptionChunk {
+            text: text.to_string(),
+            speaker: None,
+            is_partial,
+            timestamp_ms: 0,
+        }
+    }
+
+    #[test]
+    fn final_transcript_with_keyword_fires_once_per_debounce() {
+        let mut detector = KeywordDetector::new("stop", Duration::from_secs(2));
+        let start = Instant::now();
+
+        assert!(detector.observe(&chunk("Stop!", false), start));
+        assert!(!detector.observe(&chunk("stop", false), start + Duration::from_secs(1)));
+        assert!(detector.observe(&chunk("please stop", false), start + Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn partials_need_confirmation_and_whole_words() {
+        let mut detector = KeywordDetector::new("stop now", Duration::from_secs(2));
+        let now = Instant::now();
+
+        assert!(!detector.observe(&chunk("unstoppable now", false), now));
+        assert!(!detector.observe(&chunk("stop now", true), now));
+        assert!(detector.observe(&chunk("stop now please", true), now));
+    }
+}
@@ -0,0 +1,126 @@
+//! Voice input for a running `ChatActor`, configured from `VoiceSettings`.
+//!
+//! `VoiceInput` drives push-to-talk against the configured speech-to-text
+//! provider and input device: partial transcripts are forwarded as live
+//! captions, and the final transcript is sent as a `VoiceTranscript` when the
+//! talk key is released. When `cancel_keyword` is set, a `CancelListener`
+//! runs for as long as the `VoiceInput` is alive and cancels the actor's
+//! current turn whenever the keyword is heard.
+//!
+//! Like `PushToTalk`, this must be used inside a `LocalSet`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use super::cancel_keyword::{CancelListener, KeywordDetector};
+use super::push_to_talk::{PushToTalk, PushToTalkState};
+use super::stt::aws_transcribe::{AwsTranscribe, AwsTranscribeConfig};
+use super::stt::elevenlabs_transcribe::{ElevenLabsTranscribe, ElevenLabsTranscribeConfig};
+use super::stt::provider::SpeechToText;
+use crate::chat::actor::ChatActor;
+use crate::settings::config::{SttProviderConfig, VoiceSettings};
+
+/// Builds the speech-to-text provider described by `config`.
+pub async fn speech_to_text(config: &SttProviderConfig) -> Result<Arc<dyn SpeechToText>> {
+    match config {
+        SttProviderConfig::AwsTranscribe { profile, region } => {
+            let provider = AwsTranscribe::new(AwsTranscribeConfig {
+                profile: profile.clone(),
+                region: region.clone(),
+                ..AwsTranscribeConfig::default()
+            })
+            .await?;
+            Ok(Arc::new(provider))
+        }
+        SttProviderConfig::ElevenLabs { api_key, model_id } => {
+            let mut config = ElevenLabsTranscribeConfig::new(api_key.clone());
+            config.model_id = model_id.clone();
+            Ok(Arc::new(ElevenLabsTranscribe::new(config)))
+        }
+        SttProviderConfig::Unknown => anyhow::bail!("Unsupported speech-to-text provider"),
+    }
+}
+
+pub struct VoiceInput {
+    actor: ChatActor,
+    push_to_talk: PushToTalk,
+    _cancel_listener: Option<CancelListener>,
+}
+
+impl VoiceInput {
+    /// Sets up voice input for `actor` using the default STT provider from
+    /// `settings`, and starts the cancel keyword listener if one is set.
+    pub async fn start(actor: ChatActor, settings: &VoiceSettings) -> Result<Self> {
+        let config = settings
+            .active_stt()
+            .context("No speech-to-text provider configured (voice.default_stt)")?;
+        let stt = speech_to_text(config).await?;
+        Self::with_stt(actor, settings, stt).await
+    }
+
+    pub async fn with_stt(
+        actor: ChatActor,
+        settings: &VoiceSettings,
+        stt: Arc<dyn SpeechToText>,
+    ) -> Result<Self> {
+        let captions = actor.clone();
+        let push_to_talk = PushToTalk::new(stt.clone())
+            .with_input_device(settings.input_device.clone())
+            .with_partial_handler(move |text| {
+                // Fails only once the actor has stopped
+                let _ = captions.send_voice_partial(text.to_string());
+            });
+
+        let cancel_listener = match settings
+            .cancel_keyword
+            .as_deref()
+            .filter(|keyword| !keyword.trim().is_empty())
+        {
+            Some(keyword) => {
+                let detector = KeywordDetector::new(
+                    keyword,
+                    Duration::from_millis(settings.cancel_debounce_ms),
+                );
+                let canceller = actor.clone();
+                let listener = CancelListener::start(
+                    stt,
+                    settings.input_device.as_deref(),
+                    detector,
+                    move || {
+                        let _ = canceller.cancel();
+                    },
+                )
+                .await?;
+                Some(listener)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            actor,
+            push_to_talk,
+            _cancel_listener: cancel_listener,
+        })
+    }
+
+    pub fn state(&self) -> PushToTalkState {
+        self.push_to_talk.state()
+    }
+
+    /// Talk key pressed: starts listening.
+    pub async fn press(&mut self) -> Result<()> {
+        self.push_to_talk.press().await
+    }
+
+    /// Talk key released: waits for the final transcript and sends it to the
+    /// actor. Returns whether anything was sent.
+    pub async fn release(&mut self) -> Result<bool> {
+        let Some(transcript) = self.push_to_talk.release().await? else {
+            return Ok(false);
+        };
+        self.actor.send_voice_transcript(transcript)?;
+        Ok(true)
+    }
+}
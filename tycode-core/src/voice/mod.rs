@@ -1,5 +1,8 @@
 //! Voice functionality (speech-to-text, text-to-speech)
 
 pub mod audio;
+pub mod cancel_keyword;
+pub mod input;
+pub mod push_to_talk;
 pub mod stt;
 pub mod tts;
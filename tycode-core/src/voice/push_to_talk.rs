@@ -0,0 +1,274 @@
+//! Push-to-talk: capture audio only while the talk key is held.
+//!
+//! `press` starts the microphone and a transcription session; `release`
+//! stops the microphone, closes the audio sink so the provider can finish,
+//! and waits for the remaining final transcripts. The returned text is meant
+//! to be sent as a user message. Partial transcripts go only to the optional
+//! partial handler, for live captions.
+//!
+//! Tasks are spawned with `spawn_local` because cpal streams are not `Send`,
+//! so this must run inside a `LocalSet`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+use super::audio::capture::{AudioCapture, AudioStream};
+use super::stt::provider::{AudioSink, SpeechToText, TranscriptionStream};
+
+/// How long `release` waits for the provider to flush final transcripts.
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushToTalkState {
+    Idle,
+    /// Key held; audio is streaming to the provider.
+    Listening,
+    /// Key released; waiting for the last transcripts.
+    Finalizing,
+}
+
+/// Source of captured PCM audio chunks.
+#[async_trait::async_trait(?Send)]
+pub trait AudioSource {
+    async fn recv(&mut self) -> Option<Vec<u8>>;
+}
+
+#[async_trait::async_trait(?Send)]
+impl AudioSource for AudioStream {
+    async fn recv(&mut self) -> Option<Vec<u8>> {
+        AudioStream::recv(self).await
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl AudioSource for mpsc::Receiver<Vec<u8>> {
+    async fn recv(&mut self) -> Option<Vec<u8>> {
+        mpsc::Receiver::recv(self).await
+    }
+}
+
+struct Session {
+    stop: oneshot::Sender<()>,
+    pump: JoinHandle<()>,
+    transcript: JoinHandle<Result<String>>,
+}
+
+pub type PartialHandler = Arc<dyn Fn(&str)>;
+
+pub struct PushToTalk {
+    stt: Arc<dyn SpeechToText>,
+    input_device: Option<String>,
+    on_partial: Option<PartialHandler>,
+    state: PushToTalkState,
+    session: Option<Session>,
+}
+
+impl PushToTalk {
+    pub fn new(stt: Arc<dyn SpeechToText>) -> Self {
+        Self {
+            stt,
+            input_device: None,
+            on_partial: None,
+            state: PushToTalkState::Idle,
+            session: None,
+        }
+    }
+
+    /// Capture from the named input device instead of the system default.
+    pub fn with_input_device(mut self, input_device: Option<String>) -> Self {
+        self.input_device = input_device;
+        self
+    }
+
+    /// Called with each interim transcript, e.g. to forward it with
+    /// `ChatActor::send_voice_partial`.
+    pub fn with_partial_handler(mut self, on_partial: impl Fn(&str) + 'static) -> Self {
+        self.on_partial = Some(Arc::new(on_partial));
+        self
+    }
+
+    pub fn state(&self) -> PushToTalkState {
+        self.state
+    }
+
+    /// Starts capturing from the configured input device. Does nothing if
+    /// already listening.
+    pub async fn press(&mut self) -> Result<()> {
+        if self.state != PushToTalkState::Idle {
+            return Ok(());
+        }
+        let profile = self.stt.required_audio_profile();
+        let audio = AudioCapture::with_device(profile, self.input_device.as_deref())?.start()?;
+        self.press_with_source(audio).await
+    }
+
+    /// Starts a session fed from `audio` instead of the microphone.
+    pub async fn press_with_source(&mut self, audio: impl AudioSource + 'static) -> Result<()> {
+        if self.state != PushToTalkState::Idle {
+            return Ok(());
+        }
+        let (sink, transcriptions) = self.stt.start().await?;
+        let (stop, stopped) = oneshot::channel();
+
+        self.session = Some(Session {
+            stop,
+            pump: tokio::task::spawn_local(pump_audio(audio, sink, stopped)),
+            transcript: tokio::task::spawn_local(collect_finals(
+                transcriptions,
+                self.on_partial.clone(),
+            )),
+        });
+        self.state = PushToTalkState::Listening;
+        Ok(())
+    }
+
+    /// Stops capturing and returns the final transcript, or None if nothing
+    /// was said. Waits for transcription to complete even if the key was
+    /// released mid-utterance.
+    pub async fn release(&mut self) -> Result<Option<String>> {
+        let Some(session) = self.session.take() else {
+            return Ok(None);
+        };
+        self.state = PushToTalkState::Finalizing;
+
+        let _ = session.stop.send(());
+        let result = finalize(session).await;
+        self.state = PushToTalkState::Idle;
+
+        let text = result?;
+        Ok((!text.is_empty()).then_some(text))
+    }
+}
+
+async fn finalize(session: Session) -> Result<String> {
+    session.pump.await.context("audio pump failed")?;
+    tokio::time::timeout(FINALIZE_TIMEOUT, session.transcript)
+        .await
+        .context("timed out waiting for final transcript")?
+        .context("transcript collector failed")?
+}
+
+/// Forwards audio to the provider until stopped. Dropping the source stops
+/// the microphone; dropping the sink tells the provider the utterance ended.
+async fn pump_audio(
+    mut audio: impl AudioSource,
+    sink: AudioSink,
+    mut stopped: oneshot::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            _ = &mut stopped => break,
+            chunk = audio.recv() => {
+                let Some(chunk) = chunk else { break };
+                if sink.send(chunk).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn collect_finals(
+    mut transcriptions: TranscriptionStream,
+    on_partial: Option<PartialHandler>,
+) -> Result<String> {
+    let mut parts: Vec<String> = Vec::new();
+    while let Some(chunk) = transcriptions.recv().await {
+        let chunk = chunk?;
+        if !chunk.is_partial {
+            parts.push(chunk.text.trim().to_string());
+        } else if let Some(on_partial) = &on_partial {
+            // Partials cover only the current utterance; prefix earlier finals
+            // so the caption shows everything said since the key was pressed
+            let mut caption = parts.join(" ");
+            if !caption.is_empty() {
+                caption.push(' ');
+            }
+            caption.push_str(chunk.text.trim());
+            on_partial(&caption);
+        }
+    }
+    Ok(parts.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voice::audio::AudioProfile;
+    use crate::voice::stt::types::TranscriptionChunk;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Emits one partial per audio chunk and a final transcript once the
+    /// audio sink closes, like a streaming provider would.
+    struct FakeStt;
+
+    #[async_trait::async_trait]
+    impl SpeechToText for FakeStt {
+        fn required_audio_profile(&self) -> AudioProfile {
+            AudioProfile {
+                sample_rate: 16000,
+                channels: 1,
+            }
+        }
+
+        async fn start(&self) -> Result<(AudioSink, TranscriptionStream)> {
+            let (audio_tx, mut audio_rx) = mpsc::channel::<Vec<u8>>(10);
+            let (result_tx, result_rx) = mpsc::channel(10);
+            tokio::spawn(async move {
+                while audio_rx.recv().await.is_some() {
+                    let _ = result_tx.send(Ok(chunk_of("hel", true))).await;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                let _ = result_tx.send(Ok(chunk_of("hello there", false))).await;
+            });
+            Ok((
+                AudioSink::new(audio_tx),
+                TranscriptionStream::new(result_rx),
+            ))
+        }
+    }
+
+    fn chunk_of(text: &str, is_partial: bool) -> TranscriptionChunk {
+        TranscriptionChunk {
+            text: text.to_string(),
+            speaker: None,
+            is_partial,
+            timestamp_ms: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn release_waits_for_final_transcript() {
+        let local = tokio::task::LocalSet::new();
+        local
+            .run_until(async {
+                let captions = Rc::new(RefCell::new(Vec::new()));
+                let seen = captions.clone();
+                let mut ptt = PushToTalk::new(Arc::new(FakeStt))
+                    .with_partial_handler(move |text| seen.borrow_mut().push(text.to_string()));
+                let (audio_tx, audio_rx) = mpsc::channel(10);
+                ptt.press_with_source(audio_rx).await.unwrap();
+                assert_eq!(ptt.state(), PushToTalkState::Listening);
+
+                audio_tx.send(vec![0; 4]).await.unwrap();
+                tokio::task::yield_now().await;
+
+                let transcript = ptt.release().await.unwrap();
+                assert_eq!(transcript.as_deref(), Some("hello there"));
+                assert_eq!(*captions.borrow(), vec!["hel".to_string()]);
+                assert_eq!(ptt.state(), PushToTalkState::Idle);
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn release_without_press_is_noop() {
+        let mut ptt = PushToTalk::new(Arc::new(FakeStt));
+        assert_eq!(ptt.release().await.unwrap(), None);
+    }
+}
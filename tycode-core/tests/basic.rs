@@ -1,5 +1,7 @@
 use tycode_core::ai::mock::MockBehavior;
-use tycode_core::chat::events::{ChatEvent, MessageSender};
+use tycode_core::chat::events::{ChatEvent, MessageSender, ToolErrorCategory, ToolExecutionResult};
+use tycode_core::modules::image::config::Image;
+use tycode_core::settings::config::{SecurityMode, TemperatureSchedule};
 
 mod fixture;
 
@@ -168,3 +170,260 @@ fn test_actor_builds_and_serves_settings_without_workspace_roots() {
         );
     }));
 }
+
+#[test]
+fn invalid_tool_call_reports_error_category() {
+    fixture::run(|mut fixture| async move {
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "nonexistent_tool".to_string(),
+            tool_arguments: r#"{"foo": "bar"}"#.to_string(),
+        });
+
+        let events = fixture.step("Use a tool").await;
+
+        let category = events.iter().find_map(|e| match e {
+            ChatEvent::ToolExecutionCompleted {
+                tool_result: ToolExecutionResult::Error { category, .. },
+                ..
+            } => Some(*category),
+            _ => None,
+        });
+        assert_eq!(
+            category,
+            Some(Some(ToolErrorCategory::NotFound)),
+            "events={events:?}"
+        );
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        let error_result = request
+            .messages
+            .iter()
+            .flat_map(|m| m.content.tool_results())
+            .find(|r| r.is_error)
+            .expect("tool error result sent to the model");
+        assert!(
+            error_result.content.contains("[error category: not_found]"),
+            "{}",
+            error_result.content
+        );
+    });
+}
+
+#[test]
+fn temperature_schedule_sets_request_temperature_per_turn() {
+    fixture::run(|mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                settings.agent_temperature_schedules.insert(
+                    "one_shot".to_string(),
+                    TemperatureSchedule {
+                        start: 1.5,
+                        end: 0.2,
+                        turns: 2,
+                    },
+                );
+            })
+            .await;
+
+        fixture.step("First").await;
+        let first = fixture.get_last_ai_request().expect("AI request");
+        assert_eq!(
+            first.model.temperature,
+            Some(1.0),
+            "the start temperature is clamped to the provider's range"
+        );
+
+        fixture.step("Second").await;
+        fixture.step("Third").await;
+        let third = fixture.get_last_ai_request().expect("AI request");
+        assert_eq!(third.model.temperature, Some(0.2));
+    });
+}
+
+#[test]
+fn auto_security_mode_hides_network_tools() {
+    fixture::run(|mut fixture| async move {
+        fixture.set_image_gen_enabled(true);
+        fixture
+            .update_settings(|settings| {
+                let mut config: Image = settings.get_module_config("image");
+                config.enabled = true;
+                settings.set_module_config("image", config);
+            })
+            .await;
+        fixture.step("Hello").await;
+        let request = fixture.get_last_ai_request().expect("AI request");
+        let tools: Vec<&str> = request.tools.iter().map(|t| t.name.as_str()).collect();
+        assert!(tools.contains(&"generate_image"), "{tools:?}");
+
+        fixture
+            .update_settings(|settings| settings.security.mode = SecurityMode::Auto)
+            .await;
+        fixture.step("Hello again").await;
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        let tools: Vec<&str> = request.tools.iter().map(|t| t.name.as_str()).collect();
+        assert!(
+            !tools.contains(&"generate_image"),
+            "network tools must be hidden in auto mode: {tools:?}"
+        );
+        assert!(tools.contains(&"write_file"), "{tools:?}");
+        assert!(tools.contains(&"bash"), "{tools:?}");
+    });
+}
+
+#[test]
+fn read_only_security_mode_hides_write_and_execute_tools() {
+    fixture::run(|mut fixture| async move {
+        fixture.step("Hello").await;
+        let request = fixture.get_last_ai_request().expect("AI request");
+        let tools: Vec<&str> = request.tools.iter().map(|t| t.name.as_str()).collect();
+        assert!(tools.contains(&"write_file"), "{tools:?}");
+        assert!(tools.contains(&"bash"), "{tools:?}");
+
+        fixture
+            .update_settings(|settings| settings.security.mode = SecurityMode::ReadOnly)
+            .await;
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: r#"{"file_path": "/tmp/should_not_exist.txt", "content": "x"}"#
+                .to_string(),
+        });
+        fixture.step("Write a file").await;
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        let tools: Vec<&str> = request.tools.iter().map(|t| t.name.as_str()).collect();
+        for hidden in [
+            "write_file",
+            "modify_file",
+            "delete_file",
+            "bash",
+            "generate_image",
+        ] {
+            assert!(
+                !tools.contains(&hidden),
+                "{hidden} offered in read-only mode"
+            );
+        }
+        assert!(tools.contains(&"read_files"), "{tools:?}");
+
+        let rejected = request
+            .messages
+            .iter()
+            .flat_map(|m| m.content.tool_results())
+            .any(|r| r.is_error && r.content.contains("Unknown tool: write_file"));
+        assert!(
+            rejected,
+            "write_file calls should be rejected in read-only mode"
+        );
+    });
+}
+
+/// Reads the same file `times` times in a row, then answers with text.
+fn repeated_reads(fixture: &fixture::Fixture, times: usize) -> MockBehavior {
+    let path = fixture.workspace_path().join("loop.txt");
+    std::fs::write(&path, "same\n").unwrap();
+    let read = MockBehavior::ToolUse {
+        tool_name: "read_files".to_string(),
+        tool_arguments: serde_json::json!({ "paths": [path.display().to_string()] }).to_string(),
+    };
+    let mut behaviors = vec![read; times];
+    behaviors.push(MockBehavior::Success);
+    MockBehavior::BehaviorQueue { behaviors }
+}
+
+#[test]
+fn repeated_tool_calls_are_nudged() {
+    fixture::run(|mut fixture| async move {
+        let behavior = repeated_reads(&fixture, 3);
+        fixture.set_mock_behavior(behavior);
+        fixture.step("Read loop.txt").await;
+
+        let requests = fixture.get_all_ai_requests();
+        assert_eq!(requests.len(), 4, "the turn continues after the nudge");
+        let nudged = requests[3].messages.iter().any(|m| {
+            m.content
+                .text()
+                .contains("read_files with the same arguments 3 times")
+        });
+        assert!(nudged, "the tool results should carry the nudge");
+    });
+}
+
+#[test]
+fn repeated_tool_calls_pause_when_configured() {
+    fixture::run(|mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                settings.loop_detection.action = tycode_core::settings::config::LoopAction::Pause
+            })
+            .await;
+        let behavior = repeated_reads(&fixture, 3);
+        fixture.set_mock_behavior(behavior);
+        let events = fixture.step("Read loop.txt").await;
+
+        assert_eq!(
+            fixture.get_all_ai_requests().len(),
+            3,
+            "the turn stops once the loop is detected"
+        );
+        assert!(events.iter().any(|e| matches!(
+            e,
+            ChatEvent::MessageAdded(message)
+                if message.sender == MessageSender::Warning && message.content.starts_with("Paused:")
+        )));
+    });
+}
+
+#[test]
+fn transient_errors_report_exhausted_retries() {
+    fixture::run(|mut fixture| async move {
+        fixture.set_mock_behavior(MockBehavior::AlwaysTransientError);
+        let events = fixture.step("Hello").await;
+
+        let exhausted = events.iter().find_map(|event| match event {
+            ChatEvent::RetriesExhausted {
+                attempts,
+                max_retries,
+                ..
+            } => Some((*attempts, *max_retries)),
+            _ => None,
+        });
+        assert_eq!(exhausted, Some((10, 10)));
+    });
+}
+
+#[test]
+fn retry_budgets_are_tracked_per_error_kind() {
+    fixture::run(|mut fixture| async move {
+        // Two throttling errors, then more transient errors than the shared
+        // count would allow but fewer than the transient budget
+        let mut behaviors = vec![MockBehavior::AlwaysRetryableError; 2];
+        behaviors.extend(vec![MockBehavior::AlwaysTransientError; 9]);
+        fixture.set_mock_behavior(MockBehavior::BehaviorQueue { behaviors });
+        let events = fixture.step("Hello").await;
+
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, ChatEvent::RetriesExhausted { .. })),
+            "Retries should not be exhausted"
+        );
+        let transient_retries: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                ChatEvent::RetryAttempt {
+                    attempt,
+                    max_retries: 10,
+                    ..
+                } => Some(*attempt),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(transient_retries, (1..=9).collect::<Vec<_>>());
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ChatEvent::StreamEnd { message } if matches!(message.sender, MessageSender::Assistant { .. })
+        )));
+    });
+}
@@ -163,3 +163,102 @@ fn test_debug_ui_not_in_help() {
         );
     });
 }
+
+#[test]
+fn test_spoken_transcript_matches_commands() {
+    use tycode_core::chat::commands::{get_available_commands, match_spoken_command};
+
+    let commands = get_available_commands(&[]);
+    assert_eq!(
+        match_spoken_command("Clear.", &commands).as_deref(),
+        Some("/clear")
+    );
+    assert_eq!(
+        match_spoken_command("slash sessions list", &commands).as_deref(),
+        Some("/sessions list")
+    );
+    assert_eq!(
+        match_spoken_command("Command review level none", &commands).as_deref(),
+        Some("/review_level none")
+    );
+    assert_eq!(
+        match_spoken_command("Review level", &commands).as_deref(),
+        Some("/review_level")
+    );
+    assert_eq!(
+        match_spoken_command("Review level none", &commands),
+        None,
+        "Arguments need a trigger word"
+    );
+    assert_eq!(
+        match_spoken_command("Clear up this function", &commands),
+        None,
+        "Speech that merely starts with a command name goes to the model"
+    );
+    assert_eq!(
+        match_spoken_command("Can you clear up this function?", &commands),
+        None
+    );
+    assert_eq!(
+        match_spoken_command("debug ui", &commands),
+        None,
+        "Hidden commands should not be triggered by speech"
+    );
+}
+
+#[test]
+fn test_export_context_writes_full_context_to_file() {
+    fixture::run(|mut fixture| async move {
+        let events = fixture.step("/export context debug/context.txt").await;
+
+        let exported = events.iter().any(|e| {
+            matches!(
+                e,
+                ChatEvent::MessageAdded(msg)
+                    if matches!(msg.sender, MessageSender::System)
+                        && msg.content.starts_with("Exported context")
+            )
+        });
+        assert!(exported, "Should confirm the export. Got: {events:?}");
+
+        let path = fixture.workspace_path().join("debug/context.txt");
+        let contents = std::fs::read_to_string(&path).expect("context file should be written");
+        assert!(contents.starts_with("=== Current Context ===\nEstimated size: ~"));
+        assert!(
+            contents.contains("Await user request"),
+            "Export should include the task list. Got: {contents}"
+        );
+
+        let events = fixture.step("/export context").await;
+        assert!(events.iter().any(|e| {
+            matches!(
+                e,
+                ChatEvent::MessageAdded(msg) if matches!(msg.sender, MessageSender::Error)
+            )
+        }));
+    });
+}
+
+#[test]
+fn test_export_context_overwrites_only_with_force() {
+    fixture::run(|mut fixture| async move {
+        let path = fixture.workspace_path().join("context.txt");
+        std::fs::write(&path, "keep me").unwrap();
+
+        let events = fixture.step("/export context context.txt").await;
+        let refused = events.iter().any(|e| {
+            matches!(
+                e,
+                ChatEvent::MessageAdded(msg)
+                    if matches!(msg.sender, MessageSender::Error)
+                        && msg.content.contains("already exists")
+            )
+        });
+        assert!(refused, "Should refuse to overwrite. Got: {events:?}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+
+        fixture.step("/export context context.txt --force").await;
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("=== Current Context ==="));
+    });
+}
@@ -61,6 +61,68 @@ fn tycode_root_sees_file_tree_on_normal_turn() {
     });
 }
 
+#[test]
+fn context_command_reports_token_estimate() {
+    fixture::run(|mut fixture| async move {
+        std::fs::write(fixture.workspace_path().join("main.rs"), "fn main() {}").unwrap();
+
+        let events = fixture.step("/context").await;
+
+        let response_text: String = events
+            .iter()
+            .filter_map(|e| match e {
+                ChatEvent::MessageAdded(msg) => Some(msg.content.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(
+            response_text.contains("Estimated size: ~") && response_text.contains(" tokens ("),
+            "/context should report an estimated token count. Response: {response_text}"
+        );
+    });
+}
+
+#[test]
+fn context_order_setting_moves_sections() {
+    fixture::run(|mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        std::fs::write(workspace.join("ordered.rs"), "fn main() {}").unwrap();
+
+        fixture.set_mock_behavior(fixture::MockBehavior::ToolUseThenSuccess {
+            tool_name: "manage_task_list".to_string(),
+            tool_arguments: r#"{"title": "Ordering", "tasks": [{"description": "check order", "status": "in_progress"}]}"#
+                .to_string(),
+        });
+        fixture.step("Plan the work").await;
+
+        fixture.set_mock_behavior(fixture::MockBehavior::Success);
+        fixture.step("Hello").await;
+        let text = last_request_text(&fixture);
+        let files = text.rfind("Project Files:").expect("file tree section");
+        let tasks = text
+            .rfind("Task List: Ordering")
+            .expect("task list section");
+        assert!(files < tasks, "default order puts files first:\n{text}");
+
+        fixture
+            .update_settings(|settings| {
+                settings.context_order = vec!["not_a_section".to_string(), "file_tree".to_string()];
+            })
+            .await;
+        fixture.step("Hello again").await;
+        let text = last_request_text(&fixture);
+        let files = text.rfind("Project Files:").expect("file tree section");
+        let tasks = text
+            .rfind("Task List: Ordering")
+            .expect("task list section");
+        assert!(
+            tasks < files,
+            "listed sections should follow unlisted ones:\n{text}"
+        );
+    });
+}
+
 /// Regression test: context should not hang when workspace directory is deleted
 /// after the ChatActor is initialized. This simulates VSCode multi-workspace
 /// scenarios where a folder is removed from disk while still referenced.
@@ -247,6 +247,11 @@ impl Session {
         self.mock_provider.set_image_gen_enabled(enabled);
     }
 
+    #[allow(dead_code)]
+    pub fn set_embeddings_enabled(&self, enabled: bool) {
+        self.mock_provider.set_embeddings_enabled(enabled);
+    }
+
     #[allow(dead_code)]
     pub fn send_message(&mut self, message: impl Into<String>) {
         self.actor.send_message(message.into()).unwrap();
@@ -0,0 +1,84 @@
+//! Paths under the home directory, workspace paths included, must reach the
+//! model relative to `~`, never with the real home directory (and username)
+//! in them.
+//!
+//! Kept in its own test binary because it points `HOME` at a temporary
+//! directory, which would race with other tests in the same process.
+
+use std::sync::Arc;
+
+use tycode_core::ai::mock::{MockBehavior, MockProvider};
+use tycode_core::chat::actor::ChatActorBuilder;
+use tycode_core::chat::events::ChatEvent;
+use tycode_core::file::config::File;
+use tycode_core::settings::manager::SettingsManager;
+
+#[test]
+fn tool_results_hide_the_home_directory() {
+    let home = tempfile::TempDir::new().unwrap();
+    let home_path = home.path().canonicalize().unwrap();
+    std::env::set_var("HOME", &home_path);
+
+    let workspace = home_path.join("project");
+    std::fs::create_dir_all(&workspace).unwrap();
+    std::fs::write(workspace.join("big.txt"), "x".repeat(100)).unwrap();
+
+    // Session data, including the saved copy of a truncated read, lives
+    // under ~/.tycode
+    let tycode_dir = home_path.join(".tycode");
+    std::fs::create_dir_all(&tycode_dir).unwrap();
+    let settings = SettingsManager::from_path(tycode_dir.join("settings.toml")).unwrap();
+    let mut config: File = settings.get_module_config(File::NAMESPACE);
+    config.max_read_file_bytes = 10;
+    settings.set_module_config(File::NAMESPACE, config);
+    settings.save().unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let local = tokio::task::LocalSet::new();
+
+    runtime.block_on(local.run_until(async {
+        // The model only ever sees the workspace as ~/project and must be
+        // able to pass such paths back
+        let provider = MockProvider::new(MockBehavior::ToolUseThenSuccess {
+            tool_name: "read_files".to_string(),
+            tool_arguments: serde_json::json!({ "paths": ["~/project/big.txt"] }).to_string(),
+        });
+        let (actor, mut event_rx) =
+            ChatActorBuilder::tycode(vec![workspace.clone()], Some(tycode_dir.clone()), None)
+                .unwrap()
+                .provider(Arc::new(provider.clone()))
+                .build()
+                .unwrap();
+
+        actor.send_message("Read the big file".to_string()).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(30), async {
+            while let Some(event) = event_rx.recv().await {
+                if matches!(event, ChatEvent::TypingStatusChanged(false)) {
+                    break;
+                }
+            }
+        })
+        .await
+        .expect("the turn should finish");
+
+        let request = provider
+            .get_last_captured_request()
+            .expect("the tool result is sent back to the model");
+        let result = serde_json::to_string(&request.messages).unwrap();
+        assert!(
+            result.contains("~/.tycode/tool-calls/"),
+            "the saved copy should be shown under ~: {result}"
+        );
+        assert!(
+            result.contains("~/project"),
+            "the workspace should be shown under ~: {result}"
+        );
+        assert!(
+            !result.contains(&home_path.display().to_string()),
+            "the real home directory leaked: {result}"
+        );
+    }));
+}
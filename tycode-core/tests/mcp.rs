@@ -770,6 +770,171 @@ fn mcp_image_tool_result_reaches_model() {
     });
 }
 
+#[test]
+fn mcp_call_command_invokes_tool_directly() {
+    fixture::run(|mut fixture| async move {
+        let node_check = std::process::Command::new("node").arg("--version").output();
+        if node_check.is_err() || !node_check.unwrap().status.success() {
+            eprintln!("Skipping mcp_call_command_invokes_tool_directly: node not available");
+            return;
+        }
+
+        let script_path = std::env::temp_dir().join("tycode_mcp_call_server.js");
+        std::fs::write(&script_path, MCP_IMAGE_SERVER_SCRIPT).unwrap();
+        fixture
+            .step(&format!(
+                "/mcp add img_server node --args \"{}\"",
+                script_path.display()
+            ))
+            .await;
+
+        let messages = |events: Vec<ChatEvent>, sender: MessageSender| -> Vec<String> {
+            events
+                .into_iter()
+                .filter_map(|e| match e {
+                    ChatEvent::MessageAdded(msg) if msg.sender == sender => Some(msg.content),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let events = fixture
+            .step(r#"/mcp call img_server generate_image {"size": "small"}"#)
+            .await;
+        let system = messages(events, MessageSender::System);
+        assert!(
+            system
+                .iter()
+                .any(|m| m.contains("returned") && m.contains("image/png")),
+            "Should print the raw tool result. Got: {:?}",
+            system
+        );
+        assert!(
+            fixture.get_last_ai_request().is_none(),
+            "The call should bypass the model"
+        );
+
+        let events = fixture.step("/mcp call img_server missing_tool").await;
+        let errors = messages(events, MessageSender::Error);
+        assert!(
+            errors
+                .iter()
+                .any(|m| m.contains("no tool named 'missing_tool'")),
+            "Should reject unknown tools. Got: {:?}",
+            errors
+        );
+
+        let events = fixture
+            .step("/mcp call img_server generate_image {not json}")
+            .await;
+        let errors = messages(events, MessageSender::Error);
+        assert!(
+            errors.iter().any(|m| m.contains("Invalid JSON arguments")),
+            "Should reject unparseable arguments. Got: {:?}",
+            errors
+        );
+    });
+}
+
+// Minimal MCP stdio server offering one prompt with a required `topic`
+// argument. The prompt text deliberately starts with a slash command.
+const MCP_PROMPT_SERVER_SCRIPT: &str = r#"
+process.stdin.setEncoding('utf8');
+let buf = '';
+
+process.stdin.on('data', chunk => {
+    buf += chunk;
+    const lines = buf.split('\n');
+    buf = lines.pop();
+    for (const line of lines) {
+        if (line.trim()) handle(JSON.parse(line));
+    }
+});
+
+function send(obj) {
+    process.stdout.write(JSON.stringify(obj) + '\n');
+}
+
+function handle(msg) {
+    if (msg.method === 'initialize') {
+        send({ jsonrpc: '2.0', id: msg.id, result: {
+            protocolVersion: '2024-11-05',
+            capabilities: { tools: {}, prompts: {} },
+            serverInfo: { name: 'prompt_server', version: '1.0' }
+        }});
+    } else if (msg.method === 'notifications/initialized') {
+        // no response needed for notifications
+    } else if (msg.method === 'tools/list') {
+        send({ jsonrpc: '2.0', id: msg.id, result: { tools: [] } });
+    } else if (msg.method === 'prompts/list') {
+        send({ jsonrpc: '2.0', id: msg.id, result: {
+            prompts: [{ name: 'explain', arguments: [{ name: 'topic', required: true }] }]
+        }});
+    } else if (msg.method === 'prompts/get') {
+        const topic = msg.params.arguments.topic;
+        send({ jsonrpc: '2.0', id: msg.id, result: {
+            messages: [{ role: 'user', content: { type: 'text', text: '/clear and explain ' + topic } }]
+        }});
+    } else if (msg.id !== undefined) {
+        send({ jsonrpc: '2.0', id: msg.id, error: { code: -32601, message: 'Method not found' } });
+    }
+}
+"#;
+
+#[test]
+fn mcp_prompt_is_sent_to_the_model_verbatim() {
+    fixture::run(|mut fixture| async move {
+        let node_check = std::process::Command::new("node").arg("--version").output();
+        if node_check.is_err() || !node_check.unwrap().status.success() {
+            eprintln!("Skipping mcp_prompt_is_sent_to_the_model_verbatim: node not available");
+            return;
+        }
+
+        let script_path = std::env::temp_dir().join("tycode_mcp_prompt_server.js");
+        std::fs::write(&script_path, MCP_PROMPT_SERVER_SCRIPT).unwrap();
+        fixture
+            .step(&format!(
+                "/mcp add prompt_server node --args \"{}\"",
+                script_path.display()
+            ))
+            .await;
+
+        let events = fixture.step("/mcp-prompt prompt_server explain").await;
+        let errors: Vec<String> = events
+            .into_iter()
+            .filter_map(|e| match e {
+                ChatEvent::MessageAdded(msg) if msg.sender == MessageSender::Error => {
+                    Some(msg.content)
+                }
+                _ => None,
+            })
+            .collect();
+        assert!(
+            errors
+                .iter()
+                .any(|m| m.contains("requires argument(s): topic")),
+            "Should report the missing required argument. Got: {:?}",
+            errors
+        );
+        assert!(
+            fixture.get_last_ai_request().is_none(),
+            "A failed expansion should not reach the model"
+        );
+
+        fixture
+            .step("/mcp-prompt prompt_server explain topic=lifetimes")
+            .await;
+        let request = fixture
+            .get_last_ai_request()
+            .expect("The expanded prompt should be sent to the model");
+        let sent = serde_json::to_string(&request.messages).unwrap();
+        assert!(
+            sent.contains("/clear and explain lifetimes"),
+            "The prompt text should reach the model unchanged, not run as /clear. Got: {sent}"
+        );
+    });
+}
+
 #[test]
 fn end_to_end_mcp_call() {
     fixture::run(|mut fixture| async move {
@@ -1309,7 +1474,7 @@ Authorization = "Bearer token123"
 
     let parsed: Partial = toml::from_str(toml_content).expect("Should parse HTTP config");
     match &parsed.mcp_servers["remote"] {
-        McpServerConfig::Http { url, headers } => {
+        McpServerConfig::Http { url, headers, .. } => {
             assert_eq!(url, "http://localhost:8000/mcp");
             assert_eq!(headers.get("Authorization").unwrap(), "Bearer token123");
         }
@@ -1359,6 +1524,7 @@ fn test_config_http_roundtrip() {
             h.insert("Authorization".to_string(), "Bearer secret".to_string());
             h
         },
+        startup_timeout_secs: None,
     };
 
     let serialized = toml::to_string(&config).expect("Should serialize HTTP config");
@@ -1366,7 +1532,7 @@ fn test_config_http_roundtrip() {
         toml::from_str(&serialized).expect("Should deserialize HTTP config");
 
     match deserialized {
-        McpServerConfig::Http { url, headers } => {
+        McpServerConfig::Http { url, headers, .. } => {
             assert_eq!(url, "http://localhost:8000/mcp");
             assert_eq!(headers.get("Authorization").unwrap(), "Bearer secret");
         }
@@ -1380,6 +1546,7 @@ fn test_config_stdio_roundtrip() {
         command: "uvx".to_string(),
         args: vec!["mcp-server-fetch".to_string()],
         env: std::collections::HashMap::new(),
+        startup_timeout_secs: None,
     };
 
     let serialized = toml::to_string(&config).expect("Should serialize Stdio config");
@@ -1562,6 +1729,7 @@ fn test_extra_mcp_servers_via_builder() {
             McpServerConfig::Http {
                 url: server_url,
                 headers: HashMap::new(),
+                startup_timeout_secs: None,
             },
         );
 
@@ -32,3 +32,9 @@ mod context_management;
 
 #[path = "modules/orchestration.rs"]
 mod orchestration;
+
+#[path = "modules/git.rs"]
+mod git;
+
+#[path = "modules/semantic_search.rs"]
+mod semantic_search;
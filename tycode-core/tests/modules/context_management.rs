@@ -286,6 +286,49 @@ fn test_compact_reasoning_command_no_pruning_needed() {
     });
 }
 
+#[test]
+fn test_compact_reasoning_now_uses_configured_retain_count() {
+    fixture::run(|mut f: fixture::Fixture| async move {
+        f.set_mock_behavior(fixture::MockBehavior::ReasoningContentThenSuccess {
+            remaining_reasonings: 5,
+            reasoning_text: "Step by step reasoning".to_string(),
+        });
+        let mut config = always_compact_config(1);
+        config["auto_compact"] = serde_json::json!(false);
+        f.update_settings(|s| {
+            s.set_module_config("context_management", config);
+        })
+        .await;
+
+        let _ = f.step("First message").await;
+        let _ = f.step("Second message").await;
+        let _ = f.step("Third message").await;
+
+        let events = f.step("/compact reasoning now").await;
+        let message = events
+            .iter()
+            .filter_map(|e| match e {
+                ChatEvent::MessageAdded(msg) => match msg.sender {
+                    tycode_core::chat::events::MessageSender::System => Some(msg),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .last()
+            .expect("Expected system message");
+        assert!(
+            message.content.contains("→ 1."),
+            "Confirmation should report the counts: got '{}'",
+            message.content
+        );
+
+        f.clear_captured_requests();
+        let _ = f.step("Verify pruning worked").await;
+        let request = f.get_last_ai_request().expect("Expected AI request");
+        assert!(count_reasoning(&request) <= 1);
+    });
+}
+
 #[test]
 fn test_compact_reasoning_command_missing_argument() {
     fixture::run(|mut f: fixture::Fixture| async move {
@@ -446,3 +489,44 @@ fn test_compact_reasoning_command_no_reasoning_blocks() {
         );
     });
 }
+
+#[test]
+fn test_context_warning_fires_once_when_over_threshold() {
+    fixture::run(|mut f: fixture::Fixture| async move {
+        f.update_settings(|s| {
+            s.set_module_config(
+                "context_management",
+                serde_json::json!({ "context_warning_fraction": 0.000001 }),
+            );
+        })
+        .await;
+
+        let count_warnings = |events: &[ChatEvent]| {
+            events
+                .iter()
+                .filter(|e| matches!(e, ChatEvent::ContextWarning { .. }))
+                .count()
+        };
+
+        let events = f.step("First message").await;
+        assert_eq!(count_warnings(&events), 1, "first request should warn");
+        let warning = events
+            .iter()
+            .find_map(|e| match e {
+                ChatEvent::ContextWarning {
+                    used_tokens,
+                    limit_tokens,
+                } => Some((*used_tokens, *limit_tokens)),
+                _ => None,
+            })
+            .unwrap();
+        assert!(warning.0 > 0 && warning.1 > 0, "{warning:?}");
+
+        let events = f.step("Second message").await;
+        assert_eq!(
+            count_warnings(&events),
+            0,
+            "warning should not repeat while still over the threshold"
+        );
+    });
+}
@@ -193,3 +193,215 @@ fn test_command_output_remains_in_conversation() {
         );
     });
 }
+
+#[test]
+fn test_timeout_above_configured_cap_is_rejected() {
+    fixture::run(|mut fixture| async move {
+        use tycode_core::ai::mock::MockBehavior;
+
+        let workspace_path = fixture.workspace_path();
+        fixture
+            .update_settings(|settings| {
+                let mut config: ExecutionConfig = settings.get_module_config("execution");
+                config.max_timeout_seconds = 5;
+                settings.set_module_config("execution", config);
+            })
+            .await;
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "bash".to_string(),
+            tool_arguments: bash_args("echo should_not_run", &workspace_path),
+        });
+
+        fixture.step("Run with too long a timeout").await;
+
+        let results = tool_results_from_last_request(&fixture);
+        assert_eq!(results.len(), 1);
+        assert!(
+            results[0].contains("exceeds the configured maximum"),
+            "Captured: {}",
+            results[0]
+        );
+    });
+}
+
+#[test]
+fn test_working_directory_persists_between_commands() {
+    fixture::run(|mut fixture| async move {
+        use tycode_core::ai::mock::MockBehavior;
+
+        let workspace_path = fixture.workspace_path();
+        let subdir = workspace_path.join("nested_dir");
+        std::fs::create_dir(&subdir).unwrap();
+
+        fixture.set_mock_behavior(MockBehavior::MultipleToolUses {
+            tool_uses: vec![
+                ("bash".to_string(), bash_args("true", &subdir)),
+                ("bash".to_string(), json!({ "command": "pwd" }).to_string()),
+            ],
+        });
+
+        fixture.step("Run commands in the same directory").await;
+
+        let results = tool_results_from_last_request(&fixture);
+        assert_eq!(results.len(), 2);
+        assert!(
+            results[1].contains("nested_dir"),
+            "Captured: {}",
+            results[1]
+        );
+    });
+}
+
+#[test]
+fn test_output_filters_drop_matching_lines() {
+    fixture::run(|mut fixture| async move {
+        use tycode_core::ai::mock::MockBehavior;
+
+        let workspace_path = fixture.workspace_path();
+        fixture
+            .update_settings(|settings| {
+                let mut config: ExecutionConfig = settings.get_module_config("execution");
+                config.output_filters = vec![r"^\s*Compiling ".to_string()];
+                settings.set_module_config("execution", config);
+            })
+            .await;
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "bash".to_string(),
+            tool_arguments: bash_args(
+                "printf '   Compiling foo\\n   Compiling bar\\nerror: kept\\n'",
+                &workspace_path,
+            ),
+        });
+
+        fixture.step("Build with noisy output").await;
+
+        let results = tool_results_from_last_request(&fixture);
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert!(!result.contains("Compiling"), "Captured: {result}");
+        assert!(result.contains("error: kept"), "Captured: {result}");
+        assert!(
+            result.contains("2 lines matched output filters"),
+            "Captured: {result}"
+        );
+    });
+}
+
+#[test]
+fn test_output_encoding_decodes_locale_output() {
+    fixture::run(|mut fixture| async move {
+        use tycode_core::ai::mock::MockBehavior;
+
+        let workspace_path = fixture.workspace_path();
+        fixture
+            .update_settings(|settings| {
+                let mut config: ExecutionConfig = settings.get_module_config("execution");
+                config.execution_mode = CommandExecutionMode::Bash;
+                config.output_encoding = Some("windows-1252".to_string());
+                settings.set_module_config("execution", config);
+            })
+            .await;
+
+        // "café" in windows-1252, which is not valid UTF-8
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "bash".to_string(),
+            tool_arguments: bash_args("printf 'caf\\351'", &workspace_path),
+        });
+
+        fixture.step("Run a command with locale output").await;
+
+        let results = tool_results_from_last_request(&fixture);
+        assert!(
+            results.iter().any(|result| result.contains("café")),
+            "{results:?}"
+        );
+    });
+}
+
+#[test]
+fn test_failing_command_output_is_not_categorized() {
+    fixture::run(|mut fixture| async move {
+        use tycode_core::ai::mock::MockBehavior;
+
+        let workspace_path = fixture.workspace_path();
+        fixture
+            .update_settings(|settings| {
+                let mut config: ExecutionConfig = settings.get_module_config("execution");
+                config.execution_mode = CommandExecutionMode::Bash;
+                settings.set_module_config("execution", config);
+            })
+            .await;
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "bash".to_string(),
+            tool_arguments: bash_args("echo 'error: file not found' >&2; exit 1", &workspace_path),
+        });
+
+        fixture.step("Run a failing command").await;
+
+        let results = tool_results_from_last_request(&fixture);
+        assert_eq!(results.len(), 1);
+        assert!(
+            results[0].contains("file not found"),
+            "Captured: {}",
+            results[0]
+        );
+        assert!(
+            !results[0].contains("[error category"),
+            "command output must not be classified as a tool error: {}",
+            results[0]
+        );
+    });
+}
+
+#[test]
+fn test_format_file_is_confirmed_and_undoable() {
+    fixture::run_with_agent("coder", |mut fixture| async move {
+        use tycode_core::ai::mock::MockBehavior;
+        use tycode_core::file::config::File;
+        use tycode_core::modules::execution::config::FormatterConfig;
+
+        let workspace_path = fixture.workspace_path();
+        let target = workspace_path.join("messy.txt");
+        std::fs::write(&target, "messy\n").unwrap();
+        std::fs::write(workspace_path.join("formatted.out"), "tidy\n").unwrap();
+        fixture
+            .update_settings(|settings| {
+                let mut config: ExecutionConfig = settings.get_module_config("execution");
+                config.formatters = vec![FormatterConfig {
+                    extensions: vec!["txt".to_string()],
+                    command: "cp formatted.out {file}".to_string(),
+                    config_files: vec![],
+                }];
+                settings.set_module_config("execution", config);
+
+                let mut file: File = settings.get_module_config(File::NAMESPACE);
+                file.confirm_edits = true;
+                settings.set_module_config(File::NAMESPACE, file);
+            })
+            .await;
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "format_file".to_string(),
+            tool_arguments: json!({ "file_path": target.display().to_string() }).to_string(),
+        });
+        fixture.step("Format the file").await;
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap(),
+            "messy\n",
+            "Formatting must wait for confirmation"
+        );
+
+        // The coder keeps working until it completes its task
+        fixture.set_mock_behavior(MockBehavior::ToolUse {
+            tool_name: "complete_task".to_string(),
+            tool_arguments: json!({ "success": true, "result": "Formatted" }).to_string(),
+        });
+        fixture.step("y").await;
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "tidy\n");
+
+        fixture.step("/undo").await;
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "messy\n");
+    });
+}
@@ -4,3 +4,6 @@
 
 #[path = "file/modify.rs"]
 mod modify;
+
+#[path = "file/search.rs"]
+mod search;
@@ -6,6 +6,10 @@
 mod fixture;
 
 use fixture::MockBehavior;
+use tycode_core::ai::types::ContentBlock;
+use tycode_core::chat::events::{ChatEvent, MessageSender};
+use tycode_core::persistence::storage;
+use tycode_core::settings::config::AutonomyLevel;
 
 #[test]
 fn test_write_file_creates_new_file() {
@@ -137,3 +141,674 @@ fn test_modify_file_with_real_absolute_path() {
         );
     });
 }
+
+#[test]
+fn test_modify_file_preserves_crlf_line_endings() {
+    fixture::run(|mut fixture| async move {
+        let workspace_path = fixture.workspace_path();
+        let test_file = workspace_path.join("windows.txt");
+
+        std::fs::write(&test_file, "line 1\r\nline 2\r\nline 3\r\n").unwrap();
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "modify_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "diff": [{"search": "line 1\nline 2", "replace": "line 1\nnew line\nline 2"}]
+            })
+            .to_string(),
+        });
+        fixture.step("Insert a line").await;
+
+        let content = std::fs::read_to_string(&test_file).unwrap();
+        assert_eq!(
+            content, "line 1\r\nnew line\r\nline 2\r\nline 3\r\n",
+            "CRLF endings should be preserved"
+        );
+    });
+}
+
+async fn enable_confirm_edits(fixture: &mut fixture::Fixture) {
+    fixture
+        .update_settings(|settings| {
+            let mut config: tycode_core::file::config::File =
+                settings.get_module_config(tycode_core::file::config::File::NAMESPACE);
+            config.confirm_edits = true;
+            settings.set_module_config(tycode_core::file::config::File::NAMESPACE, config);
+        })
+        .await;
+}
+
+#[test]
+fn test_confirm_edits_applies_after_approval() {
+    fixture::run(|mut fixture| async move {
+        enable_confirm_edits(&mut fixture).await;
+        let test_file = fixture.workspace_path().join("confirmed.txt");
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "content": "approved"
+            })
+            .to_string(),
+        });
+        fixture.step("Create a file").await;
+        assert!(!test_file.exists(), "Edit must wait for confirmation");
+
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("y").await;
+
+        let content = std::fs::read_to_string(&test_file).unwrap();
+        assert_eq!(content, "approved");
+    });
+}
+
+#[test]
+fn test_confirm_edits_discards_after_decline() {
+    fixture::run(|mut fixture| async move {
+        enable_confirm_edits(&mut fixture).await;
+        let test_file = fixture.workspace_path().join("declined.txt");
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "content": "declined"
+            })
+            .to_string(),
+        });
+        fixture.step("Create a file").await;
+
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("n").await;
+
+        assert!(!test_file.exists(), "Declined edit must not be written");
+        let request = fixture.get_last_ai_request().expect("AI saw the reply");
+        let last = request.messages.last().unwrap().content.text();
+        assert!(last.contains("declined the edit"), "Got: {last}");
+    });
+}
+
+#[test]
+fn test_unanswered_confirmation_expires_on_its_own() {
+    fixture::run(|mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                let mut config: tycode_core::file::config::File =
+                    settings.get_module_config(tycode_core::file::config::File::NAMESPACE);
+                config.confirm_edits = true;
+                config.confirm_edits_timeout_seconds = Some(1);
+                settings.set_module_config(tycode_core::file::config::File::NAMESPACE, config);
+            })
+            .await;
+        let test_file = fixture.workspace_path().join("expired.txt");
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "content": "expired"
+            })
+            .to_string(),
+        });
+        fixture.step("Create a file").await;
+
+        // Nothing is sent to the actor; the timer alone discards the edit
+        let warning = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                match fixture.event_rx.recv().await {
+                    Some(ChatEvent::MessageAdded(message))
+                        if message.sender == MessageSender::Warning =>
+                    {
+                        return message.content;
+                    }
+                    Some(_) => continue,
+                    None => panic!("actor stopped"),
+                }
+            }
+        })
+        .await
+        .expect("the timeout should fire without further input");
+        assert!(warning.contains("not confirmed in time"), "Got: {warning}");
+
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("y").await;
+
+        assert!(!test_file.exists(), "Expired edit must not be written");
+        let request = fixture.get_last_ai_request().expect("AI saw the reply");
+        let last = request.messages.last().unwrap().content.text();
+        assert!(last.contains("not confirmed in time"), "Got: {last}");
+    });
+}
+
+#[test]
+fn test_proposed_edits_are_reviewed_one_at_a_time() {
+    fixture::run(|mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                let mut config: tycode_core::file::config::File =
+                    settings.get_module_config(tycode_core::file::config::File::NAMESPACE);
+                config.propose_edits = true;
+                settings.set_module_config(tycode_core::file::config::File::NAMESPACE, config);
+            })
+            .await;
+        let accepted = fixture.workspace_path().join("accepted.txt");
+        let rejected = fixture.workspace_path().join("rejected.txt");
+        let write = |path: &std::path::Path| {
+            serde_json::json!({
+                "file_path": path.display().to_string(),
+                "content": "proposed"
+            })
+            .to_string()
+        };
+
+        fixture.set_mock_behavior(MockBehavior::MultipleToolUses {
+            tool_uses: vec![
+                ("write_file".to_string(), write(&accepted)),
+                ("write_file".to_string(), write(&rejected)),
+            ],
+        });
+        let events = fixture.step("Create two files").await;
+        let proposed = events
+            .iter()
+            .find_map(|event| match event {
+                ChatEvent::EditsProposed { edits } => Some(edits.clone()),
+                _ => None,
+            })
+            .expect("edits should be proposed");
+        assert_eq!(proposed.len(), 2);
+        assert!(!accepted.exists() && !rejected.exists());
+
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.clear_captured_requests();
+        fixture
+            .step(format!("accept {}", proposed[0].edit_id))
+            .await;
+        assert_eq!(std::fs::read_to_string(&accepted).unwrap(), "proposed");
+        assert!(
+            fixture.get_last_ai_request().is_none(),
+            "the turn resumes only once every edit is decided"
+        );
+
+        fixture
+            .step(format!("reject {}", proposed[1].edit_id))
+            .await;
+        assert!(!rejected.exists(), "Rejected edit must not be written");
+        let request = fixture.get_last_ai_request().expect("AI saw the outcomes");
+        let last = request.messages.last().unwrap().content.text();
+        assert!(last.contains("accepted edit"), "Got: {last}");
+        assert!(last.contains("rejected edit"), "Got: {last}");
+    });
+}
+
+#[test]
+fn test_proposed_edits_are_saved_with_the_session() {
+    fixture::run(|mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                let mut config: tycode_core::file::config::File =
+                    settings.get_module_config(tycode_core::file::config::File::NAMESPACE);
+                config.propose_edits = true;
+                settings.set_module_config(tycode_core::file::config::File::NAMESPACE, config);
+            })
+            .await;
+        let test_file = fixture.workspace_path().join("proposed.txt");
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "content": "proposed"
+            })
+            .to_string(),
+        });
+        fixture.step("Create a file").await;
+        let sessions = storage::list_sessions(Some(&fixture.sessions_dir())).unwrap();
+        let session_id = sessions[0].id.clone();
+
+        // A new session starts without the previous session's proposals
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("/new").await;
+        fixture.step("accept 1").await;
+        assert!(!test_file.exists(), "/new must discard proposed edits");
+
+        fixture.step(format!("/sessions resume {session_id}")).await;
+        fixture.step("accept 1").await;
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "proposed");
+        let request = fixture.get_last_ai_request().expect("AI saw the outcome");
+        let last = request.messages.last().unwrap().content.text();
+        assert!(last.contains("accepted edit 1"), "Got: {last}");
+    });
+}
+
+#[test]
+fn test_conservative_agent_confirms_edits() {
+    fixture::run(|mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                settings
+                    .agent_autonomy_levels
+                    .insert("one_shot".to_string(), AutonomyLevel::Conservative);
+            })
+            .await;
+        let test_file = fixture.workspace_path().join("conservative.txt");
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "content": "approved"
+            })
+            .to_string(),
+        });
+        fixture.step("Create a file").await;
+        assert!(!test_file.exists(), "Edit must wait for confirmation");
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        assert!(
+            request
+                .system_prompt
+                .contains("Autonomy Level: Conservative"),
+            "Agent override should select the conservative prompt"
+        );
+
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("y").await;
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "approved");
+    });
+}
+
+#[test]
+fn test_autonomous_agent_skips_edit_confirmation() {
+    fixture::run(|mut fixture| async move {
+        enable_confirm_edits(&mut fixture).await;
+        fixture
+            .update_settings(|settings| {
+                settings
+                    .agent_autonomy_levels
+                    .insert("one_shot".to_string(), AutonomyLevel::Autonomous);
+            })
+            .await;
+        let test_file = fixture.workspace_path().join("autonomous.txt");
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "content": "applied"
+            })
+            .to_string(),
+        });
+        fixture.step("Create a file").await;
+        assert_eq!(
+            std::fs::read_to_string(&test_file).unwrap(),
+            "applied",
+            "Autonomous agents proceed without pausing for confirmation"
+        );
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        assert!(
+            request.system_prompt.contains("Autonomy Level: Autonomous"),
+            "Agent override should select the autonomous prompt"
+        );
+    });
+}
+
+#[test]
+fn test_balanced_is_the_default_and_honors_confirm_edits() {
+    fixture::run(|mut fixture| async move {
+        enable_confirm_edits(&mut fixture).await;
+        let test_file = fixture.workspace_path().join("balanced.txt");
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "content": "held"
+            })
+            .to_string(),
+        });
+        fixture.step("Create a file").await;
+        assert!(!test_file.exists(), "confirm_edits applies at balanced");
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        assert!(
+            request.system_prompt.contains("Autonomy Level: Balanced"),
+            "Balanced should be the default level"
+        );
+    });
+}
+
+#[test]
+fn test_undo_and_redo_last_edit() {
+    fixture::run(|mut fixture| async move {
+        let test_file = fixture.workspace_path().join("undo_me.txt");
+        std::fs::write(&test_file, "original\n").unwrap();
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "content": "rewritten\n"
+            })
+            .to_string(),
+        });
+        fixture.step("Rewrite the file").await;
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "rewritten\n");
+
+        fixture.step("/undo").await;
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "original\n");
+
+        fixture.step("/redo").await;
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "rewritten\n");
+    });
+}
+
+#[test]
+fn test_replay_reapplies_session_edits_and_stops_on_conflict() {
+    fixture::run(|mut fixture| async move {
+        let test_file = fixture.workspace_path().join("replay_me.txt");
+        std::fs::write(&test_file, "alpha\n").unwrap();
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "modify_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "diff": [{"search": "alpha", "replace": "beta"}]
+            })
+            .to_string(),
+        });
+        let events = fixture.step("Change alpha to beta").await;
+        let session_id = events
+            .iter()
+            .find_map(|event| match event {
+                ChatEvent::SessionStarted { session_id } => Some(session_id.clone()),
+                _ => None,
+            })
+            .expect("session id");
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "beta\n");
+
+        std::fs::write(&test_file, "alpha\n").unwrap();
+        let events = fixture.step(format!("/replay {session_id}")).await;
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "beta\n");
+        assert!(messages(&events).any(|m| m.contains("Replayed all 1 edits")));
+
+        std::fs::write(&test_file, "gamma\n").unwrap();
+        let events = fixture.step(format!("/replay {session_id}")).await;
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "gamma\n");
+        assert!(
+            messages(&events).any(|m| m.contains("Replayed 0 of 1 edits")),
+            "events={events:?}"
+        );
+    });
+}
+
+#[test]
+fn test_replay_goes_through_confirmation_and_undo_history() {
+    fixture::run(|mut fixture| async move {
+        let test_file = fixture.workspace_path().join("replay_confirmed.txt");
+        std::fs::write(&test_file, "alpha\n").unwrap();
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "modify_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "diff": [{"search": "alpha", "replace": "beta"}]
+            })
+            .to_string(),
+        });
+        let events = fixture.step("Change alpha to beta").await;
+        let session_id = events
+            .iter()
+            .find_map(|event| match event {
+                ChatEvent::SessionStarted { session_id } => Some(session_id.clone()),
+                _ => None,
+            })
+            .expect("session id");
+
+        std::fs::write(&test_file, "alpha\n").unwrap();
+        enable_confirm_edits(&mut fixture).await;
+        let events = fixture.step(format!("/replay {session_id}")).await;
+        assert_eq!(
+            std::fs::read_to_string(&test_file).unwrap(),
+            "alpha\n",
+            "Replayed edit must wait for confirmation"
+        );
+        assert!(
+            messages(&events)
+                .any(|m| m.contains("Replayed 0 of 1 edits") && m.contains("is waiting for you")),
+            "events={events:?}"
+        );
+
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("y").await;
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "beta\n");
+
+        fixture.step("/undo").await;
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "alpha\n");
+    });
+}
+
+#[test]
+fn test_replay_resumes_after_a_held_edit() {
+    fixture::run(|mut fixture| async move {
+        let first = fixture.workspace_path().join("first.txt");
+        let second = fixture.workspace_path().join("second.txt");
+        let write = |path: &std::path::Path| {
+            serde_json::json!({
+                "file_path": path.display().to_string(),
+                "content": "replayed"
+            })
+            .to_string()
+        };
+
+        fixture.set_mock_behavior(MockBehavior::MultipleToolUses {
+            tool_uses: vec![
+                ("write_file".to_string(), write(&first)),
+                ("write_file".to_string(), write(&second)),
+            ],
+        });
+        let events = fixture.step("Create two files").await;
+        let session_id = events
+            .iter()
+            .find_map(|event| match event {
+                ChatEvent::SessionStarted { session_id } => Some(session_id.clone()),
+                _ => None,
+            })
+            .expect("session id");
+
+        std::fs::remove_file(&first).unwrap();
+        std::fs::remove_file(&second).unwrap();
+        enable_confirm_edits(&mut fixture).await;
+        fixture.set_mock_behavior(MockBehavior::Success);
+        let events = fixture.step(format!("/replay {session_id}")).await;
+        let resume = format!("/replay {session_id} --from 2");
+        assert!(
+            messages(&events).any(|m| m.contains("Edit 1") && m.contains(&resume)),
+            "events={events:?}"
+        );
+
+        fixture.step("y").await;
+        assert!(first.exists() && !second.exists());
+
+        let events = fixture.step(resume).await;
+        assert!(!second.exists(), "The resumed edit is confirmed too");
+        fixture.step("y").await;
+        assert!(second.exists());
+        assert!(
+            messages(&events).any(|m| m.contains("Replayed 1 of 2 edits")),
+            "events={events:?}"
+        );
+    });
+}
+
+const CONFLICTED: &str =
+    "fn total() -> i32 {\n<<<<<<< HEAD\n    1\n=======\n    2\n>>>>>>> feature\n}\n";
+
+fn last_tool_result(fixture: &fixture::Fixture) -> String {
+    fixture
+        .get_last_ai_request()
+        .expect("Should have AI request")
+        .messages
+        .iter()
+        .flat_map(|message| message.content.blocks())
+        .filter_map(|block| match block {
+            ContentBlock::ToolResult(result) => Some(result.content.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .pop()
+        .expect("Should have a tool result")
+}
+
+#[test]
+fn test_modify_file_warns_when_conflict_markers_remain() {
+    fixture::run(|mut fixture| async move {
+        let test_file = fixture.workspace_path().join("conflicted.rs");
+        std::fs::write(&test_file, CONFLICTED).unwrap();
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "modify_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "diff": [{"search": "fn total()", "replace": "fn sum()"}]
+            })
+            .to_string(),
+        });
+        fixture.step("Rename the function").await;
+
+        let result = last_tool_result(&fixture);
+        assert!(
+            result.contains("1 unresolved merge conflict(s) at lines 2-6"),
+            "{result}"
+        );
+    });
+}
+
+#[test]
+fn test_resolve_conflicts_keeps_chosen_side() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                let mut config: tycode_core::file::config::File =
+                    settings.get_module_config(tycode_core::file::config::File::NAMESPACE);
+                config.resolve_conflicts = true;
+                settings.set_module_config(tycode_core::file::config::File::NAMESPACE, config);
+            })
+            .await;
+        let test_file = fixture.workspace_path().join("conflicted.rs");
+        std::fs::write(&test_file, CONFLICTED).unwrap();
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "resolve_conflicts".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "resolution": "theirs"
+            })
+            .to_string(),
+        });
+        fixture.step("Take their side").await;
+
+        let content = std::fs::read_to_string(&test_file).unwrap();
+        assert_eq!(content, "fn total() -> i32 {\n    2\n}\n");
+        let result = last_tool_result(&fixture);
+        assert!(result.contains("\"conflicts_remaining\":0"), "{result}");
+    });
+}
+
+fn last_request_text(fixture: &fixture::Fixture) -> String {
+    fixture
+        .get_last_ai_request()
+        .expect("Should have AI request")
+        .messages
+        .iter()
+        .flat_map(|message| message.content.blocks())
+        .filter_map(|block| match block {
+            ContentBlock::Text(text) => Some(text.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_lint_findings_for_edited_file_appear_in_context() {
+    fixture::run(|mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                let mut config: tycode_core::file::config::File =
+                    settings.get_module_config(tycode_core::file::config::File::NAMESPACE);
+                config.linters = vec![tycode_core::file::config::LinterConfig {
+                    extensions: vec!["txt".to_string()],
+                    command: "echo {file}:1:1: warning: demo lint finding".to_string(),
+                    trigger: Default::default(),
+                }];
+                settings.set_module_config(tycode_core::file::config::File::NAMESPACE, config);
+            })
+            .await;
+        let test_file = fixture.workspace_path().join("linted.txt");
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: serde_json::json!({
+                "file_path": test_file.display().to_string(),
+                "content": "lint me"
+            })
+            .to_string(),
+        });
+        fixture.step("Write a file").await;
+
+        let text = last_request_text(&fixture);
+        assert!(
+            text.contains(&format!(
+                "{}:1: warning: demo lint finding",
+                test_file.display()
+            )),
+            "{text}"
+        );
+    });
+}
+
+fn messages(events: &[ChatEvent]) -> impl Iterator<Item = &str> {
+    events.iter().filter_map(|event| match event {
+        ChatEvent::MessageAdded(message) => Some(message.content.as_str()),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_tool_example_follows_selected_modify_api() {
+    fixture::run(|mut fixture| async move {
+        fixture.step("Hello").await;
+        let prompt = fixture.get_last_ai_request().unwrap().system_prompt;
+        assert!(prompt.contains("## Tool Examples"), "Got: {prompt}");
+        assert!(prompt.contains("\"diff\": ["));
+
+        fixture
+            .update_settings(|settings| {
+                let mut config: tycode_core::file::config::File =
+                    settings.get_module_config(tycode_core::file::config::File::NAMESPACE);
+                config.file_modification_api =
+                    tycode_core::settings::config::FileModificationApi::Patch;
+                settings.set_module_config(tycode_core::file::config::File::NAMESPACE, config);
+            })
+            .await;
+        fixture.step("Hello again").await;
+        let prompt = fixture.get_last_ai_request().unwrap().system_prompt;
+        assert!(prompt.contains("\"hunks\":"));
+        assert!(!prompt.contains("\"diff\": ["));
+
+        fixture
+            .update_settings(|settings| {
+                let mut config: tycode_core::file::config::File =
+                    settings.get_module_config(tycode_core::file::config::File::NAMESPACE);
+                config.tool_examples = false;
+                settings.set_module_config(tycode_core::file::config::File::NAMESPACE, config);
+            })
+            .await;
+        fixture.step("And again").await;
+        let prompt = fixture.get_last_ai_request().unwrap().system_prompt;
+        assert!(!prompt.contains("## Tool Examples"));
+    });
+}
@@ -0,0 +1,142 @@
+//! search_files tool simulation tests.
+//!
+//! Tests for `src/file/search.rs` and `src/file/search_index.rs`
+
+#[path = "../../fixture.rs"]
+mod fixture;
+
+use fixture::MockBehavior;
+use tycode_core::ai::types::ContentBlock;
+use tycode_core::file::config::File;
+
+fn last_tool_result(fixture: &fixture::Fixture) -> String {
+    fixture
+        .get_last_ai_request()
+        .expect("Should have AI request")
+        .messages
+        .iter()
+        .flat_map(|message| message.content.blocks())
+        .filter_map(|block| match block {
+            ContentBlock::ToolResult(result) => Some(result.content.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .pop()
+        .expect("Should have a tool result")
+}
+
+async fn search(fixture: &mut fixture::Fixture, arguments: serde_json::Value) -> String {
+    fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+        tool_name: "search_files".to_string(),
+        tool_arguments: arguments.to_string(),
+    });
+    fixture.step("Search the workspace").await;
+    last_tool_result(fixture)
+}
+
+#[test]
+fn test_search_files_reports_matching_lines() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        let workspace_path = fixture.workspace_path();
+        std::fs::create_dir_all(workspace_path.join("src")).unwrap();
+        std::fs::write(
+            workspace_path.join("src/lib.rs"),
+            "use std::fmt;\n\npub fn parse_args() {}\n",
+        )
+        .unwrap();
+        std::fs::write(workspace_path.join("notes.txt"), "nothing here\n").unwrap();
+
+        let result = search(
+            &mut fixture,
+            serde_json::json!({ "pattern": r"fn parse_\w+" }),
+        )
+        .await;
+        assert!(
+            result.contains("lib.rs:3: pub fn parse_args() {}"),
+            "{result}"
+        );
+        assert!(!result.contains("notes.txt"), "{result}");
+
+        let result = search(
+            &mut fixture,
+            serde_json::json!({ "pattern": "PARSE_ARGS", "case_insensitive": true }),
+        )
+        .await;
+        assert!(result.contains("lib.rs:3:"), "{result}");
+
+        let result = search(
+            &mut fixture,
+            serde_json::json!({ "pattern": "missing_symbol" }),
+        )
+        .await;
+        assert!(result.contains("No matches"), "{result}");
+    });
+}
+
+#[test]
+fn test_search_index_is_persisted_and_tracks_edits() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                let mut config: File = settings.get_module_config(File::NAMESPACE);
+                config.search_index = true;
+                settings.set_module_config(File::NAMESPACE, config);
+            })
+            .await;
+        let workspace_path = fixture.workspace_path();
+        std::fs::write(workspace_path.join("a.rs"), "fn alpha_handler() {}\n").unwrap();
+        std::fs::write(workspace_path.join("b.rs"), "fn beta() {}\n").unwrap();
+
+        let result = search(&mut fixture, serde_json::json!({ "pattern": "handler" })).await;
+        assert!(result.contains("a.rs:1:"), "{result}");
+        assert!(!result.contains("b.rs"), "{result}");
+        assert!(
+            workspace_path.join(".tycode/index/search.idx").exists(),
+            "index should be written under .tycode/index"
+        );
+
+        // Edits, new directories and deletions made between searches are
+        // picked up once the watcher reports them
+        std::fs::write(
+            workspace_path.join("b.rs"),
+            "fn beta() {}\nfn beta_handler() {}\n",
+        )
+        .unwrap();
+        std::fs::create_dir(workspace_path.join("nested")).unwrap();
+        std::fs::write(
+            workspace_path.join("nested/c.rs"),
+            "fn gamma_handler() {}\n",
+        )
+        .unwrap();
+        std::fs::remove_file(workspace_path.join("a.rs")).unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+        let result = search(&mut fixture, serde_json::json!({ "pattern": "handler" })).await;
+        assert!(!result.contains("a.rs"), "{result}");
+        assert!(result.contains("b.rs:2:"), "{result}");
+        assert!(result.contains("c.rs:1:"), "{result}");
+    });
+}
+
+#[test]
+fn test_search_falls_back_to_walking_over_index_budget() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                let mut config: File = settings.get_module_config(File::NAMESPACE);
+                config.search_index = true;
+                config.search_index_max_bytes = 8;
+                settings.set_module_config(File::NAMESPACE, config);
+            })
+            .await;
+        let workspace_path = fixture.workspace_path();
+        std::fs::write(workspace_path.join("big.rs"), "fn over_budget() {}\n").unwrap();
+
+        let result = search(
+            &mut fixture,
+            serde_json::json!({ "pattern": "over_budget" }),
+        )
+        .await;
+        assert!(result.contains("big.rs:1:"), "{result}");
+    });
+}
@@ -0,0 +1,254 @@
+//! Git module simulation tests.
+
+#[path = "../fixture.rs"]
+mod fixture;
+
+use fixture::MockBehavior;
+use std::path::Path;
+use std::process::Command;
+use tycode_core::chat::events::ChatEvent;
+use tycode_core::modules::git::Git;
+
+fn git(workspace: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(workspace)
+        .output()
+        .expect("git command failed");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// A repository on `main` with a `feature` branch and one tracked file.
+fn init_repo(workspace: &Path) {
+    git(workspace, &["init", "-b", "main"]);
+    git(workspace, &["config", "user.email", "test@test.com"]);
+    git(workspace, &["config", "user.name", "Test"]);
+    std::fs::write(workspace.join("tracked.txt"), "initial\n").unwrap();
+    git(workspace, &["add", "tracked.txt"]);
+    git(workspace, &["commit", "-m", "initial"]);
+    git(workspace, &["branch", "feature"]);
+}
+
+async fn enable_git(fixture: &mut fixture::Fixture, confirm_changes: bool) {
+    fixture
+        .update_settings(|settings| {
+            settings.set_module_config(
+                Git::NAMESPACE,
+                Git {
+                    enabled: true,
+                    confirm_changes,
+                    ..Git::default()
+                },
+            );
+        })
+        .await;
+}
+
+fn checkout(branch: &str, force: bool) -> MockBehavior {
+    MockBehavior::ToolUseThenSuccess {
+        tool_name: "git_branch".to_string(),
+        tool_arguments: serde_json::json!({
+            "action": "checkout",
+            "branch": branch,
+            "force": force
+        })
+        .to_string(),
+    }
+}
+
+#[test]
+fn checkout_waits_for_approval() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        init_repo(&workspace);
+        enable_git(&mut fixture, true).await;
+
+        fixture.set_mock_behavior(checkout("feature", false));
+        fixture.step("Switch to the feature branch").await;
+        assert_eq!(git(&workspace, &["branch", "--show-current"]), "main");
+
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("y").await;
+        assert_eq!(git(&workspace, &["branch", "--show-current"]), "feature");
+    });
+}
+
+#[test]
+fn checkout_refuses_dirty_tree_without_force() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        init_repo(&workspace);
+        enable_git(&mut fixture, false).await;
+        std::fs::write(workspace.join("tracked.txt"), "uncommitted\n").unwrap();
+
+        fixture.set_mock_behavior(checkout("feature", false));
+        fixture.step("Switch to the feature branch").await;
+        assert_eq!(git(&workspace, &["branch", "--show-current"]), "main");
+
+        let request = fixture.get_last_ai_request().expect("AI saw the result");
+        let results: Vec<_> = request
+            .messages
+            .iter()
+            .flat_map(|m| m.content.tool_results())
+            .collect();
+        let result = results.last().expect("git_branch result");
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("uncommitted changes"),
+            "{}",
+            result.content
+        );
+
+        fixture.set_mock_behavior(checkout("feature", true));
+        fixture.step("Switch anyway").await;
+        assert_eq!(git(&workspace, &["branch", "--show-current"]), "feature");
+    });
+}
+
+fn commit(paths: &[&Path], message: Option<&str>) -> MockBehavior {
+    let mut arguments = serde_json::json!({
+        "paths": paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>()
+    });
+    if let Some(message) = message {
+        arguments["message"] = message.into();
+    }
+    MockBehavior::ToolUseThenSuccess {
+        tool_name: "git_commit".to_string(),
+        tool_arguments: arguments.to_string(),
+    }
+}
+
+#[test]
+fn commit_includes_only_the_given_paths() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        init_repo(&workspace);
+        enable_git(&mut fixture, false).await;
+        let added = workspace.join("added.txt");
+        std::fs::write(&added, "new\n").unwrap();
+        std::fs::write(workspace.join("tracked.txt"), "left alone\n").unwrap();
+
+        fixture.set_mock_behavior(commit(&[&added], Some("feat: add file")));
+        fixture.step("Commit the new file").await;
+
+        assert_eq!(
+            git(&workspace, &["log", "-1", "--format=%s"]),
+            "feat: add file"
+        );
+        assert_eq!(
+            git(&workspace, &["show", "--name-only", "--format=", "HEAD"]),
+            "added.txt"
+        );
+        assert_eq!(
+            git(
+                &workspace,
+                &["status", "--porcelain", "--untracked-files=no"]
+            ),
+            "M tracked.txt"
+        );
+
+        let request = fixture.get_last_ai_request().expect("AI saw the result");
+        let hash = git(&workspace, &["rev-parse", "HEAD"]);
+        let result = request
+            .messages
+            .iter()
+            .flat_map(|m| m.content.tool_results())
+            .last()
+            .cloned()
+            .expect("git_commit result");
+        assert!(result.content.contains(&hash), "{}", result.content);
+    });
+}
+
+#[test]
+fn commit_generates_a_message_when_none_is_given() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        init_repo(&workspace);
+        enable_git(&mut fixture, false).await;
+        let tracked = workspace.join("tracked.txt");
+        std::fs::write(&tracked, "changed\n").unwrap();
+
+        fixture.set_mock_behavior(commit(&[&tracked], None));
+        fixture.step("Commit it").await;
+
+        assert_eq!(
+            git(&workspace, &["log", "-1", "--format=%s"]),
+            "Mock response"
+        );
+    });
+}
+
+#[test]
+fn commit_rejects_paths_outside_the_workspace() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        init_repo(&workspace);
+        enable_git(&mut fixture, false).await;
+        let outside = tempfile::tempdir().unwrap();
+        let stray = outside.path().join("stray.txt");
+        std::fs::write(&stray, "stray\n").unwrap();
+
+        fixture.set_mock_behavior(commit(&[&stray], Some("chore: stray")));
+        fixture.step("Commit it").await;
+
+        assert_eq!(git(&workspace, &["log", "-1", "--format=%s"]), "initial");
+    });
+}
+
+#[test]
+fn git_tools_are_off_by_default() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("hello").await;
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        assert!(!request
+            .tools
+            .iter()
+            .any(|tool| tool.name.starts_with("git_") && tool.name != "git_blame"));
+    });
+}
+
+#[test]
+fn git_tools_need_a_repository() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        enable_git(&mut fixture, false).await;
+
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("hello").await;
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        assert!(!request
+            .tools
+            .iter()
+            .any(|tool| tool.name == "git_branch" || tool.name == "git_commit"));
+    });
+}
+
+#[test]
+fn commit_approval_shows_the_generated_message() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        init_repo(&workspace);
+        enable_git(&mut fixture, true).await;
+        let tracked = workspace.join("tracked.txt");
+        std::fs::write(&tracked, "changed\n").unwrap();
+
+        fixture.set_mock_behavior(commit(&[&tracked], None));
+        let events = fixture.step("Commit it").await;
+        assert_eq!(git(&workspace, &["log", "-1", "--format=%s"]), "initial");
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ChatEvent::MessageAdded(message)
+                if message.content.contains("with message \"Mock response\"")
+        )));
+
+        fixture.set_mock_behavior(MockBehavior::Success);
+        fixture.step("y").await;
+        assert_eq!(
+            git(&workspace, &["log", "-1", "--format=%s"]),
+            "Mock response"
+        );
+    });
+}
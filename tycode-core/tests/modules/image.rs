@@ -2,6 +2,7 @@
 mod fixture;
 
 use fixture::{run, MockBehavior};
+use tycode_core::ai::ImageGenerationStatus;
 use tycode_core::chat::events::ChatEvent;
 use tycode_core::modules::image::config::Image;
 
@@ -140,3 +141,201 @@ fn test_image_generation_end_to_end() {
         );
     })
 }
+
+#[test]
+fn test_image_generation_reports_progress() {
+    run(|mut fixture| async move {
+        let image_path = fixture.workspace_path().join("progress.png");
+        fixture
+            .update_settings(|settings| {
+                let mut config: Image = settings.get_module_config("image");
+                config.enabled = true;
+                settings.set_module_config("image", config);
+            })
+            .await;
+        fixture.set_image_gen_enabled(true);
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "generate_image".to_string(),
+            tool_arguments: serde_json::json!({
+                "prompt": "A red pixel",
+                "output_path": image_path.display().to_string()
+            })
+            .to_string(),
+        });
+
+        let events = fixture.step("Generate an image for me").await;
+
+        let statuses: Vec<ImageGenerationStatus> = events
+            .into_iter()
+            .filter_map(|e| match e {
+                ChatEvent::ImageGenerationProgress { status, .. } => Some(status),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ImageGenerationStatus::Started,
+                ImageGenerationStatus::Queued,
+                ImageGenerationStatus::Processing,
+                ImageGenerationStatus::Done,
+            ]
+        );
+    })
+}
+
+#[test]
+fn test_image_generation_writes_numbered_variants() {
+    run(|mut fixture| async move {
+        let workspace_path = fixture.workspace_path();
+        fixture
+            .update_settings(|settings| {
+                let mut config: Image = settings.get_module_config("image");
+                config.enabled = true;
+                config.max_image_count = 3;
+                settings.set_module_config("image", config);
+            })
+            .await;
+        fixture.set_image_gen_enabled(true);
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "generate_image".to_string(),
+            tool_arguments: serde_json::json!({
+                "prompt": "A red pixel",
+                "output_path": workspace_path.join("logo.png").display().to_string(),
+                "count": 3
+            })
+            .to_string(),
+        });
+
+        fixture.step("Give me three logo options").await;
+
+        assert!(!workspace_path.join("logo.png").exists());
+        for i in 1..=3 {
+            assert!(
+                workspace_path.join(format!("logo-{i}.png")).exists(),
+                "Variant {i} should be written"
+            );
+        }
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "generate_image".to_string(),
+            tool_arguments: serde_json::json!({
+                "prompt": "A red pixel",
+                "output_path": workspace_path.join("icon.png").display().to_string(),
+                "count": 4
+            })
+            .to_string(),
+        });
+
+        fixture.step("Give me four icon options").await;
+
+        assert!(!workspace_path.join("icon-1.png").exists());
+        let request = fixture.get_last_ai_request().unwrap();
+        let tool_result = request
+            .messages
+            .iter()
+            .flat_map(|m| m.content.tool_results())
+            .map(|result| result.content.clone())
+            .last()
+            .unwrap();
+        assert!(
+            tool_result.contains("exceeds the configured maximum of 3"),
+            "Got: {tool_result}"
+        );
+    })
+}
+
+#[test]
+fn test_convert_image_resizes_and_changes_format() {
+    run(|mut fixture| async move {
+        let workspace_path = fixture.workspace_path();
+        let input_path = workspace_path.join("wide.png");
+        let output_path = workspace_path.join("wide-small.jpg");
+        image::RgbaImage::from_pixel(40, 20, image::Rgba([255, 0, 0, 128]))
+            .save(&input_path)
+            .unwrap();
+
+        // Converting needs no image generation support from the provider
+        fixture
+            .update_settings(|settings| {
+                let mut config: Image = settings.get_module_config("image");
+                config.enabled = true;
+                settings.set_module_config("image", config);
+            })
+            .await;
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "convert_image".to_string(),
+            tool_arguments: serde_json::json!({
+                "input_path": input_path.display().to_string(),
+                "output_path": output_path.display().to_string(),
+                "max_dimension": 10
+            })
+            .to_string(),
+        });
+
+        fixture.step("Shrink wide.png into a small JPEG").await;
+
+        let converted = image::open(&output_path).expect("JPEG should be written");
+        assert_eq!((converted.width(), converted.height()), (10, 5));
+        assert_eq!(
+            image::ImageFormat::from_path(&output_path).unwrap(),
+            image::ImageFormat::Jpeg
+        );
+
+        let request = fixture.get_last_ai_request().unwrap();
+        let tool_result = request
+            .messages
+            .iter()
+            .flat_map(|m| m.content.tool_results())
+            .map(|result| result.content.clone())
+            .last()
+            .unwrap();
+        assert!(
+            tool_result.contains("(40x20)") && tool_result.contains("(10x5,"),
+            "Got: {tool_result}"
+        );
+    })
+}
+
+#[test]
+fn test_convert_image_overwrites_only_when_asked() {
+    run(|mut fixture| async move {
+        let workspace_path = fixture.workspace_path();
+        let input_path = workspace_path.join("square.png");
+        let output_path = workspace_path.join("existing.png");
+        image::RgbaImage::from_pixel(20, 20, image::Rgba([0, 255, 0, 255]))
+            .save(&input_path)
+            .unwrap();
+        std::fs::write(&output_path, b"keep me").unwrap();
+
+        fixture
+            .update_settings(|settings| {
+                let mut config: Image = settings.get_module_config("image");
+                config.enabled = true;
+                settings.set_module_config("image", config);
+            })
+            .await;
+        let convert = |overwrite: bool| MockBehavior::ToolUseThenSuccess {
+            tool_name: "convert_image".to_string(),
+            tool_arguments: serde_json::json!({
+                "input_path": input_path.display().to_string(),
+                "output_path": output_path.display().to_string(),
+                "max_dimension": 10,
+                "overwrite": overwrite
+            })
+            .to_string(),
+        };
+
+        fixture.set_mock_behavior(convert(false));
+        fixture.step("Shrink square.png into existing.png").await;
+        assert_eq!(std::fs::read(&output_path).unwrap(), b"keep me");
+
+        fixture.set_mock_behavior(convert(true));
+        fixture.step("Replace it").await;
+        let converted = image::open(&output_path).expect("PNG should be written");
+        assert_eq!((converted.width(), converted.height()), (10, 10));
+
+        fixture.step("/undo").await;
+        assert_eq!(std::fs::read(&output_path).unwrap(), b"keep me");
+    })
+}
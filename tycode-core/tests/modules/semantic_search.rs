@@ -0,0 +1,95 @@
+//! Semantic search module simulation tests.
+//!
+//! Tests for `src/modules/semantic_search/`
+
+#[path = "../fixture.rs"]
+mod fixture;
+
+use fixture::MockBehavior;
+use tycode_core::ai::types::ContentBlock;
+use tycode_core::modules::semantic_search::SemanticSearch;
+
+async fn enable(fixture: &mut fixture::Fixture) {
+    fixture
+        .update_settings(|settings| {
+            let mut config: SemanticSearch = settings.get_module_config(SemanticSearch::NAMESPACE);
+            config.enabled = true;
+            settings.set_module_config(SemanticSearch::NAMESPACE, config);
+        })
+        .await;
+}
+
+fn advertises_semantic_search(fixture: &fixture::Fixture) -> bool {
+    fixture
+        .get_last_ai_request()
+        .expect("AI request")
+        .tools
+        .iter()
+        .any(|tool| tool.name == "semantic_search")
+}
+
+#[test]
+fn semantic_search_requires_opt_in_and_embeddings() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        fixture.set_embeddings_enabled(true);
+        fixture.step("hello").await;
+        assert!(!advertises_semantic_search(&fixture), "off by default");
+
+        enable(&mut fixture).await;
+        fixture.set_embeddings_enabled(false);
+        fixture.step("hello").await;
+        assert!(
+            !advertises_semantic_search(&fixture),
+            "hidden without provider embeddings"
+        );
+
+        fixture.set_embeddings_enabled(true);
+        fixture.step("hello").await;
+        assert!(advertises_semantic_search(&fixture));
+    });
+}
+
+#[test]
+fn semantic_search_returns_most_related_chunk() {
+    fixture::run_with_agent("tycode", |mut fixture| async move {
+        enable(&mut fixture).await;
+        fixture.set_embeddings_enabled(true);
+        let workspace = fixture.workspace_path();
+        std::fs::write(
+            workspace.join("auth.rs"),
+            "// authentication token validation\nfn validate_token(token: &str) -> bool {\n    !token.is_empty()\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            workspace.join("math.rs"),
+            "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+        )
+        .unwrap();
+
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "semantic_search".to_string(),
+            tool_arguments: serde_json::json!({
+                "query": "where is the authentication token validation",
+                "max_results": 1
+            })
+            .to_string(),
+        });
+        fixture.step("Find the auth code").await;
+
+        let result = fixture
+            .get_last_ai_request()
+            .expect("AI request")
+            .messages
+            .iter()
+            .flat_map(|message| message.content.blocks())
+            .find_map(|block| match block {
+                ContentBlock::ToolResult(result) => Some(result.content.clone()),
+                _ => None,
+            })
+            .expect("tool result");
+        assert!(result.contains("auth.rs:1-4"), "{result}");
+        assert!(result.contains("fn validate_token"), "{result}");
+        assert!(!result.contains("math.rs"), "{result}");
+        assert!(workspace.join(".tycode/embeddings/index.json").exists());
+    });
+}
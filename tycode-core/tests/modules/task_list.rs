@@ -13,16 +13,19 @@
 mod fixture;
 
 use fixture::{run, MockBehavior};
-use tycode_core::ai::types::MessageRole;
+use tycode_core::ai::types::{ContentBlock, MessageRole};
 use tycode_core::chat::events::ChatEvent;
 use tycode_core::chat::events::EventSender;
 use tycode_core::module::Module;
-use tycode_core::modules::task_list::{TaskList, TaskListModule, TaskStatus, TaskWithStatus};
+use tycode_core::modules::task_list::{
+    TaskList, TaskListModule, TaskProgress, TaskStatus, TaskWithStatus,
+};
+use tycode_core::settings::config::CompletionGuard;
 
 /// Helper to find the first TaskUpdate event in a list of events
 fn find_task_update(events: &[ChatEvent]) -> Option<&TaskList> {
     events.iter().find_map(|e| match e {
-        ChatEvent::TaskUpdate(task_list) => Some(task_list),
+        ChatEvent::TaskUpdate(update) => Some(&update.task_list),
         _ => None,
     })
 }
@@ -177,6 +180,117 @@ fn test_manage_task_list_tool_replaces_task_list() {
     })
 }
 
+#[test]
+fn test_manage_tasks_tool_applies_operations_in_one_update() {
+    run(|mut fixture| async move {
+        // Default list: 0 "Await user request", 1 "Understand/Explore ..."
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "manage_tasks".to_string(),
+            tool_arguments: serde_json::json!({
+                "operations": [
+                    { "op": "update_status", "task_id": 0, "status": "completed" },
+                    { "op": "add", "description": "Write tests", "position": 0 },
+                    { "op": "remove", "task_id": 1 },
+                    { "op": "reorder", "task_id": 0, "position": 0 },
+                    { "op": "retitle", "title": "Batched Edits" }
+                ]
+            })
+            .to_string(),
+        });
+        let events = fixture.step("Edit my task list").await;
+
+        // Initial update plus exactly one for the whole batch
+        assert_eq!(count_task_updates(&events), 2);
+        let update = events
+            .iter()
+            .rev()
+            .find_map(|e| match e {
+                ChatEvent::TaskUpdate(update) => Some(update),
+                _ => None,
+            })
+            .expect("Should have a TaskUpdate");
+        let task_list = &update.task_list;
+        assert_eq!(task_list.title, "Batched Edits");
+        let tasks: Vec<(&str, TaskStatus)> = task_list
+            .tasks
+            .iter()
+            .map(|t| (t.description.as_str(), t.status))
+            .collect();
+        assert_eq!(
+            tasks,
+            vec![
+                ("Await user request", TaskStatus::Completed),
+                ("Write tests", TaskStatus::Pending),
+            ]
+        );
+    })
+}
+
+#[test]
+fn test_manage_tasks_tool_rejects_invalid_batch() {
+    run(|mut fixture| async move {
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "manage_tasks".to_string(),
+            tool_arguments: serde_json::json!({
+                "operations": [
+                    { "op": "update_status", "task_id": 0, "status": "completed" },
+                    { "op": "remove", "task_id": 7 }
+                ]
+            })
+            .to_string(),
+        });
+        let events = fixture.step("Edit my task list").await;
+
+        // Nothing is applied when any operation is invalid
+        assert_eq!(count_task_updates(&events), 1);
+        let task_list = find_task_update(&events).expect("Should have a TaskUpdate");
+        assert_eq!(task_list.tasks[0].status, TaskStatus::InProgress);
+    })
+}
+
+#[test]
+fn test_task_update_reports_progress() {
+    run(|mut fixture| async move {
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "manage_task_list".to_string(),
+            tool_arguments: serde_json::json!({
+                "title": "Progress",
+                "tasks": [
+                    { "description": "Write tests", "status": "completed" },
+                    { "description": "Flaky step", "status": "failed" },
+                    { "description": "Fix bugs", "status": "in_progress" },
+                    { "description": "Review code", "status": "pending" }
+                ]
+            })
+            .to_string(),
+        });
+        let events = fixture.step("Set up my task list").await;
+
+        let update = events
+            .iter()
+            .rev()
+            .find_map(|e| match e {
+                ChatEvent::TaskUpdate(update) => Some(update),
+                _ => None,
+            })
+            .expect("Should have a TaskUpdate");
+        assert_eq!(
+            update.progress,
+            TaskProgress {
+                done: 2,
+                failed: 1,
+                total: 4,
+                percent: 50,
+            }
+        );
+
+        // Progress is serialized alongside the flattened list fields
+        let json = serde_json::to_value(update).unwrap();
+        assert_eq!(json["title"], "Progress");
+        assert_eq!(json["progress"]["percent"], 50);
+    })
+}
+
 #[test]
 fn test_session_state_save_load_roundtrip() {
     let rt = tokio::runtime::Runtime::new().unwrap();
@@ -271,3 +385,83 @@ fn test_empty_task_list_rejected() {
         );
     })
 }
+
+#[test]
+fn test_unfinished_work_only_reports_owned_task_list() {
+    let (event_sender, _rx) = EventSender::new();
+    let module = TaskListModule::new(event_sender);
+
+    assert!(
+        module.unfinished_work().is_empty(),
+        "The default task list is not owned by any agent"
+    );
+
+    module.replace(
+        "Owned".to_string(),
+        vec![
+            TaskWithStatus {
+                description: "Done".to_string(),
+                status: TaskStatus::Completed,
+            },
+            TaskWithStatus {
+                description: "Still to do".to_string(),
+                status: TaskStatus::Pending,
+            },
+        ],
+    );
+
+    let unfinished = module.unfinished_work();
+    assert_eq!(unfinished.len(), 1, "{unfinished:?}");
+    assert!(unfinished[0].contains("Still to do"));
+}
+
+#[test]
+fn test_complete_task_blocked_with_unfinished_tasks() {
+    run(|mut fixture| async move {
+        fixture
+            .update_settings(|settings| {
+                settings.complete_task_guard = CompletionGuard::Block;
+            })
+            .await;
+
+        fixture.set_mock_behavior(MockBehavior::BehaviorQueue {
+            behaviors: vec![
+                MockBehavior::ToolUse {
+                    tool_name: "manage_task_list".to_string(),
+                    tool_arguments: r#"{"title": "Guarded", "tasks": [{"description": "Unfinished step", "status": "pending"}]}"#.to_string(),
+                },
+                MockBehavior::ToolUse {
+                    tool_name: "complete_task".to_string(),
+                    tool_arguments: r#"{"success": true, "result": "done", "allow_incomplete": true}"#.to_string(),
+                },
+                MockBehavior::Success,
+            ],
+        });
+        let events = fixture.step("Do the work").await;
+
+        let completion = events.iter().find_map(|e| match e {
+            ChatEvent::ToolExecutionCompleted {
+                tool_name, success, ..
+            } if tool_name == "complete_task" => Some(*success),
+            _ => None,
+        });
+        assert_eq!(
+            completion,
+            Some(false),
+            "complete_task should be declined while tasks are unfinished"
+        );
+
+        let request = fixture
+            .get_last_ai_request()
+            .expect("Should have AI request");
+        let declined = request
+            .messages
+            .iter()
+            .flat_map(|m| m.content.blocks())
+            .any(|b| matches!(b, ContentBlock::ToolResult(r) if r.content.contains("Unfinished step")));
+        assert!(
+            declined,
+            "The agent should be told which tasks are unfinished"
+        );
+    })
+}
@@ -3,7 +3,7 @@ mod fixture;
 use serde_json::json;
 use std::fs;
 use tycode_core::ai::mock::MockBehavior;
-use tycode_core::chat::events::{ChatEvent, MessageSender};
+use tycode_core::chat::events::{BuildWatchStatus, ChatEvent, MessageSender};
 
 fn setup_rust_project(fixture: &fixture::Fixture) {
     let workspace = fixture.workspace_path();
@@ -437,6 +437,128 @@ fn multiple_searches_in_conversation() {
     });
 }
 
+// =============================================================================
+// get_definition Tool Tests
+// =============================================================================
+
+fn get_definition_result(events: &[ChatEvent]) -> Option<(bool, serde_json::Value)> {
+    events.iter().find_map(|e| match e {
+        ChatEvent::ToolExecutionCompleted {
+            tool_name,
+            success,
+            tool_result,
+            ..
+        } if tool_name == "get_definition" => {
+            Some((*success, serde_json::to_value(tool_result).unwrap()))
+        }
+        _ => None,
+    })
+}
+
+#[test]
+fn get_definition_returns_source_and_line_range() {
+    fixture::run(|mut fixture| async move {
+        setup_rust_project(&fixture);
+        let lib = fixture.workspace_path().join("src/lib.rs");
+        fs::write(
+            &lib,
+            "pub struct Counter {\n    count: u32,\n}\n\nimpl Counter {\n    /// Adds one.\n    pub fn bump(&mut self) -> u32 {\n        self.count += 1;\n        self.count\n    }\n}\n",
+        )
+        .unwrap();
+
+        let args = json!({
+            "language": "rust",
+            "file_path": lib.display().to_string(),
+            "symbol": "Counter::bump"
+        });
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "get_definition".to_string(),
+            tool_arguments: serde_json::to_string(&args).unwrap(),
+        });
+
+        let events = fixture.step("Show Counter::bump").await;
+
+        let (success, result) = get_definition_result(&events).expect("Should run get_definition");
+        assert!(success, "{result}");
+        let result = result.to_string();
+        assert!(result.contains("\"start_line\":6"), "{result}");
+        assert!(result.contains("\"end_line\":10"), "{result}");
+        assert!(result.contains("pub fn bump(&mut self) -> u32"), "{result}");
+    });
+}
+
+#[test]
+fn get_definition_handles_missing_symbol() {
+    fixture::run(|mut fixture| async move {
+        setup_rust_project(&fixture);
+        let lib = fixture.workspace_path().join("src/lib.rs");
+
+        let args = json!({
+            "language": "rust",
+            "file_path": lib.display().to_string(),
+            "symbol": "DoesNotExist"
+        });
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "get_definition".to_string(),
+            tool_arguments: serde_json::to_string(&args).unwrap(),
+        });
+
+        let events = fixture.step("Show DoesNotExist").await;
+
+        let (success, _) = get_definition_result(&events).expect("Should run get_definition");
+        assert!(!success, "Missing symbols should fail");
+    });
+}
+
+// =============================================================================
+// find_references Tool Tests
+// =============================================================================
+
+#[test]
+fn find_references_pages_through_uses() {
+    fixture::run(|mut fixture| async move {
+        setup_rust_project(&fixture);
+        fs::write(
+            fixture.workspace_path().join("src/lib.rs"),
+            "// helper is documented here\npub fn helper() {}\n\npub fn caller() {\n    helper();\n    println!(\"{:?}\", helper());\n}\n",
+        )
+        .unwrap();
+
+        let args = json!({
+            "language": "rust",
+            "workspace_root": workspace_root_arg(&fixture),
+            "symbol": "helper",
+            "limit": 2
+        });
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "find_references".to_string(),
+            tool_arguments: serde_json::to_string(&args).unwrap(),
+        });
+
+        let events = fixture.step("Find uses of helper").await;
+
+        let result = events
+            .iter()
+            .find_map(|e| match e {
+                ChatEvent::ToolExecutionCompleted {
+                    tool_name,
+                    success: true,
+                    tool_result,
+                    ..
+                } if tool_name == "find_references" => {
+                    Some(serde_json::to_value(tool_result).unwrap())
+                }
+                _ => None,
+            })
+            .expect("find_references should succeed");
+        let result = &result["result"];
+        assert_eq!(result["total"], 3, "{result}");
+        assert_eq!(result["next_offset"], 2, "{result}");
+        assert_eq!(result["references"][0]["line"], 2, "{result}");
+        assert_eq!(result["references"][1]["text"], "helper();", "{result}");
+    });
+}
+
 // =============================================================================
 // Registry Configuration Tests
 // =============================================================================
@@ -625,3 +747,128 @@ fn conversation_continues_after_tool_error() {
         );
     });
 }
+
+// =============================================================================
+// Build Watch Tests
+// =============================================================================
+
+fn enable_build_watch(settings: &mut tycode_core::settings::Settings) {
+    use tycode_core::analyzer::build_watch::BuildWatchConfig;
+    let config = BuildWatchConfig {
+        enabled: true,
+        ..Default::default()
+    };
+    settings.set_module_config(BuildWatchConfig::NAMESPACE, config);
+}
+
+fn build_watch_statuses(events: &[ChatEvent]) -> Vec<BuildWatchStatus> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            ChatEvent::BuildWatch { status, .. } => Some(*status),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn build_watch_feeds_build_errors_back_after_edit() {
+    fixture::run(|mut fixture| async move {
+        setup_rust_project(&fixture);
+        fixture.update_settings(enable_build_watch).await;
+
+        let lib = fixture.workspace_path().join("src/lib.rs");
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: json!({
+                "file_path": lib.display().to_string(),
+                "content": "pub fn broken() -> u32 {\n    \"not a number\"\n}\n"
+            })
+            .to_string(),
+        });
+
+        let events = fixture.step("Add a broken function").await;
+
+        assert_eq!(
+            build_watch_statuses(&events),
+            vec![BuildWatchStatus::Retrying]
+        );
+        let request = fixture
+            .get_last_ai_request()
+            .expect("Should have AI request");
+        let feedback = request
+            .messages
+            .iter()
+            .map(|message| message.content.text())
+            .find(|text| text.contains("build watch 1/5"))
+            .expect("Build errors should be fed back to the model");
+        assert!(feedback.contains("src/lib.rs:2:"), "{feedback}");
+    });
+}
+
+#[test]
+fn build_watch_reports_clean_build() {
+    fixture::run(|mut fixture| async move {
+        setup_rust_project(&fixture);
+        fixture.update_settings(enable_build_watch).await;
+
+        let lib = fixture.workspace_path().join("src/lib.rs");
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "write_file".to_string(),
+            tool_arguments: json!({
+                "file_path": lib.display().to_string(),
+                "content": "pub fn answer() -> u32 {\n    42\n}\n"
+            })
+            .to_string(),
+        });
+
+        let events = fixture.step("Add a function").await;
+
+        assert_eq!(build_watch_statuses(&events), vec![BuildWatchStatus::Clean]);
+    });
+}
+
+#[test]
+fn build_watch_runs_when_coder_completes_task() {
+    fixture::run_with_agent("coder", |mut fixture| async move {
+        setup_rust_project(&fixture);
+        fixture.update_settings(enable_build_watch).await;
+
+        let lib = fixture.workspace_path().join("src/lib.rs");
+        let complete = MockBehavior::ToolUse {
+            tool_name: "complete_task".to_string(),
+            tool_arguments: json!({ "success": true, "result": "Added the function" }).to_string(),
+        };
+        fixture.set_mock_behavior(MockBehavior::BehaviorQueue {
+            behaviors: vec![
+                MockBehavior::ToolUse {
+                    tool_name: "write_file".to_string(),
+                    tool_arguments: json!({
+                        "file_path": lib.display().to_string(),
+                        "content": "pub fn broken() -> u32 {\n    \"not a number\"\n}\n"
+                    })
+                    .to_string(),
+                },
+                complete.clone(),
+                complete,
+            ],
+        });
+
+        let events = fixture.step("Add a broken function").await;
+
+        assert_eq!(
+            build_watch_statuses(&events),
+            vec![BuildWatchStatus::Retrying]
+        );
+        let request = fixture
+            .get_last_ai_request()
+            .expect("Should have AI request");
+        let feedback = request
+            .messages
+            .iter()
+            .map(|message| message.content.text())
+            .find(|text| text.contains("build watch 1/5"))
+            .expect("Build errors should be fed back to the model");
+        assert!(feedback.contains("src/lib.rs:2:"), "{feedback}");
+    });
+}
@@ -1,7 +1,9 @@
 mod fixture;
 
 use fixture::{Fixture, Workspace};
-use tycode_core::ai::types::{Content, Message, MessageRole};
+use tycode_core::ai::types::{
+    Content, ContentBlock, Message, MessageRole, ToolResultData, ToolUseData,
+};
 use tycode_core::ai::{mock::MockBehavior, model::Model, TokenUsage};
 use tycode_core::chat::events::{ChatEvent, ChatMessage, MessageSender, ModelInfo};
 
@@ -484,3 +486,142 @@ fn test_ephemeral_session_does_not_persist() {
         );
     }));
 }
+
+#[test]
+fn test_shutdown_saves_session_and_stops_actor() {
+    fixture::run(|mut fixture| async move {
+        fixture.step("Hello test").await;
+
+        let timeout = std::time::Duration::from_secs(5);
+        fixture
+            .actor
+            .shutdown(timeout)
+            .await
+            .expect("Actor should stop within the timeout");
+        fixture
+            .actor
+            .shutdown(timeout)
+            .await
+            .expect("Shutdown should be idempotent");
+
+        let sessions_dir = fixture.sessions_dir();
+        let sessions = storage::list_sessions(Some(&sessions_dir)).unwrap();
+        assert_eq!(sessions.len(), 1, "Shutdown should leave the session saved");
+
+        assert!(
+            fixture
+                .actor
+                .send_message("After shutdown".to_string())
+                .is_err(),
+            "A stopped actor should not accept messages"
+        );
+    });
+}
+
+#[test]
+fn test_new_command_starts_fresh_session() {
+    fixture::run(|mut fixture| async move {
+        fixture.step("First session message").await;
+
+        let events = fixture.step("/new").await;
+        let new_id = events
+            .iter()
+            .find_map(|event| match event {
+                ChatEvent::SessionStarted { session_id } => Some(session_id.clone()),
+                _ => None,
+            })
+            .expect("/new should announce the new session");
+        assert!(
+            events.iter().any(|event| matches!(
+                event,
+                ChatEvent::MessageAdded(msg)
+                    if msg.sender == MessageSender::System && msg.content.contains(&new_id)
+            )),
+            "Confirmation should include the new session id"
+        );
+
+        fixture.step("Second session message").await;
+
+        let request = fixture.get_last_ai_request().expect("AI request expected");
+        let history = format!("{:?}", request.messages);
+        assert!(
+            !history.contains("First session message"),
+            "New session should not carry over the old conversation"
+        );
+
+        let sessions = storage::list_sessions(Some(&fixture.sessions_dir())).unwrap();
+        assert_eq!(sessions.len(), 2, "Both sessions should be saved");
+        assert!(sessions.iter().any(|s| s.id == new_id));
+    });
+}
+
+#[test]
+fn test_resume_repairs_broken_tool_pairs() {
+    fixture::run(|mut fixture| async move {
+        let sessions_dir = fixture.sessions_dir();
+
+        let tool_use = |id: &str| {
+            ContentBlock::ToolUse(ToolUseData {
+                id: id.to_string(),
+                name: "set_tracked_files".to_string(),
+                arguments: serde_json::json!({}),
+            })
+        };
+        let broken_messages = vec![
+            Message {
+                role: MessageRole::Assistant,
+                content: Content::text_only("Leading fragment".to_string()),
+            },
+            Message {
+                role: MessageRole::User,
+                content: Content::text_only("Start".to_string()),
+            },
+            Message {
+                role: MessageRole::Assistant,
+                content: Content::new(vec![
+                    ContentBlock::Text("Reading".to_string()),
+                    tool_use("call_a"),
+                ]),
+            },
+            Message {
+                role: MessageRole::User,
+                content: Content::new(vec![ContentBlock::ToolResult(ToolResultData {
+                    tool_use_id: "call_orphan".to_string(),
+                    content: "stale".to_string(),
+                    is_error: false,
+                })]),
+            },
+            Message {
+                role: MessageRole::Assistant,
+                content: Content::new(vec![tool_use("call_b")]),
+            },
+        ];
+
+        let session = SessionData::new("broken_session".to_string(), broken_messages);
+        storage::save_session(&session, Some(&sessions_dir)).unwrap();
+
+        fixture.step("/sessions resume broken_session").await;
+        let events = fixture.step("Continue").await;
+
+        assert!(
+            events.iter().any(is_assistant_message),
+            "Resumed conversation should be accepted by the provider: {events:?}"
+        );
+        let request = fixture.get_last_ai_request().expect("AI request expected");
+        let messages = &request.messages;
+        assert!(
+            !format!("{messages:?}").contains("Leading fragment"),
+            "Conversation should start at a clean user message"
+        );
+        let results: Vec<String> = messages
+            .iter()
+            .flat_map(|m| m.content.tool_results())
+            .map(|r| r.tool_use_id.clone())
+            .collect();
+        assert_eq!(results, vec!["call_a".to_string()]);
+        assert!(messages
+            .iter()
+            .flat_map(|m| m.content.tool_uses())
+            .all(|u| u.id == "call_a"));
+    });
+}
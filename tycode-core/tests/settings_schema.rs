@@ -121,6 +121,7 @@ fn settings_schema_round_trips_saved_values_without_schema_secrets() {
             "openrouter".to_string(),
             ProviderConfig::OpenRouter {
                 api_key: secret.to_string(),
+                max_concurrent_requests: None,
             },
         );
         settings.active_provider = Some("openrouter".to_string());
@@ -415,3 +415,106 @@ fn test_assistant_message_received_with_steering() {
         );
     });
 }
+
+#[test]
+fn test_edited_custom_document_invalidates_cache() {
+    fixture::run(|mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        let tycode_dir = workspace.join(".tycode");
+        std::fs::create_dir_all(&tycode_dir).unwrap();
+        std::fs::write(tycode_dir.join("cached_rules.md"), "FIRST_VERSION").unwrap();
+
+        let _events = fixture.step("Hello").await;
+        let request = fixture
+            .get_last_ai_request()
+            .expect("Should have captured AI request");
+        assert!(request.system_prompt.contains("FIRST_VERSION"));
+
+        std::fs::write(tycode_dir.join("cached_rules.md"), "SECOND_VERSION_CONTENT").unwrap();
+
+        let _events = fixture.step("Hello again").await;
+        let request = fixture
+            .get_last_ai_request()
+            .expect("Should have captured AI request");
+        assert!(
+            request.system_prompt.contains("SECOND_VERSION_CONTENT"),
+            "System prompt should pick up the edited document"
+        );
+        assert!(
+            !request.system_prompt.contains("FIRST_VERSION"),
+            "System prompt should not contain the stale document"
+        );
+    });
+}
+
+#[test]
+fn test_frontmatter_orders_and_disables_custom_documents() {
+    fixture::run(|mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        let tycode_dir = workspace.join(".tycode");
+        std::fs::create_dir_all(&tycode_dir).unwrap();
+        std::fs::write(tycode_dir.join("a_low.md"), "LOW_PRIORITY_DOC").unwrap();
+        std::fs::write(
+            tycode_dir.join("b_high.md"),
+            "---\ntitle: High Rules\npriority: 10\n---\nHIGH_PRIORITY_DOC",
+        )
+        .unwrap();
+        std::fs::write(
+            tycode_dir.join("c_disabled.md"),
+            "---\nenabled: false\n---\nDISABLED_DOC",
+        )
+        .unwrap();
+
+        reload_agent(&mut fixture).await;
+
+        let _events = fixture.step("Hello").await;
+
+        let request = fixture
+            .get_last_ai_request()
+            .expect("Should have captured AI request");
+        let system_prompt = &request.system_prompt;
+
+        let high = system_prompt
+            .find("# High Rules\n\nHIGH_PRIORITY_DOC")
+            .expect("High priority doc should be titled and included");
+        let low = system_prompt
+            .find("LOW_PRIORITY_DOC")
+            .expect("Low priority doc should be included");
+        assert!(high < low, "Higher priority docs should come first");
+        assert!(
+            !system_prompt.contains("priority: 10"),
+            "Frontmatter should be stripped"
+        );
+        assert!(
+            !system_prompt.contains("DISABLED_DOC"),
+            "Disabled docs should be skipped"
+        );
+    });
+}
+
+#[test]
+fn test_reload_command_reports_loaded_documents() {
+    fixture::run(|mut fixture| async move {
+        let workspace = fixture.workspace_path();
+        let tycode_dir = workspace.join(".tycode");
+        std::fs::create_dir_all(&tycode_dir).unwrap();
+        std::fs::write(tycode_dir.join("new_rules.md"), "NEW_RULES_CONTENT").unwrap();
+
+        let events = fixture.step("/reload").await;
+
+        let summary = events
+            .iter()
+            .find_map(|e| match e {
+                ChatEvent::MessageAdded(msg) if matches!(msg.sender, MessageSender::System) => {
+                    Some(msg.content.clone())
+                }
+                _ => None,
+            })
+            .expect("/reload should report what was loaded");
+        assert!(
+            summary.contains("Steering: 1 custom"),
+            "Unexpected summary: {summary}"
+        );
+        assert!(summary.contains("Skills:"), "Unexpected summary: {summary}");
+    });
+}
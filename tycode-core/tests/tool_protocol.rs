@@ -193,6 +193,18 @@ fn every_advertised_builtin_tool_emits_paired_request_and_completion() {
                     "working_directory": workspace_path.display().to_string()
                 }),
             ),
+            (
+                "format_file",
+                json!({ "file_path": modify_path.display().to_string() }),
+            ),
+            (
+                "read_files",
+                json!({ "paths": [modify_path.display().to_string()] }),
+            ),
+            (
+                "search_files",
+                json!({ "pattern": "before", "path": workspace_path.display().to_string() }),
+            ),
             (
                 "manage_task_list",
                 json!({
@@ -200,6 +212,15 @@ fn every_advertised_builtin_tool_emits_paired_request_and_completion() {
                     "tasks": [{ "description": "cover tools", "status": "in_progress" }]
                 }),
             ),
+            (
+                "manage_tasks",
+                json!({
+                    "operations": [
+                        { "op": "update_status", "task_id": 0, "status": "completed" },
+                        { "op": "add", "description": "cover batched edits", "status": "in_progress" }
+                    ]
+                }),
+            ),
             (
                 "ask_user_question",
                 json!({ "question": "Protocol coverage question?" }),
@@ -212,6 +233,10 @@ fn every_advertised_builtin_tool_emits_paired_request_and_completion() {
                 "append_memory",
                 json!({ "content": "Protocol test memory", "source": "tool_protocol" }),
             ),
+            (
+                "recall_memory",
+                json!({ "query": "protocol", "source": "tool_protocol" }),
+            ),
             (
                 "invoke_skill",
                 json!({ "skill_name": "missing-protocol-test-skill" }),
@@ -232,6 +257,22 @@ fn every_advertised_builtin_tool_emits_paired_request_and_completion() {
                     "type_path": "Protocol"
                 }),
             ),
+            (
+                "find_references",
+                json!({
+                    "language": "rust",
+                    "workspace_root": workspace_path.display().to_string(),
+                    "symbol": "Protocol"
+                }),
+            ),
+            (
+                "get_definition",
+                json!({
+                    "language": "rust",
+                    "file_path": modify_path.display().to_string(),
+                    "symbol": "Protocol"
+                }),
+            ),
             (
                 "generate_image",
                 json!({
@@ -243,6 +284,13 @@ fn every_advertised_builtin_tool_emits_paired_request_and_completion() {
                 "read_image",
                 json!({ "file_path": image_path.display().to_string() }),
             ),
+            (
+                "convert_image",
+                json!({
+                    "input_path": generated_image_path.display().to_string(),
+                    "output_path": workspace_path.join("converted-protocol.jpg").display().to_string()
+                }),
+            ),
         ];
 
         let mut covered_tools = BTreeSet::new();
@@ -347,3 +395,155 @@ fn mcp_tool_emits_paired_request_and_completion() {
         let _ = std::fs::remove_file(script_path);
     });
 }
+
+#[test]
+fn concurrent_reads_keep_call_order_around_writes() {
+    fixture::run(|mut fixture| async move {
+        let workspace_path = fixture.workspace_path();
+        let first = workspace_path.join("first.txt");
+        let second = workspace_path.join("second.txt");
+        std::fs::write(&first, "original-first").unwrap();
+        std::fs::write(&second, "original-second").unwrap();
+
+        let read =
+            |path: &std::path::Path| json!({ "paths": [path.display().to_string()] }).to_string();
+        fixture.set_mock_behavior(MockBehavior::MultipleToolUses {
+            tool_uses: vec![
+                ("read_files".to_string(), read(&first)),
+                ("read_files".to_string(), read(&second)),
+                (
+                    "bash".to_string(),
+                    json!({
+                        "command": "printf updated-first > first.txt",
+                        "timeout_seconds": 5,
+                        "working_directory": workspace_path.display().to_string()
+                    })
+                    .to_string(),
+                ),
+                ("read_files".to_string(), read(&first)),
+            ],
+        });
+
+        let events = fixture.step("Read, write, read").await;
+        assert_tool_request_response_protocol(&events);
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        let results: Vec<(String, String)> = request
+            .messages
+            .iter()
+            .flat_map(|message| message.content.tool_results())
+            .map(|result| (result.tool_use_id.clone(), result.content.clone()))
+            .collect();
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "tool_read_files_0",
+                "tool_read_files_1",
+                "tool_bash_2",
+                "tool_read_files_3"
+            ]
+        );
+        assert!(results[0].1.contains("original-first"), "{results:#?}");
+        assert!(results[1].1.contains("original-second"), "{results:#?}");
+        assert!(results[3].1.contains("updated-first"), "{results:#?}");
+    });
+}
+
+#[test]
+fn tool_calls_past_the_turn_limit_are_rejected_with_paired_errors() {
+    fixture::run(|mut fixture| async move {
+        fixture
+            .update_settings(|settings| settings.max_tool_calls_per_turn = 2)
+            .await;
+        let workspace_path = fixture.workspace_path();
+        let bash = |marker: &str| {
+            (
+                "bash".to_string(),
+                json!({
+                    "command": format!("echo {marker} > {marker}.txt"),
+                    "timeout_seconds": 5,
+                    "working_directory": workspace_path.display().to_string()
+                })
+                .to_string(),
+            )
+        };
+        fixture.set_mock_behavior(MockBehavior::MultipleToolUses {
+            tool_uses: vec![bash("one"), bash("two"), bash("three")],
+        });
+
+        let events = fixture.step("Run three commands").await;
+        assert_tool_request_response_protocol(&events);
+
+        assert!(workspace_path.join("one.txt").exists());
+        assert!(workspace_path.join("two.txt").exists());
+        assert!(!workspace_path.join("three.txt").exists());
+
+        let request = fixture.get_last_ai_request().expect("AI request");
+        let results: Vec<_> = request
+            .messages
+            .iter()
+            .flat_map(|message| message.content.tool_results())
+            .collect();
+        assert_eq!(results.len(), 3, "{results:#?}");
+        let rejected = results
+            .iter()
+            .find(|result| result.tool_use_id == "tool_bash_2")
+            .expect("result for the rejected call");
+        assert!(rejected.is_error);
+        assert!(
+            rejected.content.contains("at most 2"),
+            "{}",
+            rejected.content
+        );
+        assert!(
+            rejected
+                .content
+                .contains("[error category: limit_exceeded]"),
+            "{}",
+            rejected.content
+        );
+    });
+}
+
+#[test]
+fn turn_with_tools_ends_with_tool_summary() {
+    fixture::run(|mut fixture| async move {
+        let events = fixture.step("Hello").await;
+        assert!(
+            !events
+                .iter()
+                .any(|e| matches!(e, ChatEvent::ToolSummary { .. })),
+            "turn without tools should not send a summary"
+        );
+
+        let workspace_path = fixture.workspace_path();
+        fixture.set_mock_behavior(MockBehavior::ToolUseThenSuccess {
+            tool_name: "bash".to_string(),
+            tool_arguments: json!({
+                "command": "echo summary",
+                "timeout_seconds": 5,
+                "working_directory": workspace_path.display().to_string()
+            })
+            .to_string(),
+        });
+
+        let events = fixture.step("Run a command").await;
+        let summaries: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                ChatEvent::ToolSummary {
+                    calls,
+                    failed,
+                    summary,
+                } => Some((*calls, *failed, summary.clone())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![(1, 0, "echo summary: ok".to_string())],
+            "{events:#?}"
+        );
+    });
+}
@@ -0,0 +1,292 @@
+//! WebSocket front end for the chat actor.
+//!
+//! Each connection gets its own `ChatActor` (and therefore its own session).
+//! The wire format matches `tycode-subprocess`: every text frame from the
+//! client is a JSON `ChatActorMessage` or the literal `CANCEL`, and every
+//! `ChatEvent` is sent back as one JSON text frame.
+//!
+//! Actors are `!Send`, so the server must run inside a `LocalSet`.
+//!
+//! A handshake is only accepted when it carries the server's token, either
+//! as a percent-encoded `token` query parameter (browsers cannot set headers
+//! on WebSockets) or as `Authorization: Bearer <token>`. Requests with an
+//! `Origin` header, i.e. from a web page, must also come from an allowed
+//! origin, so a site the user visits cannot open a session on their machine.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::handshake::server::{
+    Callback, ErrorResponse, Request, Response,
+};
+use tokio_tungstenite::tungstenite::http::{header, StatusCode};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use tracing::{info, warn};
+use tycode_core::chat::actor::{ChatActor, ChatActorBuilder};
+use tycode_core::chat::events::ChatEvent;
+use tycode_core::chat::ChatActorMessage;
+use tycode_core::settings::config::McpServerConfig;
+
+/// How long a closed connection waits for its actor to save the session and
+/// close MCP clients.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Default)]
+pub struct ServerConfig {
+    pub workspace_roots: Vec<PathBuf>,
+    pub mcp_servers: HashMap<String, McpServerConfig>,
+    pub ephemeral: bool,
+    pub settings_path: Option<PathBuf>,
+    /// Secret every client must present; generated per launch by default.
+    pub token: String,
+    /// Origins (e.g. `https://example.com`) allowed to connect from a
+    /// browser. Clients that send no Origin header only need the token.
+    pub allowed_origins: Vec<String>,
+}
+
+pub async fn run_server(listener: TcpListener, config: ServerConfig) -> Result<()> {
+    info!("Listening on {}", listener.local_addr()?);
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let config = config.clone();
+        tokio::task::spawn_local(async move {
+            if let Err(e) = handle_connection(stream, peer, config).await {
+                warn!(%peer, "Connection ended with error: {e:?}");
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    peer: SocketAddr,
+    config: ServerConfig,
+) -> Result<()> {
+    let websocket = tokio_tungstenite::accept_hdr_async(stream, Handshake { config: &config })
+        .await
+        .context("WebSocket handshake failed")?;
+    info!(%peer, "Session connected");
+
+    let mut builder = match config.settings_path {
+        Some(settings_path) => {
+            ChatActorBuilder::tycode_with_settings_path(config.workspace_roots, settings_path)?
+        }
+        None => ChatActorBuilder::tycode(config.workspace_roots, None, None)?,
+    };
+    if !config.mcp_servers.is_empty() {
+        builder = builder.with_extra_mcp_servers(config.mcp_servers);
+    }
+    if config.ephemeral {
+        builder = builder.ephemeral();
+    }
+    let (chat_actor, mut event_rx) = builder.build()?;
+
+    let (mut outgoing, mut incoming) = websocket.split();
+
+    let result = pump(
+        &mut outgoing,
+        &mut incoming,
+        &chat_actor,
+        &mut event_rx,
+        peer,
+    )
+    .await;
+    if let Err(e) = chat_actor.shutdown(SHUTDOWN_TIMEOUT).await {
+        warn!(%peer, "{e:?}");
+    }
+    info!(%peer, "Session disconnected");
+    result
+}
+
+async fn pump(
+    outgoing: &mut SplitSink<WebSocketStream<TcpStream>, Message>,
+    incoming: &mut SplitStream<WebSocketStream<TcpStream>>,
+    chat_actor: &ChatActor,
+    event_rx: &mut mpsc::UnboundedReceiver<ChatEvent>,
+    peer: SocketAddr,
+) -> Result<()> {
+    loop {
+        tokio::select! {
+            event = event_rx.recv() => {
+                let Some(event) = event else { break };
+                let json = serde_json::to_string(&event)?;
+                outgoing.send(Message::Text(json)).await?;
+            }
+            frame = incoming.next() => {
+                let text = match frame {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(e.into()),
+                };
+                if text == "CANCEL" {
+                    chat_actor.cancel()?;
+                    continue;
+                }
+                match serde_json::from_str::<ChatActorMessage>(&text) {
+                    Ok(message) => chat_actor.tx.send(message)?,
+                    Err(e) => warn!(%peer, "Ignoring malformed message: {e}"),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Handshake<'a> {
+    config: &'a ServerConfig,
+}
+
+impl Callback for Handshake<'_> {
+    fn on_request(self, request: &Request, response: Response) -> Result<Response, ErrorResponse> {
+        authorize(request, self.config)
+            .map(|()| response)
+            .map_err(|rejection| *rejection)
+    }
+}
+
+/// Rejects handshakes from disallowed origins or without the token.
+fn authorize(request: &Request, config: &ServerConfig) -> Result<(), Box<ErrorResponse>> {
+    if let Some(origin) = request.headers().get(header::ORIGIN) {
+        let allowed = origin
+            .to_str()
+            .is_ok_and(|origin| config.allowed_origins.iter().any(|o| o == origin));
+        if !allowed {
+            warn!(?origin, "Rejecting connection from a disallowed origin");
+            return Err(reject(StatusCode::FORBIDDEN, "Origin not allowed"));
+        }
+    }
+
+    let from_query = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .and_then(decode_query_value)
+    });
+    let from_header = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string);
+    match from_query.or(from_header) {
+        Some(token) if constant_time_eq(token.as_bytes(), config.token.as_bytes()) => Ok(()),
+        _ => {
+            warn!("Rejecting connection without a valid token");
+            Err(reject(StatusCode::UNAUTHORIZED, "Missing or invalid token"))
+        }
+    }
+}
+
+fn reject(status: StatusCode, reason: &str) -> Box<ErrorResponse> {
+    let mut response = ErrorResponse::new(Some(reason.to_string()));
+    *response.status_mut() = status;
+    Box::new(response)
+}
+
+/// Decodes a percent-encoded query value (`+` is a space). None if the
+/// encoding is malformed or the result is not UTF-8.
+fn decode_query_value(value: &str) -> Option<String> {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut bytes = value.bytes();
+    while let Some(byte) = bytes.next() {
+        decoded.push(match byte {
+            b'+' => b' ',
+            b'%' => {
+                let high = char::from(bytes.next()?).to_digit(16)?;
+                let low = char::from(bytes.next()?).to_digit(16)?;
+                (high * 16 + low) as u8
+            }
+            _ => byte,
+        });
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Compares without exiting at the first differing byte, so the token
+/// cannot be guessed one character at a time from response timings.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ServerConfig {
+        ServerConfig {
+            token: "a+b/c=".to_string(),
+            allowed_origins: vec!["https://allowed.example".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn request(uri: &str, origin: Option<&str>) -> Request {
+        let mut request = Request::builder().uri(uri);
+        if let Some(origin) = origin {
+            request = request.header(header::ORIGIN, origin);
+        }
+        request.body(()).unwrap()
+    }
+
+    fn status(request: &Request) -> Option<StatusCode> {
+        authorize(request, &config()).err().map(|e| e.status())
+    }
+
+    #[test]
+    fn accepts_a_percent_encoded_query_token() {
+        assert_eq!(status(&request("/?token=a%2Bb%2Fc%3D", None)), None);
+        assert_eq!(
+            status(&request(
+                "/?v=1&token=a%2bb%2fc%3d",
+                Some("https://allowed.example")
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_a_bad_token() {
+        for uri in [
+            "/?token=wrong",
+            "/?token=a+b/c=",
+            "/?token=a%2Bb%2Fc%3",
+            "/?token=%ZZ",
+        ] {
+            assert_eq!(
+                status(&request(uri, None)),
+                Some(StatusCode::UNAUTHORIZED),
+                "{uri}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_a_missing_token() {
+        assert_eq!(status(&request("/", None)), Some(StatusCode::UNAUTHORIZED));
+        assert_eq!(
+            status(&request("/?other=a%2Bb%2Fc%3D", None)),
+            Some(StatusCode::UNAUTHORIZED)
+        );
+    }
+
+    #[test]
+    fn rejects_a_disallowed_origin() {
+        assert_eq!(
+            status(&request(
+                "/?token=a%2Bb%2Fc%3D",
+                Some("https://evil.example")
+            )),
+            Some(StatusCode::FORBIDDEN)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::{env, path::PathBuf};
+use tokio::net::TcpListener;
+use tokio::task::LocalSet;
+use tracing_subscriber::EnvFilter;
+use tycode_core::settings::config::McpServerConfig;
+use tycode_server::{run_server, ServerConfig};
+
+/// Loopback only by default; exposing the actor on a network interface gives
+/// remote clients the same file and command access as a local user.
+const DEFAULT_BIND: &str = "127.0.0.1:7777";
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = env::args().collect();
+
+    if args
+        .iter()
+        .skip(1)
+        .any(|arg| arg == "--version" || arg == "-V")
+    {
+        println!("tycode-server {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .with_writer(std::io::stderr)
+        .init();
+
+    let mut bind = DEFAULT_BIND.to_string();
+    let mut workspace_roots: Vec<String> = vec![];
+    let mut mcp_servers: HashMap<String, McpServerConfig> = HashMap::new();
+    let mut ephemeral = false;
+    let mut settings_path: Option<PathBuf> = None;
+    let mut token = env::var("TYCODE_SERVER_TOKEN").ok();
+    let mut allowed_origins: Vec<String> = vec![];
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--bind" => {
+                i += 1;
+                if i < args.len() {
+                    bind = args[i].clone();
+                }
+            }
+            "--workspace-roots" => {
+                i += 1;
+                if i < args.len() {
+                    workspace_roots = serde_json::from_str(&args[i])?;
+                }
+            }
+            "--mcp-servers" => {
+                i += 1;
+                if i < args.len() {
+                    mcp_servers = serde_json::from_str(&args[i])?;
+                }
+            }
+            "--ephemeral" => {
+                ephemeral = true;
+            }
+            "--settings-path" => {
+                i += 1;
+                if i < args.len() {
+                    settings_path = Some(PathBuf::from(&args[i]));
+                }
+            }
+            "--token" => {
+                i += 1;
+                if i < args.len() {
+                    token = Some(args[i].clone());
+                }
+            }
+            "--allowed-origin" => {
+                i += 1;
+                if i < args.len() {
+                    allowed_origins.push(args[i].clone());
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let token = token
+        .filter(|token| !token.is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+    let config = ServerConfig {
+        workspace_roots: workspace_roots.into_iter().map(PathBuf::from).collect(),
+        mcp_servers,
+        ephemeral,
+        settings_path,
+        token: token.clone(),
+        allowed_origins,
+    };
+    let listener = TcpListener::bind(&bind).await?;
+    // The launching process reads the connection URL, token included, from stdout
+    println!("ws://{}/?token={token}", listener.local_addr()?);
+
+    let local = LocalSet::new();
+    local.run_until(run_server(listener, config)).await
+}
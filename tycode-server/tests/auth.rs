@@ -0,0 +1,80 @@
+//! Handshake checks: browsers can reach loopback servers, so a connection
+//! must present the per-launch token and come from an allowed origin.
+
+use std::net::SocketAddr;
+
+use tokio::net::TcpListener;
+use tokio::task::LocalSet;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderValue, StatusCode};
+use tokio_tungstenite::tungstenite::Error;
+use tycode_server::{run_server, ServerConfig};
+
+const TOKEN: &str = "test-token";
+
+/// Status of a handshake to `path`, or None if it was accepted.
+async fn handshake(addr: SocketAddr, path: &str, origin: Option<&str>) -> Option<StatusCode> {
+    let mut request = format!("ws://{addr}{path}").into_client_request().unwrap();
+    if let Some(origin) = origin {
+        request
+            .headers_mut()
+            .insert("Origin", HeaderValue::from_str(origin).unwrap());
+    }
+    match tokio_tungstenite::connect_async(request).await {
+        Ok(_) => None,
+        Err(Error::Http(response)) => Some(response.status()),
+        Err(e) => panic!("unexpected handshake error: {e}"),
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn rejects_missing_token_and_foreign_origins() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let config = ServerConfig {
+        settings_path: Some(
+            std::env::temp_dir().join(format!("tycode-server-auth-{}.toml", std::process::id())),
+        ),
+        ephemeral: true,
+        token: TOKEN.to_string(),
+        allowed_origins: vec!["https://allowed.example".to_string()],
+        ..Default::default()
+    };
+
+    LocalSet::new()
+        .run_until(async move {
+            tokio::task::spawn_local(run_server(listener, config));
+
+            assert_eq!(
+                handshake(addr, "/", None).await,
+                Some(StatusCode::UNAUTHORIZED)
+            );
+            assert_eq!(
+                handshake(addr, "/?token=wrong", None).await,
+                Some(StatusCode::UNAUTHORIZED)
+            );
+            assert_eq!(
+                handshake(
+                    addr,
+                    &format!("/?token={TOKEN}"),
+                    Some("https://evil.example")
+                )
+                .await,
+                Some(StatusCode::FORBIDDEN)
+            );
+            assert_eq!(
+                handshake(
+                    addr,
+                    &format!("/?token={TOKEN}"),
+                    Some("https://allowed.example")
+                )
+                .await,
+                None
+            );
+            assert_eq!(
+                handshake(addr, &format!("/?token={TOKEN}"), None).await,
+                None
+            );
+        })
+        .await;
+}
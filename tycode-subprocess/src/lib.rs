@@ -1,6 +1,7 @@
 use anyhow::anyhow;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::task::JoinSet;
 use tokio::{io, io::AsyncWriteExt};
@@ -9,6 +10,10 @@ use tycode_core::chat::actor::ChatActorBuilder;
 use tycode_core::chat::ChatActorMessage;
 use tycode_core::settings::config::McpServerConfig;
 
+/// How long exit waits for the actor to save the session and close MCP
+/// clients.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub async fn run_subprocess(
     workspace_roots: Vec<String>,
     mcp_servers: HashMap<String, McpServerConfig>,
@@ -33,6 +38,7 @@ pub async fn run_subprocess(
         builder = builder.with_custom_agent_spec(spec);
     }
     let (chat_actor, mut event_rx) = builder.build()?;
+    let shutdown_handle = chat_actor.clone();
 
     let mut join_set: JoinSet<anyhow::Result<()>> = JoinSet::new();
 
@@ -59,12 +65,40 @@ pub async fn run_subprocess(
         Ok(())
     });
 
-    if let Some(result) = join_set.join_next().await {
-        return match result {
-            Ok(Ok(_)) => Ok(()),
-            Ok(Err(e)) => Err(anyhow!(e)),
-            Err(panic) => Err(anyhow!(panic)),
-        };
+    join_set.spawn(async move {
+        wait_for_termination().await;
+        Ok(())
+    });
+
+    // Whichever finishes first (stdin closed, a termination signal, or an
+    // I/O error) ends the process; persist state before returning.
+    let result = join_set.join_next().await;
+    if let Err(e) = shutdown_handle.shutdown(SHUTDOWN_TIMEOUT).await {
+        tracing::warn!("{e:?}");
+    }
+
+    match result {
+        Some(Ok(Ok(_))) | None => Ok(()),
+        Some(Ok(Err(e))) => Err(anyhow!(e)),
+        Some(Err(panic)) => Err(anyhow!(panic)),
     }
-    Ok(())
+}
+
+#[cfg(unix)]
+async fn wait_for_termination() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let Ok(mut terminate) = signal(SignalKind::terminate()) else {
+        let _ = tokio::signal::ctrl_c().await;
+        return;
+    };
+    tokio::select! {
+        _ = terminate.recv() => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_termination() {
+    let _ = tokio::signal::ctrl_c().await;
 }
@@ -4,6 +4,8 @@ use tokio::task::LocalSet;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 use tycode_core::agents::custom::CustomAgentSpec;
+use tycode_core::file::workspace::detect_workspace_root;
+use tycode_core::redact::RedactingWriter;
 use tycode_core::settings::config::McpServerConfig;
 use tycode_subprocess::run_subprocess;
 
@@ -24,7 +26,7 @@ async fn main() -> anyhow::Result<()> {
 
     setup_tracing()?;
 
-    let mut workspace_roots: Vec<String> = vec![];
+    let mut workspace_roots: Option<Vec<String>> = None;
     let mut mcp_servers: HashMap<String, McpServerConfig> = HashMap::new();
     let mut ephemeral = false;
     let mut agent: Option<CustomAgentSpec> = None;
@@ -35,7 +37,7 @@ async fn main() -> anyhow::Result<()> {
             "--workspace-roots" => {
                 i += 1;
                 if i < args.len() {
-                    workspace_roots = serde_json::from_str(&args[i])?;
+                    workspace_roots = Some(serde_json::from_str(&args[i])?);
                 }
             }
             "--mcp-servers" => {
@@ -64,6 +66,13 @@ async fn main() -> anyhow::Result<()> {
         i += 1;
     }
 
+    // An explicit (even empty) --workspace-roots is respected as given; only
+    // a missing flag falls back to detecting the enclosing repository.
+    let workspace_roots = workspace_roots.unwrap_or_else(|| {
+        let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        vec![detect_workspace_root(&cwd).to_string_lossy().into_owned()]
+    });
+
     let local = LocalSet::new();
     local
         .run_until(run_subprocess(
@@ -92,11 +101,11 @@ fn setup_tracing() -> anyhow::Result<()> {
         .append(true)
         .open(&log_file)?;
 
-    // Setup tracing subscriber with file output
+    // Setup tracing subscriber with file output, redacting secrets
     tracing_subscriber::registry()
         .with(
             fmt::layer()
-                .with_writer(file)
+                .with_writer(std::sync::Mutex::new(RedactingWriter::new(file)))
                 .with_ansi(false)
                 .with_target(true)
                 .with_thread_ids(true)
@@ -18,7 +18,7 @@ export class Conversation extends EventEmitter {
     private _isManuallyNamed: boolean = false;
     private _hasFirstMessage: boolean = false;
     private _selectedProfile: string | undefined;
-    private _autonomyLevel: 'fully_autonomous' | 'plan_approval_required' = 'plan_approval_required';
+    private _autonomyLevel: 'conservative' | 'balanced' | 'autonomous' = 'balanced';
     private eventConsumer: Promise<void> | null = null;
     private shouldStop: boolean = false;
 
@@ -76,11 +76,11 @@ export class Conversation extends EventEmitter {
         }
     }
 
-    get autonomyLevel(): 'fully_autonomous' | 'plan_approval_required' {
+    get autonomyLevel(): 'conservative' | 'balanced' | 'autonomous' {
         return this._autonomyLevel;
     }
 
-    set autonomyLevel(level: 'fully_autonomous' | 'plan_approval_required') {
+    set autonomyLevel(level: 'conservative' | 'balanced' | 'autonomous') {
         this._autonomyLevel = level;
     }
 
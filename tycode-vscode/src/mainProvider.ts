@@ -185,6 +185,108 @@ export class MainProvider implements vscode.WebviewViewProvider {
                     // Structured orchestration progress for machine consumers;
                     // the VSCode UI renders the human system messages instead
                     return;
+                case 'VoicePartialTranscript':
+                    // Voice input is not available in VSCode
+                    return;
+                case 'ToolSummary':
+                    // The webview already renders each tool call and result
+                    return;
+                case 'BuildWatch':
+                    {
+                        const { iteration, max_iterations, errors, status } = event.data;
+                        if (status === 'retrying' || status === 'clean') {
+                            // The feedback message and the agent's fixes show up in the conversation
+                            return;
+                        }
+                        const reason = status === 'iteration_limit'
+                            ? `after ${iteration}/${max_iterations} fix attempts`
+                            : status === 'cost_limit'
+                                ? 'and the build watch cost limit was reached'
+                                : 'and they keep recurring';
+                        this.sendToWebview({
+                            type: 'conversationMessage',
+                            conversationId: id,
+                            messageType: 'messageAdded',
+                            message: {
+                                timestamp: Date.now(),
+                                sender: 'Warning',
+                                content: `Build still has ${errors} error(s) ${reason}; stopping build watch.`,
+                                tool_calls: []
+                            }
+                        });
+                    }
+                    return;
+                case 'EditsProposed':
+                    {
+                        const lines = event.data.edits.map(edit => `- #${edit.edit_id} ${edit.file_path}`);
+                        this.sendToWebview({
+                            type: 'conversationMessage',
+                            conversationId: id,
+                            messageType: 'messageAdded',
+                            message: {
+                                timestamp: Date.now(),
+                                sender: 'System',
+                                content: `Proposed edits (not yet written):\n${lines.join('\n')}\n\nReply "accept <id>" or "reject <id>" for each, or "accept all" / "reject all".`,
+                                tool_calls: []
+                            }
+                        });
+                    }
+                    return;
+                case 'ImageGenerationProgress':
+                    {
+                        // The tool call and its result are rendered already;
+                        // only the wait in between needs a status line
+                        const { status } = event.data;
+                        if (status.state !== 'queued' && status.state !== 'processing') {
+                            return;
+                        }
+                        this.sendToWebview({
+                            type: 'conversationMessage',
+                            conversationId: id,
+                            messageType: 'messageAdded',
+                            message: {
+                                timestamp: Date.now(),
+                                sender: 'System',
+                                content: status.state === 'queued'
+                                    ? 'Image generation queued...'
+                                    : 'Image generation in progress...',
+                                tool_calls: []
+                            }
+                        });
+                    }
+                    return;
+                case 'RetriesExhausted':
+                    {
+                        const { attempts, error } = event.data;
+                        this.sendToWebview({
+                            type: 'conversationMessage',
+                            conversationId: id,
+                            messageType: 'messageAdded',
+                            message: {
+                                timestamp: Date.now(),
+                                sender: 'Error',
+                                content: `Gave up after ${attempts} retries; the provider kept failing: ${error}`,
+                                tool_calls: []
+                            }
+                        });
+                    }
+                    return;
+                case 'ContextWarning':
+                    {
+                        const { used_tokens, limit_tokens } = event.data;
+                        this.sendToWebview({
+                            type: 'conversationMessage',
+                            conversationId: id,
+                            messageType: 'messageAdded',
+                            message: {
+                                timestamp: Date.now(),
+                                sender: 'Warning',
+                                content: `Context is ~${used_tokens} of ${limit_tokens} tokens. Consider /compact.`,
+                                tool_calls: []
+                            }
+                        });
+                    }
+                    return;
                 case 'RootAgentChanged':
                     {
                         this.sendToWebview({
@@ -500,7 +602,7 @@ export class MainProvider implements vscode.WebviewViewProvider {
 
             // Fetch new settings from the backend to get the autonomy level from the new profile
             const settings = await conversation.client.getSettings();
-            const autonomyLevel = settings.autonomy_level || 'plan_approval_required';
+            const autonomyLevel = settings.autonomy_level || 'balanced';
             conversation.autonomyLevel = autonomyLevel;
 
             this.sendToWebview({
@@ -548,7 +650,7 @@ export class MainProvider implements vscode.WebviewViewProvider {
         console.log(`[MainProvider] Root agent set to ${agent} for conversation ${conversationId}`);
     }
 
-    private async handleSetAutonomyLevel(conversationId: string, autonomyLevel: 'fully_autonomous' | 'plan_approval_required'): Promise<void> {
+    private async handleSetAutonomyLevel(conversationId: string, autonomyLevel: 'conservative' | 'balanced' | 'autonomous'): Promise<void> {
         const conversation = this.conversationManager.getConversation(conversationId);
         if (!conversation) {
             return;
@@ -603,7 +705,7 @@ export class MainProvider implements vscode.WebviewViewProvider {
 
         try {
             const settings = await conversation.client.getSettings();
-            const autonomyLevel = settings.autonomy_level || 'plan_approval_required';
+            const autonomyLevel = settings.autonomy_level || 'balanced';
             const defaultAgent = settings.default_agent;
             const profile = settings.profile || conversation.selectedProfile || this.cachedActiveProfile;
             const reasoningEffort = settings.reasoning_effort || 'High';
@@ -232,7 +232,7 @@ export class SettingsProvider {
                             <option value="auto">Auto</option>
                             <option value="all">All</option>
                         </select>
-                        <div class="help-text">Controls which tools the AI can use: Read Only (read files only), Auto (read + write, requires approval for dangerous operations), All (unrestricted access)</div>
+                        <div class="help-text">Controls which tools the AI can use: Read Only (read files only), Auto (read, write and run commands; no network tools such as MCP servers, image generation or semantic search), All (unrestricted access)</div>
                     </div>
                     
                     <div class="form-group">
@@ -289,10 +289,11 @@ export class SettingsProvider {
                     <div class="form-group">
                         <label for="autonomyLevel">Autonomy Level</label>
                         <select id="autonomyLevel">
-                            <option value="plan_approval_required">Plan Approval Required</option>
-                            <option value="fully_autonomous">Fully Autonomous</option>
+                            <option value="conservative">Conservative</option>
+                            <option value="balanced">Balanced</option>
+                            <option value="autonomous">Autonomous</option>
                         </select>
-                        <div class="help-text">Plan Approval Required (agent presents a plan and waits for approval before implementing), Fully Autonomous (agent proceeds directly with implementation)</div>
+                        <div class="help-text">Conservative (plan approval, and every file edit or git change waits for confirmation), Balanced (agent presents a plan and waits for approval; edits are confirmed only if Confirm Edits is on), Autonomous (agent proceeds directly without pausing for approval or confirmation)</div>
                     </div>
                 </div>
             </div>
@@ -365,6 +366,15 @@ export class SettingsProvider {
                         </select>
                         <div class="help-text">When enabled, AI responses arrive as a single complete message instead of streaming incrementally. Useful for environments where streaming causes issues.</div>
                     </div>
+                    
+                    <div class="form-group">
+                        <label for="responseCache">Response Cache</label>
+                        <select id="responseCache">
+                            <option value="false">Disabled</option>
+                            <option value="true">Enabled</option>
+                        </select>
+                        <div class="help-text">When enabled, an exact repeat of an earlier request returns the stored response instead of calling the provider again. Chat turns and tool calls are only cached when enabled in settings.toml.</div>
+                    </div>
                 </div>
             </div>
         </div>
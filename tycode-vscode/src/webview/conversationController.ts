@@ -23,7 +23,8 @@ import {
     StreamDeltaMessage,
     StreamReasoningDeltaMessage,
     StreamEndMessage,
-    AddImageDataMessage
+    AddImageDataMessage,
+    AutonomyLevel
 } from './types.js';
 import {
     addCodeActions,
@@ -38,6 +39,10 @@ type ToolContext = {
     command?: string;
 };
 
+/** Autonomy slider positions 1..3, least to most autonomous. */
+const AUTONOMY_LEVELS: readonly AutonomyLevel[] = ['conservative', 'balanced', 'autonomous'];
+const AUTONOMY_LABELS = ['Conservative', 'Balanced', 'Autonomous'];
+
 export interface ConversationController {
     handleInitialState(message: InitialStateMessage): void;
     handleConversationCreated(message: ConversationCreatedMessage): void;
@@ -567,9 +572,9 @@ export function createConversationController(context: WebviewContext): Conversat
         const autonomyValue = conversation.viewElement.querySelector<HTMLSpanElement>('.settings-slider-value');
 
         if (autonomySlider && autonomyValue) {
-            const value = message.autonomyLevel === 'fully_autonomous' ? 2 : 1;
-            autonomySlider.value = String(value);
-            autonomyValue.textContent = value === 2 ? 'Fully Autonomous' : 'Plan Approval';
+            const index = Math.max(AUTONOMY_LEVELS.indexOf(message.autonomyLevel), 0);
+            autonomySlider.value = String(index + 1);
+            autonomyValue.textContent = AUTONOMY_LABELS[index];
         }
 
         if (message.defaultAgent) {
@@ -942,11 +947,11 @@ export function createConversationController(context: WebviewContext): Conversat
                 <div class="settings-content">
                     <div class="settings-grid">
                         <div class="settings-item">
-                            <label class="settings-label" data-tooltip="Controls whether the agent asks for approval before executing plans or runs fully autonomous">Autonomy</label>
+                            <label class="settings-label" data-tooltip="How much the agent checks in. Conservative: plan approval and every edit is confirmed. Balanced: plan approval before implementing. Autonomous: proceeds without pausing.">Autonomy</label>
                             <div class="settings-control">
                                 <div class="settings-slider-container">
-                                    <input type="range" class="settings-slider autonomy-slider" min="1" max="2" value="1">
-                                    <span class="settings-slider-value">Plan Approval</span>
+                                    <input type="range" class="settings-slider autonomy-slider" min="1" max="3" value="2">
+                                    <span class="settings-slider-value">Balanced</span>
                                 </div>
                             </div>
                         </div>
@@ -1081,8 +1086,9 @@ export function createConversationController(context: WebviewContext): Conversat
                 const rSlider = conversationView.querySelector<HTMLInputElement>('.reasoning-slider');
                 const oSlider = conversationView.querySelector<HTMLInputElement>('.orchestration-slider');
 
-                const autonomyLevel = aSlider && parseInt(aSlider.value, 10) === 2
-                    ? 'fully_autonomous' as const : 'plan_approval_required' as const;
+                const autonomyLevel = aSlider
+                    ? AUTONOMY_LEVELS[parseInt(aSlider.value, 10) - 1] || 'balanced'
+                    : 'balanced';
 
                 const reasoningLabels = ['Off', 'Low', 'Medium', 'High', 'Max'] as const;
                 const reasoningEffort = rSlider
@@ -1131,14 +1137,13 @@ export function createConversationController(context: WebviewContext): Conversat
         }
 
         if (autonomySlider && autonomyValue) {
-            const autonomyLabels = ['Plan Approval', 'Fully Autonomous'];
             autonomySlider.addEventListener('input', () => {
                 const value = parseInt(autonomySlider.value, 10);
-                autonomyValue.textContent = autonomyLabels[value - 1] || 'Plan Approval';
+                autonomyValue.textContent = AUTONOMY_LABELS[value - 1] || 'Balanced';
                 context.vscode.postMessage({
                     type: 'setAutonomyLevel',
                     conversationId: id,
-                    autonomyLevel: value === 2 ? 'fully_autonomous' : 'plan_approval_required'
+                    autonomyLevel: AUTONOMY_LEVELS[value - 1] || 'balanced'
                 });
             });
         }
@@ -2,7 +2,7 @@ const vscode = acquireVsCodeApi();
 let settings = {
     active_provider: 'default',
     providers: {},
-    security: { mode: 'auto' },
+    security: { mode: 'all' },
     model_quality: null,
     review_level: 'None',
     default_agent: '',
@@ -12,7 +12,7 @@ let settings = {
     spawn_context_mode: 'Fork',
     disable_custom_steering: false,
     communication_tone: 'concise_and_logical',
-    autonomy_level: 'plan_approval_required',
+    autonomy_level: 'balanced',
     modules: {}
 };
 let moduleSchemas = [];
@@ -64,6 +64,7 @@ document.addEventListener('DOMContentLoaded', function() {
     document.getElementById('spawnContextMode').addEventListener('change', updateGeneralSettings);
     document.getElementById('disableCustomSteering').addEventListener('change', updateGeneralSettings);
     document.getElementById('disableStreaming').addEventListener('change', updateGeneralSettings);
+    document.getElementById('responseCache').addEventListener('change', updateGeneralSettings);
     
     document.getElementById('currentProfile').addEventListener('change', switchProfile);
     document.getElementById('saveProfileBtn').addEventListener('click', saveProfile);
@@ -147,9 +148,9 @@ function renderAll() {
 
 function renderGeneralSettings() {
     document.getElementById('communicationTone').value = settings.communication_tone || 'concise_and_logical';
-    document.getElementById('autonomyLevel').value = settings.autonomy_level || 'plan_approval_required';
+    document.getElementById('autonomyLevel').value = settings.autonomy_level || 'balanced';
     
-    const securityMode = settings.security && settings.security.mode ? settings.security.mode : 'auto';
+    const securityMode = settings.security && settings.security.mode ? settings.security.mode : 'all';
     document.getElementById('securityMode').value = securityMode;
     
     const modelQuality = settings.model_quality ? settings.model_quality : '';
@@ -161,6 +162,7 @@ function renderGeneralSettings() {
     document.getElementById('spawnContextMode').value = settings.spawn_context_mode || 'Fork';
     document.getElementById('disableCustomSteering').value = settings.disable_custom_steering ? 'true' : 'false';
     document.getElementById('disableStreaming').value = settings.disable_streaming ? 'true' : 'false';
+    document.getElementById('responseCache').value = settings.response_cache && settings.response_cache.enabled ? 'true' : 'false';
     document.getElementById('reasoningEffort').value = settings.reasoning_effort || '';
 }
 
@@ -184,6 +186,10 @@ function updateGeneralSettings() {
     settings.spawn_context_mode = document.getElementById('spawnContextMode').value;
     settings.disable_custom_steering = document.getElementById('disableCustomSteering').value === 'true';
     settings.disable_streaming = document.getElementById('disableStreaming').value === 'true';
+    if (!settings.response_cache) {
+        settings.response_cache = {};
+    }
+    settings.response_cache.enabled = document.getElementById('responseCache').value === 'true';
     saveSettings();
 }
 
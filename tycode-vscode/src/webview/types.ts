@@ -220,7 +220,7 @@ export type WebviewMessageInbound =
     | StreamEndMessage
     | AddImageDataMessage;
 
-export type AutonomyLevel = 'fully_autonomous' | 'plan_approval_required';
+export type AutonomyLevel = 'conservative' | 'balanced' | 'autonomous';
 export type OrchestrationModeOption = 'none' | 'auto' | 'builder' | 'swarm';
 export type ReasoningEffort = 'Off' | 'Low' | 'Medium' | 'High' | 'Max';
 